@@ -7,7 +7,7 @@ use std::cmp::Ordering;
 use wasm_bindgen::prelude::*;
 
 #[inline]
-fn cmp_nan_last(a: f64, b: f64) -> Ordering {
+pub(crate) fn cmp_nan_last(a: f64, b: f64) -> Ordering {
     let an = a.is_nan();
     let bn = b.is_nan();
     match (an, bn) {
@@ -0,0 +1,388 @@
+//! One-pass summary statistics (`describe`) WASM exports
+//!
+//! Summarises many columns at once, optionally per group. Each group is
+//! scanned once for counts, extremes and central moments while its present
+//! values are gathered; quartiles then come from one selection pass over
+//! that buffer (no full sort).
+//!
+//! Conventions shared by every kernel in this module:
+//! - Columns are column-major: column `c` owns `vals[c * n_rows..(c + 1) * n_rows]`.
+//! - `valid`: 1 = value present, 0 = missing, same length as the values.
+//!   An empty mask means "NaN is missing" (or `na_code` for code columns).
+//! - `gid_per_row`: group id per row (from `group_ids_codes_all`). Empty = one group.
+//! - Output is row-major by (column, group, statistic): the statistics of column
+//!   `c` in group `g` start at `(c * n_groups + g) * n_stats`.
+
+use super::fill::group_runs;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Statistics returned per numeric column and group, in output order.
+///
+/// `sd` is the sample standard deviation (n - 1); quartiles use R's type 7;
+/// `skewness` is the moment coefficient g1 = m3 / m2^1.5 and `kurtosis` the
+/// excess kurtosis g2 = m4 / m2^2 - 3. Statistics that are undefined for the
+/// group (no values, a single value, zero variance) are NaN.
+pub const DESCRIBE_NUMERIC_STATS: [&str; 11] = [
+    "count", "missing", "mean", "sd", "min", "q25", "median", "q75", "max", "skewness", "kurtosis",
+];
+
+/// Statistics returned per code column and group, in output order.
+///
+/// `mode` is the most frequent code (the smallest code on ties, i.e. the
+/// first-seen dictionary entry) or `na_code` when the group has no values.
+pub const DESCRIBE_CODE_STATS: [&str; 5] = ["count", "missing", "n_distinct", "mode", "mode_count"];
+
+fn check_shape(len: usize, valid: &[u8], n_cols: usize) -> Result<usize, String> {
+    if n_cols == 0 {
+        return if len == 0 {
+            Ok(0)
+        } else {
+            Err("n_cols must be positive for non-empty data".to_string())
+        };
+    }
+    if !len.is_multiple_of(n_cols) {
+        return Err(format!(
+            "data length {} is not a multiple of {} columns",
+            len, n_cols
+        ));
+    }
+    if !valid.is_empty() && valid.len() != len {
+        return Err(format!("valid length mismatch: {} vs {}", valid.len(), len));
+    }
+    Ok(len / n_cols)
+}
+
+/// Running count, mean and central moments (Terriberry's update)
+#[derive(Default)]
+struct Moments {
+    n: f64,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+}
+
+impl Moments {
+    fn push(&mut self, x: f64) {
+        let n1 = self.n;
+        self.n += 1.0;
+        let n = self.n;
+        let delta = x - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term1 = delta * delta_n * n1;
+        self.mean += delta_n;
+        self.m4 += term1 * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2
+            - 4.0 * delta_n * self.m3;
+        self.m3 += term1 * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term1;
+    }
+
+    fn sd(&self) -> f64 {
+        if self.n < 2.0 {
+            f64::NAN
+        } else {
+            (self.m2 / (self.n - 1.0)).sqrt()
+        }
+    }
+
+    fn skewness(&self) -> f64 {
+        if self.n < 2.0 || self.m2 <= 0.0 {
+            f64::NAN
+        } else {
+            self.n.sqrt() * self.m3 / self.m2.powf(1.5)
+        }
+    }
+
+    fn kurtosis(&self) -> f64 {
+        if self.n < 2.0 || self.m2 <= 0.0 {
+            f64::NAN
+        } else {
+            self.n * self.m4 / (self.m2 * self.m2) - 3.0
+        }
+    }
+}
+
+/// Type 7 quantile of `buf` by selection; reorders `buf`
+fn select_quantile(buf: &mut [f64], p: f64) -> f64 {
+    let h = (buf.len() - 1) as f64 * p;
+    let lo = h.floor() as usize;
+    let (_, &mut v_lo, above) = buf.select_nth_unstable_by(lo, f64::total_cmp);
+    let gamma = h - lo as f64;
+    if gamma == 0.0 || above.is_empty() {
+        return v_lo;
+    }
+    let v_hi = above.iter().copied().fold(f64::INFINITY, f64::min);
+    v_lo + gamma * (v_hi - v_lo)
+}
+
+/// Summarise numeric columns per group.
+///
+/// Returns `n_cols * n_groups * DESCRIBE_NUMERIC_STATS.len()` values laid out
+/// as described in the module docs. Infinite values are kept (and propagate to
+/// the moments); only missing entries are skipped.
+pub fn describe_f64(
+    vals: &[f64],
+    valid: &[u8],
+    n_cols: usize,
+    gid_per_row: &[u32],
+    n_groups: usize,
+) -> Result<Vec<f64>, String> {
+    let n_rows = check_shape(vals.len(), valid, n_cols)?;
+    let n_groups = if gid_per_row.is_empty() { 1 } else { n_groups };
+    let (offsets, rows) = group_runs(n_rows, gid_per_row, n_groups, &[])?;
+
+    let n_stats = DESCRIBE_NUMERIC_STATS.len();
+    let mut out = Vec::with_capacity(n_cols * n_groups * n_stats);
+    let mut buf: Vec<f64> = Vec::new();
+    for c in 0..n_cols {
+        let base = c * n_rows;
+        for g in 0..n_groups {
+            let run = &rows[offsets[g]..offsets[g + 1]];
+            let mut moments = Moments::default();
+            let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+            buf.clear();
+            for &r in run {
+                let i = base + r as usize;
+                let x = vals[i];
+                let present = if valid.is_empty() {
+                    !x.is_nan()
+                } else {
+                    valid[i] != 0 && !x.is_nan()
+                };
+                if present {
+                    moments.push(x);
+                    min = min.min(x);
+                    max = max.max(x);
+                    buf.push(x);
+                }
+            }
+
+            let count = buf.len();
+            out.push(count as f64);
+            out.push((run.len() - count) as f64);
+            if count == 0 {
+                out.extend(std::iter::repeat_n(f64::NAN, n_stats - 2));
+                continue;
+            }
+            out.push(moments.mean);
+            out.push(moments.sd());
+            out.push(min);
+            out.push(select_quantile(&mut buf, 0.25));
+            out.push(select_quantile(&mut buf, 0.5));
+            out.push(select_quantile(&mut buf, 0.75));
+            out.push(max);
+            out.push(moments.skewness());
+            out.push(moments.kurtosis());
+        }
+    }
+    Ok(out)
+}
+
+/// Summarise dictionary-code columns per group.
+///
+/// Returns `n_cols * n_groups * DESCRIBE_CODE_STATS.len()` values laid out as
+/// described in the module docs. Present codes must be below `n_levels` (the
+/// dictionary length); entries equal to `na_code` are missing even when the
+/// mask says present.
+pub fn describe_u32(
+    codes: &[u32],
+    valid: &[u8],
+    n_cols: usize,
+    na_code: u32,
+    n_levels: usize,
+    gid_per_row: &[u32],
+    n_groups: usize,
+) -> Result<Vec<u32>, String> {
+    let n_rows = check_shape(codes.len(), valid, n_cols)?;
+    let n_groups = if gid_per_row.is_empty() { 1 } else { n_groups };
+    let (offsets, rows) = group_runs(n_rows, gid_per_row, n_groups, &[])?;
+
+    let n_stats = DESCRIBE_CODE_STATS.len();
+    let mut out = Vec::with_capacity(n_cols * n_groups * n_stats);
+    let mut counts = vec![0u32; n_levels];
+    let mut touched: Vec<u32> = Vec::new();
+    for c in 0..n_cols {
+        let base = c * n_rows;
+        for g in 0..n_groups {
+            let run = &rows[offsets[g]..offsets[g + 1]];
+            let mut count = 0u32;
+            for &r in run {
+                let i = base + r as usize;
+                let code = codes[i];
+                if code == na_code || (!valid.is_empty() && valid[i] == 0) {
+                    continue;
+                }
+                let k = code as usize;
+                if k >= n_levels {
+                    return Err(format!(
+                        "code {} out of range for {} levels",
+                        code, n_levels
+                    ));
+                }
+                if counts[k] == 0 {
+                    touched.push(code);
+                }
+                counts[k] += 1;
+                count += 1;
+            }
+
+            let (mut mode, mut mode_count) = (na_code, 0u32);
+            for &code in &touched {
+                let n = counts[code as usize];
+                if n > mode_count || (n == mode_count && code < mode) {
+                    mode = code;
+                    mode_count = n;
+                }
+                counts[code as usize] = 0;
+            }
+            out.extend([
+                count,
+                run.len() as u32 - count,
+                touched.len() as u32,
+                mode,
+                mode_count,
+            ]);
+            touched.clear();
+        }
+    }
+    Ok(out)
+}
+
+/// WASM export for grouped numeric summaries (see [`DESCRIBE_NUMERIC_STATS`])
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn describe_f64_wasm(
+    vals: &[f64],
+    valid: &[u8],
+    n_cols: u32,
+    gid_per_row: &[u32],
+    n_groups: u32,
+) -> Result<Vec<f64>, JsValue> {
+    describe_f64(vals, valid, n_cols as usize, gid_per_row, n_groups as usize)
+        .map_err(|e| JsValue::from_str(&format!("Describe error: {}", e)))
+}
+
+/// WASM export for grouped code-column summaries (see [`DESCRIBE_CODE_STATS`])
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn describe_u32_wasm(
+    codes: &[u32],
+    valid: &[u8],
+    n_cols: u32,
+    na_code: u32,
+    n_levels: u32,
+    gid_per_row: &[u32],
+    n_groups: u32,
+) -> Result<Vec<u32>, JsValue> {
+    describe_u32(
+        codes,
+        valid,
+        n_cols as usize,
+        na_code,
+        n_levels as usize,
+        gid_per_row,
+        n_groups as usize,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Describe error: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_f64_groups_and_missing() {
+        // Two columns, two groups; column 0 has a NaN, column 1 a masked row.
+        // Expected values are R's mean, sd and quantile(type = 7) with
+        // e1071's type 1 skewness and kurtosis, from a two-pass reference
+        let nan = f64::NAN;
+        let col0 = [
+            2.5, 10.0, 3.0, nan, 7.0, 12.0, 1.5, 11.0, 9.0, 40.0, 4.0, 2.0,
+        ];
+        let col1: Vec<f64> = (1..=12).map(f64::from).collect();
+        let vals = [&col0[..], &col1].concat();
+        let mut valid = vec![1u8; 24];
+        valid[12 + 4] = 0;
+        let gid = [0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 0];
+        let out = describe_f64(&vals, &valid, 2, &gid, 2).unwrap();
+        // One statistic across (column 0, group 0), (0, 1), (1, 0) and (1, 1)
+        let n_stats = DESCRIBE_NUMERIC_STATS.len();
+        let stat = |name| {
+            let k = DESCRIBE_NUMERIC_STATS
+                .iter()
+                .position(|s| *s == name)
+                .unwrap();
+            out.iter()
+                .skip(k)
+                .step_by(n_stats)
+                .copied()
+                .collect::<Vec<_>>()
+        };
+        let check = |name, expected: [f64; 4]| {
+            for (x, e) in stat(name).iter().zip(expected) {
+                assert!((x - e).abs() < 1e-12, "{}: {} vs {}", name, x, e);
+            }
+        };
+        assert_eq!(out.len(), 4 * n_stats);
+        check("count", [7.0, 4.0, 6.0, 5.0]);
+        check("missing", [0.0, 1.0, 1.0, 0.0]);
+        check("mean", [4.142857142857143, 18.25, 7.166666666666667, 6.0]);
+        check(
+            "sd",
+            [
+                2.809422919899591,
+                14.522970311429637,
+                4.400757510550504,
+                3.1622776601683795,
+            ],
+        );
+        check("min", [1.5, 10.0, 1.0, 2.0]);
+        check("q25", [2.25, 10.75, 4.0, 4.0]);
+        check("median", [3.0, 11.5, 8.0, 6.0]);
+        check("q75", [5.5, 19.0, 10.5, 8.0]);
+        check("max", [9.0, 40.0, 12.0, 10.0]);
+        check(
+            "skewness",
+            [
+                0.8417461847161762,
+                1.1437641726128491,
+                -0.33789404459279254,
+                0.0,
+            ],
+        );
+        check(
+            "kurtosis",
+            [
+                -0.8086546139513944,
+                -0.6750721710606729,
+                -1.387180391099683,
+                -1.3,
+            ],
+        );
+
+        // A single value has no spread; an all-missing group is NaN past the counts
+        let out = describe_f64(&[5.0, nan], &[], 1, &[0, 1], 2).unwrap();
+        assert_eq!(&out[..3], &[1.0, 0.0, 5.0]);
+        assert!(out[3].is_nan() && out[9].is_nan() && out[10].is_nan());
+        assert_eq!(&out[11..13], &[0.0, 1.0]);
+        assert!(out[13..].iter().all(|x| x.is_nan()));
+        assert!(describe_f64(&vals, &[], 5, &[], 1).is_err());
+    }
+
+    #[test]
+    fn test_describe_u32_codes() {
+        // Two groups over a three-level dictionary; 3 is the NA code
+        let codes = [0, 2, 2, 3, 1, 1, 0, 2];
+        let gid = [0, 0, 0, 0, 1, 1, 1, 1];
+        let out = describe_u32(&codes, &[], 1, 3, 3, &gid, 2).unwrap();
+        assert_eq!(out, vec![3, 1, 2, 2, 2, 4, 0, 3, 1, 2]);
+
+        // Masked-out rows are not range-checked; present ones are
+        let out = describe_u32(&[7, 0], &[0, 1], 1, u32::MAX, 1, &[], 0).unwrap();
+        assert_eq!(out, vec![1, 1, 1, 0, 1]);
+        assert!(describe_u32(&[0, 7], &[], 1, u32::MAX, 3, &[], 0).is_err());
+        assert!(describe_u32(&[u32::MAX - 1], &[], 1, u32::MAX, 3, &[], 0).is_err());
+    }
+}
@@ -0,0 +1,648 @@
+//! Missing-data fill and interpolation WASM exports
+//!
+//! Forward fill, backward fill and linear/spline interpolation over a sort
+//! order, respecting `gid_per_row` group boundaries and validity masks so a
+//! grouped panel can be cleaned up in a single call.
+//!
+//! Conventions shared by every kernel in this module:
+//! - `valid`: 1 = value present, 0 = missing. An empty mask means "NaN is missing".
+//! - `gid_per_row`: group id per row (from `group_ids_codes_all`). Empty = one group.
+//! - `order`: row indices giving the processing order (e.g. from `arrange`).
+//!   Empty = natural row order. Rows are only filled from rows of the same group.
+
+#![deny(unsafe_op_in_unsafe_fn)]
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Sentinel returned by [`fill_source_rows`] for rows that stay missing.
+pub const NO_SOURCE: u32 = u32::MAX;
+
+/// Fill direction
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FillDirection {
+    Forward,
+    Backward,
+}
+
+/// Interpolation method
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InterpolationMethod {
+    Linear,
+    /// Natural cubic spline; falls back to linear with fewer than 4 known points
+    Spline,
+}
+
+#[inline]
+fn is_present(vals: &[f64], valid: &[u8], row: usize) -> bool {
+    if valid.is_empty() {
+        !vals[row].is_nan()
+    } else {
+        valid[row] != 0
+    }
+}
+
+/// Validate shapes and bucket rows by group, keeping `order` within each group.
+/// Returns (offsets, rows) where group g owns rows[offsets[g]..offsets[g + 1]].
+pub(crate) fn group_runs(
+    n_rows: usize,
+    gid_per_row: &[u32],
+    n_groups: usize,
+    order: &[u32],
+) -> Result<(Vec<usize>, Vec<u32>), String> {
+    if !gid_per_row.is_empty() && gid_per_row.len() != n_rows {
+        return Err(format!(
+            "gid_per_row length mismatch: {} vs {}",
+            gid_per_row.len(),
+            n_rows
+        ));
+    }
+    if !order.is_empty() && order.len() != n_rows {
+        return Err(format!(
+            "order length mismatch: {} vs {}",
+            order.len(),
+            n_rows
+        ));
+    }
+    if let Some(&r) = order.iter().find(|&&r| r as usize >= n_rows) {
+        return Err(format!("order index {} out of bounds", r));
+    }
+
+    let row_at = |i: usize| if order.is_empty() { i as u32 } else { order[i] };
+
+    if gid_per_row.is_empty() {
+        return Ok((vec![0, n_rows], (0..n_rows).map(row_at).collect()));
+    }
+    if let Some(&g) = gid_per_row.iter().find(|&&g| g as usize >= n_groups) {
+        return Err(format!("group id {} out of range for {} groups", g, n_groups));
+    }
+
+    // Counting sort on group id (stable w.r.t. `order`)
+    let mut offsets = vec![0usize; n_groups + 1];
+    for &g in gid_per_row {
+        offsets[g as usize + 1] += 1;
+    }
+    for g in 0..n_groups {
+        offsets[g + 1] += offsets[g];
+    }
+    let mut cursor = offsets.clone();
+    let mut rows = vec![0u32; n_rows];
+    for i in 0..n_rows {
+        let r = row_at(i);
+        let g = gid_per_row[r as usize] as usize;
+        rows[cursor[g]] = r;
+        cursor[g] += 1;
+    }
+    Ok((offsets, rows))
+}
+
+fn fill_run(
+    run: &[u32],
+    present: &impl Fn(usize) -> bool,
+    limit: usize,
+    src: &mut [u32],
+    rows: impl Iterator<Item = usize>,
+) {
+    let mut last: u32 = NO_SOURCE;
+    let mut gap = 0usize;
+    for k in rows {
+        let r = run[k] as usize;
+        if present(r) {
+            last = r as u32;
+            gap = 0;
+            src[r] = r as u32;
+        } else {
+            gap += 1;
+            if last != NO_SOURCE && (limit == 0 || gap <= limit) {
+                src[r] = last;
+            }
+        }
+    }
+}
+
+/// Compute, for every row, the row whose value fills it.
+///
+/// Present rows map to themselves; missing rows map to the nearest present row
+/// before (forward) or after (backward) them in `order` within the same group,
+/// or [`NO_SOURCE`] if there is none. `limit` caps the number of consecutive
+/// missing rows filled (0 = unlimited). The result is type-agnostic and can be
+/// used to gather any column type.
+pub fn fill_source_rows(
+    vals: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: usize,
+    order: &[u32],
+    direction: FillDirection,
+    limit: usize,
+) -> Result<Vec<u32>, String> {
+    let n_rows = if valid.is_empty() { vals.len() } else { valid.len() };
+    let (offsets, rows) = group_runs(n_rows, gid_per_row, n_groups, order)?;
+    let present = |r: usize| is_present(vals, valid, r);
+
+    let mut src = vec![NO_SOURCE; n_rows];
+    for w in offsets.windows(2) {
+        let run = &rows[w[0]..w[1]];
+        match direction {
+            FillDirection::Forward => fill_run(run, &present, limit, &mut src, 0..run.len()),
+            FillDirection::Backward => {
+                fill_run(run, &present, limit, &mut src, (0..run.len()).rev())
+            }
+        }
+    }
+    Ok(src)
+}
+
+/// Forward/backward fill an f64 column.
+/// Writes filled values into `out_vals` and the resulting mask into `out_valid`.
+#[allow(clippy::too_many_arguments)]
+pub fn fill_f64(
+    vals: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: usize,
+    order: &[u32],
+    direction: FillDirection,
+    limit: usize,
+    out_vals: &mut [f64],
+    out_valid: &mut [u8],
+) -> Result<(), String> {
+    if !valid.is_empty() && valid.len() != vals.len() {
+        return Err(format!("Size mismatch: {} vs {}", vals.len(), valid.len()));
+    }
+    if out_vals.len() != vals.len() || out_valid.len() != vals.len() {
+        return Err("output length mismatch".to_string());
+    }
+    let src = fill_source_rows(vals, valid, gid_per_row, n_groups, order, direction, limit)?;
+    for (i, &s) in src.iter().enumerate() {
+        if s == NO_SOURCE {
+            out_vals[i] = f64::NAN;
+            out_valid[i] = 0;
+        } else {
+            out_vals[i] = vals[s as usize];
+            out_valid[i] = 1;
+        }
+    }
+    Ok(())
+}
+
+/// Forward/backward fill a u32 code column (dictionary-encoded strings, booleans, ...).
+/// Missing rows are flagged by `valid`; unfilled rows receive `na_code`.
+#[allow(clippy::too_many_arguments)]
+pub fn fill_u32(
+    codes: &[u32],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: usize,
+    order: &[u32],
+    direction: FillDirection,
+    limit: usize,
+    na_code: u32,
+    out_codes: &mut [u32],
+) -> Result<(), String> {
+    if valid.len() != codes.len() || out_codes.len() != codes.len() {
+        return Err(format!("Size mismatch: {} vs {}", codes.len(), valid.len()));
+    }
+    let src = fill_source_rows(&[], valid, gid_per_row, n_groups, order, direction, limit)?;
+    for (i, &s) in src.iter().enumerate() {
+        out_codes[i] = if s == NO_SOURCE {
+            na_code
+        } else {
+            codes[s as usize]
+        };
+    }
+    Ok(())
+}
+
+/// Natural cubic spline coefficients (b, c, d) for knots (xs, ys).
+fn natural_spline(xs: &[f64], ys: &[f64]) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let n = xs.len();
+    let h: Vec<f64> = (0..n - 1).map(|i| xs[i + 1] - xs[i]).collect();
+    let mut alpha = vec![0.0; n];
+    for i in 1..n - 1 {
+        alpha[i] = 3.0 / h[i] * (ys[i + 1] - ys[i]) - 3.0 / h[i - 1] * (ys[i] - ys[i - 1]);
+    }
+    let mut l = vec![1.0; n];
+    let mut mu = vec![0.0; n];
+    let mut z = vec![0.0; n];
+    for i in 1..n - 1 {
+        l[i] = 2.0 * (xs[i + 1] - xs[i - 1]) - h[i - 1] * mu[i - 1];
+        mu[i] = h[i] / l[i];
+        z[i] = (alpha[i] - h[i - 1] * z[i - 1]) / l[i];
+    }
+    let mut b = vec![0.0; n - 1];
+    let mut c = vec![0.0; n];
+    let mut d = vec![0.0; n - 1];
+    for j in (0..n - 1).rev() {
+        c[j] = z[j] - mu[j] * c[j + 1];
+        b[j] = (ys[j + 1] - ys[j]) / h[j] - h[j] * (c[j + 1] + 2.0 * c[j]) / 3.0;
+        d[j] = (c[j + 1] - c[j]) / (3.0 * h[j]);
+    }
+    (b, c, d)
+}
+
+/// Interpolate missing values of `vals` against the x-axis `x`.
+///
+/// Within each group, rows are visited in `order` (which should sort by `x`).
+/// Only rows bracketed by a previous and a next present value are filled;
+/// leading/trailing gaps and rows with non-finite `x` stay missing. Knots with
+/// non-finite `x` are ignored. Spline uses a natural cubic spline per group and
+/// falls back to linear when the group has fewer than 4 knots.
+#[allow(clippy::too_many_arguments)]
+pub fn interpolate_f64(
+    vals: &[f64],
+    valid: &[u8],
+    x: &[f64],
+    gid_per_row: &[u32],
+    n_groups: usize,
+    order: &[u32],
+    method: InterpolationMethod,
+    out_vals: &mut [f64],
+    out_valid: &mut [u8],
+) -> Result<(), String> {
+    let n_rows = vals.len();
+    if (!valid.is_empty() && valid.len() != n_rows) || x.len() != n_rows {
+        return Err("input length mismatch".to_string());
+    }
+    if out_vals.len() != n_rows || out_valid.len() != n_rows {
+        return Err("output length mismatch".to_string());
+    }
+    let (offsets, rows) = group_runs(n_rows, gid_per_row, n_groups, order)?;
+
+    for r in 0..n_rows {
+        let p = is_present(vals, valid, r);
+        out_vals[r] = if p { vals[r] } else { f64::NAN };
+        out_valid[r] = p as u8;
+    }
+
+    let mut kx: Vec<f64> = Vec::new();
+    let mut ky: Vec<f64> = Vec::new();
+    for w in offsets.windows(2) {
+        let run = &rows[w[0]..w[1]];
+        kx.clear();
+        ky.clear();
+        for &r in run {
+            let r = r as usize;
+            if is_present(vals, valid, r) && x[r].is_finite() {
+                kx.push(x[r]);
+                ky.push(vals[r]);
+            }
+        }
+        if kx.len() < 2 {
+            continue;
+        }
+        let spline = if method == InterpolationMethod::Spline && kx.len() >= 4 {
+            Some(natural_spline(&kx, &ky))
+        } else {
+            None
+        };
+
+        // Walk the run; `seg` is the index of the last knot seen
+        let mut seg: Option<usize> = None;
+        for &r in run {
+            let r = r as usize;
+            if is_present(vals, valid, r) {
+                if x[r].is_finite() {
+                    seg = Some(seg.map_or(0, |s| s + 1));
+                }
+                continue;
+            }
+            let Some(j) = seg else { continue };
+            if j + 1 >= kx.len() || !x[r].is_finite() {
+                continue;
+            }
+            let (x0, x1) = (kx[j], kx[j + 1]);
+            let xv = x[r];
+            let y = match &spline {
+                Some((b, c, d)) => {
+                    let dx = xv - x0;
+                    ky[j] + b[j] * dx + c[j] * dx * dx + d[j] * dx * dx * dx
+                }
+                None => ky[j] + (xv - x0) / (x1 - x0) * (ky[j + 1] - ky[j]),
+            };
+            if y.is_finite() {
+                out_vals[r] = y;
+                out_valid[r] = 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// WASM export for grouped forward/backward fill of an f64 column
+///
+/// - `direction`: 0 = forward, 1 = backward
+/// - `limit`: maximum consecutive missing rows to fill (0 = unlimited)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn fill_f64_wasm(
+    vals: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+    order: &[u32],
+    direction: u8,
+    limit: u32,
+    out_vals: &mut [f64],
+    out_valid: &mut [u8],
+) -> Result<(), JsValue> {
+    let direction = parse_direction(direction)?;
+    fill_f64(
+        vals,
+        valid,
+        gid_per_row,
+        n_groups as usize,
+        order,
+        direction,
+        limit as usize,
+        out_vals,
+        out_valid,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Fill error: {}", e)))
+}
+
+/// WASM export for grouped forward/backward fill of a u32 code column
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn fill_u32_wasm(
+    codes: &[u32],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+    order: &[u32],
+    direction: u8,
+    limit: u32,
+    na_code: u32,
+    out_codes: &mut [u32],
+) -> Result<(), JsValue> {
+    let direction = parse_direction(direction)?;
+    fill_u32(
+        codes,
+        valid,
+        gid_per_row,
+        n_groups as usize,
+        order,
+        direction,
+        limit as usize,
+        na_code,
+        out_codes,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Fill error: {}", e)))
+}
+
+/// WASM export returning the source row per row (u32::MAX = stays missing),
+/// for gathering columns of any type in JS.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn fill_source_rows_wasm(
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+    order: &[u32],
+    direction: u8,
+    limit: u32,
+) -> Result<Vec<u32>, JsValue> {
+    let direction = parse_direction(direction)?;
+    fill_source_rows(
+        &[],
+        valid,
+        gid_per_row,
+        n_groups as usize,
+        order,
+        direction,
+        limit as usize,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Fill error: {}", e)))
+}
+
+/// WASM export for grouped interpolation
+///
+/// - `method`: 0 = linear, 1 = spline
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn interpolate_f64_wasm(
+    vals: &[f64],
+    valid: &[u8],
+    x: &[f64],
+    gid_per_row: &[u32],
+    n_groups: u32,
+    order: &[u32],
+    method: u8,
+    out_vals: &mut [f64],
+    out_valid: &mut [u8],
+) -> Result<(), JsValue> {
+    let method = match method {
+        0 => InterpolationMethod::Linear,
+        1 => InterpolationMethod::Spline,
+        _ => return Err(JsValue::from_str("Invalid interpolation method")),
+    };
+    interpolate_f64(
+        vals,
+        valid,
+        x,
+        gid_per_row,
+        n_groups as usize,
+        order,
+        method,
+        out_vals,
+        out_valid,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Interpolate error: {}", e)))
+}
+
+#[cfg(feature = "wasm")]
+fn parse_direction(direction: u8) -> Result<FillDirection, JsValue> {
+    match direction {
+        0 => Ok(FillDirection::Forward),
+        1 => Ok(FillDirection::Backward),
+        _ => Err(JsValue::from_str("Invalid fill direction")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const N: f64 = f64::NAN;
+    const NS: u32 = NO_SOURCE;
+
+    fn fill(
+        vals: &[f64],
+        valid: &[u8],
+        gid: &[u32],
+        order: &[u32],
+        direction: FillDirection,
+        limit: usize,
+    ) -> (Vec<f64>, Vec<u8>) {
+        let mut out = vec![0.0; vals.len()];
+        let mut out_valid = vec![0; vals.len()];
+        let n_groups = gid.iter().max().map_or(1, |&g| g as usize + 1);
+        fill_f64(
+            vals,
+            valid,
+            gid,
+            n_groups,
+            order,
+            direction,
+            limit,
+            &mut out,
+            &mut out_valid,
+        )
+        .unwrap();
+        (out, out_valid)
+    }
+
+    fn interpolate(
+        vals: &[f64],
+        x: &[f64],
+        gid: &[u32],
+        order: &[u32],
+        method: InterpolationMethod,
+    ) -> (Vec<f64>, Vec<u8>) {
+        let mut out = vec![0.0; vals.len()];
+        let mut out_valid = vec![0; vals.len()];
+        let n_groups = gid.iter().max().map_or(1, |&g| g as usize + 1);
+        interpolate_f64(
+            vals,
+            &[],
+            x,
+            gid,
+            n_groups,
+            order,
+            method,
+            &mut out,
+            &mut out_valid,
+        )
+        .unwrap();
+        (out, out_valid)
+    }
+
+    #[test]
+    fn test_fill_limit_and_group_boundaries() {
+        let vals = [1.0, N, N, N, N, 2.0, N, N];
+        let gid = [0, 0, 0, 0, 1, 1, 1, 1];
+        let src = |direction, limit| {
+            fill_source_rows(&vals, &[], &gid, 2, &[], direction, limit).unwrap()
+        };
+        // Group 0 stops after two filled rows; group 1's first row has no
+        // earlier value in its own group
+        assert_eq!(
+            src(FillDirection::Forward, 2),
+            vec![0, 0, 0, NS, NS, 5, 5, 5]
+        );
+        assert_eq!(
+            src(FillDirection::Forward, 0),
+            vec![0, 0, 0, 0, NS, 5, 5, 5]
+        );
+        // Backward never reaches across into the next group
+        assert_eq!(
+            src(FillDirection::Backward, 0),
+            vec![0, NS, NS, NS, 5, 5, NS, NS]
+        );
+
+        let (out, valid) = fill(&vals, &[], &gid, &[], FillDirection::Forward, 2);
+        assert_eq!(&out[..3], &[1.0, 1.0, 1.0]);
+        assert!(out[3].is_nan() && out[4].is_nan());
+        assert_eq!(&out[5..], &[2.0, 2.0, 2.0]);
+        assert_eq!(valid, vec![1, 1, 1, 0, 0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_fill_follows_order() {
+        // Reversed order turns a forward fill into a backward one
+        let vals = [N, 1.0, N, 3.0, N];
+        let (out, valid) = fill(&vals, &[], &[], &[4, 3, 2, 1, 0], FillDirection::Forward, 0);
+        assert_eq!(&out[..4], &[1.0, 1.0, 3.0, 3.0]);
+        assert!(out[4].is_nan());
+        assert_eq!(valid, vec![1, 1, 1, 1, 0]);
+    }
+
+    #[test]
+    fn test_fill_uses_validity_mask() {
+        // The mask wins over the values: a NaN can be present and a number missing
+        let vals = [1.0, N, 3.0, 4.0];
+        let (out, valid) = fill(&vals, &[1, 1, 0, 0], &[], &[], FillDirection::Forward, 0);
+        assert_eq!(out[0], 1.0);
+        assert!(out[1..].iter().all(|v| v.is_nan()));
+        assert_eq!(valid, vec![1, 1, 1, 1]);
+
+        let mut out = vec![0.0; 4];
+        let mut out_valid = vec![0; 4];
+        let short = fill_f64(
+            &vals,
+            &[1, 1],
+            &[],
+            1,
+            &[],
+            FillDirection::Forward,
+            0,
+            &mut out,
+            &mut out_valid,
+        );
+        assert!(short.is_err());
+    }
+
+    #[test]
+    fn test_fill_u32() {
+        let codes = [7, 0, 9, 0, 5, 0];
+        let valid = [1, 0, 1, 0, 1, 0];
+        let gid = [0, 0, 0, 0, 1, 1];
+        let mut out = vec![0; 6];
+        let run = |direction, out: &mut [u32]| {
+            fill_u32(&codes, &valid, &gid, 2, &[], direction, 0, 99, out)
+        };
+        run(FillDirection::Backward, &mut out).unwrap();
+        assert_eq!(out, vec![7, 9, 9, 99, 5, 99]);
+        run(FillDirection::Forward, &mut out).unwrap();
+        assert_eq!(out, vec![7, 7, 9, 9, 5, 5]);
+        assert!(run(FillDirection::Forward, &mut out[..5]).is_err());
+        let bad_group = fill_u32(
+            &codes,
+            &valid,
+            &gid,
+            1,
+            &[],
+            FillDirection::Forward,
+            0,
+            99,
+            &mut out,
+        );
+        assert!(bad_group.is_err());
+    }
+
+    #[test]
+    fn test_interpolate_linear() {
+        // Group 1's leading and trailing gaps stay missing, as does a row with
+        // no x; rows are visited sorted by x
+        let vals = [6.0, N, 0.0, N, N, 4.0, N, N];
+        let x = [3.0, 1.0, 0.0, 2.0, 0.0, 1.0, 2.0, f64::NAN];
+        let gid = [0, 0, 0, 0, 1, 1, 1, 0];
+        let order = [2, 4, 1, 5, 3, 6, 0, 7];
+        let (out, valid) = interpolate(&vals, &x, &gid, &order, InterpolationMethod::Linear);
+        assert_eq!(&out[..4], &[6.0, 2.0, 0.0, 4.0]);
+        assert_eq!(out[5], 4.0);
+        assert_eq!(valid, vec![1, 1, 1, 1, 0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_interpolate_natural_spline() {
+        // Knots (0, 0), (1, 1), (2, 0), (3, 1): second derivatives 0, -4, 4, 0,
+        // so S(0.25) = 39/96 and the midpoints are 0.75, 0.5 and 0.25
+        let x = [0.0, 0.25, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0];
+        let vals = [0.0, N, N, 1.0, N, 0.0, N, 1.0];
+        let (out, valid) = interpolate(&vals, &x, &[], &[], InterpolationMethod::Spline);
+        let expected = [0.0, 0.40625, 0.75, 1.0, 0.5, 0.0, 0.25, 1.0];
+        for (y, e) in out.iter().zip(expected) {
+            assert!((y - e).abs() < 1e-12, "{} vs {}", y, e);
+        }
+        assert!(valid.iter().all(|&v| v == 1));
+
+        // Three knots fall back to linear
+        let (out, _) = interpolate(&vals[..6], &x[..6], &[], &[], InterpolationMethod::Spline);
+        assert_eq!(&out[1..3], &[0.25, 0.5]);
+        assert_eq!(out[4], 0.5);
+    }
+}
@@ -16,6 +16,53 @@ pub enum ComparisonOp {
     NotEqual,
 }
 
+impl ComparisonOp {
+    /// Decode the opcode used by the WASM exports: 0=GT, 1=GTE, 2=LT, 3=LTE, 4=EQ, 5=NE
+    pub fn from_code(code: u8) -> Option<ComparisonOp> {
+        match code {
+            0 => Some(ComparisonOp::Greater),
+            1 => Some(ComparisonOp::GreaterEqual),
+            2 => Some(ComparisonOp::Less),
+            3 => Some(ComparisonOp::LessEqual),
+            4 => Some(ComparisonOp::Equal),
+            5 => Some(ComparisonOp::NotEqual),
+            _ => None,
+        }
+    }
+
+    /// Parse an operator symbol (">", ">=", "<", "<=", "==", "!=") or name ("gt", "gte", ...)
+    pub fn parse(s: &str) -> Option<ComparisonOp> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            ">" | "gt" => Some(ComparisonOp::Greater),
+            ">=" | "gte" | "ge" => Some(ComparisonOp::GreaterEqual),
+            "<" | "lt" => Some(ComparisonOp::Less),
+            "<=" | "lte" | "le" => Some(ComparisonOp::LessEqual),
+            "==" | "=" | "eq" => Some(ComparisonOp::Equal),
+            "!=" | "<>" | "ne" | "neq" => Some(ComparisonOp::NotEqual),
+            _ => None,
+        }
+    }
+
+    /// Scalar form of [`batch_compare_numbers`]: NaN and infinities never match
+    #[inline]
+    pub fn matches(self, value: f64, threshold: f64) -> bool {
+        match self {
+            ComparisonOp::Greater => {
+                value.is_finite() && threshold.is_finite() && value > threshold
+            }
+            ComparisonOp::GreaterEqual => {
+                value.is_finite() && threshold.is_finite() && value >= threshold
+            }
+            ComparisonOp::Less => value.is_finite() && threshold.is_finite() && value < threshold,
+            ComparisonOp::LessEqual => {
+                value.is_finite() && threshold.is_finite() && value <= threshold
+            }
+            ComparisonOp::Equal => f64_eq(value, threshold),
+            ComparisonOp::NotEqual => f64_ne(value, threshold),
+        }
+    }
+}
+
 /// String operations.
 #[derive(Debug, Copy, Clone)]
 pub enum StringOp {
@@ -124,15 +171,8 @@ pub fn batch_filter_numbers(
     operation: u8,
     output: &mut [u8],
 ) -> Result<(), JsValue> {
-    let op = match operation {
-        0 => ComparisonOp::Greater,
-        1 => ComparisonOp::GreaterEqual,
-        2 => ComparisonOp::Less,
-        3 => ComparisonOp::LessEqual,
-        4 => ComparisonOp::Equal,
-        5 => ComparisonOp::NotEqual,
-        _ => return Err(JsValue::from_str("Invalid comparison operation")),
-    };
+    let op = ComparisonOp::from_code(operation)
+        .ok_or_else(|| JsValue::from_str("Invalid comparison operation"))?;
 
     batch_compare_numbers(values, threshold, op, output)
         .map_err(|e| JsValue::from_str(&format!("Batch filter error: {}", e)))
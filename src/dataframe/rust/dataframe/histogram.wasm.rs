@@ -0,0 +1,789 @@
+//! Histogram and binning (`cut`) WASM exports
+//!
+//! Break rules follow R: `sturges`, `scott` and `fd` pick a number of classes
+//! (`nclass.Sturges`, `nclass.scott`, `nclass.FD`) and round it to `pretty`
+//! breaks as `hist()` does; `equal` splits the range into equal-width
+//! intervals widened by 0.1% as `cut(x, breaks = n)` does; `width` uses
+//! multiples of a fixed width; explicit breaks are used as given. Computed
+//! breaks are limited to `MAX_BINS` (10^6) intervals per group.
+//!
+//! Intervals are `(a, b]` when `right` is true and `[a, b)` otherwise;
+//! `include_lowest` closes the first (or, with `right = false`, the last)
+//! interval. Histogram counts tolerate floating-point noise at the breaks
+//! like `hist()` (a fuzz of 1e-7 of the median bin width); `cut` codes are
+//! exact like `.bincode`.
+//!
+//! Conventions shared by every kernel in this module:
+//! - `valid`: 1 = value present, 0 = missing. An empty mask means "NaN is missing".
+//! - `gid_per_row`: group id per row (from `group_ids_codes_all`). Empty = one group.
+//! - Codes are 0-based interval indices; `CUT_NA_CODE` (u32::MAX) marks missing
+//!   values and values outside the breaks.
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Code of values that fall in no interval
+pub const CUT_NA_CODE: u32 = u32::MAX;
+
+/// Most intervals a rule may produce per group
+pub const MAX_BINS: usize = 1_000_000;
+
+fn too_many_bins(n: f64) -> String {
+    format!("Too many bins: {} exceeds the limit of {}", n, MAX_BINS)
+}
+
+/// How breaks are chosen
+#[derive(Debug, Clone, PartialEq)]
+pub enum BreakRule {
+    /// `pretty` breaks for `ceiling(log2(n) + 1)` classes
+    Sturges,
+    /// `pretty` breaks for Scott's normal-reference bin width
+    Scott,
+    /// `pretty` breaks for the Freedman–Diaconis bin width
+    FreedmanDiaconis,
+    /// `pretty` breaks for about `n` classes
+    Pretty(usize),
+    /// `n` equal-width intervals over the range, widened by 0.1% (R `cut`)
+    Equal(usize),
+    /// Intervals of a fixed width aligned to multiples of the width
+    Width(f64),
+    /// Explicit, strictly increasing breaks
+    Explicit(Vec<f64>),
+}
+
+impl BreakRule {
+    /// Parse a rule name; `param` is the class count for `pretty`/`equal`,
+    /// the width for `width` and ignored otherwise
+    pub fn parse(name: &str, param: f64, breaks: &[f64]) -> Result<BreakRule, String> {
+        let count = || {
+            if param > MAX_BINS as f64 {
+                Err(too_many_bins(param))
+            } else if param >= 1.0 {
+                Ok(param as usize)
+            } else {
+                Err(format!(
+                    "Number of classes must be at least 1, got {}",
+                    param
+                ))
+            }
+        };
+        Ok(match name.to_ascii_lowercase().as_str() {
+            "sturges" => BreakRule::Sturges,
+            "scott" => BreakRule::Scott,
+            "fd" | "freedman-diaconis" => BreakRule::FreedmanDiaconis,
+            "pretty" => BreakRule::Pretty(count()?),
+            "equal" => BreakRule::Equal(count()?),
+            "width" => BreakRule::Width(param),
+            "breaks" => BreakRule::Explicit(breaks.to_vec()),
+            _ => return Err(format!("Unknown break rule '{}'", name)),
+        })
+    }
+}
+
+/// R's `R_pretty`: returns `(lo, hi, n)` with `n` intervals of a 1-2-5 unit
+fn r_pretty(lo: f64, up: f64, ndiv: usize, min_n: usize) -> (f64, f64, usize) {
+    const ROUNDING_EPS: f64 = 1e-10;
+    const H: f64 = 1.5;
+    const H5: f64 = 0.5 + 1.5 * H;
+    const SHRINK_SML: f64 = 0.75;
+
+    let dx = up - lo;
+    let (mut cell, i_small) = if dx == 0.0 && up == 0.0 {
+        (1.0, true)
+    } else {
+        let cell = lo.abs().max(up.abs());
+        let u = (1.0 + 1.0 / (1.0 + H)) * ndiv.max(1) as f64 * f64::EPSILON;
+        (cell, dx < cell * u * 3.0)
+    };
+    if i_small {
+        if cell > 10.0 {
+            cell = 9.0 + cell / 10.0;
+        }
+        cell *= SHRINK_SML;
+        if min_n > 1 {
+            cell /= min_n as f64;
+        }
+    } else {
+        cell = dx;
+        if ndiv > 1 {
+            cell /= ndiv as f64;
+        }
+    }
+    cell = cell.clamp(20.0 * f64::MIN_POSITIVE, 0.1 * f64::MAX);
+
+    let base = 10f64.powf(cell.log10().floor());
+    let mut unit = base;
+    if 2.0 * base - cell < H * (cell - unit) {
+        unit = 2.0 * base;
+        if 5.0 * base - cell < H5 * (cell - unit) {
+            unit = 5.0 * base;
+            if 10.0 * base - cell < H * (cell - unit) {
+                unit = 10.0 * base;
+            }
+        }
+    }
+    let mut ns = (lo / unit + ROUNDING_EPS).floor();
+    let mut nu = (up / unit - ROUNDING_EPS).ceil();
+    while ns * unit > lo + ROUNDING_EPS * unit {
+        ns -= 1.0;
+    }
+    while nu * unit < up - ROUNDING_EPS * unit {
+        nu += 1.0;
+    }
+    let mut k = (0.5 + nu - ns) as usize;
+    if k < min_n {
+        let d = (min_n - k) as f64;
+        let half = (d / 2.0).floor();
+        let odd = d - 2.0 * half;
+        if ns >= 0.0 {
+            nu += half;
+            ns -= half + odd;
+        } else {
+            ns -= half;
+            nu += half + odd;
+        }
+        k = min_n;
+    }
+    (ns * unit, nu * unit, k)
+}
+
+/// R's `pretty(c(lo, hi), n, min.n)`
+pub fn pretty(lo: f64, hi: f64, n: usize, min_n: usize) -> Vec<f64> {
+    let (l, u, k) = r_pretty(lo, hi, n, min_n);
+    if k == 0 {
+        return vec![l];
+    }
+    let step = (u - l) / k as f64;
+    (0..=k)
+        .map(|i| {
+            let s = l + step * i as f64;
+            if s.abs() < 1e-14 * step { 0.0 } else { s }
+        })
+        .collect()
+}
+
+/// Type 7 quantile of sorted data
+fn quantile_sorted(sorted: &[f64], p: f64) -> f64 {
+    let h = (sorted.len() - 1) as f64 * p;
+    let lo = h.floor() as usize;
+    let hi = (lo + 1).min(sorted.len() - 1);
+    sorted[lo] + (h - lo as f64) * (sorted[hi] - sorted[lo])
+}
+
+/// Number of classes for the `pretty`-based rules (R's `nclass.*`)
+fn n_classes(rule: &BreakRule, sorted: &[f64]) -> usize {
+    let n = sorted.len() as f64;
+    let range = sorted[sorted.len() - 1] - sorted[0];
+    match rule {
+        BreakRule::Sturges => (n.log2() + 1.0).ceil() as usize,
+        BreakRule::Scott => {
+            let mean = sorted.iter().sum::<f64>() / n;
+            let var = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            let h = 3.5 * var.sqrt() * n.powf(-1.0 / 3.0);
+            if h > 0.0 {
+                ((range / h).ceil() as usize).max(1)
+            } else {
+                1
+            }
+        }
+        BreakRule::FreedmanDiaconis => {
+            let mut h = 2.0 * (quantile_sorted(sorted, 0.75) - quantile_sorted(sorted, 0.25));
+            // Fall back to wider inner quantile ranges when the IQR is zero
+            let mut al = 0.25;
+            while h == 0.0 && al / 2.0 >= 1.0 / 512.0 {
+                al /= 2.0;
+                h = (quantile_sorted(sorted, 1.0 - al) - quantile_sorted(sorted, al))
+                    / (1.0 - 2.0 * al);
+            }
+            if h > 0.0 {
+                ((range / (h * n.powf(-1.0 / 3.0))).ceil() as usize).max(1)
+            } else {
+                1
+            }
+        }
+        BreakRule::Pretty(k) => *k,
+        _ => 1,
+    }
+}
+
+/// Whether breaks are strictly increasing (and not NaN)
+fn strictly_increasing(b: &[f64]) -> bool {
+    b.windows(2)
+        .all(|w| w[0].partial_cmp(&w[1]) == Some(std::cmp::Ordering::Less))
+}
+
+/// Breaks for the present values in `vals` (sorted in place)
+fn breaks_for(values: &mut [f64], rule: &BreakRule) -> Result<Vec<f64>, String> {
+    if let BreakRule::Explicit(b) = rule {
+        if b.len() < 2 {
+            return Err("At least two breaks are required".to_string());
+        }
+        if !strictly_increasing(b) {
+            return Err("Breaks must be strictly increasing".to_string());
+        }
+        return Ok(b.clone());
+    }
+    if values.is_empty() {
+        return Ok(Vec::new());
+    }
+    values.sort_by(f64::total_cmp);
+    let (lo, hi) = (values[0], values[values.len() - 1]);
+    if !lo.is_finite() || !hi.is_finite() {
+        return Err("Cannot compute breaks for infinite values".to_string());
+    }
+    match rule {
+        BreakRule::Equal(n) => {
+            if *n > MAX_BINS {
+                return Err(too_many_bins(*n as f64));
+            }
+            let nb = n + 1;
+            let dx = hi - lo;
+            let mut b: Vec<f64>;
+            if dx == 0.0 {
+                let d = lo.abs();
+                let (a, z) = (lo - d / 1000.0, hi + d / 1000.0);
+                b = (0..nb)
+                    .map(|i| a + (z - a) * i as f64 / *n as f64)
+                    .collect();
+            } else {
+                b = (0..nb).map(|i| lo + dx * i as f64 / *n as f64).collect();
+                b[0] = lo - dx / 1000.0;
+                b[*n] = hi + dx / 1000.0;
+            }
+            if !strictly_increasing(&b) {
+                return Err("Breaks are not unique".to_string());
+            }
+            Ok(b)
+        }
+        BreakRule::Width(w) => {
+            if *w <= 0.0 || !w.is_finite() {
+                return Err(format!("Bin width must be positive, got {}", w));
+            }
+            let start = (lo / w).floor();
+            let mut end = (hi / w).ceil();
+            if end == start {
+                end += 1.0;
+            }
+            // NaN when the range overflows at this width
+            let bins = end - start;
+            if bins.is_nan() || bins > MAX_BINS as f64 {
+                return Err(too_many_bins(bins));
+            }
+            Ok((0..=(end - start) as usize)
+                .map(|i| (start + i as f64) * w)
+                .collect())
+        }
+        _ => {
+            let classes = n_classes(rule, values);
+            if classes > MAX_BINS {
+                return Err(too_many_bins(classes as f64));
+            }
+            Ok(pretty(lo, hi, classes, 1))
+        }
+    }
+}
+
+/// Interval index of `x`, or `None` outside the breaks (`fuzz`: per-break
+/// offsets applied as in R's `hist`)
+fn bin_of(x: f64, breaks: &[f64], right: bool, include_lowest: bool, fuzz: f64) -> Option<usize> {
+    let nb = breaks.len();
+    let at = |i: usize| {
+        let f = if right {
+            if i == 0 && include_lowest {
+                -fuzz
+            } else {
+                fuzz
+            }
+        } else if i == nb - 1 && include_lowest {
+            fuzz
+        } else {
+            -fuzz
+        };
+        breaks[i] + f
+    };
+    if right {
+        // First break >= x, searched over the fuzzed breaks
+        let k = partition(nb, |i| at(i) < x);
+        match k {
+            0 if include_lowest && x >= at(0) => Some(0),
+            0 => None,
+            k if k < nb => Some(k - 1),
+            _ => None,
+        }
+    } else {
+        // Last break <= x
+        let k = partition(nb, |i| at(i) <= x);
+        match k {
+            0 => None,
+            k if k < nb => Some(k - 1),
+            _ if include_lowest && x <= at(nb - 1) => Some(nb - 2),
+            _ => None,
+        }
+    }
+}
+
+/// Number of leading indices in `0..n` satisfying a monotone predicate
+fn partition(n: usize, pred: impl Fn(usize) -> bool) -> usize {
+    let (mut lo, mut hi) = (0, n);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// Binning result for every group
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Binned {
+    /// Breaks of all groups concatenated; group `g` owns
+    /// `breaks[break_offsets[g]..break_offsets[g + 1]]`
+    pub breaks: Vec<f64>,
+    pub break_offsets: Vec<usize>,
+    /// Counts of all groups concatenated (one fewer per group than breaks)
+    pub counts: Vec<u32>,
+    /// Interval index per row within its group's breaks
+    pub codes: Vec<u32>,
+}
+
+/// Bin values per group.
+///
+/// With `shared` every group uses breaks computed from all values (aligned
+/// facets); otherwise each group gets its own breaks. `fuzz` selects `hist()`
+/// tolerance at the breaks (histograms) over exact `cut` semantics.
+#[allow(clippy::too_many_arguments)]
+pub fn bin_values(
+    vals: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: usize,
+    rule: &BreakRule,
+    shared: bool,
+    right: bool,
+    include_lowest: bool,
+    fuzz: bool,
+) -> Result<Binned, String> {
+    let n = vals.len();
+    if !valid.is_empty() && valid.len() != n {
+        return Err(format!("valid length mismatch: {} vs {}", valid.len(), n));
+    }
+    if !gid_per_row.is_empty() && gid_per_row.len() != n {
+        return Err(format!(
+            "gid_per_row length mismatch: {} vs {}",
+            gid_per_row.len(),
+            n
+        ));
+    }
+    let n_groups = if gid_per_row.is_empty() { 1 } else { n_groups };
+    if let Some(&g) = gid_per_row.iter().find(|&&g| g as usize >= n_groups) {
+        return Err(format!(
+            "group id {} out of range for {} groups",
+            g, n_groups
+        ));
+    }
+    let present = |i: usize| {
+        if valid.is_empty() {
+            !vals[i].is_nan()
+        } else {
+            valid[i] != 0 && !vals[i].is_nan()
+        }
+    };
+    let group = |i: usize| gid_per_row.get(i).map_or(0, |&g| g as usize);
+
+    // Breaks per group
+    let mut per_group: Vec<Vec<f64>> = vec![Vec::new(); n_groups];
+    if shared {
+        let mut all: Vec<f64> = (0..n).filter(|&i| present(i)).map(|i| vals[i]).collect();
+        let b = breaks_for(&mut all, rule)?;
+        per_group.iter_mut().for_each(|g| g.clone_from(&b));
+    } else {
+        let mut values: Vec<Vec<f64>> = vec![Vec::new(); n_groups];
+        for i in (0..n).filter(|&i| present(i)) {
+            values[group(i)].push(vals[i]);
+        }
+        for (g, v) in values.iter_mut().enumerate() {
+            per_group[g] = breaks_for(v, rule)?;
+        }
+    }
+
+    let mut out = Binned::default();
+    let mut count_offsets = Vec::with_capacity(n_groups);
+    let mut fuzzes = Vec::with_capacity(n_groups);
+    out.break_offsets.push(0);
+    for b in &per_group {
+        count_offsets.push(out.counts.len());
+        out.counts
+            .extend(std::iter::repeat_n(0, b.len().saturating_sub(1)));
+        out.breaks.extend_from_slice(b);
+        out.break_offsets.push(out.breaks.len());
+        fuzzes.push(if fuzz && b.len() > 1 {
+            let mut widths: Vec<f64> = b.windows(2).map(|w| w[1] - w[0]).collect();
+            widths.sort_by(f64::total_cmp);
+            1e-7 * quantile_sorted(&widths, 0.5)
+        } else {
+            0.0
+        });
+    }
+
+    out.codes = (0..n)
+        .map(|i| {
+            let g = group(i);
+            let b = &per_group[g];
+            if !present(i) || b.len() < 2 {
+                return CUT_NA_CODE;
+            }
+            match bin_of(vals[i], b, right, include_lowest, fuzzes[g]) {
+                Some(k) => {
+                    out.counts[count_offsets[g] + k] += 1;
+                    k as u32
+                }
+                None => CUT_NA_CODE,
+            }
+        })
+        .collect();
+    Ok(out)
+}
+
+/// Format like C's `%.{digits}g` (as R's `formatC(x, digits)`)
+fn format_g(x: f64, digits: usize) -> String {
+    if x == 0.0 {
+        return "0".to_string();
+    }
+    if !x.is_finite() {
+        return if x.is_nan() {
+            "NaN".to_string()
+        } else if x > 0.0 {
+            "Inf".to_string()
+        } else {
+            "-Inf".to_string()
+        };
+    }
+    let digits = digits.max(1);
+    let sci = format!("{:.*e}", digits - 1, x);
+    let (mantissa, exp) = sci.split_once('e').expect("exponent");
+    let exp: i32 = exp.parse().expect("integer exponent");
+    let trim = |s: &str| -> String {
+        if s.contains('.') {
+            s.trim_end_matches('0').trim_end_matches('.').to_string()
+        } else {
+            s.to_string()
+        }
+    };
+    if exp < -4 || exp >= digits as i32 {
+        let sign = if exp < 0 { '-' } else { '+' };
+        format!("{}e{}{:02}", trim(mantissa), sign, exp.abs())
+    } else {
+        let decimals = (digits as i32 - 1 - exp).max(0) as usize;
+        trim(&format!("{:.*}", decimals, x))
+    }
+}
+
+/// Interval labels as R's `cut` builds them: `(a,b]` / `[a,b)`, with digits
+/// increased from `dig_lab` (up to 12) until adjacent breaks differ
+pub fn cut_labels(
+    breaks: &[f64],
+    right: bool,
+    include_lowest: bool,
+    dig_lab: usize,
+) -> Vec<String> {
+    if breaks.len() < 2 {
+        return Vec::new();
+    }
+    let mut formatted: Vec<String> = Vec::new();
+    for dig in dig_lab.max(1)..=dig_lab.max(12) {
+        formatted = breaks.iter().map(|&b| format_g(b, dig)).collect();
+        if formatted.windows(2).all(|w| w[0] != w[1]) {
+            break;
+        }
+    }
+    let (open, close) = if right { ('(', ']') } else { ('[', ')') };
+    let nb = formatted.len();
+    (0..nb - 1)
+        .map(|i| {
+            let mut o = open;
+            let mut c = close;
+            if include_lowest {
+                if right && i == 0 {
+                    o = '[';
+                }
+                if !right && i == nb - 2 {
+                    c = ']';
+                }
+            }
+            format!("{}{},{}{}", o, formatted[i], formatted[i + 1], c)
+        })
+        .collect()
+}
+
+/// Binning result handed to JavaScript
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct BinnedWasm {
+    binned: Binned,
+    right: bool,
+    include_lowest: bool,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl BinnedWasm {
+    /// Concatenated breaks of every group
+    pub fn breaks(&self) -> Vec<f64> {
+        self.binned.breaks.clone()
+    }
+
+    /// Start of each group's breaks in [`BinnedWasm::breaks`] (`n_groups + 1` entries)
+    #[wasm_bindgen(js_name = breakOffsets)]
+    pub fn break_offsets(&self) -> Vec<u32> {
+        self.binned
+            .break_offsets
+            .iter()
+            .map(|&o| o as u32)
+            .collect()
+    }
+
+    /// Concatenated counts of every group
+    pub fn counts(&self) -> Vec<u32> {
+        self.binned.counts.clone()
+    }
+
+    /// Counts divided by the group's binned total and the bin width
+    pub fn density(&self) -> Vec<f64> {
+        let b = &self.binned;
+        let mut out = Vec::with_capacity(b.counts.len());
+        let mut start = 0;
+        for w in b.break_offsets.windows(2) {
+            let breaks = &b.breaks[w[0]..w[1]];
+            let bins = breaks.len().saturating_sub(1);
+            let counts = &b.counts[start..start + bins];
+            let total: u32 = counts.iter().sum();
+            for (k, &c) in counts.iter().enumerate() {
+                let width = breaks[k + 1] - breaks[k];
+                out.push(if total > 0 {
+                    c as f64 / (total as f64 * width)
+                } else {
+                    0.0
+                });
+            }
+            start += bins;
+        }
+        out
+    }
+
+    /// Interval index per row (`u32::MAX` = not binned)
+    pub fn codes(&self) -> Vec<u32> {
+        self.binned.codes.clone()
+    }
+
+    /// Labels of every group's intervals, concatenated like the counts
+    pub fn labels(&self, dig_lab: u32) -> Vec<String> {
+        self.binned
+            .break_offsets
+            .windows(2)
+            .flat_map(|w| {
+                cut_labels(
+                    &self.binned.breaks[w[0]..w[1]],
+                    self.right,
+                    self.include_lowest,
+                    dig_lab as usize,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[allow(clippy::too_many_arguments)]
+fn bin_wasm(
+    vals: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+    rule: &str,
+    param: f64,
+    breaks: &[f64],
+    shared: bool,
+    right: bool,
+    include_lowest: bool,
+    fuzz: bool,
+) -> Result<BinnedWasm, JsValue> {
+    let to_js = |e: String| JsValue::from_str(&format!("Binning error: {}", e));
+    let rule = BreakRule::parse(rule, param, breaks).map_err(to_js)?;
+    let binned = bin_values(
+        vals,
+        valid,
+        gid_per_row,
+        n_groups as usize,
+        &rule,
+        shared,
+        right,
+        include_lowest,
+        fuzz,
+    )
+    .map_err(to_js)?;
+    Ok(BinnedWasm {
+        binned,
+        right,
+        include_lowest,
+    })
+}
+
+/// WASM export for (grouped) histograms
+///
+/// - `rule`: "sturges" | "scott" | "fd" | "pretty" | "equal" | "width" | "breaks"
+/// - `param`: class count for "pretty"/"equal", bin width for "width"
+/// - `breaks`: explicit breaks for "breaks"
+/// - `shared`: use the same breaks for every group
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn histogram_wasm(
+    vals: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+    rule: &str,
+    param: f64,
+    breaks: &[f64],
+    shared: bool,
+) -> Result<BinnedWasm, JsValue> {
+    bin_wasm(
+        vals,
+        valid,
+        gid_per_row,
+        n_groups,
+        rule,
+        param,
+        breaks,
+        shared,
+        true,
+        true,
+        true,
+    )
+}
+
+/// WASM export for R's `cut`: exact interval codes with `right` /
+/// `include_lowest` semantics (grouped when `gid_per_row` is given and
+/// `shared` is false)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn cut_wasm(
+    vals: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+    rule: &str,
+    param: f64,
+    breaks: &[f64],
+    shared: bool,
+    right: bool,
+    include_lowest: bool,
+) -> Result<BinnedWasm, JsValue> {
+    bin_wasm(
+        vals,
+        valid,
+        gid_per_row,
+        n_groups,
+        rule,
+        param,
+        breaks,
+        shared,
+        right,
+        include_lowest,
+        false,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bin(vals: &[f64], rule: &BreakRule) -> Result<Binned, String> {
+        bin_values(vals, &[], &[], 1, rule, true, true, true, true)
+    }
+
+    #[test]
+    fn test_sturges_matches_hist() {
+        // hist(c(1, 2, 2, 3, 3, 3, 4, 4, 5))
+        let b = bin(
+            &[1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0, 4.0, 5.0],
+            &BreakRule::Sturges,
+        )
+        .unwrap();
+        assert_eq!(b.breaks, vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(b.counts, vec![3, 3, 2, 1]);
+        assert_eq!(b.codes, vec![0, 0, 0, 1, 1, 1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn test_equal_matches_cut() {
+        // cut(1:5, breaks = 2)
+        let b = bin_values(
+            &[1.0, 2.0, 3.0, 4.0, 5.0],
+            &[],
+            &[],
+            1,
+            &BreakRule::Equal(2),
+            true,
+            true,
+            false,
+            false,
+        )
+        .unwrap();
+        assert_eq!(b.breaks, vec![0.996, 3.0, 5.004]);
+        assert_eq!(b.codes, vec![0, 0, 0, 1, 1]);
+        assert_eq!(
+            cut_labels(&b.breaks, true, false, 3),
+            vec!["(0.996,3]", "(3,5]"]
+        );
+    }
+
+    #[test]
+    fn test_width_and_missing_values() {
+        let b = bin_values(
+            &[0.5, f64::NAN, 2.5, 3.0],
+            &[],
+            &[],
+            1,
+            &BreakRule::Width(1.0),
+            true,
+            true,
+            true,
+            false,
+        )
+        .unwrap();
+        assert_eq!(b.breaks, vec![0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(b.codes, vec![0, CUT_NA_CODE, 2, 2]);
+        assert_eq!(b.counts, vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn test_bin_count_is_capped() {
+        assert!(BreakRule::parse("equal", 1e7, &[]).is_err());
+        assert!(BreakRule::parse("pretty", f64::INFINITY, &[]).is_err());
+        assert!(BreakRule::parse("equal", f64::NAN, &[]).is_err());
+        assert!(BreakRule::parse("equal", 0.5, &[]).is_err());
+        assert!(bin(&[0.0, 1.0], &BreakRule::Equal(usize::MAX)).is_err());
+        assert!(bin(&[0.0, 1e9], &BreakRule::Width(1.0)).is_err());
+        assert!(bin(&[-1e300, 1e300], &BreakRule::Width(1e-300)).is_err());
+        assert!(bin(&[0.0, 1.0], &BreakRule::Pretty(MAX_BINS + 1)).is_err());
+        // Freedman–Diaconis on one far outlier asks for an enormous class count
+        let mut vals = vec![0.0; 1000];
+        vals.iter_mut()
+            .enumerate()
+            .for_each(|(i, v)| *v = i as f64 * 1e-6);
+        vals.push(1e12);
+        assert!(bin(&vals, &BreakRule::FreedmanDiaconis).is_err());
+        assert_eq!(
+            bin(&[0.0, 10.0], &BreakRule::Width(1e-5))
+                .unwrap()
+                .counts
+                .len(),
+            MAX_BINS
+        );
+    }
+}
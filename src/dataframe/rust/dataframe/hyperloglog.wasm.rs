@@ -0,0 +1,698 @@
+//! HyperLogLog approximate distinct counting WASM exports
+//!
+//! A HyperLogLog sketch estimates the number of distinct values in a stream
+//! using `2^precision` one-byte registers, independent of the cardinality.
+//! As in HLL++, small cardinalities are kept exactly in a sparse set of 64-bit
+//! hashes until that set would outgrow the dense registers. Dense estimates use
+//! Ertl's improved estimator, which needs no empirical bias tables and is
+//! unbiased from tiny to very large cardinalities. The relative standard error
+//! is about `1.04 / sqrt(2^precision)` (0.8% at the default precision 14).
+//!
+//! Sketches merge by register-wise maximum and serialise to bytes, so counts
+//! can be combined across chunks or workers.
+//!
+//! Conventions shared by the grouped kernels in this module:
+//! - `valid`: 1 = value present, 0 = missing. An empty mask means "NaN is
+//!   missing" (or `na_code` for code columns).
+//! - `gid_per_row`: group id per row (from `group_ids_codes_all`). Empty = group 0.
+//! - Dictionary codes are hashed as numbers: only merge code-based sketches
+//!   built against the same dictionary (hash the strings otherwise).
+
+#[cfg(feature = "wasm")]
+use super::join_helpers::bulk_copy_u32;
+use super::join_helpers::{hash_row_multi, mix64};
+#[cfg(feature = "wasm")]
+use js_sys::Uint32Array;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+pub const MIN_PRECISION: u8 = 4;
+pub const MAX_PRECISION: u8 = 18;
+pub const DEFAULT_PRECISION: u8 = 14;
+
+/// Magic prefix of serialised sketches
+const MAGIC: &[u8; 4] = b"HLL1";
+
+/// 64-bit hash of an f64 (`-0.0` and `0.0` hash alike)
+#[inline]
+pub fn hash_f64(x: f64) -> u64 {
+    let x = if x == 0.0 { 0.0 } else { x };
+    mix64(x.to_bits())
+}
+
+/// 64-bit hash of a dictionary code
+#[inline]
+pub fn hash_u32(code: u32) -> u64 {
+    mix64(code as u64)
+}
+
+/// 64-bit hash of a string (FNV-1a, finalised with `mix64`)
+#[inline]
+pub fn hash_str(s: &str) -> u64 {
+    let mut h = 0xcbf29ce484222325u64;
+    for b in s.as_bytes() {
+        h ^= *b as u64;
+        h = h.wrapping_mul(0x100000001b3);
+    }
+    mix64(h)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Registers {
+    /// Distinct hashes seen so far (exact), sorted after `normalize`
+    Sparse(Vec<u64>),
+    Dense(Vec<u8>),
+}
+
+/// HyperLogLog sketch
+#[derive(Debug, Clone, PartialEq)]
+pub struct HyperLogLog {
+    precision: u8,
+    registers: Registers,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        HyperLogLog::new(DEFAULT_PRECISION).expect("default precision is valid")
+    }
+}
+
+impl HyperLogLog {
+    pub fn new(precision: u8) -> Result<Self, String> {
+        if !(MIN_PRECISION..=MAX_PRECISION).contains(&precision) {
+            return Err(format!(
+                "precision must be between {} and {}, got {}",
+                MIN_PRECISION, MAX_PRECISION, precision
+            ));
+        }
+        Ok(HyperLogLog {
+            precision,
+            registers: Registers::Sparse(Vec::new()),
+        })
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    fn m(&self) -> usize {
+        1 << self.precision
+    }
+
+    /// Sparse hashes kept before switching to registers (same memory)
+    fn sparse_limit(&self) -> usize {
+        self.m() / 8
+    }
+
+    /// Add a pre-hashed value
+    pub fn add_hash(&mut self, h: u64) {
+        // Let duplicates accumulate up to twice the limit before deduplicating
+        let pending_limit = 2 * self.sparse_limit();
+        match &mut self.registers {
+            Registers::Sparse(hashes) => {
+                hashes.push(h);
+                if hashes.len() > pending_limit {
+                    self.normalize();
+                }
+            }
+            Registers::Dense(regs) => set_register(regs, self.precision, h),
+        }
+    }
+
+    /// Deduplicate the sparse set and densify it once it is too large
+    fn normalize(&mut self) {
+        let (limit, m) = (self.sparse_limit(), self.m());
+        if let Registers::Sparse(hashes) = &mut self.registers {
+            hashes.sort_unstable();
+            hashes.dedup();
+            if hashes.len() > limit {
+                let mut regs = vec![0u8; m];
+                for &h in hashes.iter() {
+                    set_register(&mut regs, self.precision, h);
+                }
+                self.registers = Registers::Dense(regs);
+            }
+        }
+    }
+
+    /// Merge another sketch of the same precision
+    pub fn merge(&mut self, other: &HyperLogLog) -> Result<(), String> {
+        if other.precision != self.precision {
+            return Err(format!(
+                "Cannot merge sketches with precision {} and {}",
+                self.precision, other.precision
+            ));
+        }
+        match &other.registers {
+            Registers::Sparse(hashes) => {
+                for &h in hashes {
+                    self.add_hash(h);
+                }
+            }
+            Registers::Dense(theirs) => {
+                self.normalize();
+                if let Registers::Sparse(hashes) = &self.registers {
+                    let mut regs = vec![0u8; self.m()];
+                    for &h in hashes {
+                        set_register(&mut regs, self.precision, h);
+                    }
+                    self.registers = Registers::Dense(regs);
+                }
+                if let Registers::Dense(ours) = &mut self.registers {
+                    for (a, &b) in ours.iter_mut().zip(theirs) {
+                        *a = (*a).max(b);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Estimated number of distinct values
+    pub fn estimate(&mut self) -> f64 {
+        self.normalize();
+        match &self.registers {
+            Registers::Sparse(hashes) => hashes.len() as f64,
+            Registers::Dense(regs) => ertl_estimate(regs, self.precision),
+        }
+    }
+
+    /// Serialise as `HLL1`, precision (u8), mode (0 = sparse, 1 = dense) and
+    /// either a u32 hash count plus u64 hashes or the `2^precision` registers
+    pub fn to_bytes(&mut self) -> Vec<u8> {
+        self.normalize();
+        let mut out = MAGIC.to_vec();
+        out.push(self.precision);
+        match &self.registers {
+            Registers::Sparse(hashes) => {
+                out.push(0);
+                out.extend_from_slice(&(hashes.len() as u32).to_le_bytes());
+                for h in hashes {
+                    out.extend_from_slice(&h.to_le_bytes());
+                }
+            }
+            Registers::Dense(regs) => {
+                out.push(1);
+                out.extend_from_slice(regs);
+            }
+        }
+        out
+    }
+
+    /// Parse one sketch from the start of `bytes`; returns it with the number
+    /// of bytes consumed
+    pub fn from_bytes(bytes: &[u8]) -> Result<(HyperLogLog, usize), String> {
+        if bytes.get(..4) != Some(MAGIC.as_slice()) || bytes.len() < 6 {
+            return Err("Not a serialised HyperLogLog sketch".to_string());
+        }
+        let mut hll = HyperLogLog::new(bytes[4])?;
+        let truncated = || "Truncated HyperLogLog sketch".to_string();
+        match bytes[5] {
+            0 => {
+                let n = bytes
+                    .get(6..10)
+                    .map(|b| u32::from_le_bytes(b.try_into().expect("4 bytes")) as usize)
+                    .ok_or_else(truncated)?;
+                let end = n
+                    .checked_mul(8)
+                    .and_then(|b| b.checked_add(10))
+                    .ok_or_else(truncated)?;
+                let body = bytes.get(10..end).ok_or_else(truncated)?;
+                let hashes = body
+                    .chunks_exact(8)
+                    .map(|c| u64::from_le_bytes(c.try_into().expect("8 bytes")))
+                    .collect();
+                hll.registers = Registers::Sparse(hashes);
+                hll.normalize();
+                Ok((hll, end))
+            }
+            1 => {
+                let end = 6 + hll.m();
+                let regs = bytes.get(6..end).ok_or_else(truncated)?;
+                let max_rank = 65 - hll.precision;
+                if regs.iter().any(|&r| r > max_rank) {
+                    return Err("Invalid HyperLogLog register".to_string());
+                }
+                hll.registers = Registers::Dense(regs.to_vec());
+                Ok((hll, end))
+            }
+            mode => Err(format!("Unknown HyperLogLog mode {}", mode)),
+        }
+    }
+}
+
+/// Register index from the top `p` bits, rank from the remaining bits
+#[inline]
+fn set_register(regs: &mut [u8], p: u8, h: u64) {
+    let idx = (h >> (64 - p)) as usize;
+    let rest = h << p;
+    let rank = (rest.leading_zeros() as u8).min(64 - p) + 1;
+    if rank > regs[idx] {
+        regs[idx] = rank;
+    }
+}
+
+/// Ertl (2017), "New cardinality estimation algorithms for HyperLogLog
+/// sketches", improved raw estimator
+fn ertl_estimate(regs: &[u8], p: u8) -> f64 {
+    let q = 64 - p as usize;
+    let m = regs.len() as f64;
+    let mut counts = vec![0u32; q + 2];
+    for &r in regs {
+        counts[r as usize] += 1;
+    }
+    let mut z = m * tau(1.0 - counts[q + 1] as f64 / m);
+    for k in (1..=q).rev() {
+        z = 0.5 * (z + counts[k] as f64);
+    }
+    z += m * sigma(counts[0] as f64 / m);
+    let alpha_inf = 0.5 / std::f64::consts::LN_2;
+    alpha_inf * m * m / z
+}
+
+fn sigma(mut x: f64) -> f64 {
+    if x == 1.0 {
+        return f64::INFINITY;
+    }
+    let mut y = 1.0;
+    let mut z = x;
+    loop {
+        x *= x;
+        let z_old = z;
+        z += x * y;
+        y += y;
+        if z == z_old {
+            return z;
+        }
+    }
+}
+
+fn tau(mut x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        return 0.0;
+    }
+    let mut y = 1.0;
+    let mut z = 1.0 - x;
+    loop {
+        x = x.sqrt();
+        let z_old = z;
+        y *= 0.5;
+        z -= (1.0 - x).powi(2) * y;
+        if z == z_old {
+            return z / 3.0;
+        }
+    }
+}
+
+/// Add one hash per row to the sketch of its group, growing `sketches` to
+/// cover every group id seen. `hashes` yields `None` for missing rows.
+pub fn hll_update(
+    sketches: &mut Vec<HyperLogLog>,
+    precision: u8,
+    n_rows: usize,
+    gid_per_row: &[u32],
+    hashes: impl Iterator<Item = Option<u64>>,
+) -> Result<(), String> {
+    if !gid_per_row.is_empty() && gid_per_row.len() != n_rows {
+        return Err(format!(
+            "gid_per_row length mismatch: {} vs {}",
+            gid_per_row.len(),
+            n_rows
+        ));
+    }
+    let n_groups = gid_per_row.iter().max().map_or(1, |&g| g as usize + 1);
+    if sketches.len() < n_groups {
+        let empty = HyperLogLog::new(precision)?;
+        sketches.resize(n_groups, empty);
+    }
+    for (i, h) in hashes.enumerate() {
+        if let Some(h) = h {
+            let g = gid_per_row.get(i).map_or(0, |&g| g as usize);
+            sketches[g].add_hash(h);
+        }
+    }
+    Ok(())
+}
+
+fn check_valid(valid: &[u8], n: usize) -> Result<(), String> {
+    if !valid.is_empty() && valid.len() != n {
+        return Err(format!("valid length mismatch: {} vs {}", valid.len(), n));
+    }
+    Ok(())
+}
+
+/// Grouped update from an f64 column
+pub fn hll_update_f64(
+    sketches: &mut Vec<HyperLogLog>,
+    precision: u8,
+    vals: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+) -> Result<(), String> {
+    check_valid(valid, vals.len())?;
+    let hashes = vals.iter().enumerate().map(|(i, &x)| {
+        let present = if valid.is_empty() {
+            !x.is_nan()
+        } else {
+            valid[i] != 0
+        };
+        present.then(|| hash_f64(x))
+    });
+    hll_update(sketches, precision, vals.len(), gid_per_row, hashes)
+}
+
+/// Grouped update from a dictionary-code column
+pub fn hll_update_u32(
+    sketches: &mut Vec<HyperLogLog>,
+    precision: u8,
+    codes: &[u32],
+    valid: &[u8],
+    na_code: u32,
+    gid_per_row: &[u32],
+) -> Result<(), String> {
+    check_valid(valid, codes.len())?;
+    let hashes = codes.iter().enumerate().map(|(i, &c)| {
+        let present = c != na_code && (valid.is_empty() || valid[i] != 0);
+        present.then(|| hash_u32(c))
+    });
+    hll_update(sketches, precision, codes.len(), gid_per_row, hashes)
+}
+
+/// Grouped update from strings (`None` = missing)
+pub fn hll_update_str(
+    sketches: &mut Vec<HyperLogLog>,
+    precision: u8,
+    values: &[Option<&str>],
+    gid_per_row: &[u32],
+) -> Result<(), String> {
+    let hashes = values.iter().map(|s| s.map(hash_str));
+    hll_update(sketches, precision, values.len(), gid_per_row, hashes)
+}
+
+/// Grouped update from multi-column rows of codes (hashed with
+/// `hash_row_multi`, as the joins do)
+pub fn hll_update_rows(
+    sketches: &mut Vec<HyperLogLog>,
+    precision: u8,
+    cols: &[&[u32]],
+    gid_per_row: &[u32],
+) -> Result<(), String> {
+    let n = cols.first().map_or(0, |c| c.len());
+    if let Some(c) = cols.iter().find(|c| c.len() != n) {
+        return Err(format!("column length mismatch: {} vs {}", c.len(), n));
+    }
+    let hashes = (0..n).map(|i| Some(hash_row_multi(cols, i)));
+    hll_update(sketches, precision, n, gid_per_row, hashes)
+}
+
+/// Grouped HyperLogLog handle for JavaScript.
+///
+/// Holds one sketch per group (a single sketch when `gid_per_row` is always
+/// empty); [`DistinctSketch::estimates`] returns one estimate per group.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct DistinctSketch {
+    precision: u8,
+    sketches: Vec<HyperLogLog>,
+}
+
+#[cfg(feature = "wasm")]
+fn to_js(e: String) -> JsValue {
+    JsValue::from_str(&format!("Distinct sketch error: {}", e))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl DistinctSketch {
+    /// `precision` 0 selects the default (14); otherwise 4..=18
+    #[wasm_bindgen(constructor)]
+    pub fn new(precision: u8) -> Result<DistinctSketch, JsValue> {
+        let precision = if precision == 0 {
+            DEFAULT_PRECISION
+        } else {
+            precision
+        };
+        Ok(DistinctSketch {
+            precision,
+            sketches: vec![HyperLogLog::new(precision).map_err(to_js)?],
+        })
+    }
+
+    #[wasm_bindgen(js_name = nGroups)]
+    pub fn n_groups(&self) -> u32 {
+        self.sketches.len() as u32
+    }
+
+    #[wasm_bindgen(js_name = updateF64)]
+    pub fn update_f64(
+        &mut self,
+        vals: &[f64],
+        valid: &[u8],
+        gid_per_row: &[u32],
+    ) -> Result<(), JsValue> {
+        hll_update_f64(&mut self.sketches, self.precision, vals, valid, gid_per_row).map_err(to_js)
+    }
+
+    #[wasm_bindgen(js_name = updateU32)]
+    pub fn update_u32(
+        &mut self,
+        codes: &[u32],
+        valid: &[u8],
+        na_code: u32,
+        gid_per_row: &[u32],
+    ) -> Result<(), JsValue> {
+        hll_update_u32(
+            &mut self.sketches,
+            self.precision,
+            codes,
+            valid,
+            na_code,
+            gid_per_row,
+        )
+        .map_err(to_js)
+    }
+
+    /// Strings (`valid` marks missing entries)
+    #[wasm_bindgen(js_name = updateStr)]
+    pub fn update_str(
+        &mut self,
+        values: Vec<String>,
+        valid: &[u8],
+        gid_per_row: &[u32],
+    ) -> Result<(), JsValue> {
+        check_valid(valid, values.len()).map_err(to_js)?;
+        let refs: Vec<Option<&str>> = values
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (valid.is_empty() || valid[i] != 0).then_some(s.as_str()))
+            .collect();
+        hll_update_str(&mut self.sketches, self.precision, &refs, gid_per_row).map_err(to_js)
+    }
+
+    /// Rows of several code columns
+    #[wasm_bindgen(js_name = updateRows)]
+    pub fn update_rows(
+        &mut self,
+        column_data: Vec<Uint32Array>,
+        gid_per_row: &[u32],
+    ) -> Result<(), JsValue> {
+        let cols = bulk_copy_u32(&column_data);
+        let refs: Vec<&[u32]> = cols.iter().map(Vec::as_slice).collect();
+        hll_update_rows(&mut self.sketches, self.precision, &refs, gid_per_row).map_err(to_js)
+    }
+
+    /// Merge another sketch group by group
+    pub fn merge(&mut self, other: &DistinctSketch) -> Result<(), JsValue> {
+        if self.sketches.len() < other.sketches.len() {
+            let empty = HyperLogLog::new(self.precision).map_err(to_js)?;
+            self.sketches.resize(other.sketches.len(), empty);
+        }
+        for (s, o) in self.sketches.iter_mut().zip(&other.sketches) {
+            s.merge(o).map_err(to_js)?;
+        }
+        Ok(())
+    }
+
+    /// Estimated distinct count per group
+    pub fn estimates(&mut self) -> Vec<f64> {
+        self.sketches
+            .iter_mut()
+            .map(HyperLogLog::estimate)
+            .collect()
+    }
+
+    /// Serialise every group's sketch, prefixed by the group count (u32 LE)
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&mut self) -> Vec<u8> {
+        let mut out = (self.sketches.len() as u32).to_le_bytes().to_vec();
+        for s in &mut self.sketches {
+            out.extend(s.to_bytes());
+        }
+        out
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<DistinctSketch, JsValue> {
+        let n = bytes
+            .get(..4)
+            .map(|b| u32::from_le_bytes(b.try_into().expect("4 bytes")) as usize)
+            .ok_or_else(|| to_js("Truncated sketch".to_string()))?;
+        let mut pos = 4;
+        let mut sketches = Vec::with_capacity(n.min(bytes.len() / 6));
+        for _ in 0..n {
+            let (s, used) = HyperLogLog::from_bytes(&bytes[pos..]).map_err(to_js)?;
+            sketches.push(s);
+            pos += used;
+        }
+        let precision = sketches
+            .first()
+            .map_or(DEFAULT_PRECISION, HyperLogLog::precision);
+        if sketches.iter().any(|s| s.precision != precision) {
+            return Err(to_js("Groups have different precisions".to_string()));
+        }
+        if sketches.is_empty() {
+            sketches.push(HyperLogLog::new(precision).map_err(to_js)?);
+        }
+        Ok(DistinctSketch {
+            precision,
+            sketches,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sketch(precision: u8, values: impl Iterator<Item = u32>) -> HyperLogLog {
+        let mut hll = HyperLogLog::new(precision).unwrap();
+        for v in values {
+            hll.add_hash(hash_f64(v as f64));
+        }
+        hll
+    }
+
+    fn is_sparse(hll: &HyperLogLog) -> bool {
+        matches!(hll.registers, Registers::Sparse(_))
+    }
+
+    #[test]
+    fn test_sparse_counts_are_exact_until_dense() {
+        // Precision 14 keeps up to 2^14 / 8 = 2048 hashes
+        let mut hll = sketch(14, (0..6000).map(|i| i % 2048));
+        assert_eq!(hll.estimate(), 2048.0);
+        assert!(is_sparse(&hll));
+        hll.add_hash(hash_f64(-1.0));
+        let estimate = hll.estimate();
+        assert!(!is_sparse(&hll));
+        assert!(
+            (estimate / 2049.0 - 1.0).abs() < 3.0 * 1.04 / 128.0,
+            "{}",
+            estimate
+        );
+        // -0.0 and 0.0 are the same value
+        let mut zeros = HyperLogLog::new(4).unwrap();
+        zeros.add_hash(hash_f64(0.0));
+        zeros.add_hash(hash_f64(-0.0));
+        assert_eq!(zeros.estimate(), 1.0);
+        assert!(HyperLogLog::new(3).is_err() && HyperLogLog::new(19).is_err());
+    }
+
+    #[test]
+    fn test_estimate_error_is_within_standard_error() {
+        let n = 100_000;
+        for precision in [10, 14] {
+            let mut hll = sketch(precision, 0..n);
+            let rel = (hll.estimate() - n as f64).abs() / n as f64;
+            let se = 1.04 / ((1u32 << precision) as f64).sqrt();
+            assert!(rel < 3.0 * se, "precision {}: {} vs {}", precision, rel, se);
+        }
+    }
+
+    #[test]
+    fn test_merge_equals_union() {
+        // Dense with dense, sparse into dense and sparse with sparse
+        for (a, b, union) in [
+            (0..60_000, 40_000..100_000, 0..100_000),
+            (0..60_000, 59_000..60_500, 0..60_500),
+            (0..1_000, 500..1_500, 0..1_500),
+        ] {
+            let mut left = sketch(14, a.clone());
+            let right = sketch(14, b.clone());
+            left.merge(&right).unwrap();
+            let mut expected = sketch(14, union);
+            assert_eq!(left.estimate(), expected.estimate());
+            assert_eq!(left, expected);
+            // Merging the other way round gives the same registers
+            let mut flipped = sketch(14, b);
+            flipped.merge(&sketch(14, a)).unwrap();
+            flipped.normalize();
+            assert_eq!(flipped, left);
+        }
+        let mut small = sketch(14, 0..1_000);
+        small.merge(&sketch(14, 500..1_500)).unwrap();
+        assert_eq!(small.estimate(), 1_500.0);
+        assert!(is_sparse(&small));
+        assert!(small.merge(&sketch(13, 0..10)).is_err());
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        for n in [0, 100, 10_000] {
+            let mut hll = sketch(10, 0..n);
+            let bytes = hll.to_bytes();
+            let (back, used) = HyperLogLog::from_bytes(&bytes).unwrap();
+            assert_eq!((back, used), (hll.clone(), bytes.len()));
+            assert!(HyperLogLog::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+            // Trailing bytes are left for the next sketch
+            let mut two = bytes.clone();
+            two.extend_from_slice(&bytes);
+            assert_eq!(HyperLogLog::from_bytes(&two).unwrap().1, bytes.len());
+        }
+    }
+
+    #[test]
+    fn test_corrupt_bytes_are_errors() {
+        let mut dense = sketch(10, 0..10_000).to_bytes();
+        assert!(HyperLogLog::from_bytes(&dense[..dense.len() - 1]).is_err());
+        // Ranks go up to 65 - precision
+        dense[6] = 55;
+        assert!(HyperLogLog::from_bytes(&dense).is_ok());
+        dense[6] = 56;
+        assert!(HyperLogLog::from_bytes(&dense).is_err());
+
+        let sparse = sketch(10, 0..3).to_bytes();
+        assert!(HyperLogLog::from_bytes(&sparse[..sparse.len() - 1]).is_err());
+        // A hash count whose byte length would overflow
+        let mut huge = sparse[..6].to_vec();
+        huge.extend_from_slice(&u32::MAX.to_le_bytes());
+        assert!(HyperLogLog::from_bytes(&huge).is_err());
+
+        let mut bad_mode = sparse.clone();
+        bad_mode[5] = 2;
+        assert!(HyperLogLog::from_bytes(&bad_mode).is_err());
+        let mut bad_precision = sparse.clone();
+        bad_precision[4] = 30;
+        assert!(HyperLogLog::from_bytes(&bad_precision).is_err());
+        assert!(HyperLogLog::from_bytes(b"HLL2\n\0").is_err());
+        assert!(HyperLogLog::from_bytes(b"HLL1").is_err());
+    }
+
+    #[test]
+    fn test_grouped_row_updates() {
+        // (1, 2) and (2, 1) are different rows; group 2 is never seen
+        let a = [1, 2, 1, 1, 7, 7, 8];
+        let b = [2, 1, 2, 3, 7, 7, 9];
+        let gid = [0, 0, 0, 0, 1, 1, 3];
+        let mut sketches = Vec::new();
+        hll_update_rows(&mut sketches, 12, &[&a, &b], &gid).unwrap();
+        let estimates: Vec<f64> = sketches.iter_mut().map(|s| s.estimate()).collect();
+        assert_eq!(estimates, vec![3.0, 1.0, 0.0, 1.0]);
+
+        // A second batch adds to the existing sketches
+        hll_update_rows(&mut sketches, 12, &[&a[..2], &[5, 5]], &[1, 1]).unwrap();
+        assert_eq!(sketches[1].estimate(), 3.0);
+        assert!(hll_update_rows(&mut sketches, 12, &[&a, &b[..3]], &gid).is_err());
+        assert!(hll_update_rows(&mut sketches, 12, &[&a], &gid[..3]).is_err());
+    }
+}
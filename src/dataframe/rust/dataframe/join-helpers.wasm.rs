@@ -73,7 +73,6 @@ pub fn rows_equal_multi(left: &[&[u32]], right: &[&[u32]], li: usize, rj: usize)
 }
 
 // splitmix64-ish mixer for hash functions
-#[cfg(feature = "wasm")]
 #[inline]
 pub fn mix64(mut x: u64) -> u64 {
     x = x.wrapping_add(0x9E3779B97F4A7C15);
@@ -85,7 +84,6 @@ pub fn mix64(mut x: u64) -> u64 {
 }
 
 /// Hash multiple columns into a single u64
-#[cfg(feature = "wasm")]
 #[inline]
 pub fn hash_row_multi(cols: &[&[u32]], i: usize) -> u64 {
     let mut h = 0x9E3779B97F4A7C15u64;
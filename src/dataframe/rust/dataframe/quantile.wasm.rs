@@ -7,7 +7,7 @@ use super::shared_types::QuantileType;
 use wasm_bindgen::prelude::*;
 
 /// Calculate quantiles using R's algorithm
-fn quantile(data: &[f64], probs: &[f64], qtype: QuantileType) -> Result<Vec<f64>, String> {
+pub(crate) fn quantile(data: &[f64], probs: &[f64], qtype: QuantileType) -> Result<Vec<f64>, String> {
     if data.is_empty() {
         return Err("Cannot calculate quantiles of empty data".to_string());
     }
@@ -0,0 +1,667 @@
+//! Mergeable approximate quantile sketch (t-digest) WASM exports
+//!
+//! A merging t-digest (Dunning & Ertl) summarises a stream of values in a
+//! bounded number of weighted centroids. Sketches can be updated chunk by
+//! chunk, merged across chunks or workers and serialised, so quantiles of very
+//! large columns never require a full sort.
+//!
+//! Accuracy is controlled by `compression` (δ, default 100): the digest keeps
+//! at most about δ centroids, sized by the arcsine scale function so that
+//! error is smallest in the tails. Quantile error is typically well below
+//! 1% of rank at δ = 100 and shrinks roughly as 1/δ.
+//!
+//! Conventions shared by the grouped kernels in this module:
+//! - `valid`: 1 = value present, 0 = missing. An empty mask means "NaN is missing".
+//! - `weights`: one non-negative weight per row. Empty = every weight is 1.
+//! - `gid_per_row`: group id per row (from `group_ids_codes_all`). Empty = group 0.
+
+use std::f64::consts::PI;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Default compression (δ)
+pub const DEFAULT_COMPRESSION: f64 = 100.0;
+
+/// Range compression is clamped to; the upper bound keeps buffer and
+/// centroid allocations (a few times δ) bounded
+const COMPRESSION_RANGE: (f64, f64) = (10.0, 1e5);
+
+/// Magic prefix of serialised digests
+const MAGIC: &[u8; 4] = b"TDG1";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Merging t-digest
+#[derive(Debug, Clone, PartialEq)]
+pub struct TDigest {
+    compression: f64,
+    /// Merged centroids, sorted by mean
+    centroids: Vec<Centroid>,
+    /// Points added since the last compression
+    buffer: Vec<Centroid>,
+    total: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for TDigest {
+    fn default() -> Self {
+        TDigest::new(DEFAULT_COMPRESSION)
+    }
+}
+
+impl TDigest {
+    /// Empty digest; `compression` is clamped to [10, 1e5], and a non-finite
+    /// value selects the default
+    pub fn new(compression: f64) -> Self {
+        let compression = if compression.is_finite() {
+            compression.clamp(COMPRESSION_RANGE.0, COMPRESSION_RANGE.1)
+        } else {
+            DEFAULT_COMPRESSION
+        };
+        TDigest {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            total: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    pub fn compression(&self) -> f64 {
+        self.compression
+    }
+
+    /// Total weight added
+    pub fn count(&self) -> f64 {
+        self.total
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0.0
+    }
+
+    /// Smallest value added (NaN when empty)
+    pub fn min(&self) -> f64 {
+        if self.is_empty() { f64::NAN } else { self.min }
+    }
+
+    /// Largest value added (NaN when empty)
+    pub fn max(&self) -> f64 {
+        if self.is_empty() { f64::NAN } else { self.max }
+    }
+
+    pub fn add(&mut self, x: f64) {
+        self.add_weighted(x, 1.0);
+    }
+
+    /// Add `x` with weight `w`; NaN values and non-positive weights are ignored
+    pub fn add_weighted(&mut self, x: f64, w: f64) {
+        if x.is_nan() || w.is_nan() || w <= 0.0 || w.is_infinite() {
+            return;
+        }
+        self.buffer.push(Centroid { mean: x, weight: w });
+        self.total += w;
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+        if self.buffer.len() >= self.buffer_limit() {
+            self.compress();
+        }
+    }
+
+    /// Merge another digest into this one
+    pub fn merge(&mut self, other: &TDigest) {
+        if other.is_empty() {
+            return;
+        }
+        self.buffer.extend_from_slice(&other.centroids);
+        self.buffer.extend_from_slice(&other.buffer);
+        self.total += other.total;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.compress();
+    }
+
+    fn buffer_limit(&self) -> usize {
+        (self.compression * 5.0) as usize
+    }
+
+    /// Scale function k1: centroids may span one unit of k
+    fn k(&self, q: f64) -> f64 {
+        self.compression / (2.0 * PI) * (2.0 * q - 1.0).asin()
+    }
+
+    fn k_inv(&self, k: f64) -> f64 {
+        ((2.0 * PI * k / self.compression).sin() + 1.0) / 2.0
+    }
+
+    /// Fold the buffer into the centroids
+    pub fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let mut all = std::mem::take(&mut self.centroids);
+        all.append(&mut self.buffer);
+        all.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+
+        let total = self.total;
+        let mut merged: Vec<Centroid> = Vec::with_capacity(self.compression as usize * 2);
+        let mut iter = all.into_iter();
+        let Some(mut cur) = iter.next() else {
+            return;
+        };
+        let mut before = 0.0;
+        let mut q_limit = self.k_inv(self.k(0.0) + 1.0);
+        for next in iter {
+            let q = (before + cur.weight + next.weight) / total;
+            if q <= q_limit {
+                let w = cur.weight + next.weight;
+                cur.mean += (next.mean - cur.mean) * next.weight / w;
+                cur.weight = w;
+            } else {
+                before += cur.weight;
+                merged.push(cur);
+                q_limit = self.k_inv(self.k(before / total) + 1.0);
+                cur = next;
+            }
+        }
+        merged.push(cur);
+        self.centroids = merged;
+    }
+
+    /// Approximate quantile at probability `p` in [0, 1] (NaN when empty)
+    pub fn quantile(&mut self, p: f64) -> f64 {
+        self.compress();
+        let c = &self.centroids;
+        if c.is_empty() || !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+        if p == 0.0 {
+            return self.min;
+        }
+        if p == 1.0 {
+            return self.max;
+        }
+        if c.len() == 1 {
+            return c[0].mean;
+        }
+        let t = p * self.total;
+        let n = c.len();
+        // Below the first centroid's centre: interpolate from the minimum
+        let first_half = c[0].weight / 2.0;
+        if t < first_half {
+            return self.min + (c[0].mean - self.min) * t / first_half;
+        }
+        // Between consecutive centroid centres
+        let mut center = first_half;
+        for i in 0..n - 1 {
+            let next_center = center + (c[i].weight + c[i + 1].weight) / 2.0;
+            if t <= next_center {
+                let gamma = (t - center) / (next_center - center);
+                return c[i].mean + gamma * (c[i + 1].mean - c[i].mean);
+            }
+            center = next_center;
+        }
+        // Above the last centroid's centre: interpolate to the maximum
+        let last_half = c[n - 1].weight / 2.0;
+        let gamma = ((t - center) / last_half).min(1.0);
+        c[n - 1].mean + gamma * (self.max - c[n - 1].mean)
+    }
+
+    /// Approximate fraction of weight at or below `x` (NaN when empty)
+    pub fn cdf(&mut self, x: f64) -> f64 {
+        self.compress();
+        let c = &self.centroids;
+        if c.is_empty() || x.is_nan() {
+            return f64::NAN;
+        }
+        if x < self.min {
+            return 0.0;
+        }
+        if x >= self.max {
+            return 1.0;
+        }
+        let n = c.len();
+        let total = self.total;
+        if x < c[0].mean {
+            let span = c[0].mean - self.min;
+            let frac = if span > 0.0 {
+                (x - self.min) / span
+            } else {
+                1.0
+            };
+            return frac * c[0].weight / 2.0 / total;
+        }
+        let mut center = c[0].weight / 2.0;
+        for i in 0..n - 1 {
+            let next_center = center + (c[i].weight + c[i + 1].weight) / 2.0;
+            if x < c[i + 1].mean {
+                let span = c[i + 1].mean - c[i].mean;
+                let frac = if span > 0.0 {
+                    (x - c[i].mean) / span
+                } else {
+                    0.0
+                };
+                return (center + frac * (next_center - center)) / total;
+            }
+            center = next_center;
+        }
+        let span = self.max - c[n - 1].mean;
+        let frac = if span > 0.0 {
+            (x - c[n - 1].mean) / span
+        } else {
+            1.0
+        };
+        (center + frac * c[n - 1].weight / 2.0) / total
+    }
+
+    /// Serialise as `TDG1`, compression, min, max, centroid count (u32) and
+    /// `(mean, weight)` pairs, all little-endian
+    pub fn to_bytes(&mut self) -> Vec<u8> {
+        self.compress();
+        let mut out = Vec::with_capacity(32 + self.centroids.len() * 16);
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&self.compression.to_le_bytes());
+        out.extend_from_slice(&self.min.to_le_bytes());
+        out.extend_from_slice(&self.max.to_le_bytes());
+        out.extend_from_slice(&(self.centroids.len() as u32).to_le_bytes());
+        for c in &self.centroids {
+            out.extend_from_slice(&c.mean.to_le_bytes());
+            out.extend_from_slice(&c.weight.to_le_bytes());
+        }
+        out
+    }
+
+    /// Parse one digest from the start of `bytes`; returns it with the number
+    /// of bytes consumed
+    pub fn from_bytes(bytes: &[u8]) -> Result<(TDigest, usize), String> {
+        let f64_at = |pos: usize| -> Result<f64, String> {
+            bytes
+                .get(pos..pos + 8)
+                .map(|b| f64::from_le_bytes(b.try_into().expect("8 bytes")))
+                .ok_or_else(|| "Truncated t-digest".to_string())
+        };
+        if bytes.get(..4) != Some(MAGIC.as_slice()) {
+            return Err("Not a serialised t-digest".to_string());
+        }
+        let compression = f64_at(4)?;
+        if !compression.is_finite() {
+            return Err(format!("Invalid t-digest compression: {}", compression));
+        }
+        let mut digest = TDigest::new(compression);
+        digest.min = f64_at(12)?;
+        digest.max = f64_at(20)?;
+        let n = bytes
+            .get(28..32)
+            .map(|b| u32::from_le_bytes(b.try_into().expect("4 bytes")) as usize)
+            .ok_or("Truncated t-digest")?;
+        let mut pos = 32;
+        for _ in 0..n {
+            let (mean, weight) = (f64_at(pos)?, f64_at(pos + 8)?);
+            if weight.is_nan() || weight <= 0.0 || mean.is_nan() {
+                return Err("Invalid t-digest centroid".to_string());
+            }
+            digest.centroids.push(Centroid { mean, weight });
+            digest.total += weight;
+            pos += 16;
+        }
+        if digest.centroids.windows(2).any(|w| w[0].mean > w[1].mean) {
+            return Err("t-digest centroids are not sorted".to_string());
+        }
+        Ok((digest, pos))
+    }
+}
+
+/// Update one digest per group from a column.
+///
+/// `digests` is grown to cover every group id seen.
+pub fn tdigest_update(
+    digests: &mut Vec<TDigest>,
+    compression: f64,
+    vals: &[f64],
+    valid: &[u8],
+    weights: &[f64],
+    gid_per_row: &[u32],
+) -> Result<(), String> {
+    let n = vals.len();
+    for (name, len) in [
+        ("valid", valid.len()),
+        ("weights", weights.len()),
+        ("gid_per_row", gid_per_row.len()),
+    ] {
+        if len != 0 && len != n {
+            return Err(format!("{} length mismatch: {} vs {}", name, len, n));
+        }
+    }
+    if let Some(w) = weights.iter().find(|w| **w < 0.0 || w.is_infinite()) {
+        return Err(format!(
+            "weights must be finite and non-negative, got {}",
+            w
+        ));
+    }
+    let n_groups = gid_per_row.iter().max().map_or(1, |&g| g as usize + 1);
+    if digests.len() < n_groups {
+        digests.resize_with(n_groups, || TDigest::new(compression));
+    }
+    for i in 0..n {
+        if !valid.is_empty() && valid[i] == 0 {
+            continue;
+        }
+        let w = if weights.is_empty() { 1.0 } else { weights[i] };
+        let g = gid_per_row.get(i).map_or(0, |&g| g as usize);
+        digests[g].add_weighted(vals[i], w);
+    }
+    Ok(())
+}
+
+/// Grouped t-digest handle for JavaScript.
+///
+/// Holds one digest per group (a single digest when `gid_per_row` is always
+/// empty). Results of [`QuantileSketch::quantiles`] and [`QuantileSketch::cdf`]
+/// are row-major by group.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct QuantileSketch {
+    compression: f64,
+    digests: Vec<TDigest>,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl QuantileSketch {
+    /// `compression` ≤ 0 selects the default (100); others are clamped to
+    /// [10, 1e5]
+    #[wasm_bindgen(constructor)]
+    pub fn new(compression: f64) -> QuantileSketch {
+        let compression = if compression > 0.0 {
+            TDigest::new(compression).compression()
+        } else {
+            DEFAULT_COMPRESSION
+        };
+        QuantileSketch {
+            compression,
+            digests: vec![TDigest::new(compression)],
+        }
+    }
+
+    #[wasm_bindgen(js_name = nGroups)]
+    pub fn n_groups(&self) -> u32 {
+        self.digests.len() as u32
+    }
+
+    /// Add a chunk of values
+    pub fn update(
+        &mut self,
+        vals: &[f64],
+        valid: &[u8],
+        weights: &[f64],
+        gid_per_row: &[u32],
+    ) -> Result<(), JsValue> {
+        tdigest_update(
+            &mut self.digests,
+            self.compression,
+            vals,
+            valid,
+            weights,
+            gid_per_row,
+        )
+        .map_err(|e| JsValue::from_str(&format!("Quantile sketch error: {}", e)))
+    }
+
+    /// Merge another sketch group by group
+    pub fn merge(&mut self, other: &QuantileSketch) {
+        if self.digests.len() < other.digests.len() {
+            let compression = self.compression;
+            self.digests
+                .resize_with(other.digests.len(), || TDigest::new(compression));
+        }
+        for (d, o) in self.digests.iter_mut().zip(&other.digests) {
+            d.merge(o);
+        }
+    }
+
+    /// Approximate quantiles per group (`n_groups * probs.len()` values)
+    pub fn quantiles(&mut self, probs: &[f64]) -> Vec<f64> {
+        let mut out = Vec::with_capacity(self.digests.len() * probs.len());
+        for d in &mut self.digests {
+            out.extend(probs.iter().map(|&p| d.quantile(p)));
+        }
+        out
+    }
+
+    /// Approximate ECDF per group at `at` (`n_groups * at.len()` values)
+    pub fn cdf(&mut self, at: &[f64]) -> Vec<f64> {
+        let mut out = Vec::with_capacity(self.digests.len() * at.len());
+        for d in &mut self.digests {
+            out.extend(at.iter().map(|&x| d.cdf(x)));
+        }
+        out
+    }
+
+    /// Total weight per group
+    pub fn counts(&self) -> Vec<f64> {
+        self.digests.iter().map(TDigest::count).collect()
+    }
+
+    /// Serialise every group's digest, prefixed by the group count (u32 LE)
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&mut self) -> Vec<u8> {
+        let mut out = (self.digests.len() as u32).to_le_bytes().to_vec();
+        for d in &mut self.digests {
+            out.extend(d.to_bytes());
+        }
+        out
+    }
+
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<QuantileSketch, JsValue> {
+        let err = |e: String| JsValue::from_str(&format!("Quantile sketch error: {}", e));
+        let n = bytes
+            .get(..4)
+            .map(|b| u32::from_le_bytes(b.try_into().expect("4 bytes")) as usize)
+            .ok_or_else(|| err("Truncated sketch".to_string()))?;
+        let mut pos = 4;
+        let mut digests = Vec::with_capacity(n.min(bytes.len() / 32));
+        for _ in 0..n {
+            let (d, used) = TDigest::from_bytes(&bytes[pos..]).map_err(err)?;
+            digests.push(d);
+            pos += used;
+        }
+        let compression = digests
+            .first()
+            .map_or(DEFAULT_COMPRESSION, TDigest::compression);
+        if digests.is_empty() {
+            digests.push(TDigest::new(compression));
+        }
+        Ok(QuantileSketch {
+            compression,
+            digests,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::distributions::Open01;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    /// Skewed sample (exponential) and its sorted copy
+    fn sample(n: usize) -> (Vec<f64>, Vec<f64>) {
+        let mut rng = StdRng::seed_from_u64(7);
+        let vals: Vec<f64> = (0..n).map(|_| -rng.sample::<f64, _>(Open01).ln()).collect();
+        let mut sorted = vals.clone();
+        sorted.sort_by(f64::total_cmp);
+        (vals, sorted)
+    }
+
+    /// Distance in rank (as a fraction of n) between `q` and probability `p`
+    fn rank_error(sorted: &[f64], q: f64, p: f64) -> f64 {
+        let below = sorted.partition_point(|&x| x < q) as f64;
+        let at_or_below = sorted.partition_point(|&x| x <= q) as f64;
+        let n = sorted.len() as f64;
+        let target = p * n;
+        if (below..=at_or_below).contains(&target) {
+            0.0
+        } else {
+            (below - target).abs().min((at_or_below - target).abs()) / n
+        }
+    }
+
+    const PROBS: [f64; 9] = [0.001, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 0.999];
+
+    #[test]
+    fn test_quantile_accuracy() {
+        let (vals, sorted) = sample(100_000);
+        let mut digest = TDigest::new(100.0);
+        for &x in &vals {
+            digest.add(x);
+        }
+        for p in PROBS {
+            let err = rank_error(&sorted, digest.quantile(p), p);
+            assert!(err < 0.002, "p = {}: rank error {}", p, err);
+        }
+        assert!(digest.centroids.len() <= 100, "{}", digest.centroids.len());
+        assert_eq!(digest.count(), 100_000.0);
+        assert_eq!(
+            (digest.quantile(0.0), digest.quantile(1.0)),
+            (sorted[0], sorted[99_999])
+        );
+        assert!((digest.cdf(sorted[50_000]) - 0.5).abs() < 0.002);
+    }
+
+    #[test]
+    fn test_merged_chunks_match_single_stream() {
+        let (vals, sorted) = sample(100_000);
+        let mut single = TDigest::new(100.0);
+        let mut merged = TDigest::new(100.0);
+        for chunk in vals.chunks(7_000) {
+            let mut part = TDigest::new(100.0);
+            for &x in chunk {
+                part.add(x);
+                single.add(x);
+            }
+            merged.merge(&part);
+        }
+        assert_eq!(merged.count(), single.count());
+        assert_eq!((merged.min(), merged.max()), (single.min(), single.max()));
+        for p in PROBS {
+            let (a, b) = (merged.quantile(p), single.quantile(p));
+            assert!(rank_error(&sorted, a, p) < 0.003, "p = {}: {}", p, a);
+            let rank = |q| sorted.partition_point(|&x| x < q) as f64 / 1e5;
+            assert!(
+                (rank(a) - rank(b)).abs() < 0.003,
+                "p = {}: {} vs {}",
+                p,
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let (vals, _) = sample(10_000);
+        let mut digest = TDigest::new(50.0);
+        for &x in &vals {
+            digest.add(x);
+        }
+        let bytes = digest.to_bytes();
+        let (mut back, used) = TDigest::from_bytes(&bytes).unwrap();
+        assert_eq!(used, bytes.len());
+        assert_eq!(back.compression(), 50.0);
+        assert_eq!(back.to_bytes(), bytes);
+        for p in PROBS {
+            assert_eq!(back.quantile(p), digest.quantile(p));
+        }
+        // Digests can be concatenated
+        let two = [&bytes[..], &bytes].concat();
+        assert_eq!(TDigest::from_bytes(&two).unwrap().1, bytes.len());
+
+        assert!(TDigest::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(TDigest::from_bytes(&bytes[..20]).is_err());
+        assert!(TDigest::from_bytes(b"TDG2").is_err());
+        // Swap the first two centroid means
+        let mut unsorted = bytes.clone();
+        let (first, second) = unsorted[32..64].split_at_mut(16);
+        first[..8].swap_with_slice(&mut second[..8]);
+        assert!(TDigest::from_bytes(&unsorted).is_err());
+        let mut zero_weight = bytes.clone();
+        zero_weight[40..48].copy_from_slice(&0.0f64.to_le_bytes());
+        assert!(TDigest::from_bytes(&zero_weight).is_err());
+    }
+
+    #[test]
+    fn test_grouped_weighted_update() {
+        // Group 0: 0..1000, triple weight below 500, so its median is near
+        // 1000 / 3; group 1: 1000..2000 unweighted. Masked rows, NaNs and zero
+        // weights are skipped
+        let n = 2_000;
+        let vals: Vec<f64> = (0..n).map(|i| i as f64).collect();
+        let gid: Vec<u32> = (0..n).map(|i| (i >= 1_000) as u32).collect();
+        let mut weights: Vec<f64> = (0..n).map(|i| if i < 500 { 3.0 } else { 1.0 }).collect();
+        let mut valid = vec![1u8; n];
+        valid[1_999] = 0;
+        weights[1_998] = 0.0;
+        let mut digests = Vec::new();
+        // Two batches into the same digests
+        for range in [0..1_200, 1_200..n] {
+            let r = range.clone();
+            tdigest_update(
+                &mut digests,
+                100.0,
+                &vals[r.clone()],
+                &valid[r.clone()],
+                &weights[r.clone()],
+                &gid[r],
+            )
+            .unwrap();
+        }
+        assert_eq!(digests.len(), 2);
+        assert_eq!(digests[0].count(), 2_000.0);
+        assert_eq!(digests[1].count(), 998.0);
+        assert_eq!(digests[1].max(), 1_997.0);
+        assert!((digests[0].quantile(0.5) - 1_000.0 / 3.0).abs() < 5.0);
+        assert!((digests[0].quantile(0.9) - 800.0).abs() < 5.0);
+        assert!((digests[1].quantile(0.5) - 1_498.5).abs() < 5.0);
+
+        let mut fresh = Vec::new();
+        assert!(tdigest_update(&mut fresh, 100.0, &vals, &[], &[-1.0; 2_000], &[]).is_err());
+        assert!(tdigest_update(&mut fresh, 100.0, &vals, &[], &[], &gid[..10]).is_err());
+        assert!(tdigest_update(&mut fresh, 100.0, &vals, &valid[..10], &[], &[]).is_err());
+    }
+
+    fn serialised(compression: f64) -> Vec<u8> {
+        let mut bytes = TDigest::new(50.0).to_bytes();
+        bytes[4..12].copy_from_slice(&compression.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_compression_is_clamped() {
+        assert_eq!(TDigest::new(1.0).compression(), 10.0);
+        assert_eq!(TDigest::new(1e300).compression(), 1e5);
+        assert_eq!(TDigest::new(f64::NAN).compression(), DEFAULT_COMPRESSION);
+
+        // A huge serialised compression no longer overflows in `compress`
+        let (mut digest, _) = TDigest::from_bytes(&serialised(1e300)).unwrap();
+        assert_eq!(digest.compression(), 1e5);
+        for i in 0..1000 {
+            digest.add(i as f64);
+        }
+        assert!((digest.quantile(0.5) - 499.5).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_non_finite_compression_is_an_error() {
+        for c in [f64::INFINITY, f64::NAN, f64::NEG_INFINITY] {
+            assert!(TDigest::from_bytes(&serialised(c)).is_err());
+        }
+    }
+}
@@ -0,0 +1,1357 @@
+//! Date/time parsing and temporal kernels
+//!
+//! Temporal columns are represented in two physical forms:
+//! - datetimes as epoch milliseconds in `f64` (NaN = missing), matching `Date.getTime()`
+//! - dates as epoch days in `i32` (`NA_DAY` = missing)
+//!
+//! All calendar arithmetic is proleptic Gregorian in UTC; time zone offsets are
+//! only honoured while parsing. Epoch-ms columns can be passed straight to the
+//! `arrange` kernels, and `temporal_key_codes` turns them into order-preserving
+//! u32 codes for the grouping and join kernels.
+
+#![deny(unsafe_op_in_unsafe_fn)]
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+pub const MS_PER_SECOND: i64 = 1_000;
+pub const MS_PER_MINUTE: i64 = 60 * MS_PER_SECOND;
+pub const MS_PER_HOUR: i64 = 60 * MS_PER_MINUTE;
+pub const MS_PER_DAY: i64 = 24 * MS_PER_HOUR;
+pub const MS_PER_WEEK: i64 = 7 * MS_PER_DAY;
+
+/// Missing value sentinel for epoch-day columns
+pub const NA_DAY: i32 = i32::MIN;
+
+const MONTH_NAMES: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+const WEEKDAY_NAMES: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+// ----------------------------- Calendar -----------------------------
+
+/// Days since 1970-01-01 for a civil date (H. Hinnant's algorithm)
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Civil date (year, month, day) for days since 1970-01-01
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    (y, m, d)
+}
+
+pub fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        _ if is_leap_year(year) => 29,
+        _ => 28,
+    }
+}
+
+/// ISO weekday (1 = Monday .. 7 = Sunday) for days since 1970-01-01
+#[inline]
+pub fn weekday_from_days(days: i64) -> u32 {
+    ((days + 3).rem_euclid(7) + 1) as u32
+}
+
+#[inline]
+fn ms_to_i64(ms: f64) -> Option<i64> {
+    if ms.is_finite() {
+        Some(ms.floor() as i64)
+    } else {
+        None
+    }
+}
+
+/// Convert epoch milliseconds to epoch days (floor), `NA_DAY` when missing
+pub fn epoch_ms_to_days(ms: f64) -> i32 {
+    match ms_to_i64(ms) {
+        Some(t) => t.div_euclid(MS_PER_DAY) as i32,
+        None => NA_DAY,
+    }
+}
+
+/// Convert epoch days to epoch milliseconds (midnight UTC), NaN when missing
+pub fn epoch_days_to_ms(days: i32) -> f64 {
+    if days == NA_DAY {
+        f64::NAN
+    } else {
+        (days as i64 * MS_PER_DAY) as f64
+    }
+}
+
+/// Broken-down UTC datetime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTimeParts {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub millisecond: u32,
+}
+
+impl DateTimeParts {
+    pub fn from_epoch_ms(t: i64) -> Self {
+        let days = t.div_euclid(MS_PER_DAY);
+        let tod = t.rem_euclid(MS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+        DateTimeParts {
+            year,
+            month,
+            day,
+            hour: (tod / MS_PER_HOUR) as u32,
+            minute: (tod % MS_PER_HOUR / MS_PER_MINUTE) as u32,
+            second: (tod % MS_PER_MINUTE / MS_PER_SECOND) as u32,
+            millisecond: (tod % MS_PER_SECOND) as u32,
+        }
+    }
+
+    pub fn to_epoch_ms(&self) -> i64 {
+        days_from_civil(self.year, self.month, self.day) * MS_PER_DAY + self.time_of_day_ms()
+    }
+
+    /// `to_epoch_ms`, `None` when the instant is not representable
+    fn checked_epoch_ms(&self) -> Option<i64> {
+        days_from_civil(self.year, self.month, self.day)
+            .checked_mul(MS_PER_DAY)?
+            .checked_add(self.time_of_day_ms())
+    }
+
+    fn time_of_day_ms(&self) -> i64 {
+        self.hour as i64 * MS_PER_HOUR
+            + self.minute as i64 * MS_PER_MINUTE
+            + self.second as i64 * MS_PER_SECOND
+            + self.millisecond as i64
+    }
+
+    fn is_valid(&self) -> bool {
+        (1..=12).contains(&self.month)
+            && self.day >= 1
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+            && self.millisecond < 1000
+    }
+}
+
+// ----------------------------- Parsing -----------------------------
+
+struct Cursor<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(s: &'a str) -> Self {
+        Cursor {
+            s: s.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_ws(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.s.len()
+    }
+
+    /// Read between `min` and `max` ASCII digits
+    fn digits(&mut self, min: usize, max: usize) -> Option<i64> {
+        let start = self.pos;
+        let mut v: i64 = 0;
+        while self.pos - start < max {
+            match self.peek() {
+                Some(c) if c.is_ascii_digit() => {
+                    v = v * 10 + (c - b'0') as i64;
+                    self.pos += 1;
+                }
+                _ => break,
+            }
+        }
+        if self.pos - start < min {
+            self.pos = start;
+            return None;
+        }
+        Some(v)
+    }
+
+    /// Fractional digits after a '.' or ',' converted to milliseconds
+    fn fraction_ms(&mut self) -> Option<u32> {
+        if !(self.eat(b'.') || self.eat(b',')) {
+            return Some(0);
+        }
+        let start = self.pos;
+        let mut ms = 0u32;
+        while let Some(c) = self.peek().filter(u8::is_ascii_digit) {
+            if self.pos - start < 3 {
+                ms = ms * 10 + (c - b'0') as u32;
+            }
+            self.pos += 1;
+        }
+        match self.pos - start {
+            0 => None,
+            1 => Some(ms * 100),
+            2 => Some(ms * 10),
+            _ => Some(ms),
+        }
+    }
+
+    /// Time zone designator: `Z`, `±HH`, `±HHMM` or `±HH:MM`; returns offset in ms
+    fn zone_offset(&mut self) -> Option<i64> {
+        if self.eat(b'Z') || self.eat(b'z') {
+            return Some(0);
+        }
+        let sign = if self.eat(b'+') {
+            1
+        } else if self.eat(b'-') {
+            -1
+        } else {
+            return None;
+        };
+        let hh = self.digits(2, 2)?;
+        self.eat(b':');
+        let mm = self.digits(2, 2).unwrap_or(0);
+        if hh > 23 || mm > 59 {
+            return None;
+        }
+        Some(sign * (hh * MS_PER_HOUR + mm * MS_PER_MINUTE))
+    }
+
+    /// Case-insensitive match of an English name (full or 3-letter abbreviation)
+    fn name(&mut self, names: &[&str]) -> Option<usize> {
+        let rest = &self.s[self.pos..];
+        for (i, name) in names.iter().enumerate() {
+            let full = name.as_bytes();
+            if rest.len() >= full.len() && rest[..full.len()].eq_ignore_ascii_case(full) {
+                self.pos += full.len();
+                return Some(i);
+            }
+        }
+        for (i, name) in names.iter().enumerate() {
+            let abbr = &name.as_bytes()[..3];
+            if rest.len() >= 3 && rest[..3].eq_ignore_ascii_case(abbr) {
+                self.pos += 3;
+                return Some(i);
+            }
+        }
+        None
+    }
+}
+
+/// Parse an ISO-8601 date or datetime into epoch milliseconds.
+///
+/// Accepts `YYYY-MM-DD` optionally followed by `T` or a space and
+/// `HH:MM[:SS[.fff]]`, then an optional zone (`Z`, `±HH`, `±HH:MM`, `±HHMM`).
+/// Datetimes without a zone are interpreted as UTC.
+pub fn parse_iso8601(s: &str) -> Option<i64> {
+    let mut c = Cursor::new(s.trim());
+    let mut p = DateTimeParts {
+        year: c.digits(4, 4)?,
+        month: 1,
+        day: 1,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        millisecond: 0,
+    };
+    if !c.eat(b'-') {
+        return None;
+    }
+    p.month = c.digits(2, 2)? as u32;
+    if !c.eat(b'-') {
+        return None;
+    }
+    p.day = c.digits(2, 2)? as u32;
+
+    let mut offset = 0;
+    if c.eat(b'T') || c.eat(b't') || c.eat(b' ') {
+        p.hour = c.digits(2, 2)? as u32;
+        if !c.eat(b':') {
+            return None;
+        }
+        p.minute = c.digits(2, 2)? as u32;
+        if c.eat(b':') {
+            p.second = c.digits(2, 2)? as u32;
+            p.millisecond = c.fraction_ms()?;
+        }
+        if !c.at_end() {
+            offset = c.zone_offset()?;
+        }
+    }
+    if !c.at_end() || !p.is_valid() {
+        return None;
+    }
+    Some(p.to_epoch_ms() - offset)
+}
+
+/// Parse a string with a strftime-style format into epoch milliseconds.
+///
+/// Supported directives: `%Y %y %m %d %e %H %I %M %S %OS %j %b %B %h %a %A %p
+/// %z %F %T %D %%`. Whitespace in the format matches any run of whitespace
+/// (including none). Two-digit years follow POSIX: 69-99 -> 19xx, 00-68 -> 20xx.
+pub fn parse_strftime(s: &str, format: &str) -> Option<i64> {
+    let mut c = Cursor::new(s.trim());
+    let mut p = DateTimeParts {
+        year: 1970,
+        month: 1,
+        day: 1,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        millisecond: 0,
+    };
+    let mut yday: Option<i64> = None;
+    let mut pm: Option<bool> = None;
+    let mut offset = 0i64;
+
+    let fmt = expand_format(format);
+    let mut f = fmt.chars().peekable();
+    while let Some(ch) = f.next() {
+        if ch.is_whitespace() {
+            c.skip_ws();
+            continue;
+        }
+        if ch != '%' {
+            let mut buf = [0u8; 4];
+            for &b in ch.encode_utf8(&mut buf).as_bytes() {
+                if !c.eat(b) {
+                    return None;
+                }
+            }
+            continue;
+        }
+        match f.next()? {
+            'Y' => {
+                let neg = c.eat(b'-');
+                let y = c.digits(1, 4)?;
+                p.year = if neg { -y } else { y };
+            }
+            'y' => {
+                let y = c.digits(1, 2)?;
+                p.year = if y >= 69 { 1900 + y } else { 2000 + y };
+            }
+            'm' => p.month = c.digits(1, 2)? as u32,
+            'd' => p.day = c.digits(1, 2)? as u32,
+            'e' => {
+                c.skip_ws();
+                p.day = c.digits(1, 2)? as u32;
+            }
+            'H' => p.hour = c.digits(1, 2)? as u32,
+            'I' => p.hour = c.digits(1, 2)? as u32 % 12,
+            'M' => p.minute = c.digits(1, 2)? as u32,
+            'S' => p.second = c.digits(1, 2)? as u32,
+            'O' => {
+                if f.next()? != 'S' {
+                    return None;
+                }
+                p.second = c.digits(1, 2)? as u32;
+                p.millisecond = c.fraction_ms()?;
+            }
+            'j' => yday = Some(c.digits(1, 3)?),
+            'b' | 'B' | 'h' => p.month = c.name(&MONTH_NAMES)? as u32 + 1,
+            'a' | 'A' => {
+                c.name(&WEEKDAY_NAMES)?;
+            }
+            'p' => {
+                let rest = &c.s[c.pos..];
+                if rest.len() < 2 {
+                    return None;
+                }
+                pm = match &rest[..2] {
+                    b if b.eq_ignore_ascii_case(b"am") => Some(false),
+                    b if b.eq_ignore_ascii_case(b"pm") => Some(true),
+                    _ => return None,
+                };
+                c.pos += 2;
+            }
+            'z' => offset = c.zone_offset()?,
+            '%' => {
+                if !c.eat(b'%') {
+                    return None;
+                }
+            }
+            _ => return None,
+        }
+    }
+    c.skip_ws();
+    if !c.at_end() {
+        return None;
+    }
+
+    if let Some(is_pm) = pm {
+        if is_pm {
+            p.hour = p.hour % 12 + 12;
+        } else {
+            p.hour %= 12;
+        }
+    }
+    if let Some(j) = yday {
+        let len = if is_leap_year(p.year) { 366 } else { 365 };
+        if j < 1 || j > len {
+            return None;
+        }
+        let (_, m, d) = civil_from_days(days_from_civil(p.year, 1, 1) + j - 1);
+        p.month = m;
+        p.day = d;
+    }
+    if !p.is_valid() {
+        return None;
+    }
+    Some(p.to_epoch_ms() - offset)
+}
+
+/// Expand composite directives (`%F`, `%T`, `%D`, `%R`) into their components
+fn expand_format(format: &str) -> String {
+    format
+        .replace("%F", "%Y-%m-%d")
+        .replace("%T", "%H:%M:%S")
+        .replace("%D", "%m/%d/%y")
+        .replace("%R", "%H:%M")
+}
+
+/// Parse with `format`, or as ISO-8601 when `format` is empty
+pub fn parse_datetime(s: &str, format: &str) -> Option<i64> {
+    if format.is_empty() {
+        parse_iso8601(s)
+    } else {
+        parse_strftime(s, format)
+    }
+}
+
+/// Format epoch milliseconds with a strftime-style format.
+///
+/// Supports the same directives as [`parse_strftime`] (`%OS` writes three
+/// decimals); `%z` always writes `+0000`.
+pub fn format_strftime(t: i64, format: &str) -> String {
+    let p = DateTimeParts::from_epoch_ms(t);
+    let days = t.div_euclid(MS_PER_DAY);
+    let capitalize = |s: &str| {
+        let mut out = s[..1].to_ascii_uppercase();
+        out.push_str(&s[1..]);
+        out
+    };
+
+    let fmt = expand_format(format);
+    let mut out = String::with_capacity(fmt.len() + 8);
+    let mut f = fmt.chars();
+    while let Some(ch) = f.next() {
+        if ch != '%' {
+            out.push(ch);
+            continue;
+        }
+        match f.next() {
+            Some('Y') => out.push_str(&format!("{:04}", p.year)),
+            Some('y') => out.push_str(&format!("{:02}", p.year.rem_euclid(100))),
+            Some('m') => out.push_str(&format!("{:02}", p.month)),
+            Some('d') => out.push_str(&format!("{:02}", p.day)),
+            Some('e') => out.push_str(&format!("{:>2}", p.day)),
+            Some('H') => out.push_str(&format!("{:02}", p.hour)),
+            Some('I') => out.push_str(&format!("{:02}", (p.hour + 11) % 12 + 1)),
+            Some('M') => out.push_str(&format!("{:02}", p.minute)),
+            Some('S') => out.push_str(&format!("{:02}", p.second)),
+            Some('O') => {
+                f.next();
+                out.push_str(&format!("{:02}.{:03}", p.second, p.millisecond));
+            }
+            Some('j') => {
+                let j = days - days_from_civil(p.year, 1, 1) + 1;
+                out.push_str(&format!("{:03}", j));
+            }
+            Some('b') | Some('h') => {
+                out.push_str(&capitalize(&MONTH_NAMES[p.month as usize - 1][..3]))
+            }
+            Some('B') => out.push_str(&capitalize(MONTH_NAMES[p.month as usize - 1])),
+            Some('a') => out.push_str(&capitalize(
+                &WEEKDAY_NAMES[weekday_from_days(days) as usize - 1][..3],
+            )),
+            Some('A') => out.push_str(&capitalize(
+                WEEKDAY_NAMES[weekday_from_days(days) as usize - 1],
+            )),
+            Some('p') => out.push_str(if p.hour < 12 { "AM" } else { "PM" }),
+            Some('z') => out.push_str("+0000"),
+            Some('%') => out.push('%'),
+            Some(other) => {
+                out.push('%');
+                out.push(other);
+            }
+            None => out.push('%'),
+        }
+    }
+    out
+}
+
+/// Format epoch milliseconds as ISO-8601 (`YYYY-MM-DDTHH:MM:SS.sssZ`, like `Date.toISOString`)
+pub fn format_iso8601(t: i64) -> String {
+    format_strftime(t, "%Y-%m-%dT%H:%M:%OSZ")
+}
+
+// ----------------------------- Units and periods -----------------------------
+
+/// Calendar/clock unit
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TimeUnit {
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl TimeUnit {
+    /// Fixed length in ms, `None` for calendar units (month, quarter, year)
+    pub fn fixed_ms(self) -> Option<i64> {
+        match self {
+            TimeUnit::Millisecond => Some(1),
+            TimeUnit::Second => Some(MS_PER_SECOND),
+            TimeUnit::Minute => Some(MS_PER_MINUTE),
+            TimeUnit::Hour => Some(MS_PER_HOUR),
+            TimeUnit::Day => Some(MS_PER_DAY),
+            TimeUnit::Week => Some(MS_PER_WEEK),
+            TimeUnit::Month | TimeUnit::Quarter | TimeUnit::Year => None,
+        }
+    }
+
+    /// Length in months for calendar units
+    fn months(self) -> Option<i64> {
+        match self {
+            TimeUnit::Month => Some(1),
+            TimeUnit::Quarter => Some(3),
+            TimeUnit::Year => Some(12),
+            _ => None,
+        }
+    }
+
+    /// Parse a unit name or abbreviation (case-insensitive, except that a bare
+    /// `M` means month as in the TypeScript `Frequency` strings)
+    pub fn parse(s: &str) -> Option<TimeUnit> {
+        if s.trim() == "M" {
+            return Some(TimeUnit::Month);
+        }
+        let unit = match s.trim().to_ascii_lowercase().as_str() {
+            "ms" | "millisecond" | "milliseconds" => TimeUnit::Millisecond,
+            "s" | "sec" | "secs" | "second" | "seconds" => TimeUnit::Second,
+            "min" | "mins" | "minute" | "minutes" => TimeUnit::Minute,
+            "h" | "hr" | "hrs" | "hour" | "hours" => TimeUnit::Hour,
+            "d" | "day" | "days" => TimeUnit::Day,
+            "w" | "wk" | "week" | "weeks" => TimeUnit::Week,
+            "mo" | "mon" | "month" | "months" => TimeUnit::Month,
+            "q" | "quarter" | "quarters" => TimeUnit::Quarter,
+            "y" | "yr" | "year" | "years" => TimeUnit::Year,
+            _ => return None,
+        };
+        Some(unit)
+    }
+}
+
+/// A multiple of a unit, e.g. "1h", "7d", "15 mins", "2 months"
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Period {
+    pub n: i64,
+    pub unit: TimeUnit,
+}
+
+impl Period {
+    pub fn new(n: i64, unit: TimeUnit) -> Self {
+        Period { n, unit }
+    }
+
+    /// Parse "<count><unit>" or "<count> <unit>"; a missing count means 1
+    pub fn parse(s: &str) -> Result<Period, String> {
+        let s = s.trim();
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let n = if split == 0 {
+            1
+        } else {
+            s[..split]
+                .parse::<i64>()
+                .map_err(|_| format!("Invalid period count in '{}'", s))?
+        };
+        let unit = TimeUnit::parse(&s[split..])
+            .ok_or_else(|| format!("Invalid period unit in '{}'", s))?;
+        if n <= 0 {
+            return Err(format!("Period count must be positive in '{}'", s));
+        }
+        Ok(Period { n, unit })
+    }
+
+    /// Fixed length in ms, `None` for calendar periods or when the length
+    /// overflows
+    pub fn fixed_ms(&self) -> Option<i64> {
+        self.unit.fixed_ms()?.checked_mul(self.n)
+    }
+}
+
+/// Add `k` months to epoch-ms `t`, clamping the day to the end of the target
+/// month; `None` when the result is not representable in epoch-ms
+pub fn add_months(t: i64, k: i64) -> Option<i64> {
+    // Epoch-ms spans under 600 million years; larger offsets cannot land in range
+    if k.unsigned_abs() > 12 * 600_000_000 {
+        return None;
+    }
+    let mut p = DateTimeParts::from_epoch_ms(t);
+    let total = p.year * 12 + (p.month as i64 - 1) + k;
+    p.year = total.div_euclid(12);
+    p.month = (total.rem_euclid(12) + 1) as u32;
+    p.day = p.day.min(days_in_month(p.year, p.month));
+    p.checked_epoch_ms()
+}
+
+/// Add `k` periods to epoch-ms `t` (calendar periods clamp to month end);
+/// `None` when the result is not representable in epoch-ms
+pub fn add_period(t: i64, period: Period, k: i64) -> Option<i64> {
+    match period.unit.months() {
+        None => t.checked_add(period.fixed_ms()?.checked_mul(k)?),
+        Some(months) => add_months(t, period.n.checked_mul(months)?.checked_mul(k)?),
+    }
+}
+
+/// Largest multiple of `step` not above `t`, `None` on overflow
+fn floor_multiple(t: i64, step: i64) -> Option<i64> {
+    t.div_euclid(step).checked_mul(step)
+}
+
+/// Round epoch-ms `t` down to the start of its `period`.
+///
+/// Sub-day periods are aligned to midnight, multi-day periods to 1970-01-01,
+/// weeks to `week_start` (1 = Monday .. 7 = Sunday), months and quarters to
+/// multiples within the year and years to multiples of `n`. Returns `None`
+/// when the boundary is not representable in epoch-ms.
+pub fn floor_date(t: i64, period: Period, week_start: u32) -> Option<i64> {
+    let n = period.n;
+    match period.unit {
+        TimeUnit::Millisecond | TimeUnit::Second | TimeUnit::Minute | TimeUnit::Hour => {
+            let step = period.unit.fixed_ms()?.checked_mul(n)?;
+            let day = floor_multiple(t, MS_PER_DAY)?;
+            day.checked_add((t - day).div_euclid(step) * step)
+        }
+        TimeUnit::Day => floor_multiple(t, MS_PER_DAY.checked_mul(n)?),
+        TimeUnit::Week => {
+            // 1970-01-01 is a Thursday (ISO 4); shift so weeks begin on `week_start`
+            let shift = (4 - week_start.clamp(1, 7) as i64).rem_euclid(7) * MS_PER_DAY;
+            let step = MS_PER_WEEK.checked_mul(n)?;
+            floor_multiple(t.checked_add(shift)?, step)?.checked_sub(shift)
+        }
+        TimeUnit::Month | TimeUnit::Quarter => {
+            let p = DateTimeParts::from_epoch_ms(t);
+            let step = n.checked_mul(period.unit.months().unwrap_or(1))?;
+            let m0 = (p.month as i64 - 1).div_euclid(step) * step;
+            let total = p.year * 12 + m0;
+            days_from_civil(total.div_euclid(12), (total.rem_euclid(12) + 1) as u32, 1)
+                .checked_mul(MS_PER_DAY)
+        }
+        TimeUnit::Year => {
+            let p = DateTimeParts::from_epoch_ms(t);
+            days_from_civil(p.year.div_euclid(n) * n, 1, 1).checked_mul(MS_PER_DAY)
+        }
+    }
+}
+
+/// Round epoch-ms `t` up to the next `period` boundary (values on a boundary
+/// are unchanged); `None` when the boundary is not representable
+pub fn ceiling_date(t: i64, period: Period, week_start: u32) -> Option<i64> {
+    let f = floor_date(t, period, week_start)?;
+    if f == t {
+        return Some(t);
+    }
+    floor_date(add_period(f, period, 1)?, period, week_start)
+}
+
+/// Round epoch-ms `t` to the nearer `period` boundary; halfway values round up
+/// (lubridate's `round_date`)
+pub fn round_date(t: i64, period: Period, week_start: u32) -> Option<i64> {
+    let f = floor_date(t, period, week_start)?;
+    let c = ceiling_date(t, period, week_start)?;
+    Some(if t - f < c - t { f } else { c })
+}
+
+/// Difference `end - start` expressed in `unit`.
+///
+/// Fixed units return fractional values; months, quarters and years return
+/// the number of whole calendar units elapsed (truncated toward zero).
+pub fn date_diff(start: i64, end: i64, unit: TimeUnit) -> f64 {
+    if let Some(ms) = unit.fixed_ms() {
+        // In f64: saturated inputs near i64::MIN/MAX would overflow `end - start`
+        return (end as f64 - start as f64) / ms as f64;
+    }
+    let (a, b, sign) = if end >= start {
+        (start, end, 1)
+    } else {
+        (end, start, -1)
+    };
+    let pa = DateTimeParts::from_epoch_ms(a);
+    let pb = DateTimeParts::from_epoch_ms(b);
+    let mut months = (pb.year - pa.year) * 12 + (pb.month as i64 - pa.month as i64);
+    if months > 0 && add_months(a, months).is_none_or(|m| m > b) {
+        months -= 1;
+    }
+    let per = unit.months().unwrap_or(1);
+    (sign * (months / per)) as f64
+}
+
+/// Extractable date/time component
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DatePart {
+    Year,
+    Quarter,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+    /// ISO weekday, 1 = Monday .. 7 = Sunday
+    Weekday,
+    DayOfYear,
+    IsoWeek,
+    IsoYear,
+}
+
+impl DatePart {
+    pub fn from_code(code: u8) -> Option<DatePart> {
+        let part = match code {
+            0 => DatePart::Year,
+            1 => DatePart::Quarter,
+            2 => DatePart::Month,
+            3 => DatePart::Day,
+            4 => DatePart::Hour,
+            5 => DatePart::Minute,
+            6 => DatePart::Second,
+            7 => DatePart::Millisecond,
+            8 => DatePart::Weekday,
+            9 => DatePart::DayOfYear,
+            10 => DatePart::IsoWeek,
+            11 => DatePart::IsoYear,
+            _ => return None,
+        };
+        Some(part)
+    }
+}
+
+/// Extract a component from epoch-ms `t`
+pub fn date_part(t: i64, part: DatePart) -> i64 {
+    let days = t.div_euclid(MS_PER_DAY);
+    let p = DateTimeParts::from_epoch_ms(t);
+    match part {
+        DatePart::Year => p.year,
+        DatePart::Quarter => (p.month as i64 - 1) / 3 + 1,
+        DatePart::Month => p.month as i64,
+        DatePart::Day => p.day as i64,
+        DatePart::Hour => p.hour as i64,
+        DatePart::Minute => p.minute as i64,
+        DatePart::Second => p.second as i64,
+        DatePart::Millisecond => p.millisecond as i64,
+        DatePart::Weekday => weekday_from_days(days) as i64,
+        DatePart::DayOfYear => days - days_from_civil(p.year, 1, 1) + 1,
+        DatePart::IsoWeek | DatePart::IsoYear => {
+            // The ISO week belongs to the year containing its Thursday
+            let thursday = days + 4 - weekday_from_days(days) as i64;
+            let (iso_year, _, _) = civil_from_days(thursday);
+            if part == DatePart::IsoYear {
+                iso_year
+            } else {
+                (thursday - days_from_civil(iso_year, 1, 1)) / 7 + 1
+            }
+        }
+    }
+}
+
+// ----------------------------- Business days -----------------------------
+
+/// Business-day calendar: Monday-Friday minus a sorted, de-duplicated holiday list (epoch days)
+pub struct BusinessCalendar {
+    holidays: Vec<i64>,
+}
+
+impl BusinessCalendar {
+    pub fn new(holidays: &[i32]) -> Self {
+        let mut h: Vec<i64> = holidays
+            .iter()
+            .filter(|&&d| d != NA_DAY && weekday_from_days(d as i64) <= 5)
+            .map(|&d| d as i64)
+            .collect();
+        h.sort_unstable();
+        h.dedup();
+        BusinessCalendar { holidays: h }
+    }
+
+    pub fn is_business_day(&self, day: i64) -> bool {
+        weekday_from_days(day) <= 5 && self.holidays.binary_search(&day).is_err()
+    }
+
+    /// Weekdays in [0, day) relative to epoch, used for O(log h) counting
+    fn weekdays_before(day: i64) -> i64 {
+        // Shift so that index 0 is a Monday (1970-01-05 = day 4)
+        let d = day - 4;
+        let weeks = d.div_euclid(7);
+        let rem = d.rem_euclid(7);
+        weeks * 5 + rem.min(5)
+    }
+
+    /// Number of business days in [start, end); negative when end < start
+    pub fn count_between(&self, start: i64, end: i64) -> i64 {
+        if end < start {
+            return -self.count_between(end, start);
+        }
+        let weekdays = Self::weekdays_before(end) - Self::weekdays_before(start);
+        let lo = self.holidays.partition_point(|&h| h < start);
+        let hi = self.holidays.partition_point(|&h| h < end);
+        weekdays - (hi - lo) as i64
+    }
+
+    /// Offset `day` by `n` business days. Non-business start days are first
+    /// rolled forward (n >= 0) or backward (n < 0) to a business day.
+    pub fn add(&self, day: i64, n: i64) -> i64 {
+        let step = if n >= 0 { 1 } else { -1 };
+        let mut d = day;
+        while !self.is_business_day(d) {
+            d += step;
+        }
+        let mut remaining = n.abs();
+        // Jump whole weeks when no holidays can interfere, then walk the rest
+        while remaining > 0 {
+            if remaining > 5 {
+                let weeks = remaining / 5 - 1;
+                let target = d + step * weeks * 7;
+                let (lo, hi) = if step > 0 {
+                    (d + 1, target + 1)
+                } else {
+                    (target, d)
+                };
+                let a = self.holidays.partition_point(|&h| h < lo);
+                let b = self.holidays.partition_point(|&h| h < hi);
+                if weeks > 0 && a == b {
+                    d = target;
+                    remaining -= weeks * 5;
+                    continue;
+                }
+            }
+            d += step;
+            if self.is_business_day(d) {
+                remaining -= 1;
+            }
+        }
+        d
+    }
+}
+
+// ----------------------------- Keys -----------------------------
+
+/// Order-preserving u32 codes for an epoch-ms column.
+///
+/// Returns (codes, uniques) where `uniques` is sorted ascending and
+/// `codes[i]` indexes into it; missing values get code `uniques.len()`
+/// (sorts last). To join two tables, encode the concatenation of both key
+/// columns so codes agree across sides.
+pub fn temporal_key_codes(values: &[f64]) -> (Vec<u32>, Vec<f64>) {
+    let mut uniques: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+    uniques.sort_unstable_by(|a, b| a.total_cmp(b));
+    uniques.dedup();
+    let na = uniques.len() as u32;
+    let codes = values
+        .iter()
+        .map(|v| {
+            if v.is_nan() {
+                na
+            } else {
+                uniques.partition_point(|u| u < v) as u32
+            }
+        })
+        .collect();
+    (codes, uniques)
+}
+
+// ----------------------------- WASM exports -----------------------------
+
+/// Parse strings into epoch milliseconds (NaN when unparseable).
+/// An empty `format` parses ISO-8601; otherwise strftime directives are used.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn parse_datetime_wasm(values: Vec<String>, format: &str) -> Vec<f64> {
+    values
+        .iter()
+        .map(|s| parse_datetime(s, format).map_or(f64::NAN, |t| t as f64))
+        .collect()
+}
+
+/// Parse strings into epoch days (`NA_DAY` when unparseable); time of day is truncated
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn parse_date_days_wasm(values: Vec<String>, format: &str) -> Vec<i32> {
+    values
+        .iter()
+        .map(|s| parse_datetime(s, format).map_or(NA_DAY, |t| t.div_euclid(MS_PER_DAY) as i32))
+        .collect()
+}
+
+/// Format epoch milliseconds (empty `format` = ISO-8601); missing values become ""
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn format_datetime_wasm(values: &[f64], format: &str) -> Vec<String> {
+    values
+        .iter()
+        .map(|&v| match ms_to_i64(v) {
+            Some(t) if format.is_empty() => format_iso8601(t),
+            Some(t) => format_strftime(t, format),
+            None => String::new(),
+        })
+        .collect()
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn epoch_ms_to_days_wasm(values: &[f64]) -> Vec<i32> {
+    values.iter().map(|&v| epoch_ms_to_days(v)).collect()
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn epoch_days_to_ms_wasm(days: &[i32]) -> Vec<f64> {
+    days.iter().map(|&d| epoch_days_to_ms(d)).collect()
+}
+
+/// Extract a date part from epoch milliseconds
+///
+/// part: 0=year, 1=quarter, 2=month, 3=day, 4=hour, 5=minute, 6=second,
+/// 7=millisecond, 8=ISO weekday, 9=day of year, 10=ISO week, 11=ISO year
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn date_part_wasm(values: &[f64], part: u8) -> Result<Vec<f64>, JsValue> {
+    let part = DatePart::from_code(part).ok_or_else(|| JsValue::from_str("Invalid date part"))?;
+    Ok(values
+        .iter()
+        .map(|&v| ms_to_i64(v).map_or(f64::NAN, |t| date_part(t, part) as f64))
+        .collect())
+}
+
+/// Apply `f` to each present epoch-ms value (missing values stay NaN); a
+/// result outside the epoch-ms range is an error
+#[cfg(feature = "wasm")]
+fn map_in_range(values: &[f64], f: impl Fn(i64) -> Option<i64>) -> Result<Vec<f64>, JsValue> {
+    values
+        .iter()
+        .map(|&v| match ms_to_i64(v) {
+            Some(t) => f(t).map(|r| r as f64).ok_or_else(|| {
+                JsValue::from_str(&format!("Date {} is out of range for this period", v))
+            }),
+            None => Ok(f64::NAN),
+        })
+        .collect()
+}
+
+/// Round epoch milliseconds down to a period such as "1h", "15 mins" or "month"
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn floor_date_wasm(values: &[f64], period: &str, week_start: u32) -> Result<Vec<f64>, JsValue> {
+    let period = Period::parse(period).map_err(|e| JsValue::from_str(&e))?;
+    map_in_range(values, |t| floor_date(t, period, week_start))
+}
+
+/// Round epoch milliseconds up to a period boundary
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn ceiling_date_wasm(
+    values: &[f64],
+    period: &str,
+    week_start: u32,
+) -> Result<Vec<f64>, JsValue> {
+    let period = Period::parse(period).map_err(|e| JsValue::from_str(&e))?;
+    map_in_range(values, |t| ceiling_date(t, period, week_start))
+}
+
+/// Round epoch milliseconds to the nearest period boundary
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn round_date_wasm(values: &[f64], period: &str, week_start: u32) -> Result<Vec<f64>, JsValue> {
+    let period = Period::parse(period).map_err(|e| JsValue::from_str(&e))?;
+    map_in_range(values, |t| round_date(t, period, week_start))
+}
+
+/// Element-wise `end - start` in `unit` (e.g. "days", "hours", "months")
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn date_diff_wasm(start: &[f64], end: &[f64], unit: &str) -> Result<Vec<f64>, JsValue> {
+    let unit = TimeUnit::parse(unit).ok_or_else(|| JsValue::from_str("Invalid time unit"))?;
+    if start.len() != end.len() {
+        return Err(JsValue::from_str("start/end length mismatch"));
+    }
+    Ok(start
+        .iter()
+        .zip(end)
+        .map(|(&a, &b)| match (ms_to_i64(a), ms_to_i64(b)) {
+            (Some(a), Some(b)) => date_diff(a, b, unit),
+            _ => f64::NAN,
+        })
+        .collect())
+}
+
+/// Offset epoch days by `n` business days (`n` has length 1 or matches `days`);
+/// results outside the epoch-day range are `NA_DAY`
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn add_business_days_wasm(
+    days: &[i32],
+    n: &[i32],
+    holidays: &[i32],
+) -> Result<Vec<i32>, JsValue> {
+    if n.len() != 1 && n.len() != days.len() {
+        return Err(JsValue::from_str("n must have length 1 or match days"));
+    }
+    let cal = BusinessCalendar::new(holidays);
+    Ok(days
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| {
+            let k = if n.len() == 1 { n[0] } else { n[i] };
+            if d == NA_DAY || k == NA_DAY {
+                NA_DAY
+            } else {
+                i32::try_from(cal.add(d as i64, k as i64)).unwrap_or(NA_DAY)
+            }
+        })
+        .collect())
+}
+
+/// Count business days in [start, end) per element
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn business_days_between_wasm(
+    start: &[i32],
+    end: &[i32],
+    holidays: &[i32],
+) -> Result<Vec<i32>, JsValue> {
+    if start.len() != end.len() {
+        return Err(JsValue::from_str("start/end length mismatch"));
+    }
+    let cal = BusinessCalendar::new(holidays);
+    Ok(start
+        .iter()
+        .zip(end)
+        .map(|(&a, &b)| {
+            if a == NA_DAY || b == NA_DAY {
+                NA_DAY
+            } else {
+                i32::try_from(cal.count_between(a as i64, b as i64)).unwrap_or(NA_DAY)
+            }
+        })
+        .collect())
+}
+
+/// Order-preserving key codes for an epoch-ms column
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct TemporalKeys {
+    codes: Vec<u32>,
+    uniques: Vec<f64>,
+    pub na_code: u32,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl TemporalKeys {
+    #[wasm_bindgen(js_name = takeCodes)]
+    pub fn take_codes(&mut self) -> Box<[u32]> {
+        std::mem::take(&mut self.codes).into_boxed_slice()
+    }
+
+    #[wasm_bindgen(js_name = takeUniques)]
+    pub fn take_uniques(&mut self) -> Box<[f64]> {
+        std::mem::take(&mut self.uniques).into_boxed_slice()
+    }
+}
+
+/// Encode an epoch-ms column as u32 codes for `group_ids_codes_all`,
+/// the join kernels and `stable_sort_indices_u32_wasm` (with `na_code`)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn temporal_key_codes_wasm(values: &[f64]) -> TemporalKeys {
+    let (codes, uniques) = temporal_key_codes(values);
+    TemporalKeys {
+        na_code: uniques.len() as u32,
+        codes,
+        uniques,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iso(s: &str) -> i64 {
+        parse_iso8601(s).unwrap()
+    }
+
+    fn day(s: &str) -> i64 {
+        iso(s).div_euclid(MS_PER_DAY)
+    }
+
+    #[test]
+    fn test_floor_ceiling_round_per_unit() {
+        // A Wednesday afternoon
+        let t = iso("2024-05-15T13:47:31.250Z");
+        let cases = [
+            ("1ms", t, t, t),
+            (
+                "1s",
+                iso("2024-05-15T13:47:31Z"),
+                iso("2024-05-15T13:47:32Z"),
+                iso("2024-05-15T13:47:31Z"),
+            ),
+            (
+                "15 mins",
+                iso("2024-05-15T13:45:00Z"),
+                iso("2024-05-15T14:00:00Z"),
+                iso("2024-05-15T13:45:00Z"),
+            ),
+            (
+                "1h",
+                iso("2024-05-15T13:00:00Z"),
+                iso("2024-05-15T14:00:00Z"),
+                iso("2024-05-15T14:00:00Z"),
+            ),
+            (
+                "day",
+                iso("2024-05-15"),
+                iso("2024-05-16"),
+                iso("2024-05-16"),
+            ),
+            (
+                "week",
+                iso("2024-05-13"),
+                iso("2024-05-20"),
+                iso("2024-05-13"),
+            ),
+            (
+                "month",
+                iso("2024-05-01"),
+                iso("2024-06-01"),
+                iso("2024-05-01"),
+            ),
+            (
+                "quarter",
+                iso("2024-04-01"),
+                iso("2024-07-01"),
+                iso("2024-04-01"),
+            ),
+            (
+                "year",
+                iso("2024-01-01"),
+                iso("2025-01-01"),
+                iso("2024-01-01"),
+            ),
+        ];
+        for (period, floor, ceiling, round) in cases {
+            let p = Period::parse(period).unwrap();
+            assert_eq!(floor_date(t, p, 1), Some(floor), "floor {}", period);
+            assert_eq!(ceiling_date(t, p, 1), Some(ceiling), "ceiling {}", period);
+            assert_eq!(round_date(t, p, 1), Some(round), "round {}", period);
+            // Boundaries are fixed points
+            assert_eq!(ceiling_date(floor, p, 1), Some(floor), "{}", period);
+            assert_eq!(round_date(floor, p, 1), Some(floor), "{}", period);
+        }
+
+        // Sunday-start weeks, and halfway values round up
+        let week = Period::parse("week").unwrap();
+        assert_eq!(floor_date(t, week, 7), Some(iso("2024-05-12")));
+        let hour = Period::parse("1h").unwrap();
+        assert_eq!(
+            round_date(iso("2024-05-15T13:30:00Z"), hour, 1),
+            Some(iso("2024-05-15T14:00:00Z"))
+        );
+        // Before the epoch
+        assert_eq!(
+            floor_date(iso("1969-12-31T23:59:59Z"), hour, 1),
+            Some(iso("1969-12-31T23:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_floor_date_overflow() {
+        let week = Period::parse("week").unwrap();
+        assert_eq!(floor_date(i64::MAX, week, 1), None);
+        assert_eq!(
+            ceiling_date(i64::MAX - 1, Period::parse("1h").unwrap(), 1),
+            None
+        );
+        assert_eq!(
+            floor_date(0, Period::new(i64::MAX / 2, TimeUnit::Week), 1),
+            None
+        );
+        assert_eq!(
+            ceiling_date(i64::MAX - 1, Period::parse("month").unwrap(), 1),
+            None
+        );
+    }
+
+    #[test]
+    fn test_add_period_and_diff_overflow() {
+        let month = Period::parse("month").unwrap();
+        assert_eq!(
+            add_period(iso("2024-01-31T12:00:00Z"), month, 1),
+            Some(iso("2024-02-29T12:00:00Z"))
+        );
+        assert_eq!(
+            add_period(
+                iso("2024-03-31T00:00:00Z"),
+                Period::parse("2 quarters").unwrap(),
+                -1
+            ),
+            Some(iso("2023-09-30T00:00:00Z"))
+        );
+
+        let huge = Period::parse("9999999999999d").unwrap();
+        assert_eq!(huge.fixed_ms(), None);
+        assert_eq!(add_period(0, huge, 1), None);
+        assert_eq!(
+            add_period(i64::MAX - 10, Period::parse("1ms").unwrap(), 100),
+            None
+        );
+        assert_eq!(
+            add_period(0, Period::new(i64::MAX, TimeUnit::Year), 1),
+            None
+        );
+        assert_eq!(add_period(0, month, i64::MIN), None);
+        assert_eq!(add_months(i64::MAX, 1), None);
+        assert_eq!(add_months(0, 12 * 400_000_000), None);
+
+        let span = date_diff(i64::MIN, i64::MAX, TimeUnit::Day);
+        assert!((span - 2f64.powi(64) / MS_PER_DAY as f64).abs() < 1.0);
+        assert!(date_diff(i64::MAX, i64::MIN, TimeUnit::Month) < 0.0);
+        assert!(date_diff(i64::MIN, i64::MAX, TimeUnit::Year) > 5e8);
+    }
+
+    #[test]
+    fn test_business_calendar() {
+        let cal = BusinessCalendar::new(&[]);
+        // Friday + 1 is Monday; Saturday rolls forward, then backward for n < 0
+        assert_eq!(cal.add(day("2024-05-17"), 1), day("2024-05-20"));
+        assert_eq!(cal.add(day("2024-05-18"), 0), day("2024-05-20"));
+        assert_eq!(cal.add(day("2024-05-18"), -1), day("2024-05-16"));
+        assert_eq!(cal.count_between(day("2024-05-13"), day("2024-05-20")), 5);
+        assert_eq!(cal.count_between(day("2024-05-20"), day("2024-05-13")), -5);
+
+        // Holidays on weekends are ignored
+        let holidays = [
+            day("2024-05-20") as i32,
+            day("2024-05-25") as i32,
+            day("2024-12-25") as i32,
+        ];
+        let cal = BusinessCalendar::new(&holidays);
+        assert!(!cal.is_business_day(day("2024-05-20")));
+        assert_eq!(cal.add(day("2024-05-17"), 1), day("2024-05-21"));
+        assert_eq!(cal.count_between(day("2024-05-13"), day("2024-05-27")), 9);
+
+        // The week-skipping path agrees with counting
+        let start = day("2024-01-02");
+        for n in [0, 4, 5, 6, 120, 261, -3, -130] {
+            let end = cal.add(start, n);
+            assert!(cal.is_business_day(end));
+            assert_eq!(
+                cal.count_between(start.min(end), start.max(end)),
+                n.abs(),
+                "{}",
+                n
+            );
+        }
+
+        // Offsets can leave the i32 epoch-day range
+        assert!(i32::try_from(cal.add(i32::MAX as i64 - 10, 100)).is_err());
+    }
+
+    #[test]
+    fn test_parse_strftime() {
+        assert_eq!(
+            parse_strftime("15/05/2024 13:47", "%d/%m/%Y %H:%M"),
+            Some(iso("2024-05-15T13:47:00Z"))
+        );
+        assert_eq!(
+            parse_strftime("May  5, 2024", "%b %e, %Y"),
+            Some(iso("2024-05-05"))
+        );
+        assert_eq!(
+            parse_strftime("Wednesday 15 MAY 24 1:05 pm", "%A %d %B %y %I:%M %p"),
+            Some(iso("2024-05-15T13:05:00Z"))
+        );
+        assert_eq!(
+            parse_strftime("12:00 AM", "%I:%M %p"),
+            Some(iso("1970-01-01T00:00:00Z"))
+        );
+        assert_eq!(parse_strftime("2024-060", "%Y-%j"), Some(iso("2024-02-29")));
+        assert_eq!(parse_strftime("2023-366", "%Y-%j"), None);
+        assert_eq!(
+            parse_strftime("2024-05-15T13:47:31.25+0200", "%FT%H:%M:%OS%z"),
+            Some(iso("2024-05-15T11:47:31.250Z"))
+        );
+        assert_eq!(parse_strftime("69", "%y"), Some(iso("1969-01-01")));
+        assert_eq!(parse_strftime("68", "%y"), Some(iso("2068-01-01")));
+        assert_eq!(parse_strftime("100%", "%d%%"), None);
+        assert_eq!(parse_strftime("2023-02-29", "%F"), None);
+        assert_eq!(parse_strftime("2024-05-15 junk", "%F"), None);
+
+        let t = iso("2024-05-15T13:47:31.250Z");
+        let format = "%a %d %b %Y %I:%M:%OS %p";
+        assert_eq!(parse_strftime(&format_strftime(t, format), format), Some(t));
+    }
+}
@@ -0,0 +1,888 @@
+//! Time-based grouping, rolling windows and resampling
+//!
+//! Unlike the row-count windows of `stats/window/rolling.ts`, these kernels
+//! define windows and buckets in time, so irregularly spaced series are handled
+//! correctly. Timestamps are epoch milliseconds (`f64`, NaN = missing) and
+//! periods use the `temporal` syntax ("1h", "7d", "1M", "2 weeks", ...).
+//! Every kernel accepts `gid_per_row` (empty = one group) so per-entity panels
+//! are processed in one call without windows crossing group boundaries.
+
+#![deny(unsafe_op_in_unsafe_fn)]
+
+use super::arrange::cmp_nan_last;
+use super::fill::{FillDirection, InterpolationMethod, fill_f64, group_runs, interpolate_f64};
+use super::temporal::{Period, add_period, floor_date};
+use std::collections::VecDeque;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Upper bound on the number of grid cells `resample` will allocate
+const MAX_RESAMPLE_CELLS: usize = 100_000_000;
+
+/// Aggregation applied to the values of a bucket or window
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TimeAggregation {
+    Sum,
+    Mean,
+    Count,
+    Min,
+    Max,
+    Var,
+    Sd,
+    /// Earliest present value (by timestamp)
+    First,
+    /// Latest present value (by timestamp)
+    Last,
+}
+
+impl TimeAggregation {
+    /// 0=sum, 1=mean, 2=count, 3=min, 4=max, 5=var, 6=sd, 7=first, 8=last
+    pub fn from_code(code: u8) -> Option<TimeAggregation> {
+        let agg = match code {
+            0 => TimeAggregation::Sum,
+            1 => TimeAggregation::Mean,
+            2 => TimeAggregation::Count,
+            3 => TimeAggregation::Min,
+            4 => TimeAggregation::Max,
+            5 => TimeAggregation::Var,
+            6 => TimeAggregation::Sd,
+            7 => TimeAggregation::First,
+            8 => TimeAggregation::Last,
+            _ => return None,
+        };
+        Some(agg)
+    }
+}
+
+/// Which ends of a rolling window `[t - width, t]` are included
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WindowClosed {
+    Both,
+    Right,
+    Left,
+    Neither,
+}
+
+impl WindowClosed {
+    /// 0=both, 1=right, 2=left, 3=neither
+    pub fn from_code(code: u8) -> Option<WindowClosed> {
+        let closed = match code {
+            0 => WindowClosed::Both,
+            1 => WindowClosed::Right,
+            2 => WindowClosed::Left,
+            3 => WindowClosed::Neither,
+            _ => return None,
+        };
+        Some(closed)
+    }
+}
+
+/// How empty grid cells are filled after resampling
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ResampleFill {
+    /// Leave empty cells missing
+    None,
+    /// Carry the previous cell forward, at most `limit` cells (0 = unlimited)
+    Forward(usize),
+    /// Carry the next cell backward, at most `limit` cells (0 = unlimited)
+    Backward(usize),
+    /// Linear interpolation in time between neighbouring cells
+    Linear,
+    /// Fill with a constant
+    Constant(f64),
+}
+
+#[inline]
+fn is_present(vals: &[f64], valid: &[u8], row: usize) -> bool {
+    if valid.is_empty() {
+        !vals[row].is_nan()
+    } else {
+        valid[row] != 0
+    }
+}
+
+/// Single-pass accumulator (Welford for mean/variance)
+#[derive(Debug, Clone, Copy)]
+struct Acc {
+    n: u64,
+    sum: f64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+    first: (f64, f64),
+    last: (f64, f64),
+}
+
+impl Default for Acc {
+    fn default() -> Self {
+        Acc {
+            n: 0,
+            sum: 0.0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            first: (f64::INFINITY, f64::NAN),
+            last: (f64::NEG_INFINITY, f64::NAN),
+        }
+    }
+}
+
+impl Acc {
+    fn push(&mut self, t: f64, v: f64) {
+        self.n += 1;
+        self.sum += v;
+        let d = v - self.mean;
+        self.mean += d / self.n as f64;
+        self.m2 += d * (v - self.mean);
+        self.min = self.min.min(v);
+        self.max = self.max.max(v);
+        if t < self.first.0 {
+            self.first = (t, v);
+        }
+        if t >= self.last.0 {
+            self.last = (t, v);
+        }
+    }
+
+    fn finish(&self, agg: TimeAggregation) -> f64 {
+        if self.n == 0 {
+            return match agg {
+                TimeAggregation::Count => 0.0,
+                TimeAggregation::Sum => 0.0,
+                _ => f64::NAN,
+            };
+        }
+        match agg {
+            TimeAggregation::Sum => self.sum,
+            TimeAggregation::Mean => self.mean,
+            TimeAggregation::Count => self.n as f64,
+            TimeAggregation::Min => self.min,
+            TimeAggregation::Max => self.max,
+            TimeAggregation::Var | TimeAggregation::Sd if self.n < 2 => f64::NAN,
+            TimeAggregation::Var => self.m2 / (self.n - 1) as f64,
+            TimeAggregation::Sd => (self.m2 / (self.n - 1) as f64).sqrt(),
+            TimeAggregation::First => self.first.1,
+            TimeAggregation::Last => self.last.1,
+        }
+    }
+}
+
+#[inline]
+fn ts_i64(t: f64) -> Option<i64> {
+    if t.is_finite() {
+        Some(t.floor() as i64)
+    } else {
+        None
+    }
+}
+
+/// Rows bucketed by group, sorted by timestamp within each group (missing
+/// last). Ties keep row order, so `First`/`Last` are deterministic.
+fn runs_by_time(
+    ts: &[f64],
+    gid_per_row: &[u32],
+    n_groups: usize,
+) -> Result<(Vec<usize>, Vec<u32>), String> {
+    // Treat ±inf like NaN so every group's timed rows form a sorted prefix
+    let keys: Vec<f64> = ts
+        .iter()
+        .map(|&t| if t.is_finite() { t } else { f64::NAN })
+        .collect();
+    let mut order: Vec<u32> = (0..ts.len() as u32).collect();
+    order.sort_by(|&a, &b| cmp_nan_last(keys[a as usize], keys[b as usize]));
+    group_runs(ts.len(), gid_per_row, n_groups, &order)
+}
+
+/// Result of bucketing rows by (group, floor(ts, period))
+#[derive(Debug, Clone, Default)]
+pub struct TimeBuckets {
+    /// Bucket id per row
+    pub bucket_per_row: Vec<u32>,
+    /// Parent group id per bucket
+    pub bucket_group: Vec<u32>,
+    /// Bucket start (epoch ms) per bucket; NaN for the bucket of missing timestamps
+    pub bucket_start: Vec<f64>,
+}
+
+fn out_of_range(t: i64) -> String {
+    format!(
+        "period boundary for time {} is outside the epoch-ms range",
+        t
+    )
+}
+
+/// Assign every row to a time bucket `floor(ts, period)` within its group.
+///
+/// Buckets are numbered by (group, start) ascending; rows with a missing
+/// timestamp share one trailing bucket per group. The resulting ids can be fed
+/// to `aggregate_by_bucket` or to the `reduce_*` aggregation kernels.
+pub fn time_buckets(
+    ts: &[f64],
+    gid_per_row: &[u32],
+    n_groups: usize,
+    period: Period,
+    week_start: u32,
+) -> Result<TimeBuckets, String> {
+    let (offsets, rows) = runs_by_time(ts, gid_per_row, n_groups)?;
+    let mut out = TimeBuckets {
+        bucket_per_row: vec![0; ts.len()],
+        ..Default::default()
+    };
+
+    for (g, w) in offsets.windows(2).enumerate() {
+        let mut current: Option<Option<i64>> = None;
+        for &r in &rows[w[0]..w[1]] {
+            let start = match ts_i64(ts[r as usize]) {
+                Some(t) => Some(floor_date(t, period, week_start).ok_or_else(|| out_of_range(t))?),
+                None => None,
+            };
+            if current != Some(start) {
+                current = Some(start);
+                out.bucket_group.push(g as u32);
+                out.bucket_start.push(start.map_or(f64::NAN, |s| s as f64));
+            }
+            out.bucket_per_row[r as usize] = (out.bucket_start.len() - 1) as u32;
+        }
+    }
+    Ok(out)
+}
+
+/// Aggregate `vals` per bucket; missing values are skipped and `First`/`Last`
+/// are resolved by timestamp.
+pub fn aggregate_by_bucket(
+    ts: &[f64],
+    vals: &[f64],
+    valid: &[u8],
+    bucket_per_row: &[u32],
+    n_buckets: usize,
+    agg: TimeAggregation,
+) -> Result<Vec<f64>, String> {
+    let n = vals.len();
+    if ts.len() != n || bucket_per_row.len() != n || (!valid.is_empty() && valid.len() != n) {
+        return Err("input length mismatch".to_string());
+    }
+    let mut accs = vec![Acc::default(); n_buckets];
+    for i in 0..n {
+        let b = bucket_per_row[i] as usize;
+        if b >= n_buckets {
+            return Err(format!("bucket id {} out of range", b));
+        }
+        if is_present(vals, valid, i) {
+            accs[b].push(ts[i], vals[i]);
+        }
+    }
+    Ok(accs.iter().map(|a| a.finish(agg)).collect())
+}
+
+/// Rolling aggregation over a time window ending at each row's timestamp.
+///
+/// The window for a row at time `t` covers `[t - width, t]` (endpoints per
+/// `closed`) among rows of the same group, so rows sharing a timestamp see the
+/// same window. `width` may be a calendar period (e.g. "1M"). Rows with fewer
+/// than `min_periods` present values in their window, or with a missing
+/// timestamp, get NaN.
+#[allow(clippy::too_many_arguments)]
+pub fn rolling_time_f64(
+    ts: &[f64],
+    vals: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: usize,
+    width: Period,
+    closed: WindowClosed,
+    agg: TimeAggregation,
+    min_periods: usize,
+    out: &mut [f64],
+) -> Result<(), String> {
+    let n = vals.len();
+    if ts.len() != n || out.len() != n || (!valid.is_empty() && valid.len() != n) {
+        return Err("input length mismatch".to_string());
+    }
+    let (offsets, rows) = runs_by_time(ts, gid_per_row, n_groups)?;
+    out.fill(f64::NAN);
+
+    let include_lo = matches!(closed, WindowClosed::Both | WindowClosed::Left);
+    let include_hi = matches!(closed, WindowClosed::Both | WindowClosed::Right);
+
+    for w in offsets.windows(2) {
+        let run = &rows[w[0]..w[1]];
+        let times: Vec<Option<i64>> = run.iter().map(|&r| ts_i64(ts[r as usize])).collect();
+        let n_timed = times.iter().take_while(|t| t.is_some()).count();
+
+        // Shift values by the first present one for a stable running variance
+        let shift = run
+            .iter()
+            .map(|&r| r as usize)
+            .find(|&r| is_present(vals, valid, r))
+            .map_or(0.0, |r| vals[r]);
+
+        let (mut lo, mut hi) = (0usize, 0usize);
+        let (mut cnt, mut sum, mut sumsq) = (0usize, 0.0f64, 0.0f64);
+        // Window positions holding present values (for first/last), and monotonic deques
+        let mut present: VecDeque<usize> = VecDeque::new();
+        let mut dq_min: VecDeque<usize> = VecDeque::new();
+        let mut dq_max: VecDeque<usize> = VecDeque::new();
+        let val_at = |k: usize| vals[run[k] as usize];
+
+        for i in 0..n_timed {
+            let t = times[i].unwrap_or_default();
+            // A window reaching back past the epoch-ms range starts at its beginning
+            let start = add_period(t, width, -1).unwrap_or(i64::MIN);
+
+            // Extend the right edge
+            while hi < n_timed {
+                let th = times[hi].unwrap_or_default();
+                if th > t || (!include_hi && th == t) {
+                    break;
+                }
+                let r = run[hi] as usize;
+                if is_present(vals, valid, r) {
+                    let v = vals[r] - shift;
+                    cnt += 1;
+                    sum += v;
+                    sumsq += v * v;
+                    present.push_back(hi);
+                    while dq_min.back().is_some_and(|&k| val_at(k) >= vals[r]) {
+                        dq_min.pop_back();
+                    }
+                    dq_min.push_back(hi);
+                    while dq_max.back().is_some_and(|&k| val_at(k) <= vals[r]) {
+                        dq_max.pop_back();
+                    }
+                    dq_max.push_back(hi);
+                }
+                hi += 1;
+            }
+            // Shrink the left edge
+            while lo < hi {
+                let tl = times[lo].unwrap_or_default();
+                if tl > start || (include_lo && tl == start) {
+                    break;
+                }
+                let r = run[lo] as usize;
+                if is_present(vals, valid, r) {
+                    let v = vals[r] - shift;
+                    cnt -= 1;
+                    sum -= v;
+                    sumsq -= v * v;
+                    present.pop_front();
+                }
+                while dq_min.front().is_some_and(|&k| k <= lo) {
+                    dq_min.pop_front();
+                }
+                while dq_max.front().is_some_and(|&k| k <= lo) {
+                    dq_max.pop_front();
+                }
+                lo += 1;
+            }
+
+            let r = run[i] as usize;
+            let needed = if agg == TimeAggregation::Count {
+                min_periods
+            } else {
+                min_periods.max(1)
+            };
+            if cnt < needed {
+                continue;
+            }
+            let nf = cnt as f64;
+            out[r] = match agg {
+                TimeAggregation::Count => nf,
+                TimeAggregation::Sum => sum + shift * nf,
+                TimeAggregation::Mean => sum / nf + shift,
+                TimeAggregation::Var | TimeAggregation::Sd if cnt < 2 => f64::NAN,
+                TimeAggregation::Var | TimeAggregation::Sd => {
+                    let var = ((sumsq - sum * sum / nf) / (nf - 1.0)).max(0.0);
+                    if agg == TimeAggregation::Sd {
+                        var.sqrt()
+                    } else {
+                        var
+                    }
+                }
+                TimeAggregation::Min => dq_min.front().map_or(f64::NAN, |&k| val_at(k)),
+                TimeAggregation::Max => dq_max.front().map_or(f64::NAN, |&k| val_at(k)),
+                TimeAggregation::First => present.front().map_or(f64::NAN, |&k| val_at(k)),
+                TimeAggregation::Last => present.back().map_or(f64::NAN, |&k| val_at(k)),
+            };
+        }
+    }
+    Ok(())
+}
+
+/// Regular time grid produced by `resample`
+#[derive(Debug, Clone, Default)]
+pub struct Resampled {
+    /// Parent group id per grid cell
+    pub group: Vec<u32>,
+    /// Cell start (epoch ms) per grid cell
+    pub time: Vec<f64>,
+    /// Aggregated (and filled) value per grid cell
+    pub values: Vec<f64>,
+    /// 1 where the cell has a value after filling
+    pub valid: Vec<u8>,
+}
+
+/// Resample each group onto a regular grid of `period` cells.
+///
+/// The grid runs from `floor(start)` to `floor(end)`, where NaN bounds default
+/// to each group's own min/max timestamp. Rows are aggregated into their cell
+/// with `agg` (downsampling); empty cells are then filled per `fill`
+/// (upsampling), never across groups.
+#[allow(clippy::too_many_arguments)]
+pub fn resample(
+    ts: &[f64],
+    vals: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: usize,
+    period: Period,
+    week_start: u32,
+    start: f64,
+    end: f64,
+    agg: TimeAggregation,
+    fill: ResampleFill,
+) -> Result<Resampled, String> {
+    let n = vals.len();
+    if ts.len() != n || (!valid.is_empty() && valid.len() != n) {
+        return Err("input length mismatch".to_string());
+    }
+    let (offsets, rows) = runs_by_time(ts, gid_per_row, n_groups)?;
+
+    let mut grid = Resampled::default();
+    let mut accs: Vec<Acc> = Vec::new();
+    let mut cell_times: Vec<i64> = Vec::new();
+
+    for (g, w) in offsets.windows(2).enumerate() {
+        let run = &rows[w[0]..w[1]];
+        let timed: Vec<i64> = run.iter().map_while(|&r| ts_i64(ts[r as usize])).collect();
+        let (Some(lo), Some(hi)) = (
+            ts_i64(start).or(timed.first().copied()),
+            ts_i64(end).or(timed.last().copied()),
+        ) else {
+            continue;
+        };
+        if hi < lo {
+            continue;
+        }
+
+        cell_times.clear();
+        let first = floor_date(lo, period, week_start).ok_or_else(|| out_of_range(lo))?;
+        let last = floor_date(hi, period, week_start).ok_or_else(|| out_of_range(hi))?;
+        let mut k = 0i64;
+        loop {
+            // A cell past the epoch-ms range is necessarily past `last`
+            let Some(c) = add_period(first, period, k).filter(|&c| c <= last) else {
+                break;
+            };
+            cell_times.push(c);
+            if grid.time.len() + cell_times.len() > MAX_RESAMPLE_CELLS {
+                return Err(format!(
+                    "resample grid exceeds {} cells",
+                    MAX_RESAMPLE_CELLS
+                ));
+            }
+            k += 1;
+        }
+
+        let base = accs.len();
+        accs.resize(base + cell_times.len(), Acc::default());
+        for (&r, &t) in run.iter().zip(&timed) {
+            let r = r as usize;
+            if t < lo || t > hi || !is_present(vals, valid, r) {
+                continue;
+            }
+            if let Some(cell) = floor_date(t, period, week_start)
+                && let Ok(c) = cell_times.binary_search(&cell)
+            {
+                accs[base + c].push(ts[r], vals[r]);
+            }
+        }
+        grid.group
+            .extend(std::iter::repeat_n(g as u32, cell_times.len()));
+        grid.time.extend(cell_times.iter().map(|&c| c as f64));
+    }
+
+    let n_cells = grid.time.len();
+    let raw: Vec<f64> = accs.iter().map(|a| a.finish(agg)).collect();
+    let raw_valid: Vec<u8> = accs.iter().map(|a| (a.n > 0) as u8).collect();
+    grid.values = vec![f64::NAN; n_cells];
+    grid.valid = vec![0; n_cells];
+
+    match fill {
+        ResampleFill::None | ResampleFill::Constant(_) => {
+            for c in 0..n_cells {
+                if raw_valid[c] != 0 {
+                    grid.values[c] = raw[c];
+                    grid.valid[c] = 1;
+                } else if let ResampleFill::Constant(v) = fill {
+                    grid.values[c] = v;
+                    grid.valid[c] = 1;
+                }
+            }
+        }
+        ResampleFill::Forward(limit) | ResampleFill::Backward(limit) => {
+            let direction = if matches!(fill, ResampleFill::Forward(_)) {
+                FillDirection::Forward
+            } else {
+                FillDirection::Backward
+            };
+            fill_f64(
+                &raw,
+                &raw_valid,
+                &grid.group,
+                n_groups.max(1),
+                &[],
+                direction,
+                limit,
+                &mut grid.values,
+                &mut grid.valid,
+            )?;
+        }
+        ResampleFill::Linear => {
+            interpolate_f64(
+                &raw,
+                &raw_valid,
+                &grid.time,
+                &grid.group,
+                n_groups.max(1),
+                &[],
+                InterpolationMethod::Linear,
+                &mut grid.values,
+                &mut grid.valid,
+            )?;
+        }
+    }
+    Ok(grid)
+}
+
+// ----------------------------- WASM exports -----------------------------
+
+/// Time bucketing result (bucket ids per row plus per-bucket group/start)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct TimeBucketing {
+    bucket_per_row: Vec<u32>,
+    bucket_group: Vec<u32>,
+    bucket_start: Vec<f64>,
+    pub n_buckets: u32,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl TimeBucketing {
+    #[wasm_bindgen(js_name = takeBucketPerRow)]
+    pub fn take_bucket_per_row(&mut self) -> Box<[u32]> {
+        std::mem::take(&mut self.bucket_per_row).into_boxed_slice()
+    }
+
+    #[wasm_bindgen(js_name = takeBucketGroup)]
+    pub fn take_bucket_group(&mut self) -> Box<[u32]> {
+        std::mem::take(&mut self.bucket_group).into_boxed_slice()
+    }
+
+    #[wasm_bindgen(js_name = takeBucketStart)]
+    pub fn take_bucket_start(&mut self) -> Box<[f64]> {
+        std::mem::take(&mut self.bucket_start).into_boxed_slice()
+    }
+}
+
+/// Group rows by (group, floor(ts, period)), e.g. period = "1h"
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn time_buckets_wasm(
+    ts: &[f64],
+    gid_per_row: &[u32],
+    n_groups: u32,
+    period: &str,
+    week_start: u32,
+) -> Result<TimeBucketing, JsValue> {
+    let period = Period::parse(period).map_err(|e| JsValue::from_str(&e))?;
+    let b = time_buckets(ts, gid_per_row, n_groups as usize, period, week_start)
+        .map_err(|e| JsValue::from_str(&format!("Time bucket error: {}", e)))?;
+    Ok(TimeBucketing {
+        n_buckets: b.bucket_start.len() as u32,
+        bucket_per_row: b.bucket_per_row,
+        bucket_group: b.bucket_group,
+        bucket_start: b.bucket_start,
+    })
+}
+
+/// Aggregate a column per time bucket
+///
+/// agg: 0=sum, 1=mean, 2=count, 3=min, 4=max, 5=var, 6=sd, 7=first, 8=last
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn aggregate_by_bucket_wasm(
+    ts: &[f64],
+    vals: &[f64],
+    valid: &[u8],
+    bucket_per_row: &[u32],
+    n_buckets: u32,
+    agg: u8,
+) -> Result<Vec<f64>, JsValue> {
+    let agg =
+        TimeAggregation::from_code(agg).ok_or_else(|| JsValue::from_str("Invalid aggregation"))?;
+    aggregate_by_bucket(ts, vals, valid, bucket_per_row, n_buckets as usize, agg)
+        .map_err(|e| JsValue::from_str(&format!("Aggregation error: {}", e)))
+}
+
+/// Time-based rolling aggregation, e.g. width = "7d"
+///
+/// closed: 0=both, 1=right, 2=left, 3=neither
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn rolling_time_f64_wasm(
+    ts: &[f64],
+    vals: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+    width: &str,
+    closed: u8,
+    agg: u8,
+    min_periods: u32,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    let width = Period::parse(width).map_err(|e| JsValue::from_str(&e))?;
+    let closed =
+        WindowClosed::from_code(closed).ok_or_else(|| JsValue::from_str("Invalid closed"))?;
+    let agg =
+        TimeAggregation::from_code(agg).ok_or_else(|| JsValue::from_str("Invalid aggregation"))?;
+    rolling_time_f64(
+        ts,
+        vals,
+        valid,
+        gid_per_row,
+        n_groups as usize,
+        width,
+        closed,
+        agg,
+        min_periods as usize,
+        out,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Rolling error: {}", e)))
+}
+
+/// Resampling result on a regular grid
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct ResampleResult {
+    group: Vec<u32>,
+    time: Vec<f64>,
+    values: Vec<f64>,
+    valid: Vec<u8>,
+    pub n_cells: u32,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl ResampleResult {
+    #[wasm_bindgen(js_name = takeGroup)]
+    pub fn take_group(&mut self) -> Box<[u32]> {
+        std::mem::take(&mut self.group).into_boxed_slice()
+    }
+
+    #[wasm_bindgen(js_name = takeTime)]
+    pub fn take_time(&mut self) -> Box<[f64]> {
+        std::mem::take(&mut self.time).into_boxed_slice()
+    }
+
+    #[wasm_bindgen(js_name = takeValues)]
+    pub fn take_values(&mut self) -> Box<[f64]> {
+        std::mem::take(&mut self.values).into_boxed_slice()
+    }
+
+    #[wasm_bindgen(js_name = takeValid)]
+    pub fn take_valid(&mut self) -> Box<[u8]> {
+        std::mem::take(&mut self.valid).into_boxed_slice()
+    }
+}
+
+/// Resample onto a regular grid (NaN `start`/`end` = per-group range)
+///
+/// fill: 0=none, 1=forward, 2=backward, 3=linear, 4=constant (`fill_value`);
+/// `fill_limit` caps forward/backward fills (0 = unlimited)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn resample_wasm(
+    ts: &[f64],
+    vals: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+    period: &str,
+    week_start: u32,
+    start: f64,
+    end: f64,
+    agg: u8,
+    fill: u8,
+    fill_limit: u32,
+    fill_value: f64,
+) -> Result<ResampleResult, JsValue> {
+    let period = Period::parse(period).map_err(|e| JsValue::from_str(&e))?;
+    let agg =
+        TimeAggregation::from_code(agg).ok_or_else(|| JsValue::from_str("Invalid aggregation"))?;
+    let fill = match fill {
+        0 => ResampleFill::None,
+        1 => ResampleFill::Forward(fill_limit as usize),
+        2 => ResampleFill::Backward(fill_limit as usize),
+        3 => ResampleFill::Linear,
+        4 => ResampleFill::Constant(fill_value),
+        _ => return Err(JsValue::from_str("Invalid fill policy")),
+    };
+    let r = resample(
+        ts,
+        vals,
+        valid,
+        gid_per_row,
+        n_groups as usize,
+        period,
+        week_start,
+        start,
+        end,
+        agg,
+        fill,
+    )
+    .map_err(|e| JsValue::from_str(&format!("Resample error: {}", e)))?;
+    Ok(ResampleResult {
+        n_cells: r.time.len() as u32,
+        group: r.group,
+        time: r.time,
+        values: r.values,
+        valid: r.valid,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataframe::temporal::MS_PER_HOUR;
+
+    /// 300 rows cycling through the timestamps 0, 30 and 60 minutes, so most
+    /// rows tie; values are the row indices
+    fn tied_series() -> (Vec<f64>, Vec<f64>) {
+        let ts = (0..300)
+            .map(|i| ((i % 3) as i64 * MS_PER_HOUR / 2) as f64)
+            .collect();
+        let vals = (0..300).map(|i| i as f64).collect();
+        (ts, vals)
+    }
+
+    #[test]
+    fn test_resample_first_last_ties_in_row_order() {
+        let (ts, vals) = tied_series();
+        let hour = Period::parse("1h").unwrap();
+        let run = |agg| {
+            resample(
+                &ts,
+                &vals,
+                &[],
+                &[],
+                1,
+                hour,
+                1,
+                f64::NAN,
+                f64::NAN,
+                agg,
+                ResampleFill::None,
+            )
+            .unwrap()
+        };
+        let first = run(TimeAggregation::First);
+        let last = run(TimeAggregation::Last);
+        assert_eq!(first.time, vec![0.0, MS_PER_HOUR as f64]);
+        // Hour 0 holds t = 0 and t = 30 min; hour 1 holds t = 60 min only
+        assert_eq!(first.values, vec![0.0, 2.0]);
+        assert_eq!(last.values, vec![298.0, 299.0]);
+    }
+
+    #[test]
+    fn test_rolling_first_last_ties_in_row_order() {
+        let (ts, vals) = tied_series();
+        let width = Period::parse("10 mins").unwrap();
+        let mut first = vec![0.0; ts.len()];
+        let mut last = vec![0.0; ts.len()];
+        for (agg, out) in [
+            (TimeAggregation::First, &mut first),
+            (TimeAggregation::Last, &mut last),
+        ] {
+            rolling_time_f64(
+                &ts,
+                &vals,
+                &[],
+                &[],
+                1,
+                width,
+                WindowClosed::Right,
+                agg,
+                1,
+                out,
+            )
+            .unwrap();
+        }
+        // Each window holds exactly the rows sharing the timestamp
+        for i in 0..ts.len() {
+            let k = i % 3;
+            assert_eq!(first[i], k as f64, "row {}", i);
+            assert_eq!(last[i], (297 + k) as f64, "row {}", i);
+        }
+    }
+
+    #[test]
+    fn test_time_buckets_missing_last() {
+        let ts = [f64::NAN, 5.0, 1.0, f64::INFINITY, 1.0];
+        let tb = time_buckets(&ts, &[], 1, Period::parse("1ms").unwrap(), 1).unwrap();
+        assert_eq!(tb.bucket_per_row, vec![2, 1, 0, 2, 0]);
+        assert_eq!(tb.bucket_start[..2], [1.0, 5.0]);
+        assert!(tb.bucket_start[2].is_nan());
+    }
+
+    #[test]
+    fn test_periods_beyond_epoch_range() {
+        let ts = [0.0, MS_PER_HOUR as f64, 2.0 * MS_PER_HOUR as f64];
+        let vals = [1.0, 2.0, 4.0];
+        let huge = Period::parse("9999999999999d").unwrap();
+
+        // Windows reaching past the representable range cover every earlier row
+        let mut out = [0.0; 3];
+        rolling_time_f64(
+            &ts,
+            &vals,
+            &[],
+            &[],
+            1,
+            huge,
+            WindowClosed::Both,
+            TimeAggregation::Sum,
+            1,
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(out, [1.0, 3.0, 7.0]);
+
+        let run = |period| {
+            resample(
+                &ts,
+                &vals,
+                &[],
+                &[],
+                1,
+                period,
+                1,
+                f64::NAN,
+                f64::NAN,
+                TimeAggregation::Sum,
+                ResampleFill::None,
+            )
+        };
+        assert!(run(huge).is_err());
+        assert!(time_buckets(&ts, &[], 1, huge, 1).is_err());
+        // The cell after the only one lies past the end of the epoch-ms range
+        let eons = run(Period::parse("300000000 years").unwrap()).unwrap();
+        assert_eq!(eons.values, vec![7.0]);
+    }
+}
@@ -0,0 +1,494 @@
+//! Weighted descriptive statistics WASM exports
+//!
+//! Weighted mean, variance, quantiles, median and ECDF for frequency or
+//! sampling weights, with one result per group.
+//!
+//! Conventions shared by every kernel in this module:
+//! - `weights`: one non-negative weight per row. Empty = every weight is 1.
+//!   Rows with a NaN weight are skipped; negative or infinite weights are an error.
+//! - `valid`: 1 = value present, 0 = missing. An empty mask means "NaN is missing".
+//! - `gid_per_row`: group id per row (from `group_ids_codes_all`). Empty = one group.
+//! - Groups with no positive weight get NaN.
+//!
+//! Weighted quantiles place the i-th smallest value (0-based, zero-weight rows
+//! dropped) at cumulative probability
+//! `p_i = (S_i - w_i / 2 - w_0 / 2) / (W - w_0 / 2 - w_last / 2)`,
+//! where `S_i` is the running weight sum including `w_i` and `W` the total,
+//! and interpolate linearly between those points. With equal weights this is
+//! `i / (n - 1)`, i.e. R's type 7. Integer frequency weights are not the same
+//! as replicating rows (which would make ties into flat steps).
+
+use super::fill::group_runs;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// How a weighted variance is normalised
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WeightKind {
+    /// Weights count repeated observations: divide by `W - 1`
+    Frequency,
+    /// Sampling/reliability weights: divide by `W - sum(w^2) / W`
+    Reliability,
+}
+
+/// Validated `(value, weight)` pairs of each group, with weight > 0
+struct WeightedGroups {
+    groups: Vec<Vec<(f64, f64)>>,
+    /// Per row: whether it contributes (present value and positive weight)
+    used: Vec<bool>,
+}
+
+fn collect_groups(
+    vals: &[f64],
+    weights: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: usize,
+) -> Result<WeightedGroups, String> {
+    let n = vals.len();
+    if !weights.is_empty() && weights.len() != n {
+        return Err(format!(
+            "weights length mismatch: {} vs {}",
+            weights.len(),
+            n
+        ));
+    }
+    if !valid.is_empty() && valid.len() != n {
+        return Err(format!("valid length mismatch: {} vs {}", valid.len(), n));
+    }
+    if let Some(w) = weights.iter().find(|w| **w < 0.0 || w.is_infinite()) {
+        return Err(format!(
+            "weights must be finite and non-negative, got {}",
+            w
+        ));
+    }
+    let n_groups = if gid_per_row.is_empty() { 1 } else { n_groups };
+    let (offsets, rows) = group_runs(n, gid_per_row, n_groups, &[])?;
+
+    let mut used = vec![false; n];
+    let groups = (0..n_groups)
+        .map(|g| {
+            rows[offsets[g]..offsets[g + 1]]
+                .iter()
+                .filter_map(|&r| {
+                    let r = r as usize;
+                    let x = vals[r];
+                    let w = if weights.is_empty() { 1.0 } else { weights[r] };
+                    let present = if valid.is_empty() {
+                        !x.is_nan()
+                    } else {
+                        valid[r] != 0 && !x.is_nan()
+                    };
+                    (present && w > 0.0).then(|| {
+                        used[r] = true;
+                        (x, w)
+                    })
+                })
+                .collect()
+        })
+        .collect();
+    Ok(WeightedGroups { groups, used })
+}
+
+fn mean_of(pairs: &[(f64, f64)]) -> f64 {
+    let total: f64 = pairs.iter().map(|p| p.1).sum();
+    if total > 0.0 {
+        pairs.iter().map(|(x, w)| x * w).sum::<f64>() / total
+    } else {
+        f64::NAN
+    }
+}
+
+fn var_of(pairs: &[(f64, f64)], kind: WeightKind) -> f64 {
+    let total: f64 = pairs.iter().map(|p| p.1).sum();
+    let mean = mean_of(pairs);
+    let ss: f64 = pairs.iter().map(|(x, w)| w * (x - mean) * (x - mean)).sum();
+    let denom = match kind {
+        WeightKind::Frequency => total - 1.0,
+        WeightKind::Reliability => total - pairs.iter().map(|p| p.1 * p.1).sum::<f64>() / total,
+    };
+    if pairs.len() < 2 || denom <= 0.0 {
+        f64::NAN
+    } else {
+        ss / denom
+    }
+}
+
+/// Weighted quantiles of pairs sorted by value (see module docs)
+fn quantiles_of(sorted: &[(f64, f64)], probs: &[f64], out: &mut Vec<f64>) {
+    let n = sorted.len();
+    if n == 0 {
+        out.extend(std::iter::repeat_n(f64::NAN, probs.len()));
+        return;
+    }
+    if n == 1 {
+        out.extend(std::iter::repeat_n(sorted[0].0, probs.len()));
+        return;
+    }
+    let total: f64 = sorted.iter().map(|p| p.1).sum();
+    let first = sorted[0].1 / 2.0;
+    let span = total - first - sorted[n - 1].1 / 2.0;
+    let mut positions = Vec::with_capacity(n);
+    let mut cum = 0.0;
+    for &(_, w) in sorted {
+        cum += w;
+        positions.push(((cum - w / 2.0 - first) / span).clamp(0.0, 1.0));
+    }
+    for &p in probs {
+        // First point at or above p; interpolate from the one before it
+        let hi = positions.partition_point(|&q| q < p).min(n - 1);
+        let q = if hi == 0 || positions[hi] <= p {
+            sorted[hi].0
+        } else {
+            let lo = hi - 1;
+            let gamma = (p - positions[lo]) / (positions[hi] - positions[lo]);
+            sorted[lo].0 + gamma * (sorted[hi].0 - sorted[lo].0)
+        };
+        out.push(q);
+    }
+}
+
+fn sort_by_value(pairs: &mut [(f64, f64)]) {
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+}
+
+/// Weighted mean per group
+pub fn weighted_mean(
+    vals: &[f64],
+    weights: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: usize,
+) -> Result<Vec<f64>, String> {
+    let wg = collect_groups(vals, weights, valid, gid_per_row, n_groups)?;
+    Ok(wg.groups.iter().map(|g| mean_of(g)).collect())
+}
+
+/// Weighted variance per group (NaN with fewer than two values or a
+/// non-positive denominator)
+pub fn weighted_var(
+    vals: &[f64],
+    weights: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: usize,
+    kind: WeightKind,
+) -> Result<Vec<f64>, String> {
+    let wg = collect_groups(vals, weights, valid, gid_per_row, n_groups)?;
+    Ok(wg.groups.iter().map(|g| var_of(g, kind)).collect())
+}
+
+/// Weighted quantiles per group, row-major by group
+/// (`out[g * probs.len() + k]` is quantile `probs[k]` of group `g`)
+pub fn weighted_quantile(
+    vals: &[f64],
+    weights: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: usize,
+    probs: &[f64],
+) -> Result<Vec<f64>, String> {
+    if let Some(p) = probs.iter().find(|p| !(0.0..=1.0).contains(*p)) {
+        return Err(format!("Probability {} is not in [0,1]", p));
+    }
+    let wg = collect_groups(vals, weights, valid, gid_per_row, n_groups)?;
+    let mut out = Vec::with_capacity(wg.groups.len() * probs.len());
+    for mut g in wg.groups {
+        sort_by_value(&mut g);
+        quantiles_of(&g, probs, &mut out);
+    }
+    Ok(out)
+}
+
+/// Weighted median per group
+pub fn weighted_median(
+    vals: &[f64],
+    weights: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: usize,
+) -> Result<Vec<f64>, String> {
+    weighted_quantile(vals, weights, valid, gid_per_row, n_groups, &[0.5])
+}
+
+/// Weighted ECDF per group evaluated at `at`: the share of weight with value
+/// `<= t`. Row-major by group like [`weighted_quantile`].
+pub fn weighted_ecdf(
+    vals: &[f64],
+    weights: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: usize,
+    at: &[f64],
+) -> Result<Vec<f64>, String> {
+    let wg = collect_groups(vals, weights, valid, gid_per_row, n_groups)?;
+    let mut out = Vec::with_capacity(wg.groups.len() * at.len());
+    for mut g in wg.groups {
+        sort_by_value(&mut g);
+        let mut cum = Vec::with_capacity(g.len());
+        let mut total = 0.0;
+        for &(_, w) in &g {
+            total += w;
+            cum.push(total);
+        }
+        for &t in at {
+            let k = g.partition_point(|&(x, _)| x <= t);
+            out.push(match k {
+                _ if g.is_empty() || t.is_nan() => f64::NAN,
+                0 => 0.0,
+                k => cum[k - 1] / total,
+            });
+        }
+    }
+    Ok(out)
+}
+
+/// Weighted ECDF of each row's own value within its group (a weighted
+/// `cume_dist`). Rows that do not contribute get NaN.
+pub fn weighted_cume_dist(
+    vals: &[f64],
+    weights: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: usize,
+) -> Result<Vec<f64>, String> {
+    let wg = collect_groups(vals, weights, valid, gid_per_row, n_groups)?;
+    // (sorted values, cumulative weight shares) per group
+    let tables: Vec<(Vec<f64>, Vec<f64>)> = wg
+        .groups
+        .into_iter()
+        .map(|mut g| {
+            sort_by_value(&mut g);
+            let total: f64 = g.iter().map(|p| p.1).sum();
+            let mut cum = 0.0;
+            let shares = g
+                .iter()
+                .map(|&(_, w)| {
+                    cum += w;
+                    cum / total
+                })
+                .collect();
+            (g.into_iter().map(|p| p.0).collect(), shares)
+        })
+        .collect();
+    Ok((0..vals.len())
+        .map(|r| {
+            if !wg.used[r] {
+                return f64::NAN;
+            }
+            let g = gid_per_row.get(r).map_or(0, |&g| g as usize);
+            let (xs, shares) = &tables[g];
+            let k = xs.partition_point(|&x| x <= vals[r]);
+            shares[k - 1]
+        })
+        .collect())
+}
+
+#[cfg(feature = "wasm")]
+fn parse_kind(kind: u8) -> Result<WeightKind, JsValue> {
+    match kind {
+        0 => Ok(WeightKind::Frequency),
+        1 => Ok(WeightKind::Reliability),
+        _ => Err(JsValue::from_str(&format!(
+            "Invalid weight kind {} (0 = frequency, 1 = reliability)",
+            kind
+        ))),
+    }
+}
+
+#[cfg(feature = "wasm")]
+fn to_js(e: String) -> JsValue {
+    JsValue::from_str(&format!("Weighted stats error: {}", e))
+}
+
+/// WASM export for grouped weighted means
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn weighted_mean_wasm(
+    vals: &[f64],
+    weights: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+) -> Result<Vec<f64>, JsValue> {
+    weighted_mean(vals, weights, valid, gid_per_row, n_groups as usize).map_err(to_js)
+}
+
+/// WASM export for grouped weighted variances
+///
+/// - `kind`: 0 = frequency weights, 1 = reliability (sampling) weights
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn weighted_var_wasm(
+    vals: &[f64],
+    weights: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+    kind: u8,
+) -> Result<Vec<f64>, JsValue> {
+    let kind = parse_kind(kind)?;
+    weighted_var(vals, weights, valid, gid_per_row, n_groups as usize, kind).map_err(to_js)
+}
+
+/// WASM export for grouped weighted quantiles (row-major by group)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn weighted_quantile_wasm(
+    vals: &[f64],
+    weights: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+    probs: &[f64],
+) -> Result<Vec<f64>, JsValue> {
+    weighted_quantile(vals, weights, valid, gid_per_row, n_groups as usize, probs).map_err(to_js)
+}
+
+/// WASM export for grouped weighted medians
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn weighted_median_wasm(
+    vals: &[f64],
+    weights: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+) -> Result<Vec<f64>, JsValue> {
+    weighted_median(vals, weights, valid, gid_per_row, n_groups as usize).map_err(to_js)
+}
+
+/// WASM export for the grouped weighted ECDF at given points (row-major by group)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn weighted_ecdf_wasm(
+    vals: &[f64],
+    weights: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+    at: &[f64],
+) -> Result<Vec<f64>, JsValue> {
+    weighted_ecdf(vals, weights, valid, gid_per_row, n_groups as usize, at).map_err(to_js)
+}
+
+/// WASM export for the per-row weighted ECDF within groups
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn weighted_cume_dist_wasm(
+    vals: &[f64],
+    weights: &[f64],
+    valid: &[u8],
+    gid_per_row: &[u32],
+    n_groups: u32,
+) -> Result<Vec<f64>, JsValue> {
+    weighted_cume_dist(vals, weights, valid, gid_per_row, n_groups as usize).map_err(to_js)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataframe::quantile::quantile;
+    use crate::dataframe::shared_types::QuantileType;
+
+    fn close(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len()
+            && a.iter()
+                .zip(b)
+                .all(|(x, y)| (x.is_nan() && y.is_nan()) || (x - y).abs() < 1e-12)
+    }
+
+    #[test]
+    fn test_unit_weights_match_type7() {
+        // Ties and an unsorted input
+        let vals: Vec<f64> = (0..37).map(|i| ((i * 17) % 23) as f64 / 4.0).collect();
+        let probs: Vec<f64> = (0..=20).map(|k| k as f64 / 20.0).collect();
+        let expected = quantile(&vals, &probs, QuantileType::Type7).unwrap();
+        let unweighted = weighted_quantile(&vals, &[], &[], &[], 1, &probs).unwrap();
+        assert!(close(&unweighted, &expected), "{:?}", unweighted);
+        let ones = vec![1.0; vals.len()];
+        let weighted = weighted_quantile(&vals, &ones, &[], &[], 1, &probs).unwrap();
+        assert!(close(&weighted, &expected), "{:?}", weighted);
+        let median = weighted_median(&vals, &ones, &[], &[], 1).unwrap();
+        assert!(close(&median, &expected[10..11]));
+    }
+
+    #[test]
+    fn test_variance_kinds() {
+        let vals = [1.0, 2.0, 4.0];
+        let weights = [1.0, 2.0, 1.0];
+        // Frequency weights equal the sample variance of [1, 2, 2, 4]: 4.75 / 3
+        let freq = weighted_var(&vals, &weights, &[], &[], 1, WeightKind::Frequency).unwrap();
+        assert!(close(&freq, &[4.75 / 3.0]));
+        // Reliability: 4.75 / (4 - 6 / 4), unchanged by rescaling the weights
+        let rel = weighted_var(&vals, &weights, &[], &[], 1, WeightKind::Reliability).unwrap();
+        assert!(close(&rel, &[1.9]));
+        let scaled = [10.0, 20.0, 10.0];
+        let rel10 = weighted_var(&vals, &scaled, &[], &[], 1, WeightKind::Reliability).unwrap();
+        assert!(close(&rel10, &[1.9]));
+        // Total weight of one leaves no frequency degrees of freedom
+        let half = [0.5, 0.5, 0.0];
+        let none = weighted_var(&vals, &half, &[], &[], 1, WeightKind::Frequency).unwrap();
+        assert!(none[0].is_nan());
+        let mean = weighted_mean(&vals, &weights, &[], &[], 1).unwrap();
+        assert!(close(&mean, &[2.25]));
+    }
+
+    #[test]
+    fn test_grouped_layout() {
+        // Group 1 is empty once its missing and zero-weight rows are dropped
+        let vals = [1.0, 10.0, 3.0, 20.0, 2.0, f64::NAN, 30.0];
+        let weights = [1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.0];
+        let valid = [1, 1, 1, 0, 1, 1, 1];
+        let gid = [0, 2, 0, 1, 0, 1, 1];
+        let probs = [0.0, 0.5, 1.0];
+        let q = weighted_quantile(&vals, &weights, &valid, &gid, 3, &probs).unwrap();
+        let nan = f64::NAN;
+        assert!(close(&q, &[1.0, 2.0, 3.0, nan, nan, nan, 10.0, 10.0, 10.0]));
+        let mean = weighted_mean(&vals, &weights, &valid, &gid, 3).unwrap();
+        assert!(close(&mean, &[2.0, nan, 10.0]));
+        assert!(weighted_quantile(&vals, &weights, &valid, &gid, 2, &probs).is_err());
+        assert!(weighted_quantile(&vals, &weights, &valid, &gid, 3, &[1.5]).is_err());
+    }
+
+    #[test]
+    fn test_ecdf_and_cume_dist() {
+        let vals = [3.0, 1.0, 2.0, 2.0, 5.0];
+        let weights = [1.0, 1.0, 2.0, 0.0, 1.0];
+        let gid = [0, 0, 0, 0, 1];
+        let at = [0.0, 1.0, 2.0, 2.5, 3.0, f64::NAN];
+        let ecdf = weighted_ecdf(&vals, &weights, &[], &gid, 2, &at).unwrap();
+        let nan = f64::NAN;
+        let expected = [
+            [0.0, 0.25, 0.75, 0.75, 1.0, nan],
+            [0.0, 0.0, 0.0, 0.0, 0.0, nan],
+        ]
+        .concat();
+        assert!(close(&ecdf, &expected), "{:?}", ecdf);
+
+        // The zero-weight row does not contribute, so it gets NaN
+        let cume = weighted_cume_dist(&vals, &weights, &[], &gid, 2).unwrap();
+        assert!(close(&cume, &[1.0, 0.25, 0.75, nan, 1.0]), "{:?}", cume);
+        let masked = weighted_cume_dist(&vals, &weights, &[1, 0, 1, 1, 1], &gid, 2).unwrap();
+        assert!(
+            close(&masked, &[1.0, nan, 2.0 / 3.0, nan, 1.0]),
+            "{:?}",
+            masked
+        );
+    }
+
+    #[test]
+    fn test_invalid_weights() {
+        let vals = [1.0, 2.0, 3.0];
+        for bad in [-1.0, f64::INFINITY, f64::NEG_INFINITY] {
+            let weights = [1.0, bad, 1.0];
+            assert!(weighted_mean(&vals, &weights, &[], &[], 1).is_err());
+            assert!(weighted_quantile(&vals, &weights, &[], &[], 1, &[0.5]).is_err());
+            assert!(weighted_ecdf(&vals, &weights, &[], &[], 1, &[2.0]).is_err());
+        }
+        // NaN weights skip the row
+        let mean = weighted_mean(&vals, &[1.0, f64::NAN, 1.0], &[], &[], 1).unwrap();
+        assert!(close(&mean, &[2.0]));
+        assert!(weighted_mean(&vals, &[1.0, 1.0], &[], &[], 1).is_err());
+    }
+}
@@ -0,0 +1,938 @@
+//! Arrow C data interface
+//!
+//! Tables are exported as a struct array with one child per column. Numeric,
+//! int32, date and dictionary-code buffers are handed over without copying:
+//! the exported array owns the column vectors until its `release` callback
+//! runs. String columns are exported as `Dictionary<UInt32, Utf8>` so the
+//! consumer sees the same codes the dataframe kernels use.
+//!
+//! Import is zero-copy for the same buffers: [`ImportedTable`] keeps the
+//! producer's structures alive and borrows float64, int32, date32 and 32-bit
+//! dictionary-index buffers in place, releasing them only when it is dropped.
+//! Other types (and misaligned buffers) are converted into owned columns, and
+//! validity bitmaps are unpacked into masks. [`import_table`] copies
+//! everything into a [`Table`] and releases the producer's structures at once.
+
+#![deny(unsafe_op_in_unsafe_fn)]
+
+use super::format::{
+    ArrowType, bitmap_from_mask, decode_column, decode_dictionary_column, encode_strings,
+    string_values, with_missing,
+};
+use crate::io::columns::{Column, ColumnData, ColumnType, Table};
+use std::ffi::{CStr, CString, c_char, c_void};
+use std::ptr;
+use std::sync::Arc;
+
+/// Field may contain nulls
+pub const ARROW_FLAG_NULLABLE: i64 = 2;
+
+/// `struct ArrowSchema` from the C data interface specification
+#[repr(C)]
+#[derive(Debug)]
+pub struct ArrowSchema {
+    pub format: *const c_char,
+    pub name: *const c_char,
+    pub metadata: *const c_char,
+    pub flags: i64,
+    pub n_children: i64,
+    pub children: *mut *mut ArrowSchema,
+    pub dictionary: *mut ArrowSchema,
+    pub release: Option<unsafe extern "C" fn(*mut ArrowSchema)>,
+    pub private_data: *mut c_void,
+}
+
+/// `struct ArrowArray` from the C data interface specification
+#[repr(C)]
+#[derive(Debug)]
+pub struct ArrowArray {
+    pub length: i64,
+    pub null_count: i64,
+    pub offset: i64,
+    pub n_buffers: i64,
+    pub n_children: i64,
+    pub buffers: *mut *const c_void,
+    pub children: *mut *mut ArrowArray,
+    pub dictionary: *mut ArrowArray,
+    pub release: Option<unsafe extern "C" fn(*mut ArrowArray)>,
+    pub private_data: *mut c_void,
+}
+
+impl ArrowSchema {
+    /// A released (empty) schema, ready to be filled by a producer
+    pub fn empty() -> Self {
+        ArrowSchema {
+            format: ptr::null(),
+            name: ptr::null(),
+            metadata: ptr::null(),
+            flags: 0,
+            n_children: 0,
+            children: ptr::null_mut(),
+            dictionary: ptr::null_mut(),
+            release: None,
+            private_data: ptr::null_mut(),
+        }
+    }
+
+    pub fn is_released(&self) -> bool {
+        self.release.is_none()
+    }
+}
+
+impl ArrowArray {
+    /// A released (empty) array, ready to be filled by a producer
+    pub fn empty() -> Self {
+        ArrowArray {
+            length: 0,
+            null_count: 0,
+            offset: 0,
+            n_buffers: 0,
+            n_children: 0,
+            buffers: ptr::null_mut(),
+            children: ptr::null_mut(),
+            dictionary: ptr::null_mut(),
+            release: None,
+            private_data: ptr::null_mut(),
+        }
+    }
+
+    pub fn is_released(&self) -> bool {
+        self.release.is_none()
+    }
+}
+
+impl Drop for ArrowSchema {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            // SAFETY: a non-null release callback was set by the producer for this struct
+            unsafe { release(self) };
+        }
+    }
+}
+
+impl Drop for ArrowArray {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            // SAFETY: a non-null release callback was set by the producer for this struct
+            unsafe { release(self) };
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Export
+// ---------------------------------------------------------------------------
+
+struct SchemaPrivate {
+    _format: CString,
+    _name: CString,
+    children: Vec<*mut ArrowSchema>,
+    dictionary: *mut ArrowSchema,
+}
+
+/// Owned storage behind exported buffers
+enum Storage {
+    Bytes(Vec<u8>),
+    I32(Vec<i32>),
+    U32(Vec<u32>),
+    F64(Vec<f64>),
+    I64(Vec<i64>),
+}
+
+impl Storage {
+    fn ptr(&self) -> *const c_void {
+        match self {
+            Storage::Bytes(v) => v.as_ptr() as *const c_void,
+            Storage::I32(v) => v.as_ptr() as *const c_void,
+            Storage::U32(v) => v.as_ptr() as *const c_void,
+            Storage::F64(v) => v.as_ptr() as *const c_void,
+            Storage::I64(v) => v.as_ptr() as *const c_void,
+        }
+    }
+}
+
+struct ArrayPrivate {
+    _storage: Vec<Storage>,
+    buffers: Vec<*const c_void>,
+    children: Vec<*mut ArrowArray>,
+    dictionary: *mut ArrowArray,
+}
+
+unsafe extern "C" fn release_schema(schema: *mut ArrowSchema) {
+    // SAFETY: only installed on schemas built by `new_schema`, whose private
+    // data is a leaked `Box<SchemaPrivate>` and whose children are leaked boxes
+    unsafe {
+        let Some(schema) = schema.as_mut() else {
+            return;
+        };
+        let private = Box::from_raw(schema.private_data as *mut SchemaPrivate);
+        for child in private.children {
+            drop(Box::from_raw(child));
+        }
+        if !private.dictionary.is_null() {
+            drop(Box::from_raw(private.dictionary));
+        }
+        schema.release = None;
+        schema.private_data = ptr::null_mut();
+    }
+}
+
+unsafe extern "C" fn release_array(array: *mut ArrowArray) {
+    // SAFETY: only installed on arrays built by `new_array`, whose private
+    // data is a leaked `Box<ArrayPrivate>` and whose children are leaked boxes
+    unsafe {
+        let Some(array) = array.as_mut() else {
+            return;
+        };
+        let private = Box::from_raw(array.private_data as *mut ArrayPrivate);
+        for child in private.children {
+            drop(Box::from_raw(child));
+        }
+        if !private.dictionary.is_null() {
+            drop(Box::from_raw(private.dictionary));
+        }
+        array.release = None;
+        array.private_data = ptr::null_mut();
+    }
+}
+
+fn new_schema(
+    format: &str,
+    name: &str,
+    children: Vec<ArrowSchema>,
+    dictionary: Option<ArrowSchema>,
+) -> ArrowSchema {
+    let format = CString::new(format).expect("format strings contain no NUL");
+    let name = CString::new(name.replace('\0', "")).expect("NUL bytes removed");
+    let mut private = Box::new(SchemaPrivate {
+        children: children
+            .into_iter()
+            .map(|c| Box::into_raw(Box::new(c)))
+            .collect(),
+        dictionary: dictionary.map_or(ptr::null_mut(), |d| Box::into_raw(Box::new(d))),
+        _format: format,
+        _name: name,
+    });
+    ArrowSchema {
+        format: private._format.as_ptr(),
+        name: private._name.as_ptr(),
+        metadata: ptr::null(),
+        flags: ARROW_FLAG_NULLABLE,
+        n_children: private.children.len() as i64,
+        children: private.children.as_mut_ptr(),
+        dictionary: private.dictionary,
+        release: Some(release_schema),
+        private_data: Box::into_raw(private) as *mut c_void,
+    }
+}
+
+/// `buffers` entries are `None` for an absent (null) buffer
+fn new_array(
+    length: usize,
+    null_count: usize,
+    buffers: Vec<Option<Storage>>,
+    children: Vec<ArrowArray>,
+    dictionary: Option<ArrowArray>,
+) -> ArrowArray {
+    let ptrs = buffers
+        .iter()
+        .map(|b| b.as_ref().map_or(ptr::null(), Storage::ptr))
+        .collect();
+    let mut private = Box::new(ArrayPrivate {
+        _storage: buffers.into_iter().flatten().collect(),
+        buffers: ptrs,
+        children: children
+            .into_iter()
+            .map(|c| Box::into_raw(Box::new(c)))
+            .collect(),
+        dictionary: dictionary.map_or(ptr::null_mut(), |d| Box::into_raw(Box::new(d))),
+    });
+    ArrowArray {
+        length: length as i64,
+        null_count: null_count as i64,
+        offset: 0,
+        n_buffers: private.buffers.len() as i64,
+        n_children: private.children.len() as i64,
+        buffers: private.buffers.as_mut_ptr(),
+        children: private.children.as_mut_ptr(),
+        dictionary: private.dictionary,
+        release: Some(release_array),
+        private_data: Box::into_raw(private) as *mut c_void,
+    }
+}
+
+fn export_column(column: Column) -> (ArrowSchema, ArrowArray) {
+    let n = column.len();
+    let (bitmap, nulls) = bitmap_from_mask(&column.validity);
+    let validity = (nulls > 0).then_some(Storage::Bytes(bitmap));
+    let ty = ArrowType::for_column(column.column_type());
+    match column.data {
+        ColumnData::Dictionary { codes, dictionary } => {
+            let (offsets, data) = encode_strings(&dictionary);
+            let values = new_array(
+                dictionary.len(),
+                0,
+                vec![
+                    None,
+                    Some(Storage::Bytes(offsets)),
+                    Some(Storage::Bytes(data)),
+                ],
+                Vec::new(),
+                None,
+            );
+            let value_schema = new_schema("u", "", Vec::new(), None);
+            (
+                new_schema("I", &column.name, Vec::new(), Some(value_schema)),
+                new_array(
+                    n,
+                    nulls,
+                    vec![validity, Some(Storage::U32(codes))],
+                    Vec::new(),
+                    Some(values),
+                ),
+            )
+        }
+        data => {
+            let values = match data {
+                ColumnData::Boolean(v) => Storage::Bytes(bitmap_from_mask(&v).0),
+                ColumnData::Int32(v) | ColumnData::Date(v) => Storage::I32(v),
+                ColumnData::Float64(v) => Storage::F64(v),
+                ColumnData::Datetime(v) => Storage::I64(
+                    v.into_iter()
+                        .map(|x| if x.is_finite() { x.round() as i64 } else { 0 })
+                        .collect(),
+                ),
+                ColumnData::Dictionary { .. } => unreachable!("handled above"),
+            };
+            (
+                new_schema(&ty.format(), &column.name, Vec::new(), None),
+                new_array(n, nulls, vec![validity, Some(values)], Vec::new(), None),
+            )
+        }
+    }
+}
+
+/// Export a table as a struct array. Ownership of the column buffers moves
+/// into the returned structures and is freed by their `release` callbacks
+/// (or on drop if they are never handed to a consumer).
+pub fn export_table(table: Table) -> (ArrowSchema, ArrowArray) {
+    let n_rows = table.n_rows();
+    let (schemas, arrays): (Vec<_>, Vec<_>) = table.columns.into_iter().map(export_column).unzip();
+    let mut schema = new_schema("+s", "", schemas, None);
+    schema.flags = 0;
+    (schema, new_array(n_rows, 0, vec![None], arrays, None))
+}
+
+// ---------------------------------------------------------------------------
+// Import
+// ---------------------------------------------------------------------------
+
+/// Borrow buffer `i` of `array` as `len` bytes (empty for a null pointer)
+///
+/// # Safety
+/// `array.buffers` must hold `n_buffers` pointers, each valid for `len` bytes.
+unsafe fn buffer(array: &ArrowArray, i: usize, len: usize) -> Result<&[u8], String> {
+    if i >= array.n_buffers as usize {
+        return Err("Arrow array has too few buffers".to_string());
+    }
+    // SAFETY: guaranteed by the caller
+    unsafe {
+        let p = *array.buffers.add(i) as *const u8;
+        if p.is_null() || len == 0 {
+            Ok(&[])
+        } else {
+            Ok(std::slice::from_raw_parts(p, len))
+        }
+    }
+}
+
+/// Validity mask of `array`
+///
+/// # Safety
+/// As for [`buffer`].
+unsafe fn import_validity(array: &ArrowArray) -> Result<Vec<u8>, String> {
+    let (len, offset) = (array.length as usize, array.offset as usize);
+    if array.null_count == 0 || array.n_buffers == 0 {
+        return Ok(vec![1; len]);
+    }
+    // SAFETY: the validity bitmap covers `offset + length` bits
+    let bits = unsafe { buffer(array, 0, (offset + len).div_ceil(8))? };
+    if bits.is_empty() {
+        return Ok(vec![1; len]);
+    }
+    super::format::mask_from_bitmap(Some(bits), offset, len)
+}
+
+/// Data buffers (after validity) of a non-dictionary array of type `ty`
+///
+/// # Safety
+/// `array` must be a valid C data interface array of type `ty`.
+unsafe fn data_buffers<'a>(array: &'a ArrowArray, ty: &ArrowType) -> Result<Vec<&'a [u8]>, String> {
+    let end = (array.offset + array.length) as usize;
+    // SAFETY: buffer sizes follow from the type, offset and length per the specification
+    unsafe {
+        Ok(match ty {
+            ArrowType::Null => Vec::new(),
+            ArrowType::Bool => vec![buffer(array, 1, end.div_ceil(8))?],
+            ArrowType::Int { bits, .. } | ArrowType::Float { bits } => {
+                vec![buffer(array, 1, end * (*bits as usize / 8))?]
+            }
+            ArrowType::Date { millis: false } => vec![buffer(array, 1, end * 4)?],
+            ArrowType::Date { millis: true } | ArrowType::Timestamp { .. } => {
+                vec![buffer(array, 1, end * 8)?]
+            }
+            ArrowType::Utf8 | ArrowType::LargeUtf8 => {
+                let width = if *ty == ArrowType::LargeUtf8 { 8 } else { 4 };
+                let offsets = buffer(array, 1, (end + 1) * width)?;
+                let data_len = if offsets.is_empty() {
+                    0
+                } else {
+                    let last = &offsets[end * width..];
+                    if width == 8 {
+                        i64::from_le_bytes(last.try_into().expect("8 bytes")) as usize
+                    } else {
+                        i32::from_le_bytes(last.try_into().expect("4 bytes")) as usize
+                    }
+                };
+                vec![offsets, buffer(array, 2, data_len)?]
+            }
+        })
+    }
+}
+
+/// Address of element `start` of the data buffer when it can be borrowed as
+/// `T` in place: little-endian, present and aligned
+///
+/// # Safety
+/// As for [`buffer`]; `start + len` must not exceed `array.length`.
+unsafe fn borrow_values<T>(array: &ArrowArray, start: usize, len: usize) -> Option<*const T> {
+    if cfg!(target_endian = "big") || array.n_buffers < 2 {
+        return None;
+    }
+    if len == 0 {
+        return Some(ptr::NonNull::dangling().as_ptr());
+    }
+    // SAFETY: guaranteed by the caller
+    let p = unsafe { *array.buffers.add(1) } as *const T;
+    if p.is_null() || !p.is_aligned() {
+        return None;
+    }
+    let first = (array.offset as usize).checked_add(start)?;
+    // SAFETY: the buffer holds `offset + length` elements
+    Some(unsafe { p.add(first) })
+}
+
+/// Values of an imported column: borrowed from the producer's buffers, or
+/// converted into owned data
+#[derive(Debug)]
+enum Values {
+    Float64(*const f64),
+    Int32(*const i32),
+    Date(*const i32),
+    Codes {
+        codes: *const u32,
+        dictionary: Arc<Vec<String>>,
+    },
+    Owned(ColumnData),
+}
+
+/// Values of an imported column as seen through [`ImportedColumn::values`].
+///
+/// Borrowed slices hold whatever the producer wrote in null slots; check
+/// [`ImportedColumn::validity`] before reading them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColumnView<'a> {
+    Float64(&'a [f64]),
+    Int32(&'a [i32]),
+    /// Epoch days
+    Date(&'a [i32]),
+    /// String column as dictionary codes
+    Codes {
+        codes: &'a [u32],
+        dictionary: &'a Arc<Vec<String>>,
+    },
+    /// Converted on import, with the usual missing sentinels
+    Owned(&'a ColumnData),
+}
+
+/// One column of an [`ImportedTable`]
+#[derive(Debug)]
+pub struct ImportedColumn {
+    name: String,
+    validity: Vec<u8>,
+    values: Values,
+}
+
+impl ImportedColumn {
+    fn owned(column: Column) -> Self {
+        ImportedColumn {
+            name: column.name,
+            validity: column.validity,
+            values: Values::Owned(column.data),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn len(&self) -> usize {
+        self.validity.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validity.is_empty()
+    }
+
+    /// Validity mask (1 = present)
+    pub fn validity(&self) -> &[u8] {
+        &self.validity
+    }
+
+    pub fn column_type(&self) -> ColumnType {
+        match &self.values {
+            Values::Float64(_) => ColumnType::Float64,
+            Values::Int32(_) => ColumnType::Int32,
+            Values::Date(_) => ColumnType::Date,
+            Values::Codes { .. } => ColumnType::String,
+            Values::Owned(data) => data.column_type(),
+        }
+    }
+
+    /// Whether the values are read in place from the producer's buffers
+    pub fn is_borrowed(&self) -> bool {
+        !matches!(self.values, Values::Owned(_))
+    }
+
+    pub fn values(&self) -> ColumnView<'_> {
+        let n = self.len();
+        // SAFETY: the pointers were checked non-null and aligned on import and
+        // cover `n` elements of buffers that the owning `ImportedTable` keeps
+        // alive; the views cannot outlive it since columns are only lent out
+        unsafe {
+            match &self.values {
+                Values::Float64(p) => ColumnView::Float64(std::slice::from_raw_parts(*p, n)),
+                Values::Int32(p) => ColumnView::Int32(std::slice::from_raw_parts(*p, n)),
+                Values::Date(p) => ColumnView::Date(std::slice::from_raw_parts(*p, n)),
+                Values::Codes { codes, dictionary } => ColumnView::Codes {
+                    codes: std::slice::from_raw_parts(*codes, n),
+                    dictionary,
+                },
+                Values::Owned(data) => ColumnView::Owned(data),
+            }
+        }
+    }
+
+    /// Copy into an owned column, writing missing sentinels into null slots
+    pub fn to_column(&self) -> Column {
+        let data = match self.values() {
+            ColumnView::Float64(v) => ColumnData::Float64(v.to_vec()),
+            ColumnView::Int32(v) => ColumnData::Int32(v.to_vec()),
+            ColumnView::Date(v) => ColumnData::Date(v.to_vec()),
+            ColumnView::Codes { codes, dictionary } => ColumnData::Dictionary {
+                codes: codes.to_vec(),
+                dictionary: dictionary.clone(),
+            },
+            ColumnView::Owned(data) => data.clone(),
+        };
+        with_missing(&self.name, data, self.validity.clone())
+    }
+}
+
+/// A table imported through the C data interface without copying its
+/// fixed-width buffers. The producer's `release` callbacks run when this is
+/// dropped.
+#[derive(Debug)]
+pub struct ImportedTable {
+    columns: Vec<ImportedColumn>,
+    // Dropped after the columns that point into their buffers
+    _schema: ArrowSchema,
+    _array: ArrowArray,
+}
+
+impl ImportedTable {
+    /// Import a struct array (one column per child) or a single array (one
+    /// column), taking ownership of both structures
+    ///
+    /// # Safety
+    /// `schema` and `array` must be valid, matching C data interface
+    /// structures whose buffers stay alive until their `release` callbacks run.
+    pub unsafe fn new(schema: ArrowSchema, array: ArrowArray) -> Result<Self, String> {
+        if schema.is_released() || array.is_released() {
+            return Err("Arrow structures have already been released".to_string());
+        }
+        // SAFETY: guaranteed by the caller; both structures are released when dropped
+        let columns = unsafe {
+            let format = CStr::from_ptr(schema.format).to_string_lossy().into_owned();
+            let (start, len) = (array.offset as usize, array.length as usize);
+            if format == "+s" {
+                if schema.n_children != array.n_children {
+                    return Err(format!(
+                        "Arrow schema has {} children but the array has {}",
+                        schema.n_children, array.n_children
+                    ));
+                }
+                let n = schema.n_children.max(0) as usize;
+                let mut columns = Vec::with_capacity(n);
+                for i in 0..n {
+                    let child_schema = &**schema.children.add(i);
+                    let child = &**array.children.add(i);
+                    // Apply the parent's offset and length
+                    columns.push(import_column(child_schema, child, start, len)?);
+                }
+                columns
+            } else {
+                vec![import_column(&schema, &array, 0, len)?]
+            }
+        };
+        Ok(ImportedTable {
+            columns,
+            _schema: schema,
+            _array: array,
+        })
+    }
+
+    /// Import from structures at the given addresses, moving them out so
+    /// that they read as released afterwards
+    ///
+    /// # Safety
+    /// Both pointers must be non-null, aligned and point to valid, matching,
+    /// unreleased `ArrowSchema` / `ArrowArray` structures that nothing else
+    /// accesses during the call; see [`ImportedTable::new`] for the buffers.
+    pub unsafe fn from_ptrs(
+        schema: *mut ArrowSchema,
+        array: *mut ArrowArray,
+    ) -> Result<Self, String> {
+        if schema.is_null() || array.is_null() {
+            return Err("null pointer".to_string());
+        }
+        // SAFETY: guaranteed by the caller; the empty structures left behind
+        // are marked released
+        unsafe {
+            let schema = ptr::replace(schema, ArrowSchema::empty());
+            let array = ptr::replace(array, ArrowArray::empty());
+            ImportedTable::new(schema, array)
+        }
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.columns.first().map_or(0, ImportedColumn::len)
+    }
+
+    pub fn n_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn columns(&self) -> &[ImportedColumn] {
+        &self.columns
+    }
+
+    /// Copy into an owned table
+    pub fn to_table(&self) -> Table {
+        Table {
+            columns: self.columns.iter().map(ImportedColumn::to_column).collect(),
+        }
+    }
+}
+
+/// Import rows `start..start + len` (clamped to the array) of one column
+///
+/// # Safety
+/// `schema` and `array` must be valid, matching C data interface structures.
+unsafe fn import_column(
+    schema: &ArrowSchema,
+    array: &ArrowArray,
+    start: usize,
+    len: usize,
+) -> Result<ImportedColumn, String> {
+    // SAFETY: the producer guarantees NUL-terminated strings and valid pointers
+    unsafe {
+        let format = CStr::from_ptr(schema.format).to_string_lossy().into_owned();
+        let name = if schema.name.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(schema.name).to_string_lossy().into_owned()
+        };
+        let ty =
+            ArrowType::from_format(&format).map_err(|e| format!("Column '{}': {}", name, e))?;
+        let validity = import_validity(array)?;
+        let offset = array.offset as usize;
+        let end = start.saturating_add(len).min(validity.len());
+        let start = start.min(end);
+        let window = |validity: &[u8]| validity[start..end].to_vec();
+
+        if let (Some(value_schema), Some(values)) =
+            (schema.dictionary.as_ref(), array.dictionary.as_ref())
+        {
+            let value_format = CStr::from_ptr(value_schema.format).to_string_lossy();
+            let value_ty = ArrowType::from_format(&value_format)?;
+            if !matches!(value_ty, ArrowType::Utf8 | ArrowType::LargeUtf8) {
+                return Err(format!(
+                    "Column '{}': only string dictionaries are supported",
+                    name
+                ));
+            }
+            let value_validity = import_validity(values)?;
+            let bufs = data_buffers(values, &value_ty)?;
+            let dictionary: Vec<String> = string_values(
+                value_ty == ArrowType::LargeUtf8,
+                bufs[0],
+                bufs[1],
+                &value_validity,
+                values.offset as usize,
+            )?
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect();
+            // 32-bit indices double as codes once they are known to be in range
+            if let ArrowType::Int { bits: 32, signed } = ty
+                && let Some(codes) = borrow_values::<u32>(array, start, end - start)
+            {
+                let validity = window(&validity);
+                let view = std::slice::from_raw_parts(codes, validity.len());
+                for (&k, &ok) in view.iter().zip(&validity) {
+                    if ok != 0 && k as usize >= dictionary.len() {
+                        let k = if signed { k as i32 as i64 } else { k as i64 };
+                        return Err(format!("Dictionary index {} out of range in '{}'", k, name));
+                    }
+                }
+                return Ok(ImportedColumn {
+                    name,
+                    validity,
+                    values: Values::Codes {
+                        codes,
+                        dictionary: Arc::new(dictionary),
+                    },
+                });
+            }
+            let indices = data_buffers(array, &ty)?;
+            let column =
+                decode_dictionary_column(&name, &ty, validity, indices[0], offset, &dictionary)?;
+            return Ok(ImportedColumn::owned(slice_column(
+                column,
+                start,
+                end - start,
+            )));
+        }
+        let n = end - start;
+        let borrowed = match ty {
+            ArrowType::Float { bits: 64 } => borrow_values(array, start, n).map(Values::Float64),
+            ArrowType::Int {
+                bits: 32,
+                signed: true,
+            } => borrow_values(array, start, n).map(Values::Int32),
+            ArrowType::Date { millis: false } => borrow_values(array, start, n).map(Values::Date),
+            _ => None,
+        };
+        if let Some(values) = borrowed {
+            return Ok(ImportedColumn {
+                name,
+                validity: window(&validity),
+                values,
+            });
+        }
+        let buffers = data_buffers(array, &ty)?;
+        let column = decode_column(&name, &ty, validity, &buffers, offset)?;
+        Ok(ImportedColumn::owned(slice_column(column, start, n)))
+    }
+}
+
+/// Import a table from a struct array (one column per child) or a single
+/// array (one column). Values are copied into owned columns; the inputs are
+/// released on return.
+///
+/// # Safety
+/// `schema` and `array` must be valid, matching C data interface structures
+/// whose buffers stay alive until this function returns.
+pub unsafe fn import_table(schema: ArrowSchema, array: ArrowArray) -> Result<Table, String> {
+    // SAFETY: guaranteed by the caller
+    unsafe { ImportedTable::new(schema, array) }.map(|t| t.to_table())
+}
+
+/// Import a table from C data interface structures at the given addresses,
+/// moving them out so that they read as released afterwards
+///
+/// # Safety
+/// As for [`ImportedTable::from_ptrs`].
+pub unsafe fn import_table_from_ptrs(
+    schema: *mut ArrowSchema,
+    array: *mut ArrowArray,
+) -> Result<Table, String> {
+    // SAFETY: guaranteed by the caller
+    unsafe { ImportedTable::from_ptrs(schema, array) }.map(|t| t.to_table())
+}
+
+fn slice_column(column: Column, start: usize, len: usize) -> Column {
+    if start == 0 && len == column.len() {
+        return column;
+    }
+    let end = (start + len).min(column.len());
+    let start = start.min(end);
+    let data = match column.data {
+        ColumnData::Boolean(v) => ColumnData::Boolean(v[start..end].to_vec()),
+        ColumnData::Int32(v) => ColumnData::Int32(v[start..end].to_vec()),
+        ColumnData::Float64(v) => ColumnData::Float64(v[start..end].to_vec()),
+        ColumnData::Date(v) => ColumnData::Date(v[start..end].to_vec()),
+        ColumnData::Datetime(v) => ColumnData::Datetime(v[start..end].to_vec()),
+        ColumnData::Dictionary { codes, dictionary } => ColumnData::Dictionary {
+            codes: codes[start..end].to_vec(),
+            dictionary,
+        },
+    };
+    Column {
+        name: column.name,
+        data,
+        validity: column.validity[start..end].to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::columns::ColumnBuilder;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn export_import_round_trip() {
+        let mut a = ColumnBuilder::new(ColumnType::Float64);
+        let mut s = ColumnBuilder::new(ColumnType::String);
+        let mut ts = ColumnBuilder::new(ColumnType::Datetime);
+        for i in 0..5 {
+            a.push_f64(i as f64);
+            if i == 2 {
+                s.push_null();
+            } else {
+                s.push_str(if i % 2 == 0 { "even" } else { "odd" });
+            }
+            ts.push_f64(1.6e12 + i as f64 * 1000.0);
+        }
+        let table = Table {
+            columns: vec![a.finish("a"), s.finish("s"), ts.finish("ts")],
+        };
+        let (schema, array) = export_table(table.clone());
+        assert_eq!(array.length, 5);
+        assert_eq!(array.n_children, 3);
+        let back = unsafe { import_table(schema, array) }.unwrap();
+        assert_eq!(back, table);
+    }
+
+    #[test]
+    fn import_from_pointers_moves_structures_out() {
+        let mut a = ColumnBuilder::new(ColumnType::Int32);
+        a.push_i32(7);
+        let table = Table {
+            columns: vec![a.finish("a")],
+        };
+        let (mut schema, mut array) = export_table(table.clone());
+        let back = unsafe { import_table_from_ptrs(&mut schema, &mut array) }.unwrap();
+        assert_eq!(back, table);
+        assert!(schema.is_released() && array.is_released());
+        let null = unsafe { import_table_from_ptrs(ptr::null_mut(), &mut array) };
+        assert!(null.is_err());
+    }
+
+    #[test]
+    fn mismatched_child_counts_are_errors() {
+        let mut a = ColumnBuilder::new(ColumnType::Int32);
+        let mut b = ColumnBuilder::new(ColumnType::Float64);
+        a.push_i32(1);
+        b.push_f64(2.0);
+        let (schema, mut array) = export_table(Table {
+            columns: vec![a.finish("a"), b.finish("b")],
+        });
+        array.n_children = 1;
+        assert!(unsafe { import_table(schema, array) }.is_err());
+    }
+
+    static RELEASED: AtomicBool = AtomicBool::new(false);
+
+    unsafe extern "C" fn observe_release(array: *mut ArrowArray) {
+        RELEASED.store(true, Ordering::SeqCst);
+        // SAFETY: forwarded from the array's own release callback
+        unsafe { release_array(array) }
+    }
+
+    #[test]
+    fn import_borrows_fixed_width_buffers() {
+        let mut x = ColumnBuilder::new(ColumnType::Float64);
+        let mut k = ColumnBuilder::new(ColumnType::Int32);
+        let mut d = ColumnBuilder::new(ColumnType::Date);
+        let mut s = ColumnBuilder::new(ColumnType::String);
+        let mut b = ColumnBuilder::new(ColumnType::Boolean);
+        for i in 0..4 {
+            x.push_f64(i as f64 / 2.0);
+            k.push_i32(i * 10);
+            if i == 1 {
+                d.push_null();
+            } else {
+                d.push_i32(19000 + i);
+            }
+            s.push_str(["a", "b"][i as usize % 2]);
+            b.push_bool(i % 2 == 0);
+        }
+        let table = Table {
+            columns: vec![
+                x.finish("x"),
+                k.finish("k"),
+                d.finish("d"),
+                s.finish("s"),
+                b.finish("b"),
+            ],
+        };
+        let (schema, mut array) = export_table(table.clone());
+        let data = |i: usize| unsafe { *(**array.children.add(i)).buffers.add(1) as usize };
+        let addresses: Vec<usize> = (0..4).map(data).collect();
+        // Rows 1..3 through the parent's offset and length
+        array.offset = 1;
+        array.length = 2;
+        array.release = Some(observe_release);
+
+        let imported = unsafe { ImportedTable::new(schema, array) }.unwrap();
+        assert_eq!((imported.n_rows(), imported.n_columns()), (2, 5));
+        let columns = imported.columns();
+        let ColumnView::Float64(v) = columns[0].values() else {
+            panic!("float64 column was not borrowed");
+        };
+        assert_eq!(v, &[0.5, 1.0]);
+        assert_eq!(v.as_ptr() as usize, addresses[0] + 8);
+        let ColumnView::Int32(v) = columns[1].values() else {
+            panic!("int32 column was not borrowed");
+        };
+        assert_eq!(v, &[10, 20]);
+        assert_eq!(v.as_ptr() as usize, addresses[1] + 4);
+        let ColumnView::Date(v) = columns[2].values() else {
+            panic!("date column was not borrowed");
+        };
+        assert_eq!(v.as_ptr() as usize, addresses[2] + 4);
+        assert_eq!((v[1], columns[2].validity()), (19002, &[0, 1][..]));
+        let ColumnView::Codes { codes, dictionary } = columns[3].values() else {
+            panic!("codes were not borrowed");
+        };
+        assert_eq!(codes.as_ptr() as usize, addresses[3] + 4);
+        assert_eq!(dictionary[codes[0] as usize], "b");
+        assert_eq!(columns[3].column_type(), ColumnType::String);
+        assert!(!columns[4].is_borrowed());
+
+        let expected: Vec<Column> = table
+            .columns
+            .into_iter()
+            .map(|c| slice_column(c, 1, 2))
+            .collect();
+        assert_eq!(imported.to_table().columns, expected);
+
+        RELEASED.store(false, Ordering::SeqCst);
+        drop(imported);
+        assert!(RELEASED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn dropping_unconsumed_export_releases() {
+        let mut b = ColumnBuilder::new(ColumnType::Boolean);
+        b.push_bool(true);
+        let (schema, array) = export_table(Table {
+            columns: vec![b.finish("b")],
+        });
+        drop(schema);
+        drop(array);
+    }
+}
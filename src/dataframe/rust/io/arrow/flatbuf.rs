@@ -0,0 +1,403 @@
+//! Minimal FlatBuffers reader and builder for Arrow IPC metadata
+//!
+//! Only the subset Arrow messages use is supported: tables with scalar,
+//! string, table, union and vector fields. Every read is bounds-checked so
+//! malformed input produces an error rather than a panic.
+
+fn out_of_bounds() -> String {
+    "Flatbuffer offset out of bounds".to_string()
+}
+
+/// `pos + delta`, failing instead of wrapping (usize is 32 bits on wasm32)
+fn add(pos: usize, delta: usize) -> Result<usize, String> {
+    pos.checked_add(delta).ok_or_else(out_of_bounds)
+}
+
+fn read_bytes<const N: usize>(buf: &[u8], pos: usize) -> Result<[u8; N], String> {
+    buf.get(pos..add(pos, N)?)
+        .and_then(|s| s.try_into().ok())
+        .ok_or_else(out_of_bounds)
+}
+
+fn read_u16(buf: &[u8], pos: usize) -> Result<u16, String> {
+    read_bytes::<2>(buf, pos).map(u16::from_le_bytes)
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> Result<u32, String> {
+    read_bytes::<4>(buf, pos).map(u32::from_le_bytes)
+}
+
+/// Follow a `uoffset` stored at `pos`
+fn follow(buf: &[u8], pos: usize) -> Result<usize, String> {
+    let target = add(pos, read_u32(buf, pos)? as usize)?;
+    if target >= buf.len() {
+        return Err(out_of_bounds());
+    }
+    Ok(target)
+}
+
+/// A table inside a flatbuffer
+#[derive(Debug, Clone, Copy)]
+pub struct FbTable<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+/// Root table of a flatbuffer
+pub fn root(buf: &[u8]) -> Result<FbTable<'_>, String> {
+    let pos = follow(buf, 0)?;
+    FbTable::at(buf, pos)
+}
+
+impl<'a> FbTable<'a> {
+    fn at(buf: &'a [u8], pos: usize) -> Result<Self, String> {
+        // Validate the vtable header up front
+        let table = FbTable { buf, pos };
+        table.vtable()?;
+        Ok(table)
+    }
+
+    fn vtable(&self) -> Result<(usize, usize), String> {
+        let soffset = i32::from_le_bytes(read_bytes::<4>(self.buf, self.pos)?) as i64;
+        let vt = self.pos as i64 - soffset;
+        if vt < 0 {
+            return Err("Flatbuffer vtable out of bounds".to_string());
+        }
+        let vt = vt as usize;
+        let vt_size = read_u16(self.buf, vt)? as usize;
+        Ok((vt, vt_size))
+    }
+
+    /// Absolute position of field `id`, `None` when absent
+    fn field(&self, id: usize) -> Result<Option<usize>, String> {
+        let (vt, vt_size) = self.vtable()?;
+        let entry = 4 + 2 * id;
+        if entry + 2 > vt_size {
+            return Ok(None);
+        }
+        let off = read_u16(self.buf, add(vt, entry)?)? as usize;
+        Ok(if off == 0 {
+            None
+        } else {
+            Some(add(self.pos, off)?)
+        })
+    }
+
+    pub fn u8(&self, id: usize, default: u8) -> Result<u8, String> {
+        match self.field(id)? {
+            Some(p) => read_bytes::<1>(self.buf, p).map(|b| b[0]),
+            None => Ok(default),
+        }
+    }
+
+    pub fn bool(&self, id: usize, default: bool) -> Result<bool, String> {
+        self.u8(id, default as u8).map(|b| b != 0)
+    }
+
+    pub fn i16(&self, id: usize, default: i16) -> Result<i16, String> {
+        match self.field(id)? {
+            Some(p) => read_bytes::<2>(self.buf, p).map(i16::from_le_bytes),
+            None => Ok(default),
+        }
+    }
+
+    pub fn i32(&self, id: usize, default: i32) -> Result<i32, String> {
+        match self.field(id)? {
+            Some(p) => read_bytes::<4>(self.buf, p).map(i32::from_le_bytes),
+            None => Ok(default),
+        }
+    }
+
+    pub fn i64(&self, id: usize, default: i64) -> Result<i64, String> {
+        match self.field(id)? {
+            Some(p) => read_bytes::<8>(self.buf, p).map(i64::from_le_bytes),
+            None => Ok(default),
+        }
+    }
+
+    /// Sub-table (also used for union values)
+    pub fn table(&self, id: usize) -> Result<Option<FbTable<'a>>, String> {
+        match self.field(id)? {
+            Some(p) => FbTable::at(self.buf, follow(self.buf, p)?).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn str(&self, id: usize) -> Result<Option<&'a str>, String> {
+        let Some(v) = self.vector(id)? else {
+            return Ok(None);
+        };
+        let bytes = v.bytes(1)?;
+        std::str::from_utf8(bytes)
+            .map(Some)
+            .map_err(|_| "Flatbuffer string is not valid UTF-8".to_string())
+    }
+
+    pub fn vector(&self, id: usize) -> Result<Option<FbVector<'a>>, String> {
+        match self.field(id)? {
+            Some(p) => {
+                let start = follow(self.buf, p)?;
+                let len = read_u32(self.buf, start)? as usize;
+                Ok(Some(FbVector {
+                    buf: self.buf,
+                    pos: add(start, 4)?,
+                    len,
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// A vector inside a flatbuffer
+#[derive(Debug, Clone, Copy)]
+pub struct FbVector<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    len: usize,
+}
+
+impl<'a> FbVector<'a> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Raw element bytes for vectors of scalars or structs of `elem_size` bytes
+    pub fn bytes(&self, elem_size: usize) -> Result<&'a [u8], String> {
+        let out_of_bounds = || "Flatbuffer vector out of bounds".to_string();
+        let end = self
+            .len
+            .checked_mul(elem_size)
+            .and_then(|n| self.pos.checked_add(n))
+            .ok_or_else(out_of_bounds)?;
+        self.buf.get(self.pos..end).ok_or_else(out_of_bounds)
+    }
+
+    /// Element `i` of a vector of tables
+    pub fn table(&self, i: usize) -> Result<FbTable<'a>, String> {
+        if i >= self.len {
+            return Err("Flatbuffer vector index out of range".to_string());
+        }
+        let slot = i.checked_mul(4).ok_or_else(out_of_bounds)?;
+        FbTable::at(self.buf, follow(self.buf, add(self.pos, slot)?)?)
+    }
+}
+
+/// A value to serialize
+#[derive(Debug, Clone)]
+pub enum Node {
+    Table(TableBuilder),
+    Str(String),
+    /// Vector of scalars or structs: raw little-endian bytes, element count and alignment
+    Structs {
+        data: Vec<u8>,
+        len: usize,
+        align: usize,
+    },
+    Tables(Vec<Node>),
+}
+
+#[derive(Debug, Clone)]
+enum Slot {
+    Scalar(Vec<u8>),
+    Offset(Node),
+}
+
+/// Table under construction; fields are identified by their schema index
+#[derive(Debug, Clone, Default)]
+pub struct TableBuilder {
+    slots: Vec<(usize, Slot)>,
+}
+
+impl TableBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn scalar(mut self, id: usize, bytes: &[u8]) -> Self {
+        self.slots.push((id, Slot::Scalar(bytes.to_vec())));
+        self
+    }
+
+    pub fn u8(self, id: usize, v: u8) -> Self {
+        self.scalar(id, &[v])
+    }
+
+    pub fn bool(self, id: usize, v: bool) -> Self {
+        self.scalar(id, &[v as u8])
+    }
+
+    pub fn i16(self, id: usize, v: i16) -> Self {
+        self.scalar(id, &v.to_le_bytes())
+    }
+
+    pub fn i32(self, id: usize, v: i32) -> Self {
+        self.scalar(id, &v.to_le_bytes())
+    }
+
+    pub fn i64(self, id: usize, v: i64) -> Self {
+        self.scalar(id, &v.to_le_bytes())
+    }
+
+    pub fn node(mut self, id: usize, node: Node) -> Self {
+        self.slots.push((id, Slot::Offset(node)));
+        self
+    }
+
+    pub fn table(self, id: usize, table: TableBuilder) -> Self {
+        self.node(id, Node::Table(table))
+    }
+
+    pub fn str(self, id: usize, s: &str) -> Self {
+        self.node(id, Node::Str(s.to_string()))
+    }
+
+    pub fn tables(self, id: usize, tables: Vec<TableBuilder>) -> Self {
+        self.node(
+            id,
+            Node::Tables(tables.into_iter().map(Node::Table).collect()),
+        )
+    }
+
+    /// Vector of fixed-size structs given as concatenated little-endian bytes
+    pub fn structs(self, id: usize, data: Vec<u8>, elem_size: usize, align: usize) -> Self {
+        let len = data.len().checked_div(elem_size).unwrap_or(0);
+        self.node(id, Node::Structs { data, len, align })
+    }
+}
+
+fn pad_to(buf: &mut Vec<u8>, align: usize) {
+    while !buf.len().is_multiple_of(align) {
+        buf.push(0);
+    }
+}
+
+fn patch(buf: &mut [u8], at: usize, target: usize) {
+    let off = (target - at) as u32;
+    buf[at..at + 4].copy_from_slice(&off.to_le_bytes());
+}
+
+/// Serialize `node`, returning the position its offsets should point to.
+/// Children are laid out after their parents so every `uoffset` points forward.
+fn serialize(buf: &mut Vec<u8>, node: &Node) -> usize {
+    match node {
+        Node::Table(t) => {
+            let n_fields = t.slots.iter().map(|(id, _)| id + 1).max().unwrap_or(0);
+            pad_to(buf, 2);
+            let vt_pos = buf.len();
+            buf.resize(vt_pos + 4 + 2 * n_fields, 0);
+            pad_to(buf, 4);
+            let table_pos = buf.len();
+            buf.extend_from_slice(&[0; 4]);
+
+            // Largest scalars first keeps padding small
+            let mut order: Vec<usize> = (0..t.slots.len()).collect();
+            order.sort_by_key(|&i| match &t.slots[i].1 {
+                Slot::Scalar(b) => std::cmp::Reverse(b.len()),
+                Slot::Offset(_) => std::cmp::Reverse(4),
+            });
+            let mut offsets = vec![0u16; n_fields];
+            let mut children = Vec::new();
+            for i in order {
+                let (id, slot) = &t.slots[i];
+                match slot {
+                    Slot::Scalar(b) => {
+                        pad_to(buf, b.len());
+                        offsets[*id] = (buf.len() - table_pos) as u16;
+                        buf.extend_from_slice(b);
+                    }
+                    Slot::Offset(child) => {
+                        pad_to(buf, 4);
+                        offsets[*id] = (buf.len() - table_pos) as u16;
+                        children.push((buf.len(), child));
+                        buf.extend_from_slice(&[0; 4]);
+                    }
+                }
+            }
+            let table_size = (buf.len() - table_pos) as u16;
+            let vt_size = (4 + 2 * n_fields) as u16;
+            buf[vt_pos..vt_pos + 2].copy_from_slice(&vt_size.to_le_bytes());
+            buf[vt_pos + 2..vt_pos + 4].copy_from_slice(&table_size.to_le_bytes());
+            for (k, off) in offsets.iter().enumerate() {
+                let p = vt_pos + 4 + 2 * k;
+                buf[p..p + 2].copy_from_slice(&off.to_le_bytes());
+            }
+            let soffset = (table_pos - vt_pos) as i32;
+            buf[table_pos..table_pos + 4].copy_from_slice(&soffset.to_le_bytes());
+
+            for (at, child) in children {
+                let target = serialize(buf, child);
+                patch(buf, at, target);
+            }
+            table_pos
+        }
+        Node::Str(s) => {
+            pad_to(buf, 4);
+            let pos = buf.len();
+            buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            buf.extend_from_slice(s.as_bytes());
+            buf.push(0);
+            pos
+        }
+        Node::Structs { data, len, align } => {
+            pad_to(buf, 4);
+            while !(buf.len() + 4).is_multiple_of((*align).max(4)) {
+                buf.push(0);
+            }
+            let pos = buf.len();
+            buf.extend_from_slice(&(*len as u32).to_le_bytes());
+            buf.extend_from_slice(data);
+            pos
+        }
+        Node::Tables(items) => {
+            pad_to(buf, 4);
+            let pos = buf.len();
+            buf.extend_from_slice(&(items.len() as u32).to_le_bytes());
+            let slots = buf.len();
+            buf.resize(slots + 4 * items.len(), 0);
+            for (i, item) in items.iter().enumerate() {
+                let target = serialize(buf, item);
+                patch(buf, slots + 4 * i, target);
+            }
+            pos
+        }
+    }
+}
+
+/// Serialize a root table into a flatbuffer padded to 8 bytes
+pub fn finish(root: TableBuilder) -> Vec<u8> {
+    let mut buf = vec![0u8; 4];
+    let target = serialize(&mut buf, &Node::Table(root));
+    patch(&mut buf, 0, target);
+    pad_to(&mut buf, 8);
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oversized_reads_are_errors() {
+        let buf = finish(
+            TableBuilder::new()
+                .str(0, "abc")
+                .structs(1, vec![1, 2, 3, 4], 4, 4),
+        );
+        let t = root(&buf).unwrap();
+        assert_eq!(t.str(0).unwrap(), Some("abc"));
+        let v = t.vector(1).unwrap().unwrap();
+        assert_eq!(v.bytes(4).unwrap(), &[1, 2, 3, 4]);
+        // Element size times length overflowing usize
+        assert!(v.bytes(usize::MAX).is_err());
+        assert!(v.table(0).is_err());
+        // Offsets pointing past the end, and positions near usize::MAX
+        assert!(root(&[0xff, 0xff, 0xff, 0x7f]).is_err());
+        assert!(read_u32(&buf, usize::MAX - 1).is_err());
+        assert!(follow(&buf, usize::MAX - 3).is_err());
+    }
+}
@@ -0,0 +1,602 @@
+//! Arrow logical types and buffer conversions shared by IPC and the C data interface
+
+use super::flatbuf::{FbTable, TableBuilder};
+use crate::io::columns::{Column, ColumnBuilder, ColumnData, ColumnType, NA_CODE};
+use crate::temporal::{MS_PER_DAY, NA_DAY};
+use std::sync::Arc;
+
+// `Type` union discriminants from Schema.fbs
+const TYPE_NULL: u8 = 1;
+const TYPE_INT: u8 = 2;
+const TYPE_FLOAT: u8 = 3;
+const TYPE_UTF8: u8 = 5;
+const TYPE_BOOL: u8 = 6;
+const TYPE_DATE: u8 = 8;
+const TYPE_TIMESTAMP: u8 = 10;
+const TYPE_LARGE_UTF8: u8 = 20;
+
+/// Arrow `TimeUnit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrowTimeUnit {
+    Second,
+    Millisecond,
+    Microsecond,
+    Nanosecond,
+}
+
+impl ArrowTimeUnit {
+    fn from_code(code: i16) -> Result<Self, String> {
+        match code {
+            0 => Ok(ArrowTimeUnit::Second),
+            1 => Ok(ArrowTimeUnit::Millisecond),
+            2 => Ok(ArrowTimeUnit::Microsecond),
+            3 => Ok(ArrowTimeUnit::Nanosecond),
+            _ => Err(format!("Unknown Arrow time unit {}", code)),
+        }
+    }
+
+    fn code(self) -> i16 {
+        self as i16
+    }
+
+    /// Milliseconds per unit
+    fn to_ms(self) -> f64 {
+        match self {
+            ArrowTimeUnit::Second => 1000.0,
+            ArrowTimeUnit::Millisecond => 1.0,
+            ArrowTimeUnit::Microsecond => 1e-3,
+            ArrowTimeUnit::Nanosecond => 1e-6,
+        }
+    }
+}
+
+/// Arrow value types this crate can exchange
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArrowType {
+    Null,
+    Bool,
+    Int {
+        bits: u8,
+        signed: bool,
+    },
+    /// 16, 32 or 64 bits
+    Float {
+        bits: u8,
+    },
+    Utf8,
+    LargeUtf8,
+    /// Date32 (days) or Date64 (milliseconds)
+    Date {
+        millis: bool,
+    },
+    Timestamp {
+        unit: ArrowTimeUnit,
+        timezone: Option<String>,
+    },
+}
+
+impl ArrowType {
+    /// Decode the `type` union of an IPC `Field`
+    pub fn from_fb(type_type: u8, t: Option<FbTable>) -> Result<ArrowType, String> {
+        let table = || t.ok_or_else(|| "Arrow field is missing its type".to_string());
+        Ok(match type_type {
+            TYPE_NULL => ArrowType::Null,
+            TYPE_BOOL => ArrowType::Bool,
+            TYPE_UTF8 => ArrowType::Utf8,
+            TYPE_LARGE_UTF8 => ArrowType::LargeUtf8,
+            TYPE_INT => {
+                let t = table()?;
+                ArrowType::Int {
+                    bits: t.i32(0, 0)? as u8,
+                    signed: t.bool(1, false)?,
+                }
+            }
+            TYPE_FLOAT => {
+                let bits = match table()?.i16(0, 0)? {
+                    0 => 16,
+                    1 => 32,
+                    2 => 64,
+                    p => return Err(format!("Unknown Arrow float precision {}", p)),
+                };
+                ArrowType::Float { bits }
+            }
+            TYPE_DATE => ArrowType::Date {
+                millis: table()?.i16(0, 1)? == 1,
+            },
+            TYPE_TIMESTAMP => {
+                let t = table()?;
+                ArrowType::Timestamp {
+                    unit: ArrowTimeUnit::from_code(t.i16(0, 0)?)?,
+                    timezone: t.str(1)?.map(str::to_string),
+                }
+            }
+            other => return Err(format!("Unsupported Arrow type id {}", other)),
+        })
+    }
+
+    /// Encode as a `type` union (discriminant, table)
+    pub fn to_fb(&self) -> (u8, TableBuilder) {
+        let t = TableBuilder::new();
+        match self {
+            ArrowType::Null => (TYPE_NULL, t),
+            ArrowType::Bool => (TYPE_BOOL, t),
+            ArrowType::Utf8 => (TYPE_UTF8, t),
+            ArrowType::LargeUtf8 => (TYPE_LARGE_UTF8, t),
+            ArrowType::Int { bits, signed } => (TYPE_INT, t.i32(0, *bits as i32).bool(1, *signed)),
+            ArrowType::Float { bits } => {
+                let precision = match bits {
+                    16 => 0,
+                    32 => 1,
+                    _ => 2,
+                };
+                (TYPE_FLOAT, t.i16(0, precision))
+            }
+            ArrowType::Date { millis } => (TYPE_DATE, t.i16(0, *millis as i16)),
+            ArrowType::Timestamp { unit, timezone } => {
+                let t = t.i16(0, unit.code());
+                match timezone {
+                    Some(tz) => (TYPE_TIMESTAMP, t.str(1, tz)),
+                    None => (TYPE_TIMESTAMP, t),
+                }
+            }
+        }
+    }
+
+    /// Parse a C data interface format string
+    pub fn from_format(format: &str) -> Result<ArrowType, String> {
+        Ok(match format {
+            "n" => ArrowType::Null,
+            "b" => ArrowType::Bool,
+            "c" => ArrowType::Int {
+                bits: 8,
+                signed: true,
+            },
+            "C" => ArrowType::Int {
+                bits: 8,
+                signed: false,
+            },
+            "s" => ArrowType::Int {
+                bits: 16,
+                signed: true,
+            },
+            "S" => ArrowType::Int {
+                bits: 16,
+                signed: false,
+            },
+            "i" => ArrowType::Int {
+                bits: 32,
+                signed: true,
+            },
+            "I" => ArrowType::Int {
+                bits: 32,
+                signed: false,
+            },
+            "l" => ArrowType::Int {
+                bits: 64,
+                signed: true,
+            },
+            "L" => ArrowType::Int {
+                bits: 64,
+                signed: false,
+            },
+            "e" => ArrowType::Float { bits: 16 },
+            "f" => ArrowType::Float { bits: 32 },
+            "g" => ArrowType::Float { bits: 64 },
+            "u" => ArrowType::Utf8,
+            "U" => ArrowType::LargeUtf8,
+            "tdD" => ArrowType::Date { millis: false },
+            "tdm" => ArrowType::Date { millis: true },
+            _ => {
+                let Some(rest) = format.strip_prefix("ts") else {
+                    return Err(format!("Unsupported Arrow format '{}'", format));
+                };
+                let (unit, tz) = rest.split_once(':').unwrap_or((rest, ""));
+                let unit = match unit {
+                    "s" => ArrowTimeUnit::Second,
+                    "m" => ArrowTimeUnit::Millisecond,
+                    "u" => ArrowTimeUnit::Microsecond,
+                    "n" => ArrowTimeUnit::Nanosecond,
+                    _ => return Err(format!("Unsupported Arrow format '{}'", format)),
+                };
+                ArrowType::Timestamp {
+                    unit,
+                    timezone: (!tz.is_empty()).then(|| tz.to_string()),
+                }
+            }
+        })
+    }
+
+    /// C data interface format string
+    pub fn format(&self) -> String {
+        match self {
+            ArrowType::Null => "n".into(),
+            ArrowType::Bool => "b".into(),
+            ArrowType::Int { bits, signed } => {
+                let c = match bits {
+                    8 => 'c',
+                    16 => 's',
+                    32 => 'i',
+                    _ => 'l',
+                };
+                if *signed { c } else { c.to_ascii_uppercase() }.to_string()
+            }
+            ArrowType::Float { bits } => match bits {
+                16 => "e".into(),
+                32 => "f".into(),
+                _ => "g".into(),
+            },
+            ArrowType::Utf8 => "u".into(),
+            ArrowType::LargeUtf8 => "U".into(),
+            ArrowType::Date { millis } => if *millis { "tdm" } else { "tdD" }.into(),
+            ArrowType::Timestamp { unit, timezone } => {
+                let u = match unit {
+                    ArrowTimeUnit::Second => 's',
+                    ArrowTimeUnit::Millisecond => 'm',
+                    ArrowTimeUnit::Microsecond => 'u',
+                    ArrowTimeUnit::Nanosecond => 'n',
+                };
+                format!("ts{}:{}", u, timezone.as_deref().unwrap_or(""))
+            }
+        }
+    }
+
+    /// Number of buffers including validity
+    pub fn n_buffers(&self) -> usize {
+        match self {
+            ArrowType::Null => 0,
+            ArrowType::Utf8 | ArrowType::LargeUtf8 => 3,
+            _ => 2,
+        }
+    }
+
+    /// Column type this Arrow type is read into
+    pub fn column_type(&self) -> ColumnType {
+        match self {
+            ArrowType::Bool => ColumnType::Boolean,
+            ArrowType::Int { bits, signed } if *bits < 32 || (*bits == 32 && *signed) => {
+                ColumnType::Int32
+            }
+            ArrowType::Null | ArrowType::Int { .. } | ArrowType::Float { .. } => {
+                ColumnType::Float64
+            }
+            ArrowType::Utf8 | ArrowType::LargeUtf8 => ColumnType::String,
+            ArrowType::Date { .. } => ColumnType::Date,
+            ArrowType::Timestamp { .. } => ColumnType::Datetime,
+        }
+    }
+
+    /// Arrow type used when writing a column of `t`
+    pub fn for_column(t: ColumnType) -> ArrowType {
+        match t {
+            ColumnType::Boolean => ArrowType::Bool,
+            ColumnType::Int32 => ArrowType::Int {
+                bits: 32,
+                signed: true,
+            },
+            ColumnType::Float64 => ArrowType::Float { bits: 64 },
+            ColumnType::String => ArrowType::Utf8,
+            ColumnType::Date => ArrowType::Date { millis: false },
+            ColumnType::Datetime => ArrowType::Timestamp {
+                unit: ArrowTimeUnit::Millisecond,
+                timezone: Some("UTC".to_string()),
+            },
+        }
+    }
+}
+
+/// Dictionary index type used when writing string columns
+pub const DICTIONARY_INDEX: ArrowType = ArrowType::Int {
+    bits: 32,
+    signed: true,
+};
+
+/// Bit `i` of an LSB-ordered bitmap
+#[inline]
+pub fn get_bit(bitmap: &[u8], i: usize) -> bool {
+    bitmap[i >> 3] & (1 << (i & 7)) != 0
+}
+
+/// Byte validity mask from an optional bitmap starting at bit `offset`
+pub fn mask_from_bitmap(
+    bitmap: Option<&[u8]>,
+    offset: usize,
+    len: usize,
+) -> Result<Vec<u8>, String> {
+    match bitmap {
+        None => Ok(vec![1; len]),
+        Some(b) => {
+            if b.len() * 8 < offset + len {
+                return Err("Arrow validity bitmap is too short".to_string());
+            }
+            Ok((0..len).map(|i| get_bit(b, offset + i) as u8).collect())
+        }
+    }
+}
+
+/// LSB-ordered bitmap padded to 8 bytes, and the number of zero entries
+pub fn bitmap_from_mask(mask: &[u8]) -> (Vec<u8>, usize) {
+    let mut bits = vec![0u8; mask.len().div_ceil(64) * 8];
+    let mut zeros = 0;
+    for (i, &m) in mask.iter().enumerate() {
+        if m != 0 {
+            bits[i >> 3] |= 1 << (i & 7);
+        } else {
+            zeros += 1;
+        }
+    }
+    (bits, zeros)
+}
+
+fn half_to_f64(h: u16) -> f64 {
+    let sign = if h & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exp = ((h >> 10) & 0x1f) as i32;
+    let frac = (h & 0x3ff) as f64;
+    sign * match exp {
+        0 => frac * 2f64.powi(-24),
+        31 if frac == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (1.0 + frac / 1024.0) * 2f64.powi(exp - 15),
+    }
+}
+
+/// Fixed-width little-endian values `[offset, offset + len)` of `buf`
+fn values<const N: usize, T>(
+    buf: &[u8],
+    offset: usize,
+    len: usize,
+    f: impl Fn([u8; N]) -> T,
+) -> Result<Vec<T>, String> {
+    let bytes = buf
+        .get(offset * N..(offset + len) * N)
+        .ok_or_else(|| "Arrow value buffer is too short".to_string())?;
+    Ok(bytes
+        .chunks_exact(N)
+        .map(|c| f(c.try_into().expect("chunk of N bytes")))
+        .collect())
+}
+
+/// Integer values widened to i64 (dictionary indices and integer columns)
+pub fn int_values(
+    buf: &[u8],
+    bits: u8,
+    signed: bool,
+    offset: usize,
+    len: usize,
+) -> Result<Vec<i64>, String> {
+    match (bits, signed) {
+        (8, true) => values::<1, _>(buf, offset, len, |b| i8::from_le_bytes(b) as i64),
+        (8, false) => values::<1, _>(buf, offset, len, |b| b[0] as i64),
+        (16, true) => values::<2, _>(buf, offset, len, |b| i16::from_le_bytes(b) as i64),
+        (16, false) => values::<2, _>(buf, offset, len, |b| u16::from_le_bytes(b) as i64),
+        (32, true) => values::<4, _>(buf, offset, len, |b| i32::from_le_bytes(b) as i64),
+        (32, false) => values::<4, _>(buf, offset, len, |b| u32::from_le_bytes(b) as i64),
+        (64, true) => values::<8, _>(buf, offset, len, i64::from_le_bytes),
+        (64, false) => values::<8, _>(buf, offset, len, |b| u64::from_le_bytes(b) as i64),
+        _ => Err(format!("Unsupported Arrow integer width {}", bits)),
+    }
+}
+
+/// Utf8 / LargeUtf8 values (missing entries are `None`)
+pub fn string_values(
+    large: bool,
+    offsets: &[u8],
+    data: &[u8],
+    validity: &[u8],
+    offset: usize,
+) -> Result<Vec<Option<String>>, String> {
+    let len = validity.len();
+    let bounds: Vec<i64> = if large {
+        values::<8, _>(offsets, offset, len + 1, i64::from_le_bytes)?
+    } else {
+        values::<4, _>(offsets, offset, len + 1, |b| i32::from_le_bytes(b) as i64)?
+    };
+    (0..len)
+        .map(|i| {
+            if validity[i] == 0 {
+                return Ok(None);
+            }
+            let (s, e) = (bounds[i] as usize, bounds[i + 1] as usize);
+            let bytes = data
+                .get(s..e)
+                .ok_or_else(|| "Arrow string offsets out of bounds".to_string())?;
+            Ok(Some(String::from_utf8_lossy(bytes).into_owned()))
+        })
+        .collect()
+}
+
+/// Decode one non-dictionary array into a column.
+///
+/// `buffers` excludes the validity bitmap; `offset` is the array's element offset.
+pub fn decode_column(
+    name: &str,
+    ty: &ArrowType,
+    mut validity: Vec<u8>,
+    buffers: &[&[u8]],
+    offset: usize,
+) -> Result<Column, String> {
+    let len = validity.len();
+    let buf = |i: usize| {
+        buffers
+            .get(i)
+            .copied()
+            .ok_or_else(|| format!("Arrow column '{}' is missing a buffer", name))
+    };
+    let data = match ty {
+        ArrowType::Null => ColumnData::Float64(vec![f64::NAN; len]),
+        ArrowType::Bool => {
+            let bits = buf(0)?;
+            ColumnData::Boolean(mask_from_bitmap(Some(bits), offset, len)?)
+        }
+        ArrowType::Int { bits, signed } => {
+            let v = int_values(buf(0)?, *bits, *signed, offset, len)?;
+            if *bits < 32 || (*bits == 32 && *signed) {
+                ColumnData::Int32(v.into_iter().map(|x| x as i32).collect())
+            } else {
+                ColumnData::Float64(v.into_iter().map(|x| x as f64).collect())
+            }
+        }
+        ArrowType::Float { bits } => ColumnData::Float64(match bits {
+            16 => values::<2, _>(buf(0)?, offset, len, |b| half_to_f64(u16::from_le_bytes(b)))?,
+            32 => values::<4, _>(buf(0)?, offset, len, |b| f32::from_le_bytes(b) as f64)?,
+            _ => values::<8, _>(buf(0)?, offset, len, f64::from_le_bytes)?,
+        }),
+        ArrowType::Utf8 | ArrowType::LargeUtf8 => {
+            let strings = string_values(
+                matches!(ty, ArrowType::LargeUtf8),
+                buf(0)?,
+                buf(1)?,
+                &validity,
+                offset,
+            )?;
+            let mut builder = ColumnBuilder::new(ColumnType::String);
+            for s in &strings {
+                match s {
+                    Some(s) => builder.push_str(s),
+                    None => builder.push_null(),
+                }
+            }
+            return Ok(builder.finish(name));
+        }
+        ArrowType::Date { millis: false } => {
+            ColumnData::Date(values::<4, _>(buf(0)?, offset, len, i32::from_le_bytes)?)
+        }
+        ArrowType::Date { millis: true } => {
+            // Days outside the i32 range become missing
+            let days = values::<8, _>(buf(0)?, offset, len, |b| {
+                i32::try_from(i64::from_le_bytes(b).div_euclid(MS_PER_DAY)).unwrap_or(NA_DAY)
+            })?;
+            for (ok, &d) in validity.iter_mut().zip(&days) {
+                if d == NA_DAY {
+                    *ok = 0;
+                }
+            }
+            ColumnData::Date(days)
+        }
+        ArrowType::Timestamp { unit, .. } => {
+            let scale = unit.to_ms();
+            ColumnData::Datetime(values::<8, _>(buf(0)?, offset, len, |b| {
+                i64::from_le_bytes(b) as f64 * scale
+            })?)
+        }
+    };
+    Ok(with_missing(name, data, validity))
+}
+
+/// Decode dictionary indices against an already decoded string dictionary
+pub fn decode_dictionary_column(
+    name: &str,
+    index: &ArrowType,
+    validity: Vec<u8>,
+    indices: &[u8],
+    offset: usize,
+    dictionary: &[String],
+) -> Result<Column, String> {
+    let ArrowType::Int { bits, signed } = index else {
+        return Err(format!("Dictionary index of '{}' is not an integer", name));
+    };
+    let raw = int_values(indices, *bits, *signed, offset, validity.len())?;
+    let mut codes = Vec::with_capacity(raw.len());
+    for (&k, &ok) in raw.iter().zip(&validity) {
+        if ok == 0 {
+            codes.push(NA_CODE);
+        } else if k < 0 || k as usize >= dictionary.len() {
+            return Err(format!("Dictionary index {} out of range in '{}'", k, name));
+        } else {
+            codes.push(k as u32);
+        }
+    }
+    Ok(Column {
+        name: name.to_string(),
+        data: ColumnData::Dictionary {
+            codes,
+            dictionary: Arc::new(dictionary.to_vec()),
+        },
+        validity,
+    })
+}
+
+/// Overwrite missing slots with the column's missing sentinel
+pub fn with_missing(name: &str, mut data: ColumnData, validity: Vec<u8>) -> Column {
+    if validity.contains(&0) {
+        let missing = validity
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| **v == 0)
+            .map(|(i, _)| i);
+        match &mut data {
+            ColumnData::Float64(v) | ColumnData::Datetime(v) => {
+                missing.for_each(|i| v[i] = f64::NAN)
+            }
+            ColumnData::Date(v) => missing.for_each(|i| v[i] = NA_DAY),
+            ColumnData::Boolean(v) => missing.for_each(|i| v[i] = 0),
+            ColumnData::Int32(v) => missing.for_each(|i| v[i] = 0),
+            ColumnData::Dictionary { codes, .. } => missing.for_each(|i| codes[i] = NA_CODE),
+        }
+    }
+    Column {
+        name: name.to_string(),
+        data,
+        validity,
+    }
+}
+
+/// Data buffers (excluding validity) of a column for writing, each padded to 8 bytes.
+/// String columns yield their dictionary indices as `DICTIONARY_INDEX`.
+pub fn encode_values(column: &Column) -> Vec<Vec<u8>> {
+    fn le<T: Copy, const N: usize>(v: &[T], f: impl Fn(T) -> [u8; N]) -> Vec<u8> {
+        let mut out = Vec::with_capacity((v.len() * N).div_ceil(8) * 8);
+        for &x in v {
+            out.extend_from_slice(&f(x));
+        }
+        out.resize(out.len().div_ceil(8) * 8, 0);
+        out
+    }
+    match &column.data {
+        ColumnData::Boolean(v) => vec![bitmap_from_mask(v).0],
+        ColumnData::Int32(v) | ColumnData::Date(v) => vec![le(v, i32::to_le_bytes)],
+        ColumnData::Float64(v) => vec![le(v, f64::to_le_bytes)],
+        ColumnData::Datetime(v) => vec![le(v, |x: f64| {
+            if x.is_finite() { x.round() as i64 } else { 0 }.to_le_bytes()
+        })],
+        ColumnData::Dictionary { codes, .. } => vec![le(codes, |c: u32| {
+            if c == NA_CODE { 0i32 } else { c as i32 }.to_le_bytes()
+        })],
+    }
+}
+
+/// Utf8 offsets and data buffers for a list of strings, padded to 8 bytes
+pub fn encode_strings(strings: &[String]) -> (Vec<u8>, Vec<u8>) {
+    let mut offsets = Vec::with_capacity((strings.len() + 1) * 4);
+    let mut data = Vec::new();
+    offsets.extend_from_slice(&0i32.to_le_bytes());
+    for s in strings {
+        data.extend_from_slice(s.as_bytes());
+        offsets.extend_from_slice(&(data.len() as i32).to_le_bytes());
+    }
+    offsets.resize(offsets.len().div_ceil(8) * 8, 0);
+    data.resize(data.len().div_ceil(8) * 8, 0);
+    (offsets, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date64_out_of_i32_range_is_missing() {
+        let ms: Vec<u8> = [0i64, 3 * MS_PER_DAY - 1, i64::MAX, -MS_PER_DAY]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let column = decode_column(
+            "d",
+            &ArrowType::Date { millis: true },
+            vec![1; 4],
+            &[&ms],
+            0,
+        )
+        .unwrap();
+        assert_eq!(column.data, ColumnData::Date(vec![0, 2, NA_DAY, -1]));
+        assert_eq!(column.validity, vec![1, 1, 0, 1]);
+    }
+}
@@ -0,0 +1,704 @@
+//! Arrow IPC stream and file formats
+
+use super::flatbuf::{self, FbTable, FbVector, TableBuilder};
+use super::format::{
+    ArrowType, DICTIONARY_INDEX, bitmap_from_mask, decode_column, decode_dictionary_column,
+    encode_strings, encode_values, mask_from_bitmap, string_values,
+};
+use crate::io::columns::{Column, ColumnBuilder, ColumnData, Table};
+use std::collections::HashMap;
+
+/// Magic bytes opening and closing the IPC file format
+pub const ARROW_MAGIC: &[u8; 6] = b"ARROW1";
+
+const CONTINUATION: u32 = 0xFFFF_FFFF;
+const METADATA_V5: i16 = 4;
+
+// `MessageHeader` union discriminants
+const HEADER_SCHEMA: u8 = 1;
+const HEADER_DICTIONARY_BATCH: u8 = 2;
+const HEADER_RECORD_BATCH: u8 = 3;
+
+/// Schema entry of a column
+#[derive(Debug, Clone)]
+struct FieldInfo {
+    name: String,
+    ty: ArrowType,
+    /// Dictionary id and index type for dictionary-encoded fields
+    dictionary: Option<(i64, ArrowType)>,
+}
+
+fn parse_schema(schema: FbTable) -> Result<Vec<FieldInfo>, String> {
+    if schema.i16(0, 0)? != 0 {
+        return Err("Big-endian Arrow data is not supported".to_string());
+    }
+    let Some(fields) = schema.vector(1)? else {
+        return Ok(Vec::new());
+    };
+    (0..fields.len())
+        .map(|i| {
+            let f = fields.table(i)?;
+            let name = f.str(0)?.unwrap_or_default().to_string();
+            if f.vector(5)?.is_some_and(|c| !c.is_empty()) {
+                return Err(format!("Nested Arrow column '{}' is not supported", name));
+            }
+            let ty = ArrowType::from_fb(f.u8(2, 0)?, f.table(3)?)
+                .map_err(|e| format!("Column '{}': {}", name, e))?;
+            let dictionary = match f.table(4)? {
+                Some(d) => {
+                    if !matches!(ty, ArrowType::Utf8 | ArrowType::LargeUtf8) {
+                        return Err(format!(
+                            "Column '{}': only string dictionaries are supported",
+                            name
+                        ));
+                    }
+                    let index = match d.table(1)? {
+                        Some(t) => ArrowType::Int {
+                            bits: t.i32(0, 32)? as u8,
+                            signed: t.bool(1, true)?,
+                        },
+                        None => DICTIONARY_INDEX,
+                    };
+                    Some((d.i64(0, 0)?, index))
+                }
+                None => None,
+            };
+            Ok(FieldInfo {
+                name,
+                ty,
+                dictionary,
+            })
+        })
+        .collect()
+}
+
+/// An encapsulated message: flatbuffer metadata and its body
+struct Message<'a> {
+    header_type: u8,
+    header: FbTable<'a>,
+    body: &'a [u8],
+}
+
+/// Read the message starting at `pos`; `None` at end-of-stream.
+/// Returns the message and the position after its body.
+fn read_message(bytes: &[u8], pos: usize) -> Result<Option<(Message<'_>, usize)>, String> {
+    let word = |p: usize| -> Option<u32> {
+        bytes
+            .get(p..p.checked_add(4)?)
+            .map(|b| u32::from_le_bytes(b.try_into().expect("4 bytes")))
+    };
+    let Some(first) = word(pos) else {
+        return Ok(None);
+    };
+    // Pre-1.0 streams omit the continuation marker
+    let (len, start) = if first == CONTINUATION {
+        (word(pos + 4).unwrap_or(0) as usize, pos + 8)
+    } else {
+        (first as usize, pos + 4)
+    };
+    if len == 0 {
+        return Ok(None);
+    }
+    let truncated = || "Arrow message metadata is truncated".to_string();
+    let meta_end = start.checked_add(len).ok_or_else(truncated)?;
+    let meta = bytes.get(start..meta_end).ok_or_else(truncated)?;
+    let message = flatbuf::root(meta)?;
+    let body_len = message.i64(3, 0)?;
+    if body_len < 0 {
+        return Err("Negative Arrow message body length".to_string());
+    }
+    let body_end = usize::try_from(body_len)
+        .ok()
+        .and_then(|l| meta_end.checked_add(l))
+        .ok_or("Arrow message body is truncated")?;
+    let body = bytes
+        .get(meta_end..body_end)
+        .ok_or("Arrow message body is truncated")?;
+    let header = message.table(2)?.ok_or("Arrow message has no header")?;
+    Ok(Some((
+        Message {
+            header_type: message.u8(1, 0)?,
+            header,
+            body,
+        },
+        body_end,
+    )))
+}
+
+/// `(offset, length)` pairs of a vector of 16-byte structs
+fn struct_pairs(v: Option<FbVector>) -> Result<Vec<(i64, i64)>, String> {
+    let Some(v) = v else {
+        return Ok(Vec::new());
+    };
+    Ok(v.bytes(16)?
+        .chunks_exact(16)
+        .map(|c| {
+            (
+                i64::from_le_bytes(c[..8].try_into().expect("8 bytes")),
+                i64::from_le_bytes(c[8..].try_into().expect("8 bytes")),
+            )
+        })
+        .collect())
+}
+
+/// Longest accepted Null array; Null arrays have no buffers to bound the
+/// length their field node declares
+const MAX_NULL_ROWS: usize = 1 << 24;
+
+/// Non-negative `usize` from an untrusted IPC integer
+fn ipc_usize(v: i64, what: &str) -> Result<usize, String> {
+    usize::try_from(v).map_err(|_| format!("Invalid Arrow {} {}", what, v))
+}
+
+/// Bytes the first data buffer of a `len`-element array must hold
+fn data_bytes(ty: &ArrowType, len: usize) -> Option<usize> {
+    match ty {
+        ArrowType::Null => Some(0),
+        ArrowType::Bool => Some(len.div_ceil(8)),
+        ArrowType::Int { bits, .. } | ArrowType::Float { bits } => {
+            len.checked_mul(*bits as usize / 8)
+        }
+        ArrowType::Date { millis } => len.checked_mul(if *millis { 8 } else { 4 }),
+        ArrowType::Timestamp { .. } => len.checked_mul(8),
+        ArrowType::Utf8 => len.checked_add(1)?.checked_mul(4),
+        ArrowType::LargeUtf8 => len.checked_add(1)?.checked_mul(8),
+    }
+}
+
+/// Cursor over the field nodes and buffers of a record batch
+struct BatchCursor<'a> {
+    body: &'a [u8],
+    /// Row count declared by the batch header
+    length: usize,
+    nodes: Vec<(i64, i64)>,
+    buffers: Vec<(i64, i64)>,
+    node: usize,
+    buffer: usize,
+}
+
+impl<'a> BatchCursor<'a> {
+    fn new(batch: FbTable, body: &'a [u8]) -> Result<Self, String> {
+        if batch.table(3)?.is_some() {
+            return Err("Compressed Arrow IPC bodies are not supported".to_string());
+        }
+        Ok(BatchCursor {
+            body,
+            length: ipc_usize(batch.i64(0, 0)?, "record batch length")?,
+            nodes: struct_pairs(batch.vector(1)?)?,
+            buffers: struct_pairs(batch.vector(2)?)?,
+            node: 0,
+            buffer: 0,
+        })
+    }
+
+    fn next_node(&mut self) -> Result<(usize, usize), String> {
+        let &(len, nulls) = self
+            .nodes
+            .get(self.node)
+            .ok_or("Arrow record batch has too few field nodes")?;
+        self.node += 1;
+        let len = ipc_usize(len, "field length")?;
+        let nulls = ipc_usize(nulls, "null count")?;
+        if len != self.length {
+            return Err(format!(
+                "Arrow field length {} does not match the record batch length {}",
+                len, self.length
+            ));
+        }
+        if nulls > len {
+            return Err(format!(
+                "Arrow null count {} exceeds the field length {}",
+                nulls, len
+            ));
+        }
+        Ok((len, nulls))
+    }
+
+    fn next_buffer(&mut self) -> Result<&'a [u8], String> {
+        let &(offset, len) = self
+            .buffers
+            .get(self.buffer)
+            .ok_or("Arrow record batch has too few buffers")?;
+        self.buffer += 1;
+        let start = ipc_usize(offset, "buffer offset")?;
+        let end = start
+            .checked_add(ipc_usize(len, "buffer length")?)
+            .ok_or("Arrow buffer lies outside the message body")?;
+        self.body
+            .get(start..end)
+            .ok_or_else(|| "Arrow buffer lies outside the message body".to_string())
+    }
+
+    /// Validity mask and data buffers of the next field
+    ///
+    /// The buffers are checked against the declared length before anything
+    /// of that length is allocated.
+    fn next_field(&mut self, ty: &ArrowType) -> Result<(Vec<u8>, Vec<&'a [u8]>), String> {
+        let (len, nulls) = self.next_node()?;
+        if *ty == ArrowType::Null {
+            if len > MAX_NULL_ROWS {
+                return Err(format!("Arrow Null array of {} rows is too long", len));
+            }
+            return Ok((vec![0; len], Vec::new()));
+        }
+        let bitmap = self.next_buffer()?;
+        let buffers: Vec<&'a [u8]> = (1..ty.n_buffers())
+            .map(|_| self.next_buffer())
+            .collect::<Result<_, _>>()?;
+        let needed = data_bytes(ty, len).ok_or("Arrow field length overflows")?;
+        if buffers.first().is_none_or(|b| b.len() < needed) {
+            return Err(format!(
+                "Arrow buffer is too short for {} values of type {:?}",
+                len, ty
+            ));
+        }
+        let validity = if nulls > 0 && !bitmap.is_empty() {
+            mask_from_bitmap(Some(bitmap), 0, len)?
+        } else {
+            vec![1; len]
+        };
+        Ok((validity, buffers))
+    }
+}
+
+/// Incremental decoder shared by the stream and file readers
+#[derive(Default)]
+struct Decoder {
+    fields: Vec<FieldInfo>,
+    dictionaries: HashMap<i64, Vec<String>>,
+    table: Table,
+}
+
+impl Decoder {
+    fn message(&mut self, m: Message) -> Result<(), String> {
+        match m.header_type {
+            HEADER_SCHEMA => self.fields = parse_schema(m.header)?,
+            HEADER_DICTIONARY_BATCH => {
+                let id = m.header.i64(0, 0)?;
+                let ty = self
+                    .fields
+                    .iter()
+                    .find(|f| f.dictionary.as_ref().is_some_and(|d| d.0 == id))
+                    .map(|f| f.ty.clone())
+                    .ok_or_else(|| format!("Arrow dictionary {} has no matching field", id))?;
+                let batch = m.header.table(1)?.ok_or("Dictionary batch has no data")?;
+                let mut cursor = BatchCursor::new(batch, m.body)?;
+                let (validity, buffers) = cursor.next_field(&ty)?;
+                let strings = string_values(
+                    matches!(ty, ArrowType::LargeUtf8),
+                    buffers[0],
+                    buffers[1],
+                    &validity,
+                    0,
+                )?;
+                let values = strings.into_iter().map(Option::unwrap_or_default);
+                let entry = self.dictionaries.entry(id).or_default();
+                if !m.header.bool(2, false)? {
+                    entry.clear();
+                }
+                entry.extend(values);
+            }
+            HEADER_RECORD_BATCH => {
+                let mut cursor = BatchCursor::new(m.header, m.body)?;
+                let mut columns = Vec::with_capacity(self.fields.len());
+                for f in &self.fields {
+                    let column = match &f.dictionary {
+                        Some((id, index)) => {
+                            let (validity, buffers) = cursor.next_field(index)?;
+                            let dict = self.dictionaries.get(id).map_or(&[][..], Vec::as_slice);
+                            decode_dictionary_column(&f.name, index, validity, buffers[0], 0, dict)?
+                        }
+                        None => {
+                            let (validity, buffers) = cursor.next_field(&f.ty)?;
+                            decode_column(&f.name, &f.ty, validity, &buffers, 0)?
+                        }
+                    };
+                    columns.push(column);
+                }
+                self.table.append(Table { columns })?;
+            }
+            // Tensors and other messages carry no table data
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// The decoded table; a schema without batches yields empty columns
+    fn finish(self) -> Table {
+        if !self.table.columns.is_empty() {
+            return self.table;
+        }
+        let columns = self
+            .fields
+            .iter()
+            .map(|f| ColumnBuilder::new(f.ty.column_type()).finish(&f.name))
+            .collect();
+        Table { columns }
+    }
+}
+
+/// Read an Arrow IPC stream or file (detected from the leading magic bytes).
+///
+/// String dictionaries map directly to dictionary-encoded columns; plain
+/// strings are dictionary-encoded on read. Integer types that fit in 32 bits
+/// become int32 and wider ones float64; Date64 and timestamps are converted
+/// to epoch days and milliseconds. Nested types and compressed bodies are
+/// rejected.
+pub fn read_ipc(bytes: &[u8]) -> Result<Table, String> {
+    let mut decoder = Decoder::default();
+    if bytes.starts_with(ARROW_MAGIC) {
+        let n = bytes.len();
+        if n < 18 || &bytes[n - 6..] != ARROW_MAGIC {
+            return Err("Arrow IPC file is truncated".to_string());
+        }
+        let footer_len = i32::from_le_bytes(bytes[n - 10..n - 6].try_into().expect("4 bytes"));
+        let footer_start = (n - 10)
+            .checked_sub(footer_len.max(0) as usize)
+            .ok_or("Invalid Arrow footer length")?;
+        let footer = flatbuf::root(&bytes[footer_start..n - 10])?;
+        let schema = footer.table(1)?.ok_or("Arrow footer has no schema")?;
+        decoder.fields = parse_schema(schema)?;
+        for id in [2, 3] {
+            let Some(blocks) = footer.vector(id)? else {
+                continue;
+            };
+            for block in blocks.bytes(24)?.chunks_exact(24) {
+                let offset = i64::from_le_bytes(block[..8].try_into().expect("8 bytes"));
+                if let Some((m, _)) = read_message(bytes, ipc_usize(offset, "block offset")?)? {
+                    decoder.message(m)?;
+                }
+            }
+        }
+    } else {
+        let mut pos = 0;
+        while let Some((m, next)) = read_message(bytes, pos)? {
+            decoder.message(m)?;
+            pos = next;
+        }
+        if decoder.fields.is_empty() && pos == 0 && !bytes.is_empty() {
+            return Err("Input is not an Arrow IPC stream".to_string());
+        }
+    }
+    Ok(decoder.finish())
+}
+
+/// A message location recorded in the file footer
+struct Block {
+    offset: usize,
+    meta_len: usize,
+    body_len: usize,
+}
+
+fn field_node(column: &Column, dictionary_id: i64) -> TableBuilder {
+    let ty = ArrowType::for_column(column.column_type());
+    let (type_type, type_table) = ty.to_fb();
+    let field = TableBuilder::new()
+        .str(0, &column.name)
+        .bool(1, true)
+        .u8(2, type_type)
+        .table(3, type_table)
+        .tables(5, Vec::new());
+    if matches!(column.data, ColumnData::Dictionary { .. }) {
+        let (_, index) = DICTIONARY_INDEX.to_fb();
+        field.table(4, TableBuilder::new().i64(0, dictionary_id).table(1, index))
+    } else {
+        field
+    }
+}
+
+/// Schema with dictionary ids assigned by column position
+fn schema_node(table: &Table) -> TableBuilder {
+    let fields = table
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| field_node(c, i as i64))
+        .collect();
+    TableBuilder::new().tables(1, fields)
+}
+
+/// Record batch header and body from field nodes and buffers
+fn record_batch(
+    length: usize,
+    nodes: &[(usize, usize)],
+    buffers: Vec<Vec<u8>>,
+) -> (TableBuilder, Vec<u8>) {
+    let mut node_bytes = Vec::with_capacity(nodes.len() * 16);
+    for &(len, nulls) in nodes {
+        node_bytes.extend_from_slice(&(len as i64).to_le_bytes());
+        node_bytes.extend_from_slice(&(nulls as i64).to_le_bytes());
+    }
+    let mut body = Vec::new();
+    let mut buffer_bytes = Vec::with_capacity(buffers.len() * 16);
+    for b in buffers {
+        buffer_bytes.extend_from_slice(&(body.len() as i64).to_le_bytes());
+        buffer_bytes.extend_from_slice(&(b.len() as i64).to_le_bytes());
+        body.extend_from_slice(&b);
+        body.resize(body.len().div_ceil(8) * 8, 0);
+    }
+    let header = TableBuilder::new()
+        .i64(0, length as i64)
+        .structs(1, node_bytes, 16, 8)
+        .structs(2, buffer_bytes, 16, 8);
+    (header, body)
+}
+
+fn write_message(out: &mut Vec<u8>, header_type: u8, header: TableBuilder, body: &[u8]) -> Block {
+    let meta = flatbuf::finish(
+        TableBuilder::new()
+            .i16(0, METADATA_V5)
+            .u8(1, header_type)
+            .table(2, header)
+            .i64(3, body.len() as i64),
+    );
+    let offset = out.len();
+    out.extend_from_slice(&CONTINUATION.to_le_bytes());
+    out.extend_from_slice(&(meta.len() as i32).to_le_bytes());
+    out.extend_from_slice(&meta);
+    out.extend_from_slice(body);
+    Block {
+        offset,
+        meta_len: 8 + meta.len(),
+        body_len: body.len(),
+    }
+}
+
+/// Write schema, dictionaries and one record batch; returns the
+/// dictionary and record batch blocks
+fn write_table(out: &mut Vec<u8>, table: &Table) -> Result<(Vec<Block>, Vec<Block>), String> {
+    let n_rows = table.n_rows();
+    if let Some(c) = table
+        .columns
+        .iter()
+        .find(|c| c.len() != n_rows || c.validity.len() != n_rows)
+    {
+        return Err(format!("Column '{}' has a different length", c.name));
+    }
+    write_message(out, HEADER_SCHEMA, schema_node(table), &[]);
+
+    let mut dictionary_blocks = Vec::new();
+    for (id, c) in table.columns.iter().enumerate() {
+        if let ColumnData::Dictionary { dictionary, .. } = &c.data {
+            let (offsets, data) = encode_strings(dictionary);
+            let (batch, body) = record_batch(
+                dictionary.len(),
+                &[(dictionary.len(), 0)],
+                vec![Vec::new(), offsets, data],
+            );
+            let header = TableBuilder::new()
+                .i64(0, id as i64)
+                .table(1, batch)
+                .bool(2, false);
+            dictionary_blocks.push(write_message(out, HEADER_DICTIONARY_BATCH, header, &body));
+        }
+    }
+
+    let mut nodes = Vec::with_capacity(table.columns.len());
+    let mut buffers = Vec::new();
+    for c in &table.columns {
+        let (bitmap, nulls) = bitmap_from_mask(&c.validity);
+        nodes.push((n_rows, nulls));
+        buffers.push(if nulls > 0 { bitmap } else { Vec::new() });
+        buffers.extend(encode_values(c));
+    }
+    let (batch, body) = record_batch(n_rows, &nodes, buffers);
+    let batch_block = write_message(out, HEADER_RECORD_BATCH, batch, &body);
+    Ok((dictionary_blocks, vec![batch_block]))
+}
+
+fn write_eos(out: &mut Vec<u8>) {
+    out.extend_from_slice(&CONTINUATION.to_le_bytes());
+    out.extend_from_slice(&0u32.to_le_bytes());
+}
+
+/// Write a table in the Arrow IPC stream format.
+///
+/// String columns are written as `Dictionary<Int32, Utf8>` using their codes
+/// and dictionary as-is; datetimes as UTC millisecond timestamps.
+pub fn write_ipc_stream(table: &Table) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    write_table(&mut out, table)?;
+    write_eos(&mut out);
+    Ok(out)
+}
+
+/// Write a table in the Arrow IPC file format (random-access footer)
+pub fn write_ipc_file(table: &Table) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    out.extend_from_slice(ARROW_MAGIC);
+    out.extend_from_slice(&[0, 0]);
+    let (dictionaries, batches) = write_table(&mut out, table)?;
+    write_eos(&mut out);
+
+    let blocks = |list: &[Block]| {
+        let mut bytes = Vec::with_capacity(list.len() * 24);
+        for b in list {
+            bytes.extend_from_slice(&(b.offset as i64).to_le_bytes());
+            bytes.extend_from_slice(&(b.meta_len as i32).to_le_bytes());
+            bytes.extend_from_slice(&[0; 4]);
+            bytes.extend_from_slice(&(b.body_len as i64).to_le_bytes());
+        }
+        bytes
+    };
+    let footer = flatbuf::finish(
+        TableBuilder::new()
+            .i16(0, METADATA_V5)
+            .table(1, schema_node(table))
+            .structs(2, blocks(&dictionaries), 24, 8)
+            .structs(3, blocks(&batches), 24, 8),
+    );
+    out.extend_from_slice(&footer);
+    out.extend_from_slice(&(footer.len() as i32).to_le_bytes());
+    out.extend_from_slice(ARROW_MAGIC);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::columns::ColumnType;
+
+    fn sample() -> Table {
+        let mut a = ColumnBuilder::new(ColumnType::Float64);
+        let mut b = ColumnBuilder::new(ColumnType::String);
+        let mut c = ColumnBuilder::new(ColumnType::Boolean);
+        let mut d = ColumnBuilder::new(ColumnType::Date);
+        let mut e = ColumnBuilder::new(ColumnType::Int32);
+        for i in 0..10 {
+            if i % 4 == 3 {
+                a.push_null();
+                b.push_null();
+            } else {
+                a.push_f64(i as f64 * 0.5);
+                b.push_str(["x", "y", "z"][i % 3]);
+            }
+            c.push_bool(i % 2 == 0);
+            d.push_i32(19000 + i as i32);
+            e.push_i32(-(i as i32));
+        }
+        let mut t = Table {
+            columns: vec![
+                a.finish("a"),
+                b.finish("b"),
+                c.finish("c"),
+                d.finish("d"),
+                e.finish("e"),
+            ],
+        };
+        let mut ts = ColumnBuilder::new(ColumnType::Datetime);
+        for i in 0..10 {
+            ts.push_f64(1.7e12 + i as f64);
+        }
+        t.columns.push(ts.finish("ts"));
+        t
+    }
+
+    #[test]
+    fn stream_round_trip() {
+        let t = sample();
+        let bytes = write_ipc_stream(&t).unwrap();
+        // Debug output compares NaN slots as equal
+        assert_eq!(
+            format!("{:?}", read_ipc(&bytes).unwrap()),
+            format!("{:?}", t)
+        );
+    }
+
+    #[test]
+    fn file_round_trip() {
+        let t = sample();
+        let bytes = write_ipc_file(&t).unwrap();
+        assert!(bytes.starts_with(ARROW_MAGIC) && bytes.ends_with(ARROW_MAGIC));
+        // Debug output compares NaN slots as equal
+        assert_eq!(
+            format!("{:?}", read_ipc(&bytes).unwrap()),
+            format!("{:?}", t)
+        );
+    }
+
+    #[test]
+    fn concatenates_batches_and_remaps_dictionaries() {
+        let mut first = ColumnBuilder::new(ColumnType::String);
+        first.push_str("a");
+        first.push_str("b");
+        let mut second = ColumnBuilder::new(ColumnType::String);
+        second.push_str("b");
+        second.push_str("c");
+        let mut out = write_ipc_stream(&Table {
+            columns: vec![first.finish("s")],
+        })
+        .unwrap();
+        out.truncate(out.len() - 8);
+        // A second stream's messages re-declare the schema and replace the dictionary
+        out.extend(
+            write_ipc_stream(&Table {
+                columns: vec![second.finish("s")],
+            })
+            .unwrap(),
+        );
+        let t = read_ipc(&out).unwrap();
+        let s = &t.columns[0];
+        let values: Vec<_> = (0..4).map(|i| s.str_at(i).unwrap()).collect();
+        assert_eq!(values, vec!["a", "b", "b", "c"]);
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(read_ipc(b"not arrow at all").is_err());
+    }
+
+    #[test]
+    fn truncated_input_is_an_error() {
+        let t = sample();
+        for bytes in [write_ipc_stream(&t).unwrap(), write_ipc_file(&t).unwrap()] {
+            for cut in 0..bytes.len() {
+                // Any prefix must decode or fail cleanly, never panic
+                let _ = read_ipc(&bytes[..cut]);
+            }
+        }
+        let file = write_ipc_file(&t).unwrap();
+        assert!(read_ipc(&file[..file.len() - 1]).is_err());
+    }
+
+    /// A Float64 column `x` followed by a record batch with the given field
+    /// nodes and raw `(offset, length)` buffer entries over an 8-byte body
+    fn corrupt_stream(length: i64, nodes: &[(i64, i64)], buffers: &[(i64, i64)]) -> Vec<u8> {
+        let mut x = ColumnBuilder::new(ColumnType::Float64);
+        x.push_f64(1.0);
+        let t = Table {
+            columns: vec![x.finish("x")],
+        };
+        let pairs = |v: &[(i64, i64)]| {
+            v.iter()
+                .flat_map(|(a, b)| a.to_le_bytes().into_iter().chain(b.to_le_bytes()))
+                .collect::<Vec<u8>>()
+        };
+        let header = TableBuilder::new()
+            .i64(0, length)
+            .structs(1, pairs(nodes), 16, 8)
+            .structs(2, pairs(buffers), 16, 8);
+        let mut out = Vec::new();
+        write_message(&mut out, HEADER_SCHEMA, schema_node(&t), &[]);
+        write_message(&mut out, HEADER_RECORD_BATCH, header, &[0; 8]);
+        write_eos(&mut out);
+        out
+    }
+
+    #[test]
+    fn corrupt_lengths_are_errors() {
+        let ok = corrupt_stream(1, &[(1, 0)], &[(0, 0), (0, 8)]);
+        assert_eq!(read_ipc(&ok).unwrap().n_rows(), 1);
+        // A huge declared length with a tiny buffer must not be allocated
+        let huge = 1i64 << 44;
+        assert!(read_ipc(&corrupt_stream(huge, &[(huge, 0)], &[(0, 0), (0, 8)])).is_err());
+        // Negative lengths and null counts
+        assert!(read_ipc(&corrupt_stream(-1, &[(-1, 0)], &[(0, 0), (0, 8)])).is_err());
+        assert!(read_ipc(&corrupt_stream(1, &[(1, -5)], &[(0, 0), (0, 8)])).is_err());
+        assert!(read_ipc(&corrupt_stream(1, &[(1, 2)], &[(0, 0), (0, 8)])).is_err());
+        // Field length disagreeing with the batch
+        assert!(read_ipc(&corrupt_stream(1, &[(2, 0)], &[(0, 0), (0, 8)])).is_err());
+        // Buffer offset + length overflowing i64, and negative offsets
+        assert!(read_ipc(&corrupt_stream(1, &[(1, 0)], &[(0, 0), (i64::MAX, 8)])).is_err());
+        assert!(read_ipc(&corrupt_stream(1, &[(1, 0)], &[(0, 0), (-8, 8)])).is_err());
+    }
+}
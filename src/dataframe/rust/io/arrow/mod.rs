@@ -0,0 +1,19 @@
+//! Apache Arrow interchange
+//!
+//! - `flatbuf`: minimal FlatBuffers reader/builder for IPC metadata
+//! - `format`: Arrow type mapping and buffer conversions
+//! - `ipc`: IPC stream and file reader/writer
+//! - `ffi`: C data interface import/export
+
+pub mod ffi;
+pub mod flatbuf;
+pub mod format;
+pub mod ipc;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use ffi::{
+    ArrowArray, ArrowSchema, ColumnView, ImportedColumn, ImportedTable, export_table, import_table,
+    import_table_from_ptrs,
+};
+pub use ipc::{read_ipc, write_ipc_file, write_ipc_stream};
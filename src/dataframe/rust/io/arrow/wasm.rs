@@ -0,0 +1,187 @@
+//! WASM bindings for Arrow IPC and the C data interface
+
+#![cfg(feature = "wasm")]
+
+use super::ffi::{
+    ArrowArray, ArrowSchema, ColumnView, ImportedTable, export_table, import_table_from_ptrs,
+};
+use super::ipc::{read_ipc, write_ipc_file, write_ipc_stream};
+use crate::io::columns::ColumnData;
+use crate::io::wasm::TableWasm;
+use wasm_bindgen::prelude::*;
+
+/// Read an Arrow IPC stream or file into typed columns
+#[wasm_bindgen]
+pub fn read_arrow_ipc_wasm(bytes: &[u8]) -> Result<TableWasm, JsValue> {
+    read_ipc(bytes)
+        .map(|t| TableWasm::new(t, 0))
+        .map_err(|e| JsValue::from_str(&format!("Arrow error: {}", e)))
+}
+
+/// Write a table as Arrow IPC (`file_format` = true for the random-access file format)
+#[wasm_bindgen]
+pub fn write_arrow_ipc_wasm(table: &TableWasm, file_format: bool) -> Result<Vec<u8>, JsValue> {
+    let result = if file_format {
+        write_ipc_file(table.table())
+    } else {
+        write_ipc_stream(table.table())
+    };
+    result.map_err(|e| JsValue::from_str(&format!("Arrow error: {}", e)))
+}
+
+/// Arrow C data interface structures living in wasm memory.
+///
+/// `schemaPtr` / `arrayPtr` are addresses into the module's memory that an
+/// FFI-aware Arrow library can read. The buffers stay valid until `free()`
+/// is called on this object.
+#[wasm_bindgen]
+pub struct ArrowCExport {
+    schema: Box<ArrowSchema>,
+    array: Box<ArrowArray>,
+}
+
+#[wasm_bindgen]
+impl ArrowCExport {
+    #[wasm_bindgen(getter, js_name = schemaPtr)]
+    pub fn schema_ptr(&self) -> usize {
+        &*self.schema as *const ArrowSchema as usize
+    }
+
+    #[wasm_bindgen(getter, js_name = arrayPtr)]
+    pub fn array_ptr(&self) -> usize {
+        &*self.array as *const ArrowArray as usize
+    }
+}
+
+/// Export a table through the C data interface (takes ownership of the table)
+#[wasm_bindgen]
+pub fn export_arrow_c_wasm(table: TableWasm) -> ArrowCExport {
+    let (schema, array) = export_table(table.into_table());
+    ArrowCExport {
+        schema: Box::new(schema),
+        array: Box::new(array),
+    }
+}
+
+/// Import a table from C data interface structures at the given addresses.
+///
+/// The structures are moved out and released after their values are copied
+/// into owned columns; the caller must not release them again.
+///
+/// # Safety
+/// `schema_ptr` and `array_ptr` must be the addresses of live `ArrowSchema`
+/// and `ArrowArray` structures in this module's memory, as required by
+/// [`import_table_from_ptrs`].
+#[wasm_bindgen]
+pub unsafe fn import_arrow_c_wasm(
+    schema_ptr: usize,
+    array_ptr: usize,
+) -> Result<TableWasm, JsValue> {
+    // SAFETY: guaranteed by the caller
+    unsafe { import_table_from_ptrs(schema_ptr as *mut ArrowSchema, array_ptr as *mut ArrowArray) }
+        .map(|t| TableWasm::new(t, 0))
+        .map_err(|e| JsValue::from_str(&format!("Arrow error: {}", e)))
+}
+
+/// A table imported through the C data interface without copying.
+///
+/// float64, int32, date32 and 32-bit dictionary-index columns stay in the
+/// producer's buffers: `dataPtr(i)` is their address in wasm memory, for a
+/// `Float64Array` / `Int32Array` / `Uint32Array` view of `n_rows` elements.
+/// Values in null slots are whatever the producer wrote; use `validity(i)`.
+/// The producer's buffers are released by `free()`.
+#[wasm_bindgen]
+pub struct ArrowCImport {
+    table: ImportedTable,
+}
+
+#[wasm_bindgen]
+impl ArrowCImport {
+    #[wasm_bindgen(getter)]
+    pub fn n_rows(&self) -> usize {
+        self.table.n_rows()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn n_columns(&self) -> usize {
+        self.table.n_columns()
+    }
+
+    #[wasm_bindgen(js_name = columnName)]
+    pub fn column_name(&self, i: usize) -> String {
+        self.table
+            .columns()
+            .get(i)
+            .map(|c| c.name().to_string())
+            .unwrap_or_default()
+    }
+
+    /// "boolean", "int32", "float64", "string", "date" or "datetime"
+    #[wasm_bindgen(js_name = columnType)]
+    pub fn column_type(&self, i: usize) -> String {
+        self.table
+            .columns()
+            .get(i)
+            .map(|c| c.column_type().as_str().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Address of a borrowed column's values (undefined if it was converted)
+    #[wasm_bindgen(js_name = dataPtr)]
+    pub fn data_ptr(&self, i: usize) -> Option<usize> {
+        match self.table.columns().get(i)?.values() {
+            ColumnView::Float64(v) => Some(v.as_ptr() as usize),
+            ColumnView::Int32(v) | ColumnView::Date(v) => Some(v.as_ptr() as usize),
+            ColumnView::Codes { codes, .. } => Some(codes.as_ptr() as usize),
+            ColumnView::Owned(_) => None,
+        }
+    }
+
+    /// Dictionary of a string column
+    pub fn dictionary(&self, i: usize) -> Vec<String> {
+        match self.table.columns().get(i).map(|c| c.values()) {
+            Some(ColumnView::Codes { dictionary, .. }) => dictionary.to_vec(),
+            Some(ColumnView::Owned(ColumnData::Dictionary { dictionary, .. })) => {
+                dictionary.to_vec()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Validity mask (1 = present)
+    pub fn validity(&self, i: usize) -> Vec<u8> {
+        self.table
+            .columns()
+            .get(i)
+            .map(|c| c.validity().to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Copy into an owned table (missing slots get the usual sentinels)
+    #[wasm_bindgen(js_name = toTable)]
+    pub fn to_table(&self) -> TableWasm {
+        TableWasm::new(self.table.to_table(), 0)
+    }
+}
+
+/// Import a table from C data interface structures at the given addresses
+/// without copying its fixed-width buffers.
+///
+/// The structures are moved out and released when the returned object is
+/// freed; the caller must not release them again.
+///
+/// # Safety
+/// As for [`import_arrow_c_wasm`]; the buffers must stay valid until the
+/// returned object is freed.
+#[wasm_bindgen]
+pub unsafe fn import_arrow_c_view_wasm(
+    schema_ptr: usize,
+    array_ptr: usize,
+) -> Result<ArrowCImport, JsValue> {
+    // SAFETY: guaranteed by the caller
+    unsafe {
+        ImportedTable::from_ptrs(schema_ptr as *mut ArrowSchema, array_ptr as *mut ArrowArray)
+    }
+    .map(|table| ArrowCImport { table })
+    .map_err(|e| JsValue::from_str(&format!("Arrow error: {}", e)))
+}
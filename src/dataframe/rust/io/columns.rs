@@ -0,0 +1,457 @@
+//! Typed column buffers shared by the IO readers and writers
+//!
+//! Columns use the same physical layouts as the dataframe kernels:
+//! numbers as `f64`, strings as dictionary-encoded `u32` codes, dates as epoch
+//! days (`i32`) and datetimes as epoch milliseconds (`f64`). Every column
+//! carries a validity mask (1 = present, 0 = missing). Dictionaries are
+//! shared (`Arc`) between the batches a streaming reader emits.
+
+use crate::temporal::NA_DAY;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Code stored for missing entries of dictionary-encoded columns
+pub const NA_CODE: u32 = u32::MAX;
+
+/// Logical column type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    Boolean,
+    Int32,
+    Float64,
+    String,
+    Date,
+    Datetime,
+}
+
+impl ColumnType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColumnType::Boolean => "boolean",
+            ColumnType::Int32 => "int32",
+            ColumnType::Float64 => "float64",
+            ColumnType::String => "string",
+            ColumnType::Date => "date",
+            ColumnType::Datetime => "datetime",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ColumnType> {
+        let t = match name.trim().to_ascii_lowercase().as_str() {
+            "boolean" | "bool" | "logical" => ColumnType::Boolean,
+            "int32" | "int" | "integer" => ColumnType::Int32,
+            "float64" | "double" | "number" | "numeric" => ColumnType::Float64,
+            "string" | "str" | "character" | "categorical" => ColumnType::String,
+            "date" => ColumnType::Date,
+            "datetime" | "timestamp" => ColumnType::Datetime,
+            _ => return None,
+        };
+        Some(t)
+    }
+}
+
+/// Physical column storage. Missing slots hold 0 / NaN / `NA_CODE` / `NA_DAY`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnData {
+    Boolean(Vec<u8>),
+    Int32(Vec<i32>),
+    Float64(Vec<f64>),
+    Dictionary {
+        codes: Vec<u32>,
+        dictionary: Arc<Vec<String>>,
+    },
+    Date(Vec<i32>),
+    Datetime(Vec<f64>),
+}
+
+impl ColumnData {
+    pub fn len(&self) -> usize {
+        match self {
+            ColumnData::Boolean(v) => v.len(),
+            ColumnData::Int32(v) => v.len(),
+            ColumnData::Float64(v) => v.len(),
+            ColumnData::Dictionary { codes, .. } => codes.len(),
+            ColumnData::Date(v) => v.len(),
+            ColumnData::Datetime(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn column_type(&self) -> ColumnType {
+        match self {
+            ColumnData::Boolean(_) => ColumnType::Boolean,
+            ColumnData::Int32(_) => ColumnType::Int32,
+            ColumnData::Float64(_) => ColumnType::Float64,
+            ColumnData::Dictionary { .. } => ColumnType::String,
+            ColumnData::Date(_) => ColumnType::Date,
+            ColumnData::Datetime(_) => ColumnType::Datetime,
+        }
+    }
+}
+
+/// A named column with its validity mask
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub data: ColumnData,
+    pub validity: Vec<u8>,
+}
+
+impl Column {
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn column_type(&self) -> ColumnType {
+        self.data.column_type()
+    }
+
+    pub fn null_count(&self) -> usize {
+        self.validity.iter().filter(|&&v| v == 0).count()
+    }
+
+    /// Numeric view as f64 (booleans as 0/1, dates as epoch days); `None` for strings
+    pub fn to_f64(&self) -> Option<Vec<f64>> {
+        let out: Vec<f64> = match &self.data {
+            ColumnData::Boolean(v) => v.iter().map(|&b| b as f64).collect(),
+            ColumnData::Int32(v) => v.iter().map(|&x| x as f64).collect(),
+            ColumnData::Float64(v) | ColumnData::Datetime(v) => v.clone(),
+            ColumnData::Date(v) => v.iter().map(|&d| d as f64).collect(),
+            ColumnData::Dictionary { .. } => return None,
+        };
+        Some(
+            out.into_iter()
+                .zip(&self.validity)
+                .map(|(x, &ok)| if ok != 0 { x } else { f64::NAN })
+                .collect(),
+        )
+    }
+
+    /// Append the rows of `other`, which must have the same type, except that
+    /// Int32 and Float64 combine as Float64 (streamed batches of a column
+    /// widen once a non-integer is seen). Dictionary codes of `other` are
+    /// remapped onto this column's dictionary.
+    pub fn append(&mut self, other: Column) -> Result<(), String> {
+        if let (ColumnData::Int32(_), ColumnData::Float64(_)) = (&self.data, &other.data) {
+            self.data = ColumnData::Float64(self.to_f64().expect("numeric column"));
+        }
+        let other_f64 = match other.data {
+            ColumnData::Int32(_) if matches!(self.data, ColumnData::Float64(_)) => {
+                other.to_f64().map(ColumnData::Float64)
+            }
+            _ => None,
+        };
+        match (&mut self.data, other_f64.unwrap_or(other.data)) {
+            (ColumnData::Boolean(a), ColumnData::Boolean(b)) => a.extend(b),
+            (ColumnData::Int32(a), ColumnData::Int32(b)) => a.extend(b),
+            (ColumnData::Float64(a), ColumnData::Float64(b)) => a.extend(b),
+            (ColumnData::Date(a), ColumnData::Date(b)) => a.extend(b),
+            (ColumnData::Datetime(a), ColumnData::Datetime(b)) => a.extend(b),
+            (
+                ColumnData::Dictionary { codes, dictionary },
+                ColumnData::Dictionary {
+                    codes: other_codes,
+                    dictionary: other_dictionary,
+                },
+            ) => {
+                let shared_prefix = Arc::ptr_eq(dictionary, &other_dictionary)
+                    || other_dictionary.len() <= dictionary.len()
+                        && other_dictionary
+                            .iter()
+                            .zip(dictionary.iter())
+                            .all(|(a, b)| a == b);
+                if shared_prefix {
+                    codes.extend(other_codes);
+                } else {
+                    let mut index: HashMap<&str, u32> = HashMap::new();
+                    for (i, s) in dictionary.iter().enumerate() {
+                        index.entry(s.as_str()).or_insert(i as u32);
+                    }
+                    let mut added = Vec::new();
+                    let remap: Vec<u32> = other_dictionary
+                        .iter()
+                        .map(|s| match index.get(s.as_str()) {
+                            Some(&c) => c,
+                            None => {
+                                let c = (dictionary.len() + added.len()) as u32;
+                                added.push(s.clone());
+                                c
+                            }
+                        })
+                        .collect();
+                    codes.extend(
+                        other_codes
+                            .into_iter()
+                            .map(|c| remap.get(c as usize).copied().unwrap_or(NA_CODE)),
+                    );
+                    Arc::make_mut(dictionary).extend(added);
+                }
+            }
+            (a, b) => {
+                return Err(format!(
+                    "Cannot append {} column to {} column '{}'",
+                    b.column_type().as_str(),
+                    a.column_type().as_str(),
+                    self.name
+                ));
+            }
+        }
+        self.validity.extend(other.validity);
+        Ok(())
+    }
+
+    /// Rows where `mask` is true
+    pub fn filter(&self, mask: &[bool]) -> Column {
+        fn keep<T: Copy>(v: &[T], mask: &[bool]) -> Vec<T> {
+            v.iter()
+                .zip(mask)
+                .filter(|(_, m)| **m)
+                .map(|(x, _)| *x)
+                .collect()
+        }
+        let data = match &self.data {
+            ColumnData::Boolean(v) => ColumnData::Boolean(keep(v, mask)),
+            ColumnData::Int32(v) => ColumnData::Int32(keep(v, mask)),
+            ColumnData::Float64(v) => ColumnData::Float64(keep(v, mask)),
+            ColumnData::Dictionary { codes, dictionary } => ColumnData::Dictionary {
+                codes: keep(codes, mask),
+                dictionary: dictionary.clone(),
+            },
+            ColumnData::Date(v) => ColumnData::Date(keep(v, mask)),
+            ColumnData::Datetime(v) => ColumnData::Datetime(keep(v, mask)),
+        };
+        Column {
+            name: self.name.clone(),
+            data,
+            validity: keep(&self.validity, mask),
+        }
+    }
+
+    /// String value at `row` (`None` when missing or not a string column)
+    pub fn str_at(&self, row: usize) -> Option<&str> {
+        match &self.data {
+            ColumnData::Dictionary { codes, dictionary } if self.validity[row] != 0 => {
+                dictionary.get(codes[row] as usize).map(String::as_str)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A set of equal-length columns
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Table {
+    pub columns: Vec<Column>,
+}
+
+impl Table {
+    pub fn n_rows(&self) -> usize {
+        self.columns.first().map_or(0, Column::len)
+    }
+
+    pub fn n_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+
+    /// Append the rows of a table with the same columns (matched by position)
+    pub fn append(&mut self, other: Table) -> Result<(), String> {
+        if self.columns.is_empty() {
+            *self = other;
+            return Ok(());
+        }
+        if other.columns.len() != self.columns.len() {
+            return Err(format!(
+                "Cannot append a table with {} columns to one with {}",
+                other.columns.len(),
+                self.columns.len()
+            ));
+        }
+        for (a, b) in self.columns.iter_mut().zip(other.columns) {
+            a.append(b)?;
+        }
+        Ok(())
+    }
+}
+
+/// Append-only builder for one column
+#[derive(Debug, Clone)]
+pub struct ColumnBuilder {
+    data: ColumnData,
+    validity: Vec<u8>,
+    index: HashMap<String, u32>,
+}
+
+impl ColumnBuilder {
+    pub fn new(column_type: ColumnType) -> Self {
+        let data = match column_type {
+            ColumnType::Boolean => ColumnData::Boolean(Vec::new()),
+            ColumnType::Int32 => ColumnData::Int32(Vec::new()),
+            ColumnType::Float64 => ColumnData::Float64(Vec::new()),
+            ColumnType::String => ColumnData::Dictionary {
+                codes: Vec::new(),
+                dictionary: Arc::default(),
+            },
+            ColumnType::Date => ColumnData::Date(Vec::new()),
+            ColumnType::Datetime => ColumnData::Datetime(Vec::new()),
+        };
+        ColumnBuilder {
+            data,
+            validity: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    pub fn column_type(&self) -> ColumnType {
+        self.data.column_type()
+    }
+
+    pub fn len(&self) -> usize {
+        self.validity.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validity.is_empty()
+    }
+
+    pub fn push_null(&mut self) {
+        match &mut self.data {
+            ColumnData::Boolean(v) => v.push(0),
+            ColumnData::Int32(v) => v.push(0),
+            ColumnData::Float64(v) | ColumnData::Datetime(v) => v.push(f64::NAN),
+            ColumnData::Dictionary { codes, .. } => codes.push(NA_CODE),
+            ColumnData::Date(v) => v.push(NA_DAY),
+        }
+        self.validity.push(0);
+    }
+
+    pub fn push_bool(&mut self, b: bool) {
+        if let ColumnData::Boolean(v) = &mut self.data {
+            v.push(b as u8);
+            self.validity.push(1);
+        } else {
+            self.push_f64(b as u8 as f64);
+        }
+    }
+
+    pub fn push_i32(&mut self, x: i32) {
+        match &mut self.data {
+            ColumnData::Int32(v) => {
+                v.push(x);
+                self.validity.push(1);
+            }
+            ColumnData::Date(v) => {
+                v.push(x);
+                self.validity.push(1);
+            }
+            _ => self.push_f64(x as f64),
+        }
+    }
+
+    /// Push a number; an Int32 column is widened to Float64 when needed
+    pub fn push_f64(&mut self, x: f64) {
+        if let ColumnData::Int32(v) = &self.data {
+            if x.fract() == 0.0 && x >= i32::MIN as f64 && x <= i32::MAX as f64 {
+                let x = x as i32;
+                self.push_i32(x);
+                return;
+            }
+            let widened = v
+                .iter()
+                .zip(&self.validity)
+                .map(|(&i, &ok)| if ok != 0 { i as f64 } else { f64::NAN })
+                .collect();
+            self.data = ColumnData::Float64(widened);
+        }
+        match &mut self.data {
+            ColumnData::Float64(v) | ColumnData::Datetime(v) => {
+                v.push(x);
+                self.validity.push(1);
+            }
+            ColumnData::Boolean(v) => {
+                v.push((x != 0.0) as u8);
+                self.validity.push(1);
+            }
+            ColumnData::Date(v) => {
+                v.push(x.floor() as i32);
+                self.validity.push(1);
+            }
+            ColumnData::Int32(_) => unreachable!("Int32 widened above"),
+            ColumnData::Dictionary { .. } => self.push_str(&x.to_string()),
+        }
+    }
+
+    /// Dictionary code for `s`, adding it to the dictionary when new
+    /// (first-seen code order). Returns `NA_CODE` for non-string columns.
+    pub fn intern(&mut self, s: &str) -> u32 {
+        let ColumnData::Dictionary { dictionary, .. } = &mut self.data else {
+            return NA_CODE;
+        };
+        match self.index.get(s) {
+            Some(&c) => c,
+            None => {
+                let c = dictionary.len() as u32;
+                // Copies the dictionary only if a taken batch still shares it
+                Arc::make_mut(dictionary).push(s.to_string());
+                self.index.insert(s.to_string(), c);
+                c
+            }
+        }
+    }
+
+    /// Push a code returned by [`ColumnBuilder::intern`]
+    pub fn push_code(&mut self, code: u32) {
+        match &mut self.data {
+            ColumnData::Dictionary { codes, .. } if code != NA_CODE => {
+                codes.push(code);
+                self.validity.push(1);
+            }
+            _ => self.push_null(),
+        }
+    }
+
+    /// Push a string, dictionary-encoding it
+    pub fn push_str(&mut self, s: &str) {
+        let code = self.intern(s);
+        self.push_code(code);
+    }
+
+    /// Drain the rows built so far. Dictionary codes stay stable across calls:
+    /// the returned dictionary holds every entry seen so far and is shared
+    /// with the builder rather than copied.
+    pub fn take(&mut self) -> (ColumnData, Vec<u8>) {
+        let data = match &mut self.data {
+            ColumnData::Boolean(v) => ColumnData::Boolean(std::mem::take(v)),
+            ColumnData::Int32(v) => ColumnData::Int32(std::mem::take(v)),
+            ColumnData::Float64(v) => ColumnData::Float64(std::mem::take(v)),
+            ColumnData::Dictionary { codes, dictionary } => ColumnData::Dictionary {
+                codes: std::mem::take(codes),
+                dictionary: Arc::clone(dictionary),
+            },
+            ColumnData::Date(v) => ColumnData::Date(std::mem::take(v)),
+            ColumnData::Datetime(v) => ColumnData::Datetime(std::mem::take(v)),
+        };
+        (data, std::mem::take(&mut self.validity))
+    }
+
+    pub fn finish(mut self, name: &str) -> Column {
+        let (data, validity) = self.take();
+        Column {
+            name: name.to_string(),
+            data,
+            validity,
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Cell parsing and column type inference for text sources
+
+use crate::io::columns::{ColumnBuilder, ColumnType};
+use crate::temporal::{MS_PER_DAY, parse_iso8601, parse_strftime};
+
+/// Formats used to recognise temporal cells (`None` = ISO-8601)
+#[derive(Debug, Clone, Default)]
+pub struct TemporalFormats {
+    pub date: Option<String>,
+    pub datetime: Option<String>,
+}
+
+pub fn parse_bool(s: &str) -> Option<bool> {
+    match s {
+        "true" | "TRUE" | "True" | "T" | "t" => Some(true),
+        "false" | "FALSE" | "False" | "F" | "f" => Some(false),
+        _ => None,
+    }
+}
+
+pub fn parse_i32(s: &str) -> Option<i32> {
+    s.strip_prefix('+').unwrap_or(s).parse::<i32>().ok()
+}
+
+/// Number in decimal or exponent notation, or one of R's spellings `Inf`,
+/// `infinity` and `NaN`; each may carry a sign
+pub fn parse_f64(s: &str) -> Option<f64> {
+    let (negative, body) = match s.as_bytes().first()? {
+        b'-' => (true, &s[1..]),
+        b'+' => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let first = body.as_bytes().first()?;
+    let x = if first.is_ascii_digit() || *first == b'.' {
+        body.parse::<f64>().ok()?
+    } else {
+        // Words Rust accepts in any case ("inf", "NAN") are taken only as R
+        // spells them
+        match body {
+            "Inf" | "infinity" => f64::INFINITY,
+            "NaN" => f64::NAN,
+            _ => return None,
+        }
+    };
+    Some(if negative { -x } else { x })
+}
+
+/// Epoch days for a date-only cell
+pub fn parse_date(s: &str, formats: &TemporalFormats) -> Option<i32> {
+    let ms = match &formats.date {
+        Some(f) => parse_strftime(s, f)?,
+        None if s.len() == 10 => parse_iso8601(s)?,
+        None => return None,
+    };
+    Some(ms.div_euclid(MS_PER_DAY) as i32)
+}
+
+/// Epoch milliseconds for a datetime cell
+pub fn parse_datetime(s: &str, formats: &TemporalFormats) -> Option<f64> {
+    match &formats.datetime {
+        Some(f) => parse_strftime(s, f).map(|t| t as f64),
+        None => parse_iso8601(s).map(|t| t as f64),
+    }
+}
+
+/// Incremental type inference for one column.
+///
+/// Candidates are narrowed from most to least specific
+/// (boolean, int32, float64, date, datetime) with string as the fallback.
+/// A column with no non-missing values is inferred as string.
+#[derive(Debug, Clone)]
+pub struct TypeInference {
+    boolean: bool,
+    int32: bool,
+    float64: bool,
+    date: bool,
+    datetime: bool,
+    seen: bool,
+}
+
+impl Default for TypeInference {
+    fn default() -> Self {
+        TypeInference {
+            boolean: true,
+            int32: true,
+            float64: true,
+            date: true,
+            datetime: true,
+            seen: false,
+        }
+    }
+}
+
+impl TypeInference {
+    pub fn observe(&mut self, s: &str, formats: &TemporalFormats) {
+        self.seen = true;
+        if self.boolean && parse_bool(s).is_none() {
+            self.boolean = false;
+        }
+        if self.int32 && parse_i32(s).is_none() {
+            self.int32 = false;
+        }
+        if self.float64 && parse_f64(s).is_none() {
+            self.float64 = false;
+        }
+        if self.date && parse_date(s, formats).is_none() {
+            self.date = false;
+        }
+        if self.datetime && parse_datetime(s, formats).is_none() {
+            self.datetime = false;
+        }
+    }
+
+    pub fn result(&self) -> ColumnType {
+        if !self.seen {
+            ColumnType::String
+        } else if self.boolean {
+            ColumnType::Boolean
+        } else if self.int32 {
+            ColumnType::Int32
+        } else if self.float64 {
+            ColumnType::Float64
+        } else if self.date {
+            ColumnType::Date
+        } else if self.datetime {
+            ColumnType::Datetime
+        } else {
+            ColumnType::String
+        }
+    }
+}
+
+/// Parse `s` into `builder` according to its current type.
+/// Returns false (and pushes a missing value) when the cell does not parse.
+pub fn push_cell(builder: &mut ColumnBuilder, s: &str, formats: &TemporalFormats) -> bool {
+    match builder.column_type() {
+        ColumnType::String => {
+            builder.push_str(s);
+            return true;
+        }
+        ColumnType::Boolean => {
+            if let Some(b) = parse_bool(s) {
+                builder.push_bool(b);
+                return true;
+            }
+        }
+        ColumnType::Int32 => {
+            if let Some(i) = parse_i32(s) {
+                builder.push_i32(i);
+                return true;
+            }
+            // Widens the column to float64
+            if let Some(x) = parse_f64(s) {
+                builder.push_f64(x);
+                return true;
+            }
+        }
+        ColumnType::Float64 => {
+            if let Some(x) = parse_f64(s) {
+                builder.push_f64(x);
+                return true;
+            }
+        }
+        ColumnType::Date => {
+            if let Some(d) = parse_date(s, formats) {
+                builder.push_i32(d);
+                return true;
+            }
+        }
+        ColumnType::Datetime => {
+            if let Some(t) = parse_datetime(s, formats) {
+                builder.push_f64(t);
+                return true;
+            }
+        }
+    }
+    builder.push_null();
+    false
+}
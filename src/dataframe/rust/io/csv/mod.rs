@@ -0,0 +1,13 @@
+//! CSV reading
+//!
+//! - `tokenizer`: incremental RFC 4180 tokenizer with configurable dialect
+//! - `infer`: cell parsers and column type inference
+//! - `reader`: streaming reader producing typed, dictionary-encoded columns
+
+pub mod infer;
+pub mod reader;
+pub mod tokenizer;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use reader::*;
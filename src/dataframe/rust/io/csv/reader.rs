@@ -0,0 +1,513 @@
+//! Streaming CSV reader producing typed columns
+
+use super::infer::{TemporalFormats, TypeInference, push_cell};
+use super::tokenizer::{Dialect, Record, Tokenizer};
+use crate::io::columns::{Column, ColumnBuilder, ColumnType, Table};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+
+/// Missing-value strings used when none are given (same as `read_csv.ts`)
+pub const DEFAULT_NA_VALUES: [&str; 5] = ["", "NA", "NaN", "null", "undefined"];
+
+/// Explicit column definition
+#[derive(Debug, Clone, Deserialize)]
+pub struct SchemaField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub column_type: ColumnType,
+}
+
+/// CSV reading options (camelCase when given as JSON)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CsvOptions {
+    pub delimiter: char,
+    /// Quote character; `None` disables quoting
+    pub quote: Option<char>,
+    /// Escape character inside quotes; `None` means doubled quotes (RFC 4180)
+    pub escape: Option<char>,
+    /// Lines starting with this character are skipped
+    pub comment: Option<char>,
+    pub has_header: bool,
+    /// Lines to skip before the header (after comment and blank-line handling)
+    pub skip_rows: usize,
+    pub skip_empty_lines: bool,
+    pub na_values: Vec<String>,
+    /// Trim surrounding whitespace from cells and header names
+    pub trim: bool,
+    /// Rows buffered for type inference before columns are built
+    pub infer_rows: usize,
+    /// Full explicit schema: output exactly these columns (matched by header
+    /// name, or by position without a header) and skip inference
+    pub schema: Vec<SchemaField>,
+    /// Per-column type overrides; other columns are inferred
+    pub column_types: HashMap<String, ColumnType>,
+    /// Subset of columns to read (empty = all)
+    pub select: Vec<String>,
+    /// strftime format for date columns (default ISO `YYYY-MM-DD`)
+    pub date_format: Option<String>,
+    /// strftime format for datetime columns (default ISO-8601)
+    pub datetime_format: Option<String>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: ',',
+            quote: Some('"'),
+            escape: None,
+            comment: None,
+            has_header: true,
+            skip_rows: 0,
+            skip_empty_lines: true,
+            na_values: DEFAULT_NA_VALUES.iter().map(|s| s.to_string()).collect(),
+            trim: true,
+            infer_rows: 1000,
+            schema: Vec::new(),
+            column_types: HashMap::new(),
+            select: Vec::new(),
+            date_format: None,
+            datetime_format: None,
+        }
+    }
+}
+
+fn ascii_byte(c: char, what: &str) -> Result<u8, String> {
+    if c.is_ascii() {
+        Ok(c as u8)
+    } else {
+        Err(format!("{} must be an ASCII character", what))
+    }
+}
+
+impl CsvOptions {
+    fn dialect(&self) -> Result<Dialect, String> {
+        Ok(Dialect {
+            delimiter: ascii_byte(self.delimiter, "delimiter")?,
+            quote: self.quote.map(|c| ascii_byte(c, "quote")).transpose()?,
+            escape: self.escape.map(|c| ascii_byte(c, "escape")).transpose()?,
+            comment: self.comment.map(|c| ascii_byte(c, "comment")).transpose()?,
+        })
+    }
+}
+
+/// Output layout once the schema is known
+struct Layout {
+    names: Vec<String>,
+    /// Source field index per output column
+    sources: Vec<usize>,
+    builders: Vec<ColumnBuilder>,
+    n_fields: usize,
+}
+
+/// Incremental CSV reader.
+///
+/// Feed bytes with [`CsvReader::push`], drain typed rows with
+/// [`CsvReader::take_batch`] and call [`CsvReader::finish`] at the end of
+/// input. Column types are inferred from the first `infer_rows` rows; later
+/// cells that do not parse become missing and are counted in
+/// [`CsvReader::problems`] (an int32 column widens to float64 instead; batches
+/// taken before that stay int32 and [`Table::append`] widens them).
+/// Dictionary codes of string columns are stable across batches, and the
+/// batches share one dictionary.
+pub struct CsvReader {
+    opts: CsvOptions,
+    formats: TemporalFormats,
+    na: HashSet<String>,
+    tokenizer: Tokenizer,
+    at_start: bool,
+    skipped: usize,
+    header: Option<Vec<String>>,
+    pending: Vec<Vec<String>>,
+    layout: Option<Layout>,
+    problems: usize,
+    error: Option<String>,
+}
+
+impl CsvReader {
+    pub fn new(opts: CsvOptions) -> Result<Self, String> {
+        let dialect = opts.dialect()?;
+        Ok(CsvReader {
+            formats: TemporalFormats {
+                date: opts.date_format.clone(),
+                datetime: opts.datetime_format.clone(),
+            },
+            na: opts.na_values.iter().cloned().collect(),
+            tokenizer: Tokenizer::new(dialect),
+            at_start: true,
+            skipped: 0,
+            header: None,
+            pending: Vec::new(),
+            layout: None,
+            problems: 0,
+            error: None,
+            opts,
+        })
+    }
+
+    /// Cells that failed to parse, ragged rows and unterminated quotes seen so far
+    pub fn problems(&self) -> usize {
+        self.problems
+    }
+
+    /// Output column names and types, once known
+    pub fn schema(&self) -> Option<Vec<(String, ColumnType)>> {
+        self.layout.as_ref().map(|l| {
+            l.names
+                .iter()
+                .cloned()
+                .zip(l.builders.iter().map(ColumnBuilder::column_type))
+                .collect()
+        })
+    }
+
+    /// Feed the next chunk of bytes
+    pub fn push(&mut self, mut chunk: &[u8]) -> Result<(), String> {
+        if self.at_start && !chunk.is_empty() {
+            self.at_start = false;
+            chunk = chunk.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(chunk);
+        }
+        let mut records = Vec::new();
+        self.tokenizer.feed(chunk, &mut |r| records.push(r));
+        for r in records {
+            self.handle_record(r);
+        }
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Signal end of input; returns the remaining rows
+    pub fn finish(&mut self) -> Result<Table, String> {
+        let mut records = Vec::new();
+        self.tokenizer.finish(&mut |r| records.push(r));
+        if self.tokenizer.unterminated_quote() {
+            self.problems += 1;
+        }
+        for r in records {
+            self.handle_record(r);
+        }
+        if self.layout.is_none() {
+            self.build_layout();
+        }
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        Ok(self.take_batch())
+    }
+
+    /// Drain the rows parsed so far (empty until the schema is known)
+    pub fn take_batch(&mut self) -> Table {
+        let Some(layout) = self.layout.as_mut() else {
+            return Table::default();
+        };
+        let columns = layout
+            .names
+            .iter()
+            .zip(layout.builders.iter_mut())
+            .map(|(name, b)| {
+                let (data, validity) = b.take();
+                Column {
+                    name: name.clone(),
+                    data,
+                    validity,
+                }
+            })
+            .collect();
+        Table { columns }
+    }
+
+    fn handle_record(&mut self, record: Record) {
+        if self.error.is_some() {
+            return;
+        }
+        if record.blank && self.opts.skip_empty_lines {
+            return;
+        }
+        if self.skipped < self.opts.skip_rows {
+            self.skipped += 1;
+            return;
+        }
+        let fields = record.fields;
+        if self.opts.has_header && self.header.is_none() {
+            let names = fields
+                .into_iter()
+                .map(|h| {
+                    if self.opts.trim {
+                        h.trim().to_string()
+                    } else {
+                        h
+                    }
+                })
+                .collect();
+            self.header = Some(names);
+            if !self.opts.schema.is_empty() {
+                self.build_layout();
+            }
+            return;
+        }
+        if self.layout.is_none() && !self.opts.schema.is_empty() {
+            self.build_layout();
+        }
+        match self.layout {
+            Some(_) => self.append_row(&fields),
+            None => {
+                self.pending.push(fields);
+                if self.pending.len() >= self.opts.infer_rows.max(1) {
+                    self.build_layout();
+                }
+            }
+        }
+    }
+
+    fn cell<'a>(&self, raw: &'a str) -> Option<&'a str> {
+        let v = if self.opts.trim { raw.trim() } else { raw };
+        if self.na.contains(v) { None } else { Some(v) }
+    }
+
+    fn build_layout(&mut self) {
+        let width = self
+            .header
+            .as_ref()
+            .map(Vec::len)
+            .unwrap_or(0)
+            .max(self.pending.iter().map(Vec::len).max().unwrap_or(0));
+        let names: Vec<String> = (0..width)
+            .map(|i| {
+                self.header
+                    .as_ref()
+                    .and_then(|h| h.get(i))
+                    .filter(|h| !h.is_empty())
+                    .cloned()
+                    .unwrap_or_else(|| format!("X{}", i + 1))
+            })
+            .collect();
+
+        // Choose (source index, name, type) per output column
+        let mut columns: Vec<(usize, String, ColumnType)> = Vec::new();
+        if !self.opts.schema.is_empty() {
+            for (pos, field) in self.opts.schema.clone().into_iter().enumerate() {
+                let src = if self.header.is_some() {
+                    match names.iter().position(|n| *n == field.name) {
+                        Some(i) => i,
+                        None => {
+                            self.error =
+                                Some(format!("Column '{}' not found in CSV header", field.name));
+                            return;
+                        }
+                    }
+                } else {
+                    pos
+                };
+                columns.push((src, field.name, field.column_type));
+            }
+        } else {
+            for (i, name) in names.iter().enumerate() {
+                if !self.opts.select.is_empty() && !self.opts.select.contains(name) {
+                    continue;
+                }
+                let t = match self.opts.column_types.get(name) {
+                    Some(&t) => t,
+                    None => {
+                        let mut inf = TypeInference::default();
+                        for row in &self.pending {
+                            if let Some(v) = row.get(i).and_then(|r| self.cell(r)) {
+                                inf.observe(v, &self.formats);
+                            }
+                        }
+                        inf.result()
+                    }
+                };
+                columns.push((i, name.clone(), t));
+            }
+            if let Some(missing) = self.opts.select.iter().find(|s| !names.contains(s)) {
+                self.error = Some(format!("Column '{}' not found in CSV", missing));
+                return;
+            }
+        }
+
+        self.layout = Some(Layout {
+            sources: columns.iter().map(|c| c.0).collect(),
+            builders: columns.iter().map(|c| ColumnBuilder::new(c.2)).collect(),
+            names: columns.into_iter().map(|c| c.1).collect(),
+            n_fields: width.max(self.opts.schema.len()),
+        });
+        for row in std::mem::take(&mut self.pending) {
+            self.append_row(&row);
+        }
+    }
+
+    fn append_row(&mut self, fields: &[String]) {
+        let Some(layout) = self.layout.as_mut() else {
+            return;
+        };
+        if self.header.is_some() && fields.len() != layout.n_fields {
+            self.problems += 1;
+        }
+        for (k, &src) in layout.sources.iter().enumerate() {
+            let builder = &mut layout.builders[k];
+            let raw = fields.get(src).map(String::as_str);
+            let v = raw.map(|r| if self.opts.trim { r.trim() } else { r });
+            match v {
+                Some(v) if !self.na.contains(v) => {
+                    if !push_cell(builder, v, &self.formats) {
+                        self.problems += 1;
+                    }
+                }
+                _ => builder.push_null(),
+            }
+        }
+    }
+}
+
+/// Read a complete CSV buffer into a table
+pub fn read_csv(bytes: &[u8], opts: CsvOptions) -> Result<(Table, usize), String> {
+    let mut reader = CsvReader::new(opts)?;
+    reader.push(bytes)?;
+    let table = reader.finish()?;
+    Ok((table, reader.problems()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::columns::ColumnData;
+
+    fn read(s: &str, opts: CsvOptions) -> Table {
+        read_csv(s.as_bytes(), opts).unwrap().0
+    }
+
+    #[test]
+    fn infers_column_types() {
+        let t = read(
+            "id,score,flag,name,day,ts\n\
+             1,2.5,true,a,2024-01-02,2024-01-02T03:04:05Z\n\
+             2,NA,false,b,2024-01-03,2024-01-02 10:00\n\
+             3,4,TRUE,a,,2024-01-04T00:00:00+01:00\n",
+            CsvOptions::default(),
+        );
+        let types: Vec<_> = t.columns.iter().map(|c| c.column_type()).collect();
+        assert_eq!(
+            types,
+            vec![
+                ColumnType::Int32,
+                ColumnType::Float64,
+                ColumnType::Boolean,
+                ColumnType::String,
+                ColumnType::Date,
+                ColumnType::Datetime
+            ]
+        );
+        assert_eq!(t.n_rows(), 3);
+        assert_eq!(t.columns[1].validity, vec![1, 0, 1]);
+        match &t.columns[3].data {
+            ColumnData::Dictionary { codes, dictionary } => {
+                assert_eq!(codes, &vec![0, 1, 0]);
+                assert_eq!(dictionary.as_slice(), ["a", "b"]);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(
+            t.columns[4].data,
+            ColumnData::Date(vec![19724, 19725, i32::MIN])
+        );
+    }
+
+    #[test]
+    fn handles_quotes_across_chunks() {
+        let text = "a;b\n\"x;\"\"y\"\"\r\nz\";2\n\"p\";3";
+        let opts = CsvOptions {
+            delimiter: ';',
+            ..Default::default()
+        };
+        let mut reader = CsvReader::new(opts).unwrap();
+        for chunk in text.as_bytes().chunks(3) {
+            reader.push(chunk).unwrap();
+        }
+        let t = reader.finish().unwrap();
+        assert_eq!(t.columns[0].str_at(0), Some("x;\"y\"\r\nz"));
+        assert_eq!(t.columns[0].str_at(1), Some("p"));
+        assert_eq!(t.columns[1].data, ColumnData::Int32(vec![2, 3]));
+        assert_eq!(reader.problems(), 0);
+    }
+
+    #[test]
+    fn widens_and_counts_problems_after_inference() {
+        let opts = CsvOptions {
+            infer_rows: 2,
+            ..Default::default()
+        };
+        let (t, problems) = read_csv(b"x,y\n1,1\n2,2\n3.5,oops\n", opts).unwrap();
+        assert_eq!(t.columns[0].data, ColumnData::Float64(vec![1.0, 2.0, 3.5]));
+        assert_eq!(t.columns[1].validity, vec![1, 1, 0]);
+        assert_eq!(problems, 1);
+    }
+
+    #[test]
+    fn explicit_schema_without_header() {
+        let opts = CsvOptions {
+            has_header: false,
+            comment: Some('#'),
+            schema: vec![
+                SchemaField {
+                    name: "when".into(),
+                    column_type: ColumnType::Date,
+                },
+                SchemaField {
+                    name: "code".into(),
+                    column_type: ColumnType::String,
+                },
+            ],
+            date_format: Some("%d/%m/%Y".into()),
+            ..Default::default()
+        };
+        let t = read("# comment\n02/01/2024,007\n\n03/01/2024,008\n", opts);
+        assert_eq!(t.columns[0].data, ColumnData::Date(vec![19724, 19725]));
+        assert_eq!(t.columns[1].str_at(0), Some("007"));
+    }
+
+    #[test]
+    fn batches_append_after_widening() {
+        let opts = CsvOptions {
+            infer_rows: 1,
+            ..Default::default()
+        };
+        let mut reader = CsvReader::new(opts).unwrap();
+        reader.push(b"x,s\n1,a\n2,b\n").unwrap();
+        let mut t = reader.take_batch();
+        reader.push(b"2.5,a\n").unwrap();
+        let second = reader.take_batch();
+        assert_eq!(t.columns[0].column_type(), ColumnType::Int32);
+        assert_eq!(second.columns[0].column_type(), ColumnType::Float64);
+        let (
+            ColumnData::Dictionary { dictionary: d1, .. },
+            ColumnData::Dictionary { dictionary: d2, .. },
+        ) = (&t.columns[1].data, &second.columns[1].data)
+        else {
+            panic!("string columns expected");
+        };
+        assert!(std::sync::Arc::ptr_eq(d1, d2));
+
+        t.append(second).unwrap();
+        t.append(reader.finish().unwrap()).unwrap();
+        assert_eq!(t.columns[0].data, ColumnData::Float64(vec![1.0, 2.0, 2.5]));
+        assert_eq!(t.columns[1].str_at(2), Some("a"));
+    }
+
+    #[test]
+    fn parses_signed_infinities() {
+        let t = read("x\nInf\n-Inf\n+infinity\nNaN\n1e3\n", CsvOptions::default());
+        let ColumnData::Float64(v) = &t.columns[0].data else {
+            panic!("float64 expected");
+        };
+        assert_eq!(&v[..3], [f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY]);
+        assert!(v[3].is_nan());
+        assert_eq!(v[4], 1000.0);
+        // Other spellings Rust accepts stay strings, whatever the sign
+        for word in ["inf", "-inf", "+INF", "nan", "-+1"] {
+            let t = read(&format!("x\n{}\n", word), CsvOptions::default());
+            assert_eq!(t.columns[0].column_type(), ColumnType::String, "{}", word);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Incremental CSV tokenizer
+//!
+//! A byte-level state machine that can be fed arbitrary chunks; records that
+//! straddle chunk boundaries (including multi-byte UTF-8 sequences and quoted
+//! newlines) are carried over to the next call.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    FieldStart,
+    Unquoted,
+    Quoted,
+    /// Saw a quote inside a quoted field: either a doubled quote or the closing one
+    QuotedQuote,
+    /// Saw the escape character inside a quoted field
+    QuotedEscape,
+    Comment,
+}
+
+/// Tokenizer configuration (ASCII bytes)
+#[derive(Debug, Clone, Copy)]
+pub struct Dialect {
+    pub delimiter: u8,
+    pub quote: Option<u8>,
+    /// Escape character inside quoted fields; `None` means quotes are doubled
+    pub escape: Option<u8>,
+    /// Lines starting with this byte are skipped
+    pub comment: Option<u8>,
+}
+
+/// One tokenized record
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Record {
+    pub fields: Vec<String>,
+    /// True for a line with no characters at all
+    pub blank: bool,
+}
+
+pub struct Tokenizer {
+    dialect: Dialect,
+    state: State,
+    field: Vec<u8>,
+    fields: Vec<String>,
+    line_started: bool,
+    pending_cr: bool,
+    unterminated: bool,
+}
+
+impl Tokenizer {
+    pub fn new(dialect: Dialect) -> Self {
+        Tokenizer {
+            dialect,
+            state: State::FieldStart,
+            field: Vec::new(),
+            fields: Vec::new(),
+            line_started: false,
+            pending_cr: false,
+            unterminated: false,
+        }
+    }
+
+    /// True if the input ended inside a quoted field
+    pub fn unterminated_quote(&self) -> bool {
+        self.unterminated
+    }
+
+    fn end_field(&mut self) {
+        let bytes = std::mem::take(&mut self.field);
+        let s = match String::from_utf8(bytes) {
+            Ok(s) => s,
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        };
+        self.fields.push(s);
+        self.state = State::FieldStart;
+    }
+
+    fn end_record(&mut self, out: &mut impl FnMut(Record)) {
+        self.end_field();
+        let blank = !self.line_started;
+        out(Record {
+            fields: std::mem::take(&mut self.fields),
+            blank,
+        });
+        self.line_started = false;
+    }
+
+    fn newline(&mut self, b: u8, out: &mut impl FnMut(Record)) {
+        self.end_record(out);
+        self.pending_cr = b == b'\r';
+    }
+
+    /// Feed a chunk, emitting every completed record
+    pub fn feed(&mut self, chunk: &[u8], out: &mut impl FnMut(Record)) {
+        let d = self.dialect;
+        for &b in chunk {
+            if self.pending_cr {
+                self.pending_cr = false;
+                if b == b'\n' {
+                    continue;
+                }
+            }
+            match self.state {
+                State::FieldStart => {
+                    if !self.line_started && self.fields.is_empty() && d.comment == Some(b) {
+                        self.state = State::Comment;
+                        continue;
+                    }
+                    if b == b'\n' || b == b'\r' {
+                        self.newline(b, out);
+                        continue;
+                    }
+                    self.line_started = true;
+                    if d.quote == Some(b) {
+                        self.state = State::Quoted;
+                    } else if b == d.delimiter {
+                        self.end_field();
+                    } else {
+                        self.field.push(b);
+                        self.state = State::Unquoted;
+                    }
+                }
+                State::Unquoted => {
+                    if b == d.delimiter {
+                        self.end_field();
+                    } else if b == b'\n' || b == b'\r' {
+                        self.newline(b, out);
+                    } else {
+                        self.field.push(b);
+                    }
+                }
+                State::Quoted => {
+                    if d.escape.is_some() && d.escape == Some(b) && d.escape != d.quote {
+                        self.state = State::QuotedEscape;
+                    } else if d.quote == Some(b) {
+                        self.state = State::QuotedQuote;
+                    } else {
+                        self.field.push(b);
+                    }
+                }
+                State::QuotedEscape => {
+                    self.field.push(b);
+                    self.state = State::Quoted;
+                }
+                State::QuotedQuote => {
+                    if d.quote == Some(b) {
+                        self.field.push(b);
+                        self.state = State::Quoted;
+                    } else if b == d.delimiter {
+                        self.end_field();
+                    } else if b == b'\n' || b == b'\r' {
+                        self.newline(b, out);
+                    } else {
+                        // Lenient: text after a closing quote is kept
+                        self.field.push(b);
+                        self.state = State::Unquoted;
+                    }
+                }
+                State::Comment => {
+                    if b == b'\n' || b == b'\r' {
+                        self.pending_cr = b == b'\r';
+                        self.state = State::FieldStart;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Flush the final record (input without a trailing newline)
+    pub fn finish(&mut self, out: &mut impl FnMut(Record)) {
+        match self.state {
+            State::Comment => self.state = State::FieldStart,
+            State::FieldStart if !self.line_started && self.fields.is_empty() => {}
+            State::Quoted | State::QuotedEscape => {
+                self.unterminated = true;
+                self.end_record(out);
+            }
+            _ => self.end_record(out),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! WASM bindings for the CSV reader
+
+#![cfg(feature = "wasm")]
+
+use super::reader::{CsvOptions, CsvReader};
+use crate::io::wasm::TableWasm;
+use wasm_bindgen::prelude::*;
+
+fn parse_options(options_json: &str) -> Result<CsvOptions, JsValue> {
+    if options_json.trim().is_empty() {
+        return Ok(CsvOptions::default());
+    }
+    serde_json::from_str(options_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid CSV options: {}", e)))
+}
+
+/// Read a complete CSV buffer into typed columns
+///
+/// # Arguments
+/// * `bytes` - UTF-8 CSV content
+/// * `options_json` - JSON `CsvOptions` (camelCase keys, empty = defaults)
+#[wasm_bindgen]
+pub fn read_csv_wasm(bytes: &[u8], options_json: &str) -> Result<TableWasm, JsValue> {
+    let opts = parse_options(options_json)?;
+    let (table, problems) = super::reader::read_csv(bytes, opts)
+        .map_err(|e| JsValue::from_str(&format!("CSV error: {}", e)))?;
+    Ok(TableWasm::new(table, problems))
+}
+
+/// Chunked CSV reader: `push` byte chunks, drain rows with `takeBatch`,
+/// and call `finish` once at the end of input
+#[wasm_bindgen]
+pub struct CsvStreamReader {
+    reader: CsvReader,
+}
+
+#[wasm_bindgen]
+impl CsvStreamReader {
+    #[wasm_bindgen(constructor)]
+    pub fn new(options_json: &str) -> Result<CsvStreamReader, JsValue> {
+        let opts = parse_options(options_json)?;
+        let reader =
+            CsvReader::new(opts).map_err(|e| JsValue::from_str(&format!("CSV error: {}", e)))?;
+        Ok(CsvStreamReader { reader })
+    }
+
+    pub fn push(&mut self, chunk: &[u8]) -> Result<(), JsValue> {
+        self.reader
+            .push(chunk)
+            .map_err(|e| JsValue::from_str(&format!("CSV error: {}", e)))
+    }
+
+    /// Rows parsed so far (no columns until the schema is known)
+    #[wasm_bindgen(js_name = takeBatch)]
+    pub fn take_batch(&mut self) -> TableWasm {
+        TableWasm::new(self.reader.take_batch(), self.reader.problems())
+    }
+
+    pub fn finish(&mut self) -> Result<TableWasm, JsValue> {
+        let table = self
+            .reader
+            .finish()
+            .map_err(|e| JsValue::from_str(&format!("CSV error: {}", e)))?;
+        Ok(TableWasm::new(table, self.reader.problems()))
+    }
+}
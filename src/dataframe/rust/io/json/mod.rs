@@ -0,0 +1,14 @@
+//! JSON reading and writing
+//!
+//! - `value`: order-preserving JSON values and flattening of nested objects
+//! - `reader`: NDJSON / records / columns reader with schema inference
+//! - `writer`: NDJSON / records / columns writer with optional re-nesting
+
+pub mod reader;
+pub mod value;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod writer;
+
+pub use reader::{JsonFormat, JsonReadOptions, read_json};
+pub use writer::{JsonWriteOptions, write_json};
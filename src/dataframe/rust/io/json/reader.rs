@@ -0,0 +1,486 @@
+//! NDJSON / JSON-records reader producing typed columns
+
+use super::value::JsonValue;
+use crate::io::columns::{ColumnBuilder, ColumnType, Table};
+use crate::io::csv::SchemaField;
+use crate::io::csv::infer::{TemporalFormats, TypeInference, push_cell};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Layout of a JSON document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonFormat {
+    /// Detect from the document: a top-level array is `records`, a single
+    /// object of equal-length arrays is `columns`, anything else `ndjson`
+    #[default]
+    Auto,
+    /// One object per line (any whitespace-separated sequence of objects)
+    Ndjson,
+    /// A single array of objects
+    Records,
+    /// A single object mapping column names to arrays of values
+    Columns,
+}
+
+/// JSON reading options (camelCase when given as JSON)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct JsonReadOptions {
+    pub format: JsonFormat,
+    /// Separator joining keys of nested objects into flat column names;
+    /// `None` keeps nested objects as JSON text
+    pub separator: Option<String>,
+    /// Rows used for type inference (`None` = all rows)
+    pub infer_rows: Option<usize>,
+    /// Full explicit schema: output exactly these columns and skip inference.
+    /// Columns absent from every record are all-missing.
+    pub schema: Vec<SchemaField>,
+    /// Per-column type overrides; other columns are inferred
+    pub column_types: HashMap<String, ColumnType>,
+    /// Subset of columns to read (empty = all)
+    pub select: Vec<String>,
+    /// Infer date and datetime columns from string values
+    pub parse_dates: bool,
+    /// strftime format for date strings (default ISO `YYYY-MM-DD`)
+    pub date_format: Option<String>,
+    /// strftime format for datetime strings (default ISO-8601)
+    pub datetime_format: Option<String>,
+}
+
+impl Default for JsonReadOptions {
+    fn default() -> Self {
+        JsonReadOptions {
+            format: JsonFormat::Auto,
+            separator: Some(".".to_string()),
+            infer_rows: None,
+            schema: Vec::new(),
+            column_types: HashMap::new(),
+            select: Vec::new(),
+            parse_dates: true,
+            date_format: None,
+            datetime_format: None,
+        }
+    }
+}
+
+/// Flattened records: column names in first-seen order and, per row, the
+/// `(column index, value)` pairs present in that record
+#[derive(Debug, Default)]
+struct Rows {
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+    rows: Vec<Vec<(usize, JsonValue)>>,
+}
+
+impl Rows {
+    fn push(&mut self, leaves: Vec<(String, JsonValue)>) {
+        let row = leaves
+            .into_iter()
+            .map(|(name, v)| {
+                let k = match self.index.get(&name) {
+                    Some(&k) => k,
+                    None => {
+                        let k = self.names.len();
+                        self.names.push(name.clone());
+                        self.index.insert(name, k);
+                        k
+                    }
+                };
+                (k, v)
+            })
+            .collect();
+        self.rows.push(row);
+    }
+}
+
+/// Whether `v` is a non-empty object of equal-length arrays
+fn is_columnar(v: &JsonValue) -> bool {
+    let JsonValue::Object(members) = v else {
+        return false;
+    };
+    let mut lens = members.iter().map(|(_, m)| match m {
+        JsonValue::Array(items) => Some(items.len()),
+        _ => None,
+    });
+    match lens.next() {
+        Some(Some(n)) => lens.all(|l| l == Some(n)),
+        _ => false,
+    }
+}
+
+fn parse_rows(bytes: &[u8], opts: &JsonReadOptions) -> Result<Rows, String> {
+    let text = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let mut values = Vec::new();
+    for v in serde_json::Deserializer::from_slice(text).into_iter::<JsonValue>() {
+        values.push(v.map_err(|e| format!("Invalid JSON: {}", e))?);
+    }
+
+    let single = values.len() == 1;
+    let format = match opts.format {
+        JsonFormat::Auto if single && matches!(values[0], JsonValue::Array(_)) => {
+            JsonFormat::Records
+        }
+        JsonFormat::Auto if single && is_columnar(&values[0]) => JsonFormat::Columns,
+        JsonFormat::Auto => JsonFormat::Ndjson,
+        f => f,
+    };
+    let sep = opts.separator.as_deref();
+    let mut rows = Rows::default();
+
+    let records = match format {
+        JsonFormat::Records => match values.pop() {
+            Some(JsonValue::Array(items)) if single => items,
+            _ => return Err("Expected a single JSON array of records".to_string()),
+        },
+        JsonFormat::Columns => {
+            let members = match values.pop() {
+                Some(JsonValue::Object(members)) if single => members,
+                _ => return Err("Expected a single JSON object of columns".to_string()),
+            };
+            let mut leaves: Vec<Vec<(String, JsonValue)>> = Vec::new();
+            for (name, column) in members {
+                let JsonValue::Array(items) = column else {
+                    return Err(format!("Column '{}' is not an array", name));
+                };
+                if leaves.is_empty() {
+                    leaves.resize_with(items.len(), Vec::new);
+                }
+                if items.len() != leaves.len() {
+                    return Err(format!(
+                        "Column '{}' has {} values, expected {}",
+                        name,
+                        items.len(),
+                        leaves.len()
+                    ));
+                }
+                for (row, item) in leaves.iter_mut().zip(items) {
+                    item.flatten_into(&name, sep, row);
+                }
+            }
+            for row in leaves {
+                rows.push(row);
+            }
+            return Ok(rows);
+        }
+        _ => values,
+    };
+
+    for (i, record) in records.into_iter().enumerate() {
+        let JsonValue::Object(members) = record else {
+            return Err(format!("Record {} is not a JSON object", i + 1));
+        };
+        let mut leaves = Vec::new();
+        for (name, v) in members {
+            v.flatten_into(&name, sep, &mut leaves);
+        }
+        rows.push(leaves);
+    }
+    Ok(rows)
+}
+
+/// Type inference over JSON values of one column.
+///
+/// Booleans, numbers and strings each keep their own type; numbers are
+/// int32 when every value is an integer in range. Strings become dates or
+/// datetimes when they all parse as such. Mixed kinds, arrays and (unflattened)
+/// objects fall back to string, as do columns with no non-null values.
+#[derive(Debug, Default)]
+struct JsonInference {
+    boolean: bool,
+    integer: bool,
+    float: bool,
+    string: bool,
+    nested: bool,
+    out_of_range: bool,
+    text: TypeInference,
+}
+
+impl JsonInference {
+    fn observe(&mut self, v: &JsonValue, formats: &TemporalFormats) {
+        match v {
+            JsonValue::Null => {}
+            JsonValue::Bool(_) => self.boolean = true,
+            JsonValue::Int(i) => {
+                self.integer = true;
+                self.out_of_range |= i32::try_from(*i).is_err();
+            }
+            JsonValue::Float(_) => self.float = true,
+            JsonValue::Str(s) => {
+                self.string = true;
+                self.text.observe(s, formats);
+            }
+            JsonValue::Array(_) | JsonValue::Object(_) => self.nested = true,
+        }
+    }
+
+    fn result(&self, parse_dates: bool) -> ColumnType {
+        let number = self.integer || self.float;
+        let kinds = [self.boolean, number, self.string, self.nested]
+            .iter()
+            .filter(|&&k| k)
+            .count();
+        if kinds != 1 || self.nested {
+            ColumnType::String
+        } else if self.boolean {
+            ColumnType::Boolean
+        } else if number {
+            if self.float || self.out_of_range {
+                ColumnType::Float64
+            } else {
+                ColumnType::Int32
+            }
+        } else {
+            match self.text.result() {
+                t @ (ColumnType::Date | ColumnType::Datetime) if parse_dates => t,
+                _ => ColumnType::String,
+            }
+        }
+    }
+}
+
+/// Push `v` into `builder` according to its type. Returns false (and pushes a
+/// missing value) when the value does not fit the column.
+fn push_value(builder: &mut ColumnBuilder, v: &JsonValue, formats: &TemporalFormats) -> bool {
+    match (v, builder.column_type()) {
+        (JsonValue::Null, _) => {
+            builder.push_null();
+            return true;
+        }
+        (JsonValue::Str(s), _) => return push_cell(builder, s, formats),
+        (v, ColumnType::String) => {
+            builder.push_str(&v.to_json());
+            return true;
+        }
+        (JsonValue::Bool(b), ColumnType::Boolean) => {
+            builder.push_bool(*b);
+            return true;
+        }
+        (
+            JsonValue::Int(_) | JsonValue::Float(_),
+            ColumnType::Int32 | ColumnType::Float64 | ColumnType::Datetime,
+        ) => {
+            let x = match v {
+                JsonValue::Int(i) => *i as f64,
+                JsonValue::Float(x) => *x,
+                _ => unreachable!(),
+            };
+            // Int32 columns widen to float64 on fractional values
+            builder.push_f64(x);
+            return true;
+        }
+        (JsonValue::Int(i), ColumnType::Date) => {
+            if let Ok(d) = i32::try_from(*i) {
+                builder.push_i32(d);
+                return true;
+            }
+        }
+        _ => {}
+    }
+    builder.push_null();
+    false
+}
+
+/// Read NDJSON, a JSON array of records or a JSON object of columns.
+///
+/// Nested objects are flattened into `parent<sep>child` columns; columns
+/// appear in first-seen order and keys missing from a record are missing
+/// values. Returns the table and the number of values that did not fit
+/// their column's type (read as missing).
+pub fn read_json(bytes: &[u8], opts: &JsonReadOptions) -> Result<(Table, usize), String> {
+    let rows = parse_rows(bytes, opts)?;
+    let formats = TemporalFormats {
+        date: opts.date_format.clone(),
+        datetime: opts.datetime_format.clone(),
+    };
+
+    // Choose (source index, name, type) per output column
+    let mut columns: Vec<(Option<usize>, String, ColumnType)> = Vec::new();
+    if !opts.schema.is_empty() {
+        for field in &opts.schema {
+            let src = rows.index.get(&field.name).copied();
+            columns.push((src, field.name.clone(), field.column_type));
+        }
+    } else {
+        if let Some(missing) = opts.select.iter().find(|s| !rows.index.contains_key(*s)) {
+            return Err(format!("Column '{}' not found in JSON", missing));
+        }
+        let mut inference: Vec<JsonInference> = rows
+            .names
+            .iter()
+            .map(|_| JsonInference::default())
+            .collect();
+        for row in rows.rows.iter().take(opts.infer_rows.unwrap_or(usize::MAX)) {
+            for (k, v) in row {
+                inference[*k].observe(v, &formats);
+            }
+        }
+        for (k, name) in rows.names.iter().enumerate() {
+            if !opts.select.is_empty() && !opts.select.contains(name) {
+                continue;
+            }
+            let t = match opts.column_types.get(name) {
+                Some(&t) => t,
+                None => inference[k].result(opts.parse_dates),
+            };
+            columns.push((Some(k), name.clone(), t));
+        }
+    }
+
+    // Output position of each source column
+    let mut target = vec![None; rows.names.len()];
+    for (pos, (src, _, _)) in columns.iter().enumerate() {
+        if let Some(k) = src {
+            target[*k] = Some(pos);
+        }
+    }
+
+    let mut builders: Vec<ColumnBuilder> =
+        columns.iter().map(|c| ColumnBuilder::new(c.2)).collect();
+    let mut problems = 0;
+    let mut filled = vec![false; builders.len()];
+    for row in &rows.rows {
+        filled.fill(false);
+        for (k, v) in row {
+            if let Some(pos) = target[*k] {
+                if !push_value(&mut builders[pos], v, &formats) {
+                    problems += 1;
+                }
+                filled[pos] = true;
+            }
+        }
+        for (b, _) in builders.iter_mut().zip(&filled).filter(|(_, f)| !**f) {
+            b.push_null();
+        }
+    }
+
+    let columns = builders
+        .into_iter()
+        .zip(columns)
+        .map(|(b, (_, name, _))| b.finish(&name))
+        .collect();
+    Ok((Table { columns }, problems))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::columns::ColumnData;
+
+    fn read(s: &str, opts: &JsonReadOptions) -> Table {
+        read_json(s.as_bytes(), opts).unwrap().0
+    }
+
+    fn types(t: &Table) -> Vec<ColumnType> {
+        t.columns.iter().map(|c| c.column_type()).collect()
+    }
+
+    #[test]
+    fn infers_types_from_ndjson() {
+        let t = read(
+            "{\"id\": 1, \"score\": 2, \"flag\": true, \"tag\": \"a\", \"day\": \"2024-01-02\"}\n\
+             \n\
+             {\"id\": 2, \"score\": 2.5, \"flag\": null, \"tag\": 3, \"user\": {\"name\": \"x\", \"age\": 30}}\n\
+             {\"id\": 3, \"flag\": false, \"tag\": [1, 2], \"day\": \"2024-01-04\", \"user\": {\"age\": 31}}\n",
+            &JsonReadOptions::default(),
+        );
+        let names: Vec<_> = t.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["id", "score", "flag", "tag", "day", "user.name", "user.age"]
+        );
+        assert_eq!(
+            types(&t),
+            vec![
+                ColumnType::Int32,
+                ColumnType::Float64,
+                ColumnType::Boolean,
+                ColumnType::String,
+                ColumnType::Date,
+                ColumnType::String,
+                ColumnType::Int32
+            ]
+        );
+        assert_eq!(t.columns[1].validity, vec![1, 1, 0]);
+        assert_eq!(t.columns[2].validity, vec![1, 0, 1]);
+        match &t.columns[3].data {
+            ColumnData::Dictionary { codes, dictionary } => {
+                assert_eq!(codes, &vec![0, 1, 2]);
+                assert_eq!(dictionary.as_slice(), ["a", "3", "[1,2]"]);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(
+            t.columns[4].data,
+            ColumnData::Date(vec![19724, i32::MIN, 19726])
+        );
+        assert_eq!(t.columns[6].validity, vec![0, 1, 1]);
+    }
+
+    #[test]
+    fn reads_records_and_columns_layouts() {
+        let records = "[{\"a\": 1, \"b\": \"x\"}, {\"a\": 2.5, \"b\": \"y\"}]";
+        let columns = "{\"a\": [1, 2.5], \"b\": [\"x\", \"y\"]}";
+        let opts = JsonReadOptions::default();
+        let r = read(records, &opts);
+        let c = read(columns, &opts);
+        assert_eq!(r, c);
+        assert_eq!(types(&r), vec![ColumnType::Float64, ColumnType::String]);
+
+        // A lone object that is not columnar is one record
+        let one = read("{\"a\": [1, 2], \"b\": 3}", &opts);
+        assert_eq!(one.n_rows(), 1);
+
+        let err = read_json(
+            b"{\"a\": [1], \"b\": [1, 2]}",
+            &JsonReadOptions {
+                format: JsonFormat::Columns,
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert!(err.contains("'b'"), "{}", err);
+    }
+
+    #[test]
+    fn applies_schema_and_counts_problems() {
+        let opts = JsonReadOptions {
+            schema: vec![
+                SchemaField {
+                    name: "n".to_string(),
+                    column_type: ColumnType::Int32,
+                },
+                SchemaField {
+                    name: "when".to_string(),
+                    column_type: ColumnType::Datetime,
+                },
+                SchemaField {
+                    name: "absent".to_string(),
+                    column_type: ColumnType::Float64,
+                },
+            ],
+            ..Default::default()
+        };
+        let (t, problems) = read_json(
+            b"{\"n\": 1, \"when\": \"2024-01-02T00:00:00Z\"}\n{\"n\": true, \"when\": 86400000}",
+            &opts,
+        )
+        .unwrap();
+        assert_eq!(problems, 1);
+        assert_eq!(t.columns[0].validity, vec![1, 0]);
+        assert_eq!(
+            t.columns[1].data,
+            ColumnData::Datetime(vec![1704153600000.0, 86400000.0])
+        );
+        assert_eq!(t.columns[2].null_count(), 2);
+
+        let flat = read(
+            "{\"u\": {\"v\": 1}}",
+            &JsonReadOptions {
+                separator: None,
+                ..Default::default()
+            },
+        );
+        assert_eq!(flat.columns[0].str_at(0), Some("{\"v\":1}"));
+    }
+}
@@ -0,0 +1,173 @@
+//! Order-preserving JSON values
+//!
+//! `serde_json::Value` sorts object keys (the crate is built without
+//! `preserve_order`), which would scramble column order, so records are
+//! deserialized into this small tree instead.
+
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Array(Vec<JsonValue>),
+    /// Members in document order
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Compact JSON text of this value
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+
+    fn write_json(&self, out: &mut String) {
+        match self {
+            JsonValue::Null => out.push_str("null"),
+            JsonValue::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            JsonValue::Int(i) => out.push_str(&i.to_string()),
+            JsonValue::Float(x) => write_f64(*x, out),
+            JsonValue::Str(s) => write_str(s, out),
+            JsonValue::Array(items) => {
+                out.push('[');
+                for (i, v) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    v.write_json(out);
+                }
+                out.push(']');
+            }
+            JsonValue::Object(members) => {
+                out.push('{');
+                for (i, (k, v)) in members.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    write_str(k, out);
+                    out.push(':');
+                    v.write_json(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+
+    /// Append the leaves of this value, found at `prefix`, to `out` as
+    /// `(path, value)` pairs.
+    ///
+    /// Nested objects are flattened with `separator` between keys; arrays and
+    /// scalars are leaves. With `separator = None` objects are leaves too.
+    pub fn flatten_into(
+        self,
+        prefix: &str,
+        separator: Option<&str>,
+        out: &mut Vec<(String, JsonValue)>,
+    ) {
+        match (self, separator) {
+            (JsonValue::Object(members), Some(sep)) => {
+                for (k, v) in members {
+                    v.flatten_into(&format!("{}{}{}", prefix, sep, k), separator, out);
+                }
+            }
+            (v, _) => out.push((prefix.to_string(), v)),
+        }
+    }
+}
+
+/// JSON number text; non-finite values become `null`
+pub fn write_f64(x: f64, out: &mut String) {
+    match serde_json::to_string(&x) {
+        Ok(s) if x.is_finite() => out.push_str(&s),
+        _ => out.push_str("null"),
+    }
+}
+
+/// Quoted, escaped JSON string
+pub fn write_str(s: &str, out: &mut String) {
+    match serde_json::to_string(s) {
+        Ok(q) => out.push_str(&q),
+        Err(_) => out.push_str("\"\""),
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(JsonValueVisitor)
+    }
+}
+
+struct JsonValueVisitor;
+
+impl<'de> Visitor<'de> for JsonValueVisitor {
+    type Value = JsonValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<JsonValue, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<JsonValue, E> {
+        Ok(JsonValue::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, d: D) -> Result<JsonValue, D::Error> {
+        JsonValue::deserialize(d)
+    }
+
+    fn visit_bool<E>(self, b: bool) -> Result<JsonValue, E> {
+        Ok(JsonValue::Bool(b))
+    }
+
+    fn visit_i64<E>(self, i: i64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Int(i))
+    }
+
+    fn visit_u64<E>(self, u: u64) -> Result<JsonValue, E> {
+        Ok(match i64::try_from(u) {
+            Ok(i) => JsonValue::Int(i),
+            Err(_) => JsonValue::Float(u as f64),
+        })
+    }
+
+    fn visit_f64<E>(self, x: f64) -> Result<JsonValue, E> {
+        Ok(JsonValue::Float(x))
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<JsonValue, E> {
+        Ok(JsonValue::Str(s.to_string()))
+    }
+
+    fn visit_string<E>(self, s: String) -> Result<JsonValue, E> {
+        Ok(JsonValue::Str(s))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<JsonValue, A::Error> {
+        let mut items = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(v) = seq.next_element()? {
+            items.push(v);
+        }
+        Ok(JsonValue::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<JsonValue, A::Error> {
+        let mut members: Vec<(String, JsonValue)> = Vec::new();
+        while let Some((k, v)) = map.next_entry::<String, JsonValue>()? {
+            // Later duplicates win, as in `JSON.parse`
+            match members.iter_mut().find(|(name, _)| *name == k) {
+                Some(slot) => slot.1 = v,
+                None => members.push((k, v)),
+            }
+        }
+        Ok(JsonValue::Object(members))
+    }
+}
@@ -0,0 +1,42 @@
+//! WASM bindings for the JSON reader and writer
+
+#![cfg(feature = "wasm")]
+
+use super::reader::{JsonReadOptions, read_json};
+use super::writer::{JsonWriteOptions, write_json};
+use crate::io::wasm::TableWasm;
+use serde::de::DeserializeOwned;
+use wasm_bindgen::prelude::*;
+
+fn parse_options<T: DeserializeOwned + Default>(options_json: &str) -> Result<T, JsValue> {
+    if options_json.trim().is_empty() {
+        return Ok(T::default());
+    }
+    serde_json::from_str(options_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid JSON options: {}", e)))
+}
+
+/// Read NDJSON, a JSON array of records or a JSON object of columns
+///
+/// # Arguments
+/// * `bytes` - UTF-8 JSON content
+/// * `options_json` - JSON `JsonReadOptions` (camelCase keys, empty = defaults),
+///   e.g. `{"format": "ndjson", "separator": "_", "columnTypes": {"id": "string"}}`
+#[wasm_bindgen]
+pub fn read_json_wasm(bytes: &[u8], options_json: &str) -> Result<TableWasm, JsValue> {
+    let opts: JsonReadOptions = parse_options(options_json)?;
+    let (table, problems) =
+        read_json(bytes, &opts).map_err(|e| JsValue::from_str(&format!("JSON error: {}", e)))?;
+    Ok(TableWasm::new(table, problems))
+}
+
+/// Write a table as JSON text
+///
+/// # Arguments
+/// * `options_json` - JSON `JsonWriteOptions`
+///   (`format`: "ndjson" | "records" | "columns", `nest`, `dateFormat`, `datetimeFormat`)
+#[wasm_bindgen]
+pub fn write_json_wasm(table: &TableWasm, options_json: &str) -> Result<String, JsValue> {
+    let opts: JsonWriteOptions = parse_options(options_json)?;
+    write_json(table.table(), &opts).map_err(|e| JsValue::from_str(&format!("JSON error: {}", e)))
+}
@@ -0,0 +1,225 @@
+//! NDJSON / JSON-records writer
+
+use super::reader::JsonFormat;
+use super::value::{write_f64, write_str};
+use crate::io::columns::{Column, ColumnData, Table};
+use crate::temporal::{MS_PER_DAY, format_iso8601, format_strftime};
+use serde::Deserialize;
+
+/// JSON writing options (camelCase when given as JSON)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct JsonWriteOptions {
+    /// `ndjson`, `records` or `columns` (`auto` is rejected)
+    pub format: JsonFormat,
+    /// Rebuild nested objects by splitting column names on this separator
+    pub nest: Option<String>,
+    /// strftime format for date columns (default ISO `YYYY-MM-DD`)
+    pub date_format: Option<String>,
+    /// strftime format for datetime columns (default ISO-8601)
+    pub datetime_format: Option<String>,
+}
+
+impl Default for JsonWriteOptions {
+    fn default() -> Self {
+        JsonWriteOptions {
+            format: JsonFormat::Ndjson,
+            nest: None,
+            date_format: None,
+            datetime_format: None,
+        }
+    }
+}
+
+/// Output object shape: leaves are column indices
+enum Node {
+    Leaf(usize),
+    Group(Vec<(String, Node)>),
+}
+
+fn build_tree(table: &Table, nest: Option<&str>) -> Result<Vec<(String, Node)>, String> {
+    let mut root: Vec<(String, Node)> = Vec::new();
+    for (k, col) in table.columns.iter().enumerate() {
+        let parts: Vec<&str> = match nest {
+            Some(sep) if !sep.is_empty() => col.name.split(sep).collect(),
+            _ => vec![col.name.as_str()],
+        };
+        let (last, parents) = parts.split_last().expect("split yields a part");
+        let mut level = &mut root;
+        for part in parents {
+            let pos = match level.iter().position(|(name, _)| name == part) {
+                Some(pos) => pos,
+                None => {
+                    level.push((part.to_string(), Node::Group(Vec::new())));
+                    level.len() - 1
+                }
+            };
+            level = match &mut level[pos].1 {
+                Node::Group(children) => children,
+                Node::Leaf(_) => {
+                    return Err(format!(
+                        "Column '{}' conflicts with another column when nesting",
+                        col.name
+                    ));
+                }
+            };
+        }
+        if level.iter().any(|(name, _)| name == last) {
+            return Err(format!(
+                "Column '{}' conflicts with another column",
+                col.name
+            ));
+        }
+        level.push((last.to_string(), Node::Leaf(k)));
+    }
+    Ok(root)
+}
+
+struct Writer<'a> {
+    table: &'a Table,
+    opts: &'a JsonWriteOptions,
+    out: String,
+}
+
+impl Writer<'_> {
+    fn cell(&mut self, col: &Column, row: usize) {
+        if col.validity.get(row) == Some(&0) {
+            self.out.push_str("null");
+            return;
+        }
+        match &col.data {
+            ColumnData::Boolean(v) => self
+                .out
+                .push_str(if v[row] != 0 { "true" } else { "false" }),
+            ColumnData::Int32(v) => self.out.push_str(&v[row].to_string()),
+            ColumnData::Float64(v) => write_f64(v[row], &mut self.out),
+            ColumnData::Dictionary { codes, dictionary } => {
+                match dictionary.get(codes[row] as usize) {
+                    Some(s) => write_str(s, &mut self.out),
+                    None => self.out.push_str("null"),
+                }
+            }
+            ColumnData::Date(v) => {
+                let t = v[row] as i64 * MS_PER_DAY;
+                let fmt = self.opts.date_format.as_deref().unwrap_or("%Y-%m-%d");
+                write_str(&format_strftime(t, fmt), &mut self.out);
+            }
+            ColumnData::Datetime(v) if v[row].is_finite() => {
+                let t = v[row].round() as i64;
+                let s = match &self.opts.datetime_format {
+                    Some(fmt) => format_strftime(t, fmt),
+                    None => format_iso8601(t),
+                };
+                write_str(&s, &mut self.out);
+            }
+            ColumnData::Datetime(_) => self.out.push_str("null"),
+        }
+    }
+
+    fn key(&mut self, i: usize, name: &str) {
+        if i > 0 {
+            self.out.push(',');
+        }
+        write_str(name, &mut self.out);
+        self.out.push(':');
+    }
+
+    fn record(&mut self, nodes: &[(String, Node)], row: usize) {
+        self.out.push('{');
+        for (i, (name, node)) in nodes.iter().enumerate() {
+            self.key(i, name);
+            match node {
+                Node::Leaf(k) => self.cell(&self.table.columns[*k], row),
+                Node::Group(children) => self.record(children, row),
+            }
+        }
+        self.out.push('}');
+    }
+
+    fn columns(&mut self, nodes: &[(String, Node)]) {
+        self.out.push('{');
+        for (i, (name, node)) in nodes.iter().enumerate() {
+            self.key(i, name);
+            match node {
+                Node::Leaf(k) => {
+                    let col = &self.table.columns[*k];
+                    self.out.push('[');
+                    for row in 0..col.len() {
+                        if row > 0 {
+                            self.out.push(',');
+                        }
+                        self.cell(col, row);
+                    }
+                    self.out.push(']');
+                }
+                Node::Group(children) => self.columns(children),
+            }
+        }
+        self.out.push('}');
+    }
+}
+
+/// Write a table as NDJSON, a JSON array of records or a JSON object of
+/// columns. Missing and non-finite values are written as `null`, dates and
+/// datetimes as formatted strings.
+pub fn write_json(table: &Table, opts: &JsonWriteOptions) -> Result<String, String> {
+    let tree = build_tree(table, opts.nest.as_deref())?;
+    let mut w = Writer {
+        table,
+        opts,
+        out: String::new(),
+    };
+    match opts.format {
+        JsonFormat::Ndjson => {
+            for row in 0..table.n_rows() {
+                w.record(&tree, row);
+                w.out.push('\n');
+            }
+        }
+        JsonFormat::Records => {
+            w.out.push('[');
+            for row in 0..table.n_rows() {
+                if row > 0 {
+                    w.out.push(',');
+                }
+                w.record(&tree, row);
+            }
+            w.out.push(']');
+        }
+        JsonFormat::Columns => w.columns(&tree),
+        JsonFormat::Auto => {
+            return Err("Output format must be ndjson, records or columns".to_string());
+        }
+    }
+    Ok(w.out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::json::reader::{JsonReadOptions, read_json};
+
+    #[test]
+    fn round_trips_through_reader() {
+        let text = "{\"id\":1,\"user\":{\"name\":\"a\\\"b\",\"score\":1.5},\"day\":\"2024-01-02\",\"ok\":true}\n\
+                    {\"id\":2,\"user\":{\"name\":null,\"score\":null},\"day\":null,\"ok\":false}\n";
+        let (table, _) = read_json(text.as_bytes(), &JsonReadOptions::default()).unwrap();
+        let opts = JsonWriteOptions {
+            nest: Some(".".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(write_json(&table, &opts).unwrap(), text);
+
+        let columns = write_json(
+            &table,
+            &JsonWriteOptions {
+                format: JsonFormat::Columns,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(columns.starts_with("{\"id\":[1,2],\"user.name\":[\"a\\\"b\",null]"));
+        let (back, _) = read_json(columns.as_bytes(), &JsonReadOptions::default()).unwrap();
+        assert_eq!(format!("{:?}", back), format!("{:?}", table));
+    }
+}
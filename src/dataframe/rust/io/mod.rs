@@ -0,0 +1,21 @@
+//! Native readers and writers for tabular file formats
+//!
+//! Readers produce a [`columns::Table`] of typed columns in the same layouts
+//! the dataframe kernels consume, so data can be loaded without a round trip
+//! through JavaScript objects.
+//!
+//! - `columns`: typed column buffers and builders
+//! - `csv`: streaming CSV reader with type inference
+//! - `arrow`: Arrow IPC stream/file and C data interface
+//! - `json`: NDJSON / JSON-records reader and writer with schema inference
+//! - `parquet`: Parquet reader/writer with projection and predicate pushdown
+
+pub mod arrow;
+pub mod columns;
+pub mod csv;
+pub mod json;
+pub mod parquet;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use columns::*;
@@ -0,0 +1,606 @@
+//! Page compression codecs
+//!
+//! Snappy (read and write), gzip and LZ4 (read) are implemented natively;
+//! the remaining Parquet codecs are reported as unsupported.
+
+/// Parquet `CompressionCodec` values
+pub const UNCOMPRESSED: i32 = 0;
+pub const SNAPPY: i32 = 1;
+pub const GZIP: i32 = 2;
+pub const LZ4: i32 = 5;
+pub const LZ4_RAW: i32 = 7;
+
+fn codec_name(codec: i32) -> &'static str {
+    match codec {
+        3 => "LZO",
+        4 => "BROTLI",
+        6 => "ZSTD",
+        _ => "unknown",
+    }
+}
+
+/// Decompress a page body into exactly `size` bytes
+///
+/// `size` comes from the page header and is trusted only as an upper bound:
+/// decoders allocate at most `size` bytes and fail as soon as the output
+/// would grow past it.
+pub fn decompress(codec: i32, data: &[u8], size: usize) -> Result<Vec<u8>, String> {
+    let out = match codec {
+        UNCOMPRESSED => data.to_vec(),
+        SNAPPY => snappy_decompress(data, size)?,
+        GZIP => gzip_decompress(data, size)?,
+        LZ4 => hadoop_lz4_decompress(data, size)?,
+        LZ4_RAW => lz4_decompress(data, size)?,
+        other => {
+            return Err(format!(
+                "Compression codec {} ({}) is not supported",
+                other,
+                codec_name(other)
+            ));
+        }
+    };
+    if out.len() != size {
+        return Err(format!(
+            "Decompressed page has {} bytes, expected {}",
+            out.len(),
+            size
+        ));
+    }
+    Ok(out)
+}
+
+/// Compress a page body with `codec` (only uncompressed and Snappy are written)
+pub fn compress(codec: i32, data: &[u8]) -> Result<Vec<u8>, String> {
+    match codec {
+        UNCOMPRESSED => Ok(data.to_vec()),
+        SNAPPY => Ok(snappy_compress(data)),
+        other => Err(format!("Cannot write compression codec {}", other)),
+    }
+}
+
+fn truncated(codec: &str) -> String {
+    format!("Truncated {} data", codec)
+}
+
+/// Fail if appending `len` bytes would take `out` past `limit` bytes
+fn check_room(out: &[u8], len: usize, limit: usize) -> Result<(), String> {
+    if len > limit.saturating_sub(out.len()) {
+        return Err(format!(
+            "Decompressed data is longer than the expected {} bytes",
+            limit
+        ));
+    }
+    Ok(())
+}
+
+/// Copy `len` bytes starting `offset` back; overlapping copies repeat the pattern
+fn copy_back(out: &mut Vec<u8>, offset: usize, len: usize, limit: usize) -> Result<(), String> {
+    check_room(out, len, limit)?;
+    if offset == 0 || offset > out.len() {
+        return Err("Invalid back-reference in compressed data".to_string());
+    }
+    let start = out.len() - offset;
+    for i in 0..len {
+        let b = out[start + i];
+        out.push(b);
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Snappy
+// ---------------------------------------------------------------------------
+
+/// Decode a Snappy block whose length preamble must equal `size`
+pub fn snappy_decompress(data: &[u8], size: usize) -> Result<Vec<u8>, String> {
+    let mut pos = 0;
+    let mut expected = 0u64;
+    for shift in (0..35).step_by(7) {
+        let b = *data.get(pos).ok_or_else(|| truncated("Snappy"))?;
+        pos += 1;
+        expected |= ((b & 0x7f) as u64) << shift;
+        if b & 0x80 == 0 {
+            break;
+        }
+    }
+    if expected != size as u64 {
+        return Err(format!(
+            "Snappy data declares {} bytes, expected {}",
+            expected, size
+        ));
+    }
+    let mut out = Vec::with_capacity(size);
+    let read_le = |pos: &mut usize, n: usize| -> Result<usize, String> {
+        let bytes = data
+            .get(*pos..*pos + n)
+            .ok_or_else(|| truncated("Snappy"))?;
+        *pos += n;
+        Ok(bytes
+            .iter()
+            .rev()
+            .fold(0usize, |acc, &b| (acc << 8) | b as usize))
+    };
+    while pos < data.len() {
+        let tag = data[pos];
+        pos += 1;
+        match tag & 3 {
+            0 => {
+                let mut len = (tag >> 2) as usize;
+                if len >= 60 {
+                    len = read_le(&mut pos, len - 59)?;
+                }
+                len += 1;
+                let lit = data
+                    .get(pos..pos + len)
+                    .ok_or_else(|| truncated("Snappy"))?;
+                check_room(&out, len, size)?;
+                out.extend_from_slice(lit);
+                pos += len;
+            }
+            1 => {
+                let len = ((tag >> 2) & 7) as usize + 4;
+                let offset = (((tag >> 5) as usize) << 8) | read_le(&mut pos, 1)?;
+                copy_back(&mut out, offset, len, size)?;
+            }
+            2 => {
+                let len = (tag >> 2) as usize + 1;
+                let offset = read_le(&mut pos, 2)?;
+                copy_back(&mut out, offset, len, size)?;
+            }
+            _ => {
+                let len = (tag >> 2) as usize + 1;
+                let offset = read_le(&mut pos, 4)?;
+                copy_back(&mut out, offset, len, size)?;
+            }
+        }
+    }
+    if out.len() != size {
+        return Err(truncated("Snappy"));
+    }
+    Ok(out)
+}
+
+fn snappy_literal(out: &mut Vec<u8>, lit: &[u8]) {
+    if lit.is_empty() {
+        return;
+    }
+    let n = lit.len() - 1;
+    if n < 60 {
+        out.push((n as u8) << 2);
+    } else {
+        let bytes = (usize::BITS - n.leading_zeros()).div_ceil(8) as usize;
+        out.push(((59 + bytes) as u8) << 2);
+        out.extend_from_slice(&n.to_le_bytes()[..bytes]);
+    }
+    out.extend_from_slice(lit);
+}
+
+fn snappy_copy(out: &mut Vec<u8>, offset: usize, mut len: usize) {
+    while len > 0 {
+        // Keep at least 4 bytes for the final copy
+        let n = if len > 64 { (len - 4).min(64) } else { len };
+        if (4..=11).contains(&n) && offset < 2048 {
+            out.push((((offset >> 8) as u8) << 5) | (((n - 4) as u8) << 2) | 1);
+            out.push(offset as u8);
+        } else if offset < 65536 {
+            out.push((((n - 1) as u8) << 2) | 2);
+            out.extend_from_slice(&(offset as u16).to_le_bytes());
+        } else {
+            out.push((((n - 1) as u8) << 2) | 3);
+            out.extend_from_slice(&(offset as u32).to_le_bytes());
+        }
+        len -= n;
+    }
+}
+
+/// Greedy single-pass Snappy compressor with a 4-byte hash table
+pub fn snappy_compress(data: &[u8]) -> Vec<u8> {
+    const HASH_BITS: u32 = 14;
+    let mut out = Vec::with_capacity(data.len() / 2 + 16);
+    let mut n = data.len();
+    while n >= 0x80 {
+        out.push((n as u8 & 0x7f) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+
+    let load = |i: usize| u32::from_le_bytes(data[i..i + 4].try_into().expect("4 bytes"));
+    let hash = |v: u32| (v.wrapping_mul(0x1e35_a7bd) >> (32 - HASH_BITS)) as usize;
+    let mut table = vec![usize::MAX; 1 << HASH_BITS];
+    let mut literal_start = 0;
+    let mut i = 0;
+    while i + 4 <= data.len() {
+        let v = load(i);
+        let h = hash(v);
+        let candidate = table[h];
+        table[h] = i;
+        if candidate != usize::MAX && i - candidate <= u32::MAX as usize && load(candidate) == v {
+            let mut len = 4;
+            while i + len < data.len() && data[candidate + len] == data[i + len] {
+                len += 1;
+            }
+            snappy_literal(&mut out, &data[literal_start..i]);
+            snappy_copy(&mut out, i - candidate, len);
+            i += len;
+            literal_start = i;
+        } else {
+            i += 1;
+        }
+    }
+    snappy_literal(&mut out, &data[literal_start..]);
+    out
+}
+
+// ---------------------------------------------------------------------------
+// LZ4
+// ---------------------------------------------------------------------------
+
+/// Decode one raw LZ4 block of at most `size` bytes
+pub fn lz4_decompress(data: &[u8], size: usize) -> Result<Vec<u8>, String> {
+    let mut out = Vec::with_capacity(size);
+    let mut pos = 0;
+    let read_len = |pos: &mut usize, mut len: usize| -> Result<usize, String> {
+        if len == 15 {
+            loop {
+                let b = *data.get(*pos).ok_or_else(|| truncated("LZ4"))?;
+                *pos += 1;
+                len += b as usize;
+                if b != 255 {
+                    break;
+                }
+            }
+        }
+        Ok(len)
+    };
+    while pos < data.len() {
+        let token = data[pos];
+        pos += 1;
+        let lit_len = read_len(&mut pos, (token >> 4) as usize)?;
+        let lit = data
+            .get(pos..pos + lit_len)
+            .ok_or_else(|| truncated("LZ4"))?;
+        check_room(&out, lit_len, size)?;
+        out.extend_from_slice(lit);
+        pos += lit_len;
+        if pos == data.len() {
+            break;
+        }
+        let offset = data
+            .get(pos..pos + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+            .ok_or_else(|| truncated("LZ4"))?;
+        pos += 2;
+        let match_len = read_len(&mut pos, (token & 15) as usize)? + 4;
+        copy_back(&mut out, offset, match_len, size)?;
+    }
+    Ok(out)
+}
+
+/// Legacy Parquet LZ4: Hadoop framing (big-endian sizes per block), with a
+/// fallback to a bare LZ4 block as some writers produced
+fn hadoop_lz4_decompress(data: &[u8], size: usize) -> Result<Vec<u8>, String> {
+    let framed = || -> Result<Vec<u8>, String> {
+        let mut out = Vec::with_capacity(size);
+        let mut pos = 0;
+        while pos < data.len() {
+            let header = data.get(pos..pos + 8).ok_or_else(|| truncated("LZ4"))?;
+            let raw = u32::from_be_bytes(header[..4].try_into().expect("4 bytes")) as usize;
+            let len = u32::from_be_bytes(header[4..].try_into().expect("4 bytes")) as usize;
+            pos += 8;
+            let block = data.get(pos..pos + len).ok_or_else(|| truncated("LZ4"))?;
+            check_room(&out, raw, size)?;
+            let decoded = lz4_decompress(block, raw)?;
+            if decoded.len() != raw {
+                return Err(truncated("LZ4"));
+            }
+            out.extend(decoded);
+            pos += len;
+        }
+        Ok(out)
+    };
+    match framed() {
+        Ok(out) if out.len() == size => Ok(out),
+        _ => lz4_decompress(data, size),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Gzip / DEFLATE
+// ---------------------------------------------------------------------------
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    bit_buf: u64,
+    bit_count: u32,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        BitReader {
+            data,
+            pos: 0,
+            bit_buf: 0,
+            bit_count: 0,
+        }
+    }
+
+    fn bits(&mut self, n: u32) -> Result<u32, String> {
+        while self.bit_count < n {
+            let b = *self.data.get(self.pos).ok_or_else(|| truncated("gzip"))?;
+            self.pos += 1;
+            self.bit_buf |= (b as u64) << self.bit_count;
+            self.bit_count += 8;
+        }
+        let v = (self.bit_buf & ((1u64 << n) - 1)) as u32;
+        self.bit_buf >>= n;
+        self.bit_count -= n;
+        Ok(v)
+    }
+
+    /// Drop the remaining bits of the current byte
+    fn align(&mut self) {
+        let drop = self.bit_count % 8;
+        self.bit_buf >>= drop;
+        self.bit_count -= drop;
+        // Return whole buffered bytes to the stream
+        self.pos -= (self.bit_count / 8) as usize;
+        self.bit_buf = 0;
+        self.bit_count = 0;
+    }
+}
+
+/// Canonical Huffman code as symbol counts per length plus sorted symbols
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self, String> {
+        let mut counts = [0u16; 16];
+        for &l in lengths {
+            counts[l as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+        let mut symbols = vec![0u16; lengths.len()];
+        for (sym, &l) in lengths.iter().enumerate() {
+            if l != 0 {
+                symbols[offsets[l as usize] as usize] = sym as u16;
+                offsets[l as usize] += 1;
+            }
+        }
+        Ok(Huffman { counts, symbols })
+    }
+
+    fn decode(&self, br: &mut BitReader) -> Result<u16, String> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+        for len in 1..16 {
+            code |= br.bits(1)? as i32;
+            let count = self.counts[len] as i32;
+            if code - count < first {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+        Err("Invalid Huffman code in gzip data".to_string())
+    }
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+fn inflate_block(
+    br: &mut BitReader,
+    out: &mut Vec<u8>,
+    limit: usize,
+    lit: &Huffman,
+    dist: &Huffman,
+) -> Result<(), String> {
+    loop {
+        let sym = lit.decode(br)? as usize;
+        if sym < 256 {
+            check_room(out, 1, limit)?;
+            out.push(sym as u8);
+        } else if sym == 256 {
+            return Ok(());
+        } else {
+            let i = sym - 257;
+            if i >= LENGTH_BASE.len() {
+                return Err("Invalid length symbol in gzip data".to_string());
+            }
+            let len = LENGTH_BASE[i] as usize + br.bits(LENGTH_EXTRA[i] as u32)? as usize;
+            let d = dist.decode(br)? as usize;
+            if d >= DIST_BASE.len() {
+                return Err("Invalid distance symbol in gzip data".to_string());
+            }
+            let offset = DIST_BASE[d] as usize + br.bits(DIST_EXTRA[d] as u32)? as usize;
+            copy_back(out, offset, len, limit)?;
+        }
+    }
+}
+
+fn dynamic_tables(br: &mut BitReader) -> Result<(Huffman, Huffman), String> {
+    const ORDER: [usize; 19] = [
+        16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+    ];
+    let n_lit = br.bits(5)? as usize + 257;
+    let n_dist = br.bits(5)? as usize + 1;
+    let n_code = br.bits(4)? as usize + 4;
+    let mut code_lengths = [0u8; 19];
+    for &i in ORDER.iter().take(n_code) {
+        code_lengths[i] = br.bits(3)? as u8;
+    }
+    let code = Huffman::new(&code_lengths)?;
+    let mut lengths = Vec::with_capacity(n_lit + n_dist);
+    while lengths.len() < n_lit + n_dist {
+        let sym = code.decode(br)?;
+        let (value, repeat) = match sym {
+            0..=15 => (sym as u8, 1),
+            16 => {
+                let prev = *lengths
+                    .last()
+                    .ok_or("Invalid code length repeat in gzip data")?;
+                (prev, 3 + br.bits(2)? as usize)
+            }
+            17 => (0, 3 + br.bits(3)? as usize),
+            _ => (0, 11 + br.bits(7)? as usize),
+        };
+        lengths.extend(std::iter::repeat_n(value, repeat));
+    }
+    if lengths.len() != n_lit + n_dist {
+        return Err("Invalid code lengths in gzip data".to_string());
+    }
+    Ok((
+        Huffman::new(&lengths[..n_lit])?,
+        Huffman::new(&lengths[n_lit..])?,
+    ))
+}
+
+/// Inflate a raw DEFLATE stream without growing `out` past `limit` bytes,
+/// returning the bytes consumed
+fn inflate(data: &[u8], out: &mut Vec<u8>, limit: usize) -> Result<usize, String> {
+    let mut br = BitReader::new(data);
+    loop {
+        let last = br.bits(1)? == 1;
+        match br.bits(2)? {
+            0 => {
+                br.align();
+                let header = data
+                    .get(br.pos..br.pos + 4)
+                    .ok_or_else(|| truncated("gzip"))?;
+                let len = u16::from_le_bytes([header[0], header[1]]) as usize;
+                br.pos += 4;
+                let block = data
+                    .get(br.pos..br.pos + len)
+                    .ok_or_else(|| truncated("gzip"))?;
+                check_room(out, len, limit)?;
+                out.extend_from_slice(block);
+                br.pos += len;
+            }
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..].fill(8);
+                let lit = Huffman::new(&lengths)?;
+                let dist = Huffman::new(&[5u8; 30])?;
+                inflate_block(&mut br, out, limit, &lit, &dist)?;
+            }
+            2 => {
+                let (lit, dist) = dynamic_tables(&mut br)?;
+                inflate_block(&mut br, out, limit, &lit, &dist)?;
+            }
+            _ => return Err("Invalid block type in gzip data".to_string()),
+        }
+        if last {
+            br.align();
+            return Ok(br.pos);
+        }
+    }
+}
+
+/// Decode one or more concatenated gzip members into at most `size` bytes
+pub fn gzip_decompress(data: &[u8], size: usize) -> Result<Vec<u8>, String> {
+    let mut out = Vec::with_capacity(size);
+    let mut pos = 0;
+    while pos < data.len() {
+        let header = data.get(pos..pos + 10).ok_or_else(|| truncated("gzip"))?;
+        if header[0] != 0x1f || header[1] != 0x8b || header[2] != 8 {
+            return Err("Invalid gzip header".to_string());
+        }
+        let flags = header[3];
+        pos += 10;
+        if flags & 4 != 0 {
+            let extra = data.get(pos..pos + 2).ok_or_else(|| truncated("gzip"))?;
+            pos += 2 + u16::from_le_bytes([extra[0], extra[1]]) as usize;
+        }
+        for flag in [8u8, 16] {
+            if flags & flag != 0 {
+                let end = data
+                    .get(pos..)
+                    .and_then(|rest| rest.iter().position(|&b| b == 0))
+                    .ok_or_else(|| truncated("gzip"))?;
+                pos += end + 1;
+            }
+        }
+        if flags & 2 != 0 {
+            pos += 2;
+        }
+        let body = data.get(pos..).ok_or_else(|| truncated("gzip"))?;
+        pos += inflate(body, &mut out, size)? + 8;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snappy_round_trips() {
+        let mut data = Vec::new();
+        for i in 0..5000u32 {
+            data.extend_from_slice(format!("row {} value {}\n", i % 37, i).as_bytes());
+        }
+        data.extend((0..300).map(|i| (i * 7 % 251) as u8));
+        let packed = snappy_compress(&data);
+        assert!(packed.len() < data.len() / 2);
+        assert_eq!(snappy_decompress(&packed, data.len()).unwrap(), data);
+        assert_eq!(snappy_decompress(&snappy_compress(b""), 0).unwrap(), b"");
+    }
+
+    #[test]
+    fn inflates_fixed_huffman_gzip() {
+        // gzip of "hello hello hello\n"
+        let gz = [
+            0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xcb, 0x48, 0xcd, 0xc9,
+            0xc9, 0x57, 0xc8, 0x40, 0x90, 0x5c, 0x00, 0x3b, 0x7c, 0x8a, 0xdf, 0x12, 0x00, 0x00,
+            0x00,
+        ];
+        assert_eq!(gzip_decompress(&gz, 18).unwrap(), b"hello hello hello\n");
+        assert!(gzip_decompress(&gz, 10).is_err());
+    }
+
+    #[test]
+    fn oversized_lengths_are_errors() {
+        // Snappy preamble claiming 2^35 - 1 bytes
+        let huge = [0xff, 0xff, 0xff, 0xff, 0x7f, 0x00, b'a'];
+        assert!(snappy_decompress(&huge, 1).is_err());
+        let packed = snappy_compress(&[7u8; 1000]);
+        assert!(snappy_decompress(&packed, 999).is_err());
+
+        // LZ4 literal followed by a long match that outgrows the page
+        let lz4 = [0x1f, b'a', 0x01, 0x00, 0xff, 0xff, 0x00];
+        assert!(lz4_decompress(&lz4, 64).is_err());
+        assert_eq!(lz4_decompress(&[0x10, b'a'], 1).unwrap(), b"a");
+        // Hadoop framing, then a block claiming more than the page holds
+        let framed = [0, 0, 0, 1, 0, 0, 0, 2, 0x10, b'a'];
+        assert_eq!(decompress(LZ4, &framed, 1).unwrap(), b"a");
+        let framed = [0, 0, 0x10, 0, 0, 0, 0, 2, 0x10, b'a'];
+        assert!(decompress(LZ4, &framed, 1).is_err());
+    }
+}
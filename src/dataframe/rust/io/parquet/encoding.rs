@@ -0,0 +1,504 @@
+//! Parquet value and level encodings
+//!
+//! Decoders cover PLAIN, dictionary indices, the RLE/bit-packed hybrid,
+//! DELTA_BINARY_PACKED, DELTA_LENGTH_BYTE_ARRAY, DELTA_BYTE_ARRAY and
+//! BYTE_STREAM_SPLIT. Encoders cover what the writer emits: PLAIN and the
+//! RLE/bit-packed hybrid.
+
+/// Parquet `Encoding` values
+pub const PLAIN: i32 = 0;
+pub const PLAIN_DICTIONARY: i32 = 2;
+pub const RLE: i32 = 3;
+pub const DELTA_BINARY_PACKED: i32 = 5;
+pub const DELTA_LENGTH_BYTE_ARRAY: i32 = 6;
+pub const DELTA_BYTE_ARRAY: i32 = 7;
+pub const RLE_DICTIONARY: i32 = 8;
+pub const BYTE_STREAM_SPLIT: i32 = 9;
+
+/// Parquet physical `Type` values
+pub const BOOLEAN: i32 = 0;
+pub const INT32: i32 = 1;
+pub const INT64: i32 = 2;
+pub const INT96: i32 = 3;
+pub const FLOAT: i32 = 4;
+pub const DOUBLE: i32 = 5;
+pub const BYTE_ARRAY: i32 = 6;
+pub const FIXED_LEN_BYTE_ARRAY: i32 = 7;
+
+/// Decoded values of one physical type
+#[derive(Debug, Clone, PartialEq)]
+pub enum Values {
+    Bool(Vec<bool>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    /// INT96 timestamps converted to epoch milliseconds
+    Int96(Vec<f64>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    Bytes(Vec<Vec<u8>>),
+}
+
+impl Values {
+    pub fn len(&self) -> usize {
+        match self {
+            Values::Bool(v) => v.len(),
+            Values::I32(v) => v.len(),
+            Values::I64(v) => v.len(),
+            Values::Int96(v) => v.len(),
+            Values::F32(v) => v.len(),
+            Values::F64(v) => v.len(),
+            Values::Bytes(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Values at the given dictionary indices
+    pub fn gather(&self, indices: &[u32]) -> Result<Values, String> {
+        if let Some(&bad) = indices.iter().find(|&&i| i as usize >= self.len()) {
+            return Err(format!(
+                "Dictionary index {} is out of range ({} entries)",
+                bad,
+                self.len()
+            ));
+        }
+        fn pick<T: Clone>(v: &[T], indices: &[u32]) -> Vec<T> {
+            indices.iter().map(|&i| v[i as usize].clone()).collect()
+        }
+        Ok(match self {
+            Values::Bool(v) => Values::Bool(pick(v, indices)),
+            Values::I32(v) => Values::I32(pick(v, indices)),
+            Values::I64(v) => Values::I64(pick(v, indices)),
+            Values::Int96(v) => Values::Int96(pick(v, indices)),
+            Values::F32(v) => Values::F32(pick(v, indices)),
+            Values::F64(v) => Values::F64(pick(v, indices)),
+            Values::Bytes(v) => Values::Bytes(pick(v, indices)),
+        })
+    }
+}
+
+/// Bits needed to store `max`
+pub fn bit_width(max: u64) -> u8 {
+    (64 - max.leading_zeros()) as u8
+}
+
+fn truncated() -> String {
+    "Unexpected end of page data".to_string()
+}
+
+fn read_uleb(data: &[u8], pos: &mut usize) -> Result<u64, String> {
+    let mut out = 0u64;
+    for shift in (0..64).step_by(7) {
+        let b = *data.get(*pos).ok_or_else(truncated)?;
+        *pos += 1;
+        out |= ((b & 0x7f) as u64) << shift;
+        if b & 0x80 == 0 {
+            return Ok(out);
+        }
+    }
+    Err("Varint is too long".to_string())
+}
+
+fn read_zigzag(data: &[u8], pos: &mut usize) -> Result<i64, String> {
+    let v = read_uleb(data, pos)?;
+    Ok((v >> 1) as i64 ^ -((v & 1) as i64))
+}
+
+fn put_uleb(out: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        out.push((v as u8 & 0x7f) | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
+
+/// Unpack `count` little-endian bit-packed values of `width` bits
+fn unpack(data: &[u8], width: u8, count: usize) -> Result<Vec<u64>, String> {
+    let width = width as usize;
+    if width > 64 {
+        return Err(format!("Invalid bit width {}", width));
+    }
+    if data.len() * 8 < width * count {
+        return Err(truncated());
+    }
+    let mut out = Vec::with_capacity(count);
+    let mut bit = 0usize;
+    for _ in 0..count {
+        let mut v = 0u64;
+        let mut got = 0usize;
+        while got < width {
+            let byte = data[(bit + got) / 8] as u64;
+            let offset = (bit + got) % 8;
+            let take = (8 - offset).min(width - got);
+            v |= ((byte >> offset) & ((1u64 << take) - 1)) << got;
+            got += take;
+        }
+        out.push(v);
+        bit += width;
+    }
+    Ok(out)
+}
+
+/// Decode `count` values of the RLE/bit-packed hybrid encoding
+pub fn decode_rle(data: &[u8], width: u8, count: usize) -> Result<Vec<u32>, String> {
+    if width > 32 {
+        return Err(format!("Invalid RLE bit width {}", width));
+    }
+    let mut out = Vec::with_capacity(count);
+    let mut pos = 0;
+    let value_bytes = (width as usize).div_ceil(8);
+    while out.len() < count {
+        let header = read_uleb(data, &mut pos)?;
+        if header >> 1 == 0 {
+            return Err("Empty run in RLE data".to_string());
+        }
+        if header & 1 == 1 {
+            let n = (header >> 1) as usize * 8;
+            let bytes = n * width as usize / 8;
+            let chunk = data.get(pos..pos + bytes).ok_or_else(truncated)?;
+            let take = n.min(count - out.len());
+            out.extend(unpack(chunk, width, take)?.into_iter().map(|v| v as u32));
+            pos += bytes;
+        } else {
+            let n = (header >> 1) as usize;
+            let bytes = data.get(pos..pos + value_bytes).ok_or_else(truncated)?;
+            let v = bytes
+                .iter()
+                .rev()
+                .fold(0u32, |acc, &b| (acc << 8) | b as u32);
+            pos += value_bytes;
+            out.extend(std::iter::repeat_n(v, n.min(count - out.len())));
+        }
+    }
+    Ok(out)
+}
+
+fn pack_group(out: &mut Vec<u8>, values: &[u32], width: u8) {
+    let start = out.len();
+    out.resize(start + values.len() * width as usize / 8, 0);
+    let mut bit = 0usize;
+    for &v in values {
+        for b in 0..width as usize {
+            if (v >> b) & 1 == 1 {
+                out[start + (bit + b) / 8] |= 1 << ((bit + b) % 8);
+            }
+        }
+        bit += width as usize;
+    }
+}
+
+/// Encode values with the RLE/bit-packed hybrid: runs of 8+ equal values as
+/// RLE, everything else bit-packed in groups of 8 (the last group zero-padded)
+pub fn encode_rle(values: &[u32], width: u8) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut literals: Vec<u32> = Vec::new();
+    let flush = |out: &mut Vec<u8>, literals: &mut Vec<u32>| {
+        if literals.is_empty() {
+            return;
+        }
+        let groups = literals.len().div_ceil(8);
+        literals.resize(groups * 8, 0);
+        put_uleb(out, ((groups as u64) << 1) | 1);
+        pack_group(out, literals, width);
+        literals.clear();
+    };
+    let mut i = 0;
+    while i < values.len() {
+        let v = values[i];
+        let run = values[i..].iter().take_while(|&&x| x == v).count();
+        // Top up pending literals to a whole group before switching to RLE
+        let fill = (8 - literals.len() % 8) % 8;
+        if run >= fill + 8 {
+            literals.extend(std::iter::repeat_n(v, fill));
+            flush(&mut out, &mut literals);
+            put_uleb(&mut out, ((run - fill) as u64) << 1);
+            let bytes = (width as usize).div_ceil(8);
+            out.extend_from_slice(&v.to_le_bytes()[..bytes]);
+            i += run;
+        } else {
+            literals.push(v);
+            i += 1;
+        }
+    }
+    flush(&mut out, &mut literals);
+    out
+}
+
+fn fixed<const N: usize>(
+    data: &[u8],
+    count: usize,
+) -> Result<impl Iterator<Item = [u8; N]> + '_, String> {
+    let bytes = data.get(..count * N).ok_or_else(truncated)?;
+    Ok(bytes
+        .chunks_exact(N)
+        .map(|c| c.try_into().expect("chunk of N bytes")))
+}
+
+/// Julian day of 1970-01-01
+const JULIAN_EPOCH_DAY: i64 = 2_440_588;
+
+/// INT96 (nanoseconds of day + Julian day) as epoch milliseconds
+pub fn int96_to_ms(b: [u8; 12]) -> f64 {
+    let nanos = i64::from_le_bytes(b[..8].try_into().expect("8 bytes"));
+    let day = i32::from_le_bytes(b[8..].try_into().expect("4 bytes")) as i64;
+    (day - JULIAN_EPOCH_DAY) as f64 * 86_400_000.0 + nanos as f64 / 1e6
+}
+
+/// Decode `count` PLAIN values, returning them with the bytes consumed
+pub fn decode_plain(
+    data: &[u8],
+    physical: i32,
+    type_length: usize,
+    count: usize,
+) -> Result<(Values, usize), String> {
+    Ok(match physical {
+        BOOLEAN => {
+            let bits = unpack(data, 1, count)?;
+            (
+                Values::Bool(bits.into_iter().map(|b| b == 1).collect()),
+                count.div_ceil(8),
+            )
+        }
+        INT32 => (
+            Values::I32(fixed::<4>(data, count)?.map(i32::from_le_bytes).collect()),
+            count * 4,
+        ),
+        INT64 => (
+            Values::I64(fixed::<8>(data, count)?.map(i64::from_le_bytes).collect()),
+            count * 8,
+        ),
+        INT96 => (
+            Values::Int96(fixed::<12>(data, count)?.map(int96_to_ms).collect()),
+            count * 12,
+        ),
+        FLOAT => (
+            Values::F32(fixed::<4>(data, count)?.map(f32::from_le_bytes).collect()),
+            count * 4,
+        ),
+        DOUBLE => (
+            Values::F64(fixed::<8>(data, count)?.map(f64::from_le_bytes).collect()),
+            count * 8,
+        ),
+        BYTE_ARRAY => {
+            let mut out = Vec::with_capacity(count);
+            let mut pos = 0;
+            for _ in 0..count {
+                let len = data
+                    .get(pos..pos + 4)
+                    .map(|b| u32::from_le_bytes(b.try_into().expect("4 bytes")) as usize)
+                    .ok_or_else(truncated)?;
+                pos += 4;
+                out.push(data.get(pos..pos + len).ok_or_else(truncated)?.to_vec());
+                pos += len;
+            }
+            (Values::Bytes(out), pos)
+        }
+        FIXED_LEN_BYTE_ARRAY => {
+            let bytes = data.get(..count * type_length).ok_or_else(truncated)?;
+            let out = if type_length == 0 {
+                vec![Vec::new(); count]
+            } else {
+                bytes
+                    .chunks_exact(type_length)
+                    .map(<[u8]>::to_vec)
+                    .collect()
+            };
+            (Values::Bytes(out), count * type_length)
+        }
+        other => return Err(format!("Unknown physical type {}", other)),
+    })
+}
+
+/// Encode values as PLAIN
+pub fn encode_plain(values: &Values) -> Vec<u8> {
+    let mut out = Vec::new();
+    match values {
+        Values::Bool(v) => {
+            out.resize(v.len().div_ceil(8), 0);
+            for (i, &b) in v.iter().enumerate() {
+                if b {
+                    out[i / 8] |= 1 << (i % 8);
+                }
+            }
+        }
+        Values::I32(v) => v
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes())),
+        Values::I64(v) => v
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes())),
+        Values::F32(v) => v
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes())),
+        Values::F64(v) => v
+            .iter()
+            .for_each(|x| out.extend_from_slice(&x.to_le_bytes())),
+        Values::Int96(v) => {
+            for &ms in v {
+                let days = (ms / 86_400_000.0).floor();
+                let nanos = ((ms - days * 86_400_000.0) * 1e6).round() as i64;
+                out.extend_from_slice(&nanos.to_le_bytes());
+                out.extend_from_slice(&((days as i64 + JULIAN_EPOCH_DAY) as i32).to_le_bytes());
+            }
+        }
+        Values::Bytes(v) => {
+            for b in v {
+                out.extend_from_slice(&(b.len() as u32).to_le_bytes());
+                out.extend_from_slice(b);
+            }
+        }
+    }
+    out
+}
+
+/// Decode a DELTA_BINARY_PACKED stream, returning the values and bytes consumed
+pub fn decode_delta_binary(data: &[u8]) -> Result<(Vec<i64>, usize), String> {
+    let mut pos = 0;
+    let block_size = read_uleb(data, &mut pos)? as usize;
+    let miniblocks = read_uleb(data, &mut pos)? as usize;
+    let total = read_uleb(data, &mut pos)? as usize;
+    let mut last = read_zigzag(data, &mut pos)?;
+    if miniblocks == 0
+        || !block_size.is_multiple_of(miniblocks)
+        || !(block_size / miniblocks).is_multiple_of(8)
+    {
+        return Err("Invalid DELTA_BINARY_PACKED header".to_string());
+    }
+    let per_mini = block_size / miniblocks;
+    let mut out = Vec::with_capacity(total.min(1 << 20));
+    if total > 0 {
+        out.push(last);
+    }
+    while out.len() < total {
+        let min_delta = read_zigzag(data, &mut pos)?;
+        let widths = data
+            .get(pos..pos + miniblocks)
+            .ok_or_else(truncated)?
+            .to_vec();
+        pos += miniblocks;
+        for &w in &widths {
+            if out.len() >= total {
+                break;
+            }
+            let bytes = per_mini * w as usize / 8;
+            let chunk = data.get(pos..pos + bytes).ok_or_else(truncated)?;
+            pos += bytes;
+            for d in unpack(chunk, w, per_mini)? {
+                if out.len() >= total {
+                    break;
+                }
+                last = last.wrapping_add(min_delta).wrapping_add(d as i64);
+                out.push(last);
+            }
+        }
+    }
+    Ok((out, pos))
+}
+
+fn decode_delta_length(data: &[u8], count: usize) -> Result<(Vec<Vec<u8>>, usize), String> {
+    let (lengths, mut pos) = decode_delta_binary(data)?;
+    if lengths.len() < count {
+        return Err(truncated());
+    }
+    let mut out = Vec::with_capacity(count);
+    for &len in &lengths[..count] {
+        let len = usize::try_from(len).map_err(|_| "Negative byte array length".to_string())?;
+        out.push(data.get(pos..pos + len).ok_or_else(truncated)?.to_vec());
+        pos += len;
+    }
+    Ok((out, pos))
+}
+
+/// Decode `count` non-dictionary values in any supported encoding
+pub fn decode_values(
+    data: &[u8],
+    encoding: i32,
+    physical: i32,
+    type_length: usize,
+    count: usize,
+) -> Result<Values, String> {
+    match encoding {
+        PLAIN => decode_plain(data, physical, type_length, count).map(|(v, _)| v),
+        RLE if physical == BOOLEAN => {
+            // Booleans use RLE with a 4-byte length prefix
+            let body = data.get(4..).ok_or_else(truncated)?;
+            let bits = decode_rle(body, 1, count)?;
+            Ok(Values::Bool(bits.into_iter().map(|b| b == 1).collect()))
+        }
+        DELTA_BINARY_PACKED => {
+            let (v, _) = decode_delta_binary(data)?;
+            let v = v.into_iter().take(count);
+            match physical {
+                INT32 => Ok(Values::I32(v.map(|x| x as i32).collect())),
+                INT64 => Ok(Values::I64(v.collect())),
+                _ => Err("DELTA_BINARY_PACKED applies to integers only".to_string()),
+            }
+        }
+        DELTA_LENGTH_BYTE_ARRAY => decode_delta_length(data, count).map(|(v, _)| Values::Bytes(v)),
+        DELTA_BYTE_ARRAY => {
+            let (prefixes, used) = decode_delta_binary(data)?;
+            let (suffixes, _) = decode_delta_length(&data[used..], count)?;
+            let mut out: Vec<Vec<u8>> = Vec::with_capacity(count);
+            for (prefix, suffix) in prefixes.into_iter().zip(suffixes) {
+                let prefix = prefix as usize;
+                let mut value = match out.last() {
+                    Some(prev) if prefix <= prev.len() => prev[..prefix].to_vec(),
+                    None if prefix == 0 => Vec::new(),
+                    _ => return Err("Invalid DELTA_BYTE_ARRAY prefix".to_string()),
+                };
+                value.extend(suffix);
+                out.push(value);
+            }
+            Ok(Values::Bytes(out))
+        }
+        BYTE_STREAM_SPLIT => {
+            let width = match physical {
+                INT32 | FLOAT => 4,
+                INT64 | DOUBLE => 8,
+                FIXED_LEN_BYTE_ARRAY => type_length,
+                _ => return Err("BYTE_STREAM_SPLIT is not valid for this type".to_string()),
+            };
+            let bytes = data.get(..count * width).ok_or_else(truncated)?;
+            let mut joined = vec![0u8; count * width];
+            for (i, value) in joined.chunks_exact_mut(width.max(1)).enumerate() {
+                for (j, b) in value.iter_mut().enumerate() {
+                    *b = bytes[j * count + i];
+                }
+            }
+            decode_plain(&joined, physical, type_length, count).map(|(v, _)| v)
+        }
+        other => Err(format!("Encoding {} is not supported", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rle_hybrid_round_trips() {
+        let mut values: Vec<u32> = vec![3, 1, 2];
+        values.extend(std::iter::repeat_n(5, 20));
+        values.extend((0..13).map(|i| i % 7));
+        values.extend(std::iter::repeat_n(0, 9));
+        let encoded = encode_rle(&values, 3);
+        assert_eq!(decode_rle(&encoded, 3, values.len()).unwrap(), values);
+    }
+
+    #[test]
+    fn decodes_delta_binary_packed() {
+        // Spec example: 1 2 3 4 5 with block size 128 and 4 miniblocks
+        let mut data = Vec::new();
+        put_uleb(&mut data, 128);
+        put_uleb(&mut data, 4);
+        put_uleb(&mut data, 5);
+        put_uleb(&mut data, 2); // zigzag(1)
+        put_uleb(&mut data, 2); // min delta zigzag(1)
+        data.extend_from_slice(&[0, 0, 0, 0]);
+        let (v, used) = decode_delta_binary(&data).unwrap();
+        assert_eq!(v, vec![1, 2, 3, 4, 5]);
+        assert_eq!(used, data.len());
+    }
+}
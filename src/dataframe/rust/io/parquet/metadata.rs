@@ -0,0 +1,351 @@
+//! Parquet footer and page header structures
+//!
+//! Field ids follow `parquet.thrift`. Only the fields the reader and writer
+//! use are modelled; everything else is skipped on read.
+
+use super::thrift::{TStruct, TValue};
+
+/// `FieldRepetitionType`
+pub const REQUIRED: i32 = 0;
+pub const OPTIONAL: i32 = 1;
+pub const REPEATED: i32 = 2;
+
+/// `PageType`
+pub const DATA_PAGE: i32 = 0;
+pub const DICTIONARY_PAGE: i32 = 2;
+pub const DATA_PAGE_V2: i32 = 3;
+
+/// Legacy `ConvertedType` values the reader understands
+mod converted {
+    pub const UTF8: i32 = 0;
+    pub const ENUM: i32 = 4;
+    pub const DECIMAL: i32 = 5;
+    pub const DATE: i32 = 6;
+    pub const TIME_MILLIS: i32 = 7;
+    pub const TIME_MICROS: i32 = 8;
+    pub const TIMESTAMP_MILLIS: i32 = 9;
+    pub const TIMESTAMP_MICROS: i32 = 10;
+    pub const UINT_32: i32 = 13;
+    pub const UINT_64: i32 = 14;
+    pub const JSON: i32 = 19;
+}
+
+/// Time unit of TIME / TIMESTAMP annotations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUnit {
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl TimeUnit {
+    /// Divisor converting a raw value to milliseconds
+    pub fn per_ms(self) -> f64 {
+        match self {
+            TimeUnit::Millis => 1.0,
+            TimeUnit::Micros => 1e3,
+            TimeUnit::Nanos => 1e6,
+        }
+    }
+
+    fn from_thrift(s: &TStruct) -> TimeUnit {
+        if s.has(2) {
+            TimeUnit::Micros
+        } else if s.has(3) {
+            TimeUnit::Nanos
+        } else {
+            TimeUnit::Millis
+        }
+    }
+}
+
+/// Logical annotation of a primitive column, merged from `logicalType` and
+/// the legacy `converted_type`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Annotation {
+    None,
+    String,
+    Date,
+    Decimal { scale: i32 },
+    Time(TimeUnit),
+    Timestamp(TimeUnit),
+    Unsigned,
+    Other,
+}
+
+#[derive(Debug, Clone)]
+pub struct SchemaElement {
+    pub name: String,
+    /// Physical type; `None` for groups
+    pub physical: Option<i32>,
+    pub type_length: i32,
+    pub repetition: i32,
+    pub num_children: i32,
+    pub annotation: Annotation,
+}
+
+impl SchemaElement {
+    fn from_thrift(s: &TStruct) -> SchemaElement {
+        let scale = s.i32(7).unwrap_or(0);
+        let annotation = match s.struct_(10) {
+            Some(logical) => match logical.fields.first() {
+                Some((1, _)) | Some((4, _)) | Some((12, _)) => Annotation::String,
+                Some((5, TValue::Struct(d))) => Annotation::Decimal {
+                    scale: d.i32(1).unwrap_or(scale),
+                },
+                Some((6, _)) => Annotation::Date,
+                Some((7, TValue::Struct(t))) => {
+                    Annotation::Time(t.struct_(2).map_or(TimeUnit::Millis, TimeUnit::from_thrift))
+                }
+                Some((8, TValue::Struct(t))) => Annotation::Timestamp(
+                    t.struct_(2).map_or(TimeUnit::Millis, TimeUnit::from_thrift),
+                ),
+                Some((10, TValue::Struct(i))) if i.bool(2) == Some(false) => Annotation::Unsigned,
+                Some((10, _)) => Annotation::None,
+                _ => Annotation::Other,
+            },
+            None => match s.i32(6) {
+                None => Annotation::None,
+                Some(converted::UTF8 | converted::ENUM | converted::JSON) => Annotation::String,
+                Some(converted::DECIMAL) => Annotation::Decimal { scale },
+                Some(converted::DATE) => Annotation::Date,
+                Some(converted::TIME_MILLIS) => Annotation::Time(TimeUnit::Millis),
+                Some(converted::TIME_MICROS) => Annotation::Time(TimeUnit::Micros),
+                Some(converted::TIMESTAMP_MILLIS) => Annotation::Timestamp(TimeUnit::Millis),
+                Some(converted::TIMESTAMP_MICROS) => Annotation::Timestamp(TimeUnit::Micros),
+                Some(converted::UINT_32 | converted::UINT_64) => Annotation::Unsigned,
+                Some(c) if (11..=18).contains(&c) => Annotation::None,
+                Some(_) => Annotation::Other,
+            },
+        };
+        SchemaElement {
+            name: s.string(4).unwrap_or_default(),
+            physical: s.i32(1),
+            type_length: s.i32(2).unwrap_or(0),
+            repetition: s.i32(3).unwrap_or(REQUIRED),
+            num_children: s.i32(5).unwrap_or(0),
+            annotation,
+        }
+    }
+}
+
+/// Column chunk statistics (raw PLAIN-encoded bounds)
+#[derive(Debug, Clone, Default)]
+pub struct Statistics {
+    pub min: Option<Vec<u8>>,
+    pub max: Option<Vec<u8>>,
+    pub null_count: Option<i64>,
+    /// Bounds come from the deprecated `min`/`max` fields (signed order only)
+    pub legacy: bool,
+}
+
+impl Statistics {
+    fn from_thrift(s: &TStruct) -> Statistics {
+        let null_count = s.i64(3);
+        match (s.binary(6), s.binary(5)) {
+            (Some(min), Some(max)) => Statistics {
+                min: Some(min.to_vec()),
+                max: Some(max.to_vec()),
+                null_count,
+                legacy: false,
+            },
+            _ => Statistics {
+                min: s.binary(2).map(<[u8]>::to_vec),
+                max: s.binary(1).map(<[u8]>::to_vec),
+                null_count,
+                legacy: true,
+            },
+        }
+    }
+
+    pub fn to_thrift(&self) -> TStruct {
+        let mut s = TStruct::new();
+        if let Some(n) = self.null_count {
+            s = s.with(3, TValue::I64(n));
+        }
+        if let (Some(min), Some(max)) = (&self.min, &self.max) {
+            s = s
+                .with(5, TValue::Binary(max.clone()))
+                .with(6, TValue::Binary(min.clone()));
+        }
+        s
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ColumnMetaData {
+    pub physical: i32,
+    pub path: Vec<String>,
+    pub codec: i32,
+    pub num_values: i64,
+    pub data_page_offset: i64,
+    pub dictionary_page_offset: Option<i64>,
+    /// Uncompressed size of all pages, headers included; bounds each page
+    pub total_uncompressed_size: i64,
+    pub total_compressed_size: i64,
+    pub statistics: Option<Statistics>,
+}
+
+impl ColumnMetaData {
+    fn from_thrift(s: &TStruct) -> Result<ColumnMetaData, String> {
+        let path = s
+            .list(3)
+            .iter()
+            .map(|v| match v {
+                TValue::Binary(b) => String::from_utf8_lossy(b).into_owned(),
+                _ => String::new(),
+            })
+            .collect();
+        Ok(ColumnMetaData {
+            physical: s.i32(1).ok_or("Column metadata has no type")?,
+            path,
+            codec: s.i32(4).unwrap_or(0),
+            num_values: s.i64(5).unwrap_or(0),
+            data_page_offset: s.i64(9).ok_or("Column metadata has no data page offset")?,
+            dictionary_page_offset: s.i64(11),
+            total_uncompressed_size: s.i64(6).unwrap_or(0),
+            total_compressed_size: s.i64(7).unwrap_or(0),
+            statistics: s.struct_(12).map(Statistics::from_thrift),
+        })
+    }
+
+    /// Byte range of the chunk's pages in the file
+    pub fn byte_range(&self) -> (usize, usize) {
+        let start = match self.dictionary_page_offset {
+            Some(d) if d > 0 && d < self.data_page_offset => d,
+            _ => self.data_page_offset,
+        };
+        (
+            start.max(0) as usize,
+            self.total_compressed_size.max(0) as usize,
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RowGroup {
+    pub columns: Vec<ColumnMetaData>,
+    pub num_rows: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileMetaData {
+    pub version: i32,
+    pub schema: Vec<SchemaElement>,
+    pub num_rows: i64,
+    pub row_groups: Vec<RowGroup>,
+    pub created_by: Option<String>,
+}
+
+impl FileMetaData {
+    pub fn from_thrift(s: &TStruct) -> Result<FileMetaData, String> {
+        let schema = s
+            .list(2)
+            .iter()
+            .filter_map(TValue::as_struct)
+            .map(SchemaElement::from_thrift)
+            .collect();
+        let mut row_groups = Vec::new();
+        for rg in s.list(4).iter().filter_map(TValue::as_struct) {
+            let mut columns = Vec::new();
+            for chunk in rg.list(1).iter().filter_map(TValue::as_struct) {
+                if chunk.string(1).is_some() {
+                    return Err("Column chunks in external files are not supported".to_string());
+                }
+                let meta = chunk.struct_(3).ok_or("Column chunk has no metadata")?;
+                columns.push(ColumnMetaData::from_thrift(meta)?);
+            }
+            row_groups.push(RowGroup {
+                columns,
+                num_rows: rg.i64(3).unwrap_or(0),
+            });
+        }
+        Ok(FileMetaData {
+            version: s.i32(1).unwrap_or(1),
+            schema,
+            num_rows: s.i64(3).unwrap_or(0),
+            row_groups,
+            created_by: s.string(6),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PageHeader {
+    pub page_type: i32,
+    pub uncompressed_size: usize,
+    pub compressed_size: usize,
+    /// Values (including nulls) in a data page, entries in a dictionary page
+    pub num_values: usize,
+    pub encoding: i32,
+    /// v2 only: byte lengths of the uncompressed level sections
+    pub rep_levels_len: usize,
+    pub def_levels_len: usize,
+    /// v2 only: whether the values section is compressed
+    pub is_compressed: bool,
+}
+
+impl PageHeader {
+    pub fn from_thrift(s: &TStruct) -> Result<PageHeader, String> {
+        let size = |id| {
+            s.i32(id)
+                .filter(|&n| n >= 0)
+                .map(|n| n as usize)
+                .ok_or_else(|| "Invalid page header size".to_string())
+        };
+        let mut header = PageHeader {
+            page_type: s.i32(1).ok_or("Page header has no type")?,
+            uncompressed_size: size(2)?,
+            compressed_size: size(3)?,
+            is_compressed: true,
+            ..PageHeader::default()
+        };
+        let count = |h: &TStruct, id| h.i32(id).unwrap_or(0).max(0) as usize;
+        match header.page_type {
+            DATA_PAGE => {
+                let h = s.struct_(5).ok_or("Data page has no header")?;
+                header.num_values = count(h, 1);
+                header.encoding = h.i32(2).unwrap_or(0);
+            }
+            DICTIONARY_PAGE => {
+                let h = s.struct_(7).ok_or("Dictionary page has no header")?;
+                header.num_values = count(h, 1);
+                header.encoding = h.i32(2).unwrap_or(0);
+            }
+            DATA_PAGE_V2 => {
+                let h = s.struct_(8).ok_or("Data page v2 has no header")?;
+                header.num_values = count(h, 1);
+                header.encoding = h.i32(4).unwrap_or(0);
+                header.def_levels_len = count(h, 5);
+                header.rep_levels_len = count(h, 6);
+                header.is_compressed = h.bool(7).unwrap_or(true);
+            }
+            _ => {}
+        }
+        Ok(header)
+    }
+
+    /// Header of a v1 data page (`def_encoding` applies to definition levels)
+    pub fn data_page(&self, def_encoding: i32) -> TStruct {
+        let data = TStruct::new()
+            .with(1, TValue::I32(self.num_values as i32))
+            .with(2, TValue::I32(self.encoding))
+            .with(3, TValue::I32(def_encoding))
+            .with(4, TValue::I32(def_encoding));
+        self.common().with(5, TValue::Struct(data))
+    }
+
+    pub fn dictionary_page(&self) -> TStruct {
+        let dict = TStruct::new()
+            .with(1, TValue::I32(self.num_values as i32))
+            .with(2, TValue::I32(self.encoding));
+        self.common().with(7, TValue::Struct(dict))
+    }
+
+    fn common(&self) -> TStruct {
+        TStruct::new()
+            .with(1, TValue::I32(self.page_type))
+            .with(2, TValue::I32(self.uncompressed_size as i32))
+            .with(3, TValue::I32(self.compressed_size as i32))
+    }
+}
@@ -0,0 +1,20 @@
+//! Apache Parquet files
+//!
+//! - `thrift`: Thrift compact protocol for footers and page headers
+//! - `metadata`: file, row group, column chunk and page structures
+//! - `compression`: Snappy, gzip and LZ4 codecs
+//! - `encoding`: PLAIN, dictionary, RLE/bit-packed and DELTA encodings
+//! - `reader`: reader with column projection and row-group pruning
+//! - `writer`: writer with per-row-group dictionaries and statistics
+
+pub mod compression;
+pub mod encoding;
+pub mod metadata;
+pub mod reader;
+pub mod thrift;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod writer;
+
+pub use reader::{ParquetFile, ParquetFilter, ParquetReadOptions, read_parquet};
+pub use writer::{ParquetCompression, ParquetWriteOptions, write_parquet};
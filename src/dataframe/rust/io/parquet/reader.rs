@@ -0,0 +1,811 @@
+//! Parquet reader with column projection and predicate pushdown
+//!
+//! Filters use the same operators as the dataframe filter kernels
+//! ([`ComparisonOp`]). Row groups whose statistics prove a filter cannot
+//! match are skipped without being decompressed; the remaining rows are then
+//! filtered exactly. Numeric filter values are compared in column units:
+//! epoch days for dates and epoch milliseconds for datetimes.
+
+use super::compression::decompress;
+use super::encoding::{
+    BOOLEAN, BYTE_ARRAY, DOUBLE, FIXED_LEN_BYTE_ARRAY, FLOAT, INT32, INT64, INT96,
+    PLAIN_DICTIONARY, RLE_DICTIONARY, Values, decode_plain, decode_rle, decode_values,
+};
+use super::metadata::{
+    Annotation, ColumnMetaData, DATA_PAGE, DATA_PAGE_V2, DICTIONARY_PAGE, FileMetaData, OPTIONAL,
+    PageHeader, REPEATED, SchemaElement,
+};
+use super::thrift;
+use crate::filter_wasm::ComparisonOp;
+use crate::io::columns::{Column, ColumnBuilder, ColumnType, NA_CODE, Table};
+use serde::{Deserialize, Deserializer};
+
+/// Magic bytes at both ends of a Parquet file
+pub const PARQUET_MAGIC: &[u8; 4] = b"PAR1";
+
+/// A row predicate `column op value`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParquetFilter {
+    pub column: String,
+    /// Operator symbol (">", ">=", "<", "<=", "==", "!=") or filter opcode 0-5
+    #[serde(deserialize_with = "deserialize_op")]
+    pub op: ComparisonOp,
+    pub value: f64,
+}
+
+fn deserialize_op<'de, D: Deserializer<'de>>(d: D) -> Result<ComparisonOp, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Op {
+        Code(u8),
+        Symbol(String),
+    }
+    let op = match Op::deserialize(d)? {
+        Op::Code(c) => ComparisonOp::from_code(c),
+        Op::Symbol(s) => ComparisonOp::parse(&s),
+    };
+    op.ok_or_else(|| serde::de::Error::custom("unknown comparison operator"))
+}
+
+/// Parquet reading options (camelCase when given as JSON)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ParquetReadOptions {
+    /// Subset of columns to read (empty = all); output keeps file order
+    pub columns: Vec<String>,
+    /// Predicates combined with AND
+    pub filters: Vec<ParquetFilter>,
+    /// Filter rows exactly; when false only whole row groups are skipped
+    pub exact: bool,
+}
+
+impl Default for ParquetReadOptions {
+    fn default() -> Self {
+        ParquetReadOptions {
+            columns: Vec::new(),
+            filters: Vec::new(),
+            exact: true,
+        }
+    }
+}
+
+/// A top-level schema field and the leaf column chunks it maps to
+#[derive(Debug, Clone)]
+struct Leaf {
+    element: SchemaElement,
+    /// Index of the column chunk within each row group
+    chunk: usize,
+    /// Primitive, non-repeated top-level column
+    flat: bool,
+}
+
+/// Walk the depth-first schema list, returning the index after `at`'s subtree
+fn walk(
+    schema: &[SchemaElement],
+    at: usize,
+    top: Option<&SchemaElement>,
+    leaves: &mut Vec<Leaf>,
+    chunks: &mut usize,
+) -> Result<usize, String> {
+    let e = schema.get(at).ok_or("Parquet schema is truncated")?;
+    let top_level = top.unwrap_or(e);
+    if e.num_children == 0 {
+        leaves.push(Leaf {
+            element: top_level.clone(),
+            chunk: *chunks,
+            flat: top.is_none() && e.repetition != REPEATED && e.physical.is_some(),
+        });
+        *chunks += 1;
+        return Ok(at + 1);
+    }
+    let mut next = at + 1;
+    for _ in 0..e.num_children {
+        next = walk(schema, next, Some(top_level), leaves, chunks)?;
+    }
+    Ok(next)
+}
+
+fn column_type(e: &SchemaElement) -> ColumnType {
+    match (e.physical.unwrap_or(BYTE_ARRAY), e.annotation) {
+        (BOOLEAN, _) => ColumnType::Boolean,
+        (INT32, Annotation::Date) => ColumnType::Date,
+        (INT32 | INT64, Annotation::Timestamp(_)) | (INT96, _) => ColumnType::Datetime,
+        (INT32, Annotation::None | Annotation::Other) => ColumnType::Int32,
+        (INT32 | INT64 | FLOAT | DOUBLE, _) => ColumnType::Float64,
+        (_, Annotation::Decimal { .. }) => ColumnType::Float64,
+        _ => ColumnType::String,
+    }
+}
+
+/// Big-endian two's complement integer
+fn decimal_bytes(b: &[u8]) -> f64 {
+    let unsigned = b.iter().fold(0.0, |acc, &x| acc * 256.0 + x as f64);
+    if b.first().is_some_and(|&x| x & 0x80 != 0) {
+        unsigned - 256f64.powi(b.len() as i32)
+    } else {
+        unsigned
+    }
+}
+
+/// Integer value in column units
+fn int_value(v: i64, annotation: Annotation, unsigned_bits: u32) -> f64 {
+    match annotation {
+        Annotation::Decimal { scale } => v as f64 / 10f64.powi(scale),
+        Annotation::Timestamp(unit) | Annotation::Time(unit) => v as f64 / unit.per_ms(),
+        Annotation::Unsigned if unsigned_bits == 32 => v as u32 as f64,
+        Annotation::Unsigned => v as u64 as f64,
+        _ => v as f64,
+    }
+}
+
+/// Push the present values of a page; `present` has one entry per row
+fn push_values(
+    builder: &mut ColumnBuilder,
+    present: &[bool],
+    values: Values,
+    e: &SchemaElement,
+) -> Result<(), String> {
+    let non_null = present.iter().filter(|&&p| p).count();
+    if values.len() < non_null {
+        return Err(format!(
+            "Column '{}': page has {} values for {} non-null rows",
+            e.name,
+            values.len(),
+            non_null
+        ));
+    }
+    let a = e.annotation;
+    let mut next = 0;
+    let mut each = |builder: &mut ColumnBuilder,
+                    push: &mut dyn FnMut(&mut ColumnBuilder, usize)| {
+        for &p in present {
+            if p {
+                push(builder, next);
+                next += 1;
+            } else {
+                builder.push_null();
+            }
+        }
+    };
+    let push_number = |b: &mut ColumnBuilder, x: f64| {
+        if x.is_nan() {
+            b.push_null()
+        } else {
+            b.push_f64(x)
+        }
+    };
+    match values {
+        Values::Bool(v) => each(builder, &mut |b, i| b.push_bool(v[i])),
+        Values::I32(v) => match a {
+            Annotation::None | Annotation::Other | Annotation::Date => {
+                each(builder, &mut |b, i| b.push_i32(v[i]))
+            }
+            _ => each(builder, &mut |b, i| {
+                push_number(b, int_value(v[i] as i64, a, 32))
+            }),
+        },
+        Values::I64(v) => each(builder, &mut |b, i| push_number(b, int_value(v[i], a, 64))),
+        Values::Int96(v) => each(builder, &mut |b, i| push_number(b, v[i])),
+        Values::F32(v) => each(builder, &mut |b, i| push_number(b, v[i] as f64)),
+        Values::F64(v) => each(builder, &mut |b, i| push_number(b, v[i])),
+        Values::Bytes(v) => match a {
+            Annotation::Decimal { scale } => each(builder, &mut |b, i| {
+                push_number(b, decimal_bytes(&v[i]) / 10f64.powi(scale))
+            }),
+            _ => each(builder, &mut |b, i| {
+                b.push_str(&String::from_utf8_lossy(&v[i]))
+            }),
+        },
+    }
+    Ok(())
+}
+
+/// Dictionary of the current column chunk
+enum Dictionary {
+    /// String dictionary already interned: builder code per dictionary index
+    Codes(Vec<u32>),
+    Values(Values),
+}
+
+fn read_chunk(bytes: &[u8], meta: &ColumnMetaData, leaf: &Leaf) -> Result<Column, String> {
+    let e = &leaf.element;
+    let physical = meta.physical;
+    let type_length = e.type_length.max(0) as usize;
+    let optional = e.repetition == OPTIONAL;
+    let (start, len) = meta.byte_range();
+    let chunk = bytes
+        .get(start..start.saturating_add(len))
+        .ok_or_else(|| format!("Column '{}': chunk is outside the file", e.name))?;
+    let target = column_type(e);
+    let interned = target == ColumnType::String;
+    let mut builder = ColumnBuilder::new(target);
+    let mut dictionary: Option<Dictionary> = None;
+    let expected = meta.num_values.max(0) as usize;
+    let size_limit = meta.total_uncompressed_size.max(0) as usize;
+    let truncated = || format!("Column '{}': page is truncated", e.name);
+
+    let mut pos = 0;
+    while builder.len() < expected && pos < chunk.len() {
+        let mut r = thrift::Reader::new(&chunk[pos..]);
+        let header = PageHeader::from_thrift(&r.read_struct()?)?;
+        pos += r.position();
+        let end = pos
+            .checked_add(header.compressed_size)
+            .ok_or_else(truncated)?;
+        let body = chunk.get(pos..end).ok_or_else(truncated)?;
+        pos = end;
+        if header.uncompressed_size > size_limit {
+            return Err(format!(
+                "Column '{}': page claims {} uncompressed bytes, more than the chunk's {}",
+                e.name, header.uncompressed_size, size_limit
+            ));
+        }
+        let n = header.num_values;
+
+        let (present, data): (Vec<bool>, Vec<u8>) = match header.page_type {
+            DICTIONARY_PAGE => {
+                let data = decompress(meta.codec, body, header.uncompressed_size)?;
+                let (values, _) = decode_plain(&data, physical, type_length, n)?;
+                dictionary = Some(match values {
+                    Values::Bytes(v) if interned => Dictionary::Codes(
+                        v.iter()
+                            .map(|s| builder.intern(&String::from_utf8_lossy(s)))
+                            .collect(),
+                    ),
+                    values => Dictionary::Values(values),
+                });
+                continue;
+            }
+            DATA_PAGE => {
+                let data = decompress(meta.codec, body, header.uncompressed_size)?;
+                if optional {
+                    let levels_end = data
+                        .get(..4)
+                        .and_then(|b| {
+                            (u32::from_le_bytes(b.try_into().expect("4 bytes")) as usize)
+                                .checked_add(4)
+                        })
+                        .ok_or_else(truncated)?;
+                    let levels = data.get(4..levels_end).ok_or_else(truncated)?;
+                    let present = decode_rle(levels, 1, n)?
+                        .into_iter()
+                        .map(|d| d == 1)
+                        .collect();
+                    (present, data[levels_end..].to_vec())
+                } else {
+                    (vec![true; n], data)
+                }
+            }
+            DATA_PAGE_V2 => {
+                let levels_end = header
+                    .rep_levels_len
+                    .checked_add(header.def_levels_len)
+                    .ok_or_else(truncated)?;
+                let levels = body
+                    .get(header.rep_levels_len..levels_end)
+                    .ok_or_else(truncated)?;
+                let present = if optional {
+                    decode_rle(levels, 1, n)?
+                        .into_iter()
+                        .map(|d| d == 1)
+                        .collect()
+                } else {
+                    vec![true; n]
+                };
+                let values = &body[levels_end..];
+                let data = if header.is_compressed {
+                    let size = header.uncompressed_size.saturating_sub(levels_end);
+                    decompress(meta.codec, values, size)?
+                } else {
+                    values.to_vec()
+                };
+                (present, data)
+            }
+            _ => continue,
+        };
+
+        let non_null = present.iter().filter(|&&p| p).count();
+        if header.encoding == PLAIN_DICTIONARY || header.encoding == RLE_DICTIONARY {
+            let width = *data.first().unwrap_or(&0);
+            let indices = decode_rle(data.get(1..).unwrap_or(&[]), width, non_null)?;
+            match &dictionary {
+                Some(Dictionary::Codes(codes)) => {
+                    let mut idx = indices.iter();
+                    for &p in &present {
+                        let code = match p.then(|| idx.next()).flatten() {
+                            Some(&i) => *codes.get(i as usize).ok_or_else(|| {
+                                format!("Column '{}': dictionary index out of range", e.name)
+                            })?,
+                            None => NA_CODE,
+                        };
+                        builder.push_code(code);
+                    }
+                }
+                Some(Dictionary::Values(dict)) => {
+                    push_values(&mut builder, &present, dict.gather(&indices)?, e)?
+                }
+                None => {
+                    return Err(format!(
+                        "Column '{}': dictionary-encoded page without a dictionary",
+                        e.name
+                    ));
+                }
+            }
+        } else {
+            let values = decode_values(&data, header.encoding, physical, type_length, non_null)?;
+            push_values(&mut builder, &present, values, e)?;
+        }
+    }
+    if builder.len() != expected {
+        return Err(format!(
+            "Column '{}': read {} values, expected {}",
+            e.name,
+            builder.len(),
+            expected
+        ));
+    }
+    Ok(builder.finish(&e.name))
+}
+
+/// Statistic bound in column units; `None` when unusable for pruning
+fn stat_value(raw: &[u8], e: &SchemaElement) -> Option<f64> {
+    let a = e.annotation;
+    let v = match e.physical? {
+        BOOLEAN => *raw.first()? as f64,
+        INT32 => int_value(i32::from_le_bytes(raw.try_into().ok()?) as i64, a, 32),
+        INT64 => int_value(i64::from_le_bytes(raw.try_into().ok()?), a, 64),
+        FLOAT => f32::from_le_bytes(raw.try_into().ok()?) as f64,
+        DOUBLE => f64::from_le_bytes(raw.try_into().ok()?),
+        BYTE_ARRAY | FIXED_LEN_BYTE_ARRAY => match a {
+            Annotation::Decimal { scale } => decimal_bytes(raw) / 10f64.powi(scale),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    v.is_finite().then_some(v)
+}
+
+/// Whether any row of a chunk with these statistics can satisfy `f`
+fn may_match(f: &ParquetFilter, meta: &ColumnMetaData, e: &SchemaElement) -> bool {
+    // Mirrors `ComparisonOp::matches`: a non-finite threshold never matches
+    if !f.value.is_finite() {
+        return false;
+    }
+    let Some(stats) = &meta.statistics else {
+        return true;
+    };
+    if meta.num_values > 0 && stats.null_count == Some(meta.num_values) {
+        return false;
+    }
+    let signed_only = stats.legacy
+        && (e.annotation == Annotation::Unsigned
+            || matches!(e.physical, Some(BYTE_ARRAY | FIXED_LEN_BYTE_ARRAY)));
+    if signed_only {
+        return true;
+    }
+    let bounds = stats
+        .min
+        .as_deref()
+        .and_then(|b| stat_value(b, e))
+        .zip(stats.max.as_deref().and_then(|b| stat_value(b, e)));
+    let Some((min, max)) = bounds else {
+        return true;
+    };
+    let v = f.value;
+    match f.op {
+        ComparisonOp::Greater => max > v,
+        ComparisonOp::GreaterEqual => max >= v,
+        ComparisonOp::Less => min < v,
+        ComparisonOp::LessEqual => min <= v,
+        ComparisonOp::Equal => v >= min - f64::EPSILON && v <= max + f64::EPSILON,
+        ComparisonOp::NotEqual => !(min == max && (min - v).abs() <= f64::EPSILON),
+    }
+}
+
+/// An opened Parquet file
+pub struct ParquetFile<'a> {
+    bytes: &'a [u8],
+    metadata: FileMetaData,
+    leaves: Vec<Leaf>,
+}
+
+impl<'a> ParquetFile<'a> {
+    /// Parse the footer of a complete in-memory Parquet file
+    pub fn open(bytes: &'a [u8]) -> Result<ParquetFile<'a>, String> {
+        let n = bytes.len();
+        if n < 12 || &bytes[..4] != PARQUET_MAGIC || &bytes[n - 4..] != PARQUET_MAGIC {
+            return Err("Not a Parquet file (missing PAR1 magic)".to_string());
+        }
+        let footer_len = u32::from_le_bytes(bytes[n - 8..n - 4].try_into().expect("4 bytes"));
+        let footer_start = (n - 8)
+            .checked_sub(footer_len as usize)
+            .filter(|&s| s >= 4)
+            .ok_or("Parquet footer length is invalid")?;
+        let footer = thrift::Reader::new(&bytes[footer_start..n - 8]).read_struct()?;
+        let metadata = FileMetaData::from_thrift(&footer)?;
+
+        let root = metadata.schema.first().ok_or("Parquet schema is empty")?;
+        let mut leaves = Vec::new();
+        let mut chunks = 0;
+        let mut at = 1;
+        for _ in 0..root.num_children {
+            at = walk(&metadata.schema, at, None, &mut leaves, &mut chunks)?;
+        }
+        for rg in &metadata.row_groups {
+            if rg.columns.len() != chunks {
+                return Err(format!(
+                    "Row group has {} column chunks, schema has {} leaves",
+                    rg.columns.len(),
+                    chunks
+                ));
+            }
+        }
+        Ok(ParquetFile {
+            bytes,
+            metadata,
+            leaves,
+        })
+    }
+
+    pub fn metadata(&self) -> &FileMetaData {
+        &self.metadata
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.metadata.num_rows.max(0) as usize
+    }
+
+    pub fn num_row_groups(&self) -> usize {
+        self.metadata.row_groups.len()
+    }
+
+    /// Top-level column names in file order
+    pub fn column_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for leaf in &self.leaves {
+            if names.last() != Some(&leaf.element.name) {
+                names.push(leaf.element.name.clone());
+            }
+        }
+        names
+    }
+
+    fn flat_leaf(&self, name: &str) -> Result<&Leaf, String> {
+        let leaf = self
+            .leaves
+            .iter()
+            .find(|l| l.element.name == name)
+            .ok_or_else(|| format!("Column '{}' not found in Parquet file", name))?;
+        if !leaf.flat {
+            return Err(format!(
+                "Nested or repeated Parquet column '{}' is not supported",
+                name
+            ));
+        }
+        Ok(leaf)
+    }
+
+    fn check_filters(&self, filters: &[ParquetFilter]) -> Result<(), String> {
+        for f in filters {
+            if column_type(&self.flat_leaf(&f.column)?.element) == ColumnType::String {
+                return Err(format!("Filter column '{}' is not numeric", f.column));
+            }
+        }
+        Ok(())
+    }
+
+    /// Row groups whose statistics do not rule out every filter
+    pub fn row_groups_to_read(&self, filters: &[ParquetFilter]) -> Result<Vec<usize>, String> {
+        self.check_filters(filters)?;
+        let mut keep = Vec::new();
+        for (i, rg) in self.metadata.row_groups.iter().enumerate() {
+            let mut possible = true;
+            for f in filters {
+                let leaf = self.flat_leaf(&f.column)?;
+                if !may_match(f, &rg.columns[leaf.chunk], &leaf.element) {
+                    possible = false;
+                    break;
+                }
+            }
+            if possible {
+                keep.push(i);
+            }
+        }
+        Ok(keep)
+    }
+
+    pub fn read(&self, opts: &ParquetReadOptions) -> Result<Table, String> {
+        let names = self.column_names();
+        if let Some(missing) = opts.columns.iter().find(|c| !names.contains(c)) {
+            return Err(format!("Column '{}' not found in Parquet file", missing));
+        }
+        let selected: Vec<&Leaf> = names
+            .iter()
+            .filter(|n| opts.columns.is_empty() || opts.columns.contains(n))
+            .map(|n| self.flat_leaf(n))
+            .collect::<Result<_, _>>()?;
+        let row_groups = self.row_groups_to_read(&opts.filters)?;
+
+        let mut table = Table {
+            columns: selected
+                .iter()
+                .map(|l| ColumnBuilder::new(column_type(&l.element)).finish(&l.element.name))
+                .collect(),
+        };
+        for i in row_groups {
+            let rg = &self.metadata.row_groups[i];
+            let mut columns: Vec<Column> = selected
+                .iter()
+                .map(|l| read_chunk(self.bytes, &rg.columns[l.chunk], l))
+                .collect::<Result<_, _>>()?;
+            if opts.exact && !opts.filters.is_empty() {
+                let n = rg.num_rows.max(0) as usize;
+                let mut mask = vec![true; n];
+                for f in &opts.filters {
+                    let values = match columns.iter().find(|c| c.name == f.column) {
+                        Some(c) => c.to_f64(),
+                        None => {
+                            let leaf = self.flat_leaf(&f.column)?;
+                            read_chunk(self.bytes, &rg.columns[leaf.chunk], leaf)?.to_f64()
+                        }
+                    }
+                    .unwrap_or_default();
+                    for (m, v) in mask.iter_mut().zip(values) {
+                        *m = *m && f.op.matches(v, f.value);
+                    }
+                }
+                columns = columns.iter().map(|c| c.filter(&mask)).collect();
+            }
+            table.append(Table { columns })?;
+        }
+        Ok(table)
+    }
+}
+
+/// Read a complete Parquet file into typed columns
+pub fn read_parquet(bytes: &[u8], opts: &ParquetReadOptions) -> Result<Table, String> {
+    ParquetFile::open(bytes)?.read(opts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::columns::ColumnData;
+    use crate::io::parquet::compression::{SNAPPY, UNCOMPRESSED};
+    use crate::io::parquet::encoding::{PLAIN, RLE};
+    use crate::io::parquet::metadata::REQUIRED;
+    use crate::io::parquet::thrift::{TStruct, TValue, write_struct};
+    use crate::io::parquet::writer::{ParquetCompression, ParquetWriteOptions, write_parquet};
+    use std::sync::Arc;
+
+    fn column(name: &str, data: ColumnData, validity: Vec<u8>) -> Column {
+        Column {
+            name: name.to_string(),
+            data,
+            validity,
+        }
+    }
+
+    fn sample() -> Table {
+        Table {
+            columns: vec![
+                column(
+                    "flag",
+                    ColumnData::Boolean(vec![1, 0, 0, 1, 1]),
+                    vec![1, 1, 0, 1, 1],
+                ),
+                column("id", ColumnData::Int32(vec![1, 2, 3, 4, 5]), vec![1; 5]),
+                column(
+                    "score",
+                    ColumnData::Float64(vec![0.5, f64::NAN, 2.5, -1.0, 1e9]),
+                    vec![1, 0, 1, 1, 1],
+                ),
+                column(
+                    "name",
+                    ColumnData::Dictionary {
+                        codes: vec![0, 1, NA_CODE, 1, 2],
+                        dictionary: Arc::new(vec!["a".into(), "b".into(), "ü".into()]),
+                    },
+                    vec![1, 1, 0, 1, 1],
+                ),
+                column(
+                    "day",
+                    ColumnData::Date(vec![19000, 19001, 0, -5, 3]),
+                    vec![1, 1, 1, 1, 1],
+                ),
+                column(
+                    "ts",
+                    ColumnData::Datetime(vec![1.7e12, 0.0, f64::NAN, -1000.0, 86_400_000.0]),
+                    vec![1, 1, 0, 1, 1],
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn round_trips_all_column_types() {
+        let table = sample();
+        for compression in [ParquetCompression::Snappy, ParquetCompression::None] {
+            for dictionary in [true, false] {
+                let opts = ParquetWriteOptions {
+                    compression,
+                    row_group_size: 2,
+                    dictionary,
+                };
+                let bytes = write_parquet(&table, &opts).unwrap();
+                let file = ParquetFile::open(&bytes).unwrap();
+                assert_eq!(file.num_row_groups(), 3);
+                let back = file.read(&ParquetReadOptions::default()).unwrap();
+                assert_eq!(format!("{:?}", back), format!("{:?}", table));
+            }
+        }
+    }
+
+    #[test]
+    fn prunes_row_groups_and_filters_rows() {
+        let n = 30;
+        let table = Table {
+            columns: vec![
+                column(
+                    "x",
+                    ColumnData::Int32((0..n).collect()),
+                    vec![1; n as usize],
+                ),
+                column(
+                    "y",
+                    ColumnData::Float64((0..n).map(|i| i as f64 / 2.0).collect()),
+                    vec![1; n as usize],
+                ),
+            ],
+        };
+        let opts = ParquetWriteOptions {
+            row_group_size: 10,
+            ..ParquetWriteOptions::default()
+        };
+        let bytes = write_parquet(&table, &opts).unwrap();
+        let file = ParquetFile::open(&bytes).unwrap();
+        let filters: Vec<ParquetFilter> =
+            serde_json::from_str(r#"[{"column": "x", "op": ">", "value": 24}]"#).unwrap();
+        assert_eq!(file.row_groups_to_read(&filters).unwrap(), vec![2]);
+
+        let read = |exact| {
+            file.read(&ParquetReadOptions {
+                columns: vec!["y".to_string()],
+                filters: filters.clone(),
+                exact,
+            })
+            .unwrap()
+        };
+        let exact = read(true);
+        assert_eq!(exact.n_columns(), 1);
+        assert_eq!(
+            exact.columns[0].data,
+            ColumnData::Float64(vec![12.5, 13.0, 13.5, 14.0, 14.5])
+        );
+        assert_eq!(read(false).n_rows(), 10);
+
+        let eq: Vec<ParquetFilter> =
+            serde_json::from_str(r#"[{"column": "y", "op": 4, "value": 7.5}]"#).unwrap();
+        assert_eq!(file.row_groups_to_read(&eq).unwrap(), vec![1]);
+        assert!(
+            file.read(&ParquetReadOptions {
+                columns: vec!["z".to_string()],
+                ..ParquetReadOptions::default()
+            })
+            .is_err()
+        );
+    }
+
+    /// Read one hand-built INT32 chunk made of `pages` (header, body)
+    fn read_pages(
+        pages: &[(TStruct, Vec<u8>)],
+        codec: i32,
+        optional: bool,
+        num_values: i64,
+    ) -> Result<Column, String> {
+        let mut bytes = Vec::new();
+        for (header, body) in pages {
+            write_struct(&mut bytes, header);
+            bytes.extend_from_slice(body);
+        }
+        let meta = ColumnMetaData {
+            physical: INT32,
+            path: vec!["x".to_string()],
+            codec,
+            num_values,
+            data_page_offset: 0,
+            dictionary_page_offset: None,
+            total_uncompressed_size: bytes.len() as i64,
+            total_compressed_size: bytes.len() as i64,
+            statistics: None,
+        };
+        let leaf = Leaf {
+            element: SchemaElement {
+                name: "x".to_string(),
+                physical: Some(INT32),
+                type_length: 0,
+                repetition: if optional { OPTIONAL } else { REQUIRED },
+                num_children: 0,
+                annotation: Annotation::None,
+            },
+            chunk: 0,
+            flat: true,
+        };
+        read_chunk(&bytes, &meta, &leaf)
+    }
+
+    fn v1_header(uncompressed: usize, compressed: usize, n: usize) -> TStruct {
+        PageHeader {
+            page_type: DATA_PAGE,
+            uncompressed_size: uncompressed,
+            compressed_size: compressed,
+            num_values: n,
+            encoding: PLAIN,
+            ..PageHeader::default()
+        }
+        .data_page(RLE)
+    }
+
+    #[test]
+    fn corrupt_pages_and_footers_are_errors() {
+        let body: Vec<u8> = [7i32, 8].iter().flat_map(|v| v.to_le_bytes()).collect();
+        let ok = read_pages(
+            &[(v1_header(8, 8, 2), body.clone())],
+            UNCOMPRESSED,
+            false,
+            2,
+        );
+        assert_eq!(ok.unwrap().data, ColumnData::Int32(vec![7, 8]));
+
+        // Page body running past the chunk
+        let long = read_pages(
+            &[(v1_header(8, 9, 2), body.clone())],
+            UNCOMPRESSED,
+            false,
+            2,
+        );
+        assert!(long.is_err());
+        // Uncompressed size beyond the chunk total is rejected before decoding
+        let packed = crate::io::parquet::compression::compress(SNAPPY, &body).unwrap();
+        let huge = v1_header(i32::MAX as usize, packed.len(), 2);
+        let err = read_pages(&[(huge, packed)], SNAPPY, false, 2).unwrap_err();
+        assert!(err.contains("more than the chunk"), "{}", err);
+        // Definition-level length near u32::MAX
+        let mut levels = u32::MAX.to_le_bytes().to_vec();
+        levels.extend(&body);
+        let n = levels.len();
+        assert!(read_pages(&[(v1_header(n, n, 2), levels)], UNCOMPRESSED, true, 2).is_err());
+        // v2 level lengths whose sum exceeds the page
+        let v2 = TStruct::new()
+            .with(1, TValue::I32(DATA_PAGE_V2))
+            .with(2, TValue::I32(8))
+            .with(3, TValue::I32(8))
+            .with(
+                8,
+                TValue::Struct(
+                    TStruct::new()
+                        .with(1, TValue::I32(2))
+                        .with(4, TValue::I32(PLAIN))
+                        .with(5, TValue::I32(i32::MAX))
+                        .with(6, TValue::I32(i32::MAX)),
+                ),
+            );
+        assert!(read_pages(&[(v2, body)], UNCOMPRESSED, true, 2).is_err());
+
+        let bytes = write_parquet(&sample(), &ParquetWriteOptions::default()).unwrap();
+        let n = bytes.len();
+        // Footer length pointing before the start of the file
+        let mut bad = bytes.clone();
+        bad[n - 8..n - 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(ParquetFile::open(&bad).is_err());
+        // Garbage footer
+        let mut bad = bytes.clone();
+        let footer_len = u32::from_le_bytes(bytes[n - 8..n - 4].try_into().unwrap()) as usize;
+        bad[n - 8 - footer_len..n - 8].fill(0xff);
+        assert!(ParquetFile::open(&bad).is_err());
+        // Chunk pages clipped by truncating the data section
+        let mut bad = bytes[..4].to_vec();
+        bad.extend_from_slice(&bytes[n - 8 - footer_len..]);
+        assert!(read_parquet(&bad, &ParquetReadOptions::default()).is_err());
+    }
+}
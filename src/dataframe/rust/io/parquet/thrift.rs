@@ -0,0 +1,372 @@
+//! Thrift compact protocol, as used by Parquet metadata
+//!
+//! Structs are decoded into a generic tree ([`TStruct`]) and read through
+//! field-id accessors; unknown fields are kept and simply ignored, which makes
+//! the reader tolerant of newer writers.
+
+const STOP: u8 = 0;
+const BOOL_TRUE: u8 = 1;
+const BOOL_FALSE: u8 = 2;
+const I8: u8 = 3;
+const I16: u8 = 4;
+const I32: u8 = 5;
+const I64: u8 = 6;
+const DOUBLE: u8 = 7;
+const BINARY: u8 = 8;
+const LIST: u8 = 9;
+const SET: u8 = 10;
+const MAP: u8 = 11;
+const STRUCT: u8 = 12;
+
+/// Nesting limit guarding against malicious input
+const MAX_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TValue {
+    Bool(bool),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    Double(f64),
+    Binary(Vec<u8>),
+    List(Vec<TValue>),
+    Map(Vec<(TValue, TValue)>),
+    Struct(TStruct),
+}
+
+impl TValue {
+    fn type_code(&self) -> u8 {
+        match self {
+            TValue::Bool(true) => BOOL_TRUE,
+            TValue::Bool(false) => BOOL_FALSE,
+            TValue::I8(_) => I8,
+            TValue::I16(_) => I16,
+            TValue::I32(_) => I32,
+            TValue::I64(_) => I64,
+            TValue::Double(_) => DOUBLE,
+            TValue::Binary(_) => BINARY,
+            TValue::List(_) => LIST,
+            TValue::Map(_) => MAP,
+            TValue::Struct(_) => STRUCT,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            TValue::I8(v) => Some(v as i64),
+            TValue::I16(v) => Some(v as i64),
+            TValue::I32(v) => Some(v as i64),
+            TValue::I64(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_struct(&self) -> Option<&TStruct> {
+        match self {
+            TValue::Struct(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// A decoded struct: `(field id, value)` pairs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TStruct {
+    pub fields: Vec<(i16, TValue)>,
+}
+
+impl TStruct {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, id: i16) -> Option<&TValue> {
+        self.fields.iter().find(|(k, _)| *k == id).map(|(_, v)| v)
+    }
+
+    pub fn has(&self, id: i16) -> bool {
+        self.get(id).is_some()
+    }
+
+    pub fn i32(&self, id: i16) -> Option<i32> {
+        self.get(id).and_then(TValue::as_i64).map(|v| v as i32)
+    }
+
+    pub fn i64(&self, id: i16) -> Option<i64> {
+        self.get(id).and_then(TValue::as_i64)
+    }
+
+    pub fn bool(&self, id: i16) -> Option<bool> {
+        match self.get(id) {
+            Some(TValue::Bool(b)) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn binary(&self, id: i16) -> Option<&[u8]> {
+        match self.get(id) {
+            Some(TValue::Binary(b)) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn string(&self, id: i16) -> Option<String> {
+        self.binary(id)
+            .map(|b| String::from_utf8_lossy(b).into_owned())
+    }
+
+    pub fn list(&self, id: i16) -> &[TValue] {
+        match self.get(id) {
+            Some(TValue::List(v)) => v,
+            _ => &[],
+        }
+    }
+
+    pub fn struct_(&self, id: i16) -> Option<&TStruct> {
+        self.get(id).and_then(TValue::as_struct)
+    }
+
+    /// Builder-style setter
+    pub fn with(mut self, id: i16, value: TValue) -> Self {
+        self.fields.push((id, value));
+        self
+    }
+}
+
+/// Compact protocol reader over a byte slice
+pub struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Reader { buf, pos: 0 }
+    }
+
+    /// Bytes consumed so far
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    fn byte(&mut self) -> Result<u8, String> {
+        let b = *self
+            .buf
+            .get(self.pos)
+            .ok_or("Unexpected end of Thrift data")?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut out = 0u64;
+        for shift in (0..64).step_by(7) {
+            let b = self.byte()?;
+            out |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Ok(out);
+            }
+        }
+        Err("Thrift varint is too long".to_string())
+    }
+
+    fn zigzag(&mut self) -> Result<i64, String> {
+        let v = self.varint()?;
+        Ok((v >> 1) as i64 ^ -((v & 1) as i64))
+    }
+
+    fn value(&mut self, ty: u8, depth: usize) -> Result<TValue, String> {
+        if depth > MAX_DEPTH {
+            return Err("Thrift data is nested too deeply".to_string());
+        }
+        Ok(match ty {
+            BOOL_TRUE | BOOL_FALSE => TValue::Bool(self.byte()? == BOOL_TRUE),
+            I8 => TValue::I8(self.byte()? as i8),
+            I16 => TValue::I16(self.zigzag()? as i16),
+            I32 => TValue::I32(self.zigzag()? as i32),
+            I64 => TValue::I64(self.zigzag()?),
+            DOUBLE => {
+                let b = self
+                    .buf
+                    .get(self.pos..self.pos + 8)
+                    .ok_or("Unexpected end of Thrift data")?;
+                self.pos += 8;
+                TValue::Double(f64::from_le_bytes(b.try_into().expect("8 bytes")))
+            }
+            BINARY => {
+                let len = self.varint()? as usize;
+                let b = self
+                    .buf
+                    .get(self.pos..self.pos + len)
+                    .ok_or("Unexpected end of Thrift data")?;
+                self.pos += len;
+                TValue::Binary(b.to_vec())
+            }
+            LIST | SET => {
+                let header = self.byte()?;
+                let mut len = (header >> 4) as usize;
+                if len == 15 {
+                    len = self.varint()? as usize;
+                }
+                let elem = header & 0x0f;
+                let mut items = Vec::with_capacity(len.min(1 << 16));
+                for _ in 0..len {
+                    items.push(self.value(elem, depth + 1)?);
+                }
+                TValue::List(items)
+            }
+            MAP => {
+                let len = self.varint()? as usize;
+                let mut items = Vec::with_capacity(len.min(1 << 16));
+                if len > 0 {
+                    let types = self.byte()?;
+                    for _ in 0..len {
+                        let k = self.value(types >> 4, depth + 1)?;
+                        let v = self.value(types & 0x0f, depth + 1)?;
+                        items.push((k, v));
+                    }
+                }
+                TValue::Map(items)
+            }
+            STRUCT => TValue::Struct(self.struct_at(depth + 1)?),
+            other => return Err(format!("Unknown Thrift type {}", other)),
+        })
+    }
+
+    fn struct_at(&mut self, depth: usize) -> Result<TStruct, String> {
+        let mut fields = Vec::new();
+        let mut last_id: i16 = 0;
+        loop {
+            let header = self.byte()?;
+            if header == STOP {
+                return Ok(TStruct { fields });
+            }
+            let delta = header >> 4;
+            let ty = header & 0x0f;
+            let id = if delta == 0 {
+                self.zigzag()? as i16
+            } else {
+                last_id + delta as i16
+            };
+            last_id = id;
+            let value = match ty {
+                // Field booleans are stored in the type nibble
+                BOOL_TRUE => TValue::Bool(true),
+                BOOL_FALSE => TValue::Bool(false),
+                _ => self.value(ty, depth)?,
+            };
+            fields.push((id, value));
+        }
+    }
+
+    /// Read one struct
+    pub fn read_struct(&mut self) -> Result<TStruct, String> {
+        self.struct_at(0)
+    }
+}
+
+fn put_varint(out: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        out.push((v as u8 & 0x7f) | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
+
+fn put_zigzag(out: &mut Vec<u8>, v: i64) {
+    put_varint(out, ((v << 1) ^ (v >> 63)) as u64);
+}
+
+fn write_value(out: &mut Vec<u8>, value: &TValue) {
+    match value {
+        TValue::Bool(b) => out.push(if *b { BOOL_TRUE } else { BOOL_FALSE }),
+        TValue::I8(v) => out.push(*v as u8),
+        TValue::I16(v) => put_zigzag(out, *v as i64),
+        TValue::I32(v) => put_zigzag(out, *v as i64),
+        TValue::I64(v) => put_zigzag(out, *v),
+        TValue::Double(v) => out.extend_from_slice(&v.to_le_bytes()),
+        TValue::Binary(b) => {
+            put_varint(out, b.len() as u64);
+            out.extend_from_slice(b);
+        }
+        TValue::List(items) => {
+            let elem = match items.first() {
+                Some(TValue::Bool(_)) => BOOL_TRUE,
+                Some(v) => v.type_code(),
+                None => STRUCT,
+            };
+            if items.len() < 15 {
+                out.push(((items.len() as u8) << 4) | elem);
+            } else {
+                out.push(0xf0 | elem);
+                put_varint(out, items.len() as u64);
+            }
+            for item in items {
+                write_value(out, item);
+            }
+        }
+        TValue::Map(items) => {
+            put_varint(out, items.len() as u64);
+            if let Some((k, v)) = items.first() {
+                out.push((k.type_code() << 4) | v.type_code());
+                for (k, v) in items {
+                    write_value(out, k);
+                    write_value(out, v);
+                }
+            }
+        }
+        TValue::Struct(s) => write_struct(out, s),
+    }
+}
+
+/// Append the compact encoding of `s` (fields are written in id order)
+pub fn write_struct(out: &mut Vec<u8>, s: &TStruct) {
+    let mut fields: Vec<&(i16, TValue)> = s.fields.iter().collect();
+    fields.sort_by_key(|(id, _)| *id);
+    let mut last_id: i16 = 0;
+    for (id, value) in fields {
+        let ty = value.type_code();
+        let delta = id - last_id;
+        if delta > 0 && delta <= 15 {
+            out.push(((delta as u8) << 4) | ty);
+        } else {
+            out.push(ty);
+            put_zigzag(out, *id as i64);
+        }
+        last_id = *id;
+        if !matches!(value, TValue::Bool(_)) {
+            write_value(out, value);
+        }
+    }
+    out.push(STOP);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_nested_structs() {
+        let inner = TStruct::new()
+            .with(1, TValue::Bool(true))
+            .with(2, TValue::Binary(b"abc".to_vec()));
+        let s = TStruct::new()
+            .with(1, TValue::I32(-5))
+            .with(3, TValue::I64(1 << 40))
+            .with(
+                4,
+                TValue::List((0..20).map(|_| TValue::Struct(inner.clone())).collect()),
+            )
+            .with(40, TValue::Double(2.5))
+            .with(41, TValue::Bool(false));
+        let mut out = Vec::new();
+        write_struct(&mut out, &s);
+        let back = Reader::new(&out).read_struct().unwrap();
+        assert_eq!(back, s);
+        assert_eq!(
+            back.list(4)[7].as_struct().unwrap().string(2).unwrap(),
+            "abc"
+        );
+    }
+}
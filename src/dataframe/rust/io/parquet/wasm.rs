@@ -0,0 +1,43 @@
+//! WASM bindings for the Parquet reader and writer
+
+#![cfg(feature = "wasm")]
+
+use super::reader::{ParquetReadOptions, read_parquet};
+use super::writer::{ParquetWriteOptions, write_parquet};
+use crate::io::wasm::TableWasm;
+use serde::de::DeserializeOwned;
+use wasm_bindgen::prelude::*;
+
+fn parse_options<T: DeserializeOwned + Default>(options_json: &str) -> Result<T, JsValue> {
+    if options_json.trim().is_empty() {
+        return Ok(T::default());
+    }
+    serde_json::from_str(options_json)
+        .map_err(|e| JsValue::from_str(&format!("Invalid Parquet options: {}", e)))
+}
+
+/// Read a Parquet file into typed columns
+///
+/// # Arguments
+/// * `bytes` - complete file contents
+/// * `options_json` - JSON `ParquetReadOptions`, e.g.
+///   `{"columns": ["a", "b"], "filters": [{"column": "a", "op": ">", "value": 3}]}`
+#[wasm_bindgen]
+pub fn read_parquet_wasm(bytes: &[u8], options_json: &str) -> Result<TableWasm, JsValue> {
+    let opts: ParquetReadOptions = parse_options(options_json)?;
+    read_parquet(bytes, &opts)
+        .map(|t| TableWasm::new(t, 0))
+        .map_err(|e| JsValue::from_str(&format!("Parquet error: {}", e)))
+}
+
+/// Write a table as Parquet
+///
+/// # Arguments
+/// * `options_json` - JSON `ParquetWriteOptions`
+///   (`compression`: "snappy" | "none", `rowGroupSize`, `dictionary`)
+#[wasm_bindgen]
+pub fn write_parquet_wasm(table: &TableWasm, options_json: &str) -> Result<Vec<u8>, JsValue> {
+    let opts: ParquetWriteOptions = parse_options(options_json)?;
+    write_parquet(table.table(), &opts)
+        .map_err(|e| JsValue::from_str(&format!("Parquet error: {}", e)))
+}
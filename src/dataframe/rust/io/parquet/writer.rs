@@ -0,0 +1,345 @@
+//! Parquet writer
+//!
+//! Every column is written as an OPTIONAL primitive with one data page per
+//! row group. String columns are dictionary-encoded per row group; all other
+//! values are PLAIN. Column chunks carry min/max/null-count statistics so
+//! readers (including this crate's) can skip row groups.
+
+use super::compression::{SNAPPY, UNCOMPRESSED, compress};
+use super::encoding::{
+    BOOLEAN, BYTE_ARRAY, DOUBLE, INT32, INT64, PLAIN, RLE, RLE_DICTIONARY, Values, bit_width,
+    encode_plain, encode_rle,
+};
+use super::metadata::{DATA_PAGE, DICTIONARY_PAGE, OPTIONAL, PageHeader, Statistics};
+use super::reader::PARQUET_MAGIC;
+use super::thrift::{TStruct, TValue, write_struct};
+use crate::io::columns::{Column, ColumnData, Table};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Page compression for written files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParquetCompression {
+    #[serde(alias = "uncompressed")]
+    None,
+    Snappy,
+}
+
+/// Parquet writing options (camelCase when given as JSON)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ParquetWriteOptions {
+    pub compression: ParquetCompression,
+    /// Maximum rows per row group
+    pub row_group_size: usize,
+    /// Dictionary-encode string columns (otherwise PLAIN)
+    pub dictionary: bool,
+}
+
+impl Default for ParquetWriteOptions {
+    fn default() -> Self {
+        ParquetWriteOptions {
+            compression: ParquetCompression::Snappy,
+            row_group_size: 1 << 20,
+            dictionary: true,
+        }
+    }
+}
+
+fn empty() -> TValue {
+    TValue::Struct(TStruct::new())
+}
+
+fn physical_type(data: &ColumnData) -> i32 {
+    match data {
+        ColumnData::Boolean(_) => BOOLEAN,
+        ColumnData::Int32(_) | ColumnData::Date(_) => INT32,
+        ColumnData::Float64(_) => DOUBLE,
+        ColumnData::Dictionary { .. } => BYTE_ARRAY,
+        ColumnData::Datetime(_) => INT64,
+    }
+}
+
+/// Schema element for one column: strings are UTF8, dates DATE and
+/// datetimes TIMESTAMP(MILLIS, UTC), with both logical and converted types
+fn schema_element(column: &Column) -> TStruct {
+    let (converted, logical) = match &column.data {
+        ColumnData::Dictionary { .. } => (Some(0), Some(TStruct::new().with(1, empty()))),
+        ColumnData::Date(_) => (Some(6), Some(TStruct::new().with(6, empty()))),
+        ColumnData::Datetime(_) => {
+            let timestamp = TStruct::new()
+                .with(1, TValue::Bool(true))
+                .with(2, TValue::Struct(TStruct::new().with(1, empty())));
+            (
+                Some(9),
+                Some(TStruct::new().with(8, TValue::Struct(timestamp))),
+            )
+        }
+        _ => (None, None),
+    };
+    let mut s = TStruct::new()
+        .with(1, TValue::I32(physical_type(&column.data)))
+        .with(3, TValue::I32(OPTIONAL))
+        .with(4, TValue::Binary(column.name.as_bytes().to_vec()));
+    if let Some(c) = converted {
+        s = s.with(6, TValue::I32(c));
+    }
+    if let Some(l) = logical {
+        s = s.with(10, TValue::Struct(l));
+    }
+    s
+}
+
+/// Present values of rows `lo..hi` in the column's physical type
+fn present_values(column: &Column, lo: usize, hi: usize) -> Values {
+    let valid = &column.validity[lo..hi];
+    fn keep<T: Copy, U>(v: &[T], valid: &[u8], f: impl Fn(T) -> U) -> Vec<U> {
+        v.iter()
+            .zip(valid)
+            .filter(|(_, ok)| **ok != 0)
+            .map(|(&x, _)| f(x))
+            .collect()
+    }
+    match &column.data {
+        ColumnData::Boolean(v) => Values::Bool(keep(&v[lo..hi], valid, |b| b != 0)),
+        ColumnData::Int32(v) | ColumnData::Date(v) => Values::I32(keep(&v[lo..hi], valid, |x| x)),
+        ColumnData::Float64(v) => Values::F64(keep(&v[lo..hi], valid, |x| x)),
+        ColumnData::Datetime(v) => Values::I64(keep(&v[lo..hi], valid, |x| x.round() as i64)),
+        ColumnData::Dictionary { codes, dictionary } => {
+            Values::Bytes(keep(&codes[lo..hi], valid, |c| {
+                dictionary
+                    .get(c as usize)
+                    .map_or(Vec::new(), |s| s.as_bytes().to_vec())
+            }))
+        }
+    }
+}
+
+/// PLAIN-encoded min and max of the present values
+fn bounds(values: &Values) -> Option<(Vec<u8>, Vec<u8>)> {
+    fn min_max<T: Copy + PartialOrd>(v: impl Iterator<Item = T>) -> Option<(T, T)> {
+        v.fold(None, |acc, x| match acc {
+            None => Some((x, x)),
+            Some((lo, hi)) => Some((if x < lo { x } else { lo }, if x > hi { x } else { hi })),
+        })
+    }
+    let pair = |lo: Values, hi: Values| (encode_plain(&lo), encode_plain(&hi));
+    Some(match values {
+        Values::Bool(v) => {
+            let (lo, hi) = min_max(v.iter().copied())?;
+            (vec![lo as u8], vec![hi as u8])
+        }
+        Values::I32(v) => {
+            let (lo, hi) = min_max(v.iter().copied())?;
+            pair(Values::I32(vec![lo]), Values::I32(vec![hi]))
+        }
+        Values::I64(v) => {
+            let (lo, hi) = min_max(v.iter().copied())?;
+            pair(Values::I64(vec![lo]), Values::I64(vec![hi]))
+        }
+        Values::F64(v) => {
+            let (lo, hi) = min_max(v.iter().copied().filter(|x| !x.is_nan()))?;
+            pair(Values::F64(vec![lo]), Values::F64(vec![hi]))
+        }
+        Values::Bytes(v) => {
+            let (lo, hi) = min_max(v.iter())?;
+            (lo.clone(), hi.clone())
+        }
+        Values::F32(_) | Values::Int96(_) => return None,
+    })
+}
+
+/// Serialized page header followed by its (compressed) body
+fn page(out: &mut Vec<u8>, header: &TStruct, body: &[u8]) -> usize {
+    let start = out.len();
+    write_struct(out, header);
+    out.extend_from_slice(body);
+    out.len() - start
+}
+
+/// Write rows `lo..hi` of `column` at the end of `out`, returning the
+/// ColumnChunk metadata and its uncompressed size
+fn write_chunk(
+    out: &mut Vec<u8>,
+    column: &Column,
+    lo: usize,
+    hi: usize,
+    opts: &ParquetWriteOptions,
+) -> Result<(TStruct, i64), String> {
+    let codec = match opts.compression {
+        ParquetCompression::None => UNCOMPRESSED,
+        ParquetCompression::Snappy => SNAPPY,
+    };
+    let n = hi - lo;
+    let values = present_values(column, lo, hi);
+    let statistics = Statistics {
+        null_count: Some((n - values.len()) as i64),
+        ..Statistics::default()
+    };
+    let statistics = match bounds(&values) {
+        Some((min, max)) => Statistics {
+            min: Some(min),
+            max: Some(max),
+            ..statistics
+        },
+        None => statistics,
+    };
+
+    let start = out.len();
+    let mut uncompressed = 0usize;
+    let mut dictionary_offset = None;
+    let mut encodings = vec![RLE];
+
+    let use_dictionary = opts.dictionary && matches!(column.data, ColumnData::Dictionary { .. });
+    let (encoding, value_bytes) = match values {
+        Values::Bytes(strings) if use_dictionary => {
+            let mut index: HashMap<&[u8], u32> = HashMap::new();
+            let mut entries: Vec<Vec<u8>> = Vec::new();
+            let indices: Vec<u32> = strings
+                .iter()
+                .map(|s| {
+                    *index.entry(s.as_slice()).or_insert_with(|| {
+                        entries.push(s.clone());
+                        (entries.len() - 1) as u32
+                    })
+                })
+                .collect();
+            let n_entries = entries.len();
+            let raw = encode_plain(&Values::Bytes(entries));
+            let body = compress(codec, &raw)?;
+            let header = PageHeader {
+                page_type: DICTIONARY_PAGE,
+                uncompressed_size: raw.len(),
+                compressed_size: body.len(),
+                num_values: n_entries,
+                encoding: PLAIN,
+                ..PageHeader::default()
+            };
+            dictionary_offset = Some(out.len() as i64);
+            let header_len = page(out, &header.dictionary_page(), &body) - body.len();
+            uncompressed += header_len + raw.len();
+            encodings.extend([PLAIN, RLE_DICTIONARY]);
+
+            let width = bit_width(n_entries.saturating_sub(1) as u64).max(1);
+            let mut bytes = vec![width];
+            bytes.extend(encode_rle(&indices, width));
+            (RLE_DICTIONARY, bytes)
+        }
+        values => {
+            encodings.push(PLAIN);
+            (PLAIN, encode_plain(&values))
+        }
+    };
+
+    let levels: Vec<u32> = column.validity[lo..hi].iter().map(|&v| v as u32).collect();
+    let levels = encode_rle(&levels, 1);
+    let mut raw = Vec::with_capacity(4 + levels.len() + value_bytes.len());
+    raw.extend_from_slice(&(levels.len() as u32).to_le_bytes());
+    raw.extend(levels);
+    raw.extend(value_bytes);
+    let body = compress(codec, &raw)?;
+    let header = PageHeader {
+        page_type: DATA_PAGE,
+        uncompressed_size: raw.len(),
+        compressed_size: body.len(),
+        num_values: n,
+        encoding,
+        ..PageHeader::default()
+    };
+    let data_offset = out.len() as i64;
+    let header_len = page(out, &header.data_page(RLE), &body) - body.len();
+    uncompressed += header_len + raw.len();
+
+    let mut meta = TStruct::new()
+        .with(1, TValue::I32(physical_type(&column.data)))
+        .with(
+            2,
+            TValue::List(encodings.into_iter().map(TValue::I32).collect()),
+        )
+        .with(
+            3,
+            TValue::List(vec![TValue::Binary(column.name.as_bytes().to_vec())]),
+        )
+        .with(4, TValue::I32(codec))
+        .with(5, TValue::I64(n as i64))
+        .with(6, TValue::I64(uncompressed as i64))
+        .with(7, TValue::I64((out.len() - start) as i64))
+        .with(9, TValue::I64(data_offset))
+        .with(12, TValue::Struct(statistics.to_thrift()));
+    if let Some(d) = dictionary_offset {
+        meta = meta.with(11, TValue::I64(d));
+    }
+    let chunk = TStruct::new()
+        .with(2, TValue::I64(start as i64))
+        .with(3, TValue::Struct(meta));
+    Ok((chunk, uncompressed as i64))
+}
+
+/// Serialize a table as a Parquet file
+pub fn write_parquet(table: &Table, opts: &ParquetWriteOptions) -> Result<Vec<u8>, String> {
+    let n_rows = table.n_rows();
+    if let Some(c) = table.columns.iter().find(|c| c.len() != n_rows) {
+        return Err(format!(
+            "Column '{}' has {} rows, expected {}",
+            c.name,
+            c.len(),
+            n_rows
+        ));
+    }
+    let group_size = opts.row_group_size.max(1);
+    let mut out = PARQUET_MAGIC.to_vec();
+    let mut row_groups = Vec::new();
+    let mut lo = 0;
+    while lo < n_rows && !table.columns.is_empty() {
+        let hi = (lo + group_size).min(n_rows);
+        let start = out.len();
+        let mut chunks = Vec::with_capacity(table.columns.len());
+        let mut total_uncompressed = 0;
+        for column in &table.columns {
+            let (chunk, size) = write_chunk(&mut out, column, lo, hi, opts)?;
+            chunks.push(TValue::Struct(chunk));
+            total_uncompressed += size;
+        }
+        row_groups.push(TValue::Struct(
+            TStruct::new()
+                .with(1, TValue::List(chunks))
+                .with(2, TValue::I64(total_uncompressed))
+                .with(3, TValue::I64((hi - lo) as i64))
+                .with(5, TValue::I64(start as i64))
+                .with(6, TValue::I64((out.len() - start) as i64))
+                .with(7, TValue::I16(row_groups.len() as i16)),
+        ));
+        lo = hi;
+    }
+
+    let mut schema = vec![TValue::Struct(
+        TStruct::new()
+            .with(4, TValue::Binary(b"schema".to_vec()))
+            .with(5, TValue::I32(table.columns.len() as i32)),
+    )];
+    schema.extend(
+        table
+            .columns
+            .iter()
+            .map(|c| TValue::Struct(schema_element(c))),
+    );
+    let column_orders = table
+        .columns
+        .iter()
+        .map(|_| TValue::Struct(TStruct::new().with(1, empty())))
+        .collect();
+    let footer = TStruct::new()
+        .with(1, TValue::I32(1))
+        .with(2, TValue::List(schema))
+        .with(3, TValue::I64(n_rows as i64))
+        .with(4, TValue::List(row_groups))
+        .with(6, TValue::Binary(b"tidy-ts".to_vec()))
+        .with(7, TValue::List(column_orders));
+    let footer_start = out.len();
+    write_struct(&mut out, &footer);
+    let footer_len = (out.len() - footer_start) as u32;
+    out.extend_from_slice(&footer_len.to_le_bytes());
+    out.extend_from_slice(PARQUET_MAGIC);
+    Ok(out)
+}
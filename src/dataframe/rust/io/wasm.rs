@@ -0,0 +1,259 @@
+//! WASM bindings shared by the IO readers
+
+#![cfg(feature = "wasm")]
+
+use super::columns::{Column, ColumnData, NA_CODE, Table};
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+
+/// A table handed to JavaScript column by column.
+///
+/// Column buffers are moved out by the `take*` methods: call each at most once
+/// per column. Numeric columns come back as `Float64Array` (int32 and boolean
+/// as `Int32Array` / `Uint8Array`), string columns as `Uint32Array` codes plus
+/// a dictionary, dates as epoch days and datetimes as epoch milliseconds.
+#[wasm_bindgen]
+pub struct TableWasm {
+    table: Table,
+    problems: usize,
+}
+
+impl TableWasm {
+    pub fn new(table: Table, problems: usize) -> Self {
+        TableWasm { table, problems }
+    }
+
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    pub fn into_table(self) -> Table {
+        self.table
+    }
+
+    fn push_column(
+        &mut self,
+        name: &str,
+        data: ColumnData,
+        validity: Vec<u8>,
+    ) -> Result<(), JsValue> {
+        let n = data.len();
+        let validity = if validity.is_empty() {
+            vec![1; n]
+        } else {
+            validity
+        };
+        if validity.len() != n {
+            return Err(JsValue::from_str(
+                "Validity mask length must match the values",
+            ));
+        }
+        if !self.table.columns.is_empty() && n != self.table.n_rows() {
+            return Err(JsValue::from_str(&format!(
+                "Column '{}' has {} rows, expected {}",
+                name,
+                n,
+                self.table.n_rows()
+            )));
+        }
+        self.table.columns.push(Column {
+            name: name.to_string(),
+            data,
+            validity,
+        });
+        Ok(())
+    }
+
+    fn data_mut(&mut self, i: usize) -> Option<&mut ColumnData> {
+        self.table.columns.get_mut(i).map(|c| &mut c.data)
+    }
+}
+
+#[wasm_bindgen]
+impl TableWasm {
+    /// Empty table to be filled with the `push*` methods (empty validity = all present)
+    #[wasm_bindgen(constructor)]
+    pub fn empty() -> TableWasm {
+        TableWasm::new(Table::default(), 0)
+    }
+
+    #[wasm_bindgen(js_name = pushF64)]
+    pub fn push_f64(
+        &mut self,
+        name: &str,
+        values: Vec<f64>,
+        validity: Vec<u8>,
+    ) -> Result<(), JsValue> {
+        let validity = if validity.is_empty() {
+            values.iter().map(|v| !v.is_nan() as u8).collect()
+        } else {
+            validity
+        };
+        self.push_column(name, ColumnData::Float64(values), validity)
+    }
+
+    #[wasm_bindgen(js_name = pushI32)]
+    pub fn push_i32(
+        &mut self,
+        name: &str,
+        values: Vec<i32>,
+        validity: Vec<u8>,
+    ) -> Result<(), JsValue> {
+        self.push_column(name, ColumnData::Int32(values), validity)
+    }
+
+    #[wasm_bindgen(js_name = pushBool)]
+    pub fn push_bool(
+        &mut self,
+        name: &str,
+        values: Vec<u8>,
+        validity: Vec<u8>,
+    ) -> Result<(), JsValue> {
+        self.push_column(name, ColumnData::Boolean(values), validity)
+    }
+
+    /// String column from dictionary codes (`u32::MAX` = missing)
+    #[wasm_bindgen(js_name = pushCodes)]
+    pub fn push_codes(
+        &mut self,
+        name: &str,
+        codes: Vec<u32>,
+        dictionary: Vec<String>,
+    ) -> Result<(), JsValue> {
+        if let Some(&bad) = codes
+            .iter()
+            .find(|&&c| c != NA_CODE && c as usize >= dictionary.len())
+        {
+            return Err(JsValue::from_str(&format!(
+                "Code {} is outside the dictionary",
+                bad
+            )));
+        }
+        let validity = codes.iter().map(|&c| (c != NA_CODE) as u8).collect();
+        let dictionary = Arc::new(dictionary);
+        self.push_column(name, ColumnData::Dictionary { codes, dictionary }, validity)
+    }
+
+    /// Date column from epoch days
+    #[wasm_bindgen(js_name = pushDate)]
+    pub fn push_date(
+        &mut self,
+        name: &str,
+        days: Vec<i32>,
+        validity: Vec<u8>,
+    ) -> Result<(), JsValue> {
+        self.push_column(name, ColumnData::Date(days), validity)
+    }
+
+    /// Datetime column from epoch milliseconds (NaN = missing)
+    #[wasm_bindgen(js_name = pushDatetime)]
+    pub fn push_datetime(
+        &mut self,
+        name: &str,
+        ms: Vec<f64>,
+        validity: Vec<u8>,
+    ) -> Result<(), JsValue> {
+        let validity = if validity.is_empty() {
+            ms.iter().map(|v| v.is_finite() as u8).collect()
+        } else {
+            validity
+        };
+        self.push_column(name, ColumnData::Datetime(ms), validity)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn n_rows(&self) -> usize {
+        self.table.n_rows()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn n_columns(&self) -> usize {
+        self.table.n_columns()
+    }
+
+    /// Number of cells or rows that could not be parsed cleanly
+    #[wasm_bindgen(getter)]
+    pub fn problems(&self) -> usize {
+        self.problems
+    }
+
+    #[wasm_bindgen(js_name = columnName)]
+    pub fn column_name(&self, i: usize) -> String {
+        self.table
+            .columns
+            .get(i)
+            .map(|c| c.name.clone())
+            .unwrap_or_default()
+    }
+
+    /// "boolean", "int32", "float64", "string", "date" or "datetime"
+    #[wasm_bindgen(js_name = columnType)]
+    pub fn column_type(&self, i: usize) -> String {
+        self.table
+            .columns
+            .get(i)
+            .map(|c| c.column_type().as_str().to_string())
+            .unwrap_or_default()
+    }
+
+    /// float64 or datetime values (missing = NaN)
+    #[wasm_bindgen(js_name = takeF64)]
+    pub fn take_f64(&mut self, i: usize) -> Box<[f64]> {
+        match self.data_mut(i) {
+            Some(ColumnData::Float64(v)) | Some(ColumnData::Datetime(v)) => {
+                std::mem::take(v).into_boxed_slice()
+            }
+            _ => Box::new([]),
+        }
+    }
+
+    /// int32 values or date epoch days
+    #[wasm_bindgen(js_name = takeI32)]
+    pub fn take_i32(&mut self, i: usize) -> Box<[i32]> {
+        match self.data_mut(i) {
+            Some(ColumnData::Int32(v)) | Some(ColumnData::Date(v)) => {
+                std::mem::take(v).into_boxed_slice()
+            }
+            _ => Box::new([]),
+        }
+    }
+
+    /// boolean values as 0/1
+    #[wasm_bindgen(js_name = takeBool)]
+    pub fn take_bool(&mut self, i: usize) -> Box<[u8]> {
+        match self.data_mut(i) {
+            Some(ColumnData::Boolean(v)) => std::mem::take(v).into_boxed_slice(),
+            _ => Box::new([]),
+        }
+    }
+
+    /// Dictionary codes of a string column (missing = `u32::MAX`)
+    #[wasm_bindgen(js_name = takeCodes)]
+    pub fn take_codes(&mut self, i: usize) -> Box<[u32]> {
+        match self.data_mut(i) {
+            Some(ColumnData::Dictionary { codes, .. }) => std::mem::take(codes).into_boxed_slice(),
+            _ => Box::new([]),
+        }
+    }
+
+    /// Dictionary entries of a string column, indexed by code
+    #[wasm_bindgen(js_name = takeDictionary)]
+    pub fn take_dictionary(&mut self, i: usize) -> Vec<String> {
+        match self.data_mut(i) {
+            Some(ColumnData::Dictionary { dictionary, .. }) => {
+                Arc::unwrap_or_clone(std::mem::take(dictionary))
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Validity mask (1 = present, 0 = missing)
+    #[wasm_bindgen(js_name = takeValidity)]
+    pub fn take_validity(&mut self, i: usize) -> Box<[u8]> {
+        self.table
+            .columns
+            .get_mut(i)
+            .map(|c| std::mem::take(&mut c.validity).into_boxed_slice())
+            .unwrap_or_default()
+    }
+}
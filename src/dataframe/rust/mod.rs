@@ -3,9 +3,15 @@
 //! This module provides comprehensive data frame operations including:
 //! - Sorting and arranging data
 //! - Filtering and subsetting
+//! - Missing-data fill and interpolation
 //! - Joining operations (inner, left, right, outer, cross)
-//! - Statistical functions (median, quantiles, IQR)
+//! - Statistical functions (median, quantiles, IQR, describe, weighted statistics)
+//! - Mergeable approximate sketches (t-digest quantiles, HyperLogLog distinct counts)
+//! - Histogram binning and R-style `cut`
 //! - Aggregation functions (sum, count, unique)
+//! - Date/time parsing and temporal arithmetic
+//! - Time-bucketed grouping, rolling windows and resampling
+//! - Native file readers and writers (CSV, JSON, Arrow, Parquet)
 //!
 //! All operations are optimized for WebAssembly and provide TypeScript/JavaScript bindings.
 
@@ -29,12 +35,20 @@ pub mod arrange;
 pub mod count;
 #[path = "dataframe/cross-join.wasm.rs"]
 pub mod cross_join;
+#[path = "dataframe/describe.wasm.rs"]
+pub mod describe;
 #[path = "dataframe/distinct.wasm.rs"]
 pub mod distinct;
+#[path = "dataframe/fill.wasm.rs"]
+pub mod fill;
 #[path = "dataframe/filter.wasm.rs"]
 pub mod filter_wasm;
 #[path = "dataframe/grouping.wasm.rs"]
 pub mod grouping;
+#[path = "dataframe/histogram.wasm.rs"]
+pub mod histogram;
+#[path = "dataframe/hyperloglog.wasm.rs"]
+pub mod hyperloglog;
 #[path = "dataframe/inner-join.wasm.rs"]
 pub mod inner_join;
 #[path = "dataframe/iqr.wasm.rs"]
@@ -57,8 +71,20 @@ pub mod quantile;
 pub mod right_join;
 #[path = "dataframe/sum.wasm.rs"]
 pub mod sum;
+#[path = "dataframe/tdigest.wasm.rs"]
+pub mod tdigest;
+#[path = "dataframe/temporal.wasm.rs"]
+pub mod temporal;
+#[path = "dataframe/time_window.wasm.rs"]
+pub mod time_window;
 #[path = "dataframe/unique.wasm.rs"]
 pub mod unique;
+#[path = "dataframe/weighted.wasm.rs"]
+pub mod weighted;
+
+// File format readers and writers
+#[path = "io/mod.rs"]
+pub mod io;
 
 // Statistics module
 #[path = "stats/mod.rs"]
@@ -75,12 +101,30 @@ pub use count::*;
 #[cfg(feature = "wasm")]
 pub use cross_join::*;
 #[cfg(feature = "wasm")]
+pub use describe::*;
+#[cfg(feature = "wasm")]
 pub use distinct::*;
 #[cfg(feature = "wasm")]
+pub use fill::*;
+#[cfg(feature = "wasm")]
 pub use filter_wasm::*;
 #[cfg(feature = "wasm")]
+pub use histogram::*;
+#[cfg(feature = "wasm")]
+pub use hyperloglog::*;
+#[cfg(feature = "wasm")]
 pub use inner_join::*;
 #[cfg(feature = "wasm")]
+pub use io::arrow::wasm::*;
+#[cfg(feature = "wasm")]
+pub use io::csv::wasm::*;
+#[cfg(feature = "wasm")]
+pub use io::json::wasm::*;
+#[cfg(feature = "wasm")]
+pub use io::parquet::wasm::*;
+#[cfg(feature = "wasm")]
+pub use io::wasm::*;
+#[cfg(feature = "wasm")]
 pub use iqr::*;
 #[cfg(feature = "wasm")]
 pub use left_join::*;
@@ -97,10 +141,20 @@ pub use quantile::*;
 #[cfg(feature = "wasm")]
 pub use right_join::*;
 #[cfg(feature = "wasm")]
+pub use stats::density::wasm::*;
+#[cfg(feature = "wasm")]
 pub use stats::distributions::distributions_wasm::*;
 #[cfg(feature = "wasm")]
 pub use stats::statistical_tests::*;
 #[cfg(feature = "wasm")]
 pub use sum::*;
 #[cfg(feature = "wasm")]
+pub use tdigest::*;
+#[cfg(feature = "wasm")]
+pub use temporal::*;
+#[cfg(feature = "wasm")]
+pub use time_window::*;
+#[cfg(feature = "wasm")]
 pub use unique::*;
+#[cfg(feature = "wasm")]
+pub use weighted::*;
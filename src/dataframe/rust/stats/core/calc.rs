@@ -79,3 +79,40 @@ pub fn calculate_chi2_confidence_interval(
     let upper_bound = (df * sample_variance) / chi_square_lower;
     (lower_bound, upper_bound)
 }
+
+/// Smallest sample size reaching a target power.
+///
+/// Searches `n >= min_n` by doubling and then bisection, so `power_at` must be
+/// non-decreasing in `n`.
+///
+/// # Arguments
+///
+/// * `min_n` - The smallest admissible sample size.
+/// * `target` - The desired power (e.g., 0.80).
+/// * `power_at` - The power achieved with `n` observations.
+///
+/// # Returns
+///
+/// The sample size, or infinity when even 2^40 observations fall short.
+///
+pub fn smallest_sample_size(min_n: f64, target: f64, power_at: impl Fn(f64) -> f64) -> f64 {
+    const MAX_N: f64 = 1_099_511_627_776.0; // 2^40
+    let mut hi = min_n;
+    let mut lo = min_n - 1.0;
+    while power_at(hi) < target {
+        if hi >= MAX_N {
+            return f64::INFINITY;
+        }
+        lo = hi;
+        hi *= 2.0;
+    }
+    while hi - lo > 1.0 {
+        let mid = (0.5 * (lo + hi)).floor();
+        if power_at(mid) < target {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    hi
+}
@@ -0,0 +1,355 @@
+//! Bandwidth selectors for Gaussian kernel density estimation
+//!
+//! Ports of R's `bw.nrd0`, `bw.nrd`, `bw.ucv`, `bw.bcv` and `bw.SJ`
+//! (stats/R/bandwidths.R and stats/src/bandwidths.c). Like R, the selectors ignore observation
+//! weights and work on the finite values only.
+
+use super::optim::{fmin, zeroin};
+use std::f64::consts::PI;
+
+/// Squared standardised distances beyond which pair terms are negligible
+const DELMAX: f64 = 1000.0;
+
+/// Default number of bins for the pairwise-distance counts (R's `nb`)
+pub const DEFAULT_NB: usize = 1000;
+
+/// Bandwidth selection rule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bandwidth {
+    /// Silverman's rule of thumb (`bw.nrd0`, R's default)
+    Nrd0,
+    /// Scott's variation with factor 1.06 (`bw.nrd`)
+    Nrd,
+    /// Unbiased (least-squares) cross-validation (`bw.ucv`)
+    Ucv,
+    /// Biased cross-validation (`bw.bcv`)
+    Bcv,
+    /// Sheather & Jones, solve-the-equation (`bw.SJ`, `method = "ste"`)
+    SjSte,
+    /// Sheather & Jones, direct plug-in (`bw.SJ`, `method = "dpi"`)
+    SjDpi,
+    /// A fixed bandwidth (the kernel standard deviation)
+    Fixed(f64),
+}
+
+impl Bandwidth {
+    /// Parse R's rule names (`"nrd0"`, `"nrd"`, `"ucv"`, `"bcv"`,
+    /// `"SJ"`/`"SJ-ste"`, `"SJ-dpi"`), case-insensitively
+    pub fn parse(name: &str) -> Result<Bandwidth, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "nrd0" => Ok(Bandwidth::Nrd0),
+            "nrd" => Ok(Bandwidth::Nrd),
+            "ucv" => Ok(Bandwidth::Ucv),
+            "bcv" => Ok(Bandwidth::Bcv),
+            "sj" | "sj-ste" => Ok(Bandwidth::SjSte),
+            "sj-dpi" => Ok(Bandwidth::SjDpi),
+            _ => Err(format!("Unknown bandwidth rule '{}'", name)),
+        }
+    }
+
+    /// Select the bandwidth for `x`
+    pub fn select(self, x: &[f64]) -> Result<f64, String> {
+        match self {
+            Bandwidth::Nrd0 => bw_nrd0(x),
+            Bandwidth::Nrd => bw_nrd(x),
+            Bandwidth::Ucv => bw_ucv(x, DEFAULT_NB),
+            Bandwidth::Bcv => bw_bcv(x, DEFAULT_NB),
+            Bandwidth::SjSte => bw_sj(x, DEFAULT_NB, false),
+            Bandwidth::SjDpi => bw_sj(x, DEFAULT_NB, true),
+            Bandwidth::Fixed(h) => {
+                if h > 0.0 && h.is_finite() {
+                    Ok(h)
+                } else {
+                    Err(format!("Bandwidth must be positive, got {}", h))
+                }
+            }
+        }
+    }
+}
+
+fn finite_sorted(x: &[f64]) -> Vec<f64> {
+    let mut v: Vec<f64> = x.iter().copied().filter(|v| v.is_finite()).collect();
+    v.sort_by(f64::total_cmp);
+    v
+}
+
+/// Sample standard deviation
+fn sd(x: &[f64]) -> f64 {
+    let n = x.len() as f64;
+    let mean = x.iter().sum::<f64>() / n;
+    (x.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+}
+
+/// Interquartile range (type 7) of sorted data
+fn iqr(sorted: &[f64]) -> f64 {
+    let q = |p: f64| {
+        let h = (sorted.len() - 1) as f64 * p;
+        let lo = h.floor() as usize;
+        let hi = (lo + 1).min(sorted.len() - 1);
+        sorted[lo] + (h - lo as f64) * (sorted[hi] - sorted[lo])
+    };
+    q(0.75) - q(0.25)
+}
+
+fn need_two(x: &[f64]) -> Result<(), String> {
+    if x.len() < 2 {
+        Err("need at least 2 data points".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Silverman's rule of thumb: `0.9 * min(sd, IQR / 1.34) * n^(-1/5)`
+pub fn bw_nrd0(x: &[f64]) -> Result<f64, String> {
+    let x = finite_sorted(x);
+    need_two(&x)?;
+    let hi = sd(&x);
+    let mut lo = hi.min(iqr(&x) / 1.34);
+    if lo == 0.0 {
+        lo = hi;
+    }
+    if lo == 0.0 {
+        lo = x[0].abs();
+    }
+    if lo == 0.0 {
+        lo = 1.0;
+    }
+    Ok(0.9 * lo * (x.len() as f64).powf(-0.2))
+}
+
+/// Scott's rule: `1.06 * min(sd, IQR / 1.34) * n^(-1/5)`
+pub fn bw_nrd(x: &[f64]) -> Result<f64, String> {
+    let x = finite_sorted(x);
+    need_two(&x)?;
+    let h = iqr(&x) / 1.34;
+    Ok(1.06 * sd(&x).min(h) * (x.len() as f64).powf(-0.2))
+}
+
+/// Bin width and binned pairwise-distance counts (R's `bw_pair_cnts`)
+fn pair_counts(x: &[f64], nb: usize) -> Result<(f64, Vec<f64>), String> {
+    let (xmin, xmax) = (x[0], x[x.len() - 1]);
+    let rang = (xmax - xmin) * 1.01;
+    if rang == 0.0 {
+        return Err("data are constant in bandwidth calculation".to_string());
+    }
+    let dd = rang / nb as f64;
+    let mut cnt = vec![0.0; nb];
+    if x.len() > nb / 2 {
+        // Bin first, then count pairs of bins
+        let codes: Vec<i64> = x.iter().map(|v| (v / dd).trunc() as i64).collect();
+        let min_code = codes.iter().copied().min().unwrap_or(0);
+        let mut tab = vec![0.0f64; nb];
+        for c in codes {
+            let k = (c - min_code) as usize;
+            if k < nb {
+                tab[k] += 1.0;
+            }
+        }
+        for ii in 0..nb {
+            let w = tab[ii];
+            cnt[0] += w * (w - 1.0);
+            for jj in 0..ii {
+                cnt[ii - jj] += w * tab[jj];
+            }
+        }
+        cnt[0] *= 0.5;
+    } else {
+        for i in 1..x.len() {
+            let ii = (x[i] / dd).trunc() as i64;
+            for &xj in &x[..i] {
+                let jj = (xj / dd).trunc() as i64;
+                cnt[ii.abs_diff(jj) as usize] += 1.0;
+            }
+        }
+    }
+    Ok((dd, cnt))
+}
+
+/// Sum of `term(delta^2) * cnt[i]` over bins with `delta = i * d / h`
+fn binned_sum(d: f64, cnt: &[f64], h: f64, term: impl Fn(f64) -> f64) -> f64 {
+    let mut sum = 0.0;
+    for (i, &c) in cnt.iter().enumerate() {
+        let delta = (i as f64 * d / h).powi(2);
+        if delta >= DELMAX {
+            break;
+        }
+        sum += term(delta) * c;
+    }
+    sum
+}
+
+/// Unbiased cross-validation bandwidth, minimised over
+/// `[0.1 * hmax, hmax]` with `hmax = 1.144 * sd * n^(-1/5)`
+pub fn bw_ucv(x: &[f64], nb: usize) -> Result<f64, String> {
+    let x = finite_sorted(x);
+    need_two(&x)?;
+    let n = x.len() as f64;
+    let hmax = 1.144 * sd(&x) * n.powf(-0.2);
+    let (lower, upper) = (0.1 * hmax, hmax);
+    let (d, cnt) = pair_counts(&x, nb)?;
+    let fucv = |h: f64| {
+        let sum = binned_sum(d, &cnt, h, |delta| {
+            (-delta / 4.0).exp() - 8f64.sqrt() * (-delta / 2.0).exp()
+        });
+        (0.5 + sum / n) / (n * h * PI.sqrt())
+    };
+    Ok(fmin(lower, upper, fucv, 0.1 * lower))
+}
+
+/// Biased cross-validation bandwidth, minimised over the same range as
+/// [`bw_ucv`]
+pub fn bw_bcv(x: &[f64], nb: usize) -> Result<f64, String> {
+    let x = finite_sorted(x);
+    need_two(&x)?;
+    let n = x.len() as f64;
+    let hmax = 1.144 * sd(&x) * n.powf(-0.2);
+    let (lower, upper) = (0.1 * hmax, hmax);
+    let (d, cnt) = pair_counts(&x, nb)?;
+    let fbcv = |h: f64| {
+        let sum = binned_sum(d, &cnt, h, |delta| {
+            (-delta / 4.0).exp() * (delta * delta - 12.0 * delta + 12.0)
+        });
+        (1.0 + sum / (32.0 * n)) / (2.0 * n * h * PI.sqrt())
+    };
+    Ok(fmin(lower, upper, fbcv, 0.1 * lower))
+}
+
+/// Sheather & Jones bandwidth (`method = "ste"` or, with `dpi`, `"dpi"`)
+pub fn bw_sj(x: &[f64], nb: usize, dpi: bool) -> Result<f64, String> {
+    let x = finite_sorted(x);
+    need_two(&x)?;
+    let n = x.len() as f64;
+    let (d, cnt) = pair_counts(&x, nb)?;
+    let norm = |h: f64, p: i32| n * (n - 1.0) * h.powi(p) * (2.0 * PI).sqrt();
+    let sdh = |h: f64| {
+        let sum = binned_sum(d, &cnt, h, |delta| {
+            (-delta / 2.0).exp() * (delta * delta - 6.0 * delta + 3.0)
+        });
+        (2.0 * sum + n * 3.0) / norm(h, 5)
+    };
+    let tdh = |h: f64| {
+        let sum = binned_sum(d, &cnt, h, |delta| {
+            (-delta / 2.0).exp() * (delta.powi(3) - 15.0 * delta * delta + 45.0 * delta - 15.0)
+        });
+        (2.0 * sum - 15.0 * n) / norm(h, 7)
+    };
+
+    let scale = sd(&x).min(iqr(&x) / 1.349);
+    let a = 1.24 * scale * n.powf(-1.0 / 7.0);
+    let b = 1.23 * scale * n.powf(-1.0 / 9.0);
+    let c1 = 1.0 / (2.0 * PI.sqrt() * n);
+    let td = -tdh(b);
+    if !td.is_finite() || td <= 0.0 {
+        return Err("sample is too sparse to find TD".to_string());
+    }
+    if dpi {
+        return Ok((c1 / sdh((2.394 / (n * td)).powf(1.0 / 7.0))).powf(0.2));
+    }
+
+    let hmax = 1.144 * scale * n.powf(-0.2);
+    let (mut lower, mut upper) = (0.1 * hmax, hmax);
+    let alph2 = 1.357 * (sdh(a) / td).powf(1.0 / 7.0);
+    if !alph2.is_finite() {
+        return Err("sample is too sparse to find alph2".to_string());
+    }
+    let fsd = |h: f64| (c1 / sdh(alph2 * h.powf(5.0 / 7.0))).powf(0.2) - h;
+    let mut itry = 1;
+    while fsd(lower) * fsd(upper) > 0.0 {
+        if itry > 99 {
+            return Err("no solution in the specified range of bandwidths".to_string());
+        }
+        if itry % 2 == 1 {
+            upper *= 1.2;
+        } else {
+            lower /= 1.2;
+        }
+        itry += 1;
+    }
+    // R's default `tol = 0.1 * lower` is only forced here, after widening
+    zeroin(lower, upper, fsd, 0.1 * lower, 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bw_nrd0_and_nrd() {
+        // 1:5 has sd 1.5811 and IQR 2, so IQR / 1.34 is the smaller scale
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let scale = 2.0 / 1.34;
+        assert!((bw_nrd0(&x).unwrap() - 0.9 * scale * 5f64.powf(-0.2)).abs() < 1e-12);
+        assert!((bw_nrd(&x).unwrap() - 1.06 * scale * 5f64.powf(-0.2)).abs() < 1e-12);
+        // bw.nrd0(c(2, 2, 2, 2)) falls back to |x[1]|
+        assert!(
+            (bw_nrd0(&[2.0, 2.0, 2.0, 2.0]).unwrap() - 0.9 * 2.0 * 4f64.powf(-0.2)).abs() < 1e-12
+        );
+        assert!(bw_nrd0(&[1.0]).is_err());
+    }
+
+    #[test]
+    fn test_bw_sj_and_ucv_are_in_range() {
+        // Deterministic, roughly normal sample
+        let x: Vec<f64> = (1..200)
+            .map(|i| crate::stats::distributions::qnorm(i as f64 / 200.0, 10.0, 2.0, true, false))
+            .collect();
+        let nrd0 = bw_nrd0(&x).unwrap();
+        for h in [
+            bw_sj(&x, DEFAULT_NB, false).unwrap(),
+            bw_sj(&x, DEFAULT_NB, true).unwrap(),
+            bw_ucv(&x, DEFAULT_NB).unwrap(),
+        ] {
+            assert!(h > 0.5 * nrd0 && h < 2.0 * nrd0, "{} vs {}", h, nrd0);
+        }
+        assert!(bw_sj(&[3.0, 3.0, 3.0], DEFAULT_NB, false).is_err());
+    }
+
+    #[test]
+    fn test_reference_values() {
+        // Bimodal sample (unbinned pair counts) and a 600-point triangular
+        // sample (binned counts). Expected values are
+        // c(bw.SJ(x), bw.SJ(x, method = "dpi"), bw.ucv(x), bw.bcv(x)) from an
+        // independent transcription of R's bandwidths.R / bandwidths.c
+        let bimodal = [
+            0.47, -0.7, -0.34, 0.19, -0.51, -0.04, 0.09, -0.42, -0.65, 0.1, 0.5, -0.32, -0.17,
+            0.82, -0.28, -0.26, 1.2, -0.77, 0.4, -1.0, 4.7, 5.75, 5.61, 4.55, 4.77, 5.04, 4.37,
+            5.28, 6.11, 4.32, 4.01, 5.14, 4.94, 5.9, 4.92, 4.97, 4.9, 4.5, 5.34, 4.34,
+        ];
+        // x <- ((1:600) * 0.7548776662) %% 1 + ((1:600) * 0.5698402910) %% 1
+        let triangular: Vec<f64> = (1..=600)
+            .map(|i| (i as f64 * 0.7548776662).fract() + (i as f64 * 0.5698402910).fract())
+            .collect();
+        let cases: [(&[f64], [f64; 4]); 2] = [
+            (
+                &bimodal,
+                [
+                    0.46341773470414094,
+                    0.6001255729147255,
+                    0.4774543973074036,
+                    0.5100189062056167,
+                ],
+            ),
+            (
+                &triangular,
+                [
+                    0.13404171935326348,
+                    0.1336622246391729,
+                    0.12935631444597337,
+                    0.1292587335666648,
+                ],
+            ),
+        ];
+        for (x, expected) in cases {
+            let got = [
+                bw_sj(x, DEFAULT_NB, false).unwrap(),
+                bw_sj(x, DEFAULT_NB, true).unwrap(),
+                bw_ucv(x, DEFAULT_NB).unwrap(),
+                bw_bcv(x, DEFAULT_NB).unwrap(),
+            ];
+            for (g, e) in got.iter().zip(expected) {
+                assert!((g - e).abs() < 1e-6 * e, "{} vs {}", g, e);
+            }
+        }
+        assert_eq!(Bandwidth::parse("BCV"), Ok(Bandwidth::Bcv));
+    }
+}
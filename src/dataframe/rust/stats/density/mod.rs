@@ -0,0 +1,15 @@
+//! Kernel density estimation
+//!
+//! Univariate KDE on an FFT-binned grid with R's kernels and bandwidth
+//! selectors (`bw.nrd0`, `bw.nrd`, `bw.ucv`, `bw.bcv`, `bw.SJ`), observation
+//! weights and reflection at support bounds.
+
+pub mod bandwidth;
+pub mod kde;
+mod optim;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use bandwidth::{Bandwidth, bw_bcv, bw_nrd, bw_nrd0, bw_sj, bw_ucv};
+pub use kde::{Kde, KdeOptions, Kernel, kde};
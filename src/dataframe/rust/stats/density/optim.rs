@@ -0,0 +1,185 @@
+//! One-dimensional minimisation and root finding
+//!
+//! Ports of R's `Brent_fmin` (behind `optimize()`) and `R_zeroin2` (behind
+//! `uniroot()`), so that bandwidth selectors land on the same values as R.
+
+/// Minimise `f` on `[ax, bx]` to tolerance `tol` (R's `optimize`)
+pub(crate) fn fmin(ax: f64, bx: f64, mut f: impl FnMut(f64) -> f64, tol: f64) -> f64 {
+    let c = (3.0 - 5f64.sqrt()) * 0.5;
+    let eps = f64::EPSILON.sqrt();
+
+    let (mut a, mut b) = (ax, bx);
+    let mut v = a + c * (b - a);
+    let (mut w, mut x) = (v, v);
+    let (mut d, mut e) = (0.0f64, 0.0f64);
+    let mut fx = f(x);
+    let (mut fv, mut fw) = (fx, fx);
+    let tol3 = tol / 3.0;
+
+    loop {
+        let xm = (a + b) * 0.5;
+        let tol1 = eps * x.abs() + tol3;
+        let t2 = tol1 * 2.0;
+        if (x - xm).abs() <= t2 - (b - a) * 0.5 {
+            break;
+        }
+        let (mut p, mut q, mut r) = (0.0, 0.0, 0.0);
+        if e.abs() > tol1 {
+            // Fit a parabola
+            r = (x - w) * (fx - fv);
+            q = (x - v) * (fx - fw);
+            p = (x - v) * q - (x - w) * r;
+            q = (q - r) * 2.0;
+            if q > 0.0 {
+                p = -p;
+            } else {
+                q = -q;
+            }
+            r = e;
+            e = d;
+        }
+        if p.abs() >= (q * 0.5 * r).abs() || p <= q * (a - x) || p >= q * (b - x) {
+            // Golden-section step
+            e = if x < xm { b - x } else { a - x };
+            d = c * e;
+        } else {
+            // Parabolic-interpolation step
+            d = p / q;
+            let u = x + d;
+            if u - a < t2 || b - u < t2 {
+                d = if x >= xm { -tol1 } else { tol1 };
+            }
+        }
+        let u = if d.abs() >= tol1 {
+            x + d
+        } else if d > 0.0 {
+            x + tol1
+        } else {
+            x - tol1
+        };
+        let fu = f(u);
+        if fu <= fx {
+            if u < x {
+                b = x;
+            } else {
+                a = x;
+            }
+            v = w;
+            w = x;
+            x = u;
+            fv = fw;
+            fw = fx;
+            fx = fu;
+        } else {
+            if u < x {
+                a = u;
+            } else {
+                b = u;
+            }
+            if fu <= fw || w == x {
+                v = w;
+                fv = fw;
+                w = u;
+                fw = fu;
+            } else if fu <= fv || v == x || v == w {
+                v = u;
+                fv = fu;
+            }
+        }
+    }
+    x
+}
+
+/// Root of `f` in `[ax, bx]` to tolerance `tol` (R's `uniroot`); `f` must
+/// change sign over the interval
+pub(crate) fn zeroin(
+    ax: f64,
+    bx: f64,
+    mut f: impl FnMut(f64) -> f64,
+    tol: f64,
+    max_iter: usize,
+) -> Result<f64, String> {
+    let (mut a, mut b) = (ax, bx);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Ok(a);
+    }
+    if fb == 0.0 {
+        return Ok(b);
+    }
+    if fa * fb > 0.0 {
+        return Err("f() values at end points not of opposite sign".to_string());
+    }
+    let (mut c, mut fc) = (a, fa);
+
+    for _ in 0..=max_iter {
+        let prev_step = b - a;
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let tol_act = 2.0 * f64::EPSILON * b.abs() + tol / 2.0;
+        let mut new_step = (c - b) / 2.0;
+        if new_step.abs() <= tol_act || fb == 0.0 {
+            return Ok(b);
+        }
+        if prev_step.abs() >= tol_act && fa.abs() > fb.abs() {
+            // Secant or inverse quadratic interpolation
+            let cb = c - b;
+            let (mut p, mut q);
+            if a == c {
+                let t1 = fb / fa;
+                p = cb * t1;
+                q = 1.0 - t1;
+            } else {
+                let qq = fa / fc;
+                let t1 = fb / fc;
+                let t2 = fb / fa;
+                p = t2 * (cb * qq * (qq - t1) - (b - a) * (t1 - 1.0));
+                q = (qq - 1.0) * (t1 - 1.0) * (t2 - 1.0);
+            }
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            if p < 0.75 * cb * q - (tol_act * q).abs() / 2.0 && p < (prev_step * q / 2.0).abs() {
+                new_step = p / q;
+            }
+        }
+        if new_step.abs() < tol_act {
+            new_step = if new_step > 0.0 { tol_act } else { -tol_act };
+        }
+        a = b;
+        fa = fb;
+        b += new_step;
+        fb = f(b);
+        if (fb > 0.0 && fc > 0.0) || (fb < 0.0 && fc < 0.0) {
+            c = a;
+            fc = fa;
+        }
+    }
+    Err(format!("root not found in {} iterations", max_iter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmin_parabola() {
+        let x = fmin(0.0, 5.0, |x| (x - 1.5) * (x - 1.5) + 2.0, 1e-8);
+        assert!((x - 1.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_zeroin_cubic() {
+        let root = zeroin(0.0, 2.0, |x| x * x * x - 2.0, 1e-12, 1000).unwrap();
+        assert!((root - 2f64.cbrt()).abs() < 1e-10);
+        assert!(zeroin(2.0, 3.0, |x| x * x * x - 2.0, 1e-12, 1000).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+//! WASM bindings for kernel density estimation
+
+#![cfg(feature = "wasm")]
+
+use super::bandwidth::Bandwidth;
+use super::kde::{Kde, KdeOptions, Kernel, kde};
+use wasm_bindgen::prelude::*;
+
+/// Density estimate returned to JavaScript
+#[wasm_bindgen]
+pub struct KdeResult {
+    inner: Kde,
+}
+
+#[wasm_bindgen]
+impl KdeResult {
+    /// Grid points
+    #[wasm_bindgen(getter)]
+    pub fn x(&self) -> Vec<f64> {
+        self.inner.x.clone()
+    }
+
+    /// Density at each grid point
+    #[wasm_bindgen(getter)]
+    pub fn y(&self) -> Vec<f64> {
+        self.inner.y.clone()
+    }
+
+    /// Bandwidth used
+    #[wasm_bindgen(getter)]
+    pub fn bw(&self) -> f64 {
+        self.inner.bw
+    }
+
+    /// Number of finite observations
+    #[wasm_bindgen(getter)]
+    pub fn n(&self) -> usize {
+        self.inner.n
+    }
+}
+
+/// WASM export for kernel density estimation
+///
+/// # Arguments
+/// * `x` - Observations (non-finite values are dropped)
+/// * `weights` - Non-negative weights, or empty for equal weights
+/// * `kernel` - "gaussian", "epanechnikov", "rectangular", "triangular",
+///   "biweight", "cosine" or "optcosine"
+/// * `bw` - "nrd0", "nrd", "ucv", "bcv", "SJ" / "SJ-ste" or "SJ-dpi"; ignored
+///   when `bw_value` is given
+/// * `bw_value` - Fixed bandwidth (kernel standard deviation)
+/// * `adjust` - Bandwidth multiplier
+/// * `n` - Number of output grid points
+/// * `from`, `to` - Grid range (defaults extend `cut` bandwidths beyond the data)
+/// * `lower`, `upper` - Reflecting support bounds
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn kde_wasm(
+    x: &[f64],
+    weights: &[f64],
+    kernel: &str,
+    bw: &str,
+    bw_value: Option<f64>,
+    adjust: f64,
+    n: usize,
+    from: Option<f64>,
+    to: Option<f64>,
+    cut: f64,
+    lower: Option<f64>,
+    upper: Option<f64>,
+) -> Result<KdeResult, JsValue> {
+    let to_js = |e: String| JsValue::from_str(&format!("KDE error: {}", e));
+    let opts = KdeOptions {
+        kernel: Kernel::parse(kernel).map_err(to_js)?,
+        bw: match bw_value {
+            Some(h) => Bandwidth::Fixed(h),
+            None => Bandwidth::parse(bw).map_err(to_js)?,
+        },
+        adjust,
+        n,
+        from,
+        to,
+        cut,
+        lower,
+        upper,
+    };
+    kde(x, weights, &opts)
+        .map(|inner| KdeResult { inner })
+        .map_err(to_js)
+}
+
+/// WASM export for bandwidth selection ("nrd0", "nrd", "ucv", "bcv", "SJ", "SJ-dpi")
+#[wasm_bindgen]
+pub fn bandwidth_wasm(x: &[f64], rule: &str) -> Result<f64, JsValue> {
+    Bandwidth::parse(rule)
+        .and_then(|b| b.select(x))
+        .map_err(|e| JsValue::from_str(&format!("KDE error: {}", e)))
+}
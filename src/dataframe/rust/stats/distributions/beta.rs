@@ -1,7 +1,19 @@
-//! Beta distribution wrapper using statrs
+//! Beta distribution
+//!
+//! Density and sampling use statrs; the distribution function is TOMS 708
+//! (via `pbeta_raw`) and the quantile a port of R's `qbeta`, so both tails
+//! and `log_p` results are accurate well beyond double-precision underflow
+//! of the probability.
+//!
+//! Source: R-4.5.1/nmath/pbeta.c, qbeta.c
 
+use super::helpers::dpq::{
+    dt_civ, dt_clog, dt_log, dt_qiv, log1_exp, q_p01_boundaries,
+};
+use super::helpers::regularized_incomplete::pbeta_raw;
+use super::helpers::toms708::betaln;
 use rand::Rng;
-use statrs::distribution::{Beta, Continuous, ContinuousCDF};
+use statrs::distribution::{Beta, Continuous};
 
 pub fn dbeta(x: f64, shape1: f64, shape2: f64, give_log: bool) -> f64 {
     if shape1 <= 0.0 || shape2 <= 0.0 {
@@ -16,25 +28,350 @@ pub fn dbeta(x: f64, shape1: f64, shape2: f64, give_log: bool) -> f64 {
 }
 
 pub fn pbeta(x: f64, shape1: f64, shape2: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || shape1.is_nan() || shape2.is_nan() {
+        return x + shape1 + shape2;
+    }
     if shape1 <= 0.0 || shape2 <= 0.0 {
         return f64::NAN;
     }
-    let dist = Beta::new(shape1, shape2).expect("validated parameters: shape1 > 0, shape2 > 0");
-    let cdf = if lower_tail { dist.cdf(x) } else { 1.0 - dist.cdf(x) };
-    if log_p { cdf.ln() } else { cdf }
+    pbeta_raw(x, shape1, shape2, lower_tail, log_p)
 }
 
 pub fn qbeta(p: f64, shape1: f64, shape2: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || shape1.is_nan() || shape2.is_nan() {
+        return p + shape1 + shape2;
+    }
     if shape1 <= 0.0 || shape2 <= 0.0 {
         return f64::NAN;
     }
-    let dist = Beta::new(shape1, shape2).expect("validated parameters: shape1 > 0, shape2 > 0");
-    let mut p_val = if log_p { p.exp() } else { p };
-    if !lower_tail {
-        p_val = 1.0 - p_val;
+    if let Some(q) = q_p01_boundaries(p, 0.0, 1.0, lower_tail, log_p) {
+        return q;
     }
-    p_val = super::helpers::clamp_unit(p_val);
-    dist.inverse_cdf(p_val)
+    qbeta_raw(p, shape1, shape2, lower_tail, log_p)
+}
+
+/// Smallest positive value tried at the left border
+const DBL_VERY_MIN: f64 = f64::MIN_POSITIVE / 4.0;
+/// `log(DBL_VERY_MIN)`
+const DBL_LOG_V_MIN: f64 = std::f64::consts::LN_2 * (f64::MIN_EXP as f64 - 2.0);
+const FPU: f64 = 3e-308;
+const ACU_MIN: f64 = 1e-300;
+const P_LO: f64 = FPU;
+const P_HI: f64 = 1.0 - 2.22e-16;
+/// Work on the `log(x)` scale once the start is below `exp(LOG_Q_CUT)`
+const LOG_Q_CUT: f64 = -5.0;
+/// Number of unguarded Newton steps
+const N_NEWTON_FREE: usize = 4;
+
+/// Beta quantile for `0 < p < 1` (on the chosen scale), `shape1, shape2 > 0`
+///
+/// AS 109 (Cran, Martin and Thomas, 1977) start, then a damped Newton
+/// iteration on `x` or, far in the left tail, on `log(x)`; the smaller tail
+/// is always the one iterated on (swapping the shapes if needed).
+fn qbeta_raw(alpha: f64, p: f64, q: f64, lower_tail: bool, log_p: bool) -> f64 {
+    const CONST1: f64 = 2.30753;
+    const CONST2: f64 = 0.27061;
+    const CONST3: f64 = 0.99229;
+    const CONST4: f64 = 0.04481;
+
+    // Infinite shapes: point masses
+    if !p.is_finite() || !q.is_finite() {
+        if p / q == 0.0 {
+            return 0.0;
+        }
+        if q / p == 0.0 {
+            return 1.0;
+        }
+        return 0.5;
+    }
+
+    // Lower-tail probability, in any case
+    let p_ = dt_qiv(alpha, lower_tail, log_p);
+    let logbeta = betaln(p, q);
+
+    let mut swap_tail = p_ > 0.5;
+    let (mut a, mut la, mut pp, mut qq);
+    if swap_tail {
+        // Change tail, swap p <-> q
+        a = dt_civ(alpha, lower_tail, log_p);
+        la = dt_clog(alpha, lower_tail, log_p);
+        pp = q;
+        qq = p;
+    } else {
+        a = p_;
+        la = dt_log(alpha, lower_tail, log_p);
+        pp = p;
+        qq = q;
+    }
+
+    // Desired accuracy for the Newton iterations, after AS 109 remark
+    let acu = ACU_MIN.max(10f64.powf(-13.0 - 2.5 / (pp * pp) - 0.5 / (a * a)));
+
+    // Initial approximation
+    let mut r = (-2.0 * la).sqrt();
+    let mut y = r - (CONST1 + CONST2 * r) / (1.0 + (CONST3 + CONST4 * r) * r);
+    let mut u;
+    let mut xinbta;
+    if pp > 1.0 && qq > 1.0 {
+        // Carter (1947), AS 109 remark 5
+        r = (y * y - 3.0) / 6.0;
+        let s = 1.0 / (pp + pp - 1.0);
+        let t = 1.0 / (qq + qq - 1.0);
+        let h = 2.0 / (s + t);
+        let w = y * (h + r).sqrt() / h - (t - s) * (r + 5.0 / 6.0 - 2.0 / (3.0 * h));
+        if w > 300.0 {
+            // exp(w + w) is huge or overflows
+            let t = w + w + qq.ln() - pp.ln();
+            u = if t <= 18.0 { -t.exp().ln_1p() } else { -t - (-t).exp() };
+            xinbta = u.exp();
+        } else {
+            xinbta = pp / (pp + qq * (w + w).exp());
+            u = -(qq / pp * (w + w).exp()).ln_1p();
+        }
+    } else {
+        // AS 64: Scheffé-Tukey (1944) and Wilson-Hilferty
+        r = qq + qq;
+        let t = 1.0 / (3.0 * qq.sqrt());
+        let t = r * (1.0 + t * (-t + y)).powi(3);
+        let s = 4.0 * pp + r - 2.0;
+        if t == 0.0 || (t < 0.0 && s >= t) {
+            // Cannot use the chi-squared approximation:
+            // x0 = 1 - {(1 - a) q B(p, q)}^(1/q)  (AS 65)
+            let l1ma = if swap_tail {
+                dt_log(alpha, lower_tail, log_p)
+            } else {
+                dt_clog(alpha, lower_tail, log_p)
+            };
+            let xx = (l1ma + qq.ln() + logbeta) / qq;
+            if xx <= 0.0 {
+                xinbta = -xx.exp_m1();
+                u = log1_exp(xx);
+            } else {
+                xinbta = 0.0;
+                u = f64::NEG_INFINITY;
+            }
+        } else {
+            let t = s / t;
+            if t <= 1.0 {
+                u = (la + pp.ln() + logbeta) / pp;
+                xinbta = u.exp();
+            } else {
+                // (1 + x0) / (1 - x0) = t, solved for x0
+                xinbta = 1.0 - 2.0 / (t + 1.0);
+                u = (-2.0 / (t + 1.0)).ln_1p();
+            }
+        }
+    }
+
+    // Revert the tail swap when the start says the other tail is smaller
+    if (swap_tail && u >= -LOG_Q_CUT.exp())
+        || (!swap_tail && u >= -(4.0 * LOG_Q_CUT).exp() && pp / qq < 1000.0)
+    {
+        swap_tail = !swap_tail;
+        if swap_tail {
+            a = dt_civ(alpha, lower_tail, log_p);
+            la = dt_clog(alpha, lower_tail, log_p);
+            pp = q;
+            qq = p;
+        } else {
+            a = p_;
+            la = dt_log(alpha, lower_tail, log_p);
+            pp = p;
+            qq = q;
+        }
+        u = log1_exp(u);
+        xinbta = u.exp();
+    }
+
+    let use_log_x = u < LOG_Q_CUT;
+    let bad_u = !u.is_finite();
+    let bad_init = bad_u || xinbta > P_HI;
+
+    let mut u_n = 1.0;
+    let mut tx = xinbta;
+    let mut add_n_step = true;
+
+    'ret: {
+        if bad_u || u < LOG_Q_CUT {
+            // Try the left border quickly, at the smallest positive number
+            let w = pbeta_raw(DBL_VERY_MIN, pp, qq, true, log_p);
+            if w > if log_p { la } else { a } {
+                if log_p || (w - a).abs() < a.abs() {
+                    tx = DBL_VERY_MIN;
+                    u_n = DBL_LOG_V_MIN;
+                } else {
+                    tx = 0.0;
+                    u_n = f64::NEG_INFINITY;
+                }
+                return finish(tx, u_n, log_p, false, swap_tail, pp, qq, a, la, logbeta, log_p);
+            } else if u < DBL_LOG_V_MIN {
+                u = DBL_LOG_V_MIN;
+                xinbta = DBL_VERY_MIN;
+            }
+        }
+
+        // Sometimes the approximation is negative (and 0 is also not ok)
+        if bad_init && !(use_log_x && tx > 0.0) {
+            if u == f64::NEG_INFINITY {
+                u = std::f64::consts::LN_2 * f64::MIN_EXP as f64;
+                xinbta = f64::MIN_POSITIVE;
+            } else {
+                xinbta = if xinbta > 1.1 {
+                    // Way off
+                    0.5
+                } else if xinbta < P_LO {
+                    u.exp()
+                } else {
+                    P_HI
+                };
+                if bad_u {
+                    u = xinbta.ln();
+                }
+            }
+        }
+
+        // Modified Newton-Raphson on pbeta_raw
+        let r = 1.0 - pp;
+        let t = 1.0 - qq;
+        let mut wprev = 0.0;
+        let mut prev: f64 = 1.0;
+        let mut adj: f64 = 1.0;
+
+        if use_log_x {
+            // Work with u = log(x)
+            for i_pb in 0..1000 {
+                y = pbeta_raw(xinbta, pp, qq, true, true);
+                // Newton step for L(u) = log F(e^u) = la:
+                // (L - la) * exp(L - u - log f(e^u))
+                let w = if y == f64::NEG_INFINITY {
+                    0.0
+                } else {
+                    (y - la) * (y - u + logbeta + r * u + t * log1_exp(u)).exp()
+                };
+                if !w.is_finite() {
+                    break;
+                }
+                if i_pb >= N_NEWTON_FREE && w * wprev <= 0.0 {
+                    prev = adj.abs().max(FPU);
+                }
+                let mut g = 1.0;
+                for _ in 0..1000 {
+                    adj = g * w;
+                    // Full Newton steps at the beginning, only then safeguard
+                    if i_pb < N_NEWTON_FREE || adj.abs() < prev {
+                        u_n = u - adj;
+                        if u_n <= 0.0 {
+                            if prev <= acu || w.abs() <= acu {
+                                break 'ret;
+                            }
+                            break;
+                        }
+                    }
+                    g /= 3.0;
+                }
+                // Cancellation in (u_n - u) may make it differ from adj
+                let d = adj.abs().min((u_n - u).abs());
+                if d <= 4e-16 * (u_n + u).abs() {
+                    break 'ret;
+                }
+                u = u_n;
+                xinbta = u.exp();
+                wprev = w;
+            }
+        } else {
+            for i_pb in 0..1000 {
+                y = pbeta_raw(xinbta, pp, qq, true, log_p);
+                if !(y.is_finite() || (log_p && y == f64::NEG_INFINITY)) {
+                    return f64::NAN;
+                }
+                // Newton step (F - a) / f, or on the log scale
+                // (log F - la) * F / f
+                let w = if log_p {
+                    (y - la) * (y + logbeta + r * xinbta.ln() + t * (-xinbta).ln_1p()).exp()
+                } else {
+                    (y - a) * (logbeta + r * xinbta.ln() + t * (-xinbta).ln_1p()).exp()
+                };
+                if i_pb >= N_NEWTON_FREE && w * wprev <= 0.0 {
+                    prev = adj.abs().max(FPU);
+                }
+                let mut g = 1.0;
+                for _ in 0..1000 {
+                    adj = g * w;
+                    if i_pb < N_NEWTON_FREE || adj.abs() < prev {
+                        tx = xinbta - adj;
+                        if (0.0..=1.0).contains(&tx) {
+                            if prev <= acu || w.abs() <= acu {
+                                break 'ret;
+                            }
+                            if tx != 0.0 && tx != 1.0 {
+                                break;
+                            }
+                        }
+                    }
+                    g /= 3.0;
+                }
+                if (tx - xinbta).abs() <= 4e-16 * (xinbta + tx) {
+                    break 'ret;
+                }
+                xinbta = tx;
+                if tx == 0.0 {
+                    // We have lost
+                    break;
+                }
+                wprev = w;
+            }
+        }
+        // Not converged within the iteration limit: return the last iterate
+    }
+
+    let log_ = log_p || use_log_x;
+    if (log_ && y == f64::NEG_INFINITY) || (!log_ && y == 0.0) {
+        // Stuck at the left border: the smallest positive number is better
+        let w = pbeta_raw(DBL_VERY_MIN, pp, qq, true, log_);
+        if log_ || (w - a).abs() <= (y - a).abs() {
+            tx = DBL_VERY_MIN;
+            u_n = DBL_LOG_V_MIN;
+        }
+        add_n_step = false;
+    }
+    finish(tx, u_n, use_log_x, add_n_step, swap_tail, pp, qq, a, la, logbeta, log_p)
+}
+
+/// Map the iterate back to `x`, undoing the tail swap
+///
+/// After iterating on `log(x)` one last Newton step is taken on the
+/// original scale unless the iterate is pinned at the left border.
+#[allow(clippy::too_many_arguments)]
+fn finish(
+    tx: f64,
+    u_n: f64,
+    use_log_x: bool,
+    add_n_step: bool,
+    swap_tail: bool,
+    pp: f64,
+    qq: f64,
+    a: f64,
+    la: f64,
+    logbeta: f64,
+    log_p: bool,
+) -> f64 {
+    let mut tx = tx;
+    if use_log_x {
+        if add_n_step {
+            let xinbta = u_n.exp();
+            let y = pbeta_raw(xinbta, pp, qq, true, log_p);
+            let (r, t) = (1.0 - pp, 1.0 - qq);
+            let w = if log_p {
+                (y - la) * (y + logbeta + r * xinbta.ln() + t * (-xinbta).ln_1p()).exp()
+            } else {
+                (y - a) * (logbeta + r * xinbta.ln() + t * (-xinbta).ln_1p()).exp()
+            };
+            tx = xinbta - w;
+        } else {
+            return if swap_tail { -u_n.exp_m1() } else { u_n.exp() };
+        }
+    }
+    if swap_tail { 1.0 - tx } else { tx }
 }
 
 pub fn rbeta<R: Rng>(shape1: f64, shape2: f64, rng: &mut R) -> f64 {
@@ -75,11 +412,9 @@ mod tests {
     #[test]
     fn test_qbeta() {
         // Test with shape1=2, shape2=3
-        // Note: R gives exactly 0.5, but statrs inverse_cdf has slight numerical error
-        // The actual value is 0.500030517578125 which is acceptable within tolerance
         let expected = 0.5; // qbeta(0.6875, 2, 3) in R
         let actual = qbeta(0.6875, 2.0, 3.0, true, false);
-        assert!((actual - expected).abs() < 5e-5); // Adjusted tolerance for statrs numerical precision
+        assert!((actual - expected).abs() < 1e-14);
     }
 
     #[test]
@@ -104,7 +439,29 @@ mod tests {
         let p = (0.6875f64).ln();
         let expected = 0.5;
         let actual = qbeta(p, 2.0, 3.0, true, true);
-        // Same numerical precision issue as test_qbeta
-        assert!((actual - expected).abs() < 5e-5); // Adjusted tolerance for statrs numerical precision
+        assert!((actual - expected).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_pbeta_extreme_tails() {
+        // Reference values from mpmath
+        let up = pbeta(0.999999, 2.0, 3.0, false, false);
+        assert!((up / 3.999997e-18 - 1.0).abs() < 1e-9);
+        let lp = pbeta(0.2, 500.0, 5000.0, false, true);
+        assert!((lp / -249.19699685362271 - 1.0).abs() < 1e-13);
+        let lp = pbeta(0.7, 5000.0, 30.0, true, true);
+        assert!((lp / -1642.4479215494326 - 1.0).abs() < 1e-13);
+    }
+
+    #[test]
+    fn test_qbeta_extreme_tails() {
+        let q = qbeta(-1642.4479215494326, 5000.0, 30.0, true, true);
+        assert!((q - 0.7).abs() < 1e-14);
+        let q = qbeta(-249.19699685362271, 500.0, 5000.0, false, true);
+        assert!((q - 0.2).abs() < 1e-14);
+        // Round trip far in the lower tail
+        let p = 1e-200;
+        let q = qbeta(p, 2.5, 40.0, true, false);
+        assert!((pbeta(q, 2.5, 40.0, true, false) / p - 1.0).abs() < 1e-13);
     }
 }
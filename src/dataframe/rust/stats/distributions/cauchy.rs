@@ -0,0 +1,193 @@
+//! Cauchy distribution functions
+//!
+//! Ports of R's `nmath/dcauchy.c`, `pcauchy.c`, `qcauchy.c` and
+//! `rcauchy.c`. The CDF switches to `atan(1/x)` in the tails so that tiny
+//! tail probabilities keep full relative accuracy.
+
+use super::helpers::dpq::{d_exp, dt_0, dt_1, q_p01_invalid};
+use rand::Rng;
+use rand::distributions::Open01;
+use std::f64::consts::PI;
+
+/// Cauchy probability density function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate density
+/// * `location` - Location (median) of the distribution
+/// * `scale` - Scale of the distribution (must be positive)
+/// * `give_log` - If true, return log density
+///
+/// # Returns
+/// The Cauchy density or log density
+pub fn dcauchy(x: f64, location: f64, scale: f64, give_log: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() {
+        return x + location + scale;
+    }
+    if scale <= 0.0 {
+        return f64::NAN;
+    }
+    let y = (x - location) / scale;
+    d_exp(-(PI * scale * (1.0 + y * y)).ln(), give_log)
+}
+
+/// Cauchy cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate CDF
+/// * `location` - Location (median) of the distribution
+/// * `scale` - Scale of the distribution (must be positive)
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn pcauchy(x: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() {
+        return x + location + scale;
+    }
+    if scale <= 0.0 {
+        return f64::NAN;
+    }
+    let mut x = (x - location) / scale;
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    if x.is_infinite() {
+        return if x < 0.0 {
+            dt_0(lower_tail, log_p)
+        } else {
+            dt_1(lower_tail, log_p)
+        };
+    }
+    if !lower_tail {
+        x = -x;
+    }
+    let val = |p: f64| if log_p { p.ln() } else { p };
+    if x.abs() > 1.0 {
+        let y = (1.0 / x).atan() / PI;
+        if x > 0.0 {
+            // 1 - y, computed accurately on the log scale
+            if log_p { (-y).ln_1p() } else { 0.5 - y + 0.5 }
+        } else {
+            val(-y)
+        }
+    } else {
+        val(0.5 + x.atan() / PI)
+    }
+}
+
+/// Cauchy quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `location` - Location (median) of the distribution
+/// * `scale` - Scale of the distribution (zero gives a point mass)
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The quantile value
+pub fn qcauchy(p: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || location.is_nan() || scale.is_nan() {
+        return p + location + scale;
+    }
+    if q_p01_invalid(p, log_p) || scale < 0.0 || !scale.is_finite() {
+        return f64::NAN;
+    }
+    if scale == 0.0 {
+        return location;
+    }
+    let mut p = p;
+    let mut lower_tail = lower_tail;
+    let signed_scale = |lower: bool| if lower { scale } else { -scale };
+    if log_p {
+        if p > -1.0 {
+            // When p = 0 the quantile is infinite, so return directly
+            if p == 0.0 {
+                return location + signed_scale(lower_tail) * f64::INFINITY;
+            }
+            lower_tail = !lower_tail;
+            p = -p.exp_m1();
+        } else {
+            p = p.exp();
+        }
+    } else if p > 0.5 {
+        if p == 1.0 {
+            return location + signed_scale(lower_tail) * f64::INFINITY;
+        }
+        p = 1.0 - p;
+        lower_tail = !lower_tail;
+    }
+    if p == 0.5 {
+        return location;
+    }
+    if p == 0.0 {
+        return location + signed_scale(lower_tail) * f64::NEG_INFINITY;
+    }
+    location - signed_scale(lower_tail) / (PI * p).tan()
+}
+
+/// Cauchy random number generation
+///
+/// # Arguments
+/// * `location` - Location (median) of the distribution
+/// * `scale` - Scale of the distribution (must be non-negative)
+/// * `rng` - Random number generator
+///
+/// # Returns
+/// A random sample from the Cauchy distribution
+pub fn rcauchy<R: Rng>(location: f64, scale: f64, rng: &mut R) -> f64 {
+    if location.is_nan() || !scale.is_finite() || scale < 0.0 {
+        return f64::NAN;
+    }
+    if scale == 0.0 || !location.is_finite() {
+        return location;
+    }
+    let u: f64 = rng.sample(Open01);
+    location + scale * (PI * u).tan()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_dcauchy() {
+        assert!((dcauchy(0.0, 0.0, 1.0, false) - std::f64::consts::FRAC_1_PI).abs() < 1e-15);
+        assert!((dcauchy(3.0, 1.0, 2.0, false) - 1.0 / (4.0 * PI)).abs() < 1e-15);
+        assert!((dcauchy(0.0, 0.0, 1.0, true) + PI.ln()).abs() < 1e-15);
+        assert!(dcauchy(0.0, 0.0, 0.0, false).is_nan());
+    }
+
+    #[test]
+    fn test_pcauchy() {
+        assert!((pcauchy(1.0, 0.0, 1.0, true, false) - 0.75).abs() < 1e-15);
+        assert!((pcauchy(1.0, 0.0, 1.0, false, false) - 0.25).abs() < 1e-15);
+        // R: pcauchy(-1e10, log.p = TRUE) = -24.17058081578986
+        assert!((pcauchy(-1e10, 0.0, 1.0, true, true) + 24.17058081578986).abs() < 1e-12);
+        assert!((pcauchy(1e10, 0.0, 1.0, false, true) + 24.17058081578986).abs() < 1e-12);
+        assert_eq!(pcauchy(f64::INFINITY, 0.0, 1.0, true, false), 1.0);
+    }
+
+    #[test]
+    fn test_qcauchy() {
+        assert!((qcauchy(0.75, 0.0, 1.0, true, false) - 1.0).abs() < 1e-14);
+        assert!((qcauchy(0.25, 2.0, 3.0, false, false) - 5.0).abs() < 1e-14);
+        assert_eq!(qcauchy(0.5, 2.0, 3.0, true, false), 2.0);
+        assert_eq!(qcauchy(1.0, 0.0, 1.0, true, false), f64::INFINITY);
+        assert_eq!(qcauchy(0.0, 0.0, 1.0, true, false), f64::NEG_INFINITY);
+        // Deep log-scale tail round-trips
+        let lp = pcauchy(-1e10, 0.0, 1.0, true, true);
+        assert!((qcauchy(lp, 0.0, 1.0, true, true) / -1e10 - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rcauchy() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let n = 20_000;
+        let below = (0..n).filter(|_| rcauchy(1.0, 2.0, &mut rng) < 1.0).count();
+        assert!((below as f64 / n as f64 - 0.5).abs() < 0.02);
+    }
+}
@@ -1,7 +1,11 @@
-//! Chi-squared distribution wrapper using statrs
+//! Chi-squared distribution
+//!
+//! Distribution and quantile functions go through the nmath ports in
+//! `gamma` (chi-squared with `df` degrees of freedom is Gamma(df/2, rate 1/2)),
+//! so tails and `log_p` results are accurate; density and sampling use statrs.
 
 use rand::Rng;
-use statrs::distribution::{ChiSquared, Continuous, ContinuousCDF};
+use statrs::distribution::{ChiSquared, Continuous};
 
 pub fn dchisq(x: f64, df: f64, give_log: bool) -> f64 {
     if df <= 0.0 {
@@ -19,9 +23,9 @@ pub fn pchisq(x: f64, df: f64, lower_tail: bool, log_p: bool) -> f64 {
     if df <= 0.0 {
         return f64::NAN;
     }
-    let dist = ChiSquared::new(df).expect("validated parameters: df > 0");
-    let cdf = if lower_tail { dist.cdf(x) } else { 1.0 - dist.cdf(x) };
-    if log_p { cdf.ln() } else { cdf }
+    // Chi-squared is Gamma(df/2, rate = 1/2); both tails come straight from
+    // the incomplete gamma function
+    super::gamma::pgamma(x, 0.5 * df, 0.5, lower_tail, log_p)
 }
 
 pub fn qchisq(p: f64, df: f64, lower_tail: bool, log_p: bool) -> f64 {
@@ -98,4 +102,15 @@ mod tests {
         let expected = 1.0;
         assert!((qchisq(p, 3.0, true, true) - expected).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_chisq_extreme_tails() {
+        // Reference values from mpmath
+        let up = pchisq(1000.0, 3.0, false, false);
+        assert!((up / 1.7994208765314477e-216 - 1.0).abs() < 1e-13);
+        let lp = pchisq(1000.0, 3.0, false, true);
+        assert!((lp / -496.77091520883995 - 1.0).abs() < 1e-14);
+        let q = qchisq(1e-200, 5.0, false, false);
+        assert!((q / 938.925_826_237_349_3 - 1.0).abs() < 1e-13);
+    }
 }
@@ -0,0 +1,109 @@
+//! Point-mass (Dirac) distribution functions
+//!
+//! All probability sits at `location`. Useful as a degenerate component in
+//! mixtures and for simulating constants through the same d/p/q/r interface.
+
+use super::helpers::dpq::{d_0, d_1, dt_0, dt_1, q_p01_invalid};
+use rand::Rng;
+
+/// Point-mass probability mass function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate the probability
+/// * `location` - Location of the point mass
+/// * `give_log` - If true, return log probability
+///
+/// # Returns
+/// 1 at `location` and 0 elsewhere (on the requested scale)
+pub fn ddirac(x: f64, location: f64, give_log: bool) -> f64 {
+    if x.is_nan() || location.is_nan() {
+        return x + location;
+    }
+    if !location.is_finite() {
+        return f64::NAN;
+    }
+    if x == location {
+        d_1(give_log)
+    } else {
+        d_0(give_log)
+    }
+}
+
+/// Point-mass cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate CDF
+/// * `location` - Location of the point mass
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn pdirac(x: f64, location: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || location.is_nan() {
+        return x + location;
+    }
+    if !location.is_finite() {
+        return f64::NAN;
+    }
+    if x >= location {
+        dt_1(lower_tail, log_p)
+    } else {
+        dt_0(lower_tail, log_p)
+    }
+}
+
+/// Point-mass quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `location` - Location of the point mass
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// `location` for every valid probability
+pub fn qdirac(p: f64, location: f64, _lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || location.is_nan() {
+        return p + location;
+    }
+    if !location.is_finite() || q_p01_invalid(p, log_p) {
+        return f64::NAN;
+    }
+    location
+}
+
+/// Point-mass random number generation
+///
+/// # Arguments
+/// * `location` - Location of the point mass
+/// * `_rng` - Random number generator (unused; kept for a uniform interface)
+///
+/// # Returns
+/// `location`
+pub fn rdirac<R: Rng>(location: f64, _rng: &mut R) -> f64 {
+    if !location.is_finite() {
+        return f64::NAN;
+    }
+    location
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_dirac() {
+        assert_eq!(ddirac(2.0, 2.0, false), 1.0);
+        assert_eq!(ddirac(2.1, 2.0, true), f64::NEG_INFINITY);
+        assert_eq!(pdirac(1.9, 2.0, true, false), 0.0);
+        assert_eq!(pdirac(2.0, 2.0, true, false), 1.0);
+        assert_eq!(pdirac(2.0, 2.0, false, true), f64::NEG_INFINITY);
+        assert_eq!(qdirac(0.3, 2.0, true, false), 2.0);
+        assert!(qdirac(1.3, 2.0, true, false).is_nan());
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(rdirac(2.0, &mut rng), 2.0);
+    }
+}
@@ -3,9 +3,26 @@
 #![cfg(feature = "wasm")]
 
 use super::*;
-use rand::thread_rng;
 use wasm_bindgen::prelude::*;
 
+// ============================================================================
+// RANDOM NUMBER GENERATOR STATE
+// ============================================================================
+
+/// WASM export for seeding the shared generator (R's `set.seed`)
+///
+/// `kind` is "Mersenne-Twister" (default, R-compatible) or "xoshiro256++";
+/// when omitted the current generator is kept.
+#[wasm_bindgen]
+pub fn wasm_set_seed(seed: u32, kind: Option<String>) -> Result<(), JsValue> {
+    let kind = kind
+        .map(|k| RngKind::parse(&k))
+        .transpose()
+        .map_err(|e| JsValue::from_str(&format!("RNG error: {}", e)))?;
+    set_seed(seed, kind);
+    Ok(())
+}
+
 // ============================================================================
 // BETA DISTRIBUTION
 // ============================================================================
@@ -31,8 +48,13 @@ pub fn wasm_qbeta(p: f64, shape1: f64, shape2: f64, lower_tail: bool, log_p: boo
 /// WASM export for beta random number generation
 #[wasm_bindgen]
 pub fn wasm_rbeta(shape1: f64, shape2: f64) -> f64 {
-    let mut rng = thread_rng();
-    rbeta(shape1, shape2, &mut rng)
+    with_rng(|rng| rbeta(shape1, shape2, rng))
+}
+
+/// WASM export for `count` beta random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rbeta_n(count: usize, shape1: f64, shape2: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rbeta(shape1, shape2, rng)).collect())
 }
 
 // ============================================================================
@@ -60,8 +82,13 @@ pub fn wasm_qnorm(p: f64, mean: f64, sd: f64, lower_tail: bool, log_p: bool) ->
 /// WASM export for normal random number generation
 #[wasm_bindgen]
 pub fn wasm_rnorm(mean: f64, sd: f64) -> f64 {
-    let mut rng = thread_rng();
-    rnorm(mean, sd, &mut rng)
+    with_rng(|rng| rng.normal(mean, sd))
+}
+
+/// WASM export for `count` normal random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rnorm_n(count: usize, mean: f64, sd: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rng.normal(mean, sd)).collect())
 }
 
 // ============================================================================
@@ -89,8 +116,13 @@ pub fn wasm_qgamma(p: f64, shape: f64, rate: f64, lower_tail: bool, log_p: bool)
 /// WASM export for gamma random number generation
 #[wasm_bindgen]
 pub fn wasm_rgamma(shape: f64, rate: f64) -> f64 {
-    let mut rng = thread_rng();
-    rgamma(shape, rate, &mut rng)
+    with_rng(|rng| rgamma(shape, rate, rng))
+}
+
+/// WASM export for `count` gamma random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rgamma_n(count: usize, shape: f64, rate: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rgamma(shape, rate, rng)).collect())
 }
 
 // ============================================================================
@@ -118,8 +150,13 @@ pub fn wasm_qexp(p: f64, rate: f64, lower_tail: bool, log_p: bool) -> f64 {
 /// WASM export for exponential random number generation
 #[wasm_bindgen]
 pub fn wasm_rexp(rate: f64) -> f64 {
-    let mut rng = thread_rng();
-    rexp(rate, &mut rng)
+    with_rng(|rng| rexp(rate, rng))
+}
+
+/// WASM export for `count` exponential random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rexp_n(count: usize, rate: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rexp(rate, rng)).collect())
 }
 
 // ============================================================================
@@ -147,8 +184,13 @@ pub fn wasm_qchisq(p: f64, df: f64, lower_tail: bool, log_p: bool) -> f64 {
 /// WASM export for chi-squared random number generation
 #[wasm_bindgen]
 pub fn wasm_rchisq(df: f64) -> f64 {
-    let mut rng = thread_rng();
-    rchisq(df, &mut rng)
+    with_rng(|rng| rchisq(df, rng))
+}
+
+/// WASM export for `count` chi-squared random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rchisq_n(count: usize, df: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rchisq(df, rng)).collect())
 }
 
 // ============================================================================
@@ -176,8 +218,13 @@ pub fn wasm_qf(p: f64, df1: f64, df2: f64, lower_tail: bool, log_p: bool) -> f64
 /// WASM export for F distribution random number generation
 #[wasm_bindgen]
 pub fn wasm_rf(df1: f64, df2: f64) -> f64 {
-    let mut rng = thread_rng();
-    rf(df1, df2, &mut rng)
+    with_rng(|rng| rf(df1, df2, rng))
+}
+
+/// WASM export for `count` F distribution random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rf_n(count: usize, df1: f64, df2: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rf(df1, df2, rng)).collect())
 }
 
 // ============================================================================
@@ -205,8 +252,13 @@ pub fn wasm_qt(p: f64, df: f64, lower_tail: bool, log_p: bool) -> f64 {
 /// WASM export for t distribution random number generation
 #[wasm_bindgen]
 pub fn wasm_rt(df: f64) -> f64 {
-    let mut rng = thread_rng();
-    rt(df, &mut rng)
+    with_rng(|rng| rt(df, rng))
+}
+
+/// WASM export for `count` t distribution random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rt_n(count: usize, df: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rt(df, rng)).collect())
 }
 
 // ============================================================================
@@ -234,8 +286,13 @@ pub fn wasm_qpois(p: f64, lambda: f64, lower_tail: bool, log_p: bool) -> f64 {
 /// WASM export for Poisson random number generation
 #[wasm_bindgen]
 pub fn wasm_rpois(lambda: f64) -> f64 {
-    let mut rng = thread_rng();
-    rpois(lambda, &mut rng)
+    with_rng(|rng| rpois(lambda, rng))
+}
+
+/// WASM export for `count` Poisson random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rpois_n(count: usize, lambda: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rpois(lambda, rng)).collect())
 }
 
 // ============================================================================
@@ -263,8 +320,13 @@ pub fn wasm_qbinom(p: f64, size: f64, prob: f64, lower_tail: bool, log_p: bool)
 /// WASM export for binomial random number generation
 #[wasm_bindgen]
 pub fn wasm_rbinom(size: f64, prob: f64) -> f64 {
-    let mut rng = thread_rng();
-    rbinom(size, prob, &mut rng)
+    with_rng(|rng| rbinom(size, prob, rng))
+}
+
+/// WASM export for `count` binomial random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rbinom_n(count: usize, size: f64, prob: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rbinom(size, prob, rng)).collect())
 }
 
 // ============================================================================
@@ -292,8 +354,13 @@ pub fn wasm_qunif(p: f64, min: f64, max: f64, lower_tail: bool, log_p: bool) ->
 /// WASM export for uniform random number generation
 #[wasm_bindgen]
 pub fn wasm_runif(min: f64, max: f64) -> f64 {
-    let mut rng = thread_rng();
-    runif(min, max, &mut rng)
+    with_rng(|rng| rng.uniform(min, max))
+}
+
+/// WASM export for `count` uniform random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_runif_n(count: usize, min: f64, max: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rng.uniform(min, max)).collect())
 }
 
 // ============================================================================
@@ -321,8 +388,13 @@ pub fn wasm_qweibull(p: f64, shape: f64, scale: f64, lower_tail: bool, log_p: bo
 /// WASM export for Weibull random number generation
 #[wasm_bindgen]
 pub fn wasm_rweibull(shape: f64, scale: f64) -> f64 {
-    let mut rng = thread_rng();
-    rweibull(shape, scale, &mut rng)
+    with_rng(|rng| rweibull(shape, scale, rng))
+}
+
+/// WASM export for `count` Weibull random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rweibull_n(count: usize, shape: f64, scale: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rweibull(shape, scale, rng)).collect())
 }
 
 // ============================================================================
@@ -350,8 +422,13 @@ pub fn wasm_qgeom(p: f64, prob: f64, lower_tail: bool, log_p: bool) -> f64 {
 /// WASM export for geometric random number generation
 #[wasm_bindgen]
 pub fn wasm_rgeom(prob: f64) -> f64 {
-    let mut rng = thread_rng();
-    rgeom(prob, &mut rng)
+    with_rng(|rng| rgeom(prob, rng))
+}
+
+/// WASM export for `count` geometric random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rgeom_n(count: usize, prob: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rgeom(prob, rng)).collect())
 }
 
 // ============================================================================
@@ -379,8 +456,13 @@ pub fn wasm_qhyper(p: f64, m: f64, n: f64, k: f64, lower_tail: bool, log_p: bool
 /// WASM export for hypergeometric random number generation
 #[wasm_bindgen]
 pub fn wasm_rhyper(m: f64, n: f64, k: f64) -> f64 {
-    let mut rng = thread_rng();
-    rhyper(m, n, k, &mut rng)
+    with_rng(|rng| rhyper(m, n, k, rng))
+}
+
+/// WASM export for `count` hypergeometric random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rhyper_n(count: usize, m: f64, n: f64, k: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rhyper(m, n, k, rng)).collect())
 }
 
 // ============================================================================
@@ -408,8 +490,13 @@ pub fn wasm_qlnorm(p: f64, meanlog: f64, sdlog: f64, lower_tail: bool, log_p: bo
 /// WASM export for log-normal random number generation
 #[wasm_bindgen]
 pub fn wasm_rlnorm(meanlog: f64, sdlog: f64) -> f64 {
-    let mut rng = thread_rng();
-    rlnorm(meanlog, sdlog, &mut rng)
+    with_rng(|rng| rlnorm(meanlog, sdlog, rng))
+}
+
+/// WASM export for `count` log-normal random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rlnorm_n(count: usize, meanlog: f64, sdlog: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rlnorm(meanlog, sdlog, rng)).collect())
 }
 
 // ============================================================================
@@ -437,8 +524,13 @@ pub fn wasm_qnbinom(p: f64, r: f64, prob: f64, lower_tail: bool, log_p: bool) ->
 /// WASM export for negative binomial random number generation
 #[wasm_bindgen]
 pub fn wasm_rnbinom(r: f64, prob: f64) -> f64 {
-    let mut rng = thread_rng();
-    rnbinom(r, prob, &mut rng)
+    with_rng(|rng| rnbinom(r, prob, rng))
+}
+
+/// WASM export for `count` negative binomial random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rnbinom_n(count: usize, r: f64, prob: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rnbinom(r, prob, rng)).collect())
 }
 
 // ============================================================================
@@ -466,6 +558,853 @@ pub fn wasm_qwilcox(p: f64, m: f64, n: f64, lower_tail: bool, log_p: bool) -> f6
 /// WASM export for Wilcoxon random number generation
 #[wasm_bindgen]
 pub fn wasm_rwilcox(m: f64, n: f64) -> f64 {
-    let mut rng = thread_rng();
-    rwilcox(m, n, &mut rng)
+    with_rng(|rng| rwilcox(m, n, rng))
+}
+
+/// WASM export for `count` Wilcoxon random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rwilcox_n(count: usize, m: f64, n: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rwilcox(m, n, rng)).collect())
+}
+
+// ============================================================================
+// GUMBEL DISTRIBUTION
+// ============================================================================
+
+/// WASM export for Gumbel density function
+#[wasm_bindgen]
+pub fn wasm_dgumbel(x: f64, location: f64, scale: f64, give_log: bool) -> f64 {
+    dgumbel(x, location, scale, give_log)
+}
+
+/// WASM export for Gumbel cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pgumbel(x: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    pgumbel(x, location, scale, lower_tail, log_p)
+}
+
+/// WASM export for Gumbel quantile function
+#[wasm_bindgen]
+pub fn wasm_qgumbel(p: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qgumbel(p, location, scale, lower_tail, log_p)
+}
+
+/// WASM export for Gumbel random number generation
+#[wasm_bindgen]
+pub fn wasm_rgumbel(location: f64, scale: f64) -> f64 {
+    with_rng(|rng| rgumbel(location, scale, rng))
+}
+
+/// WASM export for `count` Gumbel random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rgumbel_n(count: usize, location: f64, scale: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rgumbel(location, scale, rng)).collect())
+}
+
+// ============================================================================
+// GENERALIZED EXTREME VALUE DISTRIBUTION
+// ============================================================================
+
+/// WASM export for GEV density function
+#[wasm_bindgen]
+pub fn wasm_dgev(x: f64, location: f64, scale: f64, shape: f64, give_log: bool) -> f64 {
+    dgev(x, location, scale, shape, give_log)
+}
+
+/// WASM export for GEV cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pgev(
+    x: f64,
+    location: f64,
+    scale: f64,
+    shape: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    pgev(x, location, scale, shape, lower_tail, log_p)
+}
+
+/// WASM export for GEV quantile function
+#[wasm_bindgen]
+pub fn wasm_qgev(
+    p: f64,
+    location: f64,
+    scale: f64,
+    shape: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    qgev(p, location, scale, shape, lower_tail, log_p)
+}
+
+/// WASM export for GEV random number generation
+#[wasm_bindgen]
+pub fn wasm_rgev(location: f64, scale: f64, shape: f64) -> f64 {
+    with_rng(|rng| rgev(location, scale, shape, rng))
+}
+
+/// WASM export for `count` GEV random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rgev_n(count: usize, location: f64, scale: f64, shape: f64) -> Vec<f64> {
+    with_rng(|rng| {
+        (0..count)
+            .map(|_| rgev(location, scale, shape, rng))
+            .collect()
+    })
+}
+
+// ============================================================================
+// GENERALIZED PARETO DISTRIBUTION
+// ============================================================================
+
+/// WASM export for generalized Pareto density function
+#[wasm_bindgen]
+pub fn wasm_dgpd(x: f64, location: f64, scale: f64, shape: f64, give_log: bool) -> f64 {
+    dgpd(x, location, scale, shape, give_log)
+}
+
+/// WASM export for generalized Pareto cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pgpd(
+    x: f64,
+    location: f64,
+    scale: f64,
+    shape: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    pgpd(x, location, scale, shape, lower_tail, log_p)
+}
+
+/// WASM export for generalized Pareto quantile function
+#[wasm_bindgen]
+pub fn wasm_qgpd(
+    p: f64,
+    location: f64,
+    scale: f64,
+    shape: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    qgpd(p, location, scale, shape, lower_tail, log_p)
+}
+
+/// WASM export for generalized Pareto random number generation
+#[wasm_bindgen]
+pub fn wasm_rgpd(location: f64, scale: f64, shape: f64) -> f64 {
+    with_rng(|rng| rgpd(location, scale, shape, rng))
+}
+
+/// WASM export for `count` generalized Pareto random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rgpd_n(count: usize, location: f64, scale: f64, shape: f64) -> Vec<f64> {
+    with_rng(|rng| {
+        (0..count)
+            .map(|_| rgpd(location, scale, shape, rng))
+            .collect()
+    })
+}
+
+// ============================================================================
+// PARETO DISTRIBUTION
+// ============================================================================
+
+/// WASM export for Pareto density function
+#[wasm_bindgen]
+pub fn wasm_dpareto(x: f64, scale: f64, shape: f64, give_log: bool) -> f64 {
+    dpareto(x, scale, shape, give_log)
+}
+
+/// WASM export for Pareto cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_ppareto(x: f64, scale: f64, shape: f64, lower_tail: bool, log_p: bool) -> f64 {
+    ppareto(x, scale, shape, lower_tail, log_p)
+}
+
+/// WASM export for Pareto quantile function
+#[wasm_bindgen]
+pub fn wasm_qpareto(p: f64, scale: f64, shape: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qpareto(p, scale, shape, lower_tail, log_p)
+}
+
+/// WASM export for Pareto random number generation
+#[wasm_bindgen]
+pub fn wasm_rpareto(scale: f64, shape: f64) -> f64 {
+    with_rng(|rng| rpareto(scale, shape, rng))
+}
+
+/// WASM export for `count` Pareto random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rpareto_n(count: usize, scale: f64, shape: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rpareto(scale, shape, rng)).collect())
+}
+
+// ============================================================================
+// ZIPF-MANDELBROT DISTRIBUTION
+// ============================================================================
+
+/// WASM export for Zipf–Mandelbrot density function
+#[wasm_bindgen]
+pub fn wasm_dzipf(x: f64, n: f64, s: f64, q: f64, give_log: bool) -> f64 {
+    dzipf(x, n, s, q, give_log)
+}
+
+/// WASM export for Zipf–Mandelbrot cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pzipf(x: f64, n: f64, s: f64, q: f64, lower_tail: bool, log_p: bool) -> f64 {
+    pzipf(x, n, s, q, lower_tail, log_p)
+}
+
+/// WASM export for Zipf–Mandelbrot quantile function
+#[wasm_bindgen]
+pub fn wasm_qzipf(p: f64, n: f64, s: f64, q: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qzipf(p, n, s, q, lower_tail, log_p)
+}
+
+/// WASM export for Zipf–Mandelbrot random number generation
+#[wasm_bindgen]
+pub fn wasm_rzipf(n: f64, s: f64, q: f64) -> f64 {
+    with_rng(|rng| rzipf(n, s, q, rng))
+}
+
+/// WASM export for `count` Zipf–Mandelbrot random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rzipf_n(count: usize, n: f64, s: f64, q: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rzipf(n, s, q, rng)).collect())
+}
+
+// ============================================================================
+// POINT-MASS (DIRAC) DISTRIBUTION
+// ============================================================================
+
+/// WASM export for point-mass density function
+#[wasm_bindgen]
+pub fn wasm_ddirac(x: f64, location: f64, give_log: bool) -> f64 {
+    ddirac(x, location, give_log)
+}
+
+/// WASM export for point-mass cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pdirac(x: f64, location: f64, lower_tail: bool, log_p: bool) -> f64 {
+    pdirac(x, location, lower_tail, log_p)
+}
+
+/// WASM export for point-mass quantile function
+#[wasm_bindgen]
+pub fn wasm_qdirac(p: f64, location: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qdirac(p, location, lower_tail, log_p)
+}
+
+/// WASM export for point-mass random number generation
+#[wasm_bindgen]
+pub fn wasm_rdirac(location: f64) -> f64 {
+    with_rng(|rng| rdirac(location, rng))
+}
+
+/// WASM export for `count` point-mass random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rdirac_n(count: usize, location: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rdirac(location, rng)).collect())
+}
+
+// ============================================================================
+// CAUCHY DISTRIBUTION
+// ============================================================================
+
+/// WASM export for Cauchy density function
+#[wasm_bindgen]
+pub fn wasm_dcauchy(x: f64, location: f64, scale: f64, give_log: bool) -> f64 {
+    dcauchy(x, location, scale, give_log)
+}
+
+/// WASM export for Cauchy cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pcauchy(x: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    pcauchy(x, location, scale, lower_tail, log_p)
+}
+
+/// WASM export for Cauchy quantile function
+#[wasm_bindgen]
+pub fn wasm_qcauchy(p: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qcauchy(p, location, scale, lower_tail, log_p)
+}
+
+/// WASM export for Cauchy random number generation
+#[wasm_bindgen]
+pub fn wasm_rcauchy(location: f64, scale: f64) -> f64 {
+    with_rng(|rng| rcauchy(location, scale, rng))
+}
+
+/// WASM export for `count` Cauchy random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rcauchy_n(count: usize, location: f64, scale: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rcauchy(location, scale, rng)).collect())
+}
+
+// ============================================================================
+// LOGISTIC DISTRIBUTION
+// ============================================================================
+
+/// WASM export for logistic density function
+#[wasm_bindgen]
+pub fn wasm_dlogis(x: f64, location: f64, scale: f64, give_log: bool) -> f64 {
+    dlogis(x, location, scale, give_log)
+}
+
+/// WASM export for logistic cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_plogis(x: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    plogis(x, location, scale, lower_tail, log_p)
+}
+
+/// WASM export for logistic quantile function
+#[wasm_bindgen]
+pub fn wasm_qlogis(p: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qlogis(p, location, scale, lower_tail, log_p)
+}
+
+/// WASM export for logistic random number generation
+#[wasm_bindgen]
+pub fn wasm_rlogis(location: f64, scale: f64) -> f64 {
+    with_rng(|rng| rlogis(location, scale, rng))
+}
+
+/// WASM export for `count` logistic random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rlogis_n(count: usize, location: f64, scale: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rlogis(location, scale, rng)).collect())
+}
+
+// ============================================================================
+// LAPLACE DISTRIBUTION
+// ============================================================================
+
+/// WASM export for Laplace density function
+#[wasm_bindgen]
+pub fn wasm_dlaplace(x: f64, location: f64, scale: f64, give_log: bool) -> f64 {
+    dlaplace(x, location, scale, give_log)
+}
+
+/// WASM export for Laplace cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_plaplace(x: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    plaplace(x, location, scale, lower_tail, log_p)
+}
+
+/// WASM export for Laplace quantile function
+#[wasm_bindgen]
+pub fn wasm_qlaplace(p: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qlaplace(p, location, scale, lower_tail, log_p)
+}
+
+/// WASM export for Laplace random number generation
+#[wasm_bindgen]
+pub fn wasm_rlaplace(location: f64, scale: f64) -> f64 {
+    with_rng(|rng| rlaplace(location, scale, rng))
+}
+
+/// WASM export for `count` Laplace random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rlaplace_n(count: usize, location: f64, scale: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rlaplace(location, scale, rng)).collect())
+}
+
+// ============================================================================
+// TRIANGULAR DISTRIBUTION
+// ============================================================================
+
+/// WASM export for triangular density function
+#[wasm_bindgen]
+pub fn wasm_dtriang(x: f64, min: f64, max: f64, mode: f64, give_log: bool) -> f64 {
+    dtriang(x, min, max, mode, give_log)
+}
+
+/// WASM export for triangular cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_ptriang(x: f64, min: f64, max: f64, mode: f64, lower_tail: bool, log_p: bool) -> f64 {
+    ptriang(x, min, max, mode, lower_tail, log_p)
+}
+
+/// WASM export for triangular quantile function
+#[wasm_bindgen]
+pub fn wasm_qtriang(p: f64, min: f64, max: f64, mode: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qtriang(p, min, max, mode, lower_tail, log_p)
+}
+
+/// WASM export for triangular random number generation
+#[wasm_bindgen]
+pub fn wasm_rtriang(min: f64, max: f64, mode: f64) -> f64 {
+    with_rng(|rng| rtriang(min, max, mode, rng))
+}
+
+/// WASM export for `count` triangular random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rtriang_n(count: usize, min: f64, max: f64, mode: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rtriang(min, max, mode, rng)).collect())
+}
+
+// ============================================================================
+// LOCATION-SCALE T DISTRIBUTION
+// ============================================================================
+
+/// WASM export for location-scale t density function
+#[wasm_bindgen]
+pub fn wasm_dlst(x: f64, df: f64, location: f64, scale: f64, give_log: bool) -> f64 {
+    dlst(x, df, location, scale, give_log)
+}
+
+/// WASM export for location-scale t cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_plst(x: f64, df: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    plst(x, df, location, scale, lower_tail, log_p)
+}
+
+/// WASM export for location-scale t quantile function
+#[wasm_bindgen]
+pub fn wasm_qlst(p: f64, df: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qlst(p, df, location, scale, lower_tail, log_p)
+}
+
+/// WASM export for location-scale t random number generation
+#[wasm_bindgen]
+pub fn wasm_rlst(df: f64, location: f64, scale: f64) -> f64 {
+    with_rng(|rng| rlst(df, location, scale, rng))
+}
+
+/// WASM export for `count` location-scale t random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rlst_n(count: usize, df: f64, location: f64, scale: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rlst(df, location, scale, rng)).collect())
+}
+
+// ============================================================================
+// NONCENTRAL T DISTRIBUTION
+// ============================================================================
+
+/// WASM export for noncentral t density function
+#[wasm_bindgen]
+pub fn wasm_dnt(x: f64, df: f64, ncp: f64, give_log: bool) -> f64 {
+    dnt(x, df, ncp, give_log)
+}
+
+/// WASM export for noncentral t cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pnt(x: f64, df: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    pnt(x, df, ncp, lower_tail, log_p)
+}
+
+/// WASM export for noncentral t quantile function
+#[wasm_bindgen]
+pub fn wasm_qnt(p: f64, df: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qnt(p, df, ncp, lower_tail, log_p)
+}
+
+// ============================================================================
+// NONCENTRAL CHI-SQUARED DISTRIBUTION
+// ============================================================================
+
+/// WASM export for noncentral chi-squared density function
+#[wasm_bindgen]
+pub fn wasm_dnchisq(x: f64, df: f64, ncp: f64, give_log: bool) -> f64 {
+    dnchisq(x, df, ncp, give_log)
+}
+
+/// WASM export for noncentral chi-squared cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pnchisq(x: f64, df: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    pnchisq(x, df, ncp, lower_tail, log_p)
+}
+
+/// WASM export for noncentral chi-squared quantile function
+#[wasm_bindgen]
+pub fn wasm_qnchisq(p: f64, df: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qnchisq(p, df, ncp, lower_tail, log_p)
+}
+
+// ============================================================================
+// NONCENTRAL BETA DISTRIBUTION
+// ============================================================================
+
+/// WASM export for noncentral beta density function
+#[wasm_bindgen]
+pub fn wasm_dnbeta(x: f64, shape1: f64, shape2: f64, ncp: f64, give_log: bool) -> f64 {
+    dnbeta(x, shape1, shape2, ncp, give_log)
+}
+
+/// WASM export for noncentral beta cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pnbeta(
+    x: f64,
+    shape1: f64,
+    shape2: f64,
+    ncp: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    pnbeta(x, shape1, shape2, ncp, lower_tail, log_p)
+}
+
+/// WASM export for noncentral beta quantile function
+#[wasm_bindgen]
+pub fn wasm_qnbeta(
+    p: f64,
+    shape1: f64,
+    shape2: f64,
+    ncp: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    qnbeta(p, shape1, shape2, ncp, lower_tail, log_p)
+}
+
+// ============================================================================
+// NONCENTRAL F DISTRIBUTION
+// ============================================================================
+
+/// WASM export for noncentral F density function
+#[wasm_bindgen]
+pub fn wasm_dnf(x: f64, df1: f64, df2: f64, ncp: f64, give_log: bool) -> f64 {
+    dnf(x, df1, df2, ncp, give_log)
+}
+
+/// WASM export for noncentral F cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pnf(x: f64, df1: f64, df2: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    pnf(x, df1, df2, ncp, lower_tail, log_p)
+}
+
+/// WASM export for noncentral F quantile function
+#[wasm_bindgen]
+pub fn wasm_qnf(p: f64, df1: f64, df2: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qnf(p, df1, df2, ncp, lower_tail, log_p)
+}
+
+// ============================================================================
+// MULTIVARIATE DISTRIBUTIONS
+// ============================================================================
+// Matrices are flat row-major arrays; sampled matrices have one row per draw.
+
+fn multivariate_error(e: String) -> JsValue {
+    JsValue::from_str(&format!("Distribution error: {}", e))
+}
+
+/// WASM export for multivariate normal density function
+#[wasm_bindgen]
+pub fn wasm_dmvnorm(
+    x: &[f64],
+    mean: &[f64],
+    sigma: &[f64],
+    give_log: bool,
+) -> Result<f64, JsValue> {
+    dmvnorm(x, mean, sigma, give_log).map_err(multivariate_error)
+}
+
+/// WASM export for the multivariate normal probability of `[lower, upper]`
+///
+/// Returns `[value, error]`; the lattice shifts come from the shared generator.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn wasm_pmvnorm(
+    lower: &[f64],
+    upper: &[f64],
+    mean: &[f64],
+    sigma: &[f64],
+    max_pts: usize,
+    abs_eps: f64,
+    rel_eps: f64,
+) -> Result<Vec<f64>, JsValue> {
+    let options = GenzBretz {
+        max_pts,
+        abs_eps,
+        rel_eps,
+    };
+    let p = with_rng(|rng| pmvnorm(lower, upper, mean, sigma, &options, rng))
+        .map_err(multivariate_error)?;
+    Ok(vec![p.value, p.error])
+}
+
+/// WASM export for `n` multivariate normal draws (`n x d`, row-major)
+#[wasm_bindgen]
+pub fn wasm_rmvnorm(n: usize, mean: &[f64], sigma: &[f64]) -> Result<Vec<f64>, JsValue> {
+    with_rng(|rng| rmvnorm(n, mean, sigma, rng)).map_err(multivariate_error)
+}
+
+/// WASM export for multivariate t density function
+#[wasm_bindgen]
+pub fn wasm_dmvt(
+    x: &[f64],
+    delta: &[f64],
+    sigma: &[f64],
+    df: f64,
+    give_log: bool,
+) -> Result<f64, JsValue> {
+    dmvt(x, delta, sigma, df, give_log).map_err(multivariate_error)
+}
+
+/// WASM export for `n` multivariate t draws (`n x d`, row-major)
+#[wasm_bindgen]
+pub fn wasm_rmvt(n: usize, delta: &[f64], sigma: &[f64], df: f64) -> Result<Vec<f64>, JsValue> {
+    with_rng(|rng| rmvt(n, delta, sigma, df, rng)).map_err(multivariate_error)
+}
+
+/// WASM export for Dirichlet density function
+#[wasm_bindgen]
+pub fn wasm_ddirichlet(x: &[f64], alpha: &[f64], give_log: bool) -> Result<f64, JsValue> {
+    ddirichlet(x, alpha, give_log).map_err(multivariate_error)
+}
+
+/// WASM export for `n` Dirichlet draws (`n x k`, row-major)
+#[wasm_bindgen]
+pub fn wasm_rdirichlet(n: usize, alpha: &[f64]) -> Result<Vec<f64>, JsValue> {
+    with_rng(|rng| rdirichlet(n, alpha, rng)).map_err(multivariate_error)
+}
+
+/// WASM export for multinomial probability mass function
+#[wasm_bindgen]
+pub fn wasm_dmultinom(x: &[f64], prob: &[f64], give_log: bool) -> Result<f64, JsValue> {
+    dmultinom(x, prob, give_log).map_err(multivariate_error)
+}
+
+/// WASM export for `n` multinomial draws (`n x k` counts, row-major)
+#[wasm_bindgen]
+pub fn wasm_rmultinom(n: usize, size: f64, prob: &[f64]) -> Result<Vec<f64>, JsValue> {
+    with_rng(|rng| rmultinom(n, size, prob, rng)).map_err(multivariate_error)
+}
+
+// ============================================================================
+// TRUNCATED NORMAL DISTRIBUTION
+// ============================================================================
+
+/// WASM export for truncated normal density function
+#[wasm_bindgen]
+pub fn wasm_dtruncnorm(x: f64, a: f64, b: f64, mean: f64, sd: f64, give_log: bool) -> f64 {
+    dtruncnorm(x, a, b, mean, sd, give_log)
+}
+
+/// WASM export for truncated normal cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_ptruncnorm(
+    x: f64,
+    a: f64,
+    b: f64,
+    mean: f64,
+    sd: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    ptruncnorm(x, a, b, mean, sd, lower_tail, log_p)
+}
+
+/// WASM export for truncated normal quantile function
+#[wasm_bindgen]
+pub fn wasm_qtruncnorm(
+    p: f64,
+    a: f64,
+    b: f64,
+    mean: f64,
+    sd: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    qtruncnorm(p, a, b, mean, sd, lower_tail, log_p)
+}
+
+/// WASM export for truncated normal random number generation
+#[wasm_bindgen]
+pub fn wasm_rtruncnorm(a: f64, b: f64, mean: f64, sd: f64) -> f64 {
+    with_rng(|rng| rtruncnorm(a, b, mean, sd, rng))
+}
+
+/// WASM export for `count` truncated normal random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rtruncnorm_n(count: usize, a: f64, b: f64, mean: f64, sd: f64) -> Vec<f64> {
+    with_rng(|rng| {
+        (0..count)
+            .map(|_| rtruncnorm(a, b, mean, sd, rng))
+            .collect()
+    })
+}
+
+// ============================================================================
+// TRUNCATED GAMMA AND POISSON DISTRIBUTIONS
+// ============================================================================
+// Invalid parameters or bounds without probability mass give NaN.
+
+/// WASM export for truncated gamma density function
+#[wasm_bindgen]
+pub fn wasm_dtruncgamma(x: f64, shape: f64, rate: f64, a: f64, b: f64, give_log: bool) -> f64 {
+    truncated_gamma(shape, rate, a, b).map_or(f64::NAN, |t| t.d(x, give_log))
+}
+
+/// WASM export for truncated gamma cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_ptruncgamma(
+    x: f64,
+    shape: f64,
+    rate: f64,
+    a: f64,
+    b: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    truncated_gamma(shape, rate, a, b).map_or(f64::NAN, |t| t.p(x, lower_tail, log_p))
+}
+
+/// WASM export for truncated gamma quantile function
+#[wasm_bindgen]
+pub fn wasm_qtruncgamma(
+    p: f64,
+    shape: f64,
+    rate: f64,
+    a: f64,
+    b: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    truncated_gamma(shape, rate, a, b).map_or(f64::NAN, |t| t.q(p, lower_tail, log_p))
+}
+
+/// WASM export for `count` truncated gamma random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rtruncgamma_n(count: usize, shape: f64, rate: f64, a: f64, b: f64) -> Vec<f64> {
+    match truncated_gamma(shape, rate, a, b) {
+        Ok(t) => with_rng(|rng| (0..count).map(|_| t.r(rng)).collect()),
+        Err(_) => vec![f64::NAN; count],
+    }
+}
+
+/// WASM export for truncated Poisson probability mass function
+#[wasm_bindgen]
+pub fn wasm_dtruncpois(x: f64, lambda: f64, a: f64, b: f64, give_log: bool) -> f64 {
+    truncated_poisson(lambda, a, b).map_or(f64::NAN, |t| t.d(x, give_log))
+}
+
+/// WASM export for truncated Poisson cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_ptruncpois(x: f64, lambda: f64, a: f64, b: f64, lower_tail: bool, log_p: bool) -> f64 {
+    truncated_poisson(lambda, a, b).map_or(f64::NAN, |t| t.p(x, lower_tail, log_p))
+}
+
+/// WASM export for truncated Poisson quantile function
+#[wasm_bindgen]
+pub fn wasm_qtruncpois(p: f64, lambda: f64, a: f64, b: f64, lower_tail: bool, log_p: bool) -> f64 {
+    truncated_poisson(lambda, a, b).map_or(f64::NAN, |t| t.q(p, lower_tail, log_p))
+}
+
+/// WASM export for `count` truncated Poisson random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rtruncpois_n(count: usize, lambda: f64, a: f64, b: f64) -> Vec<f64> {
+    match truncated_poisson(lambda, a, b) {
+        Ok(t) => with_rng(|rng| (0..count).map(|_| t.r(rng)).collect()),
+        Err(_) => vec![f64::NAN; count],
+    }
+}
+
+// ============================================================================
+// SIGNED RANK DISTRIBUTION
+// ============================================================================
+
+/// WASM export for signed rank probability mass function
+#[wasm_bindgen]
+pub fn wasm_dsignrank(x: f64, n: f64, give_log: bool) -> f64 {
+    dsignrank(x, n, give_log)
+}
+
+/// WASM export for signed rank cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_psignrank(q: f64, n: f64, lower_tail: bool, log_p: bool) -> f64 {
+    psignrank(q, n, lower_tail, log_p)
+}
+
+/// WASM export for signed rank quantile function
+#[wasm_bindgen]
+pub fn wasm_qsignrank(p: f64, n: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qsignrank(p, n, lower_tail, log_p)
+}
+
+/// WASM export for signed rank random number generation
+#[wasm_bindgen]
+pub fn wasm_rsignrank(n: f64) -> f64 {
+    with_rng(|rng| rsignrank(n, rng))
+}
+
+/// WASM export for `count` signed rank random draws from the shared generator
+#[wasm_bindgen]
+pub fn wasm_rsignrank_n(count: usize, n: f64) -> Vec<f64> {
+    with_rng(|rng| (0..count).map(|_| rsignrank(n, rng)).collect())
+}
+
+// ============================================================================
+// STUDENTIZED RANGE DISTRIBUTION
+// ============================================================================
+
+/// WASM export for studentized range cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_ptukey(
+    q: f64,
+    nmeans: f64,
+    df: f64,
+    nranges: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    ptukey(q, nmeans, df, nranges, lower_tail, log_p)
+}
+
+/// WASM export for studentized range quantile function
+#[wasm_bindgen]
+pub fn wasm_qtukey(
+    p: f64,
+    nmeans: f64,
+    df: f64,
+    nranges: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    qtukey(p, nmeans, df, nranges, lower_tail, log_p)
+}
+
+// ============================================================================
+// MAXIMUM-LIKELIHOOD FITTING
+// ============================================================================
+
+/// WASM export for `fitdistr`, returning the fit as a JSON string
+///
+/// `family` is one of R's `densfun` names ("normal", "lognormal",
+/// "exponential", "gamma", "weibull", "beta", "poisson", "negative binomial",
+/// "geometric"); `vcov` is flat row-major.
+#[wasm_bindgen]
+pub fn wasm_fitdistr(x: &[f64], family: &str) -> Result<String, JsValue> {
+    let fit = FitFamily::from_name(family)
+        .and_then(|family| fitdistr(x, family))
+        .map_err(|e| JsValue::from_str(&format!("Fitting error: {}", e)))?;
+    serde_json::to_string(&fit).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// ============================================================================
+// QUASI-RANDOM SEQUENCES
+// ============================================================================
+// Points are flat row-major `n x dim` arrays on the unit cube; map them onto a
+// distribution with its `q*` function.
+
+fn design_error(e: String) -> JsValue {
+    JsValue::from_str(&format!("Sampling error: {}", e))
+}
+
+/// WASM export for `n` Sobol points, Owen-scrambled if `scramble`
+#[wasm_bindgen]
+pub fn wasm_sobol(n: usize, dim: usize, scramble: bool) -> Result<Vec<f64>, JsValue> {
+    with_rng(|rng| sobol(n, dim, scramble, rng)).map_err(design_error)
+}
+
+/// WASM export for `n` Halton points, digit-permuted if `scramble`
+#[wasm_bindgen]
+pub fn wasm_halton(n: usize, dim: usize, scramble: bool) -> Result<Vec<f64>, JsValue> {
+    with_rng(|rng| halton(n, dim, scramble, rng)).map_err(design_error)
+}
+
+/// WASM export for an `n`-point random Latin hypercube design
+#[wasm_bindgen]
+pub fn wasm_latin_hypercube(n: usize, dim: usize) -> Result<Vec<f64>, JsValue> {
+    with_rng(|rng| latin_hypercube(n, dim, rng)).map_err(design_error)
 }
@@ -1,7 +1,15 @@
-//! F distribution wrapper using statrs
+//! F distribution
+//!
+//! Density and sampling use statrs; `pf` and `qf` are expressed through the
+//! beta (or, for infinite degrees of freedom, chi-squared) functions as in
+//! R, so they inherit those functions' tail accuracy.
 
+use super::beta::{pbeta, qbeta};
+use super::chi_squared::{pchisq, qchisq};
+use super::helpers::dpq::{dt_0, dt_1, q_p01_boundaries};
 use rand::Rng;
-use statrs::distribution::{Continuous, ContinuousCDF, FisherSnedecor};
+use statrs::distribution::{Continuous, FisherSnedecor};
+use std::f64::consts::LN_2;
 
 pub fn df(x: f64, df1: f64, df2: f64, give_log: bool) -> f64 {
     if df1 <= 0.0 || df2 <= 0.0 {
@@ -15,32 +23,80 @@ pub fn df(x: f64, df1: f64, df2: f64, give_log: bool) -> f64 {
     }
 }
 
+/// F cumulative distribution function
+///
+/// Goes through `pbeta` on whichever side keeps its argument away from 1,
+/// and through `pchisq` when a degrees-of-freedom parameter is infinite.
+///
+/// Source: R-4.5.1/nmath/pf.c
 pub fn pf(x: f64, df1: f64, df2: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || df1.is_nan() || df2.is_nan() {
+        return x + df2 + df1;
+    }
     if df1 <= 0.0 || df2 <= 0.0 {
         return f64::NAN;
     }
-    let dist = FisherSnedecor::new(df1, df2).expect("validated parameters: df1 > 0, df2 > 0");
-    let cdf = if lower_tail { dist.cdf(x) } else { 1.0 - dist.cdf(x) };
-    if log_p { cdf.ln() } else { cdf }
+    if x <= 0.0 {
+        return dt_0(lower_tail, log_p);
+    }
+    if x == f64::INFINITY {
+        return dt_1(lower_tail, log_p);
+    }
+
+    if df2 == f64::INFINITY {
+        if df1 == f64::INFINITY {
+            if x < 1.0 {
+                return dt_0(lower_tail, log_p);
+            }
+            if x == 1.0 {
+                return if log_p { -LN_2 } else { 0.5 };
+            }
+            return dt_1(lower_tail, log_p);
+        }
+        return pchisq(x * df1, df1, lower_tail, log_p);
+    }
+    if df1 == f64::INFINITY {
+        return pchisq(df2 / x, df2, !lower_tail, log_p);
+    }
+
+    // Avoid squeezing pbeta's first argument against 1
+    let p = if df1 * x > df2 {
+        pbeta(df2 / (df2 + df1 * x), df2 / 2.0, df1 / 2.0, !lower_tail, log_p)
+    } else {
+        pbeta(df1 * x / (df2 + df1 * x), df1 / 2.0, df2 / 2.0, lower_tail, log_p)
+    };
+    if p.is_nan() { f64::NAN } else { p }
 }
 
+/// F quantile function
+///
+/// Source: R-4.5.1/nmath/qf.c
 pub fn qf(p: f64, df1: f64, df2: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || df1.is_nan() || df2.is_nan() {
+        return p + df1 + df2;
+    }
     if df1 <= 0.0 || df2 <= 0.0 {
         return f64::NAN;
     }
-    
-    // Use R's formula: qf = (1/qbeta - 1) * (df2/df1)
-    // where qbeta uses shape1=df2/2, shape2=df1/2, and !lower_tail
-    let qb = super::beta::qbeta(p, df2/2.0, df1/2.0, !lower_tail, log_p);
-    
-    if qb == 0.0 {
-        return f64::INFINITY;
+    if let Some(q) = q_p01_boundaries(p, 0.0, f64::INFINITY, lower_tail, log_p) {
+        return q;
+    }
+
+    // Chi-squared limits for (effectively) infinite degrees of freedom
+    if df1 <= df2 && df2 > 4e5 {
+        if !df1.is_finite() {
+            // df1 == df2 == Inf
+            return 1.0;
+        }
+        return qchisq(p, df1, lower_tail, log_p) / df1;
     }
-    if qb == 1.0 {
-        return 0.0;
+    if df1 > 4e5 {
+        // and so df2 < df1
+        return df2 / qchisq(p, df2, !lower_tail, log_p);
     }
-    
-    (1.0 / qb - 1.0) * (df2 / df1)
+
+    let q = (1.0 / qbeta(p, df2 / 2.0, df1 / 2.0, !lower_tail, log_p) - 1.0) * (df2 / df1);
+    if q.is_nan() { f64::NAN } else { q }
 }
 
 pub fn rf<R: Rng>(df1: f64, df2: f64, rng: &mut R) -> f64 {
@@ -83,8 +139,7 @@ mod tests {
         // Test with df1=3, df2=5
         let expected = 1.0; // qf(0.5351452, 3, 5) in R
         let actual = qf(0.5351452, 3.0, 5.0, true, false);
-        println!("qf(0.5351452, 3, 5) = {}, expected = {}", actual, expected);
-        assert!((actual - expected).abs() < 2e-4); // Adjusted tolerance due to qbeta precision
+        assert!((actual - expected).abs() < 1e-6); // p above is rounded to 7 digits
     }
 
     #[test]
@@ -108,6 +163,19 @@ mod tests {
     fn test_qf_log() {
         let p = (0.5351452f64).ln();
         let expected = 1.0;
-        assert!((qf(p, 3.0, 5.0, true, true) - expected).abs() < 2e-4); // Adjusted tolerance due to qbeta precision
+        assert!((qf(p, 3.0, 5.0, true, true) - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_f_extreme_tails() {
+        // Reference values from mpmath
+        let up = pf(1000.0, 5.0, 10.0, false, false);
+        assert!((up / 3.707_168_098_708_697e-13 - 1.0).abs() < 1e-13);
+        let lp = pf(1000.0, 5.0, 10.0, false, true);
+        assert!((lp / -28.623337939488963 - 1.0).abs() < 1e-14);
+        assert!((qf(up, 5.0, 10.0, false, false) / 1000.0 - 1.0).abs() < 1e-12);
+        // Infinite df2 reduces to chi-squared / df1
+        let p = pf(2.0, 4.0, f64::INFINITY, false, false);
+        assert!((p - pchisq(8.0, 4.0, false, false)).abs() < 1e-15);
     }
 }
@@ -0,0 +1,1121 @@
+//! Continuous distribution families
+
+use super::super::traits::{ContinuousDistribution, Distribution};
+use super::super::*;
+use super::require;
+use rand::Rng;
+use statrs::function::gamma::{gamma, ln_gamma};
+use std::f64::consts::PI;
+
+/// Euler-Mascheroni constant
+const EULER: f64 = 0.577_215_664_901_532_9;
+
+const REAL_LINE: (f64, f64) = (f64::NEG_INFINITY, f64::INFINITY);
+const POSITIVE: (f64, f64) = (0.0, f64::INFINITY);
+
+fn positive(x: f64) -> bool {
+    x.is_finite() && x > 0.0
+}
+
+/// Positive, allowing `Inf` (degrees of freedom)
+fn positive_or_inf(x: f64) -> bool {
+    x > 0.0
+}
+
+/// Moments of the Student t with `df` degrees of freedom
+fn t_moments(df: f64) -> (f64, f64) {
+    let mean = if df > 1.0 { 0.0 } else { f64::NAN };
+    let variance = if df.is_infinite() {
+        1.0
+    } else if df > 2.0 {
+        df / (df - 2.0)
+    } else if df > 1.0 {
+        f64::INFINITY
+    } else {
+        f64::NAN
+    };
+    (mean, variance)
+}
+
+/// Beta distribution on `[0, 1]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Beta {
+    pub shape1: f64,
+    pub shape2: f64,
+}
+
+impl Beta {
+    pub fn new(shape1: f64, shape2: f64) -> Result<Self, String> {
+        require(
+            positive(shape1) && positive(shape2),
+            "shape parameters must be positive",
+        )?;
+        Ok(Self { shape1, shape2 })
+    }
+}
+
+impl Distribution for Beta {
+    forward_pq!(pbeta, qbeta, rbeta; shape1, shape2);
+
+    fn mean(&self) -> f64 {
+        self.shape1 / (self.shape1 + self.shape2)
+    }
+
+    fn variance(&self) -> f64 {
+        let (a, b) = (self.shape1, self.shape2);
+        a * b / ((a + b).powi(2) * (a + b + 1.0))
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, 1.0)
+    }
+}
+
+impl ContinuousDistribution for Beta {
+    forward_d!(dbeta; shape1, shape2);
+}
+
+/// Cauchy distribution; its mean and variance are undefined
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cauchy {
+    pub location: f64,
+    pub scale: f64,
+}
+
+impl Cauchy {
+    pub fn new(location: f64, scale: f64) -> Result<Self, String> {
+        require(location.is_finite(), "location must be finite")?;
+        require(positive(scale), "scale must be positive")?;
+        Ok(Self { location, scale })
+    }
+}
+
+impl Distribution for Cauchy {
+    forward_pq!(pcauchy, qcauchy, rcauchy; location, scale);
+
+    fn mean(&self) -> f64 {
+        f64::NAN
+    }
+
+    fn variance(&self) -> f64 {
+        f64::NAN
+    }
+
+    fn support(&self) -> (f64, f64) {
+        REAL_LINE
+    }
+}
+
+impl ContinuousDistribution for Cauchy {
+    forward_d!(dcauchy; location, scale);
+}
+
+/// Chi-squared distribution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquared {
+    pub df: f64,
+}
+
+impl ChiSquared {
+    pub fn new(df: f64) -> Result<Self, String> {
+        require(positive(df), "df must be positive")?;
+        Ok(Self { df })
+    }
+}
+
+impl Distribution for ChiSquared {
+    forward_pq!(pchisq, qchisq, rchisq; df);
+
+    fn mean(&self) -> f64 {
+        self.df
+    }
+
+    fn variance(&self) -> f64 {
+        2.0 * self.df
+    }
+
+    fn support(&self) -> (f64, f64) {
+        POSITIVE
+    }
+}
+
+impl ContinuousDistribution for ChiSquared {
+    forward_d!(dchisq; df);
+}
+
+/// Exponential distribution with rate `rate`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Exponential {
+    pub rate: f64,
+}
+
+impl Exponential {
+    pub fn new(rate: f64) -> Result<Self, String> {
+        require(positive(rate), "rate must be positive")?;
+        Ok(Self { rate })
+    }
+}
+
+impl Distribution for Exponential {
+    forward_pq!(pexp, qexp, rexp; rate);
+
+    fn mean(&self) -> f64 {
+        1.0 / self.rate
+    }
+
+    fn variance(&self) -> f64 {
+        1.0 / (self.rate * self.rate)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        POSITIVE
+    }
+}
+
+impl ContinuousDistribution for Exponential {
+    forward_d!(dexp; rate);
+}
+
+/// Fisher's F distribution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FisherF {
+    pub df1: f64,
+    pub df2: f64,
+}
+
+impl FisherF {
+    pub fn new(df1: f64, df2: f64) -> Result<Self, String> {
+        require(positive(df1) && positive_or_inf(df2), "df must be positive")?;
+        Ok(Self { df1, df2 })
+    }
+}
+
+impl Distribution for FisherF {
+    forward_pq!(pf, qf, rf; df1, df2);
+
+    fn mean(&self) -> f64 {
+        let d2 = self.df2;
+        if d2.is_infinite() {
+            1.0
+        } else if d2 > 2.0 {
+            d2 / (d2 - 2.0)
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        let (d1, d2) = (self.df1, self.df2);
+        if d2.is_infinite() {
+            2.0 / d1
+        } else if d2 > 4.0 {
+            2.0 * d2 * d2 * (d1 + d2 - 2.0) / (d1 * (d2 - 2.0).powi(2) * (d2 - 4.0))
+        } else if d2 > 2.0 {
+            f64::INFINITY
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn support(&self) -> (f64, f64) {
+        POSITIVE
+    }
+}
+
+impl ContinuousDistribution for FisherF {
+    forward_d!(df; df1, df2);
+}
+
+/// Gamma distribution with shape and rate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gamma {
+    pub shape: f64,
+    pub rate: f64,
+}
+
+impl Gamma {
+    pub fn new(shape: f64, rate: f64) -> Result<Self, String> {
+        require(positive(shape), "shape must be positive")?;
+        require(positive(rate), "rate must be positive")?;
+        Ok(Self { shape, rate })
+    }
+}
+
+impl Distribution for Gamma {
+    forward_pq!(pgamma, qgamma, rgamma; shape, rate);
+
+    fn mean(&self) -> f64 {
+        self.shape / self.rate
+    }
+
+    fn variance(&self) -> f64 {
+        self.shape / (self.rate * self.rate)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        POSITIVE
+    }
+}
+
+impl ContinuousDistribution for Gamma {
+    forward_d!(dgamma; shape, rate);
+}
+
+/// Generalized extreme value distribution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gev {
+    pub location: f64,
+    pub scale: f64,
+    pub shape: f64,
+}
+
+impl Gev {
+    pub fn new(location: f64, scale: f64, shape: f64) -> Result<Self, String> {
+        require(
+            location.is_finite() && shape.is_finite(),
+            "location and shape must be finite",
+        )?;
+        require(positive(scale), "scale must be positive")?;
+        Ok(Self {
+            location,
+            scale,
+            shape,
+        })
+    }
+}
+
+impl Distribution for Gev {
+    forward_pq!(pgev, qgev, rgev; location, scale, shape);
+
+    fn mean(&self) -> f64 {
+        let xi = self.shape;
+        if xi == 0.0 {
+            self.location + self.scale * EULER
+        } else if xi < 1.0 {
+            self.location + self.scale * (gamma(1.0 - xi) - 1.0) / xi
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        let xi = self.shape;
+        if xi == 0.0 {
+            self.scale * self.scale * PI * PI / 6.0
+        } else if xi < 0.5 {
+            let (g1, g2) = (gamma(1.0 - xi), gamma(1.0 - 2.0 * xi));
+            self.scale * self.scale * (g2 - g1 * g1) / (xi * xi)
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    fn support(&self) -> (f64, f64) {
+        let bound = self.location - self.scale / self.shape;
+        if self.shape > 0.0 {
+            (bound, f64::INFINITY)
+        } else if self.shape < 0.0 {
+            (f64::NEG_INFINITY, bound)
+        } else {
+            REAL_LINE
+        }
+    }
+}
+
+impl ContinuousDistribution for Gev {
+    forward_d!(dgev; location, scale, shape);
+}
+
+/// Generalized Pareto distribution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gpd {
+    pub location: f64,
+    pub scale: f64,
+    pub shape: f64,
+}
+
+impl Gpd {
+    pub fn new(location: f64, scale: f64, shape: f64) -> Result<Self, String> {
+        require(
+            location.is_finite() && shape.is_finite(),
+            "location and shape must be finite",
+        )?;
+        require(positive(scale), "scale must be positive")?;
+        Ok(Self {
+            location,
+            scale,
+            shape,
+        })
+    }
+}
+
+impl Distribution for Gpd {
+    forward_pq!(pgpd, qgpd, rgpd; location, scale, shape);
+
+    fn mean(&self) -> f64 {
+        if self.shape < 1.0 {
+            self.location + self.scale / (1.0 - self.shape)
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        let xi = self.shape;
+        if xi < 0.5 {
+            self.scale * self.scale / ((1.0 - xi).powi(2) * (1.0 - 2.0 * xi))
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    fn support(&self) -> (f64, f64) {
+        if self.shape < 0.0 {
+            (self.location, self.location - self.scale / self.shape)
+        } else {
+            (self.location, f64::INFINITY)
+        }
+    }
+}
+
+impl ContinuousDistribution for Gpd {
+    forward_d!(dgpd; location, scale, shape);
+}
+
+/// Gumbel (type I extreme value) distribution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gumbel {
+    pub location: f64,
+    pub scale: f64,
+}
+
+impl Gumbel {
+    pub fn new(location: f64, scale: f64) -> Result<Self, String> {
+        require(location.is_finite(), "location must be finite")?;
+        require(positive(scale), "scale must be positive")?;
+        Ok(Self { location, scale })
+    }
+}
+
+impl Distribution for Gumbel {
+    forward_pq!(pgumbel, qgumbel, rgumbel; location, scale);
+
+    fn mean(&self) -> f64 {
+        self.location + self.scale * EULER
+    }
+
+    fn variance(&self) -> f64 {
+        self.scale * self.scale * PI * PI / 6.0
+    }
+
+    fn support(&self) -> (f64, f64) {
+        REAL_LINE
+    }
+}
+
+impl ContinuousDistribution for Gumbel {
+    forward_d!(dgumbel; location, scale);
+}
+
+/// Laplace (double exponential) distribution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Laplace {
+    pub location: f64,
+    pub scale: f64,
+}
+
+impl Laplace {
+    pub fn new(location: f64, scale: f64) -> Result<Self, String> {
+        require(location.is_finite(), "location must be finite")?;
+        require(positive(scale), "scale must be positive")?;
+        Ok(Self { location, scale })
+    }
+}
+
+impl Distribution for Laplace {
+    forward_pq!(plaplace, qlaplace, rlaplace; location, scale);
+
+    fn mean(&self) -> f64 {
+        self.location
+    }
+
+    fn variance(&self) -> f64 {
+        2.0 * self.scale * self.scale
+    }
+
+    fn support(&self) -> (f64, f64) {
+        REAL_LINE
+    }
+}
+
+impl ContinuousDistribution for Laplace {
+    forward_d!(dlaplace; location, scale);
+}
+
+/// Location-scale Student t distribution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocationScaleT {
+    pub df: f64,
+    pub location: f64,
+    pub scale: f64,
+}
+
+impl LocationScaleT {
+    pub fn new(df: f64, location: f64, scale: f64) -> Result<Self, String> {
+        require(positive_or_inf(df), "df must be positive")?;
+        require(location.is_finite(), "location must be finite")?;
+        require(positive(scale), "scale must be positive")?;
+        Ok(Self {
+            df,
+            location,
+            scale,
+        })
+    }
+}
+
+impl Distribution for LocationScaleT {
+    forward_pq!(plst, qlst, rlst; df, location, scale);
+
+    fn mean(&self) -> f64 {
+        self.location + self.scale * t_moments(self.df).0
+    }
+
+    fn variance(&self) -> f64 {
+        self.scale * self.scale * t_moments(self.df).1
+    }
+
+    fn support(&self) -> (f64, f64) {
+        REAL_LINE
+    }
+}
+
+impl ContinuousDistribution for LocationScaleT {
+    forward_d!(dlst; df, location, scale);
+}
+
+/// Log-normal distribution, parameterised on the log scale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogNormal {
+    pub meanlog: f64,
+    pub sdlog: f64,
+}
+
+impl LogNormal {
+    pub fn new(meanlog: f64, sdlog: f64) -> Result<Self, String> {
+        require(meanlog.is_finite(), "meanlog must be finite")?;
+        require(positive(sdlog), "sdlog must be positive")?;
+        Ok(Self { meanlog, sdlog })
+    }
+}
+
+impl Distribution for LogNormal {
+    forward_pq!(plnorm, qlnorm, rlnorm; meanlog, sdlog);
+
+    fn mean(&self) -> f64 {
+        (self.meanlog + 0.5 * self.sdlog * self.sdlog).exp()
+    }
+
+    fn variance(&self) -> f64 {
+        let s2 = self.sdlog * self.sdlog;
+        s2.exp_m1() * (2.0 * self.meanlog + s2).exp()
+    }
+
+    fn support(&self) -> (f64, f64) {
+        POSITIVE
+    }
+}
+
+impl ContinuousDistribution for LogNormal {
+    forward_d!(dlnorm; meanlog, sdlog);
+}
+
+/// Logistic distribution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Logistic {
+    pub location: f64,
+    pub scale: f64,
+}
+
+impl Logistic {
+    pub fn new(location: f64, scale: f64) -> Result<Self, String> {
+        require(location.is_finite(), "location must be finite")?;
+        require(positive(scale), "scale must be positive")?;
+        Ok(Self { location, scale })
+    }
+}
+
+impl Distribution for Logistic {
+    forward_pq!(plogis, qlogis, rlogis; location, scale);
+
+    fn mean(&self) -> f64 {
+        self.location
+    }
+
+    fn variance(&self) -> f64 {
+        self.scale * self.scale * PI * PI / 3.0
+    }
+
+    fn support(&self) -> (f64, f64) {
+        REAL_LINE
+    }
+}
+
+impl ContinuousDistribution for Logistic {
+    forward_d!(dlogis; location, scale);
+}
+
+/// Noncentral beta distribution; sampled by inversion
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoncentralBeta {
+    pub shape1: f64,
+    pub shape2: f64,
+    pub ncp: f64,
+}
+
+impl NoncentralBeta {
+    pub fn new(shape1: f64, shape2: f64, ncp: f64) -> Result<Self, String> {
+        require(
+            positive(shape1) && positive(shape2),
+            "shape parameters must be positive",
+        )?;
+        require(ncp.is_finite() && ncp >= 0.0, "ncp must be non-negative")?;
+        Ok(Self {
+            shape1,
+            shape2,
+            ncp,
+        })
+    }
+
+    /// First two raw moments from the Poisson(ncp / 2) mixture of
+    /// Beta(shape1 + j, shape2) distributions
+    fn raw_moments(&self) -> (f64, f64) {
+        let (a, b, h) = (self.shape1, self.shape2, self.ncp / 2.0);
+        let spread = 10.0 * h.sqrt() + 10.0;
+        let (from, to) = ((h - spread).max(0.0).floor(), (h + spread).ceil());
+        let (mut m1, mut m2) = (0.0, 0.0);
+        let mut j = from;
+        while j <= to {
+            let w = dpois(j, h, false);
+            let (aj, abj) = (a + j, a + b + j);
+            m1 += w * aj / abj;
+            m2 += w * aj * (aj + 1.0) / (abj * (abj + 1.0));
+            j += 1.0;
+        }
+        (m1, m2)
+    }
+}
+
+impl Distribution for NoncentralBeta {
+    forward_pq!(pnbeta, qnbeta; shape1, shape2, ncp);
+
+    fn mean(&self) -> f64 {
+        self.raw_moments().0
+    }
+
+    fn variance(&self) -> f64 {
+        let (m1, m2) = self.raw_moments();
+        m2 - m1 * m1
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, 1.0)
+    }
+}
+
+impl ContinuousDistribution for NoncentralBeta {
+    forward_d!(dnbeta; shape1, shape2, ncp);
+}
+
+/// Noncentral chi-squared distribution; sampled by inversion
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoncentralChiSquared {
+    pub df: f64,
+    pub ncp: f64,
+}
+
+impl NoncentralChiSquared {
+    pub fn new(df: f64, ncp: f64) -> Result<Self, String> {
+        require(positive(df), "df must be positive")?;
+        require(ncp.is_finite() && ncp >= 0.0, "ncp must be non-negative")?;
+        Ok(Self { df, ncp })
+    }
+}
+
+impl Distribution for NoncentralChiSquared {
+    forward_pq!(pnchisq, qnchisq; df, ncp);
+
+    fn mean(&self) -> f64 {
+        self.df + self.ncp
+    }
+
+    fn variance(&self) -> f64 {
+        2.0 * (self.df + 2.0 * self.ncp)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        POSITIVE
+    }
+}
+
+impl ContinuousDistribution for NoncentralChiSquared {
+    forward_d!(dnchisq; df, ncp);
+}
+
+/// Noncentral F distribution; sampled by inversion
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoncentralF {
+    pub df1: f64,
+    pub df2: f64,
+    pub ncp: f64,
+}
+
+impl NoncentralF {
+    pub fn new(df1: f64, df2: f64, ncp: f64) -> Result<Self, String> {
+        require(positive(df1) && positive_or_inf(df2), "df must be positive")?;
+        require(ncp.is_finite() && ncp >= 0.0, "ncp must be non-negative")?;
+        Ok(Self { df1, df2, ncp })
+    }
+}
+
+impl Distribution for NoncentralF {
+    forward_pq!(pnf, qnf; df1, df2, ncp);
+
+    fn mean(&self) -> f64 {
+        let (d1, d2) = (self.df1, self.df2);
+        if d2.is_infinite() {
+            (d1 + self.ncp) / d1
+        } else if d2 > 2.0 {
+            d2 * (d1 + self.ncp) / (d1 * (d2 - 2.0))
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        let (d1, d2, ncp) = (self.df1, self.df2, self.ncp);
+        if d2.is_infinite() {
+            2.0 * (d1 + 2.0 * ncp) / (d1 * d1)
+        } else if d2 > 4.0 {
+            2.0 * (d2 / d1).powi(2) * ((d1 + ncp).powi(2) + (d1 + 2.0 * ncp) * (d2 - 2.0))
+                / ((d2 - 2.0).powi(2) * (d2 - 4.0))
+        } else if d2 > 2.0 {
+            f64::INFINITY
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn support(&self) -> (f64, f64) {
+        POSITIVE
+    }
+}
+
+impl ContinuousDistribution for NoncentralF {
+    forward_d!(dnf; df1, df2, ncp);
+}
+
+/// Noncentral Student t distribution; sampled by inversion
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoncentralT {
+    pub df: f64,
+    pub ncp: f64,
+}
+
+impl NoncentralT {
+    pub fn new(df: f64, ncp: f64) -> Result<Self, String> {
+        require(positive_or_inf(df), "df must be positive")?;
+        require(ncp.is_finite(), "ncp must be finite")?;
+        Ok(Self { df, ncp })
+    }
+}
+
+impl Distribution for NoncentralT {
+    forward_pq!(pnt, qnt; df, ncp);
+
+    fn mean(&self) -> f64 {
+        let df = self.df;
+        if df.is_infinite() {
+            self.ncp
+        } else if df > 1.0 {
+            self.ncp * (df / 2.0).sqrt() * (ln_gamma((df - 1.0) / 2.0) - ln_gamma(df / 2.0)).exp()
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        let df = self.df;
+        if df.is_infinite() {
+            1.0
+        } else if df > 2.0 {
+            df * (1.0 + self.ncp * self.ncp) / (df - 2.0) - self.mean().powi(2)
+        } else if df > 1.0 {
+            f64::INFINITY
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn support(&self) -> (f64, f64) {
+        REAL_LINE
+    }
+}
+
+impl ContinuousDistribution for NoncentralT {
+    forward_d!(dnt; df, ncp);
+}
+
+/// Normal distribution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normal {
+    pub mean: f64,
+    pub sd: f64,
+}
+
+impl Normal {
+    pub fn new(mean: f64, sd: f64) -> Result<Self, String> {
+        require(mean.is_finite(), "mean must be finite")?;
+        require(positive(sd), "sd must be positive")?;
+        Ok(Self { mean, sd })
+    }
+}
+
+impl Distribution for Normal {
+    forward_pq!(pnorm, qnorm, rnorm; mean, sd);
+
+    fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    fn variance(&self) -> f64 {
+        self.sd * self.sd
+    }
+
+    fn support(&self) -> (f64, f64) {
+        REAL_LINE
+    }
+}
+
+impl ContinuousDistribution for Normal {
+    forward_d!(dnorm; mean, sd);
+}
+
+/// Pareto (type I) distribution with minimum `scale` and tail index `shape`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pareto {
+    pub scale: f64,
+    pub shape: f64,
+}
+
+impl Pareto {
+    pub fn new(scale: f64, shape: f64) -> Result<Self, String> {
+        require(positive(scale), "scale must be positive")?;
+        require(positive(shape), "shape must be positive")?;
+        Ok(Self { scale, shape })
+    }
+}
+
+impl Distribution for Pareto {
+    forward_pq!(ppareto, qpareto, rpareto; scale, shape);
+
+    fn mean(&self) -> f64 {
+        let a = self.shape;
+        if a > 1.0 {
+            a * self.scale / (a - 1.0)
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    fn variance(&self) -> f64 {
+        let a = self.shape;
+        if a > 2.0 {
+            self.scale * self.scale * a / ((a - 1.0).powi(2) * (a - 2.0))
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (self.scale, f64::INFINITY)
+    }
+}
+
+impl ContinuousDistribution for Pareto {
+    forward_d!(dpareto; scale, shape);
+}
+
+/// Student t distribution
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StudentsT {
+    pub df: f64,
+}
+
+impl StudentsT {
+    pub fn new(df: f64) -> Result<Self, String> {
+        require(positive_or_inf(df), "df must be positive")?;
+        Ok(Self { df })
+    }
+}
+
+impl Distribution for StudentsT {
+    forward_pq!(pt, qt, rt; df);
+
+    fn mean(&self) -> f64 {
+        t_moments(self.df).0
+    }
+
+    fn variance(&self) -> f64 {
+        t_moments(self.df).1
+    }
+
+    fn support(&self) -> (f64, f64) {
+        REAL_LINE
+    }
+}
+
+impl ContinuousDistribution for StudentsT {
+    forward_d!(dt; df);
+}
+
+/// Triangular distribution on `[min, max]` with peak at `mode`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangular {
+    pub min: f64,
+    pub max: f64,
+    pub mode: f64,
+}
+
+impl Triangular {
+    pub fn new(min: f64, max: f64, mode: f64) -> Result<Self, String> {
+        require(
+            min.is_finite() && max.is_finite() && min < max,
+            "need finite min < max",
+        )?;
+        require((min..=max).contains(&mode), "mode must lie in [min, max]")?;
+        Ok(Self { min, max, mode })
+    }
+}
+
+impl Distribution for Triangular {
+    forward_pq!(ptriang, qtriang, rtriang; min, max, mode);
+
+    fn mean(&self) -> f64 {
+        (self.min + self.max + self.mode) / 3.0
+    }
+
+    fn variance(&self) -> f64 {
+        let (a, b, c) = (self.min, self.max, self.mode);
+        (a * a + b * b + c * c - a * b - a * c - b * c) / 18.0
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (self.min, self.max)
+    }
+}
+
+impl ContinuousDistribution for Triangular {
+    forward_d!(dtriang; min, max, mode);
+}
+
+/// Normal distribution truncated to `[a, b]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TruncatedNormal {
+    pub a: f64,
+    pub b: f64,
+    pub mean: f64,
+    pub sd: f64,
+}
+
+impl TruncatedNormal {
+    pub fn new(a: f64, b: f64, mean: f64, sd: f64) -> Result<Self, String> {
+        require(!a.is_nan() && !b.is_nan() && a < b, "need a < b")?;
+        require(mean.is_finite(), "mean must be finite")?;
+        require(positive(sd), "sd must be positive")?;
+        Ok(Self { a, b, mean, sd })
+    }
+}
+
+impl Distribution for TruncatedNormal {
+    forward_pq!(ptruncnorm, qtruncnorm, rtruncnorm; a, b, mean, sd);
+
+    fn mean(&self) -> f64 {
+        etruncnorm(self.a, self.b, self.mean, self.sd)
+    }
+
+    fn variance(&self) -> f64 {
+        vtruncnorm(self.a, self.b, self.mean, self.sd)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (self.a, self.b)
+    }
+}
+
+impl ContinuousDistribution for TruncatedNormal {
+    forward_d!(dtruncnorm; a, b, mean, sd);
+}
+
+/// Continuous uniform distribution on `[min, max]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Uniform {
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Uniform {
+    pub fn new(min: f64, max: f64) -> Result<Self, String> {
+        require(
+            min.is_finite() && max.is_finite() && min < max,
+            "need finite min < max",
+        )?;
+        Ok(Self { min, max })
+    }
+}
+
+impl Distribution for Uniform {
+    forward_pq!(punif, qunif, runif; min, max);
+
+    fn mean(&self) -> f64 {
+        0.5 * (self.min + self.max)
+    }
+
+    fn variance(&self) -> f64 {
+        (self.max - self.min).powi(2) / 12.0
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (self.min, self.max)
+    }
+}
+
+impl ContinuousDistribution for Uniform {
+    forward_d!(dunif; min, max);
+}
+
+/// Weibull distribution with shape and scale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Weibull {
+    pub shape: f64,
+    pub scale: f64,
+}
+
+impl Weibull {
+    pub fn new(shape: f64, scale: f64) -> Result<Self, String> {
+        require(positive(shape), "shape must be positive")?;
+        require(positive(scale), "scale must be positive")?;
+        Ok(Self { shape, scale })
+    }
+}
+
+impl Distribution for Weibull {
+    forward_pq!(pweibull, qweibull, rweibull; shape, scale);
+
+    fn mean(&self) -> f64 {
+        self.scale * gamma(1.0 + 1.0 / self.shape)
+    }
+
+    fn variance(&self) -> f64 {
+        let g1 = gamma(1.0 + 1.0 / self.shape);
+        self.scale * self.scale * (gamma(1.0 + 2.0 / self.shape) - g1 * g1)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        POSITIVE
+    }
+}
+
+impl ContinuousDistribution for Weibull {
+    forward_d!(dweibull; shape, scale);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    /// Round-trip the quantile through the CDF and compare the sample
+    /// moments with `mean` and `variance`.
+    fn check<D: ContinuousDistribution + std::fmt::Debug>(dist: D, seed: u64, n: usize) {
+        for &p in &[0.05, 0.3, 0.5, 0.9] {
+            let x = dist.quantile(p);
+            assert!(
+                (dist.cdf(x) - p).abs() < 1e-8,
+                "{dist:?} cdf(q({p})) = {}",
+                dist.cdf(x)
+            );
+            assert!((dist.sf(x) - (1.0 - p)).abs() < 1e-8);
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        let draws = dist.sample_n(n, &mut rng);
+        let (lo, hi) = dist.support();
+        assert!(draws.iter().all(|&v| lo <= v && v <= hi));
+        let mean = draws.iter().sum::<f64>() / n as f64;
+        let var = draws.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        let sd = dist.std_dev();
+        assert!(
+            (mean - dist.mean()).abs() < 4.0 * sd / (n as f64).sqrt(),
+            "mean {mean}"
+        );
+        assert!((var / dist.variance() - 1.0).abs() < 0.1, "variance {var}");
+    }
+
+    #[test]
+    fn test_families_round_trip_and_moments() {
+        // Noncentral families sample by inversion, which is slow
+        check(Beta::new(2.0, 5.0).unwrap(), 1, 20_000);
+        check(ChiSquared::new(4.0).unwrap(), 2, 20_000);
+        check(Exponential::new(2.0).unwrap(), 3, 20_000);
+        check(FisherF::new(5.0, 20.0).unwrap(), 4, 20_000);
+        check(Gamma::new(3.0, 0.5).unwrap(), 5, 20_000);
+        check(Gev::new(1.0, 2.0, 0.1).unwrap(), 6, 20_000);
+        check(Gpd::new(0.0, 1.0, -0.2).unwrap(), 7, 20_000);
+        check(Gumbel::new(1.0, 2.0).unwrap(), 8, 20_000);
+        check(Laplace::new(-1.0, 0.5).unwrap(), 9, 20_000);
+        check(LocationScaleT::new(8.0, 1.0, 2.0).unwrap(), 10, 20_000);
+        check(LogNormal::new(0.0, 0.3).unwrap(), 11, 20_000);
+        check(Logistic::new(2.0, 1.5).unwrap(), 12, 20_000);
+        check(NoncentralBeta::new(2.0, 3.0, 4.0).unwrap(), 13, 2_000);
+        check(NoncentralChiSquared::new(3.0, 2.0).unwrap(), 14, 2_000);
+        check(NoncentralF::new(4.0, 30.0, 3.0).unwrap(), 15, 2_000);
+        check(NoncentralT::new(12.0, 1.5).unwrap(), 16, 2_000);
+        check(Normal::new(3.0, 2.0).unwrap(), 17, 20_000);
+        check(Pareto::new(1.0, 6.0).unwrap(), 18, 20_000);
+        check(StudentsT::new(8.0).unwrap(), 19, 20_000);
+        check(Triangular::new(0.0, 4.0, 1.0).unwrap(), 20, 20_000);
+        check(
+            TruncatedNormal::new(-1.0, 2.0, 0.5, 1.5).unwrap(),
+            21,
+            20_000,
+        );
+        check(Uniform::new(-2.0, 3.0).unwrap(), 22, 20_000);
+        check(Weibull::new(1.5, 2.0).unwrap(), 23, 20_000);
+    }
+
+    #[test]
+    fn test_density_forwarding() {
+        let normal = Normal::new(1.0, 2.0).unwrap();
+        assert_eq!(normal.pdf(0.5), dnorm(0.5, 1.0, 2.0, false));
+        assert_eq!(normal.log_pdf(0.5), dnorm(0.5, 1.0, 2.0, true));
+        assert_eq!(normal.log_sf(4.0), pnorm(4.0, 1.0, 2.0, false, true));
+        let cauchy = Cauchy::new(0.0, 1.0).unwrap();
+        assert!(cauchy.mean().is_nan() && cauchy.variance().is_nan());
+        assert_eq!(StudentsT::new(2.0).unwrap().variance(), f64::INFINITY);
+        assert_eq!(
+            Gev::new(0.0, 1.0, 0.5).unwrap().support(),
+            (-2.0, f64::INFINITY)
+        );
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(Normal::new(0.0, 0.0).is_err());
+        assert!(Gamma::new(-1.0, 1.0).is_err());
+        assert!(Uniform::new(1.0, 1.0).is_err());
+        assert!(Triangular::new(0.0, 1.0, 2.0).is_err());
+        assert!(TruncatedNormal::new(2.0, 1.0, 0.0, 1.0).is_err());
+        assert!(NoncentralChiSquared::new(2.0, -1.0).is_err());
+        assert!(StudentsT::new(f64::INFINITY).is_ok());
+    }
+}
@@ -0,0 +1,439 @@
+//! Discrete distribution families
+
+use super::super::traits::{DiscreteDistribution, Distribution};
+use super::super::*;
+use super::super::zipf::weight_sum;
+use super::require;
+use rand::Rng;
+
+fn count(x: f64) -> bool {
+    validate_integer(x).is_some()
+}
+
+fn probability(p: f64) -> bool {
+    (0.0..=1.0).contains(&p)
+}
+
+/// Binomial distribution with `size` trials and success probability `prob`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Binomial {
+    pub size: f64,
+    pub prob: f64,
+}
+
+impl Binomial {
+    pub fn new(size: f64, prob: f64) -> Result<Self, String> {
+        require(count(size), "size must be a non-negative integer")?;
+        require(probability(prob), "prob must lie in [0, 1]")?;
+        Ok(Self { size, prob })
+    }
+}
+
+impl Distribution for Binomial {
+    forward_pq!(pbinom, qbinom, rbinom; size, prob);
+
+    fn mean(&self) -> f64 {
+        self.size * self.prob
+    }
+
+    fn variance(&self) -> f64 {
+        self.size * self.prob * (1.0 - self.prob)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, self.size)
+    }
+}
+
+impl DiscreteDistribution for Binomial {
+    forward_d!(dbinom; size, prob);
+}
+
+/// Point mass at `location`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dirac {
+    pub location: f64,
+}
+
+impl Dirac {
+    pub fn new(location: f64) -> Result<Self, String> {
+        require(location.is_finite(), "location must be finite")?;
+        Ok(Self { location })
+    }
+}
+
+impl Distribution for Dirac {
+    forward_pq!(pdirac, qdirac, rdirac; location);
+
+    fn mean(&self) -> f64 {
+        self.location
+    }
+
+    fn variance(&self) -> f64 {
+        0.0
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (self.location, self.location)
+    }
+}
+
+impl DiscreteDistribution for Dirac {
+    forward_d!(ddirac; location);
+}
+
+/// Geometric distribution: failures before the first success
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Geometric {
+    pub prob: f64,
+}
+
+impl Geometric {
+    pub fn new(prob: f64) -> Result<Self, String> {
+        require(prob > 0.0 && prob <= 1.0, "prob must lie in (0, 1]")?;
+        Ok(Self { prob })
+    }
+}
+
+impl Distribution for Geometric {
+    forward_pq!(pgeom, qgeom, rgeom; prob);
+
+    fn mean(&self) -> f64 {
+        (1.0 - self.prob) / self.prob
+    }
+
+    fn variance(&self) -> f64 {
+        (1.0 - self.prob) / (self.prob * self.prob)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl DiscreteDistribution for Geometric {
+    forward_d!(dgeom; prob);
+}
+
+/// Hypergeometric distribution: white balls in `k` draws from an urn with
+/// `m` white and `n` black balls
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hypergeometric {
+    pub m: f64,
+    pub n: f64,
+    pub k: f64,
+}
+
+impl Hypergeometric {
+    pub fn new(m: f64, n: f64, k: f64) -> Result<Self, String> {
+        require(
+            count(m) && count(n) && count(k),
+            "m, n and k must be non-negative integers",
+        )?;
+        require(k <= m + n, "k must not exceed m + n")?;
+        Ok(Self { m, n, k })
+    }
+}
+
+impl Distribution for Hypergeometric {
+    forward_pq!(phyper, qhyper, rhyper; m, n, k);
+
+    fn mean(&self) -> f64 {
+        self.k * self.m / (self.m + self.n)
+    }
+
+    fn variance(&self) -> f64 {
+        let total = self.m + self.n;
+        if total <= 1.0 {
+            return 0.0;
+        }
+        self.k * (self.m / total) * (self.n / total) * (total - self.k) / (total - 1.0)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        ((self.k - self.n).max(0.0), self.k.min(self.m))
+    }
+}
+
+impl DiscreteDistribution for Hypergeometric {
+    forward_d!(dhyper; m, n, k);
+}
+
+/// Negative binomial distribution: failures before `size` successes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NegativeBinomial {
+    pub size: f64,
+    pub prob: f64,
+}
+
+impl NegativeBinomial {
+    pub fn new(size: f64, prob: f64) -> Result<Self, String> {
+        require(size.is_finite() && size > 0.0, "size must be positive")?;
+        require(prob > 0.0 && prob <= 1.0, "prob must lie in (0, 1]")?;
+        Ok(Self { size, prob })
+    }
+}
+
+impl Distribution for NegativeBinomial {
+    forward_pq!(pnbinom, qnbinom, rnbinom; size, prob);
+
+    fn mean(&self) -> f64 {
+        self.size * (1.0 - self.prob) / self.prob
+    }
+
+    fn variance(&self) -> f64 {
+        self.size * (1.0 - self.prob) / (self.prob * self.prob)
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl DiscreteDistribution for NegativeBinomial {
+    forward_d!(dnbinom; size, prob);
+}
+
+/// Poisson distribution with mean `lambda`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Poisson {
+    pub lambda: f64,
+}
+
+impl Poisson {
+    pub fn new(lambda: f64) -> Result<Self, String> {
+        require(
+            lambda.is_finite() && lambda >= 0.0,
+            "lambda must be non-negative",
+        )?;
+        Ok(Self { lambda })
+    }
+}
+
+impl Distribution for Poisson {
+    forward_pq!(ppois, qpois, rpois; lambda);
+
+    fn mean(&self) -> f64 {
+        self.lambda
+    }
+
+    fn variance(&self) -> f64 {
+        self.lambda
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, f64::INFINITY)
+    }
+}
+
+impl DiscreteDistribution for Poisson {
+    forward_d!(dpois; lambda);
+}
+
+/// Wilcoxon signed rank statistic for `n` observations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignRank {
+    pub n: f64,
+}
+
+impl SignRank {
+    pub fn new(n: f64) -> Result<Self, String> {
+        require(count(n) && n >= 1.0, "n must be a positive integer")?;
+        Ok(Self { n })
+    }
+}
+
+impl Distribution for SignRank {
+    forward_pq!(psignrank, qsignrank, rsignrank; n);
+
+    fn mean(&self) -> f64 {
+        self.n * (self.n + 1.0) / 4.0
+    }
+
+    fn variance(&self) -> f64 {
+        self.n * (self.n + 1.0) * (2.0 * self.n + 1.0) / 24.0
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, self.n * (self.n + 1.0) / 2.0)
+    }
+}
+
+impl DiscreteDistribution for SignRank {
+    forward_d!(dsignrank; n);
+}
+
+/// Wilcoxon rank sum statistic for samples of sizes `m` and `n`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wilcoxon {
+    pub m: f64,
+    pub n: f64,
+}
+
+impl Wilcoxon {
+    pub fn new(m: f64, n: f64) -> Result<Self, String> {
+        require(
+            count(m) && count(n) && m >= 1.0 && n >= 1.0,
+            "m and n must be positive integers",
+        )?;
+        Ok(Self { m, n })
+    }
+}
+
+impl Distribution for Wilcoxon {
+    forward_pq!(pwilcox, qwilcox, rwilcox; m, n);
+
+    fn mean(&self) -> f64 {
+        self.m * self.n / 2.0
+    }
+
+    fn variance(&self) -> f64 {
+        self.m * self.n * (self.m + self.n + 1.0) / 12.0
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (0.0, self.m * self.n)
+    }
+}
+
+impl DiscreteDistribution for Wilcoxon {
+    forward_d!(dwilcox; m, n);
+}
+
+/// Zipf–Mandelbrot distribution on ranks `1..=n` (`n = Inf` for the zeta
+/// distribution)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Zipf {
+    pub n: f64,
+    pub s: f64,
+    pub q: f64,
+}
+
+impl Zipf {
+    pub fn new(n: f64, s: f64, q: f64) -> Result<Self, String> {
+        require(s.is_finite() && s >= 0.0, "s must be non-negative")?;
+        require(
+            (count(n) && n >= 1.0) || (n == f64::INFINITY && s > 1.0),
+            "n must be a positive integer, or infinite with s > 1",
+        )?;
+        require(q.is_finite() && q > -1.0, "q must exceed -1")?;
+        Ok(Self { n, s, q })
+    }
+
+    /// First two raw moments, from weight sums with the exponent lowered by
+    /// one and two: (k + q) w_s(k) = (1 + q) w_(s-1)(k)
+    fn raw_moments(&self) -> (f64, f64) {
+        let (n, s, q) = (self.n, self.s, self.q);
+        let sum = |t: f64| {
+            if n.is_infinite() && t <= 1.0 {
+                f64::INFINITY
+            } else {
+                weight_sum(1.0, n, t, q)
+            }
+        };
+        let (w0, w1, w2) = (sum(s), (1.0 + q) * sum(s - 1.0), (1.0 + q).powi(2) * sum(s - 2.0));
+        let m1 = (w1 - q * w0) / w0;
+        let m2 = (w2 - 2.0 * q * w1 + q * q * w0) / w0;
+        (m1, m2)
+    }
+}
+
+impl Distribution for Zipf {
+    forward_pq!(pzipf, qzipf, rzipf; n, s, q);
+
+    fn mean(&self) -> f64 {
+        self.raw_moments().0
+    }
+
+    fn variance(&self) -> f64 {
+        let (m1, m2) = self.raw_moments();
+        m2 - m1 * m1
+    }
+
+    fn support(&self) -> (f64, f64) {
+        (1.0, self.n)
+    }
+}
+
+impl DiscreteDistribution for Zipf {
+    forward_d!(dzipf; n, s, q);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    /// Compare `mean` and `variance` with sums over the mass function and
+    /// check that `quantile` inverts `cdf` on the bulk of the support
+    fn check<D: DiscreteDistribution + std::fmt::Debug>(dist: D, upper: u64) {
+        let (mut total, mut m1, mut m2) = (0.0, 0.0, 0.0);
+        for x in 0..=upper {
+            let x = x as f64;
+            let p = dist.pmf(x);
+            total += p;
+            m1 += p * x;
+            m2 += p * x * x;
+        }
+        assert!((total - 1.0).abs() < 1e-9, "total {total}");
+        assert!(
+            (m1 - dist.mean()).abs() < 1e-8 * dist.mean().max(1.0),
+            "mean {m1}"
+        );
+        let var = m2 - m1 * m1;
+        assert!(
+            (var - dist.variance()).abs() < 1e-7 * dist.variance().max(1.0),
+            "var {var}"
+        );
+        let (lo, hi) = dist.support();
+        for x in (lo as u64)..=(hi.min(upper as f64) as u64) {
+            let x = x as f64;
+            // Far in the upper tail the CDF no longer resolves single points
+            if dist.pmf(x) > 1e-12 && dist.cdf(x) < 1.0 - 1e-8 {
+                assert_eq!(dist.quantile(dist.cdf(x)), x, "{dist:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_families_moments_and_quantiles() {
+        check(Binomial::new(12.0, 0.3).unwrap(), 12);
+        check(Dirac::new(3.0).unwrap(), 5);
+        check(Geometric::new(0.4).unwrap(), 200);
+        check(Hypergeometric::new(7.0, 5.0, 6.0).unwrap(), 6);
+        check(NegativeBinomial::new(3.5, 0.6).unwrap(), 200);
+        check(Poisson::new(4.5).unwrap(), 100);
+        check(SignRank::new(9.0).unwrap(), 45);
+        check(Wilcoxon::new(4.0, 6.0).unwrap(), 24);
+        check(Zipf::new(20.0, 1.2, 0.5).unwrap(), 20);
+        check(Zipf::new(500.0, 0.8, 2.0).unwrap(), 500);
+        check(Zipf::new(f64::INFINITY, 6.5, 0.0).unwrap(), 5_000);
+    }
+
+    #[test]
+    fn test_sample_support() {
+        let mut rng = StdRng::seed_from_u64(47);
+        let dist = Hypergeometric::new(3.0, 10.0, 5.0).unwrap();
+        let (lo, hi) = dist.support();
+        assert_eq!((lo, hi), (0.0, 3.0));
+        let draws = dist.sample_n(1_000, &mut rng);
+        assert!(
+            draws
+                .iter()
+                .all(|&v| (lo..=hi).contains(&v) && v.fract() == 0.0)
+        );
+        assert_eq!(Dirac::new(2.0).unwrap().sample(&mut rng), 2.0);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(Binomial::new(2.5, 0.5).is_err());
+        assert!(Binomial::new(2.0, 1.5).is_err());
+        assert!(Geometric::new(0.0).is_err());
+        assert!(Hypergeometric::new(2.0, 2.0, 5.0).is_err());
+        assert!(Zipf::new(10.0, 1.0, -1.0).is_err());
+        assert!(Zipf::new(f64::INFINITY, 1.0, 0.0).is_err());
+        assert!(SignRank::new(0.0).is_err());
+    }
+}
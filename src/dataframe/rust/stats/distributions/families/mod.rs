@@ -0,0 +1,50 @@
+//! Parameterised distribution values
+//!
+//! One struct per univariate distribution, implementing the traits in
+//! [`traits`](super::traits) by forwarding to the free d/p/q/r functions.
+//! Constructors validate the parameters and return an error rather than
+//! producing NaN on every call; the fields stay public for reading.
+//!
+//! This module is not glob re-exported: several names (`Normal`, `Gamma`,
+//! `StudentsT`, ...) coincide with statrs types used elsewhere.
+
+/// Forward `Distribution::p` and `q` (and `sample`, when an `r*` generator
+/// exists) to the free functions, passing the listed fields in order
+macro_rules! forward_pq {
+    ($p:ident, $q:ident; $($field:ident),+) => {
+        fn p(&self, x: f64, lower_tail: bool, log_p: bool) -> f64 {
+            $p(x, $(self.$field,)+ lower_tail, log_p)
+        }
+
+        fn q(&self, p: f64, lower_tail: bool, log_p: bool) -> f64 {
+            $q(p, $(self.$field,)+ lower_tail, log_p)
+        }
+    };
+    ($p:ident, $q:ident, $r:ident; $($field:ident),+) => {
+        forward_pq!($p, $q; $($field),+);
+
+        fn sample<R: Rng>(&self, rng: &mut R) -> f64 {
+            $r($(self.$field,)+ rng)
+        }
+    };
+}
+
+/// Forward the density or mass function `d` to a free `d*` function
+macro_rules! forward_d {
+    ($d:ident; $($field:ident),+) => {
+        fn d(&self, x: f64, give_log: bool) -> f64 {
+            $d(x, $(self.$field,)+ give_log)
+        }
+    };
+}
+
+pub mod continuous;
+pub mod discrete;
+
+pub use continuous::*;
+pub use discrete::*;
+
+/// Error with `message` unless `ok`
+pub(crate) fn require(ok: bool, message: &str) -> Result<(), String> {
+    if ok { Ok(()) } else { Err(message.to_string()) }
+}
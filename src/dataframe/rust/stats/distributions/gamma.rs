@@ -1,14 +1,26 @@
-//! Gamma distribution wrapper using statrs
-//! 
-//! Simple implementation following the pattern of normal.rs
+//! Gamma distribution
+//!
+//! Density, distribution function and quantile are ports of R's nmath so
+//! that both tails and `log_p` results stay accurate far into the tails;
+//! sampling uses statrs. Parameterised by shape and rate like R's
+//! `dgamma(x, shape, rate)`.
+//!
+//! Source: R-4.5.1/nmath/dgamma.c, pgamma.c, qgamma.c
 
+use super::helpers::dpois_raw::dpois_raw;
+use super::helpers::dpq::{d_0, dt_clog, dt_log, dt_qiv, q_p01_boundaries};
+use super::helpers::pgamma::lgamma1p;
+use super::helpers::regularized_incomplete::pgamma_raw;
+use super::normal::qnorm;
 use rand::Rng;
-use statrs::distribution::{Continuous, ContinuousCDF, Gamma};
+use statrs::distribution::Gamma;
+use statrs::function::gamma::ln_gamma;
+use std::f64::consts::LN_2;
 
 /// Gamma probability density function
 ///
 /// # Arguments
-/// * `x` - Value at which to evaluate density  
+/// * `x` - Value at which to evaluate density
 /// * `shape` - Shape parameter (α > 0)
 /// * `rate` - Rate parameter (β > 0)
 /// * `give_log` - If true, return log density
@@ -16,16 +28,40 @@ use statrs::distribution::{Continuous, ContinuousCDF, Gamma};
 /// # Returns
 /// The gamma density or log density
 pub fn dgamma(x: f64, shape: f64, rate: f64, give_log: bool) -> f64 {
+    if x.is_nan() || shape.is_nan() || rate.is_nan() {
+        return x + shape + rate;
+    }
     if shape <= 0.0 || rate <= 0.0 {
         return f64::NAN;
     }
-    // statrs::Gamma uses shape and rate parameters
-    let dist = Gamma::new(shape, rate).expect("validated parameters: shape > 0, rate > 0");
-    if give_log {
-        dist.ln_pdf(x)
-    } else {
-        dist.pdf(x)
+    if x < 0.0 {
+        return d_0(give_log);
+    }
+    if x == 0.0 {
+        if shape < 1.0 {
+            return f64::INFINITY;
+        }
+        if shape > 1.0 {
+            return d_0(give_log);
+        }
+        return if give_log { rate.ln() } else { rate };
+    }
+
+    if shape < 1.0 {
+        let pr = dpois_raw(shape, x * rate, give_log);
+        return if give_log {
+            pr + if (shape / x).is_finite() {
+                (shape / x).ln()
+            } else {
+                shape.ln() - x.ln()
+            }
+        } else {
+            pr * shape / x
+        };
     }
+    // shape >= 1
+    let pr = dpois_raw(shape - 1.0, x * rate, give_log);
+    if give_log { pr + rate.ln() } else { pr * rate }
 }
 
 /// Gamma cumulative distribution function
@@ -40,16 +76,85 @@ pub fn dgamma(x: f64, shape: f64, rate: f64, give_log: bool) -> f64 {
 /// # Returns
 /// The cumulative probability or log cumulative probability
 pub fn pgamma(x: f64, shape: f64, rate: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || shape.is_nan() || rate.is_nan() {
+        return x + shape + rate;
+    }
     if shape <= 0.0 || rate <= 0.0 {
         return f64::NAN;
     }
-    let dist = Gamma::new(shape, rate).expect("validated parameters: shape > 0, rate > 0");
-    let cdf = if lower_tail { dist.cdf(x) } else { 1.0 - dist.cdf(x) };
-    if log_p { cdf.ln() } else { cdf }
+    let x = x * rate;
+    if x.is_nan() {
+        // e.g. x = Inf with an infinitesimal rate
+        return x;
+    }
+    pgamma_raw(x, shape, lower_tail, log_p)
+}
+
+/// Starting approximation for a chi-squared quantile
+///
+/// `g` is `ln_gamma(nu / 2)`; `tol` is the relative tolerance for the
+/// small-`nu` iteration.
+fn qchisq_appr(p: f64, nu: f64, g: f64, lower_tail: bool, log_p: bool, tol: f64) -> f64 {
+    const C7: f64 = 4.67;
+    const C8: f64 = 6.66;
+    const C9: f64 = 6.73;
+    const C10: f64 = 13.32;
+
+    if p.is_nan() || nu.is_nan() {
+        return p + nu;
+    }
+    if nu <= 0.0 {
+        return f64::NAN;
+    }
+
+    let alpha = 0.5 * nu; // = [pq]gamma() shape
+    let c = alpha - 1.0;
+
+    let p1 = dt_log(p, lower_tail, log_p);
+    if nu < -1.24 * p1 {
+        // For small chi-squared.
+        // log(alpha) + g = log(alpha * gamma(alpha)) = lgamma(alpha + 1)
+        // suffers from catastrophic cancellation when alpha << 1
+        let lgam1pa = if alpha < 0.5 {
+            lgamma1p(alpha)
+        } else {
+            alpha.ln() + g
+        };
+        ((lgam1pa + p1) / alpha + LN_2).exp()
+    } else if nu > 0.32 {
+        // Wilson and Hilferty estimate
+        let x = qnorm(p, 0.0, 1.0, lower_tail, log_p);
+        let p1 = 2.0 / (9.0 * nu);
+        let ch = nu * (x * p1.sqrt() + 1.0 - p1).powi(3);
+
+        // Approximation for p tending to 1
+        if ch > 2.2 * nu + 6.0 {
+            -2.0 * (dt_clog(p, lower_tail, log_p) - c * (0.5 * ch).ln() + g)
+        } else {
+            ch
+        }
+    } else {
+        // "Small nu": 1.24 * (-log(p)) <= nu <= 0.32
+        let mut ch: f64 = 0.4;
+        let a = dt_clog(p, lower_tail, log_p) + g + c * LN_2;
+        loop {
+            let q = ch;
+            let p1 = 1.0 / (1.0 + ch * (C7 + ch));
+            let p2 = ch * (C9 + ch * (C8 + ch));
+            let t = -0.5 + (C7 + 2.0 * ch) * p1 - (C9 + ch * (C10 + 3.0 * ch)) / p2;
+            ch -= (1.0 - (a + 0.5 * ch).exp() * p2 * p1) / t;
+            if (q - ch).abs() <= tol * ch.abs() {
+                break ch;
+            }
+        }
+    }
 }
 
 /// Gamma quantile function
 ///
+/// AS 91 (Best and Roberts, 1975) with a seven-term Taylor series,
+/// followed by Newton steps on the log scale for full double accuracy.
+///
 /// # Arguments
 /// * `p` - Probability
 /// * `shape` - Shape parameter (α > 0)
@@ -60,16 +165,129 @@ pub fn pgamma(x: f64, shape: f64, rate: f64, lower_tail: bool, log_p: bool) -> f
 /// # Returns
 /// The quantile value
 pub fn qgamma(p: f64, shape: f64, rate: f64, lower_tail: bool, log_p: bool) -> f64 {
+    // Final precision of AS 91
+    const EPS1: f64 = 1e-2;
+    const EPS2: f64 = 5e-7;
+    // Precision of Newton step / iterations
+    const EPS_N: f64 = 1e-15;
+    const MAXIT: usize = 1000;
+    const P_MIN: f64 = 1e-100;
+    const P_MAX: f64 = 1.0 - 1e-14;
+
+    const I420: f64 = 1.0 / 420.0;
+    const I2520: f64 = 1.0 / 2520.0;
+    const I5040: f64 = 1.0 / 5040.0;
+
+    if p.is_nan() || shape.is_nan() || rate.is_nan() {
+        return p + shape + rate;
+    }
     if shape <= 0.0 || rate <= 0.0 {
         return f64::NAN;
     }
-    let dist = Gamma::new(shape, rate).expect("validated parameters: shape > 0, rate > 0");
-    let mut p_val = if log_p { p.exp() } else { p };
-    if !lower_tail {
-        p_val = 1.0 - p_val;
+    if let Some(q) = q_p01_boundaries(p, 0.0, f64::INFINITY, lower_tail, log_p) {
+        return q;
+    }
+
+    let scale = 1.0 / rate;
+    let mut max_it_newton = if shape < 1e-10 { 7 } else { 1 };
+
+    // Lower-tail probability, in any case
+    let mut p_ = dt_qiv(p, lower_tail, log_p);
+    let g = ln_gamma(shape);
+
+    // Phase I: starting approximation
+    let mut ch = qchisq_appr(p, 2.0 * shape, g, lower_tail, log_p, EPS1);
+    'phase2: {
+        if !ch.is_finite() {
+            // Forget about all iterations
+            max_it_newton = 0;
+            break 'phase2;
+        }
+        if ch < EPS2 || !(P_MIN..=P_MAX).contains(&p_) {
+            max_it_newton = 20;
+            break 'phase2;
+        }
+
+        // Phase II: iteration with pgamma (AS 239) and a seven-term
+        // Taylor series
+        let c = shape - 1.0;
+        let s6 = (120.0 + c * (346.0 + 127.0 * c)) * I5040;
+
+        let ch0 = ch;
+        for _ in 0..MAXIT {
+            let q = ch;
+            let p1 = 0.5 * ch;
+            let p2 = p_ - pgamma_raw(p1, shape, true, false);
+            if !p2.is_finite() || ch <= 0.0 {
+                ch = ch0;
+                max_it_newton = 27;
+                break 'phase2;
+            }
+
+            let t = p2 * (shape * LN_2 + g + p1 - c * ch.ln()).exp();
+            let b = t / ch;
+            let a = 0.5 * t - b * c;
+
+            let s1 = (210.0 + a * (140.0 + a * (105.0 + a * (84.0 + a * (70.0 + 60.0 * a))))) * I420;
+            let s2 = (420.0 + a * (735.0 + a * (966.0 + a * (1141.0 + 1278.0 * a)))) * I2520;
+            let s3 = (210.0 + a * (462.0 + a * (707.0 + 932.0 * a))) * I2520;
+            let s4 = (252.0 + a * (672.0 + 1182.0 * a) + c * (294.0 + a * (889.0 + 1740.0 * a))) * I5040;
+            let s5 = (84.0 + 2264.0 * a + c * (1175.0 + 606.0 * a)) * I2520;
+
+            ch += t
+                * (1.0 + 0.5 * t * s1
+                    - b * c * (s1 - b * (s2 - b * (s3 - b * (s4 - b * (s5 - b * s6))))));
+            if (q - ch).abs() < EPS2 * ch {
+                break 'phase2;
+            }
+            if (q - ch).abs() > 0.1 * ch {
+                // Diverging? -- also forces ch > 0
+                ch = if ch < q { 0.9 * q } else { 1.1 * q };
+            }
+        }
+        // No convergence in MAXIT iterations, but Newton steps follow
+    }
+
+    let mut x = 0.5 * scale * ch;
+    if max_it_newton > 0 {
+        // Always use the log scale
+        let p = if log_p { p } else { p.ln() };
+        if x == 0.0 {
+            x = f64::MIN_POSITIVE;
+            p_ = pgamma(x, shape, rate, lower_tail, true);
+            if (lower_tail && p_ > p * (1.0 + 1e-7)) || (!lower_tail && p_ < p * (1.0 - 1e-7)) {
+                return 0.0;
+            }
+            // else: continue, using x = DBL_MIN instead of 0
+        } else {
+            p_ = pgamma(x, shape, rate, lower_tail, true);
+        }
+        if p_ == f64::NEG_INFINITY {
+            return 0.0;
+        }
+        for i in 1..=max_it_newton {
+            let p1 = p_ - p;
+            if p1.abs() < (EPS_N * p).abs() {
+                break;
+            }
+            let g = dgamma(x, shape, rate, true);
+            if g == f64::NEG_INFINITY {
+                break;
+            }
+            // delta x = f(x) / f'(x) with f(x) = log P(x) - p,
+            // f'(x) = P'(x) / P(x)
+            let t = p1 * (p_ - g).exp();
+            let t = if lower_tail { x - t } else { x + t };
+            p_ = pgamma(t, shape, rate, lower_tail, true);
+            if (p_ - p).abs() > p1.abs() || (i > 1 && (p_ - p).abs() == p1.abs()) {
+                // No improvement (or flip-flop)
+                break;
+            }
+            x = t;
+        }
     }
-    p_val = super::helpers::clamp_unit(p_val);
-    dist.inverse_cdf(p_val)
+
+    x
 }
 
 /// Gamma random number generation
@@ -128,7 +346,7 @@ mod tests {
     fn test_qgamma() {
         // Test with shape=2, rate=1
         let expected = 2.0; // qgamma(0.593994, 2, 1) in R
-        assert!((qgamma(0.5939941502901619, 2.0, 1.0, true, false) - expected).abs() < 1e-6);
+        assert!((qgamma(0.5939941502901619, 2.0, 1.0, true, false) - expected).abs() < 1e-12);
     }
 
     #[test]
@@ -153,4 +371,24 @@ mod tests {
         let expected = 2.0;
         assert!((qgamma(p, 2.0, 1.0, true, true) - expected).abs() < 1e-6);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_pgamma_extreme_tails() {
+        // Reference values from mpmath; 1 - cdf would give 0 or -inf here
+        let lp = pgamma(400.0, 3.0, 2.0, false, true);
+        assert!((lp / -787.321_423_727_823_3 - 1.0).abs() < 1e-14);
+        let lp = pgamma(3.0, 120.0, 1.0, true, true);
+        assert!((lp / -328.953_812_713_612_2 - 1.0).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_qgamma_extreme_tails() {
+        let q = qgamma(1e-300, 3.0, 1.0, false, false);
+        assert!((q / 703.196_497_600_461_4 - 1.0).abs() < 1e-13);
+        let q = qgamma(-1000.0, 3.0, 1.0, true, true);
+        assert!((q / 3.122_888_288_889_268e-145 - 1.0).abs() < 1e-13);
+        // Rate scales the quantile
+        let q = qgamma(1e-300, 3.0, 2.0, false, false);
+        assert!((2.0 * q / 703.196_497_600_461_4 - 1.0).abs() < 1e-13);
+    }
+}
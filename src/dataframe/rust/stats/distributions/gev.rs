@@ -0,0 +1,189 @@
+//! Generalized extreme value (GEV) distribution functions
+//!
+//! With z = (x - location) / scale and t(x) = (1 + shape z)^(-1/shape)
+//! (exp(-z) when shape = 0), F(x) = exp(-t(x)). `shape > 0` gives the
+//! Fréchet type with a lower bound, `shape < 0` the reversed Weibull type
+//! with an upper bound and `shape = 0` the Gumbel distribution.
+
+use super::helpers::dpq::{d_exp, dt_0, dt_1, dt_from_logs, dt_log, log1_exp, q_p01_invalid};
+use rand::Rng;
+use rand::distributions::Open01;
+
+/// log t(x) for standardised `z`, or `None` outside the support
+fn log_t(z: f64, shape: f64) -> Option<f64> {
+    if shape == 0.0 {
+        return Some(-z);
+    }
+    let arg = shape * z;
+    if arg <= -1.0 {
+        return None;
+    }
+    Some(-arg.ln_1p() / shape)
+}
+
+fn invalid(location: f64, scale: f64, shape: f64) -> bool {
+    !location.is_finite() || !scale.is_finite() || !shape.is_finite() || scale <= 0.0
+}
+
+/// GEV probability density function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate density
+/// * `location` - Location parameter
+/// * `scale` - Scale parameter (must be positive)
+/// * `shape` - Shape parameter ξ
+/// * `give_log` - If true, return log density
+///
+/// # Returns
+/// The GEV density or log density
+pub fn dgev(x: f64, location: f64, scale: f64, shape: f64, give_log: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() || shape.is_nan() {
+        return x + location + scale + shape;
+    }
+    if invalid(location, scale, shape) {
+        return f64::NAN;
+    }
+    let z = (x - location) / scale;
+    match log_t(z, shape) {
+        Some(lt) if lt.is_finite() => {
+            // f = t^(shape + 1) exp(-t) / scale
+            d_exp((shape + 1.0) * lt - lt.exp() - scale.ln(), give_log)
+        }
+        _ => d_exp(f64::NEG_INFINITY, give_log),
+    }
+}
+
+/// GEV cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate CDF
+/// * `location` - Location parameter
+/// * `scale` - Scale parameter (must be positive)
+/// * `shape` - Shape parameter ξ
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn pgev(x: f64, location: f64, scale: f64, shape: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() || shape.is_nan() {
+        return x + location + scale + shape;
+    }
+    if invalid(location, scale, shape) {
+        return f64::NAN;
+    }
+    let z = (x - location) / scale;
+    match log_t(z, shape) {
+        Some(lt) => {
+            let log_lower = -lt.exp();
+            dt_from_logs(log_lower, log1_exp(log_lower), lower_tail, log_p)
+        }
+        // Below the lower bound (shape > 0) or above the upper bound (shape < 0)
+        None if shape > 0.0 => dt_0(lower_tail, log_p),
+        None => dt_1(lower_tail, log_p),
+    }
+}
+
+/// GEV quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `location` - Location parameter
+/// * `scale` - Scale parameter (must be positive)
+/// * `shape` - Shape parameter ξ
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The quantile value
+pub fn qgev(p: f64, location: f64, scale: f64, shape: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || location.is_nan() || scale.is_nan() || shape.is_nan() {
+        return p + location + scale + shape;
+    }
+    if invalid(location, scale, shape) || q_p01_invalid(p, log_p) {
+        return f64::NAN;
+    }
+    // t = -log F, so log t = log(-log F)
+    let lt = (-dt_log(p, lower_tail, log_p)).ln();
+    location + scale * z_from_log_t(lt, shape)
+}
+
+/// Standardised quantile for a given log t
+fn z_from_log_t(lt: f64, shape: f64) -> f64 {
+    if shape == 0.0 {
+        -lt
+    } else {
+        (-shape * lt).exp_m1() / shape
+    }
+}
+
+/// GEV random number generation (by inversion)
+///
+/// # Arguments
+/// * `location` - Location parameter
+/// * `scale` - Scale parameter (must be positive)
+/// * `shape` - Shape parameter ξ
+/// * `rng` - Random number generator
+///
+/// # Returns
+/// A random sample from the GEV distribution
+pub fn rgev<R: Rng>(location: f64, scale: f64, shape: f64, rng: &mut R) -> f64 {
+    if invalid(location, scale, shape) {
+        return f64::NAN;
+    }
+    let u: f64 = rng.sample(Open01);
+    location + scale * z_from_log_t((-u.ln()).ln(), shape)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::distributions::gumbel::{dgumbel, pgumbel};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_shape_zero_is_gumbel() {
+        for &x in &[-1.0, 0.3, 4.0] {
+            assert!((dgev(x, 1.0, 2.0, 0.0, false) - dgumbel(x, 1.0, 2.0, false)).abs() < 1e-15);
+            assert!(
+                (pgev(x, 1.0, 2.0, 0.0, false, true) - pgumbel(x, 1.0, 2.0, false, true)).abs()
+                    < 1e-15
+            );
+        }
+    }
+
+    #[test]
+    fn test_frechet_and_weibull_types() {
+        // shape 0.5: support x > location - scale / shape = -2
+        assert_eq!(pgev(-3.0, 0.0, 1.0, 0.5, true, false), 0.0);
+        assert_eq!(dgev(-3.0, 0.0, 1.0, 0.5, false), 0.0);
+        // F(1) = exp(-(1.5)^(-2))
+        let expected = (-(1.5f64).powf(-2.0)).exp();
+        assert!((pgev(1.0, 0.0, 1.0, 0.5, true, false) - expected).abs() < 1e-15);
+        // shape -0.5: support x < 2
+        assert_eq!(pgev(3.0, 0.0, 1.0, -0.5, true, false), 1.0);
+        assert_eq!(pgev(3.0, 0.0, 1.0, -0.5, false, true), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_qgev_inverts_pgev() {
+        for &shape in &[-0.3, 0.0, 0.2] {
+            for &x in &[-0.5, 0.0, 1.0] {
+                let p = pgev(x, 0.0, 1.0, shape, true, false);
+                assert!((qgev(p, 0.0, 1.0, shape, true, false) - x).abs() < 1e-9);
+                let lq = pgev(x, 0.0, 1.0, shape, false, true);
+                assert!((qgev(lq, 0.0, 1.0, shape, false, true) - x).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rgev_support() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..1000 {
+            assert!(rgev(0.0, 1.0, -0.5, &mut rng) < 2.0);
+            assert!(rgev(0.0, 1.0, 0.5, &mut rng) > -2.0);
+        }
+    }
+}
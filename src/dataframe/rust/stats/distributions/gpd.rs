@@ -0,0 +1,182 @@
+//! Generalized Pareto distribution (GPD) functions
+//!
+//! The peaks-over-threshold model: with z = (x - location) / scale ≥ 0,
+//! P(X > x) = (1 + shape z)^(-1/shape) (exp(-z) when shape = 0). For
+//! `shape < 0` the support ends at z = -1/shape.
+
+use super::helpers::dpq::{d_exp, dt_0, dt_1, dt_clog, dt_from_logs, log1_exp, q_p01_invalid};
+use rand::Rng;
+use rand::distributions::Open01;
+
+fn invalid(location: f64, scale: f64, shape: f64) -> bool {
+    !location.is_finite() || !scale.is_finite() || !shape.is_finite() || scale <= 0.0
+}
+
+/// log P(X > x) for standardised `z` inside the support
+fn log_survival(z: f64, shape: f64) -> f64 {
+    if shape == 0.0 {
+        -z
+    } else {
+        -(shape * z).ln_1p() / shape
+    }
+}
+
+/// Whether standardised `z` lies above the upper end point (shape < 0)
+fn above_support(z: f64, shape: f64) -> bool {
+    shape < 0.0 && shape * z <= -1.0
+}
+
+/// GPD probability density function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate density
+/// * `location` - Location (threshold) parameter
+/// * `scale` - Scale parameter (must be positive)
+/// * `shape` - Shape parameter ξ
+/// * `give_log` - If true, return log density
+///
+/// # Returns
+/// The GPD density or log density
+pub fn dgpd(x: f64, location: f64, scale: f64, shape: f64, give_log: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() || shape.is_nan() {
+        return x + location + scale + shape;
+    }
+    if invalid(location, scale, shape) {
+        return f64::NAN;
+    }
+    let z = (x - location) / scale;
+    if z < 0.0 || z.is_infinite() || above_support(z, shape) {
+        return d_exp(f64::NEG_INFINITY, give_log);
+    }
+    // f = S(x)^(1 + shape) / scale
+    d_exp(
+        (1.0 + shape) * log_survival(z, shape) - scale.ln(),
+        give_log,
+    )
+}
+
+/// GPD cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate CDF
+/// * `location` - Location (threshold) parameter
+/// * `scale` - Scale parameter (must be positive)
+/// * `shape` - Shape parameter ξ
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn pgpd(x: f64, location: f64, scale: f64, shape: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() || shape.is_nan() {
+        return x + location + scale + shape;
+    }
+    if invalid(location, scale, shape) {
+        return f64::NAN;
+    }
+    let z = (x - location) / scale;
+    if z <= 0.0 {
+        return dt_0(lower_tail, log_p);
+    }
+    if above_support(z, shape) {
+        return dt_1(lower_tail, log_p);
+    }
+    let log_upper = log_survival(z, shape);
+    dt_from_logs(log1_exp(log_upper), log_upper, lower_tail, log_p)
+}
+
+/// GPD quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `location` - Location (threshold) parameter
+/// * `scale` - Scale parameter (must be positive)
+/// * `shape` - Shape parameter ξ
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The quantile value
+pub fn qgpd(p: f64, location: f64, scale: f64, shape: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || location.is_nan() || scale.is_nan() || shape.is_nan() {
+        return p + location + scale + shape;
+    }
+    if invalid(location, scale, shape) || q_p01_invalid(p, log_p) {
+        return f64::NAN;
+    }
+    location + scale * z_from_log_survival(dt_clog(p, lower_tail, log_p), shape)
+}
+
+/// Standardised quantile for a given log survival probability
+fn z_from_log_survival(log_s: f64, shape: f64) -> f64 {
+    if shape == 0.0 {
+        -log_s
+    } else {
+        (-shape * log_s).exp_m1() / shape
+    }
+}
+
+/// GPD random number generation (by inversion)
+///
+/// # Arguments
+/// * `location` - Location (threshold) parameter
+/// * `scale` - Scale parameter (must be positive)
+/// * `shape` - Shape parameter ξ
+/// * `rng` - Random number generator
+///
+/// # Returns
+/// A random sample from the GPD
+pub fn rgpd<R: Rng>(location: f64, scale: f64, shape: f64, rng: &mut R) -> f64 {
+    if invalid(location, scale, shape) {
+        return f64::NAN;
+    }
+    let u: f64 = rng.sample(Open01);
+    location + scale * z_from_log_survival(u.ln(), shape)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_shape_zero_is_exponential() {
+        // GPD(0, 2, 0) is exponential with rate 1/2
+        let x = 1.3;
+        assert!((dgpd(x, 0.0, 2.0, 0.0, false) - 0.5 * (-x / 2.0f64).exp()).abs() < 1e-15);
+        assert!((pgpd(x, 0.0, 2.0, 0.0, false, true) + x / 2.0).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_support_and_values() {
+        // shape 0.5, scale 1: S(2) = (1 + 1)^(-2) = 0.25
+        assert!((pgpd(2.0, 0.0, 1.0, 0.5, false, false) - 0.25).abs() < 1e-15);
+        assert!((dgpd(2.0, 0.0, 1.0, 0.5, false) - 0.125).abs() < 1e-15);
+        assert_eq!(pgpd(-1.0, 0.0, 1.0, 0.5, true, false), 0.0);
+        // shape -0.5: upper end point at z = 2
+        assert_eq!(pgpd(2.5, 0.0, 1.0, -0.5, true, false), 1.0);
+        assert_eq!(dgpd(2.5, 0.0, 1.0, -0.5, false), 0.0);
+    }
+
+    #[test]
+    fn test_qgpd_inverts_pgpd() {
+        for &shape in &[-0.4, 0.0, 0.3] {
+            for &x in &[0.1, 0.9, 1.8] {
+                let p = pgpd(x, 0.0, 1.0, shape, true, false);
+                assert!((qgpd(p, 0.0, 1.0, shape, true, false) - x).abs() < 1e-10);
+                let lq = pgpd(x, 0.0, 1.0, shape, false, true);
+                assert!((qgpd(lq, 0.0, 1.0, shape, false, true) - x).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rgpd_support() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..1000 {
+            let x = rgpd(1.0, 1.0, -0.5, &mut rng);
+            assert!((1.0..=3.0).contains(&x));
+        }
+    }
+}
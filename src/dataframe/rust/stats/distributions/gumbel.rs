@@ -0,0 +1,144 @@
+//! Gumbel (extreme value type I) distribution functions
+//!
+//! The distribution of maxima with location `location` and scale `scale`:
+//! F(x) = exp(-exp(-(x - location) / scale)). Tails are computed on the log
+//! scale so that `lower_tail = false` and `log_p = true` stay accurate far
+//! into either tail.
+
+use super::helpers::dpq::{d_exp, dt_from_logs, dt_log, log1_exp, q_p01_invalid};
+use rand::Rng;
+use rand::distributions::Open01;
+
+/// Gumbel probability density function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate density
+/// * `location` - Location (mode) of the distribution
+/// * `scale` - Scale of the distribution (must be positive)
+/// * `give_log` - If true, return log density
+///
+/// # Returns
+/// The Gumbel density or log density
+pub fn dgumbel(x: f64, location: f64, scale: f64, give_log: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() {
+        return x + location + scale;
+    }
+    if scale <= 0.0 {
+        return f64::NAN;
+    }
+    let z = (x - location) / scale;
+    if z.is_infinite() {
+        return d_exp(f64::NEG_INFINITY, give_log);
+    }
+    d_exp(-scale.ln() - z - (-z).exp(), give_log)
+}
+
+/// Gumbel cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate CDF
+/// * `location` - Location (mode) of the distribution
+/// * `scale` - Scale of the distribution (must be positive)
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn pgumbel(x: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() {
+        return x + location + scale;
+    }
+    if scale <= 0.0 {
+        return f64::NAN;
+    }
+    // log P(X <= x) = -exp(-z)
+    let log_lower = -(-(x - location) / scale).exp();
+    dt_from_logs(log_lower, log1_exp(log_lower), lower_tail, log_p)
+}
+
+/// Gumbel quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `location` - Location (mode) of the distribution
+/// * `scale` - Scale of the distribution (must be positive)
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The quantile value
+pub fn qgumbel(p: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || location.is_nan() || scale.is_nan() {
+        return p + location + scale;
+    }
+    if scale <= 0.0 || q_p01_invalid(p, log_p) {
+        return f64::NAN;
+    }
+    location - scale * (-dt_log(p, lower_tail, log_p)).ln()
+}
+
+/// Gumbel random number generation (by inversion)
+///
+/// # Arguments
+/// * `location` - Location (mode) of the distribution
+/// * `scale` - Scale of the distribution (must be positive)
+/// * `rng` - Random number generator
+///
+/// # Returns
+/// A random sample from the Gumbel distribution
+pub fn rgumbel<R: Rng>(location: f64, scale: f64, rng: &mut R) -> f64 {
+    if !location.is_finite() || !scale.is_finite() || scale < 0.0 {
+        return f64::NAN;
+    }
+    let u: f64 = rng.sample(Open01);
+    location - scale * (-u.ln()).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_dgumbel() {
+        // Standard Gumbel: f(0) = exp(-1)
+        assert!((dgumbel(0.0, 0.0, 1.0, false) - (-1.0f64).exp()).abs() < 1e-15);
+        // f(x; 1, 2) = exp(-(z + exp(-z))) / 2 with z = 0.5
+        let expected = (-(0.5 + (-0.5f64).exp())).exp() / 2.0;
+        assert!((dgumbel(2.0, 1.0, 2.0, false) - expected).abs() < 1e-15);
+        assert!((dgumbel(2.0, 1.0, 2.0, true) - expected.ln()).abs() < 1e-14);
+        assert!(dgumbel(0.0, 0.0, -1.0, false).is_nan());
+    }
+
+    #[test]
+    fn test_pgumbel_tails() {
+        let p = pgumbel(1.0, 0.0, 1.0, true, false);
+        assert!((p - (-(-1.0f64).exp()).exp()).abs() < 1e-15);
+        let q = pgumbel(1.0, 0.0, 1.0, false, false);
+        assert!((p + q - 1.0).abs() < 1e-15);
+        // Far upper tail: P(X > 40) ≈ exp(-40)
+        let log_upper = pgumbel(40.0, 0.0, 1.0, false, true);
+        assert!((log_upper + 40.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_qgumbel_inverts_pgumbel() {
+        for &x in &[-2.0, 0.0, 0.7, 5.0] {
+            let p = pgumbel(x, 1.0, 2.0, true, false);
+            assert!((qgumbel(p, 1.0, 2.0, true, false) - x).abs() < 1e-10);
+            let lq = pgumbel(x, 1.0, 2.0, false, true);
+            assert!((qgumbel(lq, 1.0, 2.0, false, true) - x).abs() < 1e-10);
+        }
+        assert!(qgumbel(1.5, 0.0, 1.0, true, false).is_nan());
+    }
+
+    #[test]
+    fn test_rgumbel() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let n = 20_000;
+        let mean = (0..n).map(|_| rgumbel(0.0, 1.0, &mut rng)).sum::<f64>() / n as f64;
+        // Mean of the standard Gumbel is the Euler–Mascheroni constant
+        assert!((mean - 0.5772156649).abs() < 0.03);
+    }
+}
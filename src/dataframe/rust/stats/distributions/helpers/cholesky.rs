@@ -0,0 +1,106 @@
+//! Cholesky factor of a covariance matrix
+//!
+//! Shared by the multivariate normal and t distributions. The factorisation
+//! itself is done by `faer`; the triangular products and solves needed for
+//! densities and sampling are small enough to do directly.
+
+use faer::{Mat, Side};
+
+/// Lower-triangular factor `L` with `sigma = L L'`
+pub(crate) struct Cholesky {
+    /// Dimension
+    pub d: usize,
+    /// Row-major `d x d` factor (upper triangle is zero)
+    l: Vec<f64>,
+}
+
+impl Cholesky {
+    /// Factor a symmetric positive-definite `d x d` matrix given row-major
+    /// (a symmetric matrix reads the same in either order)
+    pub fn new(sigma: &[f64], d: usize) -> Result<Cholesky, String> {
+        if d == 0 {
+            return Err("dimension must be at least 1".to_string());
+        }
+        if sigma.len() != d * d {
+            return Err(format!(
+                "sigma has {} elements, expected {} for a {}x{} matrix",
+                sigma.len(),
+                d * d,
+                d,
+                d
+            ));
+        }
+        if sigma.iter().any(|v| !v.is_finite()) {
+            return Err("sigma contains non-finite values".to_string());
+        }
+        for i in 0..d {
+            for j in 0..i {
+                let (a, b) = (sigma[i * d + j], sigma[j * d + i]);
+                if (a - b).abs() > 1e-8 * a.abs().max(b.abs()).max(1.0) {
+                    return Err("sigma must be symmetric".to_string());
+                }
+            }
+        }
+        let mat = Mat::from_fn(d, d, |i, j| sigma[i * d + j]);
+        let llt = mat
+            .llt(Side::Lower)
+            .map_err(|_| "sigma must be positive definite".to_string())?;
+        let factor = llt.L();
+        let mut l = vec![0.0; d * d];
+        for i in 0..d {
+            for j in 0..=i {
+                l[i * d + j] = factor[(i, j)];
+            }
+        }
+        Ok(Cholesky { d, l })
+    }
+
+    /// Entry `L[i, j]`
+    #[inline]
+    pub fn at(&self, i: usize, j: usize) -> f64 {
+        self.l[i * self.d + j]
+    }
+
+    /// `log(det(sigma)) / 2`, i.e. the sum of the log diagonal of `L`
+    pub fn half_log_det(&self) -> f64 {
+        (0..self.d).map(|i| self.at(i, i).ln()).sum()
+    }
+
+    /// Squared Mahalanobis norm `v' sigma^-1 v` by forward substitution
+    pub fn mahalanobis(&self, v: &[f64]) -> f64 {
+        let mut z = vec![0.0; self.d];
+        for i in 0..self.d {
+            let s: f64 = (0..i).map(|j| self.at(i, j) * z[j]).sum();
+            z[i] = (v[i] - s) / self.at(i, i);
+        }
+        z.iter().map(|z| z * z).sum()
+    }
+
+    /// `L z`, mapping independent standard normals to covariance `sigma`
+    pub fn mul(&self, z: &[f64]) -> Vec<f64> {
+        (0..self.d)
+            .map(|i| (0..=i).map(|j| self.at(i, j) * z[j]).sum())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cholesky() {
+        let sigma = [4.0, 2.0, 2.0, 3.0];
+        let chol = Cholesky::new(&sigma, 2).unwrap();
+        assert!((chol.at(0, 0) - 2.0).abs() < 1e-14);
+        assert!((chol.at(1, 0) - 1.0).abs() < 1e-14);
+        assert!((chol.at(1, 1) - 2f64.sqrt()).abs() < 1e-14);
+        // det = 8
+        assert!((chol.half_log_det() - 0.5 * 8f64.ln()).abs() < 1e-14);
+        // sigma^-1 = [3, -2; -2, 4] / 8
+        assert!((chol.mahalanobis(&[1.0, 1.0]) - 3.0 / 8.0).abs() < 1e-14);
+        assert!(Cholesky::new(&[1.0, 2.0, 2.0, 1.0], 2).is_err());
+        assert!(Cholesky::new(&[1.0, 0.5, 0.0, 1.0], 2).is_err());
+        assert!(Cholesky::new(&[1.0], 2).is_err());
+    }
+}
@@ -0,0 +1,231 @@
+//! Tail and log-scale conversions for d/p/q functions
+//!
+//! Ports of the macros in R's `nmath/dpq.h`. They let closed-form
+//! distributions compute a log-probability once and return whichever of
+//! `lower_tail`/`log_p` the caller asked for without losing accuracy.
+
+use std::f64::consts::LN_2;
+
+/// `log(1 - exp(x))` for `x <= 0` (R's `R_Log1_Exp`)
+#[inline]
+pub fn log1_exp(x: f64) -> f64 {
+    if x > -LN_2 {
+        (-x.exp_m1()).ln()
+    } else {
+        (-x.exp()).ln_1p()
+    }
+}
+
+/// `log(1 + exp(x))` without overflow (R's `log1pexp`)
+#[inline]
+pub fn log1pexp(x: f64) -> f64 {
+    if x <= 18.0 {
+        x.exp().ln_1p()
+    } else if x > 33.3 {
+        x
+    } else {
+        x + (-x).exp()
+    }
+}
+
+/// `log(exp(lx) + exp(ly))` without overflow (R's `logspace_add`)
+#[inline]
+pub fn logspace_add(lx: f64, ly: f64) -> f64 {
+    if lx == f64::NEG_INFINITY {
+        return ly;
+    }
+    if ly == f64::NEG_INFINITY {
+        return lx;
+    }
+    lx.max(ly) + (-(lx - ly).abs()).exp().ln_1p()
+}
+
+/// `log(exp(lx) - exp(ly))` for `lx >= ly` (R's `logspace_sub`)
+///
+/// Returns `-inf` when `ly >= lx`, so rounding never yields a NaN.
+#[inline]
+pub fn logspace_sub(lx: f64, ly: f64) -> f64 {
+    if ly == f64::NEG_INFINITY {
+        return lx;
+    }
+    if ly >= lx {
+        return f64::NEG_INFINITY;
+    }
+    lx + log1_exp(ly - lx)
+}
+
+/// Density value 0 on the requested scale (`R_D__0`)
+#[inline]
+pub fn d_0(give_log: bool) -> f64 {
+    if give_log { f64::NEG_INFINITY } else { 0.0 }
+}
+
+/// Density value 1 on the requested scale (`R_D__1`)
+#[inline]
+pub fn d_1(give_log: bool) -> f64 {
+    if give_log { 0.0 } else { 1.0 }
+}
+
+/// Density from its logarithm (`R_D_exp`)
+#[inline]
+pub fn d_exp(log_value: f64, give_log: bool) -> f64 {
+    if give_log { log_value } else { log_value.exp() }
+}
+
+/// Probability 0 for the requested tail and scale (`R_DT_0`)
+#[inline]
+pub fn dt_0(lower_tail: bool, log_p: bool) -> f64 {
+    if lower_tail { d_0(log_p) } else { d_1(log_p) }
+}
+
+/// Probability 1 for the requested tail and scale (`R_DT_1`)
+#[inline]
+pub fn dt_1(lower_tail: bool, log_p: bool) -> f64 {
+    if lower_tail { d_1(log_p) } else { d_0(log_p) }
+}
+
+/// Lower-tail probability on the requested tail and scale (`R_DT_val`)
+#[inline]
+pub fn dt_val(p: f64, lower_tail: bool, log_p: bool) -> f64 {
+    match (lower_tail, log_p) {
+        (true, true) => p.ln(),
+        (true, false) => p,
+        (false, true) => (-p).ln_1p(),
+        (false, false) => 0.5 - p + 0.5,
+    }
+}
+
+/// Tail probability from the logs of both tails
+///
+/// `log_lower` and `log_upper` are `log P(X <= x)` and `log P(X > x)`; the
+/// one matching `lower_tail` is returned on the requested scale.
+#[inline]
+pub fn dt_from_logs(log_lower: f64, log_upper: f64, lower_tail: bool, log_p: bool) -> f64 {
+    d_exp(if lower_tail { log_lower } else { log_upper }, log_p)
+}
+
+/// Whether `p` is outside `[0, 1]` (or `(-inf, 0]` on the log scale)
+#[inline]
+pub fn q_p01_invalid(p: f64, log_p: bool) -> bool {
+    if log_p {
+        p > 0.0
+    } else {
+        !(0.0..=1.0).contains(&p)
+    }
+}
+
+/// Quantile at the edges of the probability range (`R_Q_P01_boundaries`)
+///
+/// Returns `Some(NaN)` for an invalid `p`, `Some(left)`/`Some(right)` when
+/// `p` is 0 or 1 on the requested tail and scale, and `None` otherwise.
+#[inline]
+pub fn q_p01_boundaries(
+    p: f64,
+    left: f64,
+    right: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> Option<f64> {
+    if q_p01_invalid(p, log_p) {
+        return Some(f64::NAN);
+    }
+    let (at_zero, at_one) = if lower_tail {
+        (left, right)
+    } else {
+        (right, left)
+    };
+    if log_p {
+        if p == 0.0 {
+            return Some(at_one);
+        }
+        if p == f64::NEG_INFINITY {
+            return Some(at_zero);
+        }
+    } else {
+        if p == 0.0 {
+            return Some(at_zero);
+        }
+        if p == 1.0 {
+            return Some(at_one);
+        }
+    }
+    None
+}
+
+/// `log P(X <= x)` for a quantile argument (`R_DT_log`)
+#[inline]
+pub fn dt_log(p: f64, lower_tail: bool, log_p: bool) -> f64 {
+    match (lower_tail, log_p) {
+        (true, true) => p,
+        (true, false) => p.ln(),
+        (false, true) => log1_exp(p),
+        (false, false) => (-p).ln_1p(),
+    }
+}
+
+/// `log P(X > x)` for a quantile argument (`R_DT_Clog`)
+#[inline]
+pub fn dt_clog(p: f64, lower_tail: bool, log_p: bool) -> f64 {
+    dt_log(p, !lower_tail, log_p)
+}
+
+/// `P(X <= x)` for a quantile argument (`R_DT_qIv`)
+#[inline]
+pub fn dt_qiv(p: f64, lower_tail: bool, log_p: bool) -> f64 {
+    match (lower_tail, log_p) {
+        (true, true) => p.exp(),
+        (true, false) => p,
+        (false, true) => -p.exp_m1(),
+        (false, false) => 0.5 - p + 0.5,
+    }
+}
+
+/// `P(X > x)` for a quantile argument (`R_DT_CIv`)
+#[inline]
+pub fn dt_civ(p: f64, lower_tail: bool, log_p: bool) -> f64 {
+    dt_qiv(p, !lower_tail, log_p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log1_exp_both_branches() {
+        for x in [-1e-20, -0.1, -0.5, -2.0, -50.0] {
+            let direct = (1.0 - f64::exp(x)).ln();
+            if x < -1e-10 {
+                assert!((log1_exp(x) - direct).abs() < 1e-12 * direct.abs().max(1.0));
+            }
+        }
+        assert!((log1_exp(-1e-20) - (1e-20f64).ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_tail_conversions_round_trip() {
+        let p = 0.2f64;
+        assert!((dt_log(p, true, false) - p.ln()).abs() < 1e-15);
+        assert!((dt_clog(p, true, false) - 0.8f64.ln()).abs() < 1e-15);
+        assert!((dt_qiv(p.ln(), false, true) - 0.8).abs() < 1e-15);
+        assert!((dt_civ(p, true, false) - 0.8).abs() < 1e-15);
+        assert!(q_p01_invalid(1.5, false) && q_p01_invalid(0.1, true));
+    }
+
+    #[test]
+    fn test_log1pexp_and_boundaries() {
+        assert!((log1pexp(0.0) - 2.0f64.ln()).abs() < 1e-15);
+        assert!((log1pexp(-40.0) - (-40.0f64).exp()).abs() < 1e-30);
+        assert_eq!(log1pexp(800.0), 800.0);
+        assert!((logspace_add(1.0f64.ln(), 3.0f64.ln()) - 4.0f64.ln()).abs() < 1e-15);
+        assert_eq!(logspace_add(f64::NEG_INFINITY, -2.0), -2.0);
+        assert!((logspace_sub(4.0f64.ln(), 3.0f64.ln())).abs() < 1e-15);
+        assert_eq!(logspace_sub(-2.0, f64::NEG_INFINITY), -2.0);
+        assert_eq!(logspace_sub(-2.0, -1.0), f64::NEG_INFINITY);
+        let (l, r) = (f64::NEG_INFINITY, f64::INFINITY);
+        assert_eq!(q_p01_boundaries(0.0, l, r, true, false), Some(l));
+        assert_eq!(q_p01_boundaries(0.0, l, r, true, true), Some(r));
+        assert_eq!(q_p01_boundaries(1.0, l, r, false, false), Some(l));
+        assert!(q_p01_boundaries(2.0, l, r, true, false).unwrap().is_nan());
+        assert_eq!(q_p01_boundaries(0.3, l, r, true, false), None);
+    }
+}
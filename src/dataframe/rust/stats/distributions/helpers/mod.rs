@@ -5,14 +5,23 @@
 
 pub mod bd0;
 pub mod beta;
+pub mod cholesky;
 pub mod clamp_unit;
+pub mod dpois_raw;
+pub mod dpq;
 pub mod incomplete_beta;
 pub mod incomplete_beta_continued_fraction;
 pub mod log_gamma;
+pub mod pgamma;
+pub mod pnorm_both;
 pub mod pow1p;
+pub mod regularized_incomplete;
 pub mod stirlerr;
+pub mod toms708;
+pub mod trigamma;
 pub mod validate_integer;
 
 // Re-export commonly used helpers
 pub use clamp_unit::clamp_unit;
+pub use trigamma::trigamma;
 pub use validate_integer::validate_integer;
@@ -0,0 +1,1637 @@
+//! Incomplete beta function ratio (ACM TOMS 708)
+//!
+//! Didonato and Morris' `bratio` with the extensions R added for working on
+//! the log scale: both `I_x(a, b)` and `1 - I_x(a, b)` are returned, each
+//! computed from its own series, continued fraction or asymptotic expansion
+//! so neither tail is formed by cancellation.
+//!
+//! Source: R-4.5.1/nmath/toms708.c
+//!
+//! Didonato, A. R. and Morris, A. H. (1992). Algorithm 708: Significant
+//! digit computation of the incomplete beta function ratios. ACM
+//! Transactions on Mathematical Software 18, 360-373.
+
+#![allow(clippy::excessive_precision)]
+
+use super::dpq::{d_0, d_1, log1_exp, logspace_add};
+use super::pgamma::log1pmx;
+use statrs::function::erf::erf;
+use statrs::function::gamma::digamma;
+
+/// `ln(sqrt(2 pi))`
+const M_LN_SQRT_2PI: f64 = 0.918_938_533_204_672_741_780_329_736_406;
+/// `sqrt(pi)`
+const M_SQRT_PI: f64 = 1.772_453_850_905_516_027_298_167_483_341;
+
+/// `I_x(a, b)` and `1 - I_x(a, b)` with `y = 1 - x`
+///
+/// Returns `(w, w1, ierr)`; `w` and `w1` are on the log scale if `log_p`.
+/// A non-zero `ierr` reports invalid arguments (1-7) or a loss of accuracy
+/// in the asymptotic expansion (`10 + code`).
+///
+/// # Arguments
+/// * `a`, `b` - Non-negative shape parameters, not both zero
+/// * `x`, `y` - Argument and its complement, `x + y == 1`
+/// * `log_p` - If true, returns log probabilities
+pub fn bratio(a: f64, b: f64, x: f64, y: f64, log_p: bool) -> (f64, f64, i32) {
+    // The smallest floating point number for which 1 + eps > 1; for most
+    // purposes replaced by 1e-15 below
+    let mut eps = f64::EPSILON;
+
+    if x.is_nan() || y.is_nan() || a.is_nan() || b.is_nan() {
+        return (f64::NAN, f64::NAN, 0);
+    }
+    let zero = d_0(log_p);
+    let one = d_1(log_p);
+    if a < 0.0 || b < 0.0 {
+        return (zero, zero, 1);
+    }
+    if a == 0.0 && b == 0.0 {
+        return (zero, zero, 2);
+    }
+    if !(0.0..=1.0).contains(&x) {
+        return (zero, zero, 3);
+    }
+    if !(0.0..=1.0).contains(&y) {
+        return (zero, zero, 4);
+    }
+
+    // Check that y == 1 - x
+    let z = x + y - 0.5 - 0.5;
+    if z.abs() > eps * 3.0 {
+        return (zero, zero, 5);
+    }
+
+    if x == 0.0 {
+        if a == 0.0 {
+            return (zero, zero, 6);
+        }
+        return (zero, one, 0);
+    }
+    if y == 0.0 {
+        if b == 0.0 {
+            return (zero, zero, 7);
+        }
+        return (one, zero, 0);
+    }
+    if a == 0.0 {
+        return (one, zero, 0);
+    }
+    if b == 0.0 {
+        return (zero, one, 0);
+    }
+
+    eps = eps.max(1e-15);
+    let a_lt_b = a < b;
+    if (if a_lt_b { b } else { a }) < eps * 0.001 {
+        // a and b both tiny: the result does not depend on x
+        return if log_p {
+            if a_lt_b {
+                ((-a / (a + b)).ln_1p(), (a / (a + b)).ln(), 0)
+            } else {
+                ((b / (a + b)).ln(), (-b / (a + b)).ln_1p(), 0)
+            }
+        } else {
+            (b / (a + b), a / (a + b), 0)
+        };
+    }
+
+    let mut ierr = 0;
+    let (do_swap, w, w1) = if a.min(b) <= 1.0 {
+        // a <= 1 or b <= 1
+        let do_swap = x > 0.5;
+        let (a0, b0, x0, y0) = if do_swap { (b, a, y, x) } else { (a, b, x, y) };
+        // Now x0 <= 1/2 <= y0 (still x0 + y0 == 1)
+
+        if b0 < eps.min(eps * a0) {
+            let w = fpser(a0, b0, x0, eps, log_p);
+            (do_swap, w, w_complement(w, log_p))
+        } else if a0 < eps.min(eps * b0) && b0 * x0 <= 1.0 {
+            let w1 = apser(a0, b0, x0, eps);
+            let (w, w1) = end_from_w1(w1, log_p);
+            (do_swap, w, w1)
+        } else {
+            enum Route {
+                WBpser,
+                W1Bpser,
+                Bgrat { w1: f64, n: i32 },
+            }
+            let route = if a0.max(b0) > 1.0 {
+                // min(a, b) <= 1 < max(a, b)
+                if b0 <= 1.0 {
+                    Route::WBpser
+                } else if x0 >= 0.29 {
+                    // Was 0.3, PR#13786
+                    Route::W1Bpser
+                } else if x0 < 0.1 && (x0 * b0).powf(a0) <= 0.7 {
+                    Route::WBpser
+                } else if b0 > 15.0 {
+                    Route::Bgrat { w1: 0.0, n: 0 }
+                } else {
+                    Route::Bgrat {
+                        w1: f64::NAN,
+                        n: 20,
+                    }
+                }
+            } else if a0 >= 0.2f64.min(b0) || x0.powf(a0) <= 0.9 {
+                // a, b <= 1
+                Route::WBpser
+            } else if x0 >= 0.3 {
+                Route::W1Bpser
+            } else {
+                Route::Bgrat {
+                    w1: f64::NAN,
+                    n: 20,
+                }
+            };
+
+            match route {
+                Route::WBpser => {
+                    let w = bpser(a0, b0, x0, eps, log_p);
+                    (do_swap, w, w_complement(w, log_p))
+                }
+                Route::W1Bpser => {
+                    let w1 = bpser(b0, a0, y0, eps, log_p);
+                    (do_swap, w_complement(w1, log_p), w1)
+                }
+                Route::Bgrat { w1, n } => {
+                    let did_bup = n > 0;
+                    let (mut w1, mut b0) = (w1, b0);
+                    if did_bup {
+                        w1 = bup(b0, a0, y0, x0, n, eps, false);
+                        b0 += n as f64;
+                    }
+                    let ierr1 = bgrat(b0, a0, y0, x0, &mut w1, 15.0 * eps, false);
+                    if w1 == 0.0 || (0.0 < w1 && w1 < f64::MIN_POSITIVE) {
+                        // "Almost surely" from underflow: redo on the log scale
+                        w1 = if did_bup {
+                            bup(b0 - n as f64, a0, y0, x0, n, eps, true)
+                        } else {
+                            f64::NEG_INFINITY
+                        };
+                        let ierr1 = bgrat(b0, a0, y0, x0, &mut w1, 15.0 * eps, true);
+                        if ierr1 != 0 {
+                            ierr = 10 + ierr1;
+                        }
+                        // w1 = log(w1) already
+                        let (w, w1) = if log_p {
+                            (log1_exp(w1), w1)
+                        } else {
+                            (-w1.exp_m1(), w1.exp())
+                        };
+                        (do_swap, w, w1)
+                    } else {
+                        if ierr1 != 0 {
+                            ierr = 10 + ierr1;
+                        }
+                        let (w, w1) = end_from_w1(w1, log_p);
+                        (do_swap, w, w1)
+                    }
+                }
+            }
+        }
+    } else {
+        // Both a, b > 1
+        // lambda := a y - b x = (a + b) y - b = a - (a + b) x, using the
+        // numerically best version
+        let mut lambda = if (a + b).is_finite() {
+            if a > b {
+                (a + b) * y - b
+            } else {
+                a - (a + b) * x
+            }
+        } else {
+            a * y - b * x
+        };
+        let do_swap = lambda < 0.0;
+        if do_swap {
+            lambda = -lambda;
+        }
+        let (mut a0, mut b0, x0, y0) = if do_swap { (b, a, y, x) } else { (a, b, x, y) };
+
+        enum Route {
+            WBpser,
+            Bfrac,
+            Basym,
+            Bup,
+        }
+        let route = if b0 < 40.0 {
+            // R also sends `log_p && lambda > 650` to bpser, whose series
+            // cancels catastrophically there (pbeta(0.7, 5000, 30, log = TRUE)
+            // is off by 12 in the log); the log-scale bup + bgrat below is
+            // accurate instead
+            if b0 * x0 <= 0.7 {
+                Route::WBpser
+            } else {
+                Route::Bup
+            }
+        } else if a0 > b0 {
+            // a0 > b0 >= 40
+            if b0 <= 100.0 || lambda > b0 * 0.03 {
+                Route::Bfrac
+            } else {
+                Route::Basym
+            }
+        } else if a0 <= 100.0 || lambda > a0 * 0.03 {
+            Route::Bfrac
+        } else {
+            Route::Basym
+        };
+
+        match route {
+            Route::WBpser => {
+                let w = bpser(a0, b0, x0, eps, log_p);
+                (do_swap, w, w_complement(w, log_p))
+            }
+            Route::Bfrac => {
+                let w = bfrac(a0, b0, x0, y0, lambda, eps * 15.0, log_p);
+                (do_swap, w, w_complement(w, log_p))
+            }
+            Route::Basym => {
+                let w = basym(a0, b0, lambda, eps * 100.0, log_p);
+                (do_swap, w, w_complement(w, log_p))
+            }
+            Route::Bup => {
+                // b0 := fractional part of b0, in (0, 1]
+                let mut n = b0 as i32;
+                b0 -= n as f64;
+                if b0 == 0.0 {
+                    n -= 1;
+                    b0 = 1.0;
+                }
+
+                // Near underflow the natural-scale bup sum has subnormal
+                // terms, so for log_p stay on the log scale whenever bgrat
+                // applies (a0 >= 15), not only once w has underflowed as R does
+                let log_scale = log_p && a0 >= 15.0;
+                let mut w = if log_scale {
+                    0.0
+                } else {
+                    bup(b0, a0, y0, x0, n, eps, false)
+                };
+
+                if log_scale || (w < f64::MIN_POSITIVE && log_p) {
+                    // Do it all on the log scale: bgrat adds I_x0(a0, b0)
+                    // to the log-scale bup sum
+                    let mut w = bup(b0, a0, y0, x0, n, eps, true);
+                    let ierr1 = if log_scale {
+                        bgrat(a0, b0, x0, y0, &mut w, 15.0 * eps, true)
+                    } else {
+                        1
+                    };
+                    if ierr1 != 0 {
+                        // As in R: the whole series at once
+                        w = bpser(a0, b0 + n as f64, x0, eps, log_p);
+                    }
+                    (do_swap, w, w_complement(w, log_p))
+                } else {
+                    if x0 <= 0.7 {
+                        w += bpser(a0, b0, x0, eps, false);
+                    } else {
+                        if a0 <= 15.0 {
+                            let n = 20;
+                            w += bup(a0, b0, x0, y0, n, eps, false);
+                            a0 += n as f64;
+                        }
+                        let ierr1 = bgrat(a0, b0, x0, y0, &mut w, 15.0 * eps, false);
+                        if ierr1 != 0 {
+                            ierr = 10 + ierr1;
+                        }
+                    }
+                    let (w, w1) = if log_p {
+                        (w.ln(), (-w).ln_1p())
+                    } else {
+                        (w, 0.5 - w + 0.5)
+                    };
+                    (do_swap, w, w1)
+                }
+            }
+        }
+    };
+
+    if do_swap {
+        (w1, w, ierr)
+    } else {
+        (w, w1, ierr)
+    }
+}
+
+/// `1 - w` on the requested scale
+#[inline]
+fn w_complement(w: f64, log_p: bool) -> f64 {
+    if log_p { log1_exp(w) } else { 0.5 - w + 0.5 }
+}
+
+/// `(w, w1)` on the requested scale from `w1` on the natural scale
+#[inline]
+fn end_from_w1(w1: f64, log_p: bool) -> (f64, f64) {
+    if log_p {
+        ((-w1).ln_1p(), w1.ln())
+    } else {
+        (0.5 - w1 + 0.5, w1)
+    }
+}
+
+/// `I_x(a, b)` for `b < min(eps, eps * a)` and `x <= 0.5`
+fn fpser(a: f64, b: f64, x: f64, eps: f64, log_p: bool) -> f64 {
+    // Set ans := x^a
+    let mut ans = if log_p {
+        a * x.ln()
+    } else if a > eps * 0.001 {
+        let t = a * x.ln();
+        if t < exparg(true) {
+            // exp(t) would underflow
+            return 0.0;
+        }
+        t.exp()
+    } else {
+        1.0
+    };
+
+    // Note that 1/B(a, b) = b
+    if log_p {
+        ans += b.ln() - a.ln();
+    } else {
+        ans *= b / a;
+    }
+
+    let tol = eps / a;
+    let mut an = a + 1.0;
+    let mut t = x;
+    let mut s = t / an;
+    loop {
+        an += 1.0;
+        t *= x;
+        let c = t / an;
+        s += c;
+        if c.abs() <= tol {
+            break;
+        }
+    }
+
+    if log_p {
+        ans + (a * s).ln_1p()
+    } else {
+        ans * (a * s + 1.0)
+    }
+}
+
+/// `I_{1-x}(b, a)` for `a <= min(eps, eps * b)`, `b * x <= 1` and `x <= 0.5`
+fn apser(a: f64, b: f64, x: f64, eps: f64) -> f64 {
+    const G: f64 = 0.577215664901533;
+
+    let bx = b * x;
+    let mut t = x - bx;
+    let c = if b * eps <= 0.02 {
+        x.ln() + digamma(b) + G + t
+    } else {
+        // b > 2e13: psi(b) ~= log(b)
+        bx.ln() + G + t
+    };
+
+    let tol = eps * 5.0 * c.abs();
+    let mut j = 1.0;
+    let mut s = 0.0;
+    loop {
+        j += 1.0;
+        t *= x - bx / j;
+        let aj = t / j;
+        s += aj;
+        if aj.abs() <= tol {
+            break;
+        }
+    }
+
+    -a * (c + s)
+}
+
+/// Power series for `I_x(a, b)` when `b <= 1` or `b * x <= 0.7`
+fn bpser(a: f64, b: f64, x: f64, eps: f64, log_p: bool) -> f64 {
+    if x == 0.0 {
+        return d_0(log_p);
+    }
+
+    // Compute the factor x^a / (a * Beta(a, b))
+    let a0 = a.min(b);
+    let mut ans = if a0 >= 1.0 {
+        // 1 <= a0 <= b0
+        let z = a * x.ln() - betaln(a, b);
+        if log_p { z - a.ln() } else { z.exp() / a }
+    } else {
+        let mut b0 = a.max(b);
+
+        if b0 < 8.0 {
+            if b0 <= 1.0 {
+                // max(a, b) = b0 <= 1
+                let mut ans = if log_p { a * x.ln() } else { x.powf(a) };
+                if !log_p && ans == 0.0 {
+                    // Once underflow, always underflow
+                    return ans;
+                }
+                let apb = a + b;
+                let z = if apb > 1.0 {
+                    (gam1(apb - 1.0) + 1.0) / apb
+                } else {
+                    gam1(apb) + 1.0
+                };
+                let c = (gam1(a) + 1.0) * (gam1(b) + 1.0) / z;
+
+                if log_p {
+                    ans += (c * (b / apb)).ln();
+                } else {
+                    ans *= c * (b / apb);
+                }
+                ans
+            } else {
+                // a0 < 1 < b0 < 8
+                let mut u = gamln1(a0);
+                let m = (b0 - 1.0) as i32;
+                if m >= 1 {
+                    let mut c = 1.0;
+                    for _ in 1..=m {
+                        b0 -= 1.0;
+                        c *= b0 / (a0 + b0);
+                    }
+                    u += c.ln();
+                }
+
+                let z = a * x.ln() - u;
+                b0 -= 1.0; // => b0 in (0, 7)
+                let apb = a0 + b0;
+                let t = if apb > 1.0 {
+                    (gam1(apb - 1.0) + 1.0) / apb
+                } else {
+                    gam1(apb) + 1.0
+                };
+
+                if log_p {
+                    z + (a0 / a).ln() + gam1(b0).ln_1p() - t.ln()
+                } else {
+                    z.exp() * (a0 / a) * (gam1(b0) + 1.0) / t
+                }
+            }
+        } else {
+            // a0 < 1 < 8 <= b0
+            let u = gamln1(a0) + algdiv(a0, b0);
+            let z = a * x.ln() - u;
+
+            if log_p {
+                z + (a0 / a).ln()
+            } else {
+                a0 / a * z.exp()
+            }
+        }
+    };
+
+    if ans == d_0(log_p) || (!log_p && a <= eps * 0.1) {
+        return ans;
+    }
+
+    // Compute the series
+    let tol = eps / a;
+    let mut n = 0.0;
+    let mut sum = 0.0;
+    let mut c = 1.0;
+    loop {
+        // sum is alternating as long as n < b (<==> 1 - b/n < 0)
+        n += 1.0;
+        c *= (0.5 - b / n + 0.5) * x;
+        let w = c / (a + n);
+        sum += w;
+        if n >= 1e7 || w.abs() <= tol {
+            break;
+        }
+    }
+
+    if log_p {
+        if a * sum > -1.0 {
+            ans += (a * sum).ln_1p();
+        } else {
+            ans = f64::NEG_INFINITY;
+        }
+    } else if a * sum > -1.0 {
+        ans *= a * sum + 1.0;
+    } else {
+        // Underflow
+        ans = 0.0;
+    }
+    ans
+}
+
+/// `I_x(a, b) - I_x(a + n, b)` for a positive integer `n`
+fn bup(a: f64, b: f64, x: f64, y: f64, n: i32, eps: f64, give_log: bool) -> f64 {
+    // Obtain the scaling factor exp(-mu) and exp(mu) * (x^a * y^b / beta(a, b)) / a
+    let apb = a + b;
+    let ap1 = a + 1.0;
+    let (mu, mut d) = if n > 1 && a >= 1.0 && apb >= ap1 * 1.1 {
+        let mu = (exparg(true).abs() as i32).min(exparg(false) as i32);
+        (mu, (-(mu as f64)).exp())
+    } else {
+        (0, 1.0)
+    };
+
+    let mut ret_val = if give_log {
+        brcmp1(mu, a, b, x, y, true) - a.ln()
+    } else {
+        brcmp1(mu, a, b, x, y, false) / a
+    };
+    if n == 1 || (give_log && ret_val == f64::NEG_INFINITY) || (!give_log && ret_val == 0.0) {
+        return ret_val;
+    }
+
+    let nm1 = n - 1;
+    let mut w = d;
+
+    // Let k be the index of the maximum term
+    let mut k = 0;
+    if b > 1.0 {
+        if y > 1e-4 {
+            let r = (b - 1.0) * x / y - a;
+            if r >= 1.0 {
+                k = if r < nm1 as f64 { r as i32 } else { nm1 };
+            }
+        } else {
+            k = nm1;
+        }
+
+        // Add the increasing terms of the series - if k > 0
+        for i in 0..k {
+            let l = i as f64;
+            d *= (apb + l) / (ap1 + l) * x;
+            w += d;
+        }
+    }
+
+    // Add the remaining terms of the series
+    for i in k..nm1 {
+        let l = i as f64;
+        d *= (apb + l) / (ap1 + l) * x;
+        w += d;
+        if d <= eps * w {
+            break;
+        }
+    }
+
+    if give_log {
+        ret_val += w.ln();
+    } else {
+        ret_val *= w;
+    }
+    ret_val
+}
+
+/// Continued fraction for `I_x(a, b)` when `a, b > 1`
+///
+/// It is assumed that `lambda = (a + b) * y - b`.
+fn bfrac(a: f64, b: f64, x: f64, y: f64, lambda: f64, eps: f64, log_p: bool) -> f64 {
+    if !lambda.is_finite() {
+        return f64::NAN;
+    }
+    let brc = brcomp(a, b, x, y, log_p);
+    if brc.is_nan() {
+        return f64::NAN;
+    }
+    if !log_p && brc == 0.0 {
+        return 0.0;
+    }
+
+    let c = lambda + 1.0;
+    let c0 = b / a;
+    let c1 = 1.0 / a + 1.0;
+    let yp1 = y + 1.0;
+
+    let mut n = 0.0;
+    let mut p = 1.0;
+    let mut s = a + 1.0;
+    let mut an = 0.0;
+    let mut bn = 1.0;
+    let mut anp1 = 1.0;
+    let mut bnp1 = c / c1;
+    let mut r = c1 / c;
+
+    // Continued fraction calculation
+    loop {
+        n += 1.0;
+        let t = n / a;
+        let w = n * (b - n) * x;
+        let e = a / s;
+        let alpha = p * (p + c0) * e * e * (w * x);
+        let e = (t + 1.0) / (c1 + t + t);
+        let beta = n + w / s + e * (c + n * yp1);
+        p = t + 1.0;
+        s += 2.0;
+
+        // Update an, bn, anp1 and bnp1
+        let t = alpha * an + beta * anp1;
+        an = anp1;
+        anp1 = t;
+        let t = alpha * bn + beta * bnp1;
+        bn = bnp1;
+        bnp1 = t;
+
+        let r0 = r;
+        r = anp1 / bnp1;
+        if (r - r0).abs() <= eps * r {
+            break;
+        }
+
+        // Rescale an, bn, anp1 and bnp1
+        an /= bnp1;
+        bn /= bnp1;
+        anp1 = r;
+        bnp1 = 1.0;
+
+        // Arbitrary limit; guards against lambda = Inf
+        if n >= 10000.0 {
+            break;
+        }
+    }
+
+    if log_p { brc + r.ln() } else { brc * r }
+}
+
+/// `x^a * y^b / Beta(a, b)`
+fn brcomp(a: f64, b: f64, x: f64, y: f64, log_p: bool) -> f64 {
+    // 1 / sqrt(2 pi)
+    const CONST: f64 = 0.398942280401433;
+
+    if x == 0.0 || y == 0.0 {
+        return d_0(log_p);
+    }
+    let a0 = a.min(b);
+    if a0 < 8.0 {
+        let (lnx, lny) = if x <= 0.375 {
+            (x.ln(), (-x).ln_1p())
+        } else if y > 0.375 {
+            (x.ln(), y.ln())
+        } else {
+            ((-y).ln_1p(), y.ln())
+        };
+
+        let mut z = a * lnx + b * lny;
+        if a0 >= 1.0 {
+            z -= betaln(a, b);
+            return if log_p { z } else { z.exp() };
+        }
+
+        // Procedure for a < 1 or b < 1
+        let mut b0 = a.max(b);
+        if b0 >= 8.0 {
+            let u = gamln1(a0) + algdiv(a0, b0);
+            return if log_p {
+                a0.ln() + (z - u)
+            } else {
+                a0 * (z - u).exp()
+            };
+        }
+
+        if b0 <= 1.0 {
+            // max(a, b) = b0 <= 1
+            let e_z = if log_p { z } else { z.exp() };
+            if !log_p && e_z == 0.0 {
+                // exp() underflow
+                return 0.0;
+            }
+
+            let apb = a + b;
+            let z = if apb > 1.0 {
+                (gam1(apb - 1.0) + 1.0) / apb
+            } else {
+                gam1(apb) + 1.0
+            };
+
+            let c = (gam1(a) + 1.0) * (gam1(b) + 1.0) / z;
+            return if log_p {
+                e_z + (a0 * c).ln() - (a0 / b0).ln_1p()
+            } else {
+                e_z * (a0 * c) / (a0 / b0 + 1.0)
+            };
+        }
+
+        // 1 < b0 < 8
+        let mut u = gamln1(a0);
+        let n = (b0 - 1.0) as i32;
+        if n >= 1 {
+            let mut c = 1.0;
+            for _ in 1..=n {
+                b0 -= 1.0;
+                c *= b0 / (a0 + b0);
+            }
+            u += c.ln();
+        }
+        z -= u;
+        b0 -= 1.0;
+        let apb = a0 + b0;
+        let t = if apb > 1.0 {
+            (gam1(apb - 1.0) + 1.0) / apb
+        } else {
+            gam1(apb) + 1.0
+        };
+
+        if log_p {
+            a0.ln() + z + gam1(b0).ln_1p() - t.ln()
+        } else {
+            a0 * z.exp() * (gam1(b0) + 1.0) / t
+        }
+    } else {
+        // Procedure for a >= 8 and b >= 8
+        let (x0, y0, lambda) = if a <= b {
+            let h = a / b;
+            (h / (h + 1.0), 1.0 / (h + 1.0), a - (a + b) * x)
+        } else {
+            let h = b / a;
+            (1.0 / (h + 1.0), h / (h + 1.0), (a + b) * y - b)
+        };
+
+        let e = -lambda / a;
+        let u = if e.abs() > 0.6 {
+            e - (x / x0).ln()
+        } else {
+            rlog1(e)
+        };
+
+        let e = lambda / b;
+        let v = if e.abs() <= 0.6 {
+            rlog1(e)
+        } else {
+            e - (y / y0).ln()
+        };
+
+        if log_p {
+            -M_LN_SQRT_2PI + 0.5 * (b * x0).ln() - (a * u + b * v) - bcorr(a, b)
+        } else {
+            CONST * (b * x0).sqrt() * (-(a * u + b * v)).exp() * (-bcorr(a, b)).exp()
+        }
+    }
+}
+
+/// `exp(mu) * x^a * y^b / Beta(a, b)`
+fn brcmp1(mu: i32, a: f64, b: f64, x: f64, y: f64, give_log: bool) -> f64 {
+    // 1 / sqrt(2 pi)
+    const CONST: f64 = 0.398942280401433;
+
+    let a0 = a.min(b);
+    if a0 < 8.0 {
+        let (lnx, lny) = if x <= 0.375 {
+            (x.ln(), (-x).ln_1p())
+        } else if y > 0.375 {
+            (x.ln(), y.ln())
+        } else {
+            ((-y).ln_1p(), y.ln())
+        };
+
+        let mut z = a * lnx + b * lny;
+        if a0 >= 1.0 {
+            z -= betaln(a, b);
+            return esum(mu, z, give_log);
+        }
+
+        // Procedure for a < 1 or b < 1
+        let mut b0 = a.max(b);
+        if b0 >= 8.0 {
+            let u = gamln1(a0) + algdiv(a0, b0);
+            return if give_log {
+                a0.ln() + esum(mu, z - u, true)
+            } else {
+                a0 * esum(mu, z - u, false)
+            };
+        } else if b0 <= 1.0 {
+            // a0 < 1, b0 <= 1
+            let ans = esum(mu, z, give_log);
+            if ans == d_0(give_log) {
+                return ans;
+            }
+
+            let apb = a + b;
+            let z = if apb > 1.0 {
+                (gam1(apb - 1.0) + 1.0) / apb
+            } else {
+                gam1(apb) + 1.0
+            };
+            let c = if give_log {
+                gam1(a).ln_1p() + gam1(b).ln_1p() - z.ln()
+            } else {
+                (gam1(a) + 1.0) * (gam1(b) + 1.0) / z
+            };
+            return if give_log {
+                ans + a0.ln() + c - (a0 / b0).ln_1p()
+            } else {
+                ans * (a0 * c) / (a0 / b0 + 1.0)
+            };
+        }
+
+        // a0 < 1 < b0 < 8
+        let mut u = gamln1(a0);
+        let n = (b0 - 1.0) as i32;
+        if n >= 1 {
+            let mut c = 1.0;
+            for _ in 1..=n {
+                b0 -= 1.0;
+                c *= b0 / (a0 + b0);
+            }
+            u += c.ln();
+        }
+        z -= u;
+        b0 -= 1.0;
+        let apb = a0 + b0;
+        let t = if apb > 1.0 {
+            (gam1(apb - 1.0) + 1.0) / apb
+        } else {
+            gam1(apb) + 1.0
+        };
+        if give_log {
+            a0.ln() + esum(mu, z, true) + gam1(b0).ln_1p() - t.ln()
+        } else {
+            a0 * esum(mu, z, false) * (gam1(b0) + 1.0) / t
+        }
+    } else {
+        // Procedure for a >= 8 and b >= 8
+        let (x0, y0, lambda) = if a > b {
+            let h = b / a;
+            (1.0 / (h + 1.0), h / (h + 1.0), (a + b) * y - b)
+        } else {
+            let h = a / b;
+            (h / (h + 1.0), 1.0 / (h + 1.0), a - (a + b) * x)
+        };
+        // log(x0), in both cases
+        let lx0 = -(b / a).ln_1p();
+
+        let e = -lambda / a;
+        let u = if e.abs() > 0.6 {
+            e - (x / x0).ln()
+        } else {
+            rlog1(e)
+        };
+
+        let e = lambda / b;
+        let v = if e.abs() > 0.6 {
+            e - (y / y0).ln()
+        } else {
+            rlog1(e)
+        };
+
+        let z = esum(mu, -(a * u + b * v), give_log);
+        if give_log {
+            CONST.ln() + (b.ln() + lx0) / 2.0 + z - bcorr(a, b)
+        } else {
+            CONST * (b * x0).sqrt() * z * (-bcorr(a, b)).exp()
+        }
+    }
+}
+
+/// Asymptotic expansion for `I_x(a, b)` when `a` is larger than `b`
+///
+/// Computes `w := w + I_x(a, b)`; if `log_w`, `w` is on the log scale on
+/// entry and exit. It is assumed `a >= 15` and `b <= 1`. Returns an error
+/// code, 0 on success.
+fn bgrat(a: f64, b: f64, x: f64, y: f64, w: &mut f64, eps: f64, log_w: bool) -> i32 {
+    const N_TERMS: usize = 30;
+    let mut c = [0.0; N_TERMS];
+    let mut d = [0.0; N_TERMS];
+
+    let bm1 = b - 0.5 - 0.5;
+    // nu = a + (b - 1) / 2 =: T, in (9.1) of Didonato & Morris (1992)
+    let nu = a + bm1 * 0.5;
+    let lnx = if y > 0.375 { x.ln() } else { (-y).ln_1p() };
+    // z =: u in (9.1) of Didonato & Morris (1992)
+    let z = -nu * lnx;
+
+    if b * z == 0.0 {
+        // The expansion cannot be computed, e.g. for subnormal x
+        return 1;
+    }
+
+    // Computation of the expansion
+    // log(r) for r := exp(-z) * z^b / gamma(b); u is factored out of the
+    // expansion and multiplied back at the end
+    let log_r = b.ln() + gam1(b).ln_1p() + b * z.ln() + nu * lnx;
+    let log_u = log_r - (algdiv(b, a) + b * nu.ln());
+    let u = log_u.exp();
+
+    if log_u == f64::NEG_INFINITY {
+        return 2;
+    }
+
+    // Underflow --> work with log(u) == log_u
+    let u_0 = u == 0.0;
+    // l := w / u, but such that it also works when u underflows to 0
+    let l = if log_w {
+        if *w == f64::NEG_INFINITY {
+            0.0
+        } else {
+            (*w - log_u).exp()
+        }
+    } else if *w == 0.0 {
+        0.0
+    } else {
+        (w.ln() - log_u).exp()
+    };
+
+    let q_r = grat_r(b, z, log_r, eps);
+    let v = 0.25 / (nu * nu);
+    let t2 = lnx * 0.25 * lnx;
+    let mut j = q_r;
+    let mut sum = j;
+    let mut t = 1.0;
+    let mut cn = 1.0;
+    let mut n2 = 0.0;
+    let mut ierr = 0;
+    for n in 1..=N_TERMS {
+        let bp2n = b + n2;
+        j = (bp2n * (bp2n + 1.0) * j + (z + bp2n + 1.0) * t) * v;
+        n2 += 2.0;
+        t *= t2;
+        cn /= n2 * (n2 + 1.0);
+        let nm1 = n - 1;
+        c[nm1] = cn;
+        let mut s = 0.0;
+        if n > 1 {
+            let mut coef = b - n as f64;
+            for i in 1..=nm1 {
+                s += coef * c[i - 1] * d[nm1 - i];
+                coef += b;
+            }
+        }
+        d[nm1] = bm1 * cn + s / n as f64;
+        let dj = d[nm1] * j;
+        sum += dj;
+        if sum <= 0.0 {
+            return 3;
+        }
+        if dj.abs() <= eps * (sum + l) {
+            ierr = 0;
+            break;
+        } else if n == N_TERMS {
+            ierr = 4;
+        }
+    }
+
+    // Add the results to w
+    if log_w {
+        *w = logspace_add(*w, log_u + sum.ln());
+    } else {
+        *w += if u_0 {
+            (log_u + sum.ln()).exp()
+        } else {
+            u * sum
+        };
+    }
+    ierr
+}
+
+/// Scaled complement of the incomplete gamma ratio, `Q(a, x) / r`
+///
+/// `r = exp(-x) * x^a / Gamma(a) = exp(log_r)`; it is assumed `a <= 1`.
+fn grat_r(a: f64, x: f64, log_r: f64, eps: f64) -> f64 {
+    if a * x == 0.0 {
+        if x <= a { (-log_r).exp() } else { 0.0 }
+    } else if a == 0.5 {
+        // e.g. when called from pt()
+        if x < 0.25 {
+            let p = erf(x.sqrt());
+            (0.5 - p + 0.5) * (-log_r).exp()
+        } else {
+            let sx = x.sqrt();
+            erfc1(true, sx) / sx * M_SQRT_PI
+        }
+    } else if x < 1.1 {
+        // Taylor series for P(a, x) / x^a
+        let mut an = 3.0;
+        let mut c = x;
+        let mut sum = x / (a + 3.0);
+        let tol = eps * 0.1 / (a + 1.0);
+        loop {
+            an += 1.0;
+            c *= -(x / an);
+            let t = c / (a + an);
+            sum += t;
+            if t.abs() <= tol {
+                break;
+            }
+        }
+
+        let j = a * x * ((sum / 6.0 - 0.5 / (a + 2.0)) * x + 1.0 / (a + 1.0));
+        let z = a * x.ln();
+        let h = gam1(a);
+        let g = h + 1.0;
+
+        if (x >= 0.25 && (a < x / 2.59)) || (z > -0.13394) {
+            let l = z.exp_m1();
+            let q = ((l + 0.5 + 0.5) * j - l) * g - h;
+            if q <= 0.0 { 0.0 } else { q * (-log_r).exp() }
+        } else {
+            let p = z.exp() * g * (0.5 - j + 0.5);
+            (0.5 - p + 0.5) * (-log_r).exp()
+        }
+    } else {
+        // x >= 1.1: continued fraction expansion
+        let mut a2n_1 = 1.0;
+        let mut a2n = 1.0;
+        let mut b2n_1 = x;
+        let mut b2n = x + (1.0 - a);
+        let mut c = 1.0;
+
+        loop {
+            a2n_1 = x * a2n + c * a2n_1;
+            b2n_1 = x * b2n + c * b2n_1;
+            let am0 = a2n_1 / b2n_1;
+            c += 1.0;
+            let c_a = c - a;
+            a2n = a2n_1 + c_a * a2n;
+            b2n = b2n_1 + c_a * b2n;
+            let an0 = a2n / b2n;
+            if (an0 - am0).abs() < eps * an0 {
+                return an0;
+            }
+        }
+    }
+}
+
+/// Asymptotic expansion for `I_x(a, b)` for large `a` and `b`
+///
+/// `lambda = (a + b) * y - b`; it is assumed that `lambda >= 0` and
+/// `a, b >= 15`.
+fn basym(a: f64, b: f64, lambda: f64, eps: f64, log_p: bool) -> f64 {
+    // Maximum value that n can take in the loop; must be even
+    const NUM_IT: usize = 20;
+
+    // 2 / sqrt(pi)
+    const E0: f64 = std::f64::consts::FRAC_2_SQRT_PI;
+    // 2^(-3/2)
+    const E1: f64 = 0.353553390593274;
+    // ln(e0)
+    const LN_E0: f64 = 0.120782237635245;
+
+    let mut a0 = [0.0; NUM_IT + 1];
+    let mut b0 = [0.0; NUM_IT + 1];
+    let mut c = [0.0; NUM_IT + 1];
+    let mut d = [0.0; NUM_IT + 1];
+
+    let f = a * rlog1(-lambda / a) + b * rlog1(lambda / b);
+    let t = if log_p {
+        -f
+    } else {
+        let t = (-f).exp();
+        if t == 0.0 {
+            // Once underflow, always underflow
+            return 0.0;
+        }
+        t
+    };
+    let z0 = f.sqrt();
+    let z = z0 / E1 * 0.5;
+    let z2 = f + f;
+
+    let (h, r0, r1, w0) = if a < b {
+        let h = a / b;
+        (
+            h,
+            1.0 / (h + 1.0),
+            (b - a) / b,
+            1.0 / (a * (h + 1.0)).sqrt(),
+        )
+    } else {
+        let h = b / a;
+        (
+            h,
+            1.0 / (h + 1.0),
+            (b - a) / a,
+            1.0 / (b * (h + 1.0)).sqrt(),
+        )
+    };
+
+    a0[0] = r1 * 0.66666666666666663;
+    c[0] = a0[0] * -0.5;
+    d[0] = -c[0];
+    let mut j0 = 0.5 / E0 * erfc1(true, z0);
+    let mut j1 = E1;
+    let mut sum = j0 + d[0] * w0 * j1;
+
+    let mut s = 1.0;
+    let h2 = h * h;
+    let mut hn = 1.0;
+    let mut w = w0;
+    let mut znm1 = z;
+    let mut zn = z2;
+    let mut n = 2;
+    while n <= NUM_IT {
+        hn *= h2;
+        a0[n - 1] = r0 * 2.0 * (h * hn + 1.0) / (n as f64 + 2.0);
+        let np1 = n + 1;
+        s += hn;
+        a0[np1 - 1] = r1 * 2.0 * s / (n as f64 + 3.0);
+
+        for i in n..=np1 {
+            let r = (i as f64 + 1.0) * -0.5;
+            b0[0] = r * a0[0];
+            for m in 2..=i {
+                let mut bsum = 0.0;
+                for j in 1..m {
+                    let mmj = m - j;
+                    bsum += (j as f64 * r - mmj as f64) * a0[j - 1] * b0[mmj - 1];
+                }
+                b0[m - 1] = r * a0[m - 1] + bsum / m as f64;
+            }
+            c[i - 1] = b0[i - 1] / (i as f64 + 1.0);
+
+            let mut dsum = 0.0;
+            for j in 1..i {
+                dsum += d[i - j - 1] * c[j - 1];
+            }
+            d[i - 1] = -(dsum + c[i - 1]);
+        }
+
+        j0 = E1 * znm1 + (n as f64 - 1.0) * j0;
+        j1 = E1 * zn + n as f64 * j1;
+        znm1 *= z2;
+        zn *= z2;
+        w *= w0;
+        let t0 = d[n - 1] * w * j0;
+        w *= w0;
+        let t1 = d[np1 - 1] * w * j1;
+        sum += t0 + t1;
+        if t0.abs() + t1.abs() <= eps * sum {
+            break;
+        }
+        n += 2;
+    }
+
+    if log_p {
+        LN_E0 + t - bcorr(a, b) + sum.ln()
+    } else {
+        let u = (-bcorr(a, b)).exp();
+        E0 * t * u * sum
+    }
+}
+
+/// Approximate limits of the exponential function
+///
+/// `exparg(false)` is the largest `w` for which `exp(w)` is finite,
+/// `exparg(true)` the most negative `w` for which it is non-zero.
+fn exparg(negative: bool) -> f64 {
+    const LNB: f64 = std::f64::consts::LN_2;
+    let m = if negative {
+        f64::MIN_EXP - 1
+    } else {
+        f64::MAX_EXP
+    };
+    m as f64 * LNB * 0.99999
+}
+
+/// `exp(mu + x)`
+fn esum(mu: i32, x: f64, give_log: bool) -> f64 {
+    if give_log {
+        return x + mu as f64;
+    }
+
+    let mu = mu as f64;
+    let w;
+    if x > 0.0 {
+        if mu > 0.0 {
+            return mu.exp() * x.exp();
+        }
+        w = mu + x;
+        if w < 0.0 {
+            return mu.exp() * x.exp();
+        }
+    } else {
+        if mu < 0.0 {
+            return mu.exp() * x.exp();
+        }
+        w = mu + x;
+        if w > 0.0 {
+            return mu.exp() * x.exp();
+        }
+    }
+    w.exp()
+}
+
+/// `x - ln(1 + x)`
+#[inline]
+fn rlog1(x: f64) -> f64 {
+    -log1pmx(x)
+}
+
+/// `erfc(x)` if `!scaled`, `exp(x^2) * erfc(x)` otherwise
+fn erfc1(scaled: bool, x: f64) -> f64 {
+    const C: f64 = 0.564189583547756;
+    const A: [f64; 5] = [
+        7.7105849500132e-5,
+        -0.00133733772997339,
+        0.0323076579225834,
+        0.0479137145607681,
+        0.128379167095513,
+    ];
+    const B: [f64; 3] = [0.00301048631703895, 0.0538971687740286, 0.375795757275549];
+    const P: [f64; 8] = [
+        -1.36864857382717e-7,
+        0.564195517478974,
+        7.21175825088309,
+        43.1622272220567,
+        152.98928504694,
+        339.320816734344,
+        451.918953711873,
+        300.459261020162,
+    ];
+    const Q: [f64; 8] = [
+        1.0,
+        12.7827273196294,
+        77.0001529352295,
+        277.585444743988,
+        638.980264465631,
+        931.35409485061,
+        790.950925327898,
+        300.459260956983,
+    ];
+    const R: [f64; 5] = [
+        2.10144126479064,
+        26.2370141675169,
+        21.3688200555087,
+        4.6580782871847,
+        0.282094791773523,
+    ];
+    const S: [f64; 4] = [
+        94.153775055546,
+        187.11481179959,
+        99.0191814623914,
+        18.0124575948747,
+    ];
+
+    let ax = x.abs();
+    if ax <= 0.5 {
+        let t = x * x;
+        let top = (((A[0] * t + A[1]) * t + A[2]) * t + A[3]) * t + A[4] + 1.0;
+        let bot = ((B[0] * t + B[1]) * t + B[2]) * t + 1.0;
+        let ret_val = 0.5 - x * (top / bot) + 0.5;
+        return if scaled { t.exp() * ret_val } else { ret_val };
+    }
+
+    let ret_val = if ax <= 4.0 {
+        // 0.5 < |x| <= 4
+        let top = ((((((P[0] * ax + P[1]) * ax + P[2]) * ax + P[3]) * ax + P[4]) * ax + P[5]) * ax
+            + P[6])
+            * ax
+            + P[7];
+        let bot = ((((((Q[0] * ax + Q[1]) * ax + Q[2]) * ax + Q[3]) * ax + Q[4]) * ax + Q[5]) * ax
+            + Q[6])
+            * ax
+            + Q[7];
+        top / bot
+    } else {
+        // |x| > 4
+        if x <= -5.6 {
+            // Limit value for "large" negative x
+            return if scaled { (x * x).exp() * 2.0 } else { 2.0 };
+        }
+        if !scaled && (x > 100.0 || x * x > -exparg(true)) {
+            // Limit value for large positive x when not scaled
+            return 0.0;
+        }
+
+        let t = 1.0 / (x * x);
+        let top = (((R[0] * t + R[1]) * t + R[2]) * t + R[3]) * t + R[4];
+        let bot = (((S[0] * t + S[1]) * t + S[2]) * t + S[3]) * t + 1.0;
+        (C - t * top / bot) / ax
+    };
+
+    // Final assembly
+    if scaled {
+        if x < 0.0 {
+            (x * x).exp() * 2.0 - ret_val
+        } else {
+            ret_val
+        }
+    } else {
+        let ret_val = (-x * x).exp() * ret_val;
+        if x < 0.0 { 2.0 - ret_val } else { ret_val }
+    }
+}
+
+/// `1 / Gamma(a + 1) - 1` for `-0.5 <= a <= 1.5`
+fn gam1(a: f64) -> f64 {
+    let d = a - 0.5;
+    // t := if (a > 1/2) a - 1 else a
+    let t = if d > 0.0 { d - 0.5 } else { a };
+    if t < 0.0 {
+        const R: [f64; 9] = [
+            -0.422784335098468,
+            -0.771330383816272,
+            -0.244757765222226,
+            0.118378989872749,
+            9.30357293360349e-4,
+            -0.0118290993445146,
+            0.00223047661158249,
+            2.66505979058923e-4,
+            -1.32674909766242e-4,
+        ];
+        const S1: f64 = 0.273076135303957;
+        const S2: f64 = 0.0559398236957378;
+
+        let top = (((((((R[8] * t + R[7]) * t + R[6]) * t + R[5]) * t + R[4]) * t + R[3]) * t
+            + R[2])
+            * t
+            + R[1])
+            * t
+            + R[0];
+        let bot = (S2 * t + S1) * t + 1.0;
+        let w = top / bot;
+        if d > 0.0 {
+            t * w / a
+        } else {
+            a * (w + 0.5 + 0.5)
+        }
+    } else if t == 0.0 {
+        // a in {0, 1}
+        0.0
+    } else {
+        const P: [f64; 7] = [
+            0.577215664901533,
+            -0.409078193005776,
+            -0.230975380857675,
+            0.0597275330452234,
+            0.0076696818164949,
+            -0.00514889771323592,
+            5.89597428611429e-4,
+        ];
+        const Q: [f64; 5] = [
+            1.0,
+            0.427569613095214,
+            0.158451672430138,
+            0.0261132021441447,
+            0.00423244297896961,
+        ];
+
+        let top = (((((P[6] * t + P[5]) * t + P[4]) * t + P[3]) * t + P[2]) * t + P[1]) * t + P[0];
+        let bot = (((Q[4] * t + Q[3]) * t + Q[2]) * t + Q[1]) * t + 1.0;
+        let w = top / bot;
+        if d > 0.0 {
+            t / a * (w - 0.5 - 0.5)
+        } else {
+            a * w
+        }
+    }
+}
+
+/// `ln(Gamma(1 + a))` for `-0.2 <= a <= 1.25`
+fn gamln1(a: f64) -> f64 {
+    if a < 0.6 {
+        const P0: f64 = 0.577215664901533;
+        const P1: f64 = 0.844203922187225;
+        const P2: f64 = -0.168860593646662;
+        const P3: f64 = -0.780427615533591;
+        const P4: f64 = -0.402055799310489;
+        const P5: f64 = -0.0673562214325671;
+        const P6: f64 = -0.00271935708322958;
+        const Q1: f64 = 2.88743195473681;
+        const Q2: f64 = 3.12755088914843;
+        const Q3: f64 = 1.56875193295039;
+        const Q4: f64 = 0.361951990101499;
+        const Q5: f64 = 0.0325038868253937;
+        const Q6: f64 = 6.67465618796164e-4;
+        let w = ((((((P6 * a + P5) * a + P4) * a + P3) * a + P2) * a + P1) * a + P0)
+            / ((((((Q6 * a + Q5) * a + Q4) * a + Q3) * a + Q2) * a + Q1) * a + 1.0);
+        -a * w
+    } else {
+        // 0.6 <= a <= 1.25
+        const R0: f64 = 0.422784335098467;
+        const R1: f64 = 0.848044614534529;
+        const R2: f64 = 0.565221050691933;
+        const R3: f64 = 0.156513060486551;
+        const R4: f64 = 0.017050248402265;
+        const R5: f64 = 4.97958207639485e-4;
+        const S1: f64 = 1.24313399877507;
+        const S2: f64 = 0.548042109832463;
+        const S3: f64 = 0.10155218743983;
+        const S4: f64 = 0.00713309612391;
+        const S5: f64 = 1.16165475989616e-4;
+        let x = a - 0.5 - 0.5;
+        let w = (((((R5 * x + R4) * x + R3) * x + R2) * x + R1) * x + R0)
+            / (((((S5 * x + S4) * x + S3) * x + S2) * x + S1) * x + 1.0);
+        x * w
+    }
+}
+
+/// `ln(Gamma(a))` for positive `a`
+fn gamln(a: f64) -> f64 {
+    // 0.5 * (ln(2 pi) - 1)
+    const D: f64 = 0.418938533204673;
+    const C0: f64 = 0.0833333333333333;
+    const C1: f64 = -0.00277777777760991;
+    const C2: f64 = 7.9365066682539e-4;
+    const C3: f64 = -5.9520293135187e-4;
+    const C4: f64 = 8.37308034031215e-4;
+    const C5: f64 = -0.00165322962780713;
+
+    if a <= 0.8 {
+        // ln(G(a + 1)) - ln(a) == ln(G(a))
+        gamln1(a) - a.ln()
+    } else if a <= 2.25 {
+        gamln1(a - 0.5 - 0.5)
+    } else if a < 10.0 {
+        let n = (a - 1.25) as i32;
+        let mut t = a;
+        let mut w = 1.0;
+        for _ in 1..=n {
+            t -= 1.0;
+            w *= t;
+        }
+        gamln1(t - 1.0) + w.ln()
+    } else {
+        // a >= 10
+        let t = 1.0 / (a * a);
+        let w = (((((C5 * t + C4) * t + C3) * t + C2) * t + C1) * t + C0) / a;
+        D + w + (a - 0.5) * (a.ln() - 1.0)
+    }
+}
+
+/// `ln(Gamma(a + b))` for `1 <= a <= 2` and `1 <= b <= 2`
+fn gsumln(a: f64, b: f64) -> f64 {
+    let x = a + b - 2.0; // in [0, 2]
+    if x <= 0.25 {
+        gamln1(x + 1.0)
+    } else if x <= 1.25 {
+        gamln1(x) + x.ln_1p()
+    } else {
+        gamln1(x - 1.0) + (x * (x + 1.0)).ln()
+    }
+}
+
+/// Stirling-series coefficients shared by `algdiv` and `bcorr`
+const DEL_C: [f64; 6] = [
+    0.0833333333333333,
+    -0.00277777777760991,
+    7.9365066682539e-4,
+    -5.9520293135187e-4,
+    8.37308034031215e-4,
+    -0.00165322962780713,
+];
+
+/// `del(b) - del(a + b)` given `x = b / (a + b)` and `c = a / (a + b)`,
+/// where `ln(Gamma(x)) = (x - 0.5) ln(x) - x + 0.5 ln(2 pi) + del(x)`
+fn del_difference(b: f64, x: f64, c: f64) -> f64 {
+    // Set s<n> = (1 - x^n) / (1 - x)
+    let x2 = x * x;
+    let s3 = x + x2 + 1.0;
+    let s5 = x + x2 * s3 + 1.0;
+    let s7 = x + x2 * s5 + 1.0;
+    let s9 = x + x2 * s7 + 1.0;
+    let s11 = x + x2 * s9 + 1.0;
+
+    let t = 1.0 / (b * b);
+    let w = ((((DEL_C[5] * s11 * t + DEL_C[4] * s9) * t + DEL_C[3] * s7) * t + DEL_C[2] * s5) * t
+        + DEL_C[1] * s3)
+        * t
+        + DEL_C[0];
+    w * c / b
+}
+
+/// `ln(Gamma(b) / Gamma(a + b))` when `b >= 8`
+fn algdiv(a: f64, b: f64) -> f64 {
+    let (c, x, d) = if a > b {
+        let h = b / a;
+        (1.0 / (h + 1.0), h / (h + 1.0), a + (b - 0.5))
+    } else {
+        let h = a / b;
+        (h / (h + 1.0), 1.0 / (h + 1.0), b + (a - 0.5))
+    };
+
+    // w := del(b) - del(a + b)
+    let w = del_difference(b, x, c);
+
+    // Combine the results
+    let u = d * (a / b).ln_1p();
+    let v = a * (b.ln() - 1.0);
+    if u > v { w - v - u } else { w - u - v }
+}
+
+/// `del(a0) + del(b0) - del(a0 + b0)` for `a0, b0 >= 8`
+fn bcorr(a0: f64, b0: f64) -> f64 {
+    let a = a0.min(b0);
+    let b = a0.max(b0);
+
+    let h = a / b;
+    let c = h / (h + 1.0);
+    let x = 1.0 / (h + 1.0);
+
+    // w := del(b) - del(a + b)
+    let w = del_difference(b, x, c);
+
+    // Compute del(a) + w
+    let t = 1.0 / (a * a);
+    (((((DEL_C[5] * t + DEL_C[4]) * t + DEL_C[3]) * t + DEL_C[2]) * t + DEL_C[1]) * t + DEL_C[0])
+        / a
+        + w
+}
+
+/// `ln(Beta(a0, b0))`
+///
+/// Accurate also when one or both arguments are large, where
+/// `lgamma(a) + lgamma(b) - lgamma(a + b)` cancels badly.
+pub fn betaln(a0: f64, b0: f64) -> f64 {
+    // 0.5 * ln(2 pi)
+    const E: f64 = 0.918938533204673;
+
+    let mut a = a0.min(b0);
+    let mut b = a0.max(b0);
+
+    if a >= 8.0 {
+        // a >= 8
+        let w = bcorr(a, b);
+        let h = a / b;
+        let c = h / (h + 1.0);
+        let u = -(a - 0.5) * c.ln();
+        let v = b * h.ln_1p();
+        return if u > v {
+            b.ln() * -0.5 + E + w - v - u
+        } else {
+            b.ln() * -0.5 + E + w - u - v
+        };
+    }
+
+    if a < 1.0 {
+        // a < 1
+        return if b < 8.0 {
+            gamln(a) + (gamln(b) - gamln(a + b))
+        } else {
+            gamln(a) + algdiv(a, b)
+        };
+    }
+
+    // 1 <= a < 8
+    let w;
+    if a < 2.0 {
+        if b <= 2.0 {
+            return gamln(a) + gamln(b) - gsumln(a, b);
+        }
+        if b >= 8.0 {
+            return gamln(a) + algdiv(a, b);
+        }
+        w = 0.0;
+    } else if b <= 1e3 {
+        // Reduction of a when b <= 1000
+        let n = (a - 1.0) as i32;
+        let mut prod = 1.0;
+        for _ in 1..=n {
+            a -= 1.0;
+            let h = a / b;
+            prod *= h / (h + 1.0);
+        }
+        w = prod.ln();
+
+        if b >= 8.0 {
+            return w + gamln(a) + algdiv(a, b);
+        }
+    } else {
+        // Reduction of a when b > 1000
+        let n = (a - 1.0) as i32;
+        let mut prod = 1.0;
+        for _ in 1..=n {
+            a -= 1.0;
+            prod *= a / (a / b + 1.0);
+        }
+        return prod.ln() - n as f64 * b.ln() + (gamln(a) + algdiv(a, b));
+    }
+
+    // Reduction of b when b < 8
+    let n = (b - 1.0) as i32;
+    let mut z = 1.0;
+    for _ in 1..=n {
+        b -= 1.0;
+        z *= b / (a + b);
+    }
+    w + z.ln() + (gamln(a) + (gamln(b) - gsumln(a, b)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rel(got: f64, want: f64) -> f64 {
+        ((got - want) / want).abs()
+    }
+
+    #[test]
+    fn test_bratio_matches_reference() {
+        let (w, w1, ierr) = bratio(2.0, 3.0, 0.5, 0.5, false);
+        assert_eq!(ierr, 0);
+        assert!((w - 0.6875).abs() < 1e-15 && (w1 - 0.3125).abs() < 1e-15);
+
+        // Reference values from mpmath; the x = 0.7 case is an exact
+        // negative-binomial sum, where series evaluation cancels badly
+        let (_, w1, _) = bratio(500.0, 5000.0, 0.2, 0.8, false);
+        assert!(rel(w1, 5.9582587522472498e-109) < 1e-12);
+        let (_, lw1, _) = bratio(500.0, 5000.0, 0.2, 0.8, true);
+        assert!(rel(lw1, -249.19699685362271) < 1e-13);
+        let (lw, _, _) = bratio(5000.0, 30.0, 0.7, 0.5 - 0.7 + 0.5, true);
+        assert!(rel(lw, -1642.4479215494326) < 1e-13);
+        let (lw, _, _) = bratio(1e5, 30.0, 0.95, 0.5 - 0.95 + 0.5, true);
+        assert!(rel(lw, -4953.5780014766046) < 1e-13);
+    }
+
+    #[test]
+    fn test_betaln_large_arguments() {
+        // lgamma(a) + lgamma(b) - lgamma(a + b) loses about 12 digits here
+        assert!(rel(betaln(1e5, 30.0), -274.13507455425191) < 1e-14);
+        assert!(rel(betaln(2.0, 3.0), (1.0f64 / 12.0).ln()) < 1e-14);
+    }
+}
@@ -0,0 +1,163 @@
+//! Laplace (double exponential) distribution functions
+//!
+//! f(x) = exp(-|x - location| / scale) / (2 scale). Each half of the
+//! distribution is an exponential tail, so both CDF tails are computed from
+//! their own closed form rather than as the complement of the other.
+
+use super::helpers::dpq::{d_exp, dt_clog, dt_from_logs, dt_log, q_p01_boundaries};
+use rand::Rng;
+use rand::distributions::Open01;
+use std::f64::consts::LN_2;
+
+/// Laplace probability density function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate density
+/// * `location` - Location (median) of the distribution
+/// * `scale` - Scale of the distribution (must be positive)
+/// * `give_log` - If true, return log density
+///
+/// # Returns
+/// The Laplace density or log density
+pub fn dlaplace(x: f64, location: f64, scale: f64, give_log: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() {
+        return x + location + scale;
+    }
+    if scale <= 0.0 || !scale.is_finite() {
+        return f64::NAN;
+    }
+    d_exp(
+        -((x - location) / scale).abs() - LN_2 - scale.ln(),
+        give_log,
+    )
+}
+
+/// Laplace cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate CDF
+/// * `location` - Location (median) of the distribution
+/// * `scale` - Scale of the distribution (must be positive)
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn plaplace(x: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() {
+        return x + location + scale;
+    }
+    if scale <= 0.0 || !scale.is_finite() {
+        return f64::NAN;
+    }
+    let z = (x - location) / scale;
+    // log of the smaller tail, 0.5 exp(-|z|)
+    let log_small = -z.abs() - LN_2;
+    let log_large = (-log_small.exp()).ln_1p();
+    let (log_lower, log_upper) = if z < 0.0 {
+        (log_small, log_large)
+    } else {
+        (log_large, log_small)
+    };
+    dt_from_logs(log_lower, log_upper, lower_tail, log_p)
+}
+
+/// Laplace quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `location` - Location (median) of the distribution
+/// * `scale` - Scale of the distribution (must be positive)
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The quantile value
+pub fn qlaplace(p: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || location.is_nan() || scale.is_nan() {
+        return p + location + scale;
+    }
+    if scale <= 0.0 || !scale.is_finite() {
+        return f64::NAN;
+    }
+    if let Some(q) = q_p01_boundaries(p, f64::NEG_INFINITY, f64::INFINITY, lower_tail, log_p) {
+        return q;
+    }
+    // Invert whichever tail is below one half
+    let log_lower = dt_log(p, lower_tail, log_p);
+    let z = if log_lower < -LN_2 {
+        log_lower + LN_2
+    } else {
+        -(dt_clog(p, lower_tail, log_p) + LN_2)
+    };
+    location + scale * z
+}
+
+/// Laplace random number generation (by inversion)
+///
+/// # Arguments
+/// * `location` - Location (median) of the distribution
+/// * `scale` - Scale of the distribution (must be positive)
+/// * `rng` - Random number generator
+///
+/// # Returns
+/// A random sample from the Laplace distribution
+pub fn rlaplace<R: Rng>(location: f64, scale: f64, rng: &mut R) -> f64 {
+    if !location.is_finite() || !scale.is_finite() || scale <= 0.0 {
+        return f64::NAN;
+    }
+    let u: f64 = rng.sample(Open01);
+    let z = if u < 0.5 {
+        (2.0 * u).ln()
+    } else {
+        -(2.0 * (1.0 - u)).ln()
+    };
+    location + scale * z
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_dlaplace() {
+        assert_eq!(dlaplace(0.0, 0.0, 1.0, false), 0.5);
+        assert!((dlaplace(3.0, 1.0, 2.0, false) - 0.25 * (-1.0f64).exp()).abs() < 1e-15);
+        assert!((dlaplace(-700.0, 0.0, 1.0, true) + 700.0 + LN_2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_plaplace() {
+        assert_eq!(plaplace(0.0, 0.0, 1.0, true, false), 0.5);
+        assert!((plaplace(-1.0, 0.0, 1.0, true, false) - 0.18393972058572117).abs() < 1e-15);
+        assert!((plaplace(1.0, 0.0, 1.0, false, false) - 0.18393972058572117).abs() < 1e-15);
+        assert!((plaplace(1.0, 0.0, 1.0, true, false) - 0.8160602794142788).abs() < 1e-15);
+        // Upper tail far out on the log scale
+        assert!((plaplace(800.0, 0.0, 1.0, false, true) + 800.0 + LN_2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_qlaplace_inverts_plaplace() {
+        for &x in &[-30.0, -1.0, 0.0, 0.4, 30.0] {
+            let p = plaplace(x, 1.0, 2.0, true, true);
+            assert!((qlaplace(p, 1.0, 2.0, true, true) - x).abs() < 1e-10);
+            let q = plaplace(x, 1.0, 2.0, false, false);
+            assert!((qlaplace(q, 1.0, 2.0, false, false) - x).abs() < 1e-8);
+        }
+        assert_eq!(qlaplace(0.0, 0.0, 1.0, true, false), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_rlaplace() {
+        let mut rng = StdRng::seed_from_u64(21);
+        let n = 20_000;
+        let mean_abs = (0..n)
+            .map(|_| rlaplace(0.0, 2.0, &mut rng).abs())
+            .sum::<f64>()
+            / n as f64;
+        // E|X - location| = scale
+        assert!((mean_abs - 2.0).abs() < 0.06);
+    }
+}
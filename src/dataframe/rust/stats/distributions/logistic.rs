@@ -0,0 +1,176 @@
+//! Logistic distribution functions
+//!
+//! Ports of R's `nmath/dlogis.c`, `plogis.c`, `qlogis.c` and `rlogis.c`.
+//! Log probabilities go through `log1pexp` so that `plogis(x, log.p = TRUE)`
+//! stays finite and accurate for large |x|.
+
+use super::helpers::dpq::{dt_0, dt_1, log1_exp, log1pexp, q_p01_boundaries};
+use rand::Rng;
+use rand::distributions::Open01;
+
+/// Logistic probability density function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate density
+/// * `location` - Location of the distribution
+/// * `scale` - Scale of the distribution (must be positive)
+/// * `give_log` - If true, return log density
+///
+/// # Returns
+/// The logistic density or log density
+pub fn dlogis(x: f64, location: f64, scale: f64, give_log: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() {
+        return x + location + scale;
+    }
+    if scale <= 0.0 {
+        return f64::NAN;
+    }
+    let x = ((x - location) / scale).abs();
+    let e = (-x).exp();
+    let f = 1.0 + e;
+    if give_log {
+        -(x + (scale * f * f).ln())
+    } else {
+        e / (scale * f * f)
+    }
+}
+
+/// Logistic cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate CDF
+/// * `location` - Location of the distribution
+/// * `scale` - Scale of the distribution (must be positive)
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn plogis(x: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() {
+        return x + location + scale;
+    }
+    if scale <= 0.0 {
+        return f64::NAN;
+    }
+    let x = (x - location) / scale;
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    if x.is_infinite() {
+        return if x > 0.0 {
+            dt_1(lower_tail, log_p)
+        } else {
+            dt_0(lower_tail, log_p)
+        };
+    }
+    let t = if lower_tail { -x } else { x };
+    if log_p {
+        -log1pexp(t)
+    } else {
+        1.0 / (1.0 + t.exp())
+    }
+}
+
+/// Logistic quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `location` - Location of the distribution
+/// * `scale` - Scale of the distribution (zero gives a point mass)
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The quantile value
+pub fn qlogis(p: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || location.is_nan() || scale.is_nan() {
+        return p + location + scale;
+    }
+    if let Some(q) = q_p01_boundaries(p, f64::NEG_INFINITY, f64::INFINITY, lower_tail, log_p) {
+        return q;
+    }
+    if scale < 0.0 {
+        return f64::NAN;
+    }
+    if scale == 0.0 {
+        return location;
+    }
+    // logit of the lower-tail probability
+    let logit = if log_p {
+        if lower_tail {
+            p - log1_exp(p)
+        } else {
+            log1_exp(p) - p
+        }
+    } else if lower_tail {
+        (p / (1.0 - p)).ln()
+    } else {
+        ((1.0 - p) / p).ln()
+    };
+    location + scale * logit
+}
+
+/// Logistic random number generation (by inversion)
+///
+/// # Arguments
+/// * `location` - Location of the distribution
+/// * `scale` - Scale of the distribution (must be non-negative)
+/// * `rng` - Random number generator
+///
+/// # Returns
+/// A random sample from the logistic distribution
+pub fn rlogis<R: Rng>(location: f64, scale: f64, rng: &mut R) -> f64 {
+    if location.is_nan() || !scale.is_finite() || scale < 0.0 {
+        return f64::NAN;
+    }
+    if scale == 0.0 || !location.is_finite() {
+        return location;
+    }
+    let u: f64 = rng.sample(Open01);
+    location + scale * (u / (1.0 - u)).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_dlogis() {
+        assert!((dlogis(0.0, 0.0, 1.0, false) - 0.25).abs() < 1e-15);
+        // Symmetric about the location
+        assert_eq!(dlogis(1.5, 1.0, 2.0, false), dlogis(0.5, 1.0, 2.0, false));
+        assert!((dlogis(0.0, 0.0, 1.0, true) - 0.25f64.ln()).abs() < 1e-15);
+        // Far tail on the log scale: log f(x) ≈ -x
+        assert!((dlogis(800.0, 0.0, 1.0, true) + 800.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_plogis() {
+        assert_eq!(plogis(0.0, 0.0, 1.0, true, false), 0.5);
+        assert!((plogis(2.0, 0.0, 1.0, true, false) - 0.8807970779778823).abs() < 1e-15);
+        assert!((plogis(2.0, 0.0, 1.0, false, false) - 0.11920292202211755).abs() < 1e-15);
+        assert_eq!(plogis(-800.0, 0.0, 1.0, true, true), -800.0);
+        assert!((plogis(40.0, 0.0, 1.0, true, true) + (-40.0f64).exp()).abs() < 1e-30);
+    }
+
+    #[test]
+    fn test_qlogis() {
+        assert!((qlogis(0.8807970779778823, 0.0, 1.0, true, false) - 2.0).abs() < 1e-12);
+        assert!((qlogis(0.11920292202211755, 0.0, 1.0, false, false) - 2.0).abs() < 1e-12);
+        assert!((qlogis(-800.0, 0.0, 1.0, true, true) + 800.0).abs() < 1e-12);
+        assert!((qlogis(-800.0, 0.0, 1.0, false, true) - 800.0).abs() < 1e-12);
+        assert_eq!(qlogis(1.0, 0.0, 1.0, true, false), f64::INFINITY);
+        assert!(qlogis(1.2, 0.0, 1.0, true, false).is_nan());
+    }
+
+    #[test]
+    fn test_rlogis() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let n = 20_000;
+        let mean = (0..n).map(|_| rlogis(3.0, 1.0, &mut rng)).sum::<f64>() / n as f64;
+        assert!((mean - 3.0).abs() < 0.05);
+    }
+}
@@ -12,8 +12,34 @@
 //! ## Current Implementations
 //!
 //! - **Statrs Wrappers**: Complete DPQR functions for all major distributions
+//! - **Tail-Accurate Ports**: gamma, chi-squared, beta, F, Poisson and Weibull
+//!   p/q functions follow R's `pgamma.c`, TOMS 708, `qgamma.c` and `qbeta.c`,
+//!   so extreme tails and `log_p = true` do not underflow
 //! - **Custom Implementations**: Binomial, Wilcoxon, and other specialized distributions
+//! - **Extreme Value and Heavy Tails**: Gumbel, GEV, generalized Pareto, Pareto,
+//!   Zipf–Mandelbrot and the point-mass (Dirac) distribution
+//! - **Location-Scale Families**: Cauchy, logistic, Laplace, triangular and
+//!   location-scale t
+//! - **Quasi-Random Sampling**: Sobol (Owen-scrambled), Halton and Latin
+//!   hypercube designs, mapped onto any distribution by its quantile function
+//! - **Noncentral Distributions**: t, chi-squared, beta and F (d/p/q), as used
+//!   for exact power calculations
+//! - **Multivariate Distributions**: Multivariate normal (density, Genz CDF,
+//!   sampling), multivariate t, Dirichlet and multinomial
+//! - **Truncation**: Generic `Truncated` adaptor over any d/p/q triple, plus a
+//!   tail-accurate truncated normal with a dedicated sampler
+//! - **Common Interface**: `Distribution`, `ContinuousDistribution` and
+//!   `DiscreteDistribution` traits, implemented by the parameterised values in
+//!   `families` so generic code can accept any distribution
+//! - **Fitting**: Maximum-likelihood `fitdistr` for the common families, with
+//!   standard errors, covariance matrix and AIC/BIC
+//! - **Test Statistic Distributions**: Studentized range (p/q) and Wilcoxon
+//!   signed rank (d/p/q/r) for critical values and simulation
 //! - **Helper Functions**: Numerical utilities for high-precision calculations
+//! - **Random Number Generation**: Seedable shared generator (`set_seed`) used by
+//!   every `r*` WASM export
+//! - **Vectorised Evaluation**: Slice-based d/p/q functions with broadcast
+//!   parameters writing into caller-provided buffers
 //!
 //! ## Source Material
 //!
@@ -43,15 +69,46 @@
 
 // Custom implementations
 pub mod binomial;
+pub mod cauchy;
+pub mod dirac;
+pub mod dirichlet;
+pub mod families;
+pub mod fitdistr;
 pub mod gamma;
+pub mod gev;
+pub mod gpd;
+pub mod gumbel;
 pub mod helpers;
+pub mod laplace;
+pub mod location_scale_t;
+pub mod logistic;
+pub mod multinomial;
+pub mod multivariate_normal;
+pub mod multivariate_t;
+pub mod noncentral_beta;
+pub mod noncentral_chi_squared;
+pub mod noncentral_f;
+pub mod noncentral_t;
 pub mod normal;
+pub mod pareto;
+pub mod quasi_random;
+pub mod rng;
 pub mod shapiro_wilk;
+pub mod signrank;
 pub mod students_t;
+pub mod traits;
+pub mod triangular;
+pub mod truncated;
+pub mod truncated_normal;
+pub mod tukey;
+pub mod zipf;
 
 // Re-export commonly used helpers for backward compatibility
 pub use helpers::{clamp_unit, validate_integer};
 
+// Slice-based evaluation helpers
+pub mod vectorized;
+
 // Statrs wrapper implementations
 pub mod beta;
 pub mod chi_squared;
@@ -68,10 +125,37 @@ pub mod wilcoxon;
 
 // Re-export custom implementations
 pub use binomial::*;
+pub use cauchy::*;
+pub use dirac::*;
+pub use dirichlet::*;
+pub use fitdistr::*;
 pub use gamma::*;
+pub use gev::*;
+pub use gpd::*;
+pub use gumbel::*;
+pub use laplace::*;
+pub use location_scale_t::*;
+pub use logistic::*;
+pub use multinomial::*;
+pub use multivariate_normal::*;
+pub use multivariate_t::*;
+pub use noncentral_beta::*;
+pub use noncentral_chi_squared::*;
+pub use noncentral_f::*;
+pub use noncentral_t::*;
 pub use normal::*;
+pub use pareto::*;
+pub use quasi_random::*;
+pub use rng::*;
 pub use shapiro_wilk::*;
+pub use signrank::*;
 pub use students_t::*;
+pub use traits::*;
+pub use triangular::*;
+pub use truncated::*;
+pub use truncated_normal::*;
+pub use tukey::*;
+pub use zipf::*;
 
 // Re-export statrs wrapper implementations
 pub use beta::*;
@@ -92,3 +176,7 @@ pub use wilcoxon::*;
 pub mod distributions_wasm;
 #[cfg(feature = "wasm")]
 pub use distributions_wasm::*;
+#[cfg(feature = "wasm")]
+pub mod vectorized_wasm;
+#[cfg(feature = "wasm")]
+pub use vectorized_wasm::*;
@@ -0,0 +1,143 @@
+//! Multinomial distribution functions
+//!
+//! Ports of R's `dmultinom` (stats/R/distn.R) and `rmultinom`
+//! (nmath/rmultinom.c). Probabilities are normalised by their sum, so
+//! unnormalised weights are accepted as in R.
+
+use super::binomial::rbinom;
+use super::helpers::dpq::d_0;
+use super::helpers::validate_integer;
+use rand::Rng;
+use statrs::function::gamma::ln_gamma;
+
+fn check_prob(prob: &[f64]) -> Result<f64, String> {
+    if prob.is_empty() {
+        return Err("prob must not be empty".to_string());
+    }
+    if prob.iter().any(|&p| !(p.is_finite() && p >= 0.0)) {
+        return Err("probabilities must be finite and non-negative".to_string());
+    }
+    let total: f64 = prob.iter().sum();
+    if total <= 0.0 {
+        return Err("probabilities must not all be zero".to_string());
+    }
+    Ok(total)
+}
+
+/// Multinomial probability mass function
+///
+/// # Arguments
+/// * `x` - Counts in each category (non-negative integers); the number of
+///   trials is their sum
+/// * `prob` - Category probabilities (normalised to sum to one)
+/// * `give_log` - If true, return log probability
+///
+/// # Returns
+/// The probability or log probability of observing `x`
+pub fn dmultinom(x: &[f64], prob: &[f64], give_log: bool) -> Result<f64, String> {
+    let total = check_prob(prob)?;
+    if x.len() != prob.len() {
+        return Err(format!("x has length {}, expected {}", x.len(), prob.len()));
+    }
+    if x.iter().any(|&v| validate_integer(v).is_none()) {
+        return Err("x must contain non-negative integers".to_string());
+    }
+    let size: f64 = x.iter().sum();
+    let mut log_p = ln_gamma(size + 1.0);
+    for (&xi, &p) in x.iter().zip(prob) {
+        if p == 0.0 {
+            // Categories with zero probability must be empty
+            if xi != 0.0 {
+                return Ok(d_0(give_log));
+            }
+            continue;
+        }
+        log_p += xi * (p / total).ln() - ln_gamma(xi + 1.0);
+    }
+    Ok(if give_log { log_p } else { log_p.exp() })
+}
+
+/// Multinomial random number generation
+///
+/// Draws each category count from a binomial conditional on the counts
+/// already drawn.
+///
+/// # Arguments
+/// * `n` - Number of draws
+/// * `size` - Number of trials per draw (non-negative integer)
+/// * `prob` - Category probabilities (normalised to sum to one)
+/// * `rng` - Random number generator
+///
+/// # Returns
+/// An `n x k` row-major matrix of counts, where `k` is the length of `prob`
+pub fn rmultinom<R: Rng>(
+    n: usize,
+    size: f64,
+    prob: &[f64],
+    rng: &mut R,
+) -> Result<Vec<f64>, String> {
+    let total = check_prob(prob)?;
+    let size = validate_integer(size).ok_or("size must be a non-negative integer")? as f64;
+    let k = prob.len();
+    let mut out = vec![0.0; n * k];
+    for row in out.chunks_mut(k) {
+        let mut left = size;
+        let mut p_left = 1.0;
+        for (count, &p) in row.iter_mut().zip(prob).take(k - 1) {
+            let p = p / total;
+            if p > 0.0 && left > 0.0 {
+                let pp = p / p_left;
+                *count = if pp < 1.0 {
+                    rbinom(left, pp, rng)
+                } else {
+                    left
+                };
+                left -= *count;
+            }
+            if left <= 0.0 {
+                break;
+            }
+            p_left -= p;
+        }
+        row[k - 1] += left.max(0.0);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::distributions::dbinom;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_dmultinom() {
+        // R: dmultinom(c(1, 2, 1), prob = c(0.2, 0.5, 0.3)) = 0.18
+        let d = dmultinom(&[1.0, 2.0, 1.0], &[0.2, 0.5, 0.3], false).unwrap();
+        assert!((d - 0.18).abs() < 1e-14);
+        // Unnormalised weights and the binomial special case
+        let d = dmultinom(&[3.0, 7.0], &[1.0, 3.0], false).unwrap();
+        assert!((d - dbinom(3.0, 10.0, 0.25, false)).abs() < 1e-14);
+        assert_eq!(dmultinom(&[1.0, 1.0], &[1.0, 0.0], false), Ok(0.0));
+        assert!(dmultinom(&[1.5, 1.0], &[0.5, 0.5], false).is_err());
+        assert!(dmultinom(&[1.0, 1.0], &[0.0, 0.0], false).is_err());
+    }
+
+    #[test]
+    fn test_rmultinom() {
+        let mut rng = StdRng::seed_from_u64(47);
+        let prob = [0.2, 0.0, 0.5, 0.3];
+        let n = 5_000;
+        let draws = rmultinom(n, 20.0, &prob, &mut rng).unwrap();
+        for row in draws.chunks(4) {
+            assert_eq!(row.iter().sum::<f64>(), 20.0);
+            assert_eq!(row[1], 0.0);
+        }
+        for (j, &p) in prob.iter().enumerate() {
+            let mean = draws.iter().skip(j).step_by(4).sum::<f64>() / n as f64;
+            assert!((mean - 20.0 * p).abs() < 0.1, "category {j}: {mean}");
+        }
+        assert!(rmultinom(1, -1.0, &prob, &mut rng).is_err());
+    }
+}
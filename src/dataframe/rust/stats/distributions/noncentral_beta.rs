@@ -0,0 +1,316 @@
+//! Noncentral beta distribution functions
+//!
+//! Ports of R's `nmath/dnbeta.c`, `pnbeta.c` and `qnbeta.c` (AS 226 with
+//! the R84 and later improvements). The noncentral beta is a
+//! Poisson(ncp / 2) mixture of Beta(a + i, b) distributions and underlies
+//! the noncentral F.
+//!
+//! Source: R-4.5.1/nmath/{dnbeta,pnbeta,qnbeta}.c
+
+use super::beta::dbeta;
+use super::helpers::dpois_raw::dpois_raw;
+use super::helpers::dpq::{d_0, d_exp, dt_0, dt_1, dt_qiv, q_p01_boundaries};
+use super::helpers::regularized_incomplete::pbeta_raw;
+use statrs::function::gamma::ln_gamma;
+
+/// Noncentral beta probability density function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate density
+/// * `shape1` - First shape parameter (must be positive)
+/// * `shape2` - Second shape parameter (must be positive)
+/// * `ncp` - Noncentrality parameter (non-negative)
+/// * `give_log` - If true, return log density
+///
+/// # Returns
+/// The noncentral beta density or log density
+pub fn dnbeta(x: f64, shape1: f64, shape2: f64, ncp: f64, give_log: bool) -> f64 {
+    const EPS: f64 = 1e-15;
+    let (a, b) = (shape1, shape2);
+    if x.is_nan() || a.is_nan() || b.is_nan() || ncp.is_nan() {
+        return x + a + b + ncp;
+    }
+    if ncp < 0.0 || a <= 0.0 || b <= 0.0 || !a.is_finite() || !b.is_finite() || !ncp.is_finite() {
+        return f64::NAN;
+    }
+    if !(0.0..=1.0).contains(&x) {
+        return d_0(give_log);
+    }
+    if ncp == 0.0 {
+        return dbeta(x, a, b, give_log);
+    }
+
+    // Start the sum at its largest term
+    let ncp2 = 0.5 * ncp;
+    let dx2 = ncp2 * x;
+    let d = (dx2 - a - 1.0) / 2.0;
+    let disc = d * d + dx2 * (a + b) - a;
+    let k_max = if disc <= 0.0 {
+        0.0
+    } else {
+        (d + disc.sqrt()).ceil().max(0.0)
+    };
+
+    let term = dbeta(x, a + k_max, b, true);
+    let mut p_k = dpois_raw(k_max, ncp2, true);
+    if x == 0.0 || !term.is_finite() || !p_k.is_finite() {
+        return d_exp(p_k + term, give_log);
+    }
+
+    // Sum the rescaled terms (middle term = 1) and rescale at the end
+    p_k += term;
+    let mut sum = 1.0;
+    // Middle to the left
+    let mut term = 1.0;
+    let mut k = k_max;
+    while k > 0.0 && term > sum * EPS {
+        k -= 1.0;
+        term *= (k + 1.0) * (k + a) / (k + a + b) / dx2;
+        sum += term;
+    }
+    // Middle to the right
+    let mut term = 1.0;
+    let mut k = k_max;
+    loop {
+        term *= dx2 * (k + a + b) / (k + a) / (k + 1.0);
+        k += 1.0;
+        sum += term;
+        if term <= sum * EPS {
+            break;
+        }
+    }
+    d_exp(p_k + sum.ln(), give_log)
+}
+
+/// Lower-tail noncentral beta probability (R's `pnbeta_raw`)
+///
+/// `o_x` is `1 - x`, passed separately so callers can supply it accurately.
+fn pnbeta_raw(x: f64, o_x: f64, a: f64, b: f64, ncp: f64) -> f64 {
+    const ERRMAX: f64 = 1.0e-9;
+    // 100 is not enough for pf(ncp = 200), see R's PR#11277
+    const ITRMAX: f64 = 10000.0;
+
+    if x < 0.0 || o_x > 1.0 || (x == 0.0 && o_x == 1.0) {
+        return 0.0;
+    }
+    if x > 1.0 || o_x < 0.0 || (x == 1.0 && o_x == 0.0) {
+        return 1.0;
+    }
+
+    let c = ncp / 2.0;
+
+    // Initialise the series at the Poisson mode region
+    let x0 = (c - 7.0 * c.sqrt()).max(0.0).floor();
+    let a0 = a + x0;
+    let lbeta = ln_gamma(a0) + ln_gamma(b) - ln_gamma(a0 + b);
+    let mut temp = pbeta_raw(x, a0, b, true, false);
+    let log_o_x = if x < 0.5 { (-x).ln_1p() } else { o_x.ln() };
+    let mut gx = (a0 * x.ln() + b * log_o_x - lbeta - a0.ln()).exp();
+    let mut q = if a0 > a {
+        (-c + x0 * c.ln() - ln_gamma(x0 + 1.0)).exp()
+    } else {
+        (-c).exp()
+    };
+    let mut sumq = 1.0 - q;
+    let mut ans = q * temp;
+
+    // Recurse over subsequent terms until convergence
+    let mut j = x0;
+    loop {
+        j += 1.0;
+        temp -= gx;
+        gx *= x * (a + b + j - 1.0) / (a + j);
+        q *= c / j;
+        sumq -= q;
+        ans += temp * q;
+        let errbd = (temp - gx) * sumq;
+        if errbd <= ERRMAX || j >= ITRMAX + x0 {
+            break;
+        }
+    }
+    ans
+}
+
+/// Noncentral beta CDF from `x` and `1 - x` (R's `pnbeta2`)
+pub(crate) fn pnbeta2(
+    x: f64,
+    o_x: f64,
+    a: f64,
+    b: f64,
+    ncp: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    let ans = pnbeta_raw(x, o_x, a, b, ncp);
+    if lower_tail {
+        if log_p { ans.ln() } else { ans }
+    } else {
+        // Precaution against rounding above one
+        let ans = ans.min(1.0);
+        if log_p { (-ans).ln_1p() } else { 1.0 - ans }
+    }
+}
+
+/// Noncentral beta cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate CDF
+/// * `shape1` - First shape parameter (must be positive)
+/// * `shape2` - Second shape parameter (must be positive)
+/// * `ncp` - Noncentrality parameter (non-negative)
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn pnbeta(x: f64, shape1: f64, shape2: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || shape1.is_nan() || shape2.is_nan() || ncp.is_nan() {
+        return x + shape1 + shape2 + ncp;
+    }
+    if ncp < 0.0 || shape1 <= 0.0 || shape2 <= 0.0 {
+        return f64::NAN;
+    }
+    if x <= 0.0 {
+        return dt_0(lower_tail, log_p);
+    }
+    if x >= 1.0 {
+        return dt_1(lower_tail, log_p);
+    }
+    pnbeta2(x, 1.0 - x, shape1, shape2, ncp, lower_tail, log_p)
+}
+
+/// Noncentral beta quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `shape1` - First shape parameter (must be positive)
+/// * `shape2` - Second shape parameter (must be positive)
+/// * `ncp` - Noncentrality parameter (non-negative)
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The quantile value
+pub fn qnbeta(p: f64, shape1: f64, shape2: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    const ACCU: f64 = 1e-15;
+    // Must be larger than ACCU
+    const EPS: f64 = 1e-14;
+
+    if p.is_nan() || shape1.is_nan() || shape2.is_nan() || ncp.is_nan() {
+        return p + shape1 + shape2 + ncp;
+    }
+    if !shape1.is_finite() || ncp < 0.0 || shape1 <= 0.0 || shape2 <= 0.0 {
+        return f64::NAN;
+    }
+    if let Some(q) = q_p01_boundaries(p, 0.0, 1.0, lower_tail, log_p) {
+        return q;
+    }
+    let p = dt_qiv(p, lower_tail, log_p);
+    if p > 1.0 - f64::EPSILON {
+        return 1.0;
+    }
+    let cdf = |x: f64| pnbeta(x, shape1, shape2, ncp, true, false);
+
+    // 1. Bracket the quantile
+    let pp = (1.0 - f64::EPSILON).min(p * (1.0 + EPS));
+    let mut ux = 0.5;
+    while ux < 1.0 - f64::EPSILON && cdf(ux) < pp {
+        ux = 0.5 * (1.0 + ux);
+    }
+    let pp = p * (1.0 - EPS);
+    let mut lx = 0.5;
+    while lx > f64::MIN_POSITIVE && cdf(lx) > pp {
+        lx *= 0.5;
+    }
+
+    // 2. Interval halving
+    loop {
+        let nx = 0.5 * (lx + ux);
+        if cdf(nx) > p {
+            ux = nx;
+        } else {
+            lx = nx;
+        }
+        if (ux - lx) / nx <= ACCU {
+            break;
+        }
+    }
+    0.5 * (ux + lx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::distributions::pbeta;
+
+    #[test]
+    fn test_pnbeta_reduces_to_central() {
+        for &x in &[0.1, 0.4, 0.8] {
+            let central = pbeta(x, 2.0, 3.0, true, false);
+            assert!((pnbeta(x, 2.0, 3.0, 0.0, true, false) - central).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_pnbeta_poisson_mixture() {
+        // Direct Poisson mixture of central beta CDFs
+        let (x, a, b, ncp) = (0.6, 2.0, 4.0, 3.0);
+        let mut mix = 0.0;
+        for i in 0..200 {
+            let w = dpois_raw(i as f64, ncp / 2.0, false);
+            mix += w * pbeta_raw(x, a + i as f64, b, true, false);
+        }
+        let p = pnbeta(x, a, b, ncp, true, false);
+        assert!((p - mix).abs() < 1e-9, "{p} vs {mix}");
+        assert!((pnbeta(x, a, b, ncp, false, false) - (1.0 - mix)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dnbeta_matches_cdf_slope() {
+        for &(x, ncp) in &[(0.3, 2.0), (0.7, 40.0), (0.95, 250.0)] {
+            let h = 1e-6;
+            let num = (pnbeta(x + h, 2.5, 3.5, ncp, true, false)
+                - pnbeta(x - h, 2.5, 3.5, ncp, true, false))
+                / (2.0 * h);
+            let d = dnbeta(x, 2.5, 3.5, ncp, false);
+            assert!((num - d).abs() < 1e-5 * d.max(1.0), "x={x}: {num} vs {d}");
+        }
+        assert_eq!(dnbeta(1.5, 2.0, 2.0, 1.0, false), 0.0);
+    }
+
+    #[test]
+    fn test_qnbeta_inverts_pnbeta() {
+        for &p in &[0.01, 0.5, 0.975] {
+            let x = qnbeta(p, 2.0, 5.0, 4.0, true, false);
+            assert!((pnbeta(x, 2.0, 5.0, 4.0, true, false) - p).abs() < 1e-10);
+            let x = qnbeta(p.ln(), 2.0, 5.0, 4.0, false, true);
+            assert!((pnbeta(x, 2.0, 5.0, 4.0, false, false) - p).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_reference_values() {
+        // High-precision references: the Poisson-mixture series evaluated in
+        // 50-digit arithmetic (mpmath). AS 226 stops at an absolute error
+        // bound of 1e-9 and forms the upper tail as 1 - P, as R does, so small
+        // probabilities (and quantiles in the tails) are only that accurate
+        let cases = [
+            (0.3, 2.0, 3.0, 1.0, true, 0.26643631261584928),
+            (0.9, 5.0, 2.0, 10.0, true, 0.6983930640447402),
+            (0.01, 3.0, 4.0, 2.0, true, 7.320066625138665e-06),
+            (0.5, 10.0, 10.0, 50.0, true, 0.00044088755076215273),
+            (0.999, 2.0, 2.0, 20.0, false, 8.230788615980695e-05),
+        ];
+        for (x, a, b, ncp, lower, expected) in cases {
+            let p = pnbeta(x, a, b, ncp, lower, false);
+            assert!(
+                (p - expected).abs() < 1e-9,
+                "pbeta({x}, {a}, {b}, {ncp}) = {p}"
+            );
+        }
+        let q = qnbeta(0.5, 2.0, 3.0, 1.0, true, false);
+        assert!((q - 0.4416063918535663).abs() < 1e-9, "{q}");
+        let q = qnbeta(1e-5, 5.0, 2.0, 10.0, true, false);
+        assert!(((q - 0.16223118958953123) / q).abs() < 1e-4, "{q}");
+    }
+}
@@ -0,0 +1,538 @@
+//! Noncentral chi-squared distribution functions
+//!
+//! Ports of R's `nmath/dnchisq.c`, `pnchisq.c` and `qnchisq.c`. The
+//! distribution is a Poisson(ncp / 2) mixture of central chi-squared
+//! distributions with `df + 2i` degrees of freedom.
+//!
+//! Source: R-4.5.1/nmath/{dnchisq,pnchisq,qnchisq}.c
+
+use super::chi_squared::{dchisq, qchisq};
+use super::helpers::dpois_raw::dpois_raw;
+use super::helpers::dpq::{d_0, d_exp, log1_exp, logspace_add, q_p01_boundaries};
+use super::helpers::regularized_incomplete::pgamma_raw;
+use statrs::function::gamma::ln_gamma;
+use std::f64::consts::{LN_2, PI};
+
+/// `M_LN2 * DBL_MIN_EXP`: logs below this underflow
+const DBL_MIN_EXP_LN: f64 = -LN_2 * 1021.0;
+
+/// Noncentral chi-squared probability density function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate density
+/// * `df` - Degrees of freedom (non-negative)
+/// * `ncp` - Noncentrality parameter (non-negative)
+/// * `give_log` - If true, return log density
+///
+/// # Returns
+/// The noncentral chi-squared density or log density
+pub fn dnchisq(x: f64, df: f64, ncp: f64, give_log: bool) -> f64 {
+    const EPS: f64 = 5e-15;
+    if x.is_nan() || df.is_nan() || ncp.is_nan() {
+        return x + df + ncp;
+    }
+    if !df.is_finite() || !ncp.is_finite() || ncp < 0.0 || df < 0.0 {
+        return f64::NAN;
+    }
+    if x < 0.0 {
+        return d_0(give_log);
+    }
+    if x == 0.0 && df < 2.0 {
+        return f64::INFINITY;
+    }
+    if ncp == 0.0 {
+        return if df > 0.0 {
+            dchisq(x, df, give_log)
+        } else {
+            d_0(give_log)
+        };
+    }
+    if x == f64::INFINITY {
+        return d_0(give_log);
+    }
+
+    let ncp2 = 0.5 * ncp;
+
+    // Find the largest term of the mixture
+    let imax = ((-(2.0 + df) + ((2.0 - df) * (2.0 - df) + 4.0 * ncp * x).sqrt()) / 4.0)
+        .ceil()
+        .max(0.0);
+    let dfmid = df + 2.0 * imax;
+    let mid = if imax.is_finite() {
+        dpois_raw(imax, ncp2, false) * dchisq(x, dfmid, false)
+    } else {
+        0.0
+    };
+
+    if mid == 0.0 {
+        // Underflow: fall back to the central approximation where useful
+        if give_log || ncp > 1000.0 {
+            let nl = df + ncp;
+            let ic = nl / (nl + ncp);
+            return dchisq(x * ic, nl * ic, give_log);
+        }
+        return d_0(give_log);
+    }
+
+    let mut sum = mid;
+    let x2 = x * ncp2;
+
+    // Upper tail of the mixture
+    let (mut term, mut d, mut i) = (mid, dfmid, imax);
+    loop {
+        i += 1.0;
+        let q = x2 / i / d;
+        d += 2.0;
+        term *= q;
+        sum += term;
+        if !(q >= 1.0 || term * q > (1.0 - q) * EPS || term > 1e-10 * sum) {
+            break;
+        }
+    }
+    // Lower tail of the mixture
+    let (mut term, mut d, mut i) = (mid, dfmid, imax);
+    while i != 0.0 {
+        d -= 2.0;
+        let q = i * d / x2;
+        i -= 1.0;
+        term *= q;
+        sum += term;
+        if q < 1.0 && term * q <= (1.0 - q) * EPS {
+            break;
+        }
+    }
+    if give_log { sum.ln() } else { sum }
+}
+
+/// `log P(chi^2_f <= x)`, accurate even when the probability underflows
+fn log_pchisq_lower(x: f64, f: f64) -> f64 {
+    let p = pgamma_raw(0.5 * x, 0.5 * f, true, false);
+    if p >= f64::MIN_POSITIVE / f64::EPSILON {
+        return p.ln();
+    }
+    // Series P(a, y) = y^a e^-y / Gamma(a + 1) * sum_k y^k / ((a+1)...(a+k))
+    let (a, y) = (0.5 * f, 0.5 * x);
+    let (mut term, mut sum, mut k) = (1.0, 1.0, 0.0);
+    while term > sum * f64::EPSILON {
+        k += 1.0;
+        term *= y / (a + k);
+        sum += term;
+    }
+    a * y.ln() - y - ln_gamma(a + 1.0) + sum.ln()
+}
+
+/// Series evaluation of the noncentral chi-squared CDF (R's `pnchisq_raw`)
+///
+/// `errmax` and `reltol` are the absolute and relative convergence
+/// tolerances and `itrmax` the iteration limit for `ncp >= 80`.
+#[allow(clippy::too_many_arguments)]
+fn pnchisq_raw(
+    x: f64,
+    f: f64,
+    theta: f64,
+    errmax: f64,
+    reltol: f64,
+    itrmax: usize,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    let r_d_val = |p: f64| if log_p { p.ln() } else { p };
+    if x <= 0.0 {
+        if x == 0.0 && f == 0.0 {
+            let minus_lambda = -0.5 * theta;
+            return if lower_tail {
+                d_exp(minus_lambda, log_p)
+            } else if log_p {
+                log1_exp(minus_lambda)
+            } else {
+                -minus_lambda.exp_m1()
+            };
+        }
+        return if lower_tail {
+            r_d_val(0.0)
+        } else {
+            r_d_val(1.0)
+        };
+    }
+    if !x.is_finite() {
+        return if lower_tail {
+            r_d_val(1.0)
+        } else {
+            r_d_val(0.0)
+        };
+    }
+
+    if theta < 80.0 {
+        // Poisson-weighted sum of 110 central terms; ppois(110, 40, lower =
+        // FALSE) is about 2e-20
+        let lambda = 0.5 * theta;
+        if lower_tail
+            && f > 0.0
+            && x.ln() < LN_2 + 2.0 / f * (ln_gamma(f / 2.0 + 1.0) + DBL_MIN_EXP_LN)
+        {
+            // Every central term underflows: work on the log scale
+            let (mut sum, mut sum2) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+            let mut pr = -lambda;
+            for i in 0..110 {
+                let i = i as f64;
+                sum2 = logspace_add(sum2, pr);
+                sum = logspace_add(sum, pr + log_pchisq_lower(x, f + 2.0 * i));
+                pr += lambda.ln() - (i + 1.0).ln();
+            }
+            let ans = sum - sum2;
+            return if log_p { ans } else { ans.exp() };
+        }
+        let (mut sum, mut sum2) = (0.0, 0.0);
+        let mut pr = (-lambda).exp();
+        for i in 0..110 {
+            sum2 += pr;
+            sum += pr * pgamma_raw(0.5 * x, 0.5 * (f + 2.0 * i as f64), lower_tail, false);
+            pr *= lambda / (i + 1) as f64;
+        }
+        let ans = sum / sum2;
+        return if log_p { ans.ln() } else { ans };
+    }
+
+    // theta >= 80: series expansion
+    let lam = 0.5 * theta;
+    let mut lam_sml = -lam < DBL_MIN_EXP_LN;
+    let (mut u, mut lu, l_lam) = if lam_sml {
+        (0.0, -lam, lam.ln())
+    } else {
+        ((-lam).exp(), -1.0, -1.0)
+    };
+    let mut v = u;
+    let x2 = 0.5 * x;
+    let f2 = 0.5 * f;
+    let mut f_x_2n = f - x;
+
+    let t0 = x2 - f2;
+    let mut lt = if f2 * f64::EPSILON > 0.125 && t0.abs() < f64::EPSILON.sqrt() * f2 {
+        // Evade cancellation error for very large f with x close to f
+        (1.0 - t0) * (2.0 - t0 / (f2 + 1.0)) - 0.5 * (2.0 * PI).ln() - 0.5 * (f2 + 1.0).ln()
+    } else {
+        f2 * x2.ln() - x2 - ln_gamma(f2 + 1.0)
+    };
+    let mut t_sml = lt < DBL_MIN_EXP_LN;
+    let mut l_x = -1.0;
+    let (mut ans, mut term, mut t);
+    if t_sml {
+        if x > f + theta + 5.0 * (2.0 * (f + 2.0 * theta)).sqrt() {
+            // x > E[X] + 5 sd(X)
+            return if lower_tail {
+                r_d_val(1.0)
+            } else {
+                r_d_val(0.0)
+            };
+        }
+        l_x = x.ln();
+        ans = 0.0;
+        term = 0.0;
+        t = 0.0;
+    } else {
+        t = lt.exp();
+        ans = v * t;
+        term = ans;
+    }
+
+    let mut n = 1;
+    let mut f_2n = f + 2.0;
+    f_x_2n += 2.0;
+    while n <= itrmax {
+        // f_2n = f + 2n and f_x_2n = f - x + 2n
+        if f_x_2n > 0.0 {
+            let bound = t * x / f_x_2n;
+            if bound <= errmax && term <= reltol * ans {
+                break;
+            }
+        }
+        if lam_sml {
+            lu += l_lam - (n as f64).ln();
+            if lu >= DBL_MIN_EXP_LN {
+                // No underflow any more: change regime
+                u = lu.exp();
+                v = u;
+                lam_sml = false;
+            }
+        } else {
+            u *= lam / n as f64;
+            v += u;
+        }
+        if t_sml {
+            lt += l_x - f_2n.ln();
+            if lt >= DBL_MIN_EXP_LN {
+                t = lt.exp();
+                t_sml = false;
+            }
+        } else {
+            t *= x / f_2n;
+        }
+        if !lam_sml && !t_sml {
+            term = v * t;
+            ans += term;
+        }
+        n += 1;
+        f_2n += 2.0;
+        f_x_2n += 2.0;
+    }
+    // The series always sums the lower tail
+    if lower_tail {
+        r_d_val(ans)
+    } else if log_p {
+        (-ans).ln_1p()
+    } else {
+        0.5 - ans + 0.5
+    }
+}
+
+/// Noncentral chi-squared cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate CDF
+/// * `df` - Degrees of freedom (non-negative)
+/// * `ncp` - Noncentrality parameter (non-negative)
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn pnchisq(x: f64, df: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || df.is_nan() || ncp.is_nan() {
+        return x + df + ncp;
+    }
+    if !df.is_finite() || !ncp.is_finite() || df < 0.0 || ncp < 0.0 {
+        return f64::NAN;
+    }
+    let mut ans = pnchisq_raw(
+        x,
+        df,
+        ncp,
+        1e-12,
+        8.0 * f64::EPSILON,
+        1_000_000,
+        lower_tail,
+        log_p,
+    );
+    if x <= 0.0 || x == f64::INFINITY {
+        return ans;
+    }
+    if ncp >= 80.0 {
+        if lower_tail {
+            ans = ans.min(if log_p { 0.0 } else { 1.0 });
+        } else if !log_p && ans < 0.0 {
+            // The other tail was computed, so cancellation is likely
+            ans = 0.0;
+        }
+    }
+    if !log_p || ans < -1e-8 {
+        return ans;
+    }
+    // Probability near one: use the other tail for the log
+    let other = pnchisq_raw(
+        x,
+        df,
+        ncp,
+        1e-12,
+        8.0 * f64::EPSILON,
+        1_000_000,
+        !lower_tail,
+        false,
+    );
+    (-other).ln_1p()
+}
+
+/// Noncentral chi-squared quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `df` - Degrees of freedom (non-negative)
+/// * `ncp` - Noncentrality parameter (non-negative)
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The quantile value
+pub fn qnchisq(p: f64, df: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    const ACCU: f64 = 1e-13;
+    const RACC: f64 = 4.0 * f64::EPSILON;
+    // Looser tolerances for the bracketing search
+    const EPS: f64 = 1e-11;
+    const R_EPS: f64 = 1e-10;
+
+    if p.is_nan() || df.is_nan() || ncp.is_nan() {
+        return p + df + ncp;
+    }
+    if !df.is_finite() || df < 0.0 || ncp < 0.0 {
+        return f64::NAN;
+    }
+    if let Some(q) = q_p01_boundaries(p, 0.0, f64::INFINITY, lower_tail, log_p) {
+        return q;
+    }
+
+    let pp = if log_p { p.exp() } else { p };
+    if pp > 1.0 - f64::EPSILON {
+        return if lower_tail { f64::INFINITY } else { 0.0 };
+    }
+
+    // Pearson's (1959) approximation as a starting point
+    let mut ux = {
+        let b = (ncp * ncp) / (df + 3.0 * ncp);
+        let c = (df + 3.0 * ncp) / (df + 2.0 * ncp);
+        let ff = (df + 2.0 * ncp) / (c * c);
+        b + c * qchisq(p, ff, lower_tail, log_p)
+    };
+    if ux < 0.0 || ux.is_nan() {
+        ux = 1.0;
+    }
+    let ux0 = ux;
+
+    let (p, lower_tail) = if !lower_tail && ncp >= 80.0 {
+        // pnchisq_raw() sums the lower tail in this case anyway
+        (if log_p { -p.exp_m1() } else { 0.5 - p + 0.5 }, true)
+    } else {
+        (pp, lower_tail)
+    };
+    let raw = |x: f64, errmax: f64, reltol: f64, itrmax: usize| {
+        pnchisq_raw(x, df, ncp, errmax, reltol, itrmax, lower_tail, false)
+    };
+
+    // 1. Bracket the quantile
+    let mut pp = (1.0 - f64::EPSILON).min(p * (1.0 + EPS));
+    let mut lx;
+    if lower_tail {
+        while ux < f64::MAX && raw(ux, EPS, R_EPS, 10_000) < pp {
+            ux *= 2.0;
+        }
+        pp = p * (1.0 - EPS);
+        lx = ux0.min(f64::MAX);
+        while lx > f64::MIN_POSITIVE && raw(lx, EPS, R_EPS, 10_000) > pp {
+            lx *= 0.5;
+        }
+    } else {
+        while ux < f64::MAX && raw(ux, EPS, R_EPS, 10_000) > pp {
+            ux *= 2.0;
+        }
+        pp = p * (1.0 - EPS);
+        lx = ux0.min(f64::MAX);
+        while lx > f64::MIN_POSITIVE && raw(lx, EPS, R_EPS, 10_000) < pp {
+            lx *= 0.5;
+        }
+    }
+
+    // 2. Interval halving
+    loop {
+        let nx = 0.5 * (lx + ux);
+        let above = if lower_tail {
+            raw(nx, ACCU, RACC, 100_000) > p
+        } else {
+            raw(nx, ACCU, RACC, 100_000) < p
+        };
+        if above {
+            ux = nx;
+        } else {
+            lx = nx;
+        }
+        if (ux - lx) / nx <= ACCU {
+            break;
+        }
+    }
+    0.5 * (ux + lx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::distributions::pnorm;
+
+    /// Exact CDF for df = 1: X = (Z + sqrt(ncp))^2
+    fn pnchisq_df1(x: f64, ncp: f64) -> f64 {
+        let (r, m) = (x.sqrt(), ncp.sqrt());
+        pnorm(r - m, 0.0, 1.0, true, false) - pnorm(-r - m, 0.0, 1.0, true, false)
+    }
+
+    #[test]
+    fn test_pnchisq_df1_closed_form() {
+        for &(x, ncp) in &[
+            (0.5, 1.0),
+            (3.0, 2.5),
+            (10.0, 4.0),
+            (90.0, 100.0),
+            (130.0, 100.0),
+        ] {
+            let p = pnchisq(x, 1.0, ncp, true, false);
+            assert!(
+                (p - pnchisq_df1(x, ncp)).abs() < 1e-9,
+                "x={x} ncp={ncp}: {p}"
+            );
+            let q = pnchisq(x, 1.0, ncp, false, false);
+            assert!((p + q - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_pnchisq_reduces_to_central() {
+        // ncp = 0 is the central chi-squared: pchisq(3, 4) = 1 - 2.5 exp(-1.5)
+        let expected = 1.0 - 2.5 * (-1.5f64).exp();
+        assert!((pnchisq(3.0, 4.0, 0.0, true, false) - expected).abs() < 1e-12);
+        // Tiny x: the log-scale branch stays finite
+        let lp = pnchisq(1e-300, 10.0, 1.0, true, true);
+        assert!(lp.is_finite() && lp < -3000.0);
+    }
+
+    #[test]
+    fn test_dnchisq_integrates_pnchisq() {
+        // Central difference of the CDF matches the density
+        for &(x, df, ncp) in &[(2.0, 3.0, 1.5), (40.0, 5.0, 30.0), (150.0, 4.0, 120.0)] {
+            let h = 1e-5 * x;
+            let num = (pnchisq(x + h, df, ncp, true, false) - pnchisq(x - h, df, ncp, true, false))
+                / (2.0 * h);
+            let d = dnchisq(x, df, ncp, false);
+            assert!((num - d).abs() < 1e-6 * d.max(1e-3), "x={x}: {num} vs {d}");
+        }
+        assert_eq!(dnchisq(-1.0, 3.0, 1.0, false), 0.0);
+    }
+
+    #[test]
+    fn test_qnchisq_inverts_pnchisq() {
+        for &(p, df, ncp) in &[(0.05, 3.0, 2.0), (0.5, 10.0, 5.0), (0.99, 2.0, 100.0)] {
+            let x = qnchisq(p, df, ncp, true, false);
+            assert!((pnchisq(x, df, ncp, true, false) - p).abs() < 1e-9);
+            let x = qnchisq(p, df, ncp, false, false);
+            assert!((pnchisq(x, df, ncp, false, false) - p).abs() < 1e-9);
+        }
+        assert_eq!(qnchisq(1.0, 3.0, 2.0, true, false), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_reference_values() {
+        // High-precision references: the Poisson-mixture series evaluated in
+        // 50-digit arithmetic (mpmath), both tails summed directly
+        let cases = [
+            (5.0, 3.0, 2.0, true, 0.5934051800831557),
+            (0.1, 2.0, 4.0, true, 0.006933125644005353),
+            (1e-3, 1.0, 0.5, true, 0.01964853836572198),
+            (2.0, 6.0, 1.0, true, 0.05474882642983324),
+            (500.0, 50.0, 300.0, true, 0.9999273975193127),
+            (60.0, 4.0, 10.0, false, 8.964654685147428e-6),
+            (150.0, 10.0, 40.0, false, 2.9704613717939795e-8),
+        ];
+        for (x, df, ncp, lower, expected) in cases {
+            let p = pnchisq(x, df, ncp, lower, false);
+            assert!(
+                ((p - expected) / expected).abs() < 1e-12,
+                "pchisq({x}, {df}, {ncp}) = {p}"
+            );
+        }
+        for (p, df, ncp, lower, expected) in [
+            (0.95, 4.0, 3.0, true, 15.553058736887856),
+            (1e-4, 3.0, 1.0, true, 0.007277356821187859),
+            (1e-6, 5.0, 2.0, false, 45.6266679276893),
+        ] {
+            let q = qnchisq(p, df, ncp, lower, false);
+            assert!(
+                ((q - expected) / expected).abs() < 1e-11,
+                "qchisq({p}) = {q}"
+            );
+        }
+    }
+}
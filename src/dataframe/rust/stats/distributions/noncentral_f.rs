@@ -0,0 +1,223 @@
+//! Noncentral F distribution functions
+//!
+//! Ports of R's `nmath/dnf.c`, `pnf.c` and `qnf.c`. With
+//! y = (df1 / df2) x, the noncentral F maps onto the noncentral beta at
+//! y / (1 + y); for very large `df2` it reduces to a scaled noncentral
+//! chi-squared.
+//!
+//! Source: R-4.5.1/nmath/{dnf,pnf,qnf}.c
+
+use super::gamma::dgamma;
+use super::helpers::dpq::{d_0, dt_0, dt_1, q_p01_boundaries};
+use super::noncentral_beta::{dnbeta, pnbeta2, qnbeta};
+use super::noncentral_chi_squared::{dnchisq, pnchisq, qnchisq};
+
+/// Noncentral F probability density function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate density
+/// * `df1` - Numerator degrees of freedom (must be positive)
+/// * `df2` - Denominator degrees of freedom (must be positive)
+/// * `ncp` - Noncentrality parameter (non-negative)
+/// * `give_log` - If true, return log density
+///
+/// # Returns
+/// The noncentral F density or log density
+pub fn dnf(x: f64, df1: f64, df2: f64, ncp: f64, give_log: bool) -> f64 {
+    if x.is_nan() || df1.is_nan() || df2.is_nan() || ncp.is_nan() {
+        return x + df1 + df2 + ncp;
+    }
+    if df1 <= 0.0 || df2 <= 0.0 || ncp < 0.0 {
+        return f64::NAN;
+    }
+    if x < 0.0 {
+        return d_0(give_log);
+    }
+    if !ncp.is_finite() {
+        return f64::NAN;
+    }
+    if !df1.is_finite() && !df2.is_finite() {
+        return if x == 1.0 {
+            f64::INFINITY
+        } else {
+            d_0(give_log)
+        };
+    }
+    if !df2.is_finite() {
+        return df1 * dnchisq(x * df1, df1, ncp, give_log);
+    }
+    if df1 > 1e14 && ncp < 1e7 {
+        // Includes df1 = Inf, where the beta form is inaccurate; assumes
+        // ncp << df1
+        let f = 1.0 + ncp / df1;
+        let z = dgamma(1.0 / x / f, df2 / 2.0, df2 / 2.0, give_log);
+        return if give_log {
+            z - 2.0 * x.ln() - f.ln()
+        } else {
+            z / (x * x) / f
+        };
+    }
+
+    let y = (df1 / df2) * x;
+    let z = dnbeta(y / (1.0 + y), df1 / 2.0, df2 / 2.0, ncp, give_log);
+    if give_log {
+        z + df1.ln() - df2.ln() - 2.0 * y.ln_1p()
+    } else {
+        z * (df1 / df2) / (1.0 + y) / (1.0 + y)
+    }
+}
+
+/// Noncentral F cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate CDF
+/// * `df1` - Numerator degrees of freedom (must be positive)
+/// * `df2` - Denominator degrees of freedom (must be positive)
+/// * `ncp` - Noncentrality parameter (non-negative)
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn pnf(x: f64, df1: f64, df2: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || df1.is_nan() || df2.is_nan() || ncp.is_nan() {
+        return x + df1 + df2 + ncp;
+    }
+    if df1 <= 0.0 || df2 <= 0.0 || ncp < 0.0 || !ncp.is_finite() {
+        return f64::NAN;
+    }
+    if !df1.is_finite() && !df2.is_finite() {
+        return f64::NAN;
+    }
+    if x <= 0.0 {
+        return dt_0(lower_tail, log_p);
+    }
+    if x == f64::INFINITY {
+        return dt_1(lower_tail, log_p);
+    }
+    if df2 > 1e8 {
+        // Avoid problems with Inf and loss of accuracy
+        return pnchisq(x * df1, df1, ncp, lower_tail, log_p);
+    }
+    let y = (df1 / df2) * x;
+    pnbeta2(
+        y / (1.0 + y),
+        1.0 / (1.0 + y),
+        df1 / 2.0,
+        df2 / 2.0,
+        ncp,
+        lower_tail,
+        log_p,
+    )
+}
+
+/// Noncentral F quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `df1` - Numerator degrees of freedom (must be positive)
+/// * `df2` - Denominator degrees of freedom (must be positive)
+/// * `ncp` - Noncentrality parameter (non-negative)
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The quantile value
+pub fn qnf(p: f64, df1: f64, df2: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || df1.is_nan() || df2.is_nan() || ncp.is_nan() {
+        return p + df1 + df2 + ncp;
+    }
+    if df1 <= 0.0 || df2 <= 0.0 || ncp < 0.0 || !ncp.is_finite() {
+        return f64::NAN;
+    }
+    if !df1.is_finite() && !df2.is_finite() {
+        return f64::NAN;
+    }
+    if let Some(q) = q_p01_boundaries(p, 0.0, f64::INFINITY, lower_tail, log_p) {
+        return q;
+    }
+    if df2 > 1e8 {
+        return qnchisq(p, df1, ncp, lower_tail, log_p) / df1;
+    }
+    let y = qnbeta(p, df1 / 2.0, df2 / 2.0, ncp, lower_tail, log_p);
+    y / (1.0 - y) * (df2 / df1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::distributions::pf;
+
+    #[test]
+    fn test_pnf_reduces_to_central() {
+        for &x in &[0.5, 1.0, 3.0] {
+            let central = pf(x, 3.0, 12.0, true, false);
+            assert!((pnf(x, 3.0, 12.0, 0.0, true, false) - central).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_power_anova_reference() {
+        // R: power.anova.test(groups = 4, n = 5, between.var = 1,
+        //                     within.var = 3)$power = 0.3535594
+        let (df1, df2) = (3.0, 16.0);
+        let ncp = 3.0 * 5.0 * (1.0 / 3.0);
+        // Critical value qf(0.95, 3, 16) solved on pf directly
+        let (mut lo, mut hi) = (0.0, 10.0);
+        for _ in 0..100 {
+            let mid = 0.5 * (lo + hi);
+            if pf(mid, df1, df2, true, false) < 0.95 {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let crit = 0.5 * (lo + hi);
+        let power = pnf(crit, df1, df2, ncp, false, false);
+        assert!((power - 0.3535594).abs() < 1e-6, "power = {power}");
+    }
+
+    #[test]
+    fn test_dnf_and_qnf() {
+        let (df1, df2, ncp) = (4.0, 20.0, 6.0);
+        for &x in &[0.5, 2.0, 6.0] {
+            let h = 1e-6;
+            let num = (pnf(x + h, df1, df2, ncp, true, false)
+                - pnf(x - h, df1, df2, ncp, true, false))
+                / (2.0 * h);
+            assert!((num - dnf(x, df1, df2, ncp, false)).abs() < 1e-6);
+        }
+        for &p in &[0.1, 0.5, 0.9] {
+            let x = qnf(p, df1, df2, ncp, true, false);
+            assert!((pnf(x, df1, df2, ncp, true, false) - p).abs() < 1e-9);
+        }
+        // Infinite denominator df reduces to a scaled noncentral chi-squared
+        let p = pnf(2.0, 3.0, f64::INFINITY, 1.5, true, false);
+        assert!((p - pnchisq(6.0, 3.0, 1.5, true, false)).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_reference_values() {
+        // High-precision references: the Poisson-mixture series evaluated in
+        // 50-digit arithmetic (mpmath). Computed through the noncentral beta,
+        // so accurate to about 1e-9 absolute
+        let cases = [
+            (2.0, 3.0, 12.0, 4.0, true, 0.47046162142039703),
+            (0.2, 5.0, 10.0, 1.0, true, 0.029869832052715768),
+            (1.0, 10.0, 40.0, 15.0, true, 0.030481343526005057),
+            (10.0, 4.0, 20.0, 6.0, false, 0.007636073033892592),
+            (25.0, 3.0, 30.0, 2.0, false, 9.326355590529286e-07),
+        ];
+        for (x, df1, df2, ncp, lower, expected) in cases {
+            let p = pnf(x, df1, df2, ncp, lower, false);
+            assert!(
+                (p - expected).abs() < 1e-9,
+                "pf({x}, {df1}, {df2}, {ncp}) = {p}"
+            );
+        }
+        let q = qnf(0.95, 3.0, 16.0, 5.0, true, false);
+        assert!(((q - 7.631891543910761) / q).abs() < 1e-8, "{q}");
+        let q = qnf(1e-5, 4.0, 20.0, 6.0, false, false);
+        assert!(((q - 28.50472668250977) / q).abs() < 1e-4, "{q}");
+    }
+}
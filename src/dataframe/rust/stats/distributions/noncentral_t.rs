@@ -0,0 +1,328 @@
+//! Noncentral Student's t distribution functions
+//!
+//! Ports of R's `nmath/dnt.c`, `pnt.c` and `qnt.c`. The CDF uses the
+//! twin-series algorithm of Lenth (1989, AS 243) with Guenther's (1978)
+//! initialisation, switching to the Abramowitz & Stegun 26.7.10 normal
+//! approximation for very large `df` or `ncp`.
+//!
+//! Source: R-4.5.1/nmath/{dnt,pnt,qnt}.c
+
+use super::helpers::dpq::{dt_0, dt_1, dt_qiv, q_p01_boundaries};
+use super::helpers::regularized_incomplete::pbeta_raw;
+use super::normal::{dnorm, pnorm, qnorm};
+use super::students_t::{dt, pt, qt};
+use statrs::function::gamma::ln_gamma;
+use std::f64::consts::{FRAC_2_PI, LN_2};
+
+/// log(sqrt(pi))
+const LN_SQRT_PI: f64 = 0.572_364_942_924_700_1;
+
+/// Noncentral t probability density function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate density
+/// * `df` - Degrees of freedom (must be positive)
+/// * `ncp` - Noncentrality parameter
+/// * `give_log` - If true, return log density
+///
+/// # Returns
+/// The noncentral t density or log density
+pub fn dnt(x: f64, df: f64, ncp: f64, give_log: bool) -> f64 {
+    if x.is_nan() || df.is_nan() || ncp.is_nan() {
+        return x + df + ncp;
+    }
+    if df <= 0.0 {
+        return f64::NAN;
+    }
+    if ncp == 0.0 {
+        return dt(x, df, give_log);
+    }
+    if !x.is_finite() {
+        return if give_log { f64::NEG_INFINITY } else { 0.0 };
+    }
+    // For infinite df the density is N(ncp, 1); the formula below loses
+    // accuracy around df = 1e9
+    if !df.is_finite() || df > 1e8 {
+        return dnorm(x, ncp, 1.0, give_log);
+    }
+
+    let u = if x.abs() > (df * f64::EPSILON).sqrt() {
+        df.ln() - x.abs().ln()
+            + (pnt(x * ((df + 2.0) / df).sqrt(), df + 2.0, ncp, true, false)
+                - pnt(x, df, ncp, true, false))
+            .abs()
+            .ln()
+    } else {
+        // x ~= 0: same value as for x = 0
+        ln_gamma((df + 1.0) / 2.0) - ln_gamma(df / 2.0) - (LN_SQRT_PI + 0.5 * (df.ln() + ncp * ncp))
+    };
+    if give_log { u } else { u.exp() }
+}
+
+/// Noncentral t cumulative distribution function
+///
+/// # Arguments
+/// * `t` - Value at which to evaluate CDF
+/// * `df` - Degrees of freedom (must be positive)
+/// * `ncp` - Noncentrality parameter
+/// * `lower_tail` - If true, return P(X ≤ t), otherwise P(X > t)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn pnt(t: f64, df: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    const ITRMAX: usize = 1000;
+    const ERRMAX: f64 = 1e-12;
+
+    if t.is_nan() || df.is_nan() || ncp.is_nan() {
+        return t + df + ncp;
+    }
+    if df <= 0.0 {
+        return f64::NAN;
+    }
+    if ncp == 0.0 {
+        return pt(t, df, lower_tail, log_p);
+    }
+    if !t.is_finite() {
+        return if t < 0.0 {
+            dt_0(lower_tail, log_p)
+        } else {
+            dt_1(lower_tail, log_p)
+        };
+    }
+
+    let (negdel, tt, del) = if t >= 0.0 {
+        (false, t, ncp)
+    } else {
+        // The left tail is tiny when ncp is large: pnt(t) <= pnorm(-ncp)
+        if ncp > 40.0 && (!log_p || !lower_tail) {
+            return dt_0(lower_tail, log_p);
+        }
+        (true, -t, -ncp)
+    };
+
+    if df > 4e5 || del * del > 2.0 * LN_2 * 1021.0 {
+        // Abramowitz & Stegun 26.7.10
+        let s = 1.0 / (4.0 * df);
+        return pnorm(
+            tt * (1.0 - s),
+            del,
+            (1.0 + tt * tt * 2.0 * s).sqrt(),
+            lower_tail != negdel,
+            log_p,
+        );
+    }
+
+    // Initialise the twin series (Guenther 1978)
+    let x = t * t;
+    // rxb = 1 - x below, computed accurately when t^2 << df
+    let mut rxb = df / (x + df);
+    let x = x / (x + df);
+
+    let mut tnc;
+    if x > 0.0 {
+        let lambda = del * del;
+        let mut p = 0.5 * (-0.5 * lambda).exp();
+        if p == 0.0 {
+            // Underflow
+            return dt_0(lower_tail, log_p);
+        }
+        let mut q = FRAC_2_PI.sqrt() * p * del;
+        let mut s = 0.5 - p;
+        if s < 1e-7 {
+            s = -0.5 * (-0.5 * lambda).exp_m1();
+        }
+        let mut a = 0.5;
+        let b = 0.5 * df;
+        rxb = rxb.powf(b);
+        let albeta = LN_SQRT_PI + ln_gamma(b) - ln_gamma(0.5 + b);
+        let mut xodd = pbeta_raw(x, a, b, true, false);
+        let mut godd = 2.0 * rxb * (a * x.ln() - albeta).exp();
+        tnc = b * x;
+        let mut xeven = if tnc < f64::EPSILON { tnc } else { 1.0 - rxb };
+        let mut geven = tnc * rxb;
+        tnc = p * xodd + q * xeven;
+
+        // Repeat until convergence or the iteration limit
+        for it in 1..=ITRMAX {
+            a += 1.0;
+            xodd -= godd;
+            xeven -= geven;
+            godd *= x * (a + b - 1.0) / a;
+            geven *= x * (a + b - 0.5) / (a + 0.5);
+            p *= lambda / (2 * it) as f64;
+            q *= lambda / (2 * it + 1) as f64;
+            tnc += p * xodd + q * xeven;
+            s -= p;
+            // Rounding error, e.g. for (t, df, ncp) = (40, 10, 38.5)
+            if s < -1e-10 || (s <= 0.0 && it > 1) {
+                break;
+            }
+            let errbd = 2.0 * s * (xodd - godd);
+            if errbd.abs() < ERRMAX {
+                break;
+            }
+        }
+    } else {
+        // t = 0
+        tnc = 0.0;
+    }
+    tnc += pnorm(-del, 0.0, 1.0, true, false);
+
+    let lower_tail = lower_tail != negdel;
+    let tmp = tnc.min(1.0);
+    match (lower_tail, log_p) {
+        (true, false) => tmp,
+        (true, true) => tmp.ln(),
+        (false, false) => 0.5 - tmp + 0.5,
+        (false, true) => (-tmp).ln_1p(),
+    }
+}
+
+/// Noncentral t quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `df` - Degrees of freedom (must be positive)
+/// * `ncp` - Noncentrality parameter
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The quantile value
+pub fn qnt(p: f64, df: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    const ACCU: f64 = 1e-13;
+    // Must be larger than ACCU
+    const EPS: f64 = 1e-11;
+
+    if p.is_nan() || df.is_nan() || ncp.is_nan() {
+        return p + df + ncp;
+    }
+    if df <= 0.0 {
+        return f64::NAN;
+    }
+    if ncp == 0.0 && df >= 1.0 {
+        return qt(p, df, lower_tail, log_p);
+    }
+    if let Some(q) = q_p01_boundaries(p, f64::NEG_INFINITY, f64::INFINITY, lower_tail, log_p) {
+        return q;
+    }
+    if !df.is_finite() {
+        // df = Inf: the limit is N(ncp, 1)
+        return qnorm(p, ncp, 1.0, lower_tail, log_p);
+    }
+
+    let p = dt_qiv(p, lower_tail, log_p);
+    if p > 1.0 - f64::EPSILON {
+        return f64::INFINITY;
+    }
+    let cdf = |x: f64| pnt(x, df, ncp, true, false);
+
+    // 1. Bracket the quantile
+    let pp = (1.0 - f64::EPSILON).min(p * (1.0 + EPS));
+    let mut ux = ncp.max(1.0);
+    while ux < f64::MAX && cdf(ux) < pp {
+        ux *= 2.0;
+    }
+    let pp = p * (1.0 - EPS);
+    let mut lx = (-ncp).min(-1.0);
+    while lx > -f64::MAX && cdf(lx) > pp {
+        lx *= 2.0;
+    }
+
+    // 2. Interval halving
+    loop {
+        let nx = 0.5 * (lx + ux);
+        if cdf(nx) > p {
+            ux = nx;
+        } else {
+            lx = nx;
+        }
+        if ux - lx <= lx.abs().max(ux.abs()) * ACCU {
+            break;
+        }
+    }
+    0.5 * (lx + ux)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_pnt_at_zero_is_normal() {
+        // P(T <= 0) = pnorm(-ncp) for every df
+        for &(df, ncp) in &[(3.0, 1.0), (25.0, -0.7), (8.0, 2.5)] {
+            let expected = pnorm(-ncp, 0.0, 1.0, true, false);
+            assert!((pnt(0.0, df, ncp, true, false) - expected).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_power_t_test_reference() {
+        // R: power.t.test(n = 20, delta = 1)$power = 0.8689528
+        let df = 38.0;
+        let ncp = (20.0f64 / 2.0).sqrt();
+        let crit = qt(0.975, df, true, false);
+        let power = pnt(crit, df, ncp, false, false);
+        assert!((power - 0.8689528).abs() < 1e-6, "power = {power}");
+    }
+
+    #[test]
+    fn test_pnt_symmetry_and_tails() {
+        // P(T <= -t; ncp) = P(T >= t; -ncp)
+        let lo = pnt(-1.3, 7.0, 0.8, true, false);
+        let hi = pnt(1.3, 7.0, -0.8, false, false);
+        assert!((lo - hi).abs() < 1e-13);
+        let up = pnt(2.0, 10.0, 1.5, false, false);
+        assert!((up + pnt(2.0, 10.0, 1.5, true, false) - 1.0).abs() < 1e-13);
+    }
+
+    #[test]
+    fn test_dnt_and_qnt() {
+        for &x in &[-1.0, 0.0, 0.5, 3.0] {
+            let h = 1e-5;
+            let num =
+                (pnt(x + h, 6.0, 1.2, true, false) - pnt(x - h, 6.0, 1.2, true, false)) / (2.0 * h);
+            assert!((num - dnt(x, 6.0, 1.2, false)).abs() < 1e-7, "x = {x}");
+        }
+        // x = 0 uses the closed form
+        let d0 = (ln_gamma(3.5) - ln_gamma(3.0) - 0.5 * (6.0 * PI).ln() - 0.72).exp();
+        assert!((dnt(0.0, 6.0, 1.2, false) - d0).abs() < 1e-14);
+        for &p in &[0.05, 0.5, 0.95] {
+            let x = qnt(p, 6.0, 1.2, true, false);
+            assert!((pnt(x, 6.0, 1.2, true, false) - p).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_reference_values() {
+        // High-precision references: the Poisson-mixture series evaluated in
+        // 50-digit arithmetic (mpmath), cross-checked by quadrature of
+        // P(Z <= t sqrt(V / df) - ncp). AS 243 is accurate to about 1e-12
+        // absolute; tails computed as 1 - P keep that absolute accuracy
+        let cases = [
+            (1.5, 10.0, 1.0, true, 0.6695168482153547),
+            (-2.0, 5.0, 0.5, true, 0.018978976156883057),
+            (-1.0, 15.0, 2.0, true, 0.0016562401312168364),
+            (6.0, 20.0, 1.0, false, 0.00010792155904812577),
+            (12.0, 4.0, 2.0, false, 0.0037325838175833814),
+            (-4.0, 30.0, 1.0, true, 4.156799100009034e-06),
+            (30.0, 25.0, 20.0, false, 0.009538848842855581),
+            (0.5, 3.0, -1.0, true, 0.9242738812886191),
+        ];
+        for (t, df, ncp, lower, expected) in cases {
+            let p = pnt(t, df, ncp, lower, false);
+            assert!((p - expected).abs() < 1e-10, "pt({t}, {df}, {ncp}) = {p}");
+        }
+        for (p, df, ncp, lower, expected) in [
+            (0.9, 10.0, 1.0, true, 2.5260798970601866),
+            (0.01, 20.0, -1.0, true, -3.723282359916962),
+            (1e-4, 8.0, 1.5, false, 10.746092017800296),
+        ] {
+            let q = qnt(p, df, ncp, lower, false);
+            assert!(((q - expected) / expected).abs() < 1e-8, "qt({p}) = {q}");
+        }
+    }
+}
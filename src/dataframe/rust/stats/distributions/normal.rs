@@ -1,7 +1,10 @@
 //! Normal distribution functions
 //!
 //! This module provides normal distribution functions used by z-tests and other statistical tests.
-//! It wraps the statrs Normal distribution to provide a consistent interface.
+//! It wraps the statrs Normal distribution to provide a consistent interface; the quantile
+//! function is a port of R's nmath so it stays accurate far into both tails and on the log scale.
+//!
+//! Source: R-4.5.1/nmath/qnorm.c
 
 use super::super::core::{TailType, calculate_ci, calculate_p};
 
@@ -10,9 +13,13 @@ pub struct TestResultData {
     pub p_value: f64,
     pub confidence_interval: (f64, f64),
 }
+use super::helpers::dpq::{dt_civ, dt_qiv, q_p01_boundaries};
+use super::helpers::pnorm_both::pnorm_both;
 use rand::Rng;
 use statrs::distribution::{Continuous, ContinuousCDF, Normal};
 
+const LN_SQRT_2PI: f64 = 0.918_938_533_204_672_741_780_329_736_406;
+
 /// Creates a standard normal distribution (mean=0, std=1)
 ///
 /// # Returns
@@ -139,16 +146,128 @@ pub fn pnorm(x: f64, mean: f64, sd: f64, lower_tail: bool, log_p: bool) -> f64 {
 /// # Returns
 /// The quantile value
 pub fn qnorm(p: f64, mean: f64, sd: f64, lower_tail: bool, log_p: bool) -> f64 {
-    if sd <= 0.0 {
+    if p.is_nan() || mean.is_nan() || sd.is_nan() {
+        return p + mean + sd;
+    }
+    if let Some(x) = q_p01_boundaries(p, f64::NEG_INFINITY, f64::INFINITY, lower_tail, log_p) {
+        return x;
+    }
+    if sd < 0.0 {
         return f64::NAN;
     }
-    let dist = Normal::new(mean, sd).expect("validated parameters: sd > 0");
-    let mut p_val = if log_p { p.exp() } else { p };
-    if !lower_tail {
-        p_val = 1.0 - p_val;
+    if sd == 0.0 {
+        return mean;
     }
-    p_val = super::helpers::clamp_unit(p_val);
-    dist.inverse_cdf(p_val)
+
+    // Wichura's AS 241 rational approximations, accurate to about 1e-16
+    let p_ = dt_qiv(p, lower_tail, log_p);
+    let q = p_ - 0.5;
+    let val = if q.abs() <= 0.425 {
+        // 0.075 <= p_ <= 0.925
+        let r = 0.180625 - q * q;
+        q * (((((((r * 2509.0809287301226727 + 33430.575583588128105) * r
+            + 67265.770927008700853)
+            * r
+            + 45921.953931549871457)
+            * r
+            + 13731.693765509461125)
+            * r
+            + 1971.5909503065514427)
+            * r
+            + 133.14166789178437745)
+            * r
+            + 3.387132872796366608)
+            / (((((((r * 5226.495278852545925 + 28729.085735721942674) * r
+                + 39307.89580009271061)
+                * r
+                + 21213.794301586595867)
+                * r
+                + 5394.1960214247511077)
+                * r
+                + 687.1870074920579083)
+                * r
+                + 42.313330701600911252)
+                * r
+                + 1.0)
+    } else {
+        // r = sqrt(-log(min(p_, 1 - p_))), taken from log p directly when
+        // that is the small tail so extreme log probabilities stay exact
+        let lp = if log_p && ((lower_tail && q <= 0.0) || (!lower_tail && q > 0.0)) {
+            p
+        } else if q > 0.0 {
+            dt_civ(p, lower_tail, log_p).ln()
+        } else {
+            p_.ln()
+        };
+        let r = (-lp).sqrt();
+        let mut val = if r <= 5.0 {
+            // min(p_, 1 - p_) >= exp(-25)
+            let r = r - 1.6;
+            (((((((r * 7.7454501427834140764e-4 + 0.0227238449892691845833) * r
+                + 0.24178072517745061177)
+                * r
+                + 1.27045825245236838258)
+                * r
+                + 3.64784832476320460504)
+                * r
+                + 5.7694972214606914055)
+                * r
+                + 4.6303378461565452959)
+                * r
+                + 1.42343711074968357734)
+                / (((((((r * 1.05075007164441684324e-9 + 5.475938084995344946e-4) * r
+                    + 0.0151986665636164571966)
+                    * r
+                    + 0.14810397642748007459)
+                    * r
+                    + 0.68976733498510000455)
+                    * r
+                    + 1.6763848301838038494)
+                    * r
+                    + 2.05319162663775882187)
+                    * r
+                    + 1.0)
+        } else if r >= 816.0 {
+            // Only reachable on the log scale: leading asymptotic term
+            r * std::f64::consts::SQRT_2
+        } else {
+            let r = r - 5.0;
+            (((((((r * 2.01033439929228813265e-7 + 2.71155556874348757815e-5) * r
+                + 0.0012426609473880784386)
+                * r
+                + 0.026532189526576123093)
+                * r
+                + 0.29656057182850489123)
+                * r
+                + 1.7848265399172913358)
+                * r
+                + 5.4637849111641143699)
+                * r
+                + 6.6579046435011037772)
+                / (((((((r * 2.04426310338993978564e-15 + 1.4215117583164458887e-7) * r
+                    + 1.8463183175100546818e-5)
+                    * r
+                    + 7.868691311456132591e-4)
+                    * r
+                    + 0.0148753612908506148525)
+                    * r
+                    + 0.13692988092273580531)
+                    * r
+                    + 0.59983220655588793769)
+                    * r
+                    + 1.0)
+        };
+        if log_p && r > 27.0 {
+            // Beyond the range AS 241 was fitted on (p < 1e-300 or so):
+            // Newton steps on log Phi(-val) = lp, with the log tail from pnorm_both
+            for _ in 0..4 {
+                let (log_lower, _) = pnorm_both(-val, true);
+                val += (log_lower - lp) * (log_lower + 0.5 * val * val + LN_SQRT_2PI).exp();
+            }
+        }
+        if q < 0.0 { -val } else { val }
+    };
+    mean + sd * val
 }
 
 /// Normal random number generation
@@ -203,6 +322,41 @@ mod tests {
         assert!((p975 - 1.96).abs() < 1e-2); // 97.5th percentile ≈ 1.96
     }
 
+    #[test]
+    fn test_qnorm_reference_values() {
+        // mpmath at 60 digits; one case per AS 241 branch plus log-scale tails
+        let cases = [
+            (0.975, true, false, 1.9599639845400543),
+            (0.3, true, false, -0.5244005127080408),
+            (0.01, true, false, -2.326347874040841),
+            (0.01, false, false, 2.326347874040841),
+            (1e-10, true, false, -6.361340902404057),
+            (1e-300, true, false, -37.0470962993612),
+            (-800.0, true, true, -39.88469483825668),
+            (-800.0, false, true, 39.88469483825668),
+            (-1e5, true, true, -447.1978936785251),
+            (-1e6, false, true, 1414.2077829910174),
+        ];
+        for (p, lower_tail, log_p, expected) in cases {
+            let got = qnorm(p, 0.0, 1.0, lower_tail, log_p);
+            assert!(
+                ((got - expected) / expected).abs() < 1e-13,
+                "qnorm({}, {}, {}) = {}, expected {}",
+                p,
+                lower_tail,
+                log_p,
+                got,
+                expected
+            );
+        }
+        assert_eq!(qnorm(0.5, 3.0, 2.0, true, false), 3.0);
+        assert_eq!(qnorm(0.0, 0.0, 1.0, true, false), f64::NEG_INFINITY);
+        assert_eq!(qnorm(0.0, 0.0, 1.0, true, true), f64::INFINITY);
+        assert_eq!(qnorm(0.3, 1.5, 0.0, true, false), 1.5);
+        assert!(qnorm(1.5, 0.0, 1.0, true, false).is_nan());
+        assert!(qnorm(0.5, 0.0, -1.0, true, false).is_nan());
+    }
+
     #[test]
     fn test_z_test_result() {
         let result = z_test_result(1.96, TailType::Two, 100.0, 1.0, 0.05);
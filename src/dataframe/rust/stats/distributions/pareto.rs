@@ -0,0 +1,135 @@
+//! Pareto (type I) distribution functions
+//!
+//! Parameterised like the TypeScript `pareto.ts`: `scale` is the minimum
+//! value x_m > 0 and `shape` the tail index α > 0, so that
+//! P(X > x) = (x_m / x)^α for x ≥ x_m.
+
+use super::helpers::dpq::{d_exp, dt_0, dt_clog, dt_from_logs, log1_exp, q_p01_invalid};
+use rand::Rng;
+use rand::distributions::Open01;
+
+fn invalid(scale: f64, shape: f64) -> bool {
+    !scale.is_finite() || !shape.is_finite() || scale <= 0.0 || shape <= 0.0
+}
+
+/// Pareto probability density function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate density
+/// * `scale` - Minimum value x_m (must be positive)
+/// * `shape` - Tail index α (must be positive)
+/// * `give_log` - If true, return log density
+///
+/// # Returns
+/// The Pareto density or log density
+pub fn dpareto(x: f64, scale: f64, shape: f64, give_log: bool) -> f64 {
+    if x.is_nan() || scale.is_nan() || shape.is_nan() {
+        return x + scale + shape;
+    }
+    if invalid(scale, shape) {
+        return f64::NAN;
+    }
+    if x < scale || x.is_infinite() {
+        return d_exp(f64::NEG_INFINITY, give_log);
+    }
+    d_exp(
+        shape.ln() + shape * scale.ln() - (shape + 1.0) * x.ln(),
+        give_log,
+    )
+}
+
+/// Pareto cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate CDF
+/// * `scale` - Minimum value x_m (must be positive)
+/// * `shape` - Tail index α (must be positive)
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn ppareto(x: f64, scale: f64, shape: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || scale.is_nan() || shape.is_nan() {
+        return x + scale + shape;
+    }
+    if invalid(scale, shape) {
+        return f64::NAN;
+    }
+    if x <= scale {
+        return dt_0(lower_tail, log_p);
+    }
+    let log_upper = -shape * (x / scale).ln();
+    dt_from_logs(log1_exp(log_upper), log_upper, lower_tail, log_p)
+}
+
+/// Pareto quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `scale` - Minimum value x_m (must be positive)
+/// * `shape` - Tail index α (must be positive)
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The quantile value
+pub fn qpareto(p: f64, scale: f64, shape: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || scale.is_nan() || shape.is_nan() {
+        return p + scale + shape;
+    }
+    if invalid(scale, shape) || q_p01_invalid(p, log_p) {
+        return f64::NAN;
+    }
+    scale * (-dt_clog(p, lower_tail, log_p) / shape).exp()
+}
+
+/// Pareto random number generation (by inversion)
+///
+/// # Arguments
+/// * `scale` - Minimum value x_m (must be positive)
+/// * `shape` - Tail index α (must be positive)
+/// * `rng` - Random number generator
+///
+/// # Returns
+/// A random sample from the Pareto distribution
+pub fn rpareto<R: Rng>(scale: f64, shape: f64, rng: &mut R) -> f64 {
+    if invalid(scale, shape) {
+        return f64::NAN;
+    }
+    let u: f64 = rng.sample(Open01);
+    scale * (-u.ln() / shape).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_dpareto() {
+        // α x_m^α / x^(α + 1) = 3 * 8 / 81 at x = 3, x_m = 2, α = 3
+        assert!((dpareto(3.0, 2.0, 3.0, false) - 24.0 / 81.0).abs() < 1e-15);
+        assert_eq!(dpareto(1.0, 2.0, 3.0, false), 0.0);
+        assert!(dpareto(3.0, -2.0, 3.0, false).is_nan());
+    }
+
+    #[test]
+    fn test_ppareto_and_qpareto() {
+        assert!((ppareto(4.0, 2.0, 3.0, false, false) - 0.125).abs() < 1e-15);
+        assert!((ppareto(4.0, 2.0, 3.0, true, false) - 0.875).abs() < 1e-15);
+        assert!((qpareto(0.875, 2.0, 3.0, true, false) - 4.0).abs() < 1e-12);
+        assert!((qpareto(0.125f64.ln(), 2.0, 3.0, false, true) - 4.0).abs() < 1e-12);
+        // Far tail stays exact on the log scale
+        assert!((ppareto(1e300, 1.0, 2.0, false, true) + 2.0 * 1e300f64.ln()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rpareto() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for _ in 0..1000 {
+            assert!(rpareto(2.0, 3.0, &mut rng) >= 2.0);
+        }
+    }
+}
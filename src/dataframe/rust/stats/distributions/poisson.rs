@@ -1,7 +1,13 @@
 //! Poisson distribution wrapper using statrs
+//!
+//! `ppois` goes through the regularized incomplete gamma function for
+//! accurate tails, and `qpois` searches on `ppois` as R does.
 
+use super::helpers::dpq::{dt_0, dt_1, q_p01_boundaries};
+use super::helpers::regularized_incomplete::pgamma_raw;
+use super::normal::qnorm;
 use rand::Rng;
-use statrs::distribution::{Discrete, DiscreteCDF, Poisson};
+use statrs::distribution::{Discrete, Poisson};
 
 pub fn dpois(x: f64, lambda: f64, give_log: bool) -> f64 {
     if lambda <= 0.0 {
@@ -19,34 +25,130 @@ pub fn dpois(x: f64, lambda: f64, give_log: bool) -> f64 {
     }
 }
 
+/// Poisson cumulative distribution function
+///
+/// Uses `P(X <= x) = P(Gamma(floor(x) + 1, 1) > lambda)`, so either tail
+/// (and its log) is computed directly rather than as `1 - cdf`.
+///
+/// Source: R-4.5.1/nmath/ppois.c
 pub fn ppois(x: f64, lambda: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || lambda.is_nan() {
+        return x + lambda;
+    }
     if lambda <= 0.0 {
         return f64::NAN;
     }
-    let x_int = match super::helpers::validate_integer(x) {
-        Some(xi) => xi,
-        None => return f64::NAN,
-    };
-    let dist = Poisson::new(lambda).expect("validated parameters: lambda > 0");
-    let cdf = if lower_tail {
-        dist.cdf(x_int)
-    } else {
-        1.0 - dist.cdf(x_int)
-    };
-    if log_p { cdf.ln() } else { cdf }
+    if x < 0.0 {
+        return dt_0(lower_tail, log_p);
+    }
+    if !x.is_finite() {
+        return dt_1(lower_tail, log_p);
+    }
+    let x = (x + 1e-7).floor();
+    pgamma_raw(lambda, x + 1.0, !lower_tail, log_p)
 }
 
+/// Poisson quantile function
+///
+/// Cornish-Fisher start followed by a discrete search on `ppois`, so the
+/// result is consistent with `ppois` in both tails and on the log scale.
+///
+/// Source: R-4.5.1/nmath/qpois.c, qDiscrete_search.h
 pub fn qpois(p: f64, lambda: f64, lower_tail: bool, log_p: bool) -> f64 {
-    if lambda <= 0.0 {
+    // Tuning parameters of R's discrete quantile search
+    const PF_N: f64 = 8.0;
+    const PF_L: f64 = 2.0;
+    const Y_LARGE: f64 = 4096.0;
+    const INC_F: f64 = 1.0 / 64.0;
+    const I_SHRINK: f64 = 8.0;
+    const REL_TOL: f64 = 1e-15;
+    const XF: f64 = 4.0;
+
+    if p.is_nan() || lambda.is_nan() {
+        return p + lambda;
+    }
+    if lambda <= 0.0 || !lambda.is_finite() {
         return f64::NAN;
     }
-    let dist = Poisson::new(lambda).expect("validated parameters: lambda > 0");
-    let mut p_val = if log_p { p.exp() } else { p };
-    if !lower_tail {
-        p_val = 1.0 - p_val;
+    if let Some(q) = q_p01_boundaries(p, 0.0, f64::INFINITY, lower_tail, log_p) {
+        return q;
+    }
+
+    let mu = lambda;
+    let sigma = lambda.sqrt();
+    // Skewness
+    let gamma = 1.0 / sigma;
+
+    // y := approximate value (Cornish-Fisher expansion)
+    let z = qnorm(p, 0.0, 1.0, lower_tail, log_p);
+    let y = (mu + sigma * (z + gamma * (z * z - 1.0) / 6.0))
+        .round()
+        .max(0.0);
+
+    // Fuzz to ensure left continuity, without losing too much in the
+    // upper tail
+    let mut p = p;
+    if log_p {
+        let e = PF_L * f64::EPSILON;
+        if lower_tail && p > -f64::MAX {
+            p *= 1.0 + e;
+        } else {
+            p *= 1.0 - e;
+        }
+    } else {
+        let e = PF_N * f64::EPSILON;
+        if lower_tail {
+            p *= 1.0 - e;
+        } else if 1.0 - p > XF * e {
+            p *= 1.0 + e;
+        }
+    }
+
+    let mut z = ppois(y, lambda, lower_tail, log_p);
+    let search = |y: f64, z: &mut f64, incr: f64| -> f64 {
+        let mut y = y;
+        let left = if lower_tail { *z >= p } else { *z < p };
+        if left {
+            // Search to the left
+            loop {
+                let new_z = if y > 0.0 {
+                    ppois(y - incr, lambda, lower_tail, log_p)
+                } else {
+                    f64::NAN
+                };
+                if y <= 0.0 || new_z.is_nan() || (if lower_tail { new_z < p } else { new_z >= p }) {
+                    return y.max(0.0);
+                }
+                y = (y - incr).max(0.0);
+                *z = new_z;
+            }
+        } else {
+            // Search to the right
+            loop {
+                y += incr;
+                *z = ppois(y, lambda, lower_tail, log_p);
+                if z.is_nan() || (if lower_tail { *z >= p } else { *z < p }) {
+                    return y;
+                }
+            }
+        }
+    };
+
+    // If the Cornish-Fisher value is not too large a simple search is fine
+    if y < Y_LARGE {
+        return search(y, &mut z, 1.0);
+    }
+    // Otherwise use larger increments, notably initially
+    let mut y = y;
+    let mut incr = (y * INC_F).floor();
+    loop {
+        let old_incr = incr;
+        y = search(y, &mut z, incr);
+        incr = (incr / I_SHRINK).floor().max(1.0);
+        if !(old_incr > 1.0 && incr > y * REL_TOL) {
+            return y;
+        }
     }
-    p_val = super::helpers::clamp_unit(p_val);
-    dist.inverse_cdf(p_val) as f64
 }
 
 pub fn rpois<R: Rng>(lambda: f64, rng: &mut R) -> f64 {
@@ -113,4 +215,17 @@ mod tests {
         let expected = 1.0;
         assert!((qpois(p, 2.0, true, true) - expected).abs() < 1e-6);
     }
+
+    #[test]
+    fn test_poisson_extreme_tails() {
+        // Reference values from mpmath
+        let up = ppois(100.0, 2.0, false, false);
+        assert!((up / 3.7129068716887033e-131 - 1.0).abs() < 1e-13);
+        let lp = ppois(100.0, 2.0, false, true);
+        assert!((lp / -300.326_832_089_028_6 - 1.0).abs() < 1e-14);
+        // The quantile search agrees with ppois in the tail
+        assert_eq!(qpois(up, 2.0, false, false), 100.0);
+        assert_eq!(qpois(lp, 2.0, false, true), 100.0);
+        assert_eq!(ppois(-1.0, 2.0, true, false), 0.0);
+    }
 }
@@ -0,0 +1,461 @@
+//! Quasi-random sequences and Latin hypercube sampling
+//!
+//! Low-discrepancy point sets on the unit cube for Monte Carlo integration,
+//! sensitivity analysis and simulation designs:
+//!
+//! - [`sobol`]: Sobol' sequence in Gray-code order (Antonov–Saleev), with
+//!   optional Owen (nested uniform) scrambling
+//! - [`halton`]: Halton sequence, with optional random digit permutations
+//! - [`latin_hypercube`]: random Latin hypercube design
+//!
+//! Points are returned as flat row-major `n x dim` arrays, like the
+//! multivariate `r*` functions. Unscrambled sequences skip the origin, as R's
+//! `randtoolbox` does, so every coordinate lies in (0, 1); scrambled ones
+//! start at it, because the scramble moves it off the boundary and dropping
+//! it would spoil the balance of the first `2^m` points. Randomness comes
+//! from the caller's generator, so the shared one from [`with_rng`](super::with_rng)
+//! makes designs reproducible after [`set_seed`](super::set_seed).
+//!
+//! [`qmc_transform`] maps unit-cube points onto any [`Distribution`] through
+//! its quantile function, one distribution per column.
+//!
+//! References:
+//! - Joe, S. and Kuo, F. Y. (2008). Constructing Sobol sequences with better
+//!   two-dimensional projections. SIAM Journal on Scientific Computing, 30,
+//!   2635-2654.
+//! - Owen, A. B. (1995). Randomly permuted (t,m,s)-nets and (t,s)-sequences.
+//!   Monte Carlo and Quasi-Monte Carlo Methods in Scientific Computing,
+//!   299-317.
+//! - Burley, B. (2020). Practical Hash-based Owen Scrambling. Journal of
+//!   Computer Graphics Techniques, 9(4), 1-20.
+//! - McKay, M. D., Beckman, R. J. and Conover, W. J. (1979). A comparison of
+//!   three methods for selecting values of input variables in the analysis
+//!   of output from a computer code. Technometrics, 21, 239-245.
+
+use super::traits::Distribution;
+use rand::Rng;
+use rand::distributions::Open01;
+use rand::seq::SliceRandom;
+
+/// Bits of precision in a Sobol' coordinate
+const SOBOL_BITS: usize = 32;
+
+/// Primitive polynomials of degree `s` with middle coefficients `a`, and
+/// initial direction numbers `m`, for dimensions 2 onwards (Joe and Kuo,
+/// 2008). The first dimension is the van der Corput sequence. The table
+/// covers every primitive polynomial up to degree 7.
+const SOBOL_DIRECTIONS: [(u32, u32, &[u32]); 36] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+    (7, 7, &[1, 1, 3, 13, 7, 35, 63]),
+    (7, 8, &[1, 3, 5, 9, 1, 25, 53]),
+    (7, 14, &[1, 3, 1, 13, 9, 35, 107]),
+    (7, 19, &[1, 3, 1, 5, 27, 61, 31]),
+    (7, 21, &[1, 1, 5, 11, 19, 41, 61]),
+    (7, 28, &[1, 3, 5, 3, 3, 13, 69]),
+    (7, 31, &[1, 1, 7, 13, 1, 19, 1]),
+    (7, 32, &[1, 3, 7, 5, 13, 19, 59]),
+    (7, 37, &[1, 1, 3, 9, 25, 29, 41]),
+    (7, 41, &[1, 3, 5, 13, 23, 1, 55]),
+    (7, 42, &[1, 3, 7, 3, 13, 59, 17]),
+    (7, 50, &[1, 3, 1, 3, 5, 53, 69]),
+    (7, 55, &[1, 1, 5, 5, 23, 33, 13]),
+    (7, 56, &[1, 1, 7, 7, 1, 61, 123]),
+    (7, 59, &[1, 1, 7, 9, 13, 61, 49]),
+    (7, 62, &[1, 3, 3, 5, 3, 55, 33]),
+];
+
+/// Largest dimension supported by [`sobol`]
+pub const SOBOL_MAX_DIM: usize = SOBOL_DIRECTIONS.len() + 1;
+
+/// Direction numbers `v_k = m_k / 2^k` of dimension `j` (0-based), scaled
+/// to `SOBOL_BITS` bits
+fn sobol_directions(j: usize) -> [u32; SOBOL_BITS] {
+    let mut v = [0u32; SOBOL_BITS];
+    if j == 0 {
+        for (k, v) in v.iter_mut().enumerate() {
+            *v = 1 << (SOBOL_BITS - 1 - k);
+        }
+        return v;
+    }
+    let (s, a, m) = SOBOL_DIRECTIONS[j - 1];
+    let s = s as usize;
+    for k in 0..s {
+        v[k] = m[k] << (SOBOL_BITS - 1 - k);
+    }
+    for k in s..SOBOL_BITS {
+        let mut x = v[k - s] ^ (v[k - s] >> s);
+        for i in 1..s {
+            if (a >> (s - 1 - i)) & 1 == 1 {
+                x ^= v[k - i];
+            }
+        }
+        v[k] = x;
+    }
+    v
+}
+
+/// Owen scramble of a 32-bit fraction, keyed by `seed`
+///
+/// The Laine–Karras hash is applied to the bit-reversed value: each output
+/// bit then depends only on the bits above it in the original, which is
+/// exactly a nested uniform scramble (Burley, 2020).
+fn owen_scramble(x: u32, seed: u32) -> u32 {
+    let mut x = x.reverse_bits().wrapping_add(seed);
+    x ^= x.wrapping_mul(0x6c50b47c);
+    x ^= x.wrapping_mul(0xb82f1e52);
+    x ^= x.wrapping_mul(0xc7afe638);
+    x ^= x.wrapping_mul(0x8d22f6e6);
+    x.reverse_bits()
+}
+
+/// Sobol' low-discrepancy sequence
+///
+/// # Arguments
+/// * `n` - Number of points
+/// * `dim` - Dimension, at most [`SOBOL_MAX_DIM`]
+/// * `scramble` - Apply an independent Owen scramble to each coordinate
+/// * `rng` - Generator for the scramble seeds (unused when not scrambling)
+///
+/// # Returns
+/// Points as a flat row-major `n x dim` array in (0, 1), or an error for an
+/// unsupported dimension or more than 2^32 points
+pub fn sobol<R: Rng>(
+    n: usize,
+    dim: usize,
+    scramble: bool,
+    rng: &mut R,
+) -> Result<Vec<f64>, String> {
+    if dim == 0 || dim > SOBOL_MAX_DIM {
+        return Err(format!(
+            "Sobol dimension must be between 1 and {SOBOL_MAX_DIM}; use halton or latin_hypercube for more"
+        ));
+    }
+    let start = usize::from(!scramble);
+    if (n + start) as u64 > 1u64 << SOBOL_BITS {
+        return Err("Sobol sequence is limited to 2^32 points".to_string());
+    }
+    let directions: Vec<[u32; SOBOL_BITS]> = (0..dim).map(sobol_directions).collect();
+    let seeds: Vec<u32> = if scramble {
+        (0..dim).map(|_| rng.next_u32()).collect()
+    } else {
+        Vec::new()
+    };
+
+    let scale = 1.0 / (1u64 << SOBOL_BITS) as f64;
+    let mut x = vec![0u32; dim];
+    let mut out = Vec::with_capacity(n * dim);
+    for i in 0..n + start {
+        if i > 0 {
+            // Gray-code order: point i differs from point i - 1 in one
+            // direction number, the one at the lowest set bit of i
+            let c = i.trailing_zeros() as usize;
+            for (x, v) in x.iter_mut().zip(&directions) {
+                *x ^= v[c];
+            }
+        }
+        if i < start {
+            continue;
+        }
+        if scramble {
+            // The centre of the 2^-32 cell keeps points off the boundary
+            out.extend(
+                x.iter()
+                    .zip(&seeds)
+                    .map(|(&x, &seed)| (owen_scramble(x, seed) as f64 + 0.5) * scale),
+            );
+        } else {
+            out.extend(x.iter().map(|&x| x as f64 * scale));
+        }
+    }
+    Ok(out)
+}
+
+/// The first `count` primes
+fn first_primes(count: usize) -> Vec<u64> {
+    let mut primes: Vec<u64> = Vec::with_capacity(count);
+    let mut candidate = 2u64;
+    while primes.len() < count {
+        if primes
+            .iter()
+            .take_while(|&&p| p * p <= candidate)
+            .all(|&p| !candidate.is_multiple_of(p))
+        {
+            primes.push(candidate);
+        }
+        candidate += 1;
+    }
+    primes
+}
+
+/// Radical inverse of `i` in base `b`, with digit `k` replaced by
+/// `perms[k][digit]` when permutations are given
+fn radical_inverse(mut i: u64, b: u64, perms: Option<&[Vec<u64>]>) -> f64 {
+    let inv_b = 1.0 / b as f64;
+    let mut scale = inv_b;
+    let mut u = 0.0;
+    match perms {
+        None => {
+            while i > 0 {
+                u += (i % b) as f64 * scale;
+                i /= b;
+                scale *= inv_b;
+            }
+        }
+        // Trailing zero digits are permuted too, down to double precision
+        Some(perms) => {
+            for perm in perms {
+                u += perm[(i % b) as usize] as f64 * scale;
+                i /= b;
+                scale *= inv_b;
+            }
+        }
+    }
+    u
+}
+
+/// Halton low-discrepancy sequence
+///
+/// Coordinate `j` is the radical inverse of the point index in the `j`-th
+/// prime base. Without scrambling, coordinates in large bases are strongly
+/// correlated for small `n`; scrambling permutes each digit of each
+/// coordinate with an independent random permutation, which removes this.
+///
+/// # Arguments
+/// * `n` - Number of points
+/// * `dim` - Dimension
+/// * `scramble` - Apply random digit permutations
+/// * `rng` - Generator for the permutations (unused when not scrambling)
+///
+/// # Returns
+/// Points as a flat row-major `n x dim` array, or an error for `dim = 0`
+pub fn halton<R: Rng>(
+    n: usize,
+    dim: usize,
+    scramble: bool,
+    rng: &mut R,
+) -> Result<Vec<f64>, String> {
+    if dim == 0 {
+        return Err("Halton dimension must be at least 1".to_string());
+    }
+    let bases = first_primes(dim);
+    let perms: Vec<Vec<Vec<u64>>> = if scramble {
+        bases
+            .iter()
+            .map(|&b| {
+                // Enough digits for b^-digits to fall below double precision
+                let digits = (f64::MANTISSA_DIGITS as f64 / (b as f64).log2()).ceil() as usize;
+                (0..digits)
+                    .map(|_| {
+                        let mut perm: Vec<u64> = (0..b).collect();
+                        perm.shuffle(rng);
+                        perm
+                    })
+                    .collect()
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let start = u64::from(!scramble);
+    let mut out = Vec::with_capacity(n * dim);
+    for i in start..n as u64 + start {
+        for (j, &b) in bases.iter().enumerate() {
+            out.push(radical_inverse(i, b, perms.get(j).map(Vec::as_slice)));
+        }
+    }
+    Ok(out)
+}
+
+/// Random Latin hypercube design
+///
+/// Each coordinate takes exactly one value in each of the `n` intervals
+/// `[k/n, (k+1)/n)`, uniformly within the interval, with the intervals
+/// matched across coordinates by independent random permutations (as R's
+/// `lhs::randomLHS`).
+///
+/// # Arguments
+/// * `n` - Number of points
+/// * `dim` - Dimension
+/// * `rng` - Random number generator
+///
+/// # Returns
+/// Points as a flat row-major `n x dim` array in (0, 1), or an error for
+/// `dim = 0`
+pub fn latin_hypercube<R: Rng>(n: usize, dim: usize, rng: &mut R) -> Result<Vec<f64>, String> {
+    if dim == 0 {
+        return Err("Latin hypercube dimension must be at least 1".to_string());
+    }
+    let mut out = vec![0.0; n * dim];
+    let mut perm: Vec<usize> = (0..n).collect();
+    for j in 0..dim {
+        perm.shuffle(rng);
+        for (i, &cell) in perm.iter().enumerate() {
+            let u: f64 = rng.sample(Open01);
+            out[i * dim + j] = (cell as f64 + u) / n as f64;
+        }
+    }
+    Ok(out)
+}
+
+/// Map unit-cube points onto distributions through their quantile functions
+///
+/// # Arguments
+/// * `points` - Flat row-major `n x d` array of points in (0, 1), e.g. from
+///   [`sobol`], [`halton`] or [`latin_hypercube`]
+/// * `dists` - One distribution per column (length d)
+///
+/// # Returns
+/// The transformed points, same layout, or an error if `points` does not
+/// have `d` columns
+pub fn qmc_transform(points: &[f64], dists: &[&dyn Distribution]) -> Result<Vec<f64>, String> {
+    if dists.is_empty() || !points.len().is_multiple_of(dists.len()) {
+        return Err(format!(
+            "points must have one column per distribution ({} given)",
+            dists.len()
+        ));
+    }
+    Ok(points
+        .chunks(dists.len())
+        .flat_map(|row| row.iter().zip(dists).map(|(&u, d)| d.q(u, true, false)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::families::{Gamma, Normal, Poisson};
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    /// Number of points of the column `j` of `points` in each of `cells`
+    /// equal intervals
+    fn counts(points: &[f64], dim: usize, j: usize, cells: usize) -> Vec<usize> {
+        let mut c = vec![0; cells];
+        for row in points.chunks(dim) {
+            c[(row[j] * cells as f64) as usize] += 1;
+        }
+        c
+    }
+
+    #[test]
+    fn test_sobol_matches_reference() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let x = sobol(4, 3, false, &mut rng).unwrap();
+        let expected = [
+            [0.5, 0.5, 0.5],
+            [0.75, 0.25, 0.25],
+            [0.25, 0.75, 0.75],
+            [0.375, 0.375, 0.625],
+        ];
+        assert_eq!(x, expected.concat());
+        assert!(sobol(4, SOBOL_MAX_DIM + 1, false, &mut rng).is_err());
+        assert!(sobol(4, 0, false, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_sobol_net_property() {
+        // The first 2^8 points of the first two coordinates form a
+        // (0, 8, 2)-net: every 2^-a x 2^-(8-a) box holds one point, and
+        // Owen scrambling preserves this
+        let mut rng = StdRng::seed_from_u64(2);
+        for scramble in [false, true] {
+            let n = 256;
+            let x = if scramble {
+                sobol(n, SOBOL_MAX_DIM, true, &mut rng).unwrap()
+            } else {
+                // Include the origin, which the unscrambled sequence skips
+                let mut x = vec![0.0; SOBOL_MAX_DIM];
+                x.extend(sobol(n - 1, SOBOL_MAX_DIM, false, &mut rng).unwrap());
+                x
+            };
+            for a in 0..=8 {
+                let mut boxes = vec![0; n];
+                for row in x.chunks(SOBOL_MAX_DIM) {
+                    let i = (row[0] * (1 << a) as f64) as usize;
+                    let j = (row[1] * (1 << (8 - a)) as f64) as usize;
+                    boxes[(i << (8 - a)) | j] += 1;
+                }
+                assert!(
+                    boxes.iter().all(|&c| c == 1),
+                    "scramble {scramble}, a = {a}"
+                );
+            }
+            // Every coordinate is stratified on its own
+            for j in 0..SOBOL_MAX_DIM {
+                assert!(counts(&x, SOBOL_MAX_DIM, j, n).iter().all(|&c| c == 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_scrambled_sobol_is_reproducible() {
+        let a = sobol(64, 5, true, &mut StdRng::seed_from_u64(3)).unwrap();
+        let b = sobol(64, 5, true, &mut StdRng::seed_from_u64(3)).unwrap();
+        let c = sobol(64, 5, true, &mut StdRng::seed_from_u64(4)).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(a.iter().all(|&u| u > 0.0 && u < 1.0));
+    }
+
+    #[test]
+    fn test_halton() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let x = halton(3, 2, false, &mut rng).unwrap();
+        let expected = [0.5, 1.0 / 3.0, 0.25, 2.0 / 3.0, 0.75, 1.0 / 9.0];
+        assert!(x.iter().zip(&expected).all(|(a, b)| (a - b).abs() < 1e-15));
+
+        // Scrambled coordinates in base 3 stay stratified over 3^3 points
+        let x = halton(27, 4, true, &mut rng).unwrap();
+        assert!(counts(&x, 4, 1, 27).iter().all(|&c| c == 1));
+        assert!(x.iter().all(|&u| u > 0.0 && u < 1.0));
+        assert!(halton(3, 0, false, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_latin_hypercube() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let x = latin_hypercube(50, 3, &mut rng).unwrap();
+        for j in 0..3 {
+            assert!(counts(&x, 3, j, 50).iter().all(|&c| c == 1));
+        }
+        assert!(latin_hypercube(5, 0, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_qmc_transform_integration() {
+        // Scrambled Sobol estimates of the means are an order of magnitude
+        // tighter than the Monte Carlo standard error sd / sqrt(4096)
+        let mut rng = StdRng::seed_from_u64(7);
+        let normal = Normal::new(3.0, 2.0).unwrap();
+        let gamma = Gamma::new(2.0, 0.5).unwrap();
+        let poisson = Poisson::new(4.0).unwrap();
+        let dists: [&dyn Distribution; 3] = [&normal, &gamma, &poisson];
+        let u = sobol(4096, 3, true, &mut rng).unwrap();
+        let x = qmc_transform(&u, &dists).unwrap();
+        for (j, d) in dists.iter().enumerate() {
+            let mean = x.iter().skip(j).step_by(3).sum::<f64>() / 4096.0;
+            assert!((mean - d.mean()).abs() < 0.1 * d.std_dev() / 64.0, "{mean}");
+        }
+        assert!(qmc_transform(&u[..5], &dists).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+//! Wilcoxon signed rank distribution functions
+//!
+//! Ports of R's `nmath/signrank.c`. The statistic is the sum of the ranks
+//! `1..=n` carrying a positive sign, each sign being positive with
+//! probability one half. Exact probabilities use the count of subsets of
+//! `1..=n` with each sum, which is symmetric about `n (n + 1) / 4`.
+//!
+//! Source: R-4.5.1/nmath/signrank.c
+
+use super::helpers::dpq::{d_0, d_exp, dt_0, dt_1, dt_qiv, dt_val, q_p01_boundaries};
+use rand::Rng;
+use std::f64::consts::LN_2;
+use std::sync::{Arc, Mutex};
+
+/// Count table of the most recently used `n`, kept between calls as R's
+/// `signrank.c` does (building it costs O(n^3))
+static SIGNRANK_TABLE: Mutex<Option<(usize, Arc<Vec<f64>>)>> = Mutex::new(None);
+
+/// Clear the cached count table
+pub fn signrank_free() {
+    if let Ok(mut table) = SIGNRANK_TABLE.lock() {
+        *table = None;
+    }
+}
+
+/// Cached `csignrank_table(n)`
+fn signrank_table(n: usize) -> Arc<Vec<f64>> {
+    if let Ok(table) = SIGNRANK_TABLE.lock()
+        && let Some((cached_n, w)) = table.as_ref()
+        && *cached_n == n
+    {
+        return Arc::clone(w);
+    }
+    let w = Arc::new(csignrank_table(n));
+    if let Ok(mut table) = SIGNRANK_TABLE.lock() {
+        *table = Some((n, Arc::clone(&w)));
+    }
+    w
+}
+
+/// Number of subsets of `1..=n` summing to each of `0..=n (n + 1) / 4`
+///
+/// Only the lower half is stored; counts above it follow by symmetry.
+fn csignrank_table(n: usize) -> Vec<f64> {
+    let u = n * (n + 1) / 2;
+    let c = u / 2;
+    let mut w = vec![0.0; c + 1];
+    w[0] = 1.0;
+    if c >= 1 {
+        w[1] = 1.0;
+    }
+    for j in 2..=n {
+        let end = (j * (j + 1) / 2).min(c);
+        for i in (j..=end).rev() {
+            w[i] += w[i - j];
+        }
+    }
+    w
+}
+
+/// Count of subsets with sum `k`, from the half table of `csignrank_table`
+fn csignrank(w: &[f64], k: usize, n: usize) -> f64 {
+    let u = n * (n + 1) / 2;
+    if k > u {
+        return 0.0;
+    }
+    let k = if k > u / 2 { u - k } else { k };
+    w[k]
+}
+
+/// Signed rank probability mass function
+///
+/// # Arguments
+/// * `x` - Value of the statistic (non-integers have zero mass)
+/// * `n` - Number of observations (positive; rounded to an integer)
+/// * `give_log` - If true, return log probability
+///
+/// # Returns
+/// The probability or log probability of `x`
+pub fn dsignrank(x: f64, n: f64, give_log: bool) -> f64 {
+    if x.is_nan() || n.is_nan() {
+        return x + n;
+    }
+    if !n.is_finite() {
+        return f64::NAN;
+    }
+    let n = n.round();
+    if n <= 0.0 {
+        return f64::NAN;
+    }
+    if (x - x.round()).abs() > 1e-7 {
+        return d_0(give_log);
+    }
+    let x = x.round();
+    if x < 0.0 || x > n * (n + 1.0) / 2.0 {
+        return d_0(give_log);
+    }
+    let nn = n as usize;
+    let w = signrank_table(nn);
+    d_exp(csignrank(&w, x as usize, nn).ln() - n * LN_2, give_log)
+}
+
+/// Signed rank cumulative distribution function
+///
+/// # Arguments
+/// * `q` - Value at which to evaluate CDF
+/// * `n` - Number of observations (positive; rounded to an integer)
+/// * `lower_tail` - If true, return P(V <= q), otherwise P(V > q)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability
+pub fn psignrank(q: f64, n: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if q.is_nan() || n.is_nan() {
+        return q + n;
+    }
+    if !n.is_finite() {
+        return f64::NAN;
+    }
+    let n = n.round();
+    if n <= 0.0 {
+        return f64::NAN;
+    }
+    let x = (q + 1e-7).round();
+    if x < 0.0 {
+        return dt_0(lower_tail, log_p);
+    }
+    let u = n * (n + 1.0) / 2.0;
+    if x >= u {
+        return dt_1(lower_tail, log_p);
+    }
+    let nn = n as usize;
+    let w = signrank_table(nn);
+    let f = (-n * LN_2).exp();
+    // Sum over the shorter tail and flip if needed
+    let (p, lower_tail) = if x <= u / 2.0 {
+        let p: f64 = (0..=x as usize).map(|i| csignrank(&w, i, nn) * f).sum();
+        (p, lower_tail)
+    } else {
+        let x = u - x;
+        let p: f64 = (0..x as usize).map(|i| csignrank(&w, i, nn) * f).sum();
+        (p, !lower_tail)
+    };
+    dt_val(p, lower_tail, log_p)
+}
+
+/// Signed rank quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `n` - Number of observations (positive; rounded to an integer)
+/// * `lower_tail` - If true, p is P(V <= q), otherwise P(V > q)
+/// * `log_p` - If true, p is given as log probability
+///
+/// # Returns
+/// The smallest `q` with P(V <= q) >= p
+pub fn qsignrank(p: f64, n: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || n.is_nan() {
+        return p + n;
+    }
+    if !n.is_finite() {
+        return f64::NAN;
+    }
+    let n = n.round();
+    if n <= 0.0 {
+        return f64::NAN;
+    }
+    let u = n * (n + 1.0) / 2.0;
+    if let Some(q) = q_p01_boundaries(p, 0.0, u, lower_tail, log_p) {
+        return q;
+    }
+    let x = dt_qiv(p, lower_tail, log_p);
+    let nn = n as usize;
+    let w = signrank_table(nn);
+    let f = (-n * LN_2).exp();
+    let mut p = 0.0;
+    let mut q = 0;
+    if x <= 0.5 {
+        let x = x - 10.0 * f64::EPSILON;
+        loop {
+            p += csignrank(&w, q, nn) * f;
+            if p >= x {
+                break;
+            }
+            q += 1;
+        }
+        q as f64
+    } else {
+        let x = 1.0 - x + 10.0 * f64::EPSILON;
+        loop {
+            p += csignrank(&w, q, nn) * f;
+            if p > x {
+                break;
+            }
+            q += 1;
+        }
+        u - q as f64
+    }
+}
+
+/// Signed rank random number generation
+///
+/// # Arguments
+/// * `n` - Number of observations (non-negative; rounded to an integer)
+/// * `rng` - Random number generator
+///
+/// # Returns
+/// A random value of the statistic
+pub fn rsignrank<R: Rng>(n: f64, rng: &mut R) -> f64 {
+    if n.is_nan() {
+        return n;
+    }
+    if !n.is_finite() {
+        return f64::NAN;
+    }
+    let n = n.round();
+    if n < 0.0 {
+        return f64::NAN;
+    }
+    (1..=n as usize)
+        .map(|k| {
+            let u: f64 = rng.gen_range(0.0..1.0);
+            k as f64 * (u + 0.5).floor()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_dsignrank() {
+        // n = 5: 32 sign patterns, sums 0 and 15 reached once, 7 and 8 three times
+        assert!((dsignrank(0.0, 5.0, false) - 1.0 / 32.0).abs() < 1e-15);
+        assert!((dsignrank(7.0, 5.0, false) - 3.0 / 32.0).abs() < 1e-15);
+        assert_eq!(dsignrank(8.0, 5.0, false), dsignrank(7.0, 5.0, false));
+        assert_eq!(dsignrank(15.0, 5.0, false), dsignrank(0.0, 5.0, false));
+        assert_eq!(dsignrank(16.0, 5.0, false), 0.0);
+        assert_eq!(dsignrank(2.5, 5.0, false), 0.0);
+        let total: f64 = (0..=55).map(|x| dsignrank(x as f64, 10.0, false)).sum();
+        assert!((total - 1.0).abs() < 1e-14);
+        assert!(dsignrank(1.0, 0.0, false).is_nan());
+        assert!(dsignrank(1.0, f64::INFINITY, false).is_nan());
+    }
+
+    #[test]
+    fn test_table_cache() {
+        // Alternating sizes rebuild the table; results must not depend on it
+        let d20 = dsignrank(60.0, 20.0, false);
+        let d21 = dsignrank(60.0, 21.0, false);
+        assert_eq!(dsignrank(60.0, 20.0, false), d20);
+        signrank_free();
+        assert_eq!(dsignrank(60.0, 21.0, false), d21);
+    }
+
+    #[test]
+    fn test_psignrank() {
+        // R: psignrank(10, 10) = 0.04199219; for n = 5, sums 0..=3 arise 5 ways
+        assert!((psignrank(10.0, 10.0, true, false) - 0.04199219).abs() < 1e-8);
+        assert!((psignrank(3.0, 5.0, true, false) - 5.0 / 32.0).abs() < 1e-15);
+        // Symmetry: P(V <= u - k - 1) = P(V > k)
+        let upper = psignrank(40.0, 10.0, false, false);
+        let lower = psignrank(14.0, 10.0, true, false);
+        assert!((upper - lower).abs() < 1e-15);
+        let log_p = psignrank(10.0, 10.0, true, true);
+        assert!((log_p - 0.04199219f64.ln()).abs() < 1e-6);
+        assert_eq!(psignrank(-1.0, 10.0, true, false), 0.0);
+        assert_eq!(psignrank(55.0, 10.0, true, false), 1.0);
+    }
+
+    #[test]
+    fn test_qsignrank() {
+        // R: qsignrank(0.05, 10) = 11
+        assert_eq!(qsignrank(0.05, 10.0, true, false), 11.0);
+        assert_eq!(qsignrank(0.05, 10.0, false, false), 44.0);
+        for x in 0..=55 {
+            let p = psignrank(x as f64, 10.0, true, false);
+            assert_eq!(qsignrank(p, 10.0, true, false), x as f64, "x = {x}");
+        }
+        assert_eq!(qsignrank(0.0, 10.0, true, false), 0.0);
+        assert_eq!(qsignrank(1.0, 10.0, true, false), 55.0);
+        assert!(qsignrank(-0.1, 10.0, true, false).is_nan());
+    }
+
+    #[test]
+    fn test_rsignrank() {
+        let mut rng = StdRng::seed_from_u64(46);
+        let n = 10_000;
+        let draws: Vec<f64> = (0..n).map(|_| rsignrank(12.0, &mut rng)).collect();
+        assert!(draws.iter().all(|&v| (0.0..=78.0).contains(&v)));
+        // Mean n (n + 1) / 4 = 39, variance n (n + 1) (2n + 1) / 24 = 162.5
+        let mean = draws.iter().sum::<f64>() / n as f64;
+        let var = draws.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        assert!((mean - 39.0).abs() < 0.5, "mean = {mean}");
+        assert!((var - 162.5).abs() < 8.0, "var = {var}");
+        assert_eq!(rsignrank(0.0, &mut rng), 0.0);
+        assert!(rsignrank(f64::INFINITY, &mut rng).is_nan());
+    }
+}
@@ -108,21 +108,8 @@ pub fn t_sample_size(
     std_dev: f64,
     tail: TailType,
 ) -> f64 {
-    // Use a large df approximation for initial calculation
-    let df = 1e6;
-    let t_dist = standard_students_t(df);
-
-    let alpha_value = match tail {
-        TailType::Two => alpha / 2.0, // Two-tailed
-        _ => alpha,                   // One-tailed (left or right)
-    };
-
-    let t_alpha = t_dist.inverse_cdf(1.0 - alpha_value);
-    let t_beta = t_dist.inverse_cdf(power);
-
-    // Formula: n = ((t_alpha + t_beta) * std_dev / effect_size)^2
-    let n = ((t_alpha + t_beta) * std_dev / effect_size).powi(2);
-    n.ceil() // Rounds up to the next whole sample size
+    // Exact search on the noncentral t power
+    crate::stats::statistical_tests::t::t_sample_size(effect_size, alpha, power, std_dev, tail)
 }
 
 /// Student's t probability density function
@@ -185,6 +172,17 @@ pub fn qt(p: f64, df: f64, lower_tail: bool, log_p: bool) -> f64 {
         p_val = 1.0 - p_val;
     }
     p_val = p_val.clamp(0.0, 1.0);
+    if df > 1e5 && p_val > 0.0 && p_val < 1.0 {
+        // statrs' root search stalls for very large df; the Cornish-Fisher
+        // expansion (Abramowitz & Stegun 26.7.5) is exact to double precision here
+        let z = super::normal::qnorm(p_val, 0.0, 1.0, true, false);
+        let z2 = z * z;
+        let g1 = (z2 + 1.0) * z / 4.0;
+        let g2 = ((5.0 * z2 + 16.0) * z2 + 3.0) * z / 96.0;
+        let g3 = (((3.0 * z2 + 19.0) * z2 + 17.0) * z2 - 15.0) * z / 384.0;
+        let g4 = ((((79.0 * z2 + 776.0) * z2 + 1482.0) * z2 - 1920.0) * z2 - 945.0) * z / 92160.0;
+        return z + (g1 + (g2 + (g3 + g4 / df) / df) / df) / df;
+    }
     dist.inverse_cdf(p_val)
 }
 
@@ -234,6 +232,22 @@ mod tests {
         assert!((p975 - 1.96).abs() < 1e-2); // 97.5th percentile ≈ 1.96 for large df
     }
 
+    #[test]
+    fn test_qt_large_df() {
+        // mpmath references; statrs' inverse_cdf does not terminate at these df
+        let cases = [
+            (0.025, 2e5, -1.9599758459667684),
+            (0.9, 2e5, 1.2815557984827084),
+            (0.025, 1e7, -1.9599642217672055),
+            (0.9, 1e7, 1.281551650203083),
+            (0.025, 2f64.powi(40), -1.9599639845422119),
+        ];
+        for (p, df, expected) in cases {
+            let q = qt(p, df, true, false);
+            assert!((q - expected).abs() < 1e-12, "qt({p}, {df}) = {q}");
+        }
+    }
+
     #[test]
     fn test_t_test_result() {
         let result = t_test_result(2.0, 10.0, TailType::Two, 100.0, 1.0, 0.05);
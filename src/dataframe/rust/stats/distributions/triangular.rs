@@ -0,0 +1,183 @@
+//! Triangular distribution functions
+//!
+//! Support `[min, max]` with peak at `mode`. The CDF on each side of the
+//! mode is a quadratic; the tail on the far side of the mode is computed
+//! directly so that upper-tail probabilities near `max` are not lost to
+//! cancellation.
+
+use super::helpers::dpq::{d_0, d_exp, dt_0, dt_1, dt_civ, dt_qiv, q_p01_invalid};
+use rand::Rng;
+
+fn invalid(min: f64, max: f64, mode: f64) -> bool {
+    !min.is_finite()
+        || !max.is_finite()
+        || !mode.is_finite()
+        || min >= max
+        || mode < min
+        || mode > max
+}
+
+/// Triangular probability density function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate density
+/// * `min` - Lower limit of the support
+/// * `max` - Upper limit of the support (must exceed `min`)
+/// * `mode` - Peak of the density (between `min` and `max`)
+/// * `give_log` - If true, return log density
+///
+/// # Returns
+/// The triangular density or log density
+pub fn dtriang(x: f64, min: f64, max: f64, mode: f64, give_log: bool) -> f64 {
+    if x.is_nan() || min.is_nan() || max.is_nan() || mode.is_nan() {
+        return x + min + max + mode;
+    }
+    if invalid(min, max, mode) {
+        return f64::NAN;
+    }
+    if x < min || x > max {
+        return d_0(give_log);
+    }
+    let density = if x < mode {
+        2.0 * (x - min) / ((max - min) * (mode - min))
+    } else if x > mode {
+        2.0 * (max - x) / ((max - min) * (max - mode))
+    } else {
+        2.0 / (max - min)
+    };
+    d_exp(density.ln(), give_log)
+}
+
+/// Triangular cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate CDF
+/// * `min` - Lower limit of the support
+/// * `max` - Upper limit of the support (must exceed `min`)
+/// * `mode` - Peak of the density (between `min` and `max`)
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn ptriang(x: f64, min: f64, max: f64, mode: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || min.is_nan() || max.is_nan() || mode.is_nan() {
+        return x + min + max + mode;
+    }
+    if invalid(min, max, mode) {
+        return f64::NAN;
+    }
+    if x <= min {
+        return dt_0(lower_tail, log_p);
+    }
+    if x >= max {
+        return dt_1(lower_tail, log_p);
+    }
+    // Probability of the tail on the near side of the mode, and whether
+    // that is the lower tail
+    let (near, near_is_lower) = if x <= mode {
+        ((x - min) * (x - min) / ((max - min) * (mode - min)), true)
+    } else {
+        ((max - x) * (max - x) / ((max - min) * (max - mode)), false)
+    };
+    match (lower_tail == near_is_lower, log_p) {
+        (true, false) => near,
+        (true, true) => near.ln(),
+        (false, false) => 0.5 - near + 0.5,
+        (false, true) => (-near).ln_1p(),
+    }
+}
+
+/// Triangular quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `min` - Lower limit of the support
+/// * `max` - Upper limit of the support (must exceed `min`)
+/// * `mode` - Peak of the density (between `min` and `max`)
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The quantile value
+pub fn qtriang(p: f64, min: f64, max: f64, mode: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || min.is_nan() || max.is_nan() || mode.is_nan() {
+        return p + min + max + mode;
+    }
+    if invalid(min, max, mode) || q_p01_invalid(p, log_p) {
+        return f64::NAN;
+    }
+    let lower = dt_qiv(p, lower_tail, log_p);
+    if lower <= (mode - min) / (max - min) {
+        min + (lower * (max - min) * (mode - min)).sqrt()
+    } else {
+        max - (dt_civ(p, lower_tail, log_p) * (max - min) * (max - mode)).sqrt()
+    }
+}
+
+/// Triangular random number generation (by inversion)
+///
+/// # Arguments
+/// * `min` - Lower limit of the support
+/// * `max` - Upper limit of the support (must exceed `min`)
+/// * `mode` - Peak of the density (between `min` and `max`)
+/// * `rng` - Random number generator
+///
+/// # Returns
+/// A random sample from the triangular distribution
+pub fn rtriang<R: Rng>(min: f64, max: f64, mode: f64, rng: &mut R) -> f64 {
+    if invalid(min, max, mode) {
+        return f64::NAN;
+    }
+    let u = rng.gen_range(0.0..1.0);
+    qtriang(u, min, max, mode, true, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_dtriang() {
+        assert_eq!(dtriang(0.5, 0.0, 1.0, 0.5, false), 2.0);
+        assert_eq!(dtriang(0.25, 0.0, 1.0, 0.5, false), 1.0);
+        assert_eq!(dtriang(1.5, 0.0, 1.0, 0.5, false), 0.0);
+        // Mode at an end point
+        assert_eq!(dtriang(0.0, 0.0, 2.0, 0.0, false), 1.0);
+        assert!(dtriang(0.5, 1.0, 0.0, 0.5, false).is_nan());
+    }
+
+    #[test]
+    fn test_ptriang() {
+        assert_eq!(ptriang(0.25, 0.0, 1.0, 0.5, true, false), 0.125);
+        assert_eq!(ptriang(0.75, 0.0, 1.0, 0.5, false, false), 0.125);
+        assert_eq!(ptriang(0.5, 0.0, 1.0, 0.5, true, false), 0.5);
+        // Tiny upper tail near max stays exact
+        let x = 1.0 - 1e-9;
+        let log_upper = ptriang(x, 0.0, 1.0, 0.5, false, true);
+        assert!((log_upper - (2e-18f64).ln()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_qtriang_inverts_ptriang() {
+        for &x in &[0.1, 0.3, 0.5, 0.9, 1.9] {
+            let p = ptriang(x, 0.0, 2.0, 0.5, true, false);
+            assert!((qtriang(p, 0.0, 2.0, 0.5, true, false) - x).abs() < 1e-12);
+            let lq = ptriang(x, 0.0, 2.0, 0.5, false, true);
+            assert!((qtriang(lq, 0.0, 2.0, 0.5, false, true) - x).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_rtriang() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let n = 20_000;
+        let mean = (0..n)
+            .map(|_| rtriang(0.0, 3.0, 1.5, &mut rng))
+            .sum::<f64>()
+            / n as f64;
+        assert!((mean - 1.5).abs() < 0.03);
+    }
+}
@@ -0,0 +1,369 @@
+//! Truncated distributions
+//!
+//! [`Truncated`] restricts any distribution to `[lower, upper]` given its
+//! density, CDF and quantile functions (in the usual d/p/q signatures with
+//! the distribution parameters captured by closures). The retained mass and
+//! all tail probabilities are computed on the log scale, and on whichever
+//! side of the base distribution keeps them away from 1, so truncation to a
+//! region far in either tail stays accurate as long as the base functions
+//! are accurate there.
+//!
+//! For integer-valued distributions ([`Truncated::integer`]) the bounds are
+//! inclusive: the support is `ceil(lower), ..., floor(upper)`.
+
+use super::gamma::{dgamma, pgamma, qgamma};
+use super::helpers::dpq::{
+    d_0, dt_0, dt_1, dt_clog, dt_from_logs, dt_log, logspace_add, logspace_sub, q_p01_boundaries,
+};
+use super::poisson::{dpois, ppois, qpois};
+use rand::Rng;
+use rand::distributions::Open01;
+use std::f64::consts::LN_2;
+
+/// A distribution truncated to `[lower, upper]`
+///
+/// `D`, `P` and `Q` are the base density `(x, give_log)`, CDF
+/// `(x, lower_tail, log_p)` and quantile `(p, lower_tail, log_p)` functions.
+pub struct Truncated<D, P, Q> {
+    density: D,
+    cdf: P,
+    quantile: Q,
+    /// Smallest point of the support
+    lower: f64,
+    /// Largest point of the support
+    upper: f64,
+    integer: bool,
+    /// Work with upper tails of the base distribution (truncation above its
+    /// median), otherwise with lower tails
+    upper_side: bool,
+    /// Log base-tail probability beyond `lower` on the working side
+    log_lo: f64,
+    /// Log base-tail probability beyond `upper` on the working side
+    log_hi: f64,
+    /// Log probability of `[lower, upper]` under the base distribution
+    log_mass: f64,
+}
+
+impl<D, P, Q> Truncated<D, P, Q>
+where
+    D: Fn(f64, bool) -> f64,
+    P: Fn(f64, bool, bool) -> f64,
+    Q: Fn(f64, bool, bool) -> f64,
+{
+    /// Truncate a continuous distribution to `[lower, upper]`
+    ///
+    /// Errors if the bounds are NaN or reversed, or if the base
+    /// distribution puts no mass on the interval.
+    pub fn new(density: D, cdf: P, quantile: Q, lower: f64, upper: f64) -> Result<Self, String> {
+        Self::build(density, cdf, quantile, lower, upper, false)
+    }
+
+    /// Truncate an integer-valued distribution to `ceil(lower), ...,
+    /// floor(upper)`
+    pub fn integer(
+        density: D,
+        cdf: P,
+        quantile: Q,
+        lower: f64,
+        upper: f64,
+    ) -> Result<Self, String> {
+        Self::build(density, cdf, quantile, lower.ceil(), upper.floor(), true)
+    }
+
+    fn build(
+        density: D,
+        cdf: P,
+        quantile: Q,
+        lower: f64,
+        upper: f64,
+        integer: bool,
+    ) -> Result<Self, String> {
+        if lower.is_nan() || upper.is_nan() || lower > upper {
+            return Err(format!("invalid truncation bounds [{}, {}]", lower, upper));
+        }
+        let mut t = Truncated {
+            density,
+            cdf,
+            quantile,
+            lower,
+            upper,
+            integer,
+            upper_side: false,
+            log_lo: 0.0,
+            log_hi: 0.0,
+            log_mass: 0.0,
+        };
+        t.upper_side = t.log_cdf(t.below_cut(), true) > -LN_2;
+        t.log_lo = t.log_cdf(t.below_cut(), !t.upper_side);
+        t.log_hi = t.log_cdf(upper, !t.upper_side);
+        t.log_mass = if t.upper_side {
+            logspace_sub(t.log_lo, t.log_hi)
+        } else {
+            logspace_sub(t.log_hi, t.log_lo)
+        };
+        if t.log_mass.is_nan() || t.log_mass == f64::NEG_INFINITY {
+            return Err(format!(
+                "the distribution has no mass in [{}, {}]",
+                lower, upper
+            ));
+        }
+        Ok(t)
+    }
+
+    /// Point whose base CDF excludes exactly the mass below `lower`
+    fn below_cut(&self) -> f64 {
+        if self.integer {
+            self.lower - 1.0
+        } else {
+            self.lower
+        }
+    }
+
+    /// Base `log P(X <= x)` (or `log P(X > x)`), with the infinite limits
+    /// handled here rather than by the base function
+    fn log_cdf(&self, x: f64, lower_tail: bool) -> f64 {
+        if x == f64::NEG_INFINITY {
+            dt_0(lower_tail, true)
+        } else if x == f64::INFINITY {
+            dt_1(lower_tail, true)
+        } else {
+            (self.cdf)(x, lower_tail, true)
+        }
+    }
+
+    /// Log of the base mass of `[lower, upper]`
+    pub fn log_mass(&self) -> f64 {
+        self.log_mass
+    }
+
+    /// Truncated density (or probability mass)
+    pub fn d(&self, x: f64, give_log: bool) -> f64 {
+        if x.is_nan() {
+            return x;
+        }
+        if x < self.lower || x > self.upper {
+            return d_0(give_log);
+        }
+        let log_d = (self.density)(x, true) - self.log_mass;
+        if give_log { log_d } else { log_d.exp() }
+    }
+
+    /// Truncated cumulative distribution function
+    pub fn p(&self, x: f64, lower_tail: bool, log_p: bool) -> f64 {
+        if x.is_nan() {
+            return x;
+        }
+        let x = if self.integer { x.floor() } else { x };
+        if x < self.lower {
+            return dt_0(lower_tail, log_p);
+        }
+        if x >= self.upper {
+            return dt_1(lower_tail, log_p);
+        }
+        let log_x = self.log_cdf(x, !self.upper_side);
+        // Base mass of [lower, x] and (x, upper]
+        let (below, above) = if self.upper_side {
+            (
+                logspace_sub(self.log_lo, log_x),
+                logspace_sub(log_x, self.log_hi),
+            )
+        } else {
+            (
+                logspace_sub(log_x, self.log_lo),
+                logspace_sub(self.log_hi, log_x),
+            )
+        };
+        dt_from_logs(
+            below - self.log_mass,
+            above - self.log_mass,
+            lower_tail,
+            log_p,
+        )
+    }
+
+    /// Truncated quantile function
+    pub fn q(&self, p: f64, lower_tail: bool, log_p: bool) -> f64 {
+        if p.is_nan() {
+            return p;
+        }
+        if let Some(q) = q_p01_boundaries(p, self.lower, self.upper, lower_tail, log_p) {
+            return q;
+        }
+        let x = if self.upper_side {
+            // P(X > x) = P(X > upper) + (1 - p) * mass
+            let target = logspace_add(self.log_hi, dt_clog(p, lower_tail, log_p) + self.log_mass);
+            (self.quantile)(target.min(0.0), false, true)
+        } else {
+            // P(X <= x) = P(X < lower) + p * mass
+            let target = logspace_add(self.log_lo, dt_log(p, lower_tail, log_p) + self.log_mass);
+            (self.quantile)(target.min(0.0), true, true)
+        };
+        x.clamp(self.lower, self.upper)
+    }
+
+    /// Random draw by inversion of an open-interval uniform, so an unbounded
+    /// side never yields an infinite draw
+    pub fn r<R: Rng>(&self, rng: &mut R) -> f64 {
+        let u: f64 = rng.sample(Open01);
+        self.q(u, true, false)
+    }
+}
+
+/// Boxed base density `(x, give_log)`
+pub type DensityFn = Box<dyn Fn(f64, bool) -> f64>;
+
+/// Boxed base CDF or quantile function `(x, lower_tail, log_p)`
+pub type ProbabilityFn = Box<dyn Fn(f64, bool, bool) -> f64>;
+
+/// A truncated distribution over boxed base functions
+pub type BoxedTruncated = Truncated<DensityFn, ProbabilityFn, ProbabilityFn>;
+
+/// Gamma distribution (shape, rate) truncated to `[lower, upper]`
+///
+/// Both tails of the base CDF come from the regularised incomplete gamma
+/// functions directly, so truncation far into the right tail is accurate.
+pub fn truncated_gamma(
+    shape: f64,
+    rate: f64,
+    lower: f64,
+    upper: f64,
+) -> Result<BoxedTruncated, String> {
+    if shape.is_nan() || rate.is_nan() || shape <= 0.0 || rate <= 0.0 {
+        return Err("shape and rate must be positive".to_string());
+    }
+    Truncated::new(
+        Box::new(move |x, give_log| dgamma(x, shape, rate, give_log)),
+        Box::new(move |x, lower_tail, log_p| pgamma(x, shape, rate, lower_tail, log_p)),
+        Box::new(move |p, lower_tail, log_p| qgamma(p, shape, rate, lower_tail, log_p)),
+        lower.max(0.0),
+        upper,
+    )
+}
+
+/// Poisson distribution truncated to `ceil(lower), ..., floor(upper)`
+///
+/// The base CDF uses `P(X <= k) = P(Gamma(k + 1) > lambda)` for both tails.
+pub fn truncated_poisson(lambda: f64, lower: f64, upper: f64) -> Result<BoxedTruncated, String> {
+    if lambda.is_nan() || lambda <= 0.0 {
+        return Err("lambda must be positive".to_string());
+    }
+    Truncated::integer(
+        Box::new(move |x, give_log| dpois(x, lambda, give_log)),
+        Box::new(move |x, lower_tail, log_p| ppois(x, lambda, lower_tail, log_p)),
+        Box::new(move |p, lower_tail, log_p| qpois(p, lambda, lower_tail, log_p)),
+        lower.max(0.0),
+        upper,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::distributions::helpers::dpq::{d_exp, log1_exp};
+    use crate::stats::distributions::{dcauchy, dexp, pcauchy, pexp, qcauchy, qexp};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use rand::rngs::mock::StepRng;
+
+    #[test]
+    fn test_draws_on_unbounded_side_are_finite() {
+        // Cauchy truncated to (-inf, 0]: a zero uniform would map to -inf
+        let t = Truncated::new(
+            |x, g| dcauchy(x, 0.0, 1.0, g),
+            |x, l, lg| pcauchy(x, 0.0, 1.0, l, lg),
+            |p, l, lg| qcauchy(p, 0.0, 1.0, l, lg),
+            f64::NEG_INFINITY,
+            0.0,
+        )
+        .unwrap();
+        for fill in [0, u64::MAX] {
+            let x = t.r(&mut StepRng::new(fill, 0));
+            assert!(x.is_finite() && x <= 0.0, "{x}");
+        }
+    }
+
+    #[test]
+    fn test_truncated_exponential_is_shifted() {
+        // Memorylessness: Exp(2) truncated to [1, inf) is 1 + Exp(2)
+        let t = Truncated::new(
+            |x, g| dexp(x, 2.0, g),
+            |x, l, lg| pexp(x, 2.0, l, lg),
+            |p, l, lg| qexp(p, 2.0, l, lg),
+            1.0,
+            f64::INFINITY,
+        )
+        .unwrap();
+        assert!((t.log_mass() + 2.0).abs() < 1e-14);
+        for &x in &[1.0, 1.5, 3.0] {
+            assert!((t.d(x, false) - dexp(x - 1.0, 2.0, false)).abs() < 1e-13);
+            assert!((t.p(x, true, false) - pexp(x - 1.0, 2.0, true, false)).abs() < 1e-13);
+        }
+        assert!((t.q(0.5, true, false) - (1.0 + 0.5f64.ln().abs() / 2.0)).abs() < 1e-12);
+        assert_eq!(t.d(0.5, false), 0.0);
+        assert_eq!(t.p(0.5, true, false), 0.0);
+    }
+
+    #[test]
+    fn test_truncated_gamma() {
+        let t = truncated_gamma(2.0, 1.0, 1.0, 4.0).unwrap();
+        let mass = pgamma(4.0, 2.0, 1.0, true, false) - pgamma(1.0, 2.0, 1.0, true, false);
+        assert!((t.d(2.0, false) - dgamma(2.0, 2.0, 1.0, false) / mass).abs() < 1e-12);
+        for &p in &[0.1, 0.5, 0.9] {
+            let x = t.q(p, true, false);
+            assert!((1.0..=4.0).contains(&x));
+            assert!((t.p(x, true, false) - p).abs() < 1e-9);
+            assert!((t.p(x, false, false) - (1.0 - p)).abs() < 1e-9);
+        }
+        assert!(truncated_gamma(2.0, 1.0, 3.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_truncated_poisson() {
+        // Zero-truncated Poisson: P(X = k) = dpois(k) / (1 - exp(-lambda))
+        let t = truncated_poisson(1.5, 1.0, f64::INFINITY).unwrap();
+        let norm = 1.0 - (-1.5f64).exp();
+        assert_eq!(t.d(0.0, false), 0.0);
+        assert!((t.d(1.0, false) - dpois(1.0, 1.5, false) / norm).abs() < 1e-14);
+        assert!(
+            (t.p(2.0, true, false) - (ppois(2.0, 1.5, true, false) - 1.0 + norm) / norm).abs()
+                < 1e-12
+        );
+        assert_eq!(t.q(0.0, true, false), 1.0);
+
+        // Inclusive integer bounds
+        let t = truncated_poisson(4.0, 2.5, 5.0).unwrap();
+        let total: f64 = (0..10).map(|k| t.d(k as f64, false)).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        assert_eq!(t.p(5.0, true, false), 1.0);
+        assert_eq!(t.p(2.9, true, false), 0.0);
+
+        let mut rng = StdRng::seed_from_u64(48);
+        let draws: Vec<f64> = (0..2000).map(|_| t.r(&mut rng)).collect();
+        assert!(
+            draws
+                .iter()
+                .all(|&x| (3.0..=5.0).contains(&x) && x.fract() == 0.0)
+        );
+    }
+
+    #[test]
+    fn test_far_upper_tail_uses_upper_side() {
+        // Exp(1) on [40, 41]: mass exp(-40) (1 - exp(-1)) is far below eps,
+        // so the base needs an accurate upper tail
+        let t = Truncated::new(
+            |x: f64, g| d_exp(-x, g),
+            |x: f64, l, lg| dt_from_logs(log1_exp(-x), -x, l, lg),
+            |p, l, lg| -dt_clog(p, l, lg),
+            40.0,
+            41.0,
+        )
+        .unwrap();
+        let expected = -40.0 + (-(-1.0f64).exp()).ln_1p();
+        assert!((t.log_mass() - expected).abs() < 1e-12);
+        let p = t.p(40.5, true, false);
+        let exact = (-(-0.5f64).exp_m1()) / (-(-1.0f64).exp_m1());
+        assert!((p - exact).abs() < 1e-12);
+        assert!((t.q(exact, true, false) - 40.5).abs() < 1e-12);
+        assert!((t.d(40.0, true) - (-40.0 - expected)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,410 @@
+//! Truncated normal distribution functions
+//!
+//! The normal distribution restricted to `[a, b]` (argument order as in R's
+//! `truncnorm` package). Tail probabilities are evaluated on the log scale
+//! with an asymptotic expansion beyond the range of `erfc`, so truncation
+//! to a region tens of standard deviations from the mean stays accurate.
+//! Sampling uses Robert's (1995) accept-reject scheme rather than inversion.
+//!
+//! References:
+//! - Robert, C. P. (1995). Simulation of truncated normal variables.
+//!   Statistics and Computing, 5, 121-125.
+
+use super::helpers::dpq::{
+    d_0, dt_0, dt_1, dt_clog, dt_from_logs, dt_log, logspace_add, logspace_sub, q_p01_boundaries,
+};
+use super::normal::{pnorm, qnorm, rnorm};
+use rand::Rng;
+use std::f64::consts::{LN_2, PI};
+
+/// log(sqrt(2 pi))
+const LN_SQRT_2PI: f64 = 0.918_938_533_204_672_8;
+
+/// `log P(Z > z)` for a standard normal `Z`
+fn log_upper(z: f64) -> f64 {
+    if z < 35.0 {
+        return pnorm(z, 0.0, 1.0, false, false).ln();
+    }
+    // Mills ratio expansion: 1 - 1/z^2 + 3/z^4 - 15/z^6 + ...
+    let r = 1.0 / (z * z);
+    let series = 1.0 - r * (1.0 - 3.0 * r * (1.0 - 5.0 * r * (1.0 - 7.0 * r * (1.0 - 9.0 * r))));
+    -0.5 * z * z - z.ln() - LN_SQRT_2PI + series.ln()
+}
+
+/// `log P(Z <= z)` for a standard normal `Z`
+fn log_lower(z: f64) -> f64 {
+    log_upper(-z)
+}
+
+/// Solve `log P(Z > z) = t` for `z`
+fn upper_quantile(t: f64) -> f64 {
+    if t >= -LN_2 {
+        // Central and left part: the lower tail -expm1(t) is accurate
+        return qnorm(-t.exp_m1(), 0.0, 1.0, true, false);
+    }
+    let mut z = if t > -700.0 {
+        -qnorm(t.exp(), 0.0, 1.0, true, false)
+    } else {
+        // Leading terms of the asymptotic inverse
+        (-2.0 * t - (-4.0 * PI * t).ln()).sqrt()
+    };
+    // Newton steps on the log scale: d/dz log P(Z > z) = -phi(z) / P(Z > z)
+    for _ in 0..4 {
+        let lu = log_upper(z);
+        let step = (lu - t) * (lu + 0.5 * z * z + LN_SQRT_2PI).exp();
+        z += step;
+        if step.abs() <= 1e-15 * z.abs().max(1.0) {
+            break;
+        }
+    }
+    z
+}
+
+fn invalid(a: f64, b: f64, sd: f64) -> bool {
+    !sd.is_finite() || sd <= 0.0 || a.is_nan() || b.is_nan() || a >= b
+}
+
+/// Log tail masses on the working side for the standardised bounds
+///
+/// Returns `(upper_side, log_lo, log_hi, log_mass)`: above the median the
+/// upper tails `P(Z > alpha)`, `P(Z > beta)` are used, otherwise the lower
+/// tails `P(Z < alpha)`, `P(Z < beta)`.
+fn tails(alpha: f64, beta: f64) -> (bool, f64, f64, f64) {
+    if alpha > 0.0 {
+        let (lo, hi) = (log_upper(alpha), log_upper(beta));
+        (true, lo, hi, logspace_sub(lo, hi))
+    } else {
+        let (lo, hi) = (log_lower(alpha), log_lower(beta));
+        (false, lo, hi, logspace_sub(hi, lo))
+    }
+}
+
+/// Truncated normal probability density function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate density
+/// * `a` - Lower truncation point (may be `-inf`)
+/// * `b` - Upper truncation point (may be `inf`, must exceed `a`)
+/// * `mean` - Mean of the untruncated normal
+/// * `sd` - Standard deviation of the untruncated normal
+/// * `give_log` - If true, return log density
+///
+/// # Returns
+/// The truncated normal density or log density
+pub fn dtruncnorm(x: f64, a: f64, b: f64, mean: f64, sd: f64, give_log: bool) -> f64 {
+    if x.is_nan() || a.is_nan() || b.is_nan() || mean.is_nan() || sd.is_nan() {
+        return x + a + b + mean + sd;
+    }
+    if invalid(a, b, sd) {
+        return f64::NAN;
+    }
+    if x < a || x > b {
+        return d_0(give_log);
+    }
+    let z = (x - mean) / sd;
+    let (_, _, _, log_mass) = tails((a - mean) / sd, (b - mean) / sd);
+    let log_d = -0.5 * z * z - LN_SQRT_2PI - sd.ln() - log_mass;
+    if give_log { log_d } else { log_d.exp() }
+}
+
+/// Truncated normal cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Value at which to evaluate CDF
+/// * `a` - Lower truncation point (may be `-inf`)
+/// * `b` - Upper truncation point (may be `inf`, must exceed `a`)
+/// * `mean` - Mean of the untruncated normal
+/// * `sd` - Standard deviation of the untruncated normal
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn ptruncnorm(
+    x: f64,
+    a: f64,
+    b: f64,
+    mean: f64,
+    sd: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    if x.is_nan() || a.is_nan() || b.is_nan() || mean.is_nan() || sd.is_nan() {
+        return x + a + b + mean + sd;
+    }
+    if invalid(a, b, sd) {
+        return f64::NAN;
+    }
+    if x <= a {
+        return dt_0(lower_tail, log_p);
+    }
+    if x >= b {
+        return dt_1(lower_tail, log_p);
+    }
+    let z = (x - mean) / sd;
+    let (upper_side, lo, hi, log_mass) = tails((a - mean) / sd, (b - mean) / sd);
+    let (below, above) = if upper_side {
+        let lz = log_upper(z);
+        (logspace_sub(lo, lz), logspace_sub(lz, hi))
+    } else {
+        let lz = log_lower(z);
+        (logspace_sub(lz, lo), logspace_sub(hi, lz))
+    };
+    dt_from_logs(below - log_mass, above - log_mass, lower_tail, log_p)
+}
+
+/// Truncated normal quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `a` - Lower truncation point (may be `-inf`)
+/// * `b` - Upper truncation point (may be `inf`, must exceed `a`)
+/// * `mean` - Mean of the untruncated normal
+/// * `sd` - Standard deviation of the untruncated normal
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The quantile value
+pub fn qtruncnorm(
+    p: f64,
+    a: f64,
+    b: f64,
+    mean: f64,
+    sd: f64,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    if p.is_nan() || a.is_nan() || b.is_nan() || mean.is_nan() || sd.is_nan() {
+        return p + a + b + mean + sd;
+    }
+    if invalid(a, b, sd) {
+        return f64::NAN;
+    }
+    if let Some(q) = q_p01_boundaries(p, a, b, lower_tail, log_p) {
+        return q;
+    }
+    let (upper_side, lo, hi, log_mass) = tails((a - mean) / sd, (b - mean) / sd);
+    let z = if upper_side {
+        // P(Z > z) = P(Z > beta) + (1 - p) * mass
+        upper_quantile(logspace_add(hi, dt_clog(p, lower_tail, log_p) + log_mass).min(0.0))
+    } else {
+        // P(Z < z) = P(Z < alpha) + p * mass, mirrored onto the upper tail
+        -upper_quantile(logspace_add(lo, dt_log(p, lower_tail, log_p) + log_mass).min(0.0))
+    };
+    (mean + sd * z).clamp(a, b)
+}
+
+/// Truncated normal random number generation (Robert 1995)
+///
+/// # Arguments
+/// * `a` - Lower truncation point (may be `-inf`)
+/// * `b` - Upper truncation point (may be `inf`, must exceed `a`)
+/// * `mean` - Mean of the untruncated normal
+/// * `sd` - Standard deviation of the untruncated normal
+/// * `rng` - Random number generator
+///
+/// # Returns
+/// A random sample from the truncated normal distribution
+pub fn rtruncnorm<R: Rng>(a: f64, b: f64, mean: f64, sd: f64, rng: &mut R) -> f64 {
+    if a.is_nan() || b.is_nan() || mean.is_nan() || invalid(a, b, sd) {
+        return f64::NAN;
+    }
+    let (alpha, beta) = ((a - mean) / sd, (b - mean) / sd);
+    // Sample with the interval on the non-negative side when it lies
+    // entirely to one side of zero
+    let z = if beta <= 0.0 {
+        -sample_standard(-beta, -alpha, rng)
+    } else {
+        sample_standard(alpha, beta, rng)
+    };
+    mean + sd * z
+}
+
+/// Inverse Mills ratios `phi(alpha) / Z` and `phi(beta) / Z`, where `Z` is
+/// the standard normal mass of `[alpha, beta]`, computed on the log scale
+fn mills_ratios(alpha: f64, beta: f64) -> (f64, f64) {
+    let (_, _, _, log_mass) = tails(alpha, beta);
+    let log_phi = |z: f64| -0.5 * z * z - LN_SQRT_2PI;
+    (
+        (log_phi(alpha) - log_mass).exp(),
+        (log_phi(beta) - log_mass).exp(),
+    )
+}
+
+/// Truncated normal expected value (`truncnorm::etruncnorm`)
+///
+/// # Arguments
+/// * `a` - Lower truncation point (may be `-inf`)
+/// * `b` - Upper truncation point (may be `inf`, must exceed `a`)
+/// * `mean` - Mean of the untruncated normal
+/// * `sd` - Standard deviation of the untruncated normal
+///
+/// # Returns
+/// The mean of the truncated distribution
+pub fn etruncnorm(a: f64, b: f64, mean: f64, sd: f64) -> f64 {
+    if a.is_nan() || b.is_nan() || mean.is_nan() || invalid(a, b, sd) {
+        return f64::NAN;
+    }
+    let (alpha, beta) = ((a - mean) / sd, (b - mean) / sd);
+    let (ra, rb) = mills_ratios(alpha, beta);
+    mean + sd * (ra - rb)
+}
+
+/// Truncated normal variance (`truncnorm::vtruncnorm`)
+///
+/// # Arguments
+/// * `a` - Lower truncation point (may be `-inf`)
+/// * `b` - Upper truncation point (may be `inf`, must exceed `a`)
+/// * `mean` - Mean of the untruncated normal
+/// * `sd` - Standard deviation of the untruncated normal
+///
+/// # Returns
+/// The variance of the truncated distribution
+pub fn vtruncnorm(a: f64, b: f64, mean: f64, sd: f64) -> f64 {
+    if a.is_nan() || b.is_nan() || mean.is_nan() || invalid(a, b, sd) {
+        return f64::NAN;
+    }
+    let (alpha, beta) = ((a - mean) / sd, (b - mean) / sd);
+    let (ra, rb) = mills_ratios(alpha, beta);
+    // z phi(z) vanishes at infinite bounds
+    let term = |z: f64, r: f64| if z.is_finite() { z * r } else { 0.0 };
+    sd * sd * (1.0 + term(alpha, ra) - term(beta, rb) - (ra - rb).powi(2))
+}
+
+/// Standard normal restricted to `[alpha, beta]` with `beta > 0`
+fn sample_standard<R: Rng>(alpha: f64, beta: f64, rng: &mut R) -> f64 {
+    if alpha <= 0.0 {
+        if beta - alpha >= (2.0 * PI).sqrt() {
+            // Wide interval around zero: plain normal rejection
+            loop {
+                let z = rnorm(0.0, 1.0, rng);
+                if (alpha..=beta).contains(&z) {
+                    return z;
+                }
+            }
+        }
+        // Narrow interval around zero: uniform proposal
+        loop {
+            let z = alpha + (beta - alpha) * rng.gen_range(0.0..1.0);
+            if rng.gen_range(0.0..1.0) <= (-0.5 * z * z).exp() {
+                return z;
+            }
+        }
+    }
+    // One-sided: 0 <= alpha < beta
+    let root = (alpha * alpha + 4.0).sqrt();
+    let uniform_width = 2.0 * 0.5f64.exp() / (alpha + root) * (0.25 * alpha * (alpha - root)).exp();
+    if beta - alpha <= uniform_width {
+        loop {
+            let z = alpha + (beta - alpha) * rng.gen_range(0.0..1.0);
+            if rng.gen_range(0.0..1.0) <= (0.5 * (alpha * alpha - z * z)).exp() {
+                return z;
+            }
+        }
+    }
+    // Translated exponential proposal with the optimal rate
+    let lambda = 0.5 * (alpha + root);
+    loop {
+        let u: f64 = rng.gen_range(0.0..1.0);
+        let z = alpha - (1.0 - u).ln() / lambda;
+        if z <= beta && rng.gen_range(0.0..1.0) <= (-0.5 * (z - lambda) * (z - lambda)).exp() {
+            return z;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::distributions::dnorm;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_dtruncnorm() {
+        // Half-normal: twice the normal density on [0, inf)
+        let d = dtruncnorm(1.0, 0.0, f64::INFINITY, 0.0, 1.0, false);
+        assert!((d - 2.0 * dnorm(1.0, 0.0, 1.0, false)).abs() < 1e-15);
+        assert_eq!(dtruncnorm(-0.1, 0.0, 1.0, 0.0, 1.0, false), 0.0);
+        assert!(dtruncnorm(0.5, 1.0, 0.0, 0.0, 1.0, false).is_nan());
+        // Far tail: the density on [50, inf) is close to 50 exp(-50 (x - 50))
+        let d = dtruncnorm(50.0, 50.0, f64::INFINITY, 0.0, 1.0, true);
+        assert!((d - (50.0f64 + 1.0 / 50.0).ln()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_ptruncnorm_and_qtruncnorm() {
+        let (a, b, mu, sd) = (-1.0, 2.0, 0.5, 1.5);
+        let mass = pnorm(b, mu, sd, true, false) - pnorm(a, mu, sd, true, false);
+        for &x in &[-0.5, 0.5, 1.5] {
+            let expected = (pnorm(x, mu, sd, true, false) - pnorm(a, mu, sd, true, false)) / mass;
+            assert!((ptruncnorm(x, a, b, mu, sd, true, false) - expected).abs() < 1e-14);
+            let q = qtruncnorm(expected, a, b, mu, sd, true, false);
+            assert!((q - x).abs() < 1e-10, "{q} vs {x}");
+            let up = ptruncnorm(x, a, b, mu, sd, false, true);
+            assert!((qtruncnorm(up, a, b, mu, sd, false, true) - x).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_far_tail_truncation() {
+        // [40, 41] lies 40 standard deviations out: P(X <= 40.01 | X in [40, 41])
+        // is about 1 - exp(-40 * 0.01 - 0.01^2 / 2)
+        let p = ptruncnorm(40.01, 40.0, 41.0, 0.0, 1.0, true, false);
+        assert!((p - (1.0 - (-0.40005f64).exp())).abs() < 1e-3);
+        let x = qtruncnorm(p, 40.0, 41.0, 0.0, 1.0, true, false);
+        assert!((x - 40.01).abs() < 1e-9, "x = {x}");
+        // Mirror image in the lower tail
+        let p = ptruncnorm(-40.01, -41.0, -40.0, 0.0, 1.0, false, false);
+        assert!((p - (1.0 - (-0.40005f64).exp())).abs() < 1e-3);
+        assert!((qtruncnorm(p, -41.0, -40.0, 0.0, 1.0, false, false) + 40.01).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_truncnorm_moments() {
+        // Half-normal: mean sqrt(2 / pi), variance 1 - 2 / pi
+        let m = etruncnorm(0.0, f64::INFINITY, 0.0, 1.0);
+        assert!((m - (2.0 / PI).sqrt()).abs() < 1e-14);
+        let v = vtruncnorm(0.0, f64::INFINITY, 0.0, 1.0);
+        assert!((v - (1.0 - 2.0 / PI)).abs() < 1e-14);
+        // Symmetric interval keeps the mean; scaling carries through
+        assert!((etruncnorm(-1.0, 3.0, 1.0, 2.0) - 1.0).abs() < 1e-14);
+        // 40 sd out the Mills expansion gives mean 40 + 1/40 - 2/40^3
+        // and variance 1/40^2 - 6/40^4
+        let m = etruncnorm(40.0, f64::INFINITY, 0.0, 1.0);
+        assert!(
+            (m - (40.0 + 1.0 / 40.0 - 2.0 / 64_000.0)).abs() < 1e-6,
+            "{m}"
+        );
+        let v = vtruncnorm(40.0, f64::INFINITY, 0.0, 1.0);
+        assert!((v - (1.0 / 1600.0 - 6.0 / 2_560_000.0)).abs() < 1e-7, "{v}");
+        assert!(etruncnorm(1.0, 1.0, 0.0, 1.0).is_nan());
+    }
+
+    #[test]
+    fn test_rtruncnorm_regimes() {
+        let mut rng = StdRng::seed_from_u64(49);
+        let n = 20_000;
+        // Wide two-sided, narrow two-sided, one-sided tail, narrow tail, mirrored
+        for &(a, b) in &[
+            (-1.0, 3.0),
+            (-0.5, 0.5),
+            (2.0, f64::INFINITY),
+            (3.0, 3.2),
+            (f64::NEG_INFINITY, -2.5),
+        ] {
+            let draws: Vec<f64> = (0..n)
+                .map(|_| rtruncnorm(a, b, 0.0, 1.0, &mut rng))
+                .collect();
+            assert!(draws.iter().all(|&x| x >= a && x <= b));
+            // Compare the sample median with the exact one
+            let below = draws
+                .iter()
+                .filter(|&&x| x <= qtruncnorm(0.5, a, b, 0.0, 1.0, true, false))
+                .count();
+            assert!((below as f64 / n as f64 - 0.5).abs() < 0.015, "[{a}, {b}]");
+        }
+        assert!(rtruncnorm(1.0, 0.0, 0.0, 1.0, &mut rng).is_nan());
+    }
+}
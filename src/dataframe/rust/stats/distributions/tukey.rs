@@ -0,0 +1,348 @@
+//! Studentized range distribution functions
+//!
+//! Ports of R's `nmath/ptukey.c` and `qtukey.c` (Copenhaver & Holland 1988,
+//! AS 190). The CDF integrates the range probability of `nmeans` normal
+//! means against the chi density of the variance estimate with
+//! Gauss-Legendre quadrature; the quantile is found by secant iteration
+//! from Odeh & Evans' starting value.
+//!
+//! Source: R-4.5.1/nmath/{ptukey,qtukey}.c
+
+use super::helpers::dpq::{dt_0, dt_1, dt_qiv, dt_val, q_p01_boundaries};
+use super::normal::pnorm;
+use statrs::function::gamma::ln_gamma;
+use std::f64::consts::LN_2;
+
+/// 1 / sqrt(2 pi)
+const INV_SQRT_2PI: f64 = 0.398_942_280_401_432_7;
+
+/// Positive Gauss-Legendre nodes and weights of order 12 (`wprob`)
+const XLEG: [f64; 6] = [
+    0.981_560_634_246_719_3,
+    0.904_117_256_370_474_9,
+    0.769_902_674_194_304_7,
+    0.587_317_954_286_617_4,
+    0.367_831_498_998_180_2,
+    0.125_233_408_511_468_9,
+];
+const ALEG: [f64; 6] = [
+    0.047_175_336_386_511_83,
+    0.106_939_325_995_318_4,
+    0.160_078_328_543_346_2,
+    0.203_167_426_723_065_9,
+    0.233_492_536_538_354_8,
+    0.249_147_045_813_402_8,
+];
+
+/// Positive Gauss-Legendre nodes and weights of order 16 (`ptukey`)
+const XLEGQ: [f64; 8] = [
+    0.989_400_934_991_649_9,
+    0.944_575_023_073_232_6,
+    0.865_631_202_387_831_7,
+    0.755_404_408_355_003,
+    0.617_876_244_402_643_7,
+    0.458_016_777_657_227_4,
+    0.281_603_550_779_258_9,
+    0.095_012_509_837_637_44,
+];
+const ALEGQ: [f64; 8] = [
+    0.027_152_459_411_754_095,
+    0.062_253_523_938_647_89,
+    0.095_158_511_682_492_78,
+    0.124_628_971_255_533_87,
+    0.149_595_988_816_576_73,
+    0.169_156_519_395_002_54,
+    0.182_603_415_044_923_6,
+    0.189_450_610_455_068_5,
+];
+
+/// Probability that the range of `cc` standard normals, maximised over
+/// `rr` independent groups, is at most `w` (known variance)
+fn wprob(w: f64, rr: f64, cc: f64) -> f64 {
+    const C1: f64 = -30.0;
+    const C2: f64 = -50.0;
+    const C3: f64 = 60.0;
+    const BB: f64 = 8.0;
+    const WLAR: f64 = 3.0;
+
+    let qsqz = w * 0.5;
+    // For w >= 16 the integral is 1 to within 1e-14
+    if qsqz >= BB {
+        return 1.0;
+    }
+
+    // First term of Hartley's form: (2 Phi(w/2) - 1)^cc
+    let mut pr_w = 2.0 * pnorm(qsqz, 0.0, 1.0, true, false) - 1.0;
+    pr_w = if pr_w >= (C2 / cc).exp() {
+        pr_w.powf(cc)
+    } else {
+        0.0
+    };
+
+    // Fewer intervals are needed for large w
+    let wincr = if w > WLAR { 2.0 } else { 3.0 };
+
+    // Second term of Hartley's form, integrated from w/2 to BB
+    let mut blb = qsqz;
+    let binc = (BB - qsqz) / wincr;
+    let mut bub = blb + binc;
+    let mut einsum = 0.0;
+    let cc1 = cc - 1.0;
+    for _ in 0..wincr as usize {
+        let mut elsum = 0.0;
+        let a = 0.5 * (bub + blb);
+        let b = 0.5 * (bub - blb);
+        for jj in 0..12 {
+            let (j, xx) = if jj >= 6 {
+                (11 - jj, XLEG[11 - jj])
+            } else {
+                (jj, -XLEG[jj])
+            };
+            let ac = a + b * xx;
+            // exp(-qexpo / 2) < 9e-14 contributes nothing
+            let qexpo = ac * ac;
+            if qexpo > C3 {
+                break;
+            }
+            let pplus = pnorm(ac, 0.0, 1.0, true, false);
+            let pminus = pnorm(ac, w, 1.0, true, false);
+            let rinsum = pplus - pminus;
+            if rinsum >= (C1 / cc1).exp() {
+                elsum += ALEG[j] * (-0.5 * qexpo).exp() * rinsum.powf(cc1);
+            }
+        }
+        einsum += elsum * 2.0 * b * cc * INV_SQRT_2PI;
+        blb = bub;
+        bub += binc;
+    }
+
+    pr_w += einsum;
+    if pr_w <= (C1 / rr).exp() {
+        return 0.0;
+    }
+    pr_w.powf(rr).min(1.0)
+}
+
+/// Studentized range cumulative distribution function
+///
+/// # Arguments
+/// * `q` - Value at which to evaluate CDF
+/// * `nmeans` - Number of means in each range (at least 2)
+/// * `df` - Degrees of freedom of the variance estimate (at least 2; may be
+///   `Inf`)
+/// * `nranges` - Number of independent ranges (at least 1; usually 1)
+/// * `lower_tail` - If true, return P(Q <= q), otherwise P(Q > q)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability, accurate to about 1e-8 as in R
+pub fn ptukey(q: f64, nmeans: f64, df: f64, nranges: f64, lower_tail: bool, log_p: bool) -> f64 {
+    const EPS1: f64 = -30.0;
+    const EPS2: f64 = 1.0e-14;
+    const DLARG: f64 = 25000.0;
+
+    if q.is_nan() || nmeans.is_nan() || df.is_nan() || nranges.is_nan() {
+        return f64::NAN;
+    }
+    if q <= 0.0 {
+        return dt_0(lower_tail, log_p);
+    }
+    if df < 2.0 || nranges < 1.0 || nmeans < 2.0 {
+        return f64::NAN;
+    }
+    if !q.is_finite() {
+        return dt_1(lower_tail, log_p);
+    }
+    if df > DLARG {
+        return dt_val(wprob(q, nranges, nmeans), lower_tail, log_p);
+    }
+
+    // Leading constant of the chi density of sqrt(df) s / sigma
+    let f2 = df * 0.5;
+    let f21 = f2 - 1.0;
+    let ff4 = df * 0.25;
+    // Unit, half-, quarter- or eighth-unit intervals depending on df
+    let ulen: f64 = if df <= 100.0 {
+        1.0
+    } else if df <= 800.0 {
+        0.5
+    } else if df <= 5000.0 {
+        0.25
+    } else {
+        0.125
+    };
+    let f2lf = f2 * df.ln() - df * LN_2 - ln_gamma(f2) + ulen.ln();
+
+    let mut ans = 0.0;
+    for i in 1..=50 {
+        let mut otsum = 0.0;
+        let twa1 = (2 * i - 1) as f64 * ulen;
+        for jj in 0..16 {
+            let (j, node) = if jj >= 8 {
+                (jj - 8, XLEGQ[jj - 8] * ulen)
+            } else {
+                (jj, -XLEGQ[jj] * ulen)
+            };
+            let t1 = f2lf + f21 * (twa1 + node).ln() - (twa1 + node) * ff4;
+            // exp(t1) < 9e-14 contributes nothing
+            if t1 >= EPS1 {
+                let qsqz = q * ((twa1 + node) * 0.5).sqrt();
+                otsum += wprob(qsqz, nranges, nmeans) * ALEGQ[j] * t1.exp();
+            }
+        }
+        // Stop once an interval is negligible, but cover at least [0, 1]
+        // to avoid losing area in the left tail. R warns about lost
+        // precision if this never happens within 50 intervals.
+        if i as f64 * ulen >= 1.0 && otsum <= EPS2 {
+            break;
+        }
+        ans += otsum;
+    }
+    dt_val(ans.min(1.0), lower_tail, log_p)
+}
+
+/// Starting value for `qtukey` (Odeh & Evans 1974, AS 70)
+fn qinv(p: f64, c: f64, v: f64) -> f64 {
+    const P0: f64 = 0.322_232_421_088;
+    const Q0: f64 = 0.099_348_462_606;
+    const P1: f64 = -1.0;
+    const Q1: f64 = 0.588_581_570_495;
+    const P2: f64 = -0.342_242_088_547;
+    const Q2: f64 = 0.531_103_462_366;
+    const P3: f64 = -0.204_231_210_125;
+    const Q3: f64 = 0.103_537_752_85;
+    const P4: f64 = -0.453_642_210_148e-4;
+    const Q4: f64 = 0.385_607_006_34e-2;
+    const C1: f64 = 0.8832;
+    const C2: f64 = 0.2368;
+    const C3: f64 = 1.214;
+    const C4: f64 = 1.208;
+    // Truncated sqrt(2) as published; kept so starting values match R
+    #[allow(clippy::approx_constant)]
+    const C5: f64 = 1.4142;
+    const VMAX: f64 = 120.0;
+
+    let ps = 0.5 - 0.5 * p;
+    let yi = (1.0 / (ps * ps)).ln().sqrt();
+    let mut t = yi
+        + ((((yi * P4 + P3) * yi + P2) * yi + P1) * yi + P0)
+            / ((((yi * Q4 + Q3) * yi + Q2) * yi + Q1) * yi + Q0);
+    if v < VMAX {
+        t += (t * t * t + t) / v / 4.0;
+    }
+    let mut q = C1 - C2 * t;
+    if v < VMAX {
+        q += -C3 / v + C4 * t / v;
+    }
+    t * (q * (c - 1.0).ln() + C5)
+}
+
+/// Studentized range quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `nmeans` - Number of means in each range (at least 2)
+/// * `df` - Degrees of freedom of the variance estimate (at least 2; may be
+///   `Inf`)
+/// * `nranges` - Number of independent ranges (at least 1; usually 1)
+/// * `lower_tail` - If true, p is P(Q <= q), otherwise P(Q > q)
+/// * `log_p` - If true, p is given as log probability
+///
+/// # Returns
+/// The quantile, to the four-decimal accuracy of R's secant iteration
+pub fn qtukey(p: f64, nmeans: f64, df: f64, nranges: f64, lower_tail: bool, log_p: bool) -> f64 {
+    const EPS: f64 = 0.0001;
+    const MAXITER: usize = 50;
+
+    if p.is_nan() || nmeans.is_nan() || df.is_nan() || nranges.is_nan() {
+        return p + nmeans + df + nranges;
+    }
+    if df < 2.0 || nranges < 1.0 || nmeans < 2.0 {
+        return f64::NAN;
+    }
+    if let Some(q) = q_p01_boundaries(p, 0.0, f64::INFINITY, lower_tail, log_p) {
+        return q;
+    }
+    let p = dt_qiv(p, lower_tail, log_p);
+    let cdf = |x: f64| ptukey(x, nmeans, df, nranges, true, false) - p;
+
+    let mut x0 = qinv(p, nmeans, df);
+    let mut valx0 = cdf(x0);
+    // Second iterate one unit towards the root
+    let mut x1 = if valx0 > 0.0 {
+        (x0 - 1.0).max(0.0)
+    } else {
+        x0 + 1.0
+    };
+    let mut valx1 = cdf(x1);
+
+    let mut ans = 0.0;
+    for _ in 1..MAXITER {
+        ans = x1 - valx1 * (x1 - x0) / (valx1 - valx0);
+        valx0 = valx1;
+        x0 = x1;
+        if ans < 0.0 {
+            ans = 0.0;
+        }
+        valx1 = cdf(ans);
+        x1 = ans;
+        if (x1 - x0).abs() < EPS {
+            return ans;
+        }
+    }
+    // Not converged in MAXITER steps; R warns and returns the last iterate
+    ans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::distributions::{pnorm, pt};
+    use std::f64::consts::SQRT_2;
+
+    #[test]
+    fn test_ptukey_two_means() {
+        // The range of two means is |X1 - X2|, so Q / sqrt(2) is |t|
+        for &(q, df) in &[(0.5, 5.0), (2.0, 10.0), (3.5, 30.0), (6.0, 3.0)] {
+            let expected = 2.0 * pt(q / SQRT_2, df, true, false) - 1.0;
+            let p = ptukey(q, 2.0, df, 1.0, true, false);
+            assert!((p - expected).abs() < 1e-8, "q = {q}, df = {df}: {p}");
+        }
+        let expected = 2.0 * pnorm(2.0 / SQRT_2, 0.0, 1.0, true, false) - 1.0;
+        let p = ptukey(2.0, 2.0, f64::INFINITY, 1.0, true, false);
+        assert!((p - expected).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_ptukey() {
+        // Table critical values for k = 3, df = 12: q(0.95) = 3.773, q(0.99) = 5.046
+        let p = ptukey(3.773, 3.0, 12.0, 1.0, true, false);
+        assert!((p - 0.95).abs() < 1e-4, "{p}");
+        let p = ptukey(5.046, 3.0, 12.0, 1.0, true, false);
+        assert!((p - 0.99).abs() < 1e-4, "{p}");
+        let p = ptukey(3.5, 3.0, 12.0, 1.0, true, false);
+        let upper = ptukey(3.5, 3.0, 12.0, 1.0, false, true);
+        assert!((upper.exp() - (1.0 - p)).abs() < 1e-12);
+        assert_eq!(ptukey(0.0, 3.0, 12.0, 1.0, true, false), 0.0);
+        assert_eq!(ptukey(f64::INFINITY, 3.0, 12.0, 1.0, true, false), 1.0);
+        assert!(ptukey(1.0, 1.0, 12.0, 1.0, true, false).is_nan());
+        assert!(ptukey(1.0, 3.0, 1.0, 1.0, true, false).is_nan());
+    }
+
+    #[test]
+    fn test_qtukey() {
+        // Critical values from tables of the studentized range (alpha = 0.05)
+        // R: qtukey(0.95, 3, 10) = 3.877676, qtukey(0.95, 5, 20) = 4.231857
+        let q = qtukey(0.95, 3.0, 10.0, 1.0, true, false);
+        assert!((q - 3.877676).abs() < 1e-3, "{q}");
+        let q = qtukey(0.95, 5.0, 20.0, 1.0, true, false);
+        assert!((q - 4.231857).abs() < 1e-3, "{q}");
+        // Round trip through the CDF
+        let q = qtukey(0.05, 4.0, 15.0, 1.0, false, false);
+        let p = ptukey(q, 4.0, 15.0, 1.0, false, false);
+        assert!((p - 0.05).abs() < 1e-4, "{p}");
+        assert_eq!(qtukey(0.0, 3.0, 10.0, 1.0, true, false), 0.0);
+        assert_eq!(qtukey(1.0, 3.0, 10.0, 1.0, true, false), f64::INFINITY);
+        assert!(qtukey(1.5, 3.0, 10.0, 1.0, true, false).is_nan());
+    }
+}
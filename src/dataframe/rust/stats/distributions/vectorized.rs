@@ -0,0 +1,179 @@
+//! Slice-based evaluation of scalar distribution functions
+//!
+//! Each helper applies a scalar function element-wise over `x`, writing into
+//! a caller-provided `out` buffer of the same length. Every parameter slice
+//! either has length 1 (broadcast to all elements) or the length of `x`
+//! (one value per element).
+
+fn check(name: &str, len: usize, n: usize) -> Result<(), String> {
+    if len == 1 || len == n {
+        Ok(())
+    } else {
+        Err(format!(
+            "parameter '{}' has length {}; expected 1 or {}",
+            name, len, n
+        ))
+    }
+}
+
+fn check_out(x: &[f64], out: &[f64]) -> Result<(), String> {
+    if out.len() != x.len() {
+        return Err(format!(
+            "output buffer length {} does not match input length {}",
+            out.len(),
+            x.len()
+        ));
+    }
+    Ok(())
+}
+
+#[inline]
+fn at(v: &[f64], i: usize) -> f64 {
+    if v.len() == 1 { v[0] } else { v[i] }
+}
+
+/// Evaluate a one-parameter function over `x`
+pub fn map1(
+    x: &[f64],
+    a: (&str, &[f64]),
+    out: &mut [f64],
+    f: impl Fn(f64, f64) -> f64,
+) -> Result<(), String> {
+    check_out(x, out)?;
+    check(a.0, a.1.len(), x.len())?;
+    for (i, (o, &xi)) in out.iter_mut().zip(x).enumerate() {
+        *o = f(xi, at(a.1, i));
+    }
+    Ok(())
+}
+
+/// Evaluate a two-parameter function over `x`
+pub fn map2(
+    x: &[f64],
+    a: (&str, &[f64]),
+    b: (&str, &[f64]),
+    out: &mut [f64],
+    f: impl Fn(f64, f64, f64) -> f64,
+) -> Result<(), String> {
+    check_out(x, out)?;
+    check(a.0, a.1.len(), x.len())?;
+    check(b.0, b.1.len(), x.len())?;
+    for (i, (o, &xi)) in out.iter_mut().zip(x).enumerate() {
+        *o = f(xi, at(a.1, i), at(b.1, i));
+    }
+    Ok(())
+}
+
+/// Evaluate a three-parameter function over `x`
+pub fn map3(
+    x: &[f64],
+    a: (&str, &[f64]),
+    b: (&str, &[f64]),
+    c: (&str, &[f64]),
+    out: &mut [f64],
+    f: impl Fn(f64, f64, f64, f64) -> f64,
+) -> Result<(), String> {
+    check_out(x, out)?;
+    check(a.0, a.1.len(), x.len())?;
+    check(b.0, b.1.len(), x.len())?;
+    check(c.0, c.1.len(), x.len())?;
+    for (i, (o, &xi)) in out.iter_mut().zip(x).enumerate() {
+        *o = f(xi, at(a.1, i), at(b.1, i), at(c.1, i));
+    }
+    Ok(())
+}
+
+/// Evaluate a four-parameter function over `x`
+pub fn map4(
+    x: &[f64],
+    a: (&str, &[f64]),
+    b: (&str, &[f64]),
+    c: (&str, &[f64]),
+    d: (&str, &[f64]),
+    out: &mut [f64],
+    f: impl Fn(f64, f64, f64, f64, f64) -> f64,
+) -> Result<(), String> {
+    check_out(x, out)?;
+    check(a.0, a.1.len(), x.len())?;
+    check(b.0, b.1.len(), x.len())?;
+    check(c.0, c.1.len(), x.len())?;
+    check(d.0, d.1.len(), x.len())?;
+    for (i, (o, &xi)) in out.iter_mut().zip(x).enumerate() {
+        *o = f(xi, at(a.1, i), at(b.1, i), at(c.1, i), at(d.1, i));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::distributions::{dnorm, dtruncnorm, pnorm, qbeta};
+
+    #[test]
+    fn test_broadcast_and_per_element() {
+        let x = [-1.0, 0.0, 1.0];
+        let mut out = [0.0; 3];
+        map2(
+            &x,
+            ("mean", &[0.0]),
+            ("sd", &[1.0, 2.0, 3.0]),
+            &mut out,
+            |x, m, s| dnorm(x, m, s, false),
+        )
+        .unwrap();
+        for (i, &o) in out.iter().enumerate() {
+            assert_eq!(o, dnorm(x[i], 0.0, (i + 1) as f64, false));
+        }
+
+        map2(&x, ("mean", &[0.0]), ("sd", &[1.0]), &mut out, |x, m, s| {
+            pnorm(x, m, s, false, true)
+        })
+        .unwrap();
+        assert_eq!(out[1], 0.5f64.ln());
+
+        let p = [0.1, 0.5];
+        let mut q = [0.0; 2];
+        map2(
+            &p,
+            ("shape1", &[2.0]),
+            ("shape2", &[3.0]),
+            &mut q,
+            |p, a, b| qbeta(p, a, b, true, false),
+        )
+        .unwrap();
+        assert_eq!(q[1], qbeta(0.5, 2.0, 3.0, true, false));
+
+        map4(
+            &x,
+            ("a", &[-2.0]),
+            ("b", &[0.5, 1.5, 2.5]),
+            ("mean", &[0.0]),
+            ("sd", &[1.0]),
+            &mut out,
+            |x, a, b, m, s| dtruncnorm(x, a, b, m, s, false),
+        )
+        .unwrap();
+        assert_eq!(out[2], dtruncnorm(1.0, -2.0, 2.5, 0.0, 1.0, false));
+    }
+
+    #[test]
+    fn test_length_errors() {
+        let mut out = [0.0; 3];
+        let err = map1(&[1.0, 2.0, 3.0], ("rate", &[1.0, 2.0]), &mut out, |x, r| {
+            x * r
+        });
+        assert!(err.unwrap_err().contains("rate"));
+        let mut short = [0.0; 2];
+        assert!(map1(&[1.0, 2.0, 3.0], ("rate", &[1.0]), &mut short, |x, r| x * r).is_err());
+        let err = map4(
+            &[1.0, 2.0, 3.0],
+            ("a", &[0.0]),
+            ("b", &[5.0]),
+            ("mean", &[0.0]),
+            ("sd", &[1.0, 2.0]),
+            &mut out,
+            |x, _, _, _, s| x * s,
+        );
+        assert!(err.unwrap_err().contains("sd"));
+    }
+}
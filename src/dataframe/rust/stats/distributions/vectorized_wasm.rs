@@ -0,0 +1,2081 @@
+//! WASM bindings for slice-based distribution functions
+//!
+//! Vectorised counterparts of the scalar `wasm_d*`, `wasm_p*` and `wasm_q*`
+//! exports. Each evaluates over the whole input slice and writes into the
+//! caller's `out` buffer (same length as the input); every parameter slice
+//! has length 1 (broadcast) or the input length (per element).
+
+#![cfg(feature = "wasm")]
+
+use super::vectorized::{map1, map2, map3, map4};
+use super::*;
+use wasm_bindgen::prelude::*;
+
+fn vec_error(e: String) -> JsValue {
+    JsValue::from_str(&format!("Distribution error: {}", e))
+}
+
+// ============================================================================
+// BETA DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for beta density function
+#[wasm_bindgen]
+pub fn wasm_dbeta_vec(
+    x: &[f64],
+    shape1: &[f64],
+    shape2: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("shape1", shape1),
+        ("shape2", shape2),
+        out,
+        |x, shape1, shape2| dbeta(x, shape1, shape2, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for beta cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pbeta_vec(
+    x: &[f64],
+    shape1: &[f64],
+    shape2: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("shape1", shape1),
+        ("shape2", shape2),
+        out,
+        |x, shape1, shape2| pbeta(x, shape1, shape2, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for beta quantile function
+#[wasm_bindgen]
+pub fn wasm_qbeta_vec(
+    p: &[f64],
+    shape1: &[f64],
+    shape2: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        p,
+        ("shape1", shape1),
+        ("shape2", shape2),
+        out,
+        |p, shape1, shape2| qbeta(p, shape1, shape2, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// NORMAL DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for normal density function
+#[wasm_bindgen]
+pub fn wasm_dnorm_vec(
+    x: &[f64],
+    mean: &[f64],
+    sd: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("mean", mean), ("sd", sd), out, |x, mean, sd| {
+        dnorm(x, mean, sd, give_log)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for normal cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pnorm_vec(
+    x: &[f64],
+    mean: &[f64],
+    sd: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("mean", mean), ("sd", sd), out, |x, mean, sd| {
+        pnorm(x, mean, sd, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for normal quantile function
+#[wasm_bindgen]
+pub fn wasm_qnorm_vec(
+    p: &[f64],
+    mean: &[f64],
+    sd: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(p, ("mean", mean), ("sd", sd), out, |p, mean, sd| {
+        qnorm(p, mean, sd, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// GAMMA DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for gamma density function
+#[wasm_bindgen]
+pub fn wasm_dgamma_vec(
+    x: &[f64],
+    shape: &[f64],
+    rate: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("shape", shape),
+        ("rate", rate),
+        out,
+        |x, shape, rate| dgamma(x, shape, rate, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for gamma cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pgamma_vec(
+    x: &[f64],
+    shape: &[f64],
+    rate: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("shape", shape),
+        ("rate", rate),
+        out,
+        |x, shape, rate| pgamma(x, shape, rate, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for gamma quantile function
+#[wasm_bindgen]
+pub fn wasm_qgamma_vec(
+    p: &[f64],
+    shape: &[f64],
+    rate: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        p,
+        ("shape", shape),
+        ("rate", rate),
+        out,
+        |p, shape, rate| qgamma(p, shape, rate, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// EXPONENTIAL DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for exponential density function
+#[wasm_bindgen]
+pub fn wasm_dexp_vec(
+    x: &[f64],
+    rate: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(x, ("rate", rate), out, |x, rate| dexp(x, rate, give_log)).map_err(vec_error)
+}
+
+/// Vectorised WASM export for exponential cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pexp_vec(
+    x: &[f64],
+    rate: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(x, ("rate", rate), out, |x, rate| {
+        pexp(x, rate, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for exponential quantile function
+#[wasm_bindgen]
+pub fn wasm_qexp_vec(
+    p: &[f64],
+    rate: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(p, ("rate", rate), out, |p, rate| {
+        qexp(p, rate, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// CHI-SQUARED DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for chi-squared density function
+#[wasm_bindgen]
+pub fn wasm_dchisq_vec(
+    x: &[f64],
+    df: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(x, ("df", df), out, |x, df| dchisq(x, df, give_log)).map_err(vec_error)
+}
+
+/// Vectorised WASM export for chi-squared cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pchisq_vec(
+    x: &[f64],
+    df: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(x, ("df", df), out, |x, df| pchisq(x, df, lower_tail, log_p)).map_err(vec_error)
+}
+
+/// Vectorised WASM export for chi-squared quantile function
+#[wasm_bindgen]
+pub fn wasm_qchisq_vec(
+    p: &[f64],
+    df: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(p, ("df", df), out, |p, df| qchisq(p, df, lower_tail, log_p)).map_err(vec_error)
+}
+
+// ============================================================================
+// F DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for F density function
+#[wasm_bindgen]
+pub fn wasm_df_vec(
+    x: &[f64],
+    df1: &[f64],
+    df2: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("df1", df1), ("df2", df2), out, |x, df1, df2| {
+        df(x, df1, df2, give_log)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for F cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pf_vec(
+    x: &[f64],
+    df1: &[f64],
+    df2: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("df1", df1), ("df2", df2), out, |x, df1, df2| {
+        pf(x, df1, df2, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for F quantile function
+#[wasm_bindgen]
+pub fn wasm_qf_vec(
+    p: &[f64],
+    df1: &[f64],
+    df2: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(p, ("df1", df1), ("df2", df2), out, |p, df1, df2| {
+        qf(p, df1, df2, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for t density function
+#[wasm_bindgen]
+pub fn wasm_dt_vec(x: &[f64], df: &[f64], give_log: bool, out: &mut [f64]) -> Result<(), JsValue> {
+    map1(x, ("df", df), out, |x, df| dt(x, df, give_log)).map_err(vec_error)
+}
+
+/// Vectorised WASM export for t cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pt_vec(
+    x: &[f64],
+    df: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(x, ("df", df), out, |x, df| pt(x, df, lower_tail, log_p)).map_err(vec_error)
+}
+
+/// Vectorised WASM export for t quantile function
+#[wasm_bindgen]
+pub fn wasm_qt_vec(
+    p: &[f64],
+    df: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(p, ("df", df), out, |p, df| qt(p, df, lower_tail, log_p)).map_err(vec_error)
+}
+
+// ============================================================================
+// POISSON DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for Poisson density function
+#[wasm_bindgen]
+pub fn wasm_dpois_vec(
+    x: &[f64],
+    lambda: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(x, ("lambda", lambda), out, |x, lambda| {
+        dpois(x, lambda, give_log)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Poisson cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_ppois_vec(
+    x: &[f64],
+    lambda: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(x, ("lambda", lambda), out, |x, lambda| {
+        ppois(x, lambda, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Poisson quantile function
+#[wasm_bindgen]
+pub fn wasm_qpois_vec(
+    p: &[f64],
+    lambda: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(p, ("lambda", lambda), out, |p, lambda| {
+        qpois(p, lambda, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// BINOMIAL DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for binomial density function
+#[wasm_bindgen]
+pub fn wasm_dbinom_vec(
+    x: &[f64],
+    size: &[f64],
+    prob: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("size", size), ("prob", prob), out, |x, size, prob| {
+        dbinom(x, size, prob, give_log)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for binomial cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pbinom_vec(
+    x: &[f64],
+    size: &[f64],
+    prob: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("size", size), ("prob", prob), out, |x, size, prob| {
+        pbinom(x, size, prob, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for binomial quantile function
+#[wasm_bindgen]
+pub fn wasm_qbinom_vec(
+    p: &[f64],
+    size: &[f64],
+    prob: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(p, ("size", size), ("prob", prob), out, |p, size, prob| {
+        qbinom(p, size, prob, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// UNIFORM DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for uniform density function
+#[wasm_bindgen]
+pub fn wasm_dunif_vec(
+    x: &[f64],
+    min: &[f64],
+    max: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("min", min), ("max", max), out, |x, min, max| {
+        dunif(x, min, max, give_log)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for uniform cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_punif_vec(
+    x: &[f64],
+    min: &[f64],
+    max: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("min", min), ("max", max), out, |x, min, max| {
+        punif(x, min, max, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for uniform quantile function
+#[wasm_bindgen]
+pub fn wasm_qunif_vec(
+    p: &[f64],
+    min: &[f64],
+    max: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(p, ("min", min), ("max", max), out, |p, min, max| {
+        qunif(p, min, max, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// WEIBULL DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for Weibull density function
+#[wasm_bindgen]
+pub fn wasm_dweibull_vec(
+    x: &[f64],
+    shape: &[f64],
+    scale: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("shape", shape),
+        ("scale", scale),
+        out,
+        |x, shape, scale| dweibull(x, shape, scale, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Weibull cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pweibull_vec(
+    x: &[f64],
+    shape: &[f64],
+    scale: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("shape", shape),
+        ("scale", scale),
+        out,
+        |x, shape, scale| pweibull(x, shape, scale, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Weibull quantile function
+#[wasm_bindgen]
+pub fn wasm_qweibull_vec(
+    p: &[f64],
+    shape: &[f64],
+    scale: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        p,
+        ("shape", shape),
+        ("scale", scale),
+        out,
+        |p, shape, scale| qweibull(p, shape, scale, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// GEOMETRIC DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for geometric density function
+#[wasm_bindgen]
+pub fn wasm_dgeom_vec(
+    x: &[f64],
+    prob: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(x, ("prob", prob), out, |x, prob| dgeom(x, prob, give_log)).map_err(vec_error)
+}
+
+/// Vectorised WASM export for geometric cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pgeom_vec(
+    x: &[f64],
+    prob: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(x, ("prob", prob), out, |x, prob| {
+        pgeom(x, prob, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for geometric quantile function
+#[wasm_bindgen]
+pub fn wasm_qgeom_vec(
+    p: &[f64],
+    prob: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(p, ("prob", prob), out, |p, prob| {
+        qgeom(p, prob, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// HYPERGEOMETRIC DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for hypergeometric density function
+#[wasm_bindgen]
+pub fn wasm_dhyper_vec(
+    x: &[f64],
+    m: &[f64],
+    n: &[f64],
+    k: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(x, ("m", m), ("n", n), ("k", k), out, |x, m, n, k| {
+        dhyper(x, m, n, k, give_log)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for hypergeometric cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_phyper_vec(
+    x: &[f64],
+    m: &[f64],
+    n: &[f64],
+    k: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(x, ("m", m), ("n", n), ("k", k), out, |x, m, n, k| {
+        phyper(x, m, n, k, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for hypergeometric quantile function
+#[wasm_bindgen]
+pub fn wasm_qhyper_vec(
+    p: &[f64],
+    m: &[f64],
+    n: &[f64],
+    k: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(p, ("m", m), ("n", n), ("k", k), out, |p, m, n, k| {
+        qhyper(p, m, n, k, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// LOG-NORMAL DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for log-normal density function
+#[wasm_bindgen]
+pub fn wasm_dlnorm_vec(
+    x: &[f64],
+    meanlog: &[f64],
+    sdlog: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("meanlog", meanlog),
+        ("sdlog", sdlog),
+        out,
+        |x, meanlog, sdlog| dlnorm(x, meanlog, sdlog, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for log-normal cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_plnorm_vec(
+    x: &[f64],
+    meanlog: &[f64],
+    sdlog: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("meanlog", meanlog),
+        ("sdlog", sdlog),
+        out,
+        |x, meanlog, sdlog| plnorm(x, meanlog, sdlog, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for log-normal quantile function
+#[wasm_bindgen]
+pub fn wasm_qlnorm_vec(
+    p: &[f64],
+    meanlog: &[f64],
+    sdlog: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        p,
+        ("meanlog", meanlog),
+        ("sdlog", sdlog),
+        out,
+        |p, meanlog, sdlog| qlnorm(p, meanlog, sdlog, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// NEGATIVE BINOMIAL DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for negative binomial density function
+#[wasm_bindgen]
+pub fn wasm_dnbinom_vec(
+    x: &[f64],
+    r: &[f64],
+    p: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("r", r), ("p", p), out, |x, r, p| {
+        dnbinom(x, r, p, give_log)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for negative binomial cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pnbinom_vec(
+    x: &[f64],
+    r: &[f64],
+    p: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("r", r), ("p", p), out, |x, r, p| {
+        pnbinom(x, r, p, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for negative binomial quantile function
+#[wasm_bindgen]
+pub fn wasm_qnbinom_vec(
+    p: &[f64],
+    r: &[f64],
+    prob: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(p, ("r", r), ("prob", prob), out, |p, r, prob| {
+        qnbinom(p, r, prob, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// WILCOXON DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for Wilcoxon density function
+#[wasm_bindgen]
+pub fn wasm_dwilcox_vec(
+    x: &[f64],
+    m: &[f64],
+    n: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("m", m), ("n", n), out, |x, m, n| {
+        dwilcox(x, m, n, give_log)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Wilcoxon cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pwilcox_vec(
+    q: &[f64],
+    m: &[f64],
+    n: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(q, ("m", m), ("n", n), out, |q, m, n| {
+        pwilcox(q, m, n, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Wilcoxon quantile function
+#[wasm_bindgen]
+pub fn wasm_qwilcox_vec(
+    p: &[f64],
+    m: &[f64],
+    n: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(p, ("m", m), ("n", n), out, |p, m, n| {
+        qwilcox(p, m, n, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// GUMBEL DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for Gumbel density function
+#[wasm_bindgen]
+pub fn wasm_dgumbel_vec(
+    x: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("location", location),
+        ("scale", scale),
+        out,
+        |x, location, scale| dgumbel(x, location, scale, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Gumbel cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pgumbel_vec(
+    x: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("location", location),
+        ("scale", scale),
+        out,
+        |x, location, scale| pgumbel(x, location, scale, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Gumbel quantile function
+#[wasm_bindgen]
+pub fn wasm_qgumbel_vec(
+    p: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        p,
+        ("location", location),
+        ("scale", scale),
+        out,
+        |p, location, scale| qgumbel(p, location, scale, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// GENERALIZED EXTREME VALUE DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for GEV density function
+#[wasm_bindgen]
+pub fn wasm_dgev_vec(
+    x: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    shape: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("location", location),
+        ("scale", scale),
+        ("shape", shape),
+        out,
+        |x, location, scale, shape| dgev(x, location, scale, shape, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for GEV cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pgev_vec(
+    x: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    shape: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("location", location),
+        ("scale", scale),
+        ("shape", shape),
+        out,
+        |x, location, scale, shape| pgev(x, location, scale, shape, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for GEV quantile function
+#[wasm_bindgen]
+pub fn wasm_qgev_vec(
+    p: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    shape: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        p,
+        ("location", location),
+        ("scale", scale),
+        ("shape", shape),
+        out,
+        |p, location, scale, shape| qgev(p, location, scale, shape, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// GENERALIZED PARETO DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for generalized Pareto density function
+#[wasm_bindgen]
+pub fn wasm_dgpd_vec(
+    x: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    shape: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("location", location),
+        ("scale", scale),
+        ("shape", shape),
+        out,
+        |x, location, scale, shape| dgpd(x, location, scale, shape, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for generalized Pareto cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pgpd_vec(
+    x: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    shape: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("location", location),
+        ("scale", scale),
+        ("shape", shape),
+        out,
+        |x, location, scale, shape| pgpd(x, location, scale, shape, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for generalized Pareto quantile function
+#[wasm_bindgen]
+pub fn wasm_qgpd_vec(
+    p: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    shape: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        p,
+        ("location", location),
+        ("scale", scale),
+        ("shape", shape),
+        out,
+        |p, location, scale, shape| qgpd(p, location, scale, shape, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// PARETO DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for Pareto density function
+#[wasm_bindgen]
+pub fn wasm_dpareto_vec(
+    x: &[f64],
+    scale: &[f64],
+    shape: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("scale", scale),
+        ("shape", shape),
+        out,
+        |x, scale, shape| dpareto(x, scale, shape, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Pareto cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_ppareto_vec(
+    x: &[f64],
+    scale: &[f64],
+    shape: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("scale", scale),
+        ("shape", shape),
+        out,
+        |x, scale, shape| ppareto(x, scale, shape, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Pareto quantile function
+#[wasm_bindgen]
+pub fn wasm_qpareto_vec(
+    p: &[f64],
+    scale: &[f64],
+    shape: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        p,
+        ("scale", scale),
+        ("shape", shape),
+        out,
+        |p, scale, shape| qpareto(p, scale, shape, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// ZIPF-MANDELBROT DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for Zipf–Mandelbrot density function
+#[wasm_bindgen]
+pub fn wasm_dzipf_vec(
+    x: &[f64],
+    n: &[f64],
+    s: &[f64],
+    q: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(x, ("n", n), ("s", s), ("q", q), out, |x, n, s, q| {
+        dzipf(x, n, s, q, give_log)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Zipf–Mandelbrot cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pzipf_vec(
+    x: &[f64],
+    n: &[f64],
+    s: &[f64],
+    q: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(x, ("n", n), ("s", s), ("q", q), out, |x, n, s, q| {
+        pzipf(x, n, s, q, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Zipf–Mandelbrot quantile function
+#[wasm_bindgen]
+pub fn wasm_qzipf_vec(
+    p: &[f64],
+    n: &[f64],
+    s: &[f64],
+    q: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(p, ("n", n), ("s", s), ("q", q), out, |p, n, s, q| {
+        qzipf(p, n, s, q, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// POINT-MASS (DIRAC) DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for point-mass density function
+#[wasm_bindgen]
+pub fn wasm_ddirac_vec(
+    x: &[f64],
+    location: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(x, ("location", location), out, |x, location| {
+        ddirac(x, location, give_log)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for point-mass cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pdirac_vec(
+    x: &[f64],
+    location: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(x, ("location", location), out, |x, location| {
+        pdirac(x, location, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for point-mass quantile function
+#[wasm_bindgen]
+pub fn wasm_qdirac_vec(
+    p: &[f64],
+    location: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(p, ("location", location), out, |p, location| {
+        qdirac(p, location, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// CAUCHY DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for Cauchy density function
+#[wasm_bindgen]
+pub fn wasm_dcauchy_vec(
+    x: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("location", location),
+        ("scale", scale),
+        out,
+        |x, location, scale| dcauchy(x, location, scale, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Cauchy cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pcauchy_vec(
+    x: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("location", location),
+        ("scale", scale),
+        out,
+        |x, location, scale| pcauchy(x, location, scale, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Cauchy quantile function
+#[wasm_bindgen]
+pub fn wasm_qcauchy_vec(
+    p: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        p,
+        ("location", location),
+        ("scale", scale),
+        out,
+        |p, location, scale| qcauchy(p, location, scale, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// LOGISTIC DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for logistic density function
+#[wasm_bindgen]
+pub fn wasm_dlogis_vec(
+    x: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("location", location),
+        ("scale", scale),
+        out,
+        |x, location, scale| dlogis(x, location, scale, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for logistic cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_plogis_vec(
+    x: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("location", location),
+        ("scale", scale),
+        out,
+        |x, location, scale| plogis(x, location, scale, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for logistic quantile function
+#[wasm_bindgen]
+pub fn wasm_qlogis_vec(
+    p: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        p,
+        ("location", location),
+        ("scale", scale),
+        out,
+        |p, location, scale| qlogis(p, location, scale, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// LAPLACE DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for Laplace density function
+#[wasm_bindgen]
+pub fn wasm_dlaplace_vec(
+    x: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("location", location),
+        ("scale", scale),
+        out,
+        |x, location, scale| dlaplace(x, location, scale, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Laplace cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_plaplace_vec(
+    x: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        x,
+        ("location", location),
+        ("scale", scale),
+        out,
+        |x, location, scale| plaplace(x, location, scale, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for Laplace quantile function
+#[wasm_bindgen]
+pub fn wasm_qlaplace_vec(
+    p: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(
+        p,
+        ("location", location),
+        ("scale", scale),
+        out,
+        |p, location, scale| qlaplace(p, location, scale, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// TRIANGULAR DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for triangular density function
+#[wasm_bindgen]
+pub fn wasm_dtriang_vec(
+    x: &[f64],
+    min: &[f64],
+    max: &[f64],
+    mode: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("min", min),
+        ("max", max),
+        ("mode", mode),
+        out,
+        |x, min, max, mode| dtriang(x, min, max, mode, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for triangular cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_ptriang_vec(
+    x: &[f64],
+    min: &[f64],
+    max: &[f64],
+    mode: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("min", min),
+        ("max", max),
+        ("mode", mode),
+        out,
+        |x, min, max, mode| ptriang(x, min, max, mode, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for triangular quantile function
+#[wasm_bindgen]
+pub fn wasm_qtriang_vec(
+    p: &[f64],
+    min: &[f64],
+    max: &[f64],
+    mode: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        p,
+        ("min", min),
+        ("max", max),
+        ("mode", mode),
+        out,
+        |p, min, max, mode| qtriang(p, min, max, mode, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// LOCATION-SCALE T DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for location-scale t density function
+#[wasm_bindgen]
+pub fn wasm_dlst_vec(
+    x: &[f64],
+    df: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("df", df),
+        ("location", location),
+        ("scale", scale),
+        out,
+        |x, df, location, scale| dlst(x, df, location, scale, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for location-scale t cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_plst_vec(
+    x: &[f64],
+    df: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("df", df),
+        ("location", location),
+        ("scale", scale),
+        out,
+        |x, df, location, scale| plst(x, df, location, scale, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for location-scale t quantile function
+#[wasm_bindgen]
+pub fn wasm_qlst_vec(
+    p: &[f64],
+    df: &[f64],
+    location: &[f64],
+    scale: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        p,
+        ("df", df),
+        ("location", location),
+        ("scale", scale),
+        out,
+        |p, df, location, scale| qlst(p, df, location, scale, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// NONCENTRAL T DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for noncentral t density function
+#[wasm_bindgen]
+pub fn wasm_dnt_vec(
+    x: &[f64],
+    df: &[f64],
+    ncp: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("df", df), ("ncp", ncp), out, |x, df, ncp| {
+        dnt(x, df, ncp, give_log)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for noncentral t cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pnt_vec(
+    x: &[f64],
+    df: &[f64],
+    ncp: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("df", df), ("ncp", ncp), out, |x, df, ncp| {
+        pnt(x, df, ncp, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for noncentral t quantile function
+#[wasm_bindgen]
+pub fn wasm_qnt_vec(
+    p: &[f64],
+    df: &[f64],
+    ncp: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(p, ("df", df), ("ncp", ncp), out, |p, df, ncp| {
+        qnt(p, df, ncp, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// NONCENTRAL CHI-SQUARED DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for noncentral chi-squared density function
+#[wasm_bindgen]
+pub fn wasm_dnchisq_vec(
+    x: &[f64],
+    df: &[f64],
+    ncp: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("df", df), ("ncp", ncp), out, |x, df, ncp| {
+        dnchisq(x, df, ncp, give_log)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for noncentral chi-squared cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pnchisq_vec(
+    x: &[f64],
+    df: &[f64],
+    ncp: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(x, ("df", df), ("ncp", ncp), out, |x, df, ncp| {
+        pnchisq(x, df, ncp, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for noncentral chi-squared quantile function
+#[wasm_bindgen]
+pub fn wasm_qnchisq_vec(
+    p: &[f64],
+    df: &[f64],
+    ncp: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map2(p, ("df", df), ("ncp", ncp), out, |p, df, ncp| {
+        qnchisq(p, df, ncp, lower_tail, log_p)
+    })
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// NONCENTRAL BETA DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for noncentral beta density function
+#[wasm_bindgen]
+pub fn wasm_dnbeta_vec(
+    x: &[f64],
+    shape1: &[f64],
+    shape2: &[f64],
+    ncp: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("shape1", shape1),
+        ("shape2", shape2),
+        ("ncp", ncp),
+        out,
+        |x, shape1, shape2, ncp| dnbeta(x, shape1, shape2, ncp, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for noncentral beta cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pnbeta_vec(
+    x: &[f64],
+    shape1: &[f64],
+    shape2: &[f64],
+    ncp: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("shape1", shape1),
+        ("shape2", shape2),
+        ("ncp", ncp),
+        out,
+        |x, shape1, shape2, ncp| pnbeta(x, shape1, shape2, ncp, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for noncentral beta quantile function
+#[wasm_bindgen]
+pub fn wasm_qnbeta_vec(
+    p: &[f64],
+    shape1: &[f64],
+    shape2: &[f64],
+    ncp: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        p,
+        ("shape1", shape1),
+        ("shape2", shape2),
+        ("ncp", ncp),
+        out,
+        |p, shape1, shape2, ncp| qnbeta(p, shape1, shape2, ncp, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// NONCENTRAL F DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for noncentral F density function
+#[wasm_bindgen]
+pub fn wasm_dnf_vec(
+    x: &[f64],
+    df1: &[f64],
+    df2: &[f64],
+    ncp: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("df1", df1),
+        ("df2", df2),
+        ("ncp", ncp),
+        out,
+        |x, df1, df2, ncp| dnf(x, df1, df2, ncp, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for noncentral F cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_pnf_vec(
+    x: &[f64],
+    df1: &[f64],
+    df2: &[f64],
+    ncp: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("df1", df1),
+        ("df2", df2),
+        ("ncp", ncp),
+        out,
+        |x, df1, df2, ncp| pnf(x, df1, df2, ncp, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for noncentral F quantile function
+#[wasm_bindgen]
+pub fn wasm_qnf_vec(
+    p: &[f64],
+    df1: &[f64],
+    df2: &[f64],
+    ncp: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        p,
+        ("df1", df1),
+        ("df2", df2),
+        ("ncp", ncp),
+        out,
+        |p, df1, df2, ncp| qnf(p, df1, df2, ncp, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// TRUNCATED NORMAL DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for truncated normal density function
+#[wasm_bindgen]
+pub fn wasm_dtruncnorm_vec(
+    x: &[f64],
+    a: &[f64],
+    b: &[f64],
+    mean: &[f64],
+    sd: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map4(
+        x,
+        ("a", a),
+        ("b", b),
+        ("mean", mean),
+        ("sd", sd),
+        out,
+        |x, a, b, mean, sd| dtruncnorm(x, a, b, mean, sd, give_log),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for truncated normal cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_ptruncnorm_vec(
+    x: &[f64],
+    a: &[f64],
+    b: &[f64],
+    mean: &[f64],
+    sd: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map4(
+        x,
+        ("a", a),
+        ("b", b),
+        ("mean", mean),
+        ("sd", sd),
+        out,
+        |x, a, b, mean, sd| ptruncnorm(x, a, b, mean, sd, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for truncated normal quantile function
+#[wasm_bindgen]
+pub fn wasm_qtruncnorm_vec(
+    p: &[f64],
+    a: &[f64],
+    b: &[f64],
+    mean: &[f64],
+    sd: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map4(
+        p,
+        ("a", a),
+        ("b", b),
+        ("mean", mean),
+        ("sd", sd),
+        out,
+        |p, a, b, mean, sd| qtruncnorm(p, a, b, mean, sd, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// TRUNCATED GAMMA AND POISSON DISTRIBUTIONS
+// ============================================================================
+// Invalid parameters or bounds without probability mass give NaN.
+
+/// Vectorised WASM export for truncated gamma density function
+#[wasm_bindgen]
+pub fn wasm_dtruncgamma_vec(
+    x: &[f64],
+    shape: &[f64],
+    rate: &[f64],
+    a: &[f64],
+    b: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map4(
+        x,
+        ("shape", shape),
+        ("rate", rate),
+        ("a", a),
+        ("b", b),
+        out,
+        |x, shape, rate, a, b| {
+            truncated_gamma(shape, rate, a, b).map_or(f64::NAN, |t| t.d(x, give_log))
+        },
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for truncated gamma cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_ptruncgamma_vec(
+    x: &[f64],
+    shape: &[f64],
+    rate: &[f64],
+    a: &[f64],
+    b: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map4(
+        x,
+        ("shape", shape),
+        ("rate", rate),
+        ("a", a),
+        ("b", b),
+        out,
+        |x, shape, rate, a, b| {
+            truncated_gamma(shape, rate, a, b).map_or(f64::NAN, |t| t.p(x, lower_tail, log_p))
+        },
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for truncated gamma quantile function
+#[wasm_bindgen]
+pub fn wasm_qtruncgamma_vec(
+    p: &[f64],
+    shape: &[f64],
+    rate: &[f64],
+    a: &[f64],
+    b: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map4(
+        p,
+        ("shape", shape),
+        ("rate", rate),
+        ("a", a),
+        ("b", b),
+        out,
+        |p, shape, rate, a, b| {
+            truncated_gamma(shape, rate, a, b).map_or(f64::NAN, |t| t.q(p, lower_tail, log_p))
+        },
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for truncated Poisson probability mass function
+#[wasm_bindgen]
+pub fn wasm_dtruncpois_vec(
+    x: &[f64],
+    lambda: &[f64],
+    a: &[f64],
+    b: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("lambda", lambda),
+        ("a", a),
+        ("b", b),
+        out,
+        |x, lambda, a, b| truncated_poisson(lambda, a, b).map_or(f64::NAN, |t| t.d(x, give_log)),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for truncated Poisson cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_ptruncpois_vec(
+    x: &[f64],
+    lambda: &[f64],
+    a: &[f64],
+    b: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        x,
+        ("lambda", lambda),
+        ("a", a),
+        ("b", b),
+        out,
+        |x, lambda, a, b| {
+            truncated_poisson(lambda, a, b).map_or(f64::NAN, |t| t.p(x, lower_tail, log_p))
+        },
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for truncated Poisson quantile function
+#[wasm_bindgen]
+pub fn wasm_qtruncpois_vec(
+    p: &[f64],
+    lambda: &[f64],
+    a: &[f64],
+    b: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        p,
+        ("lambda", lambda),
+        ("a", a),
+        ("b", b),
+        out,
+        |p, lambda, a, b| {
+            truncated_poisson(lambda, a, b).map_or(f64::NAN, |t| t.q(p, lower_tail, log_p))
+        },
+    )
+    .map_err(vec_error)
+}
+
+// ============================================================================
+// SIGNED RANK DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for signed rank probability mass function
+#[wasm_bindgen]
+pub fn wasm_dsignrank_vec(
+    x: &[f64],
+    n: &[f64],
+    give_log: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(x, ("n", n), out, |x, n| dsignrank(x, n, give_log)).map_err(vec_error)
+}
+
+/// Vectorised WASM export for signed rank cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_psignrank_vec(
+    q: &[f64],
+    n: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(q, ("n", n), out, |q, n| psignrank(q, n, lower_tail, log_p)).map_err(vec_error)
+}
+
+/// Vectorised WASM export for signed rank quantile function
+#[wasm_bindgen]
+pub fn wasm_qsignrank_vec(
+    p: &[f64],
+    n: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map1(p, ("n", n), out, |p, n| qsignrank(p, n, lower_tail, log_p)).map_err(vec_error)
+}
+
+// ============================================================================
+// STUDENTIZED RANGE DISTRIBUTION
+// ============================================================================
+
+/// Vectorised WASM export for studentized range cumulative distribution function
+#[wasm_bindgen]
+pub fn wasm_ptukey_vec(
+    q: &[f64],
+    nmeans: &[f64],
+    df: &[f64],
+    nranges: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        q,
+        ("nmeans", nmeans),
+        ("df", df),
+        ("nranges", nranges),
+        out,
+        |q, nmeans, df, nranges| ptukey(q, nmeans, df, nranges, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
+
+/// Vectorised WASM export for studentized range quantile function
+#[wasm_bindgen]
+pub fn wasm_qtukey_vec(
+    p: &[f64],
+    nmeans: &[f64],
+    df: &[f64],
+    nranges: &[f64],
+    lower_tail: bool,
+    log_p: bool,
+    out: &mut [f64],
+) -> Result<(), JsValue> {
+    map3(
+        p,
+        ("nmeans", nmeans),
+        ("df", df),
+        ("nranges", nranges),
+        out,
+        |p, nmeans, df, nranges| qtukey(p, nmeans, df, nranges, lower_tail, log_p),
+    )
+    .map_err(vec_error)
+}
@@ -1,6 +1,10 @@
 //! Weibull distribution wrapper using statrs
+//!
+//! `pweibull` and `qweibull` use the closed forms on the log scale, as in
+//! R's pweibull.c and qweibull.c, so both tails stay accurate.
 
-use statrs::distribution::{Continuous, ContinuousCDF, Weibull};
+use super::helpers::dpq::{d_exp, dt_0, dt_clog, log1_exp, q_p01_boundaries};
+use statrs::distribution::{Continuous, Weibull};
 use rand::Rng;
 
 pub fn dweibull(x: f64, shape: f64, scale: f64, give_log: bool) -> f64 {
@@ -12,25 +16,35 @@ pub fn dweibull(x: f64, shape: f64, scale: f64, give_log: bool) -> f64 {
 }
 
 pub fn pweibull(x: f64, shape: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || shape.is_nan() || scale.is_nan() {
+        return x + shape + scale;
+    }
     if shape <= 0.0 || scale <= 0.0 {
         return f64::NAN;
     }
-    let dist = Weibull::new(shape, scale).expect("validated parameters: shape > 0, scale > 0");
-    let cdf = if lower_tail { dist.cdf(x) } else { 1.0 - dist.cdf(x) };
-    if log_p { cdf.ln() } else { cdf }
+    if x <= 0.0 {
+        return dt_0(lower_tail, log_p);
+    }
+    // log P(X > x) in closed form; the lower tail is its log-complement
+    let x = -(x / scale).powf(shape);
+    if lower_tail {
+        if log_p { log1_exp(x) } else { -x.exp_m1() }
+    } else {
+        d_exp(x, log_p)
+    }
 }
 
 pub fn qweibull(p: f64, shape: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || shape.is_nan() || scale.is_nan() {
+        return p + shape + scale;
+    }
     if shape <= 0.0 || scale <= 0.0 {
         return f64::NAN;
     }
-    let dist = Weibull::new(shape, scale).expect("validated parameters: shape > 0, scale > 0");
-    let mut p_val = if log_p { p.exp() } else { p };
-    if !lower_tail {
-        p_val = 1.0 - p_val;
+    if let Some(q) = q_p01_boundaries(p, 0.0, f64::INFINITY, lower_tail, log_p) {
+        return q;
     }
-    p_val = super::helpers::clamp_unit(p_val);
-    dist.inverse_cdf(p_val)
+    scale * (-dt_clog(p, lower_tail, log_p)).powf(1.0 / shape)
 }
 
 pub fn rweibull<R: Rng>(shape: f64, scale: f64, rng: &mut R) -> f64 {
@@ -99,4 +113,13 @@ mod tests {
         let expected = 0.5;
         assert!((qweibull(p, 2.0, 1.0, true, true) - expected).abs() < 1e-4);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_weibull_extreme_tails() {
+        // P(X > 30) = exp(-900) underflows, its log does not
+        assert_eq!(pweibull(30.0, 2.0, 1.0, false, true), -900.0);
+        assert!((pweibull(1e-10, 2.0, 1.0, true, false) / 1e-20 - 1.0).abs() < 1e-15);
+        assert!((qweibull(-900.0, 2.0, 1.0, false, true) - 30.0).abs() < 1e-13);
+        assert!((qweibull(1e-20, 2.0, 1.0, true, false) / 1e-10 - 1.0).abs() < 1e-15);
+    }
+}
@@ -3,6 +3,7 @@
 //! This module provides the exact Wilcoxon distribution functions based on R's implementation.
 //! The Wilcoxon rank-sum test (Mann-Whitney U test) uses this distribution for exact p-values.
 
+use rand::Rng;
 use std::collections::HashMap;
 use std::f64;
 use std::sync::Mutex;
@@ -13,6 +14,10 @@ use std::sync::Once;
 static WILCOX_CACHE: Mutex<Option<HashMap<(usize, usize, usize), f64>>> = Mutex::new(None);
 static INIT: Once = Once::new();
 
+/// Probability slack in `qwilcox` (R uses 10 ulps) so that it inverts the
+/// rounded cumulative sums of `pwilcox`
+const QWILCOX_SLACK: f64 = 10.0 * f64::EPSILON;
+
 /// Initialize the Wilcoxon cache
 fn init_cache() {
     INIT.call_once(|| {
@@ -295,6 +300,7 @@ pub fn qwilcox(p: f64, m: f64, n: f64, lower_tail: bool, log_p: bool) -> f64 {
 
     if p_val <= 0.5 {
         // Search from 0 upward
+        p_val -= QWILCOX_SLACK;
         for q in 0..=m * n {
             cum_prob += cwilcox(q, m, n) / total;
             if cum_prob >= p_val {
@@ -303,7 +309,7 @@ pub fn qwilcox(p: f64, m: f64, n: f64, lower_tail: bool, log_p: bool) -> f64 {
         }
     } else {
         // Search from maximum downward (more efficient for p > 0.5)
-        p_val = 1.0 - p_val;
+        p_val = 1.0 - p_val + QWILCOX_SLACK;
         for q in 0..=m * n {
             cum_prob += cwilcox(q, m, n) / total;
             if cum_prob > p_val {
@@ -315,17 +321,43 @@ pub fn qwilcox(p: f64, m: f64, n: f64, lower_tail: bool, log_p: bool) -> f64 {
     (m * n) as f64
 }
 
-/// Random number generation for Wilcoxon distribution (not implemented)
+/// Random number generation for Wilcoxon distribution
+///
+/// Follows R's `rwilcox`: draws `n` of the ranks `0..m + n` without
+/// replacement and returns their sum minus `n(n - 1)/2`.
 ///
 /// # Arguments
 /// * `m` - First sample size
 /// * `n` - Second sample size
-/// * `_rng` - Random number generator (unused)
+/// * `rng` - Random number generator
 ///
 /// # Returns
-/// Always returns NaN as random generation is not implemented
-pub fn rwilcox<R>(_m: f64, _n: f64, _rng: &mut R) -> f64 {
-    f64::NAN
+/// A random value of the Wilcoxon rank sum statistic
+pub fn rwilcox<R: Rng>(m: f64, n: f64, rng: &mut R) -> f64 {
+    if m.is_nan() || n.is_nan() {
+        return m + n;
+    }
+    let m = m.round();
+    let n = n.round();
+    // Indices are drawn as u32 so the stream is the same on 32- and 64-bit
+    // targets (wasm and native)
+    if m < 0.0 || n < 0.0 || m + n > u32::MAX as f64 {
+        return f64::NAN;
+    }
+    if m == 0.0 || n == 0.0 {
+        return 0.0;
+    }
+    let (m, n) = (m as u32, n as u32);
+    let mut k = m + n;
+    let mut x: Vec<u32> = (0..k).collect();
+    let mut r = 0.0;
+    for _ in 0..n {
+        let j = rng.gen_range(0..k) as usize;
+        r += x[j] as f64;
+        k -= 1;
+        x[j] = x[k as usize];
+    }
+    r - n as f64 * (n as f64 - 1.0) / 2.0
 }
 
 #[cfg(test)]
@@ -398,12 +430,42 @@ mod tests {
         assert_eq!(qwilcox(1.0, 1.0, 1.0, true, false), 1.0);
     }
 
+    #[test]
+    fn test_qwilcox_slack_inverts_pwilcox() {
+        // pwilcox sums rounded terms, so without the slack some exact
+        // cumulative probabilities map to the next quantile
+        for (m, n) in [(4.0, 6.0), (5.0, 7.0), (10.0, 3.0)] {
+            for q in 0..(m * n) as usize {
+                let q = q as f64;
+                let lower = pwilcox(q, m, n, true, false);
+                assert_eq!(qwilcox(lower, m, n, true, false), q, "lower {} {} {}", q, m, n);
+                let log_lower = pwilcox(q, m, n, true, true);
+                assert_eq!(qwilcox(log_lower, m, n, true, true), q, "log {} {} {}", q, m, n);
+                let upper = pwilcox(q, m, n, false, false);
+                assert_eq!(qwilcox(upper, m, n, false, false), q, "upper {} {} {}", q, m, n);
+                // The slack is far smaller than the gap to the next quantile
+                assert_eq!(qwilcox(lower + 1e-12, m, n, true, false), q + 1.0);
+                assert_eq!(qwilcox(upper - 1e-12, m, n, false, false), q + 1.0);
+            }
+        }
+    }
+
     #[test]
     fn test_rwilcox() {
         let mut rng = StdRng::seed_from_u64(42);
-        let sample = rwilcox(3.0, 4.0, &mut rng);
-        // Random generation is not implemented, so it should return NaN
-        assert!(sample.is_nan());
+        for _ in 0..100 {
+            let sample = rwilcox(3.0, 4.0, &mut rng);
+            assert!((0.0..=12.0).contains(&sample) && sample.fract() == 0.0);
+        }
+        assert!(rwilcox(f64::INFINITY, 4.0, &mut rng).is_nan());
+    }
+
+    #[test]
+    fn test_rwilcox_stream() {
+        // Pinned so native and wasm builds draw the same values for a seed
+        let mut rng = StdRng::seed_from_u64(39);
+        let draws: Vec<f64> = (0..8).map(|_| rwilcox(5.0, 7.0, &mut rng)).collect();
+        assert_eq!(draws, [26.0, 19.0, 16.0, 23.0, 16.0, 23.0, 23.0, 13.0]);
     }
 
     #[test]
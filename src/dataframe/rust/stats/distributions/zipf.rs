@@ -0,0 +1,352 @@
+//! Zipf and Zipf–Mandelbrot distribution functions
+//!
+//! Support is k = 1, ..., n with P(X = k) ∝ (k + q)^(-s). `q = 0` gives the
+//! classical Zipf law, and `n = Inf` (allowed for s > 1) the zeta
+//! distribution. Probabilities are normalised by the sum over the support;
+//! both tails are summed separately so that upper-tail probabilities do not
+//! suffer from cancellation.
+//!
+//! Sums over many ranks add the leading terms directly and the rest by the
+//! Euler–Maclaurin formula (the Hurwitz zeta tail), so every function costs
+//! O(s) rather than O(n).
+
+use super::helpers::dpq::{d_0, d_exp, dt_0, dt_1, dt_civ, dt_from_logs, dt_qiv, q_p01_invalid};
+use rand::Rng;
+
+/// Largest rank the quantile search will return before giving up with `Inf`
+const MAX_RANK: f64 = 9007199254740992.0; // 2^53
+
+/// Validated number of elements (`Inf` for the zeta distribution), or `None`
+/// for invalid parameters
+fn support_size(n: f64, s: f64, q: f64) -> Option<f64> {
+    if !s.is_finite() || s < 0.0 || !q.is_finite() || q <= -1.0 {
+        return None;
+    }
+    if n == f64::INFINITY {
+        return (s > 1.0).then_some(n);
+    }
+    match super::helpers::validate_integer(n) {
+        Some(n) if n >= 1 => Some(n as f64),
+        _ => None,
+    }
+}
+
+/// Log of the unnormalised weight of rank `k`, scaled so that rank 1 has
+/// weight 1
+#[inline]
+fn log_weight(k: f64, s: f64, q: f64) -> f64 {
+    -s * ((k - 1.0) / (1.0 + q)).ln_1p()
+}
+
+/// Sum of weights `((k + q) / (1 + q))^(-s)` over ranks `from..=to`
+///
+/// `to` may be infinite when `s > 1`. The exponent may be negative, as used
+/// for the moments.
+pub(crate) fn weight_sum(from: f64, to: f64, s: f64, q: f64) -> f64 {
+    if to < from {
+        return 0.0;
+    }
+    // Beyond `cut` the Euler–Maclaurin remainder after the B6 term is below
+    // ((s + 7) / (k + q))^7 / 8! relative, i.e. negligible
+    let cut = (10.0 * (s.abs() + 7.0) - q).ceil().max(from);
+    let mut sum = 0.0;
+    let mut k = from;
+    while k <= to && k < cut {
+        let w = log_weight(k, s, q).exp();
+        sum += w;
+        // Weights decrease, and at most `cut` terms plus a tail of similar
+        // size remain: stop once they cannot change the sum
+        if s >= 2.0 && w * 20.0 * (s + 7.0) < 1e-17 * sum {
+            return sum;
+        }
+        k += 1.0;
+    }
+    if k > to {
+        return sum;
+    }
+
+    // Euler–Maclaurin for f(x) = ((x + q) / (1 + q))^(-s) on [k, to]:
+    // the integral, the end-point average and the B2, B4, B6 corrections
+    let a = k + q;
+    let fa = log_weight(k, s, q).exp();
+    let integral = if to == f64::INFINITY {
+        fa * a / (s - 1.0)
+    } else {
+        let l = ((to - k) / a).ln_1p();
+        if s == 1.0 {
+            fa * a * l
+        } else {
+            fa * a * ((1.0 - s) * l).exp_m1() / (1.0 - s)
+        }
+    };
+    // Odd derivatives f^(m)(x) = f(x) (-s)(-s-1)...(-s-m+1) / (x + q)^m
+    let corrections = |x: f64, fx: f64| {
+        let (d1, d3, d5) = (
+            -s / x,
+            -s * (s + 1.0) * (s + 2.0) / x.powi(3),
+            -s * (s + 1.0) * (s + 2.0) * (s + 3.0) * (s + 4.0) / x.powi(5),
+        );
+        fx * (d1 / 12.0 - d3 / 720.0 + d5 / 30240.0)
+    };
+    let mut tail = integral + 0.5 * fa - corrections(a, fa);
+    if to.is_finite() {
+        let fb = log_weight(to, s, q).exp();
+        tail += 0.5 * fb + corrections(to + q, fb);
+    }
+    sum + tail
+}
+
+/// Zipf–Mandelbrot probability mass function
+///
+/// # Arguments
+/// * `x` - Rank at which to evaluate the probability (1 to n)
+/// * `n` - Number of elements (`Inf` for s > 1)
+/// * `s` - Exponent (must be non-negative)
+/// * `q` - Mandelbrot shift (must exceed -1; 0 for plain Zipf)
+/// * `give_log` - If true, return log probability
+///
+/// # Returns
+/// The probability mass or log probability mass
+pub fn dzipf(x: f64, n: f64, s: f64, q: f64, give_log: bool) -> f64 {
+    if x.is_nan() || n.is_nan() || s.is_nan() || q.is_nan() {
+        return x + n + s + q;
+    }
+    let Some(n) = support_size(n, s, q) else {
+        return f64::NAN;
+    };
+    let k = match super::helpers::validate_integer(x) {
+        Some(k) if k >= 1 && k as f64 <= n => k as f64,
+        _ => return d_0(give_log),
+    };
+    d_exp(
+        log_weight(k, s, q) - weight_sum(1.0, n, s, q).ln(),
+        give_log,
+    )
+}
+
+/// Zipf–Mandelbrot cumulative distribution function
+///
+/// # Arguments
+/// * `x` - Rank at which to evaluate the CDF
+/// * `n` - Number of elements (`Inf` for s > 1)
+/// * `s` - Exponent (must be non-negative)
+/// * `q` - Mandelbrot shift (must exceed -1; 0 for plain Zipf)
+/// * `lower_tail` - If true, return P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, return log probability
+///
+/// # Returns
+/// The cumulative probability or log cumulative probability
+pub fn pzipf(x: f64, n: f64, s: f64, q: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || n.is_nan() || s.is_nan() || q.is_nan() {
+        return x + n + s + q;
+    }
+    let Some(n) = support_size(n, s, q) else {
+        return f64::NAN;
+    };
+    // Fuzz against representation error, as R does for discrete CDFs
+    let x = (x + 1e-7).floor();
+    if x < 1.0 {
+        return dt_0(lower_tail, log_p);
+    }
+    if x >= n {
+        return dt_1(lower_tail, log_p);
+    }
+    let lower = weight_sum(1.0, x, s, q);
+    let upper = weight_sum(x + 1.0, n, s, q);
+    let log_total = (lower + upper).ln();
+    dt_from_logs(
+        lower.ln() - log_total,
+        upper.ln() - log_total,
+        lower_tail,
+        log_p,
+    )
+}
+
+/// Zipf–Mandelbrot quantile function
+///
+/// # Arguments
+/// * `p` - Probability
+/// * `n` - Number of elements (`Inf` for s > 1)
+/// * `s` - Exponent (must be non-negative)
+/// * `q` - Mandelbrot shift (must exceed -1; 0 for plain Zipf)
+/// * `lower_tail` - If true, p is P(X ≤ x), otherwise P(X > x)
+/// * `log_p` - If true, p is log probability
+///
+/// # Returns
+/// The smallest rank whose CDF reaches `p` (`Inf` if that rank exceeds 2^53)
+pub fn qzipf(p: f64, n: f64, s: f64, q: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || n.is_nan() || s.is_nan() || q.is_nan() {
+        return p + n + s + q;
+    }
+    let Some(n) = support_size(n, s, q) else {
+        return f64::NAN;
+    };
+    if q_p01_invalid(p, log_p) {
+        return f64::NAN;
+    }
+    let total = weight_sum(1.0, n, s, q);
+    let fuzz = 64.0 * f64::EPSILON;
+    // Both conditions are monotone in k and hold at k = n
+    let reached: Box<dyn Fn(f64) -> bool> = if lower_tail {
+        let target = dt_qiv(p, lower_tail, log_p) * total * (1.0 - fuzz);
+        Box::new(move |k| weight_sum(1.0, k, s, q) >= target)
+    } else {
+        // Upper tail summed directly: the smallest k with P(X > k) <= p
+        let target = dt_civ(p, lower_tail, log_p) * total * (1.0 + fuzz);
+        Box::new(move |k| weight_sum(k + 1.0, n, s, q) <= target)
+    };
+
+    // Bracket by doubling, then bisect on the rank
+    let mut lo = 0.0;
+    let mut hi = 1.0;
+    while !reached(hi) {
+        lo = hi;
+        hi = (2.0 * hi).min(n);
+        if hi > MAX_RANK {
+            return f64::INFINITY;
+        }
+    }
+    while hi - lo > 1.0 {
+        let mid = (0.5 * (lo + hi)).floor();
+        if reached(mid) { hi = mid } else { lo = mid }
+    }
+    hi
+}
+
+/// Zipf–Mandelbrot random number generation (by inversion)
+///
+/// # Arguments
+/// * `n` - Number of elements (`Inf` for s > 1)
+/// * `s` - Exponent (must be non-negative)
+/// * `q` - Mandelbrot shift (must exceed -1; 0 for plain Zipf)
+/// * `rng` - Random number generator
+///
+/// # Returns
+/// A random rank from the distribution
+pub fn rzipf<R: Rng>(n: f64, s: f64, q: f64, rng: &mut R) -> f64 {
+    if support_size(n, s, q).is_none() {
+        return f64::NAN;
+    }
+    qzipf(rng.gen_range(0.0..1.0), n, s, q, true, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_dzipf() {
+        // n = 3, s = 1: weights 1, 1/2, 1/3 with total 11/6
+        assert!((dzipf(1.0, 3.0, 1.0, 0.0, false) - 6.0 / 11.0).abs() < 1e-15);
+        assert!((dzipf(3.0, 3.0, 1.0, 0.0, false) - 2.0 / 11.0).abs() < 1e-15);
+        assert_eq!(dzipf(4.0, 3.0, 1.0, 0.0, false), 0.0);
+        assert_eq!(dzipf(1.5, 3.0, 1.0, 0.0, false), 0.0);
+        // s = 0 is uniform on 1..n
+        assert!((dzipf(2.0, 4.0, 0.0, 0.0, false) - 0.25).abs() < 1e-15);
+        // Mandelbrot shift: weights 1/2, 1/3 with total 5/6
+        assert!((dzipf(1.0, 2.0, 1.0, 1.0, false) - 0.6).abs() < 1e-15);
+        assert!(dzipf(1.0, 0.0, 1.0, 0.0, false).is_nan());
+    }
+
+    #[test]
+    fn test_pzipf() {
+        assert!((pzipf(2.0, 3.0, 1.0, 0.0, true, false) - 9.0 / 11.0).abs() < 1e-15);
+        assert!((pzipf(2.0, 3.0, 1.0, 0.0, false, false) - 2.0 / 11.0).abs() < 1e-15);
+        assert_eq!(pzipf(0.5, 3.0, 1.0, 0.0, true, false), 0.0);
+        assert_eq!(pzipf(3.0, 3.0, 1.0, 0.0, true, false), 1.0);
+        // Upper tail is accurate where 1 - F would underflow to 0
+        let log_upper = pzipf(1.0, 2.0, 60.0, 0.0, false, true);
+        assert!((log_upper - (-60.0 * 2.0f64.ln() - (1.0 + 2.0f64.powi(-60)).ln())).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_qzipf_inverts_pzipf() {
+        for k in 1..=5 {
+            let x = k as f64;
+            let p = pzipf(x, 5.0, 1.2, 0.5, true, false);
+            assert_eq!(qzipf(p, 5.0, 1.2, 0.5, true, false), x);
+            let lq = pzipf(x, 5.0, 1.2, 0.5, false, true);
+            assert_eq!(qzipf(lq, 5.0, 1.2, 0.5, false, true), x);
+        }
+        assert_eq!(qzipf(0.0, 5.0, 1.0, 0.0, true, false), 1.0);
+        assert_eq!(qzipf(1.0, 5.0, 1.0, 0.0, true, false), 5.0);
+    }
+
+    #[test]
+    fn test_weight_sum_matches_direct_sum() {
+        for &s in &[0.0, 0.5, 1.0, 1.1, 3.0, -2.0] {
+            for &q in &[0.0, 2.5, -0.5] {
+                for &(from, to) in &[(1.0, 100_000.0), (37.0, 5_000.0), (1.0, 150.0)] {
+                    let direct: f64 = (from as u64..=to as u64)
+                        .rev()
+                        .map(|k| log_weight(k as f64, s, q).exp())
+                        .sum();
+                    let sum = weight_sum(from, to, s, q);
+                    assert!(
+                        ((sum - direct) / direct).abs() < 1e-13,
+                        "s={s} q={q} [{from}, {to}]: {sum} vs {direct}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_zeta_support() {
+        let zeta2 = std::f64::consts::PI.powi(2) / 6.0;
+        assert!((dzipf(1.0, f64::INFINITY, 2.0, 0.0, false) - 1.0 / zeta2).abs() < 1e-15);
+        let zeta4 = std::f64::consts::PI.powi(4) / 90.0;
+        assert!((dzipf(2.0, f64::INFINITY, 4.0, 0.0, false) - 1.0 / (16.0 * zeta4)).abs() < 1e-15);
+        // P(X > 1) = 1 - 1/zeta(2)
+        let upper = pzipf(1.0, f64::INFINITY, 2.0, 0.0, false, false);
+        assert!((upper - (1.0 - 1.0 / zeta2)).abs() < 1e-15);
+        assert_eq!(
+            pzipf(f64::INFINITY, f64::INFINITY, 2.0, 0.0, true, false),
+            1.0
+        );
+        // Tail index 1: P(X > k) ~ 1 / (zeta(2) k) far out
+        let k = qzipf(1e-9, f64::INFINITY, 2.0, 0.0, false, false);
+        assert!(pzipf(k, f64::INFINITY, 2.0, 0.0, false, false) <= 1e-9);
+        assert!(pzipf(k - 1.0, f64::INFINITY, 2.0, 0.0, false, false) > 1e-9);
+        assert!((k / (1e9 / zeta2) - 1.0).abs() < 1e-6);
+        // Beyond 2^53 the quantile is reported as infinite
+        assert_eq!(
+            qzipf(1e-300, f64::INFINITY, 1.01, 0.0, false, false),
+            f64::INFINITY
+        );
+        // The series diverges for s <= 1
+        assert!(dzipf(1.0, f64::INFINITY, 1.0, 0.0, false).is_nan());
+        assert!(pzipf(1.0, f64::INFINITY, 0.5, 0.0, true, false).is_nan());
+    }
+
+    #[test]
+    fn test_huge_support() {
+        // Finite n = 1e13 with s = 2 differs from zeta(2) by about 1/n
+        let n = 1e13;
+        let zeta2 = std::f64::consts::PI.powi(2) / 6.0;
+        let total = zeta2 - 1.0 / n + 0.5 / (n * n);
+        assert!((dzipf(1.0, n, 2.0, 0.0, false) - 1.0 / total).abs() < 1e-15);
+        // The median is found by bisection without materialising weights
+        let m = qzipf(0.5, n, 1.1, 0.0, true, false);
+        assert!(m.is_finite() && m < n);
+        assert!(pzipf(m, n, 1.1, 0.0, true, false) >= 0.5);
+        assert!(pzipf(m - 1.0, n, 1.1, 0.0, true, false) < 0.5);
+        let mut rng = StdRng::seed_from_u64(41);
+        for _ in 0..100 {
+            let x = rzipf(n, 1.1, 0.0, &mut rng);
+            assert!((1.0..=n).contains(&x) && x.fract() == 0.0);
+        }
+    }
+
+    #[test]
+    fn test_rzipf() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let n = 20_000;
+        let ones = (0..n)
+            .filter(|_| rzipf(3.0, 1.0, 0.0, &mut rng) == 1.0)
+            .count();
+        assert!((ones as f64 / n as f64 - 6.0 / 11.0).abs() < 0.02);
+    }
+}
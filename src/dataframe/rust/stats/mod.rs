@@ -2,17 +2,20 @@
 //!
 //! This module provides comprehensive statistical functionality including:
 //! - Probability distributions (normal, binomial, chi-squared, etc.)
+//! - Kernel density estimation with R's bandwidth selectors
 //! - Statistical tests (t-tests, ANOVA, chi-square tests, etc.)
 //! - Common statistical utilities and helpers
 //!
 //! The module is organized into several submodules:
 //! - `core`: Core statistical types, utilities, and error handling
+//! - `density`: Kernel density estimation and bandwidth selection
 //! - `distributions`: Probability distribution functions
 //! - `extensions`: Statistical helper functions and data manipulation traits
 //! - `statistical_tests`: Hypothesis testing functions
 //! - `helpers`: Helper functions for WASM bindings and common operations
 
 pub mod core;
+pub mod density;
 pub mod distributions;
 pub mod extensions;
 pub mod helpers;
@@ -4,6 +4,7 @@
 
 use super::glm_main_core::glm;
 use super::types::GlmResult;
+use crate::io::json::{JsonFormat, JsonReadOptions, read_json};
 use crate::stats::regression::shared::formula_parser::parse_formula;
 use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
@@ -232,8 +233,10 @@ fn generate_interaction_combinations(var_expansions: &[Vec<String>]) -> Vec<Stri
 
 /// Parse data from JSON string into numeric data and categorical variable information.
 ///
-/// Processes JSON data to identify categorical variables (string arrays) and converts them
-/// to dummy variables, while preserving numeric variables as-is.
+/// The column-oriented JSON is read with the IO layer's JSON reader, so column
+/// types come from its inference pass over every value rather than the first
+/// one. String (and mixed-type) columns are categorical and converted to dummy
+/// variables; numeric, boolean and date columns are kept as numbers.
 ///
 /// # Arguments
 /// * `json` - JSON string containing data as object with column names as keys
@@ -249,69 +252,46 @@ fn generate_interaction_combinations(var_expansions: &[Vec<String>]) -> Vec<Stri
 fn parse_data_json(
     json: &str,
 ) -> Result<(HashMap<String, Vec<f64>>, HashMap<String, Vec<String>>), String> {
-    use serde_json::Value;
-
-    // Parse JSON properly using serde_json
-    let parsed: Value =
-        serde_json::from_str(json).map_err(|e| format!("JSON parsing error: {}", e))?;
-
-    let obj = parsed
-        .as_object()
-        .ok_or_else(|| format!("Expected JSON object, got: {:?}", parsed))?;
+    let opts = JsonReadOptions {
+        format: JsonFormat::Columns,
+        parse_dates: false,
+        ..Default::default()
+    };
+    let (table, _) = read_json(json.as_bytes(), &opts)?;
 
     let mut data = HashMap::new();
     let mut categorical_vars = HashMap::new();
 
-    // First pass: identify categorical variables and collect unique values
-    for (key, value) in obj.iter() {
-        if let Some(array) = value.as_array() {
-            if !array.is_empty() {
-                if array[0].is_string() {
-                    // This is a categorical variable
-                    let mut unique_values = std::collections::HashSet::new();
-                    for item in array.iter() {
-                        if let Some(s) = item.as_str() {
-                            unique_values.insert(s.to_string());
-                        }
-                    }
-                    let mut sorted_values: Vec<String> = unique_values.into_iter().collect();
-                    sorted_values.sort();
-                    categorical_vars.insert(key.clone(), sorted_values);
+    for column in &table.columns {
+        match column.to_f64() {
+            Some(values) => {
+                if let Some(row) = values.iter().position(|x| x.is_nan()) {
+                    return Err(format!(
+                        "Non-numeric value in column '{}' at row {}",
+                        column.name,
+                        row + 1
+                    ));
                 }
+                data.insert(column.name.clone(), values);
             }
-        }
-    }
-
-    // Second pass: convert data to numeric, creating dummy variables for categoricals
-    for (key, value) in obj.iter() {
-        if let Some(array) = value.as_array() {
-            if let Some(categories) = categorical_vars.get(key) {
-                // Convert categorical to dummy variables (exclude first category as reference)
-                for (_i, category) in categories.iter().enumerate().skip(1) {
-                    let dummy_name = format!("{}{}", key, category);
-                    let dummy_values: Vec<f64> = array
-                        .iter()
-                        .map(|item| {
-                            if let Some(s) = item.as_str() {
-                                if s == category { 1.0 } else { 0.0 }
-                            } else {
-                                0.0
-                            }
-                        })
+            None => {
+                // Levels are the sorted distinct values actually present
+                let mut levels: Vec<String> = (0..column.len())
+                    .filter_map(|row| column.str_at(row))
+                    .map(str::to_string)
+                    .collect();
+                levels.sort();
+                levels.dedup();
+
+                // Dummy variables for every level but the first (the reference);
+                // missing values are 0 in every dummy
+                for level in levels.iter().skip(1) {
+                    let dummy_values = (0..column.len())
+                        .map(|row| (column.str_at(row) == Some(level.as_str())) as u8 as f64)
                         .collect();
-                    data.insert(dummy_name, dummy_values);
+                    data.insert(format!("{}{}", column.name, level), dummy_values);
                 }
-            } else {
-                // Convert numeric array
-                let values: Result<Vec<f64>, String> = array
-                    .iter()
-                    .map(|item| {
-                        item.as_f64().ok_or_else(|| {
-                            format!("Non-numeric value in column '{}': {:?}", key, item)
-                        })
-                    })
-                    .collect();
-                data.insert(key.clone(), values?);
+                categorical_vars.insert(column.name.clone(), levels);
             }
         }
     }
@@ -1,6 +1,8 @@
 use statrs::distribution::{ContinuousCDF, Normal};
 use std::cmp;
 
+use crate::stats::distributions::Distribution;
+
 use super::super::super::core::types::{
     AndersonDarlingTestResult, TestStatistic, TestStatisticName,
 };
@@ -118,6 +120,104 @@ impl AndersonDarlingTest {
         })
     }
 
+    /// Run the Anderson-Darling test of `x` against a fully specified
+    /// continuous distribution, such as one returned by `fitdistr`.
+    ///
+    /// The p-value uses Marsaglia & Marsaglia's (2004) approximation to the
+    /// finite-sample null distribution of A². It assumes the parameters were
+    /// not estimated from `x`, so it is conservative for fitted ones.
+    ///
+    /// # Arguments
+    /// * `x` - Sample data
+    /// * `dist` - Hypothesised distribution
+    /// * `alpha` - Significance level for the test
+    ///
+    /// # Returns
+    /// * `Ok(AndersonDarlingTestResult)` - Test results
+    /// * `Err(String)` - Error message
+    pub fn with_distribution<D: Distribution + ?Sized>(
+        x: &[f64],
+        dist: &D,
+        alpha: f64,
+    ) -> Result<AndersonDarlingTestResult, String> {
+        let n = x.len();
+        if n == 0 {
+            return Err("Sample must contain at least one observation".to_string());
+        }
+        if x.iter().any(|v| !v.is_finite()) {
+            return Err("Sample must contain only finite values".to_string());
+        }
+        let mut sorted = x.to_owned();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(cmp::Ordering::Equal));
+
+        // A² = -n - mean((2i - 1) (log F(x_(i)) + log(1 - F(x_(n+1-i)))))
+        // with both tails on the log scale to keep the extremes accurate
+        let sum: f64 = (0..n)
+            .map(|i| {
+                (2 * i + 1) as f64 * (dist.log_cdf(sorted[i]) + dist.log_sf(sorted[n - 1 - i]))
+            })
+            .sum();
+        let a = -(n as f64) - sum / n as f64;
+        let p_value = if a.is_finite() {
+            (1.0 - Self::marsaglia_cdf(n, a)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        Ok(AndersonDarlingTestResult {
+            test_statistic: TestStatistic {
+                value: a,
+                name: TestStatisticName::AStatistic.as_str().to_string(),
+            },
+            p_value,
+            test_name: "Anderson-Darling Test".to_string(),
+            alpha,
+            error_message: None,
+            sample_size: n,
+        })
+    }
+
+    /// P(A² < z) for a sample of size `n` from a fully specified
+    /// distribution: the limiting distribution plus a correction in `n`
+    ///
+    /// Marsaglia, G., & Marsaglia, J. (2004). Evaluating the Anderson-Darling
+    ///     distribution. Journal of Statistical Software, 9(2).
+    fn marsaglia_cdf(n: usize, z: f64) -> f64 {
+        let x = if z < 2.0 {
+            (-1.2337141 / z).exp() / z.sqrt()
+                * (2.00012
+                    + (0.247105
+                        - (0.0649821 - (0.0347962 - (0.011672 - 0.00168691 * z) * z) * z) * z)
+                        * z)
+        } else {
+            (-(1.0776
+                - (2.30695 - (0.43424 - (0.082433 - (0.008056 - 0.0003146 * z) * z) * z) * z)
+                    * z)
+                .exp())
+            .exp()
+        };
+        let n = n as f64;
+        let correction = if x > 0.8 {
+            (-130.2137
+                + (745.2337 - (1705.091 - (1950.646 - (1116.360 - 255.7844 * x) * x) * x) * x)
+                    * x)
+                / n
+        } else {
+            let c = 0.01265 + 0.1757 / n;
+            if x < c {
+                let t = x / c;
+                t.sqrt() * (1.0 - t) * (49.0 * t - 102.0) * (0.0037 / (n * n) + 0.00078 / n + 0.00006)
+                    / n
+            } else {
+                let t = (x - c) / (0.8 - c);
+                (-0.00022633
+                    + (6.54034 - (14.6538 - (14.458 - (8.259 - 1.91864 * t) * t) * t) * t) * t)
+                    * (0.04213 / n + 0.01365 / (n * n))
+            }
+        };
+        x + correction
+    }
+
     /// Calculate p-value for the Anderson-Darling statistic exactly as R does
     /// From the R adTest implementation
     fn calculate_p_value_r(aa: f64) -> f64 {
@@ -184,6 +284,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn anderson_darling_distribution_critical_values() {
+        // Case 0 asymptotic critical values: 1.933 (10%), 2.492 (5%), 3.857 (1%)
+        for (z, p) in [(1.933, 0.10), (2.492, 0.05), (3.857, 0.01)] {
+            let upper = 1.0 - AndersonDarlingTest::marsaglia_cdf(100_000, z);
+            assert!((upper - p).abs() < 5e-4, "z = {z}: {upper}");
+        }
+    }
+
+    #[test]
+    fn anderson_darling_distribution() {
+        use crate::stats::distributions::families;
+        let x: Vec<f64> = (1..=20).map(|i| (i as f64 - 0.5) / 20.0).collect();
+        let uniform = families::Uniform::new(0.0, 1.0).unwrap();
+        let test = AndersonDarlingTest::with_distribution(&x, &uniform, 0.05).unwrap();
+        assert!(test.p_value > 0.9);
+        assert_eq!(test.sample_size, 20);
+
+        let shifted: Vec<f64> = x.iter().map(|v| v * 0.5).collect();
+        let test = AndersonDarlingTest::with_distribution(&shifted, &uniform, 0.05).unwrap();
+        assert!(test.p_value < 0.01);
+        let outside = [0.5, 1.5];
+        let test = AndersonDarlingTest::with_distribution(&outside, &uniform, 0.05).unwrap();
+        assert_eq!(test.p_value, 0.0);
+    }
+
     #[test]
     fn anderson_darling_zero_variance() {
         let x = vec![5.0; 10];
@@ -10,6 +10,7 @@
 //!
 //! To calculate the required sample size for ANOVA tests, you can use the following function:
 //! - `f_sample_size`: Calculates the necessary sample size for one-way ANOVA tests
+//! - `f_power`: Calculates the exact power of a one-way ANOVA from the noncentral F distribution
 //!
 //! ## Submodules
 //!
@@ -21,6 +22,7 @@
 //! The following functions are made available for use:
 //! - `anova`: Performs one-way ANOVA tests on multiple groups of data.
 //! - `f_sample_size`: Calculates the required sample size for one-way ANOVA tests
+//! - `f_power`: Calculates the power of a one-way ANOVA
 //!
 //! ## Example
 //! ```rust
@@ -36,7 +38,7 @@ pub mod two_way;
 pub mod wasm;
 
 pub use one_way::{anova, welch_anova};
-pub use sample_size::f_sample_size;
+pub use sample_size::{f_power, f_sample_size};
 pub use two_way::{
     TwoWayAnovaResult, anova_two_way, anova_two_way_factor_a, anova_two_way_factor_b,
     anova_two_way_interaction,
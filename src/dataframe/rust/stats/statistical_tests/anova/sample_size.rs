@@ -1,16 +1,74 @@
-use super::super::super::distributions::qf;
+use super::super::super::core::calc::smallest_sample_size;
+use super::super::super::distributions::{pnf, qf};
 
-/// Calculates the required sample size for an ANOVA test.
+/// Calculates the power of a one-way ANOVA with `n` observations per group.
+///
+/// Power comes from the noncentral F distribution with `k - 1` and
+/// `k (n - 1)` degrees of freedom and noncentrality `k n f^2`, where `f` is
+/// Cohen's effect size.
+///
+/// # Arguments
+///
+/// * `n` - The number of observations per group.
+/// * `effect_size` - Cohen's f.
+/// * `alpha` - The significance level (e.g., 0.05).
+/// * `num_groups` - The number of groups (`k`).
+///
+/// # Returns
+///
+/// The probability of rejecting the null hypothesis.
+///
+pub fn f_power(n: f64, effect_size: f64, alpha: f64, num_groups: usize) -> f64 {
+    let k = num_groups as f64;
+    let (df1, df2) = (k - 1.0, k * (n - 1.0));
+    let crit = qf(alpha, df1, df2, false, false);
+    pnf(
+        crit,
+        df1,
+        df2,
+        k * n * effect_size * effect_size,
+        false,
+        false,
+    )
+}
 
+/// Calculates the required sample size for an ANOVA test.
+///
+/// Returns the smallest number of observations per group whose exact power
+/// ([`f_power`]) reaches `power`, as R's `pwr.anova.test`.
+///
+/// # Arguments
+///
+/// * `effect_size` - Cohen's f.
+/// * `alpha` - The significance level (e.g., 0.05).
+/// * `power` - The desired statistical power (e.g., 0.80).
+/// * `num_groups` - The number of groups.
+///
+/// # Returns
+///
+/// The required sample size per group (NaN for invalid input).
+///
 pub fn f_sample_size(effect_size: f64, alpha: f64, power: f64, num_groups: usize) -> f64 {
-    let df1 = (num_groups - 1) as f64; // Degrees of freedom for the numerator
-    let df2 = 1e6; // Approximation for large sample sizes (will be refined later)
+    if !(alpha > 0.0 && alpha < 1.0 && power > 0.0 && power < 1.0 && effect_size.is_finite())
+        || num_groups < 2
+    {
+        return f64::NAN;
+    }
+    smallest_sample_size(2.0, power, |n| f_power(n, effect_size, alpha, num_groups))
+}
 
-    // Calculate critical F-values based on alpha and power using our wrappers
-    let f_alpha = qf(1.0 - alpha, df1, df2, true, false);
-    let f_beta = qf(power, df1, df2, true, false);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Formula: n = ((f_alpha + f_beta) * (num_groups - 1) / effect_size^2)^2
-    let n = ((f_alpha + f_beta) * df1 / effect_size.powi(2)).powi(2);
-    n.ceil() // Rounds up to the next whole sample size
+    #[test]
+    fn test_f_sample_size_exact() {
+        // Cohen's medium effect: pwr.anova.test(k = 4, f = 0.25,
+        // power = 0.8)$n = 44.6
+        assert_eq!(f_sample_size(0.25, 0.05, 0.8, 4), 45.0);
+        assert!((f_power(44.0, 0.25, 0.05, 4) - 0.79391510).abs() < 1e-6);
+        assert!((f_power(45.0, 0.25, 0.05, 4) - 0.80398691).abs() < 1e-6);
+        assert!(f_sample_size(0.25, 0.05, 0.8, 1).is_nan());
+        assert_eq!(f_sample_size(0.0, 0.05, 0.8, 4), f64::INFINITY);
+    }
 }
@@ -15,6 +15,8 @@
 //! ## Sample Size Calculation
 //!
 //! To calculate the required sample size for Chi-Square tests, you can use the following functions:
+//! - `chi2_sample_size`: Calculates the required sample size for a given effect size (Cohen's w) and power, using the noncentral chi-squared distribution.
+//! - `chi2_power`: Calculates the exact power of a Chi-Square test.
 //! - `chi2_sample_size_gof`: Calculates the required sample size for the Chi-Square Goodness of Fit Test.
 //! - `chi2_sample_size_ind`: Calculates the required sample size for the Chi-Square Test for Independence.
 //! - `chi2_sample_size_variance`: Calculates the required sample size for the Chi-Square Test for Variance.
@@ -34,6 +36,8 @@
 //! - `chi2_sample_size_gof`: Calculates the required sample size for the Chi-Square Goodness of Fit Test.
 //! - `chi2_sample_size_ind`: Calculates the required sample size for the Chi-Square Test for Independence.
 //! - `chi2_sample_size_variance`: Calculates the required sample size for the Chi-Square Test for Variance.
+//! - `chi2_sample_size`: Calculates the required sample size for a given effect size and power.
+//! - `chi2_power`: Calculates the power of a Chi-Square test.
 //!
 //! ## Example
 //!
@@ -50,5 +54,8 @@ pub mod variance;
 pub mod wasm;
 
 pub use categorical::{goodness_of_fit, independence};
-pub use sample_size::{chi2_sample_size_gof, chi2_sample_size_ind, chi2_sample_size_variance};
+pub use sample_size::{
+    chi2_power, chi2_sample_size, chi2_sample_size_gof, chi2_sample_size_ind,
+    chi2_sample_size_variance,
+};
 pub use variance::variance;
@@ -1,4 +1,56 @@
-use super::super::super::distributions::qchisq;
+use super::super::super::core::calc::smallest_sample_size;
+use super::super::super::distributions::{pnchisq, qchisq};
+
+/// Calculates the power of a chi-square test (goodness of fit or
+/// independence) with `n` observations.
+///
+/// Power comes from the noncentral chi-squared distribution with `df` degrees
+/// of freedom and noncentrality `n w^2`, where `w` is Cohen's effect size.
+///
+/// # Arguments
+///
+/// * `n` - The total sample size.
+/// * `effect_size` - Cohen's w.
+/// * `alpha` - The significance level (e.g., 0.05).
+/// * `df` - The degrees of freedom of the test.
+///
+/// # Returns
+///
+/// The probability of rejecting the null hypothesis.
+///
+pub fn chi2_power(n: f64, effect_size: f64, alpha: f64, df: f64) -> f64 {
+    let crit = qchisq(alpha, df, false, false);
+    pnchisq(crit, df, n * effect_size * effect_size, false, false)
+}
+
+/// Calculates the required sample size for a chi-square test.
+///
+/// Returns the smallest total sample size whose exact power ([`chi2_power`])
+/// reaches `power`, as R's `pwr.chisq.test`.
+///
+/// # Arguments
+///
+/// * `effect_size` - Cohen's w.
+/// * `alpha` - The significance level (e.g., 0.05).
+/// * `power` - The desired statistical power (e.g., 0.80).
+/// * `df` - The degrees of freedom of the test.
+///
+/// # Returns
+///
+/// The required total sample size (NaN for invalid input).
+///
+pub fn chi2_sample_size(effect_size: f64, alpha: f64, power: f64, df: f64) -> f64 {
+    if !(alpha > 0.0
+        && alpha < 1.0
+        && power > 0.0
+        && power < 1.0
+        && df > 0.0
+        && effect_size.is_finite())
+    {
+        return f64::NAN;
+    }
+    smallest_sample_size(1.0, power, |n| chi2_power(n, effect_size, alpha, df))
+}
 
 /// Calculates the required sample size for a chi-square goodness-of-fit test.
 
@@ -81,3 +133,20 @@ pub fn chi2_sample_size_variance(effect_size: f64, alpha: f64, power: f64, varia
     let n = ((chi_alpha + chi_beta) * variance / effect_size).powi(2);
     n.ceil() // Rounds up to the next whole sample size
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chi2_sample_size_exact() {
+        // pwr.chisq.test(w = 0.3, df = 1, power = 0.8)$N = 87.2
+        assert_eq!(chi2_sample_size(0.3, 0.05, 0.8, 1.0), 88.0);
+        assert!((chi2_power(88.0, 0.3, 0.05, 1.0) - 0.80352748).abs() < 1e-6);
+        // pwr.chisq.test(w = 0.3, df = 2, power = 0.8)$N = 107.1
+        assert_eq!(chi2_sample_size(0.3, 0.05, 0.8, 2.0), 108.0);
+        assert!((chi2_power(108.0, 0.3, 0.05, 2.0) - 0.80369390).abs() < 1e-6);
+        assert!(chi2_sample_size(0.3, 0.05, 0.8, 0.0).is_nan());
+        assert_eq!(chi2_sample_size(0.0, 0.05, 0.8, 1.0), f64::INFINITY);
+    }
+}
@@ -4,7 +4,7 @@
 
 use super::{
     categorical::{goodness_of_fit, independence},
-    sample_size::chi2_sample_size_variance,
+    sample_size::chi2_sample_size,
     variance::variance,
 };
 use crate::stats::core::types::{
@@ -161,8 +161,9 @@ pub fn chi_square_variance(
     }
 }
 
-/// WASM export for chi-square sample size calculation
+/// WASM export for chi-square sample size calculation (Cohen's w effect size,
+/// exact power from the noncentral chi-squared distribution)
 #[wasm_bindgen]
-pub fn chi_square_sample_size_wasm(effect_size: f64, alpha: f64, power: f64, _df: usize) -> f64 {
-    chi2_sample_size_variance(effect_size, alpha, power, 1.0) // Using variance=1.0 as default
+pub fn chi_square_sample_size_wasm(effect_size: f64, alpha: f64, power: f64, df: usize) -> f64 {
+    chi2_sample_size(effect_size, alpha, power, df as f64)
 }
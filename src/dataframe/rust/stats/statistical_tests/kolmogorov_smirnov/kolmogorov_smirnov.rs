@@ -5,6 +5,7 @@
 //! come from the same distribution.
 
 use crate::stats::core::types::{KolmogorovSmirnovTestResult, TestStatistic, TestStatisticName};
+use crate::stats::distributions::Distribution;
 
 /// Apply R's q rounding correction: q <- (0.5 + floor(q*m*n - 1e-7))/(m*n)
 fn psmirnov_q_adjust(q: f64, m: i32, n: i32) -> f64 {
@@ -664,6 +665,25 @@ where
     })
 }
 
+/// Performs one-sample Kolmogorov-Smirnov test against a distribution
+///
+/// # Arguments
+/// * `x` - Sample data
+/// * `dist` - Hypothesised distribution, e.g. `families::Normal::new(0.0, 1.0)?`
+/// * `alternative` - Type of test: "two-sided", "less", or "greater"
+/// * `alpha` - Significance level
+///
+/// # Returns
+/// * `KolmogorovSmirnovTestResult` as for [`kolmogorov_smirnov_one_sample`]
+pub fn kolmogorov_smirnov_distribution<D: Distribution + ?Sized>(
+    x: &[f64],
+    dist: &D,
+    alternative: &str,
+    alpha: f64,
+) -> Result<KolmogorovSmirnovTestResult, String> {
+    kolmogorov_smirnov_one_sample(x, |value| dist.cdf(value), alternative, alpha)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -711,6 +731,24 @@ mod tests {
         // Should not reject null hypothesis for uniform sample
         assert!(result.p_value > 0.05);
     }
+
+    #[test]
+    fn test_ks_distribution() {
+        use crate::stats::distributions::families::{Exponential, Uniform};
+
+        let x = vec![0.1, 0.3, 0.5, 0.7, 0.9];
+        let uniform_cdf = |v: f64| v.clamp(0.0, 1.0);
+        let expected = kolmogorov_smirnov_one_sample(&x, uniform_cdf, "two-sided", 0.05).unwrap();
+        let uniform = Uniform::new(0.0, 1.0).unwrap();
+        let result = kolmogorov_smirnov_distribution(&x, &uniform, "two-sided", 0.05).unwrap();
+        assert_eq!(result.d_statistic, expected.d_statistic);
+        assert_eq!(result.p_value, expected.p_value);
+
+        // Trait objects work too; uniform data is far from Exp(10)
+        let exponential: &dyn Distribution = &Exponential::new(10.0).unwrap();
+        let result = kolmogorov_smirnov_distribution(&x, exponential, "two-sided", 0.05).unwrap();
+        assert!(result.p_value < 0.05);
+    }
 }
 
 /// R's K2l function for asymptotic one-sample KS distribution
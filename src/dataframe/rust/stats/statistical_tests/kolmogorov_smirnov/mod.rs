@@ -11,5 +11,6 @@ pub mod wasm;
 
 pub use kolmogorov_smirnov::{
     kolmogorov_smirnov_test, 
-    kolmogorov_smirnov_one_sample
+    kolmogorov_smirnov_one_sample,
+    kolmogorov_smirnov_distribution
 };
\ No newline at end of file
@@ -5,117 +5,14 @@
 
 use super::types::{PairwiseComparison, TukeyHsdTestResult};
 use crate::stats::core::types::{ConfidenceInterval, TestStatistic};
+use crate::stats::distributions::ptukey;
 use statrs::distribution::{ContinuousCDF, StudentsT};
-use std::f64::consts::PI;
 
-/// Standard normal PDF
-fn phi(z: f64) -> f64 {
-    (1.0 / (2.0 * PI).sqrt()) * (-0.5 * z * z).exp()
-}
-
-/// Standard normal CDF (using approximation)
-fn big_phi(z: f64) -> f64 {
-    if z < -8.0 {
-        return 0.0;
-    }
-    if z > 8.0 {
-        return 1.0;
-    }
-
-    // Use statrs for accurate standard normal CDF
-    use statrs::distribution::{ContinuousCDF, Normal};
-    let normal = Normal::new(0.0, 1.0).unwrap();
-    normal.cdf(z)
-}
-
-/// Gamma function using statrs
-fn gamma(x: f64) -> f64 {
-    use statrs::function::gamma::gamma as statrs_gamma;
-    statrs_gamma(x)
-}
-
-/// Studentized range CDF implementation based on the mathematical definition
-/// F_R(q;k,ν) = (sqrt(2π) k ν^(ν/2)) / (Γ(ν/2) 2^(ν/2-1)) ∫[0,∞] s^(ν-1) φ(√ν s) [∫[-∞,∞] φ(z) [Φ(z+qs) - Φ(z)]^(k-1) dz] ds
+/// Studentized range CDF P(Q <= q) for `k` means and `nu` degrees of freedom
+///
+/// Thin wrapper over [`ptukey`], kept for existing callers.
 pub fn ptukey_exact(q: f64, k: f64, nu: f64) -> f64 {
-    if q <= 0.0 {
-        return 0.0;
-    }
-    if q >= 20.0 {
-        return 1.0; // For very large q, CDF approaches 1
-    }
-
-    // Pre-calculate constants
-    let sqrt_2pi = (2.0 * PI).sqrt();
-    let sqrt_nu = nu.sqrt();
-    let gamma_nu_2 = gamma(nu / 2.0);
-    let power_2 = 2.0_f64.powf(nu / 2.0 - 1.0);
-    let nu_power = nu.powf(nu / 2.0);
-
-    let constant = (sqrt_2pi * k * nu_power) / (gamma_nu_2 * power_2);
-
-    // Numerical integration over s using Simpson's rule
-    let n_steps = 100;
-    let s_max = 10.0; // Integration limit for s
-    let ds = s_max / n_steps as f64;
-
-    let mut integral_s = 0.0;
-
-    for i in 0..=n_steps {
-        let s = i as f64 * ds;
-        if s == 0.0 {
-            continue; // Skip s=0 to avoid division issues
-        }
-
-        // Calculate s^(ν-1) * φ(√ν s)
-        let s_term = s.powf(nu - 1.0) * phi(sqrt_nu * s);
-
-        // Inner integral over z using numerical integration
-        let n_z_steps = 50;
-        let z_min = -8.0;
-        let z_max = 8.0;
-        let dz = (z_max - z_min) / n_z_steps as f64;
-
-        let mut integral_z = 0.0;
-
-        for j in 0..=n_z_steps {
-            let z = z_min + j as f64 * dz;
-            let phi_z = phi(z);
-            let cdf_diff = big_phi(z + q * s) - big_phi(z);
-
-            if cdf_diff > 0.0 {
-                let term = phi_z * cdf_diff.powf(k - 1.0);
-
-                // Simpson's rule weights
-                let weight = if j == 0 || j == n_z_steps {
-                    1.0
-                } else if j % 2 == 1 {
-                    4.0
-                } else {
-                    2.0
-                };
-                integral_z += weight * term;
-            }
-        }
-
-        integral_z *= dz / 3.0; // Simpson's rule
-
-        // Simpson's rule weights for outer integral
-        let weight = if i == 0 || i == n_steps {
-            1.0
-        } else if i % 2 == 1 {
-            4.0
-        } else {
-            2.0
-        };
-        integral_s += weight * s_term * integral_z;
-    }
-
-    integral_s *= ds / 3.0; // Simpson's rule
-
-    let result = constant * integral_s;
-
-    // Clamp result to [0, 1]
-    result.max(0.0).min(1.0)
+    ptukey(q, k, nu, 1.0, true, false)
 }
 
 /// Performs Tukey's HSD test for multiple comparisons
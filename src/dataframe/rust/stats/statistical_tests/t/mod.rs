@@ -9,6 +9,7 @@
 //!
 //! To calculate the required sample size for t-tests, you can use the following function:
 //! - `t_sample_size`: Calculates the necessary sample size for one-sample t-tests based on effect size, alpha, power, and standard deviation.
+//! - `t_power`: Calculates the exact power of a one-sample t-test from the noncentral t distribution.
 //!
 //! ## Submodules
 //!
@@ -23,6 +24,7 @@
 //! - `t_test_ind`: Performs an independent two-sample t-test.
 //! - `t_test_paired`: Performs a paired two-sample t-test.
 //! - `t_sample_size`: Calculates the required sample size for one-sample t-tests.
+//! - `t_power`: Calculates the power of a one-sample t-test.
 //!
 //! ## Example
 //! ```rust
@@ -38,5 +40,5 @@ pub mod two_sample;
 pub mod wasm;
 
 pub use one_sample::t_test;
-pub use sample_size::t_sample_size;
+pub use sample_size::{t_power, t_sample_size};
 pub use two_sample::{t_test_ind, t_test_paired};
@@ -1,8 +1,54 @@
 use super::super::super::core::TailType;
-use super::super::super::distributions::students_t;
+use super::super::super::core::calc::smallest_sample_size;
+use super::super::super::distributions::{pnt, qnt};
 
-/// Calculates the required sample size for a one-sample t-test.
+/// Calculates the power of a one-sample t-test.
+///
+/// Power comes from the noncentral t distribution with `n - 1` degrees of
+/// freedom and noncentrality `sqrt(n) * |effect_size| / std_dev`. Two-sided
+/// tests count both rejection regions (R's `power.t.test(strict = TRUE)`);
+/// one-sided tests are taken in the direction of the effect.
+///
+/// # Arguments
+///
+/// * `n` - The sample size.
+/// * `effect_size` - The difference in means to detect.
+/// * `alpha` - The significance level (e.g., 0.05).
+/// * `std_dev` - The population standard deviation (or a reasonable estimate).
+/// * `tail` - The type of tail (left, right, or two).
+///
+/// # Returns
+///
+/// The probability of rejecting the null hypothesis.
+///
+pub fn t_power(n: f64, effect_size: f64, alpha: f64, std_dev: f64, tail: TailType) -> f64 {
+    let df = n - 1.0;
+    let ncp = n.sqrt() * effect_size.abs() / std_dev;
+    match tail {
+        TailType::Two => {
+            let crit = qnt(alpha / 2.0, df, 0.0, false, false);
+            pnt(crit, df, ncp, false, false) + pnt(-crit, df, ncp, true, false)
+        }
+        _ => pnt(qnt(alpha, df, 0.0, false, false), df, ncp, false, false),
+    }
+}
 
+/// Calculates the required sample size for a one-sample t-test.
+///
+/// Returns the smallest `n` whose exact power ([`t_power`]) reaches `power`.
+///
+/// # Arguments
+///
+/// * `effect_size` - The difference in means to detect.
+/// * `alpha` - The significance level (e.g., 0.05).
+/// * `power` - The desired statistical power (e.g., 0.80).
+/// * `std_dev` - The population standard deviation (or a reasonable estimate).
+/// * `tail` - The type of tail (left, right, or two).
+///
+/// # Returns
+///
+/// The required sample size (infinite for a zero effect, NaN for invalid input).
+///
 pub fn t_sample_size(
     effect_size: f64,
     alpha: f64,
@@ -10,5 +56,38 @@ pub fn t_sample_size(
     std_dev: f64,
     tail: TailType,
 ) -> f64 {
-    students_t::t_sample_size(effect_size, alpha, power, std_dev, tail)
+    if !(alpha > 0.0
+        && alpha < 1.0
+        && power > 0.0
+        && power < 1.0
+        && std_dev > 0.0
+        && effect_size.is_finite())
+    {
+        return f64::NAN;
+    }
+    smallest_sample_size(2.0, power, |n| {
+        t_power(n, effect_size, alpha, std_dev, tail.clone())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_t_sample_size_exact() {
+        // R: power.t.test(delta = 0.5, sd = 1, power = 0.8,
+        //                 type = "one.sample")$n = 33.37
+        assert_eq!(t_sample_size(0.5, 0.05, 0.8, 1.0, TailType::Two), 34.0);
+        let at_33 = t_power(33.0, 0.5, 0.05, 1.0, TailType::Two);
+        let at_34 = t_power(34.0, 0.5, 0.05, 1.0, TailType::Two);
+        assert!((at_33 - 0.79536584).abs() < 1e-6, "{at_33}");
+        assert!((at_34 - 0.80777750).abs() < 1e-6, "{at_34}");
+        assert!(t_sample_size(0.5, 0.05, 0.8, 1.0, TailType::Right) < 34.0);
+        assert_eq!(
+            t_sample_size(0.0, 0.05, 0.8, 1.0, TailType::Two),
+            f64::INFINITY
+        );
+        assert!(t_sample_size(0.5, 1.5, 0.8, 1.0, TailType::Two).is_nan());
+    }
 }